@@ -0,0 +1,222 @@
+//! Pong against the computer, first to five points wins
+//!
+//! Run with `cargo run --example pong`. W/S or the arrow keys move your paddle,
+//! Space serves, Enter starts a new match, Esc quits.
+//!
+//! Shows paddles and the ball as game objects moved through commands,
+//! collisions through [`check_collision`], and a [`StateMachine`] driving the
+//! serve / rally / game over flow.
+
+use std::time::Duration;
+use lonely_engine::{
+    audio::tone,
+    color::Color,
+    engine::{Engine, EngineCommand, GroupSelector, Updatable, UpdateContext},
+    font::Font,
+    game_object::GameObject,
+    helpers::check_collision,
+    input::Key,
+    keybindings::ActionMap,
+    renderer::{Cell, Renderer},
+    state_machine::StateMachine,
+    style::Style,
+    tilemap::Tilemap,
+};
+
+const WIDTH: usize = 60;
+const HEIGHT: usize = 20;
+const PADDLE_SIZE: usize = 4;
+const WINNING_SCORE: u32 = 5;
+/// Seconds between ball steps
+const BALL_INTERVAL: f32 = 0.05;
+/// Seconds between computer paddle steps, slower than the ball so it can be beaten
+const AI_INTERVAL: f32 = 0.09;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Phase {
+    Serve,
+    Rally,
+    GameOver,
+}
+
+struct Pong {
+    actions: ActionMap,
+    flow: StateMachine<Phase>,
+    velocity: (i32, i32),
+    ball_timer: f32,
+    ai_timer: f32,
+    scores: (u32, u32),
+}
+
+impl Pong {
+    fn new(actions: ActionMap) -> Self {
+        let mut flow = StateMachine::new(Phase::Serve);
+        let serve_actions = actions.clone();
+        flow.add_transition(Phase::Serve, Phase::Rally, move |_, keys| serve_actions.is_active("serve", keys));
+        flow.on_enter(Phase::GameOver, || vec![EngineCommand::PlaySound("win".to_string())]);
+
+        Self { actions, flow, velocity: (1, 1), ball_timer: 0.0, ai_timer: 0.0, scores: (0, 0) }
+    }
+
+    /// Top row of a paddle
+    fn paddle_top(ctx: &UpdateContext, tag: &str) -> usize {
+        ctx.with_tag(tag).map(|(_, part)| part.y).min().unwrap_or(0)
+    }
+
+    /// Moves a paddle one row, keeping it between the walls
+    fn move_paddle(ctx: &UpdateContext, tag: &str, dy: i32) -> Option<EngineCommand> {
+        let top = Self::paddle_top(ctx, tag) as i32 + dy;
+        let bottom = top + PADDLE_SIZE as i32;
+        (top >= 1 && bottom < HEIGHT as i32).then(|| EngineCommand::MoveGroup(GroupSelector::tag(tag), 0, dy))
+    }
+
+    /// Advances the ball one cell, bouncing off walls and paddles
+    fn step_ball(&mut self, ctx: &mut UpdateContext, commands: &mut Vec<EngineCommand>) {
+        let Some((ball_index, ball)) = ctx.find_tag("ball") else {
+            return;
+        };
+
+        let mut next_y = ball.y as i32 + self.velocity.1;
+        if next_y <= 0 || next_y >= HEIGHT as i32 - 1 {
+            self.velocity.1 = -self.velocity.1;
+            next_y = ball.y as i32 + self.velocity.1;
+            commands.push(EngineCommand::PlaySound("wall".to_string()));
+        }
+
+        let next_x = ball.x as i32 + self.velocity.0;
+        let probe = GameObject::new(next_x as usize, next_y as usize, ball.character);
+        let paddle_hit = ctx.objects.iter().find(|obj| obj.in_group("paddle") && check_collision(&probe, obj, &[]));
+        if let Some(paddle) = paddle_hit {
+            // Edges of the paddle send the ball off at a steeper angle
+            let top = Self::paddle_top(ctx, &paddle.tag);
+            let offset = paddle.y - top;
+            self.velocity.0 = -self.velocity.0;
+            self.velocity.1 = if offset == 0 { -1 } else if offset == PADDLE_SIZE - 1 { 1 } else { self.velocity.1 };
+            commands.push(EngineCommand::PlaySound("paddle".to_string()));
+            return;
+        }
+
+        if next_x <= 0 || next_x >= WIDTH as i32 - 1 {
+            if next_x <= 0 { self.scores.1 += 1 } else { self.scores.0 += 1 }
+            commands.push(EngineCommand::PlaySound("score".to_string()));
+            // Back to the center, serving towards whoever lost the point
+            commands.push(EngineCommand::MoveObject(ball_index, WIDTH as i32 / 2 - ball.x as i32, HEIGHT as i32 / 2 - ball.y as i32));
+            self.velocity = (if next_x <= 0 { -1 } else { 1 }, if ctx.rng.chance(0.5) { 1 } else { -1 });
+
+            let finished = self.scores.0 >= WINNING_SCORE || self.scores.1 >= WINNING_SCORE;
+            self.flow.transition_to(if finished { Phase::GameOver } else { Phase::Serve });
+            return;
+        }
+
+        commands.push(EngineCommand::MoveObject(ball_index, self.velocity.0, next_y - ball.y as i32));
+    }
+}
+
+impl Updatable for Pong {
+    fn update_with_context(&mut self, ctx: &mut UpdateContext) -> Vec<EngineCommand> {
+        if self.actions.is_active("quit", ctx.active_keys) {
+            return vec![EngineCommand::Quit];
+        }
+
+        let mut commands = self.flow.step(ctx.delta_time, ctx.active_keys);
+        match self.flow.current() {
+            Phase::GameOver => {
+                if self.actions.is_active("restart", ctx.active_keys) {
+                    self.scores = (0, 0);
+                    self.flow.transition_to(Phase::Serve);
+                }
+                return commands;
+            },
+            Phase::Serve => return commands,
+            Phase::Rally => {},
+        }
+
+        let dy = self.actions.is_active("down", ctx.active_keys) as i32 - self.actions.is_active("up", ctx.active_keys) as i32;
+        if dy != 0 {
+            commands.extend(Self::move_paddle(ctx, "player", dy));
+        }
+
+        self.ai_timer += ctx.delta_time;
+        if self.ai_timer >= AI_INTERVAL {
+            self.ai_timer = 0.0;
+            let ball_y = ctx.find_tag("ball").map(|(_, ball)| ball.y).unwrap_or(HEIGHT / 2);
+            let center = Self::paddle_top(ctx, "computer") + PADDLE_SIZE / 2;
+            let dy = (ball_y as i32 - center as i32).signum();
+            if dy != 0 {
+                commands.extend(Self::move_paddle(ctx, "computer", dy));
+            }
+        }
+
+        self.ball_timer += ctx.delta_time;
+        if self.ball_timer >= BALL_INTERVAL {
+            self.ball_timer = 0.0;
+            self.step_ball(ctx, &mut commands);
+        }
+
+        commands
+    }
+
+    fn draw(&self, renderer: &mut Renderer) {
+        let font = Font::small();
+        let score_style = Style::new().fg(Color::BRIGHT_WHITE);
+        renderer.draw_big_text(WIDTH / 2 - 6, 2, &self.scores.0.to_string(), &font, &score_style);
+        renderer.draw_big_text(WIDTH / 2 + 4, 2, &self.scores.1.to_string(), &font, &score_style);
+
+        let message = match self.flow.current() {
+            Phase::Serve => "Space to serve",
+            Phase::GameOver if self.scores.0 > self.scores.1 => "You win! Enter for a rematch",
+            Phase::GameOver => "Computer wins. Enter for a rematch",
+            Phase::Rally => return,
+        };
+        renderer.draw_text((WIDTH - message.len()) / 2, HEIGHT - 3, message);
+    }
+}
+
+fn paddle(x: usize, tag: &str, color: Color) -> Vec<GameObject> {
+    (0..PADDLE_SIZE)
+        .map(|i| {
+            let mut part = GameObject::new(x, (HEIGHT - PADDLE_SIZE) / 2 + i, '█');
+            part.tag = tag.to_string();
+            part.fg_color = color;
+            part.groups.push("paddle".to_string());
+            part
+        })
+        .collect()
+}
+
+/// Top and bottom walls with a dashed net down the middle
+fn court() -> Tilemap {
+    let mut tilemap = Tilemap::new(WIDTH, HEIGHT);
+    let wall = Cell::styled('─', &Style::new().fg(Color::GREY));
+    tilemap.fill_rect(0, 0, WIDTH, 1, wall);
+    tilemap.fill_rect(0, HEIGHT - 1, WIDTH, 1, wall);
+    for y in (1..HEIGHT - 1).step_by(2) {
+        tilemap.set(WIDTH / 2, y, Cell::styled('┆', &Style::new().fg(Color::GREY)));
+    }
+    tilemap
+}
+
+fn main() {
+    let mut actions = ActionMap::new();
+    actions.set_keys("up", vec![Key::Up, Key::Char('w')]);
+    actions.set_keys("down", vec![Key::Down, Key::Char('s')]);
+    actions.set_keys("serve", vec![Key::Space]);
+    actions.set_keys("restart", vec![Key::Enter]);
+    actions.set_keys("quit", vec![Key::Esc]);
+
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.renderer.set_background(court());
+    for (name, frequency, millis) in [("wall", 440.0, 30), ("paddle", 660.0, 40), ("score", 220.0, 250), ("win", 1320.0, 600)] {
+        let _ = engine.audio.preload_bytes(name, tone(frequency, Duration::from_millis(millis)));
+    }
+
+    for part in paddle(2, "player", Color::BRIGHT_CYAN).into_iter().chain(paddle(WIDTH - 3, "computer", Color::BRIGHT_MAGENTA)) {
+        engine.add_object(part);
+    }
+    let mut ball = GameObject::new(WIDTH / 2, HEIGHT / 2, '●');
+    ball.tag = "ball".to_string();
+    engine.add_object(ball);
+
+    engine.add_updatable(Pong::new(actions));
+    engine.run();
+}
@@ -0,0 +1,256 @@
+//! A slice of a roguelike: find the stairs while goblins close in
+//!
+//! Run with `cargo run --example roguelike`. Arrow keys or WASD move and attack,
+//! Enter starts, Esc quits.
+//!
+//! Shows turn-based mode, a tilemap dungeon with a palette, bump attacks through
+//! [`check_collision`], and a [`StateMachine`] switching between the title,
+//! dungeon, and ending scenes.
+
+use std::time::Duration;
+use lonely_engine::{
+    audio::tone,
+    color::Color,
+    effects::FloatingText,
+    engine::{Engine, EngineCommand, EngineMode, GroupSelector, Updatable, UpdateContext},
+    font::Font,
+    game_object::GameObject,
+    helpers::check_collision,
+    input::Key,
+    keybindings::ActionMap,
+    renderer::Renderer,
+    sprite::Palette,
+    state_machine::StateMachine,
+    style::Style,
+    tilemap::Tilemap,
+};
+
+const MAP: &str = "\
+##################################################
+#........#...................#...................#
+#........#...................#...................#
+#........+.......#####.......#.......#######.....#
+#........#.......#...#.......+.......#.....#.....#
+#####+####.......#...#.......#.......#..>..#.....#
+#........#.......##+##.......#.......#.....#.....#
+#........#...................#.......###+###.....#
+#........#...................#...................#
+#........##########+##########...................#
+#................................................#
+#................................................#
+##################################################";
+
+/// Mask coloring the map, `w` walls, `d` doors, `s` stairs
+const MAP_COLORS: &str = "\
+wwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwww
+w        w                   w                   w
+w        w                   w                   w
+w        d       wwwww       w       wwwwwww     w
+w        w       w   w       d       w     w     w
+wwwwwdwwww       w   w       w       w  s  w     w
+w        w       wwdww       w       w     w     w
+w        w                   w       wwwdwww     w
+w        w                   w                   w
+w        wwwwwwwwwwdwwwwwwwwww                   w
+w                                                w
+w                                                w
+wwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwwww";
+
+const HUD_ROW: usize = 13;
+const PLAYER_HP: u32 = 10;
+/// Goblins notice the player within this many cells
+const SIGHT: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Scene {
+    Title,
+    Dungeon,
+    Dead,
+    Escaped,
+}
+
+struct Roguelike {
+    actions: ActionMap,
+    scenes: StateMachine<Scene>,
+    map: Vec<Vec<char>>,
+    hp: u32,
+    kills: u32,
+    message: String,
+}
+
+impl Roguelike {
+    fn new(actions: ActionMap) -> Self {
+        let mut scenes = StateMachine::new(Scene::Title);
+        let start_actions = actions.clone();
+        scenes.add_transition(Scene::Title, Scene::Dungeon, move |_, keys| start_actions.is_active("start", keys));
+        // Actors only show while exploring, the other scenes draw over an empty dungeon
+        scenes.on_enter(Scene::Dungeon, || vec![EngineCommand::SetGroupVisible(GroupSelector::group("actors"), true)]);
+        scenes.on_exit(Scene::Dungeon, || vec![EngineCommand::SetGroupVisible(GroupSelector::group("actors"), false)]);
+        scenes.on_enter(Scene::Escaped, || vec![EngineCommand::PlaySound("stairs".to_string())]);
+
+        Self {
+            actions,
+            scenes,
+            map: MAP.lines().map(|row| row.chars().collect()).collect(),
+            hp: PLAYER_HP,
+            kills: 0,
+            message: "You enter the dungeon.".to_string(),
+        }
+    }
+
+    fn is_floor(&self, x: usize, y: usize) -> bool {
+        self.map.get(y).and_then(|row| row.get(x)).is_some_and(|tile| *tile != '#')
+    }
+
+    /// Moves or attacks with the player, then lets every goblin act
+    fn take_turn(&mut self, ctx: &mut UpdateContext, dx: i32, dy: i32) -> Vec<EngineCommand> {
+        let mut commands = Vec::new();
+        let Some((player_index, player)) = ctx.find_tag("player") else {
+            return commands;
+        };
+
+        let (x, y) = ((player.x as i32 + dx) as usize, (player.y as i32 + dy) as usize);
+        let target = GameObject::new(x, y, '@');
+        let mut player_at = (player.x, player.y);
+        let mut slain = None;
+
+        if let Some((goblin_index, _)) = ctx.with_tag("goblin").find(|(_, goblin)| check_collision(&target, goblin, &[])) {
+            slain = Some(goblin_index);
+            self.kills += 1;
+            self.message = "You slay the goblin.".to_string();
+            commands.push(EngineCommand::PlaySound("hit".to_string()));
+            let text = FloatingText::new(x, y, "x_x", Style::new().fg(Color::RED)).color_ramp(vec![Color::BRIGHT_RED, Color::RED, Color::GREY]);
+            commands.push(EngineCommand::SpawnFloatingText(text));
+        } else if self.is_floor(x, y) {
+            commands.push(EngineCommand::MoveObject(player_index, dx, dy));
+            player_at = (x, y);
+            if self.map[y][x] == '>' {
+                self.scenes.transition_to(Scene::Escaped);
+                return commands;
+            }
+        }
+
+        // Goblins chase the player, attacking when adjacent
+        let mut occupied: Vec<(usize, usize)> = ctx.with_tag("goblin").map(|(_, goblin)| (goblin.x, goblin.y)).collect();
+        for (index, goblin) in ctx.with_tag("goblin") {
+            if Some(index) == slain {
+                continue;
+            }
+            let (gx, gy) = (goblin.x as i32, goblin.y as i32);
+            let (px, py) = (player_at.0 as i32, player_at.1 as i32);
+            if (px - gx).abs().max((py - gy).abs()) == 1 {
+                self.hp = self.hp.saturating_sub(1);
+                self.message = "A goblin stabs you!".to_string();
+                commands.push(EngineCommand::PlaySound("hurt".to_string()));
+                continue;
+            }
+            if (px - gx).unsigned_abs() as usize + (py - gy).unsigned_abs() as usize > SIGHT {
+                continue;
+            }
+
+            let (step_x, step_y) = ((px - gx).signum(), (py - gy).signum());
+            let next = ((gx + step_x) as usize, (gy + step_y) as usize);
+            if next != player_at && self.is_floor(next.0, next.1) && !occupied.contains(&next) {
+                occupied.retain(|cell| *cell != (goblin.x, goblin.y));
+                occupied.push(next);
+                commands.push(EngineCommand::MoveObject(index, step_x, step_y));
+            }
+        }
+
+        // Despawn last so the goblin indices above stay valid
+        if let Some(index) = slain {
+            commands.push(EngineCommand::DespawnObject(index));
+        }
+        if self.hp == 0 {
+            self.scenes.transition_to(Scene::Dead);
+        }
+        commands
+    }
+}
+
+impl Updatable for Roguelike {
+    fn update_with_context(&mut self, ctx: &mut UpdateContext) -> Vec<EngineCommand> {
+        if self.actions.is_active("quit", ctx.active_keys) {
+            return vec![EngineCommand::Quit];
+        }
+
+        let mut commands = Vec::new();
+        if self.scenes.is_in(&Scene::Dungeon) {
+            let direction = [("up", (0, -1)), ("down", (0, 1)), ("left", (-1, 0)), ("right", (1, 0))]
+                .into_iter()
+                .find(|(action, _)| self.actions.is_active(action, ctx.active_keys));
+            if let Some((_, (dx, dy))) = direction {
+                commands = self.take_turn(ctx, dx, dy);
+            }
+        }
+
+        // Stepped last so scene changes made this turn take effect right away
+        commands.extend(self.scenes.step(ctx.delta_time, ctx.active_keys));
+        commands
+    }
+
+    fn draw(&self, renderer: &mut Renderer) {
+        let width = renderer.get_width();
+        let banner = |renderer: &mut Renderer, title: &str, subtitle: &str, color: Color| {
+            let font = Font::small();
+            let (title_width, _) = font.measure(title);
+            renderer.draw_big_text(width.saturating_sub(title_width) / 2, 3, title, &font, &Style::new().fg(color));
+            renderer.draw_text(width.saturating_sub(subtitle.len()) / 2, 10, subtitle);
+        };
+
+        match self.scenes.current() {
+            Scene::Title => banner(renderer, "DUNGEON", "Press Enter to descend", Color::BRIGHT_YELLOW),
+            Scene::Dead => banner(renderer, "YOU DIED", &format!("Goblins slain: {}", self.kills), Color::RED),
+            Scene::Escaped => banner(renderer, "ESCAPED", &format!("Goblins slain: {}", self.kills), Color::BRIGHT_GREEN),
+            Scene::Dungeon => {
+                let hp_style = Style::new().fg(if self.hp > PLAYER_HP / 3 { Color::GREEN } else { Color::RED });
+                renderer.draw_styled_text(0, HUD_ROW, &format!("HP {:>2}/{}", self.hp, PLAYER_HP), &hp_style);
+                renderer.draw_text(12, HUD_ROW, &self.message);
+            },
+        }
+    }
+}
+
+fn goblin(x: usize, y: usize) -> GameObject {
+    let mut goblin = GameObject::new(x, y, 'g');
+    goblin.tag = "goblin".to_string();
+    goblin.fg_color = Color::BRIGHT_GREEN;
+    goblin.visible = false;
+    goblin.groups.push("actors".to_string());
+    goblin
+}
+
+fn main() {
+    let mut actions = ActionMap::new();
+    actions.set_keys("up", vec![Key::Up, Key::Char('w')]);
+    actions.set_keys("down", vec![Key::Down, Key::Char('s')]);
+    actions.set_keys("left", vec![Key::Left, Key::Char('a')]);
+    actions.set_keys("right", vec![Key::Right, Key::Char('d')]);
+    actions.set_keys("start", vec![Key::Enter]);
+    actions.set_keys("quit", vec![Key::Esc]);
+
+    let mut palette = Palette::new();
+    palette.insert('w', Style::new().fg(Color::GREY));
+    palette.insert('d', Style::new().fg(Color::YELLOW));
+    palette.insert('s', Style::new().fg(Color::BRIGHT_WHITE).bold());
+
+    let mut engine = Engine::builder(50, HUD_ROW + 1).mode(EngineMode::TurnBased).build();
+    for (name, frequency, millis) in [("hit", 330.0, 80), ("hurt", 140.0, 120), ("stairs", 990.0, 500)] {
+        let _ = engine.audio.preload_bytes(name, tone(frequency, Duration::from_millis(millis)));
+    }
+
+    let game = Roguelike::new(actions);
+    let mut player = GameObject::new(3, 2, '@');
+    player.tag = "player".to_string();
+    player.fg_color = Color::BRIGHT_CYAN;
+    player.visible = false;
+    player.groups.push("actors".to_string());
+
+    engine.renderer.set_background(Tilemap::from_str_with_palette(MAP, MAP_COLORS, &palette));
+    engine.add_object(player);
+    for (x, y) in [(14, 2), (24, 7), (33, 2), (44, 10), (40, 4)] {
+        engine.add_object(goblin(x, y));
+    }
+    engine.add_updatable(game);
+    engine.run();
+}
@@ -0,0 +1,176 @@
+//! Snake: eat food to grow, avoid the walls and your own tail
+//!
+//! Run with `cargo run --example snake`. Arrow keys or WASD steer, Enter restarts, Esc quits.
+//!
+//! Shows an [`Updatable`] that keeps its own game state and draws it directly
+//! through [`Updatable::draw`], with a tilemap background for the walls.
+
+use std::{collections::VecDeque, time::Duration};
+use lonely_engine::{
+    audio::tone,
+    color::Color,
+    engine::{Engine, EngineCommand, Updatable, UpdateContext},
+    font::Font,
+    input::Key,
+    keybindings::ActionMap,
+    renderer::{Cell, Renderer},
+    rng::Rng,
+    style::Style,
+    tilemap::Tilemap,
+};
+
+const WIDTH: usize = 40;
+const HEIGHT: usize = 20;
+/// Seconds between steps at the start, shrinking as the snake grows
+const START_INTERVAL: f32 = 0.15;
+const MIN_INTERVAL: f32 = 0.05;
+
+struct Snake {
+    actions: ActionMap,
+    /// Head first
+    body: VecDeque<(usize, usize)>,
+    direction: (i32, i32),
+    step_timer: f32,
+    food: (usize, usize),
+    score: u32,
+    alive: bool,
+}
+
+impl Snake {
+    fn new(actions: ActionMap, rng: &mut Rng) -> Self {
+        let mut snake = Self {
+            actions,
+            body: VecDeque::new(),
+            direction: (1, 0),
+            step_timer: 0.0,
+            food: (0, 0),
+            score: 0,
+            alive: true,
+        };
+        snake.reset(rng);
+        snake
+    }
+
+    fn reset(&mut self, rng: &mut Rng) {
+        self.body = (0..3).map(|i| (WIDTH / 2 - i, HEIGHT / 2)).collect();
+        self.direction = (1, 0);
+        self.step_timer = 0.0;
+        self.score = 0;
+        self.alive = true;
+        self.place_food(rng);
+    }
+
+    /// Puts the food on a random cell not covered by the snake
+    fn place_food(&mut self, rng: &mut Rng) {
+        loop {
+            let food = (rng.range(1, WIDTH - 1), rng.range(1, HEIGHT - 1));
+            if !self.body.contains(&food) {
+                self.food = food;
+                return;
+            }
+        }
+    }
+
+    /// Advances the snake one cell, returning the sound to play
+    fn step(&mut self, rng: &mut Rng) -> Option<&'static str> {
+        let (x, y) = self.body[0];
+        let head = ((x as i32 + self.direction.0) as usize, (y as i32 + self.direction.1) as usize);
+
+        let hit_wall = head.0 == 0 || head.1 == 0 || head.0 >= WIDTH - 1 || head.1 >= HEIGHT - 1;
+        // The tail moves away this step, so only the rest of the body blocks
+        let hit_self = self.body.iter().take(self.body.len() - 1).any(|part| *part == head);
+        if hit_wall || hit_self {
+            self.alive = false;
+            return Some("crash");
+        }
+
+        self.body.push_front(head);
+        if head == self.food {
+            self.score += 1;
+            self.place_food(rng);
+            Some("eat")
+        } else {
+            self.body.pop_back();
+            None
+        }
+    }
+}
+
+impl Updatable for Snake {
+    fn update_with_context(&mut self, ctx: &mut UpdateContext) -> Vec<EngineCommand> {
+        if self.actions.is_active("quit", ctx.active_keys) {
+            return vec![EngineCommand::Quit];
+        }
+        if !self.alive {
+            if self.actions.is_active("restart", ctx.active_keys) {
+                self.reset(ctx.rng);
+            }
+            return Vec::new();
+        }
+
+        for (action, direction) in [("up", (0, -1)), ("down", (0, 1)), ("left", (-1, 0)), ("right", (1, 0))] {
+            // Reversing into the neck would be instant death
+            if self.actions.is_active(action, ctx.active_keys) && direction != (-self.direction.0, -self.direction.1) {
+                self.direction = direction;
+            }
+        }
+
+        let mut commands = Vec::new();
+        let interval = (START_INTERVAL - self.score as f32 * 0.005).max(MIN_INTERVAL);
+        self.step_timer += ctx.delta_time;
+        while self.alive && self.step_timer >= interval {
+            self.step_timer -= interval;
+            if let Some(sound) = self.step(ctx.rng) {
+                commands.push(EngineCommand::PlaySound(sound.to_string()));
+            }
+        }
+
+        commands
+    }
+
+    fn draw(&self, renderer: &mut Renderer) {
+        let body_style = Style::new().fg(Color::GREEN);
+        for (i, (x, y)) in self.body.iter().enumerate() {
+            renderer.set_cell(*x, *y, Cell::styled(if i == 0 { '@' } else { 'o' }, &body_style));
+        }
+        renderer.set_cell(self.food.0, self.food.1, Cell::styled('*', &Style::new().fg(Color::BRIGHT_RED).bold()));
+        renderer.draw_text(2, 0, &format!(" Score: {} ", self.score));
+
+        if !self.alive {
+            let font = Font::small();
+            let (text_width, _) = font.measure("GAME OVER");
+            renderer.draw_big_text((WIDTH - text_width) / 2, HEIGHT / 2 - 4, "GAME OVER", &font, &Style::new().fg(Color::RED));
+            renderer.draw_text(WIDTH / 2 - 9, HEIGHT / 2 + 3, "Enter to play again");
+        }
+    }
+}
+
+/// Border around the play field
+fn walls() -> Tilemap {
+    let wall = Cell::styled('#', &Style::new().fg(Color::GREY));
+    let mut tilemap = Tilemap::new(WIDTH, HEIGHT);
+    tilemap.fill_rect(0, 0, WIDTH, 1, wall);
+    tilemap.fill_rect(0, HEIGHT - 1, WIDTH, 1, wall);
+    tilemap.fill_rect(0, 0, 1, HEIGHT, wall);
+    tilemap.fill_rect(WIDTH - 1, 0, 1, HEIGHT, wall);
+    tilemap
+}
+
+fn main() {
+    let mut actions = ActionMap::new();
+    actions.set_keys("up", vec![Key::Up, Key::Char('w')]);
+    actions.set_keys("down", vec![Key::Down, Key::Char('s')]);
+    actions.set_keys("left", vec![Key::Left, Key::Char('a')]);
+    actions.set_keys("right", vec![Key::Right, Key::Char('d')]);
+    actions.set_keys("restart", vec![Key::Enter]);
+    actions.set_keys("quit", vec![Key::Esc]);
+
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.renderer.set_background(walls());
+    let _ = engine.audio.preload_bytes("eat", tone(880.0, Duration::from_millis(60)));
+    let _ = engine.audio.preload_bytes("crash", tone(110.0, Duration::from_millis(400)));
+
+    let snake = Snake::new(actions, &mut engine.rng);
+    engine.add_updatable(snake);
+    engine.run();
+}
@@ -0,0 +1,121 @@
+//! Accessibility options resolved by the engine for every game
+//!
+//! [`Accessibility`] gathers the options players may need regardless of what
+//! the game draws: a high-contrast palette, turning off blinking, flicker, and
+//! screen shake, and slowing down animations that change too quickly. The
+//! engine reads them from the player's settings and hands them to the
+//! renderer and camera, so games get them without checking any of it.
+//!
+//! - High contrast recolors every presented cell to bright colors on black,
+//!   keeping light backgrounds such as menu highlights as black on white
+//! - Reduced flashing drops the blink attribute, animated post effects such as
+//!   `PostFx::Flicker`, and camera shake
+//! - A minimum frame duration holds every animation frame at least that long
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{accessibility::Accessibility, color::Color, renderer::Cell};
+//!
+//! let options = Accessibility::new().high_contrast(true).reduce_flashing(true);
+//! let mut cell = Cell::default();
+//! cell.fg = Color::Rgb(120, 20, 20);
+//! cell.attrs.blink = true;
+//!
+//! options.apply(&mut cell);
+//! assert_eq!(cell.fg, Color::BRIGHT_RED);
+//! assert_eq!(cell.bg, Color::BLACK);
+//! assert!(!cell.attrs.blink);
+//! ```
+
+use crate::{color::Color, renderer::Cell};
+
+/// Saturation below which a color counts as grey
+const GREY_SATURATION: f32 = 0.25;
+
+/// Brightness from which a background counts as light
+const LIGHT_BACKGROUND: f32 = 0.5;
+
+/// Options making games easier to see and safer to watch
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Accessibility {
+    /// Recolor presented frames to bright colors on black
+    pub high_contrast: bool,
+    /// Drop blinking, flicker, and screen shake
+    pub reduce_flashing: bool,
+    /// Seconds every animation frame is shown at least, `0.0` for no minimum
+    pub min_frame_duration: f32,
+}
+
+impl Accessibility {
+    /// Creates options changing nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether presented frames use the high-contrast palette
+    pub fn high_contrast(mut self, enabled: bool) -> Self {
+        self.high_contrast = enabled;
+        self
+    }
+
+    /// Sets whether blinking, flicker, and screen shake are dropped
+    pub fn reduce_flashing(mut self, enabled: bool) -> Self {
+        self.reduce_flashing = enabled;
+        self
+    }
+
+    /// Sets the seconds every animation frame is shown at least
+    pub fn min_frame_duration(mut self, seconds: f32) -> Self {
+        self.min_frame_duration = seconds.max(0.0);
+        self
+    }
+
+    /// Returns whether presented cells are changed, see [`apply`](Self::apply)
+    pub fn changes_cells(&self) -> bool {
+        self.high_contrast || self.reduce_flashing
+    }
+
+    /// Changes a presented cell to follow the options
+    ///
+    /// # Notes
+    /// - Called by the renderer for every presented cell, after post effects
+    pub fn apply(&self, cell: &mut Cell) {
+        if self.reduce_flashing {
+            cell.attrs.blink = false;
+        }
+        if self.high_contrast {
+            let light = cell.bg.to_hsv().is_some_and(|(_, saturation, value)| saturation < GREY_SATURATION && value >= LIGHT_BACKGROUND);
+            if light {
+                (cell.fg, cell.bg) = (Color::BLACK, Color::BRIGHT_WHITE);
+            } else {
+                (cell.fg, cell.bg) = (high_contrast(cell.fg), Color::BLACK);
+            }
+        }
+    }
+}
+
+/// Closest bright color to a foreground, white for greys and the terminal default
+///
+/// # Example
+/// ```
+/// use lonely_engine::{accessibility::high_contrast, color::Color};
+///
+/// assert_eq!(high_contrast(Color::Rgb(30, 90, 30)), Color::BRIGHT_GREEN);
+/// assert_eq!(high_contrast(Color::GREY), Color::BRIGHT_WHITE);
+/// assert_eq!(high_contrast(Color::Default), Color::BRIGHT_WHITE);
+/// ```
+pub fn high_contrast(color: Color) -> Color {
+    match color.to_hsv() {
+        Some((hue, saturation, _)) if saturation >= GREY_SATURATION => match hue {
+            hue if hue < 30.0 => Color::BRIGHT_RED,
+            hue if hue < 90.0 => Color::BRIGHT_YELLOW,
+            hue if hue < 150.0 => Color::BRIGHT_GREEN,
+            hue if hue < 210.0 => Color::BRIGHT_CYAN,
+            hue if hue < 270.0 => Color::BRIGHT_BLUE,
+            hue if hue < 330.0 => Color::BRIGHT_MAGENTA,
+            _ => Color::BRIGHT_RED,
+        },
+        _ => Color::BRIGHT_WHITE,
+    }
+}
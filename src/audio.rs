@@ -3,17 +3,145 @@
 //! Provides functionality for playing sound effects using native system APIs.
 //! Currently supports WAV file playback on Windows via the Win32 API.
 //! Non-Windows platforms have a stub implementation that returns errors.
+//!
+//! [`AudioEngine`] preloads sounds into memory up front so playback during the
+//! game never touches the disk, and hands out [`SoundHandle`]s for tracking
+//! playback until the engine reports `EngineEvent::SoundFinished`. Up to
+//! [`MAX_VOICES`] sounds play at once, mixed in software and streamed to the
+//! sound card through waveOut. Sounds played with [`AudioEngine::play_at`] are
+//! panned and attenuated relative to a listener.
+//! [`tone`] generates placeholder beeps for prototyping.
+//!
+//! Every sound belongs to an [`AudioGroup`] with its own volume, so players can
+//! turn the music down without losing effects. A group can [duck](Ducking) under
+//! others: by default music fades down while voice lines play and fades back up
+//! after them.
+//!
+//! Single playbacks can [fade in](AudioEngine::fade_in) and
+//! [out](AudioEngine::fade_out), such as music across scene transitions, and
+//! play [faster or slower](AudioEngine::set_speed), shifting their pitch so
+//! repeated footsteps do not all sound the same.
+//!
+//! The free functions [`play_sound`], [`play_sound_bytes`], and [`loop_sound_bytes`]
+//! go through PlaySoundW instead, which plays one sound at a time.
 
-use std::io;
-use std::ffi::OsStr;
-use std::os::windows::ffi::OsStrExt;
+use std::{collections::HashMap, fs, io, ops::Range, path::Path, sync::{Arc, Mutex}, time::{Duration, Instant}};
+use crate::mixer::{Mixer, Samples};
 
 #[cfg(windows)]
 mod windows_audio {
     use super::*;
-    use windows::Win32::Media::Audio::{PlaySoundW, SND_FILENAME, SND_ASYNC};
-    use windows::Win32::Foundation::PWSTR;
-    
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::thread::{self, JoinHandle};
+    use windows::Win32::Media::Audio::{
+        PlaySoundW, SND_FILENAME, SND_ASYNC, SND_LOOP, SND_MEMORY, SND_NODEFAULT, CALLBACK_NULL, HWAVEOUT, WAVEFORMATEX,
+        WAVEHDR, WAVE_FORMAT_PCM, WAVE_MAPPER, WHDR_DONE, waveOutClose, waveOutOpen, waveOutPrepareHeader, waveOutReset,
+        waveOutUnprepareHeader, waveOutWrite,
+    };
+    use windows::Win32::Foundation::{PSTR, PWSTR};
+    use crate::mixer::OUTPUT_SAMPLE_RATE;
+
+    /// Blocks queued on the device, more survive longer frame hitches at the cost of latency
+    const BLOCK_COUNT: usize = 4;
+
+    /// Stereo frames per block, 20 ms
+    const BLOCK_FRAMES: usize = OUTPUT_SAMPLE_RATE as usize / 50;
+
+    /// Mixed stream playing on the default waveOut device, closed on drop
+    #[derive(Debug)]
+    pub struct OutputStream {
+        running: Arc<AtomicBool>,
+        thread: Option<JoinHandle<()>>,
+    }
+
+    impl Drop for OutputStream {
+        fn drop(&mut self) {
+            self.running.store(false, Ordering::Relaxed);
+            if let Some(thread) = self.thread.take() {
+                let _ = thread.join();
+            }
+        }
+    }
+
+    /// Opens the default output device and keeps it fed from a mixer on a background thread
+    pub fn open_output(mixer: Arc<Mutex<Mixer>>) -> io::Result<OutputStream> {
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = Arc::clone(&running);
+        let (opened, open_result) = std::sync::mpsc::channel();
+        let thread = thread::spawn(move || {
+            let format = WAVEFORMATEX {
+                wFormatTag: WAVE_FORMAT_PCM as u16,
+                nChannels: 2,
+                nSamplesPerSec: OUTPUT_SAMPLE_RATE,
+                nAvgBytesPerSec: OUTPUT_SAMPLE_RATE * 4,
+                nBlockAlign: 4,
+                wBitsPerSample: 16,
+                cbSize: 0,
+            };
+            let mut device = HWAVEOUT::default();
+            // SAFETY: The format is fully initialized and the handle is written by the call
+            let result = unsafe { waveOutOpen(&mut device, WAVE_MAPPER, &format, 0, 0, CALLBACK_NULL) };
+            if result != 0 {
+                let _ = opened.send(Err(io::Error::other(format!("could not open the audio device (error {})", result))));
+                return;
+            }
+            let _ = opened.send(Ok(()));
+
+            // The device reads blocks and headers until they are unprepared, so neither may move
+            let mut blocks = vec![vec![0i16; BLOCK_FRAMES * 2].into_boxed_slice(); BLOCK_COUNT];
+            let mut header_storage: Box<[WAVEHDR]> = (0..BLOCK_COUNT).map(|_| WAVEHDR::default()).collect();
+            let header_size = std::mem::size_of::<WAVEHDR>() as u32;
+            let headers = header_storage.as_mut_ptr();
+            for (index, block) in blocks.iter_mut().enumerate() {
+                // SAFETY: `headers` holds BLOCK_COUNT headers and outlives the device
+                unsafe {
+                    let header = headers.add(index);
+                    (*header).lpData = PSTR(block.as_mut_ptr().cast());
+                    (*header).dwBufferLength = (block.len() * 2) as u32;
+                    waveOutPrepareHeader(device, header, header_size);
+                    // Marked done so the first pass fills every block
+                    (*header).dwFlags |= WHDR_DONE;
+                }
+            }
+
+            while thread_running.load(Ordering::Relaxed) {
+                let mut queued = false;
+                for (index, block) in blocks.iter_mut().enumerate() {
+                    // SAFETY: The device sets WHDR_DONE once it no longer reads the block
+                    unsafe {
+                        let header = headers.add(index);
+                        if std::ptr::addr_of!((*header).dwFlags).read_unaligned() & WHDR_DONE == 0 {
+                            continue;
+                        }
+                        mixer.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).mix(block);
+                        waveOutWrite(device, header, header_size);
+                    }
+                    queued = true;
+                }
+                if !queued {
+                    thread::sleep(std::time::Duration::from_millis(2));
+                }
+            }
+
+            // SAFETY: Resetting returns every block, after which they can be released
+            unsafe {
+                waveOutReset(device);
+                for index in 0..BLOCK_COUNT {
+                    waveOutUnprepareHeader(device, headers.add(index), header_size);
+                }
+                waveOutClose(device);
+            }
+            drop(header_storage);
+        });
+
+        match open_result.recv() {
+            Ok(Ok(())) => Ok(OutputStream { running, thread: Some(thread) }),
+            Ok(Err(error)) => Err(error),
+            Err(_) => Err(io::Error::other("audio thread stopped before opening the device")),
+        }
+    }
 
     /// Plays a WAV file asynchronously using the Windows PlaySoundW API.
     ///
@@ -48,21 +176,81 @@ mod windows_audio {
         // SAFETY: We ensure the wide string is properly null-terminated and
         // valid for the duration of the PlaySoundW call
         let result = unsafe {
-            PlaySoundW(PWSTR(wide.as_ptr() as *mut u16), None, SND_FILENAME as u32 | SND_ASYNC as u32)
+            PlaySoundW(PWSTR(wide.as_ptr() as *mut u16), None, SND_FILENAME as u32 | SND_ASYNC)
         };
 
         // If the result if 0, the function failed.
         if !result.as_bool() {
-            Err(io::Error::new(io::ErrorKind::Other, "Failed to play sound"))
+            Err(io::Error::other("Failed to play sound"))
         } else {
             Ok(())
         }
     }
+
+    /// Plays an in-memory WAV file asynchronously using PlaySoundW.
+    ///
+    /// # Arguments
+    /// * `wav` - Complete WAV file contents (header and samples)
+    ///
+    /// # Safety
+    /// PlaySoundW keeps reading `wav` while the sound plays, so callers must keep
+    /// the buffer alive and unmoved until playback ends or [`stop_sound`] is called.
+    /// [`AudioEngine`] guarantees this by owning the buffers it plays.
+    pub fn play_sound_bytes(wav: &[u8]) -> io::Result<()> {
+        // SAFETY: With SND_MEMORY the "name" pointer is reinterpreted as a pointer to the WAV image
+        let result = unsafe {
+            PlaySoundW(PWSTR(wav.as_ptr() as *mut u16), None, SND_MEMORY | SND_ASYNC | SND_NODEFAULT)
+        };
+
+        if !result.as_bool() {
+            Err(io::Error::other("Failed to play sound"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Plays an in-memory WAV file on repeat until [`stop_sound`] or another sound starts.
+    ///
+    /// # Safety
+    /// Same buffer lifetime requirements as [`play_sound_bytes`].
+    pub fn loop_sound_bytes(wav: &[u8]) -> io::Result<()> {
+        // SAFETY: With SND_MEMORY the "name" pointer is reinterpreted as a pointer to the WAV image
+        let result = unsafe {
+            PlaySoundW(PWSTR(wav.as_ptr() as *mut u16), None, SND_MEMORY | SND_ASYNC | SND_LOOP | SND_NODEFAULT)
+        };
+
+        if !result.as_bool() {
+            Err(io::Error::other("Failed to play sound"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Stops any sound started with [`play_sound`], [`play_sound_bytes`] or [`loop_sound_bytes`].
+    pub fn stop_sound() {
+        // SAFETY: A null sound name is documented to stop the currently playing sound
+        unsafe {
+            PlaySoundW(PWSTR(std::ptr::null_mut()), None, 0);
+        }
+    }
 }
 
 #[cfg(not(windows))]
 mod unix_audio {
-    use std::io;
+    use std::{io, sync::{Arc, Mutex}};
+    use crate::mixer::Mixer;
+
+    /// Stub output stream for non-Windows platforms, never opened
+    #[derive(Debug)]
+    pub struct OutputStream;
+
+    /// Stub implementation for non-Windows platforms
+    ///
+    /// # Platform Specific
+    /// Always returns an error on non-Windows platforms
+    pub fn open_output(_mixer: Arc<Mutex<Mixer>>) -> io::Result<OutputStream> {
+        Err(io::Error::other("Audio not implement for non-Window platforms"))
+    }
 
     /// Stub implementation for non-Windows platforms
     ///
@@ -73,12 +261,995 @@ mod unix_audio {
     /// This is a placeholder implementation. Consider using platform-specific
     /// audio libraries (e.g., ALSA, PulseAudio) for Unix support.
     pub fn play_sound(_file: &str) -> io::Result<()> {
-        Err(io::Error::new(io::ErrorKind::Other, "Audio not implement for non-Window platforms"))
+        Err(io::Error::other("Audio not implement for non-Window platforms"))
+    }
+
+    /// Stub implementation for non-Windows platforms
+    ///
+    /// # Platform Specific
+    /// Always returns an error on non-Windows platforms
+    pub fn play_sound_bytes(_wav: &[u8]) -> io::Result<()> {
+        Err(io::Error::other("Audio not implement for non-Window platforms"))
     }
+
+    /// Stub implementation for non-Windows platforms
+    ///
+    /// # Platform Specific
+    /// Always returns an error on non-Windows platforms
+    pub fn loop_sound_bytes(_wav: &[u8]) -> io::Result<()> {
+        Err(io::Error::other("Audio not implement for non-Window platforms"))
+    }
+
+    /// Stub implementation for non-Windows platforms, does nothing
+    pub fn stop_sound() {}
 }
 
 #[cfg(windows)]
 pub use windows_audio::*;
 
 #[cfg(not(windows))]
-pub use unix_audio::*;
\ No newline at end of file
+pub use unix_audio::*;
+
+/// Sounds that failed to preload, reported by [`AudioEngine::preload_all`]
+#[derive(Debug, Default)]
+pub struct PreloadReport {
+    /// Names of sounds loaded successfully
+    pub loaded: Vec<String>,
+    /// Names of sounds that failed, with the reason
+    pub failed: Vec<(String, io::Error)>,
+}
+
+impl PreloadReport {
+    /// Returns whether every requested sound was loaded
+    pub fn is_ok(&self) -> bool {
+        self.failed.is_empty()
+    }
+}
+
+/// Sample rate of sounds generated by [`tone`]
+const TONE_SAMPLE_RATE: u32 = 22_050;
+
+/// Generates a square wave beep as a mono 16-bit WAV file
+///
+/// Handy for prototyping sound effects before real assets exist.
+///
+/// # Arguments
+/// * `frequency` - Pitch in hertz
+/// * `duration` - Length of the sound
+///
+/// # Notes
+/// - The volume fades out linearly so the beep ends without a click
+pub fn tone(frequency: f32, duration: Duration) -> Vec<u8> {
+    let frames = (duration.as_secs_f64() * f64::from(TONE_SAMPLE_RATE)).round() as usize;
+    let mut wav = wav_header(1, TONE_SAMPLE_RATE, frames * 2);
+    for frame in 0..frames {
+        let phase = (frame as f32 * frequency / TONE_SAMPLE_RATE as f32).fract();
+        let fade = 1.0 - frame as f32 / frames as f32;
+        let sample = if phase < 0.5 { 8000.0 } else { -8000.0 } * fade;
+        wav.extend_from_slice(&(sample as i16).to_le_bytes());
+    }
+    wav
+}
+
+/// Identifies one playback started by [`AudioEngine::play`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SoundHandle(pub u64);
+
+/// Default distance in cells at which positional sounds fade to silence
+pub const DEFAULT_HEARING_RANGE: f32 = 40.0;
+
+/// Sounds [`AudioEngine`] plays at the same time, starting another stops the oldest
+pub const MAX_VOICES: usize = 16;
+
+/// Slowest and fastest playback speeds accepted by [`AudioEngine::set_speed`]
+pub const SPEED_RANGE: (f32, f32) = (0.1, 8.0);
+
+/// Category of sounds sharing a volume and ducking
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AudioGroup {
+    /// Background tracks
+    Music,
+    /// Gameplay sound effects, the group of sounds not assigned to another
+    Sfx,
+    /// Menu and interface sounds
+    Ui,
+    /// Dialogue and voice lines
+    Voice,
+}
+
+impl AudioGroup {
+    /// Every group
+    pub const ALL: [AudioGroup; 4] = [AudioGroup::Music, AudioGroup::Sfx, AudioGroup::Ui, AudioGroup::Voice];
+}
+
+/// Lowers a group's volume while sounds of other groups play
+///
+/// # Example
+/// ```
+/// use lonely_engine::audio::{AudioEngine, AudioGroup, Ducking};
+///
+/// let mut audio = AudioEngine::new();
+/// // Music also makes room for explosions and other effects
+/// audio.set_ducking(AudioGroup::Music, Some(Ducking::new(&[AudioGroup::Voice, AudioGroup::Sfx]).level(0.5)));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ducking {
+    /// Groups whose sounds lower the ducked group
+    triggers: Vec<AudioGroup>,
+    /// Volume multiplier while ducked
+    level: f32,
+    /// Seconds to fade down once a trigger sound starts
+    fade_out: f32,
+    /// Seconds to fade back up once the last trigger sound ends
+    fade_in: f32,
+}
+
+impl Ducking {
+    /// Ducks to 30% while sounds of any trigger group play, fading down in 0.15 s and back up in 0.75 s
+    pub fn new(triggers: &[AudioGroup]) -> Self {
+        Self { triggers: triggers.to_vec(), level: 0.3, fade_out: 0.15, fade_in: 0.75 }
+    }
+
+    /// Sets the volume multiplier while ducked, from `0.0` (silent) to `1.0` (no ducking)
+    pub fn level(mut self, level: f32) -> Self {
+        self.level = level.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the seconds taken to fade down
+    pub fn fade_out(mut self, seconds: f32) -> Self {
+        self.fade_out = seconds.max(0.0);
+        self
+    }
+
+    /// Sets the seconds taken to fade back up
+    pub fn fade_in(mut self, seconds: f32) -> Self {
+        self.fade_in = seconds.max(0.0);
+        self
+    }
+}
+
+/// Volume and ducking of one [`AudioGroup`]
+#[derive(Debug, Clone)]
+struct GroupMix {
+    volume: f32,
+    ducking: Option<Ducking>,
+    /// Current ducking multiplier, `1.0` when not ducked
+    duck_level: f32,
+}
+
+impl GroupMix {
+    fn gain(&self) -> f32 {
+        self.volume * self.duck_level
+    }
+}
+
+/// Preloaded WAV file with its length
+#[derive(Debug)]
+struct Sound {
+    samples: Arc<Samples>,
+    format: WavFormat,
+    group: AudioGroup,
+}
+
+/// Layout of a PCM WAV file
+#[derive(Debug, Clone)]
+struct WavFormat {
+    /// `1` for integer PCM, `3` for floating point
+    encoding: u16,
+    channels: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+    byte_rate: u32,
+    data: Range<usize>,
+}
+
+impl WavFormat {
+    fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.data.len() as f64 / f64::from(self.byte_rate))
+    }
+}
+
+/// Sound currently playing
+#[derive(Debug)]
+struct Playback {
+    handle: SoundHandle,
+    name: String,
+    /// When `speed` last changed, or the start
+    resumed: Instant,
+    /// Position in the sound reached at `resumed`
+    offset: Duration,
+    speed: f32,
+    duration: Duration,
+    looping: bool,
+    /// Grid position of sounds started with [`AudioEngine::play_at`]
+    position: Option<(i32, i32)>,
+    group: AudioGroup,
+    fade: Fade,
+}
+
+impl Playback {
+    /// Position reached in the sound, wrapping around for loops
+    fn progress(&self) -> Duration {
+        let progress = self.offset + self.resumed.elapsed().mul_f32(self.speed);
+        if self.looping && !self.duration.is_zero() {
+            Duration::from_secs_f64(progress.as_secs_f64() % self.duration.as_secs_f64())
+        } else {
+            progress
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.looping || self.progress() < self.duration
+    }
+}
+
+/// Volume ramp of one playback
+#[derive(Debug, Clone, Copy)]
+struct Fade {
+    /// Current volume multiplier
+    level: f32,
+    target: f32,
+    /// Change of `level` per second
+    rate: f32,
+    /// Stop the playback once `target` is reached
+    stop: bool,
+}
+
+impl Fade {
+    const NONE: Fade = Fade { level: 1.0, target: 1.0, rate: 0.0, stop: false };
+
+    fn towards(level: f32, target: f32, seconds: f32, stop: bool) -> Self {
+        let rate = if seconds > 0.0 { (target - level).abs() / seconds } else { f32::INFINITY };
+        Fade { level, target, rate, stop }
+    }
+}
+
+/// Named sound bank with sounds held in memory
+///
+/// Loading reads and validates files once, so [`AudioEngine::play`] never hits the
+/// disk mid-game.
+///
+/// # Notes
+/// - Up to [`MAX_VOICES`] sounds play at once, starting another stops the oldest one, preferring one-shots over loops
+/// - The output device is opened on the first unmuted playback and stays open
+/// - Completion is derived from the WAV length, the engine polls it every frame
+///
+/// # Example
+/// ```no_run
+/// use lonely_engine::audio::AudioEngine;
+///
+/// let mut audio = AudioEngine::new();
+/// let report = audio.preload_all(&[("jump", "assets/jump.wav"), ("coin", "assets/coin.wav")]);
+/// for (name, error) in &report.failed {
+///     eprintln!("Could not load {}: {}", name, error);
+/// }
+///
+/// audio.play("jump").ok();
+/// audio.play("coin").ok(); // Plays over the jump
+/// ```
+#[derive(Debug)]
+pub struct AudioEngine {
+    sounds: HashMap<String, Sound>,
+    muted: bool,
+    /// Master volume from `0.0` to `1.0`
+    volume: f32,
+    /// Running playbacks, oldest first
+    playing: Vec<Playback>,
+    finished: Vec<(SoundHandle, String)>,
+    next_handle: u64,
+    listener: (i32, i32),
+    hearing_range: f32,
+    mixer: Arc<Mutex<Mixer>>,
+    /// Device fed by `mixer`, opened on first use
+    output: Option<OutputStream>,
+    /// Indexed by [`AudioGroup`]
+    groups: [GroupMix; 4],
+}
+
+impl Default for AudioEngine {
+    fn default() -> Self {
+        Self {
+            sounds: HashMap::new(),
+            muted: false,
+            volume: 1.0,
+            playing: Vec::new(),
+            finished: Vec::new(),
+            next_handle: 0,
+            listener: (0, 0),
+            hearing_range: DEFAULT_HEARING_RANGE,
+            mixer: Arc::new(Mutex::new(Mixer::new(crate::mixer::OUTPUT_SAMPLE_RATE))),
+            output: None,
+            groups: AudioGroup::ALL.map(|group| GroupMix {
+                volume: 1.0,
+                ducking: (group == AudioGroup::Music).then(|| Ducking::new(&[AudioGroup::Voice])),
+                duck_level: 1.0,
+            }),
+        }
+    }
+}
+
+impl AudioEngine {
+    /// Creates an empty sound bank
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mutes or unmutes playback
+    ///
+    /// # Notes
+    /// - Muting stops every sound playing
+    /// - While muted, [`AudioEngine::play`] succeeds without playing anything
+    pub fn set_muted(&mut self, muted: bool) {
+        if muted && !self.muted {
+            self.stop();
+        }
+        self.muted = muted;
+    }
+
+    /// Returns whether playback is muted
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+
+    /// Sets the master volume
+    ///
+    /// # Arguments
+    /// * `volume` - `0.0` (silent) to `1.0` (as recorded), clamped
+    ///
+    /// # Notes
+    /// - Sounds already playing change volume too
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume.clamp(0.0, 1.0);
+        self.remix_all();
+    }
+
+    /// Master volume from `0.0` to `1.0`
+    pub fn volume(&self) -> f32 {
+        self.volume
+    }
+
+    /// Sets the volume of one group, on top of the master volume
+    ///
+    /// # Arguments
+    /// * `volume` - `0.0` (silent) to `1.0` (as recorded), clamped
+    ///
+    /// # Notes
+    /// - Sounds already playing change volume too
+    pub fn set_group_volume(&mut self, group: AudioGroup, volume: f32) {
+        self.groups[group as usize].volume = volume.clamp(0.0, 1.0);
+        self.remix_all();
+    }
+
+    /// Volume of one group from `0.0` to `1.0`
+    pub fn group_volume(&self, group: AudioGroup) -> f32 {
+        self.groups[group as usize].volume
+    }
+
+    /// Sets which groups lower a group's volume while they play, `None` turns ducking off
+    ///
+    /// # Notes
+    /// - By default music ducks under voice lines and no other group ducks
+    pub fn set_ducking(&mut self, group: AudioGroup, ducking: Option<Ducking>) {
+        let mix = &mut self.groups[group as usize];
+        mix.ducking = ducking;
+        if mix.ducking.is_none() && mix.duck_level != 1.0 {
+            mix.duck_level = 1.0;
+            self.remix_all();
+        }
+    }
+
+    /// Ducking of a group, `None` when it never ducks
+    pub fn ducking(&self, group: AudioGroup) -> Option<&Ducking> {
+        self.groups[group as usize].ducking.as_ref()
+    }
+
+    /// Current ducking multiplier of a group, `1.0` when not ducked
+    pub fn duck_level(&self, group: AudioGroup) -> f32 {
+        self.groups[group as usize].duck_level
+    }
+
+    /// Fades a playback in from silence, such as music starting with a new scene
+    ///
+    /// # Arguments
+    /// * `handle` - Playback to fade in, usually right after starting it
+    /// * `seconds` - Time taken to reach full volume
+    ///
+    /// # Notes
+    /// - The fade advances with [`AudioEngine::update`], which the engine calls every frame
+    /// - Does nothing when the playback already finished
+    pub fn fade_in(&mut self, handle: SoundHandle, seconds: f32) {
+        if let Some(playback) = self.playing.iter_mut().find(|playback| playback.handle == handle) {
+            playback.fade = Fade::towards(0.0, 1.0, seconds, false);
+            self.remix(handle);
+        }
+    }
+
+    /// Fades a playback out to silence and stops it there
+    ///
+    /// # Arguments
+    /// * `handle` - Playback to fade out
+    /// * `seconds` - Time taken to become silent, starting from the current fade level
+    ///
+    /// # Notes
+    /// - The playback is reported finished once silent, like a stopped one
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use lonely_engine::audio::{tone, AudioEngine};
+    ///
+    /// let mut audio = AudioEngine::new();
+    /// audio.set_muted(true);
+    /// audio.preload_bytes("theme", tone(220.0, Duration::from_secs(5))).unwrap();
+    /// let theme = audio.play_looping("theme").unwrap();
+    ///
+    /// audio.fade_out(theme, 2.0);
+    /// audio.update(1.0);
+    /// assert_eq!(audio.fade_level(theme), Some(0.5));
+    /// audio.update(1.0);
+    /// assert!(!audio.is_playing(theme));
+    /// ```
+    pub fn fade_out(&mut self, handle: SoundHandle, seconds: f32) {
+        if let Some(playback) = self.playing.iter_mut().find(|playback| playback.handle == handle) {
+            playback.fade = Fade::towards(playback.fade.level, 0.0, seconds, true);
+            if seconds <= 0.0 {
+                self.stop_playback(handle);
+            }
+        }
+    }
+
+    /// Current fade multiplier of a playback, `1.0` when not fading, `None` once it finished
+    pub fn fade_level(&self, handle: SoundHandle) -> Option<f32> {
+        self.running_playback(handle).map(|playback| playback.fade.level)
+    }
+
+    /// Changes how fast a playback plays, raising or lowering its pitch along
+    ///
+    /// # Arguments
+    /// * `handle` - Playback to change
+    /// * `factor` - `1.0` as recorded, `0.5` half speed an octave lower, clamped to [`SPEED_RANGE`]
+    ///
+    /// # Notes
+    /// - Completion timing follows the speed, a sound played twice as fast finishes in half the time
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use lonely_engine::{audio::{tone, AudioEngine}, rng::Rng};
+    ///
+    /// let mut audio = AudioEngine::new();
+    /// audio.set_muted(true);
+    /// audio.preload_bytes("step", tone(300.0, Duration::from_millis(80))).unwrap();
+    ///
+    /// // Footsteps that never sound quite the same
+    /// let mut rng = Rng::new(7);
+    /// let step = audio.play("step").unwrap();
+    /// audio.set_speed(step, 0.9 + rng.next_f32() * 0.2);
+    /// assert!(audio.speed(step).is_some_and(|speed| (0.9..1.1).contains(&speed)));
+    /// ```
+    pub fn set_speed(&mut self, handle: SoundHandle, factor: f32) {
+        let speed = factor.clamp(SPEED_RANGE.0, SPEED_RANGE.1);
+        if let Some(playback) = self.playing.iter_mut().find(|playback| playback.handle == handle) {
+            playback.offset = playback.progress();
+            playback.resumed = Instant::now();
+            playback.speed = speed;
+            self.mixer().set_speed(handle, speed);
+        }
+    }
+
+    /// Playback speed of a running playback, `None` once it finished
+    pub fn speed(&self, handle: SoundHandle) -> Option<f32> {
+        self.running_playback(handle).map(|playback| playback.speed)
+    }
+
+    /// Fades ducked groups down or back up, and advances playback fades
+    ///
+    /// # Arguments
+    /// * `delta_time` - Real seconds since the previous update, audio keeps time while the game is paused
+    ///
+    /// # Notes
+    /// - Called by the engine every frame
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use lonely_engine::audio::{tone, AudioEngine, AudioGroup};
+    ///
+    /// let mut audio = AudioEngine::new();
+    /// audio.set_muted(true);
+    /// audio.preload_bytes("line", tone(220.0, Duration::from_secs(5))).unwrap();
+    /// audio.set_sound_group("line", AudioGroup::Voice);
+    ///
+    /// audio.play("line").unwrap();
+    /// audio.update(1.0);
+    /// assert_eq!(audio.duck_level(AudioGroup::Music), 0.3);
+    ///
+    /// audio.stop();
+    /// audio.update(1.0);
+    /// assert_eq!(audio.duck_level(AudioGroup::Music), 1.0);
+    /// ```
+    pub fn update(&mut self, delta_time: f32) {
+        let mut changed = false;
+        for group in AudioGroup::ALL {
+            let mix = &self.groups[group as usize];
+            let Some(ducking) = &mix.ducking else {
+                continue;
+            };
+            let triggered = self.playing.iter().any(|playback| playback.is_running() && ducking.triggers.contains(&playback.group));
+            let (target, seconds) = if triggered { (ducking.level, ducking.fade_out) } else { (1.0, ducking.fade_in) };
+            // Linear fade covering the whole ducking range in `seconds`
+            let step = if seconds > 0.0 { (1.0 - ducking.level) * delta_time / seconds } else { f32::INFINITY };
+            let level = if mix.duck_level < target { (mix.duck_level + step).min(target) } else { (mix.duck_level - step).max(target) };
+            if level != mix.duck_level {
+                self.groups[group as usize].duck_level = level;
+                changed = true;
+            }
+        }
+
+        let mut faded = Vec::new();
+        for playback in &mut self.playing {
+            let fade = &mut playback.fade;
+            if fade.level == fade.target {
+                continue;
+            }
+            let step = fade.rate * delta_time;
+            fade.level = if fade.level < fade.target { (fade.level + step).min(fade.target) } else { (fade.level - step).max(fade.target) };
+            faded.push((playback.handle, fade.level == fade.target && fade.stop));
+        }
+        for (handle, stop) in faded {
+            if stop {
+                self.stop_playback(handle);
+            } else if !changed {
+                self.remix(handle);
+            }
+        }
+        if changed {
+            self.remix_all();
+        }
+    }
+
+    /// Loads a WAV file into memory under `name`
+    ///
+    /// # Arguments
+    /// * `name` - Name used to play the sound later
+    /// * `path` - Path to the WAV file
+    ///
+    /// # Returns
+    /// * `Ok(())` if the file was read and decoded
+    /// * `Err(io::Error)` if the file is missing, not a RIFF/WAVE file, or not 8, 16, 24, or 32-bit PCM
+    ///
+    /// # Notes
+    /// - Loading a name twice replaces the previous sound
+    pub fn preload(&mut self, name: &str, path: impl AsRef<Path>) -> io::Result<()> {
+        self.preload_bytes(name, fs::read(path)?)
+    }
+
+    /// Loads an in-memory WAV file under `name`, such as one from `include_bytes!` or [`tone`]
+    ///
+    /// # Returns
+    /// * `Ok(())` if the bytes were decoded
+    /// * `Err(io::Error)` if they are not a RIFF/WAVE file of a supported format
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use lonely_engine::audio::{tone, AudioEngine};
+    ///
+    /// let mut audio = AudioEngine::new();
+    /// audio.preload_bytes("beep", tone(880.0, Duration::from_millis(100))).unwrap();
+    /// assert_eq!(audio.sound_duration("beep"), Some(Duration::from_millis(100)));
+    /// ```
+    pub fn preload_bytes(&mut self, name: &str, bytes: Vec<u8>) -> io::Result<()> {
+        let format = parse_wav(&bytes)?;
+        let samples = Arc::new(decode_samples(&bytes, &format)?);
+        let group = self.sounds.get(name).map_or(AudioGroup::Sfx, |sound| sound.group);
+        self.sounds.insert(name.to_string(), Sound { samples, format, group });
+        Ok(())
+    }
+
+    /// Assigns a loaded sound to a group, sounds are effects until assigned
+    ///
+    /// # Notes
+    /// - Applies to playbacks started from now on, reloading the sound keeps its group
+    pub fn set_sound_group(&mut self, name: &str, group: AudioGroup) {
+        if let Some(sound) = self.sounds.get_mut(name) {
+            sound.group = group;
+        }
+    }
+
+    /// Group of a loaded sound
+    pub fn sound_group(&self, name: &str) -> Option<AudioGroup> {
+        self.sounds.get(name).map(|sound| sound.group)
+    }
+
+    /// Loads several sounds, continuing past failures
+    ///
+    /// # Arguments
+    /// * `assets` - `(name, path)` pairs
+    ///
+    /// # Returns
+    /// A [`PreloadReport`] listing missing or invalid assets
+    pub fn preload_all(&mut self, assets: &[(&str, &str)]) -> PreloadReport {
+        let mut report = PreloadReport::default();
+        for (name, path) in assets {
+            match self.preload(name, path) {
+                Ok(()) => report.loaded.push(name.to_string()),
+                Err(error) => report.failed.push((name.to_string(), error)),
+            }
+        }
+
+        report
+    }
+
+    /// Returns whether a sound with `name` is loaded
+    pub fn is_loaded(&self, name: &str) -> bool {
+        self.sounds.contains_key(name)
+    }
+
+    /// Length of a loaded sound
+    pub fn sound_duration(&self, name: &str) -> Option<Duration> {
+        self.sounds.get(name).map(|sound| sound.format.duration())
+    }
+
+    /// Drops a loaded sound, stopping its playbacks first
+    pub fn unload(&mut self, name: &str) {
+        if self.sounds.remove(name).is_some() {
+            let handles: Vec<_> = self.playing.iter().filter(|playback| playback.name == name).map(|playback| playback.handle).collect();
+            for handle in handles {
+                self.stop_playback(handle);
+            }
+        }
+    }
+
+    /// Plays a preloaded sound asynchronously, over any sounds already playing
+    ///
+    /// # Returns
+    /// * Handle for querying the playback
+    /// * `Err` with [`io::ErrorKind::NotFound`] if `name` was never preloaded
+    /// * `Err` if the output device could not be opened
+    ///
+    /// # Notes
+    /// - While muted, playback is tracked silently so completion timing still works
+    pub fn play(&mut self, name: &str) -> io::Result<SoundHandle> {
+        self.start(name, None, false)
+    }
+
+    /// Plays a preloaded sound on repeat until stopped, for music and ambience
+    pub fn play_looping(&mut self, name: &str) -> io::Result<SoundHandle> {
+        self.start(name, None, true)
+    }
+
+    /// Plays a preloaded sound panned and attenuated by its distance from the listener
+    ///
+    /// # Arguments
+    /// * `name` - Preloaded sound
+    /// * `x`, `y` - Grid position of the sound source
+    ///
+    /// # Returns
+    /// Same as [`AudioEngine::play`]
+    ///
+    /// # Notes
+    /// - Sources left or right of the listener favor that speaker
+    /// - Volume falls off linearly, reaching silence at the hearing range
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::audio::AudioEngine;
+    /// # let mut audio = AudioEngine::new();
+    /// audio.set_listener(40, 12); // Player position
+    /// audio.play_at("explosion", 70, 12).ok(); // Mostly in the right speaker
+    /// ```
+    pub fn play_at(&mut self, name: &str, x: i32, y: i32) -> io::Result<SoundHandle> {
+        self.start(name, Some((x, y)), false)
+    }
+
+    /// Plays a preloaded sound on repeat at a position, for engines, fires, and other ambient sources
+    ///
+    /// # Notes
+    /// - Keeps playing until stopped, so it never finishes on its own
+    /// - Call [`AudioEngine::move_sound`] as the source moves
+    pub fn play_looping_at(&mut self, name: &str, x: i32, y: i32) -> io::Result<SoundHandle> {
+        self.start(name, Some((x, y)), true)
+    }
+
+    /// Moves the source of a positional sound
+    ///
+    /// # Notes
+    /// - The sound is re-panned in place, continuing where it is
+    /// - Sounds started with [`AudioEngine::play`] are not positional and stay as they are
+    pub fn move_sound(&mut self, handle: SoundHandle, x: i32, y: i32) -> io::Result<()> {
+        if let Some(playback) = self.playing.iter_mut().find(|playback| playback.handle == handle && playback.position.is_some()) {
+            playback.position = Some((x, y));
+            self.remix(handle);
+        }
+        Ok(())
+    }
+
+    /// Sets the position sounds are heard from, usually the player or camera
+    ///
+    /// # Notes
+    /// - Playing positional sounds are re-panned for the new listener position
+    pub fn set_listener(&mut self, x: i32, y: i32) {
+        self.listener = (x, y);
+        self.remix_all();
+    }
+
+    /// Position sounds are heard from
+    pub fn listener(&self) -> (i32, i32) {
+        self.listener
+    }
+
+    /// Sets the distance in cells at which positional sounds become silent
+    pub fn set_hearing_range(&mut self, cells: f32) {
+        self.hearing_range = cells.max(1.0);
+        self.remix_all();
+    }
+
+    /// Distance in cells at which positional sounds become silent
+    pub fn hearing_range(&self) -> f32 {
+        self.hearing_range
+    }
+
+    /// Computes `(pan, gain)` for a source position
+    ///
+    /// # Returns
+    /// * `pan` from `-1.0` (left speaker) to `1.0` (right speaker)
+    /// * `gain` from `0.0` (out of range) to `1.0` (at the listener), scaled by the master volume
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::audio::AudioEngine;
+    /// let mut audio = AudioEngine::new();
+    /// audio.set_listener(10, 10);
+    /// audio.set_hearing_range(20.0);
+    ///
+    /// let (pan, gain) = audio.mix_at(20, 10);
+    /// assert_eq!((pan, gain), (0.5, 0.5));
+    /// ```
+    pub fn mix_at(&self, x: i32, y: i32) -> (f32, f32) {
+        let dx = (x - self.listener.0) as f32;
+        let dy = (y - self.listener.1) as f32;
+        let distance = (dx * dx + dy * dy).sqrt();
+
+        let pan = (dx / self.hearing_range).clamp(-1.0, 1.0);
+        let gain = (1.0 - distance / self.hearing_range).clamp(0.0, 1.0);
+        (pan, gain * self.volume)
+    }
+
+    /// Starts a playback, making room for it when [`MAX_VOICES`] are playing
+    fn start(&mut self, name: &str, position: Option<(i32, i32)>, looping: bool) -> io::Result<SoundHandle> {
+        let sound = self.sound(name)?;
+        let (samples, duration, group) = (Arc::clone(&sound.samples), sound.format.duration(), sound.group);
+        if !self.muted && self.output.is_none() {
+            self.output = Some(open_output(Arc::clone(&self.mixer))?);
+        }
+
+        while self.playing.len() >= MAX_VOICES {
+            let oldest = self.playing.iter().find(|playback| !playback.looping).unwrap_or(&self.playing[0]).handle;
+            self.stop_playback(oldest);
+        }
+
+        self.next_handle += 1;
+        let handle = SoundHandle(self.next_handle);
+        let (gain, pan) = self.voice_mix(position, group);
+        if !self.muted {
+            self.mixer().play(handle, samples, gain, pan, looping);
+        }
+        self.playing.push(Playback {
+            handle,
+            name: name.to_string(),
+            resumed: Instant::now(),
+            offset: Duration::ZERO,
+            speed: 1.0,
+            duration,
+            looping,
+            position,
+            group,
+            fade: Fade::NONE,
+        });
+        Ok(handle)
+    }
+
+    /// Gain and pan of a playback, panned only when positional
+    fn voice_mix(&self, position: Option<(i32, i32)>, group: AudioGroup) -> (f32, Option<f32>) {
+        let group_gain = self.groups[group as usize].gain();
+        match position {
+            Some((x, y)) => {
+                let (pan, gain) = self.mix_at(x, y);
+                (gain * group_gain, Some(pan))
+            },
+            None => (self.volume * group_gain, None),
+        }
+    }
+
+    /// Applies the current volumes and listener to a playback
+    fn remix(&mut self, handle: SoundHandle) {
+        let Some(playback) = self.playing.iter().find(|playback| playback.handle == handle) else {
+            return;
+        };
+        let (gain, pan) = self.voice_mix(playback.position, playback.group);
+        let gain = gain * playback.fade.level;
+        self.mixer().set_mix(handle, gain, pan);
+    }
+
+    /// Applies the current volumes and listener to every playback
+    fn remix_all(&mut self) {
+        let handles: Vec<_> = self.playing.iter().map(|playback| playback.handle).collect();
+        for handle in handles {
+            self.remix(handle);
+        }
+    }
+
+    fn mixer(&self) -> std::sync::MutexGuard<'_, Mixer> {
+        self.mixer.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn sound(&self, name: &str) -> io::Result<&Sound> {
+        self.sounds.get(name).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotFound, format!("Sound `{}` is not loaded", name))
+        })
+    }
+
+    /// Stops every sound playing
+    pub fn stop(&mut self) {
+        self.mixer().stop_all();
+        self.finished.extend(self.playing.drain(..).map(|playback| (playback.handle, playback.name)));
+    }
+
+    /// Stops one playback, leaving other sounds playing
+    ///
+    /// # Notes
+    /// - It is reported as finished, does nothing when it already finished
+    pub fn stop_playback(&mut self, handle: SoundHandle) {
+        self.mixer().stop(handle);
+        if let Some(index) = self.playing.iter().position(|playback| playback.handle == handle) {
+            let playback = self.playing.remove(index);
+            self.finished.push((playback.handle, playback.name));
+        }
+    }
+
+    /// Number of sounds playing, at most [`MAX_VOICES`]
+    pub fn playing_count(&self) -> usize {
+        self.playing.iter().filter(|playback| playback.is_running()).count()
+    }
+
+    /// Returns whether a playback is still running
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::audio::AudioEngine;
+    /// # let mut audio = AudioEngine::new();
+    /// let roar = audio.play("roar").expect("Sound missing");
+    /// if audio.is_playing(roar) {
+    ///     // Hold the boss intro until the roar ends
+    /// }
+    /// ```
+    pub fn is_playing(&self, handle: SoundHandle) -> bool {
+        self.running_playback(handle).is_some()
+    }
+
+    /// Position reached in a running playback, `None` once it finished
+    ///
+    /// # Notes
+    /// - Advances faster or slower than real time after [`AudioEngine::set_speed`], and wraps around for loops
+    pub fn elapsed(&self, handle: SoundHandle) -> Option<Duration> {
+        self.running_playback(handle).map(Playback::progress)
+    }
+
+    /// Total length of a running playback's sound, `None` once it finished
+    pub fn duration(&self, handle: SoundHandle) -> Option<Duration> {
+        self.running_playback(handle).map(|playback| playback.duration)
+    }
+
+    /// Collects playbacks that ended since the last call
+    ///
+    /// # Returns
+    /// `(handle, sound name)` pairs, in the order they finished
+    ///
+    /// # Notes
+    /// - Called by the engine every frame to emit `EngineEvent::SoundFinished`
+    /// - Stopped sounds and sounds making room for newer ones count as finished
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::Duration;
+    /// use lonely_engine::audio::{tone, AudioEngine};
+    ///
+    /// let mut audio = AudioEngine::new();
+    /// audio.set_muted(true);
+    /// audio.preload_bytes("beep", tone(440.0, Duration::from_secs(5))).unwrap();
+    /// let first = audio.play("beep").unwrap();
+    /// let second = audio.play("beep").unwrap();
+    /// assert_eq!(audio.playing_count(), 2);
+    ///
+    /// audio.stop_playback(first);
+    /// assert_eq!(audio.poll_finished(), vec![(first, "beep".to_string())]);
+    /// assert!(audio.is_playing(second));
+    /// ```
+    pub fn poll_finished(&mut self) -> Vec<(SoundHandle, String)> {
+        let (running, ended) = std::mem::take(&mut self.playing).into_iter().partition(Playback::is_running);
+        self.playing = running;
+        self.finished.extend(ended.into_iter().map(|playback: Playback| (playback.handle, playback.name)));
+        std::mem::take(&mut self.finished)
+    }
+
+    fn running_playback(&self, handle: SoundHandle) -> Option<&Playback> {
+        self.playing.iter().find(|playback| playback.handle == handle && playback.is_running())
+    }
+}
+
+/// Checks for a RIFF/WAVE header and reads the format and data chunks
+fn parse_wav(bytes: &[u8]) -> io::Result<WavFormat> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Not a RIFF/WAVE file"));
+    }
+
+    let read_u16 = |offset: usize| bytes.get(offset..offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]]));
+    let read_u32 = |offset: usize| bytes.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+    let mut fmt = None;
+    let mut data = None;
+    let mut offset = 12;
+    while let (Some(id), Some(size)) = (bytes.get(offset..offset + 4), read_u32(offset + 4)) {
+        let body = offset + 8;
+        match id {
+            b"fmt " => fmt = (|| {
+                // WAVE_FORMAT_EXTENSIBLE keeps the actual encoding in its sub-format
+                let encoding = match read_u16(body)? {
+                    0xFFFE => read_u16(body + 24)?,
+                    encoding => encoding,
+                };
+                Some((encoding, read_u16(body + 2)?, read_u32(body + 4)?, read_u32(body + 8)?, read_u16(body + 14)?))
+            })(),
+            // Truncated files keep whatever samples are present
+            b"data" => data = Some(body..(body + size as usize).min(bytes.len())),
+            _ => {},
+        }
+        // Chunks are padded to an even size
+        offset = body + size as usize + (size as usize & 1);
+    }
+
+    match (fmt, data) {
+        (Some((encoding, channels, sample_rate, byte_rate, bits_per_sample)), Some(data)) if byte_rate > 0 => {
+            Ok(WavFormat { encoding, channels, sample_rate, bits_per_sample, byte_rate, data })
+        },
+        _ => Err(io::Error::new(io::ErrorKind::InvalidData, "WAV file is missing its fmt or data chunk")),
+    }
+}
+
+/// Converts the data chunk to 16-bit samples for mixing
+fn decode_samples(bytes: &[u8], format: &WavFormat) -> io::Result<Samples> {
+    let samples = &bytes[format.data.clone()];
+    let data: Vec<i16> = match (format.encoding, format.bits_per_sample) {
+        (1, 8) => samples.iter().map(|&sample| (i16::from(sample) - 128) << 8).collect(),
+        (1, 16) => samples.chunks_exact(2).map(|b| i16::from_le_bytes([b[0], b[1]])).collect(),
+        (1, 24) => samples.chunks_exact(3).map(|b| i16::from_le_bytes([b[1], b[2]])).collect(),
+        (1, 32) => samples.chunks_exact(4).map(|b| i16::from_le_bytes([b[2], b[3]])).collect(),
+        (3, 32) => samples.chunks_exact(4).map(|b| {
+            let sample = f32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+            (sample.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16
+        }).collect(),
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "WAV file is not 8, 16, 24, or 32-bit PCM")),
+    };
+    Ok(Samples::new(format.channels, format.sample_rate, data))
+}
+
+/// Header of a 16-bit PCM WAV file, ready for `data_size` bytes of samples
+fn wav_header(channels: u16, sample_rate: u32, data_size: usize) -> Vec<u8> {
+    let block_align = channels * 2;
+    let mut wav = Vec::with_capacity(44 + data_size);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_size as u32).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&channels.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * u32::from(block_align)).to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes()); // Bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&(data_size as u32).to_le_bytes());
+    wav
+}
@@ -0,0 +1,87 @@
+//! Per-object behaviors
+//!
+//! Lets simple entities carry their own per-frame logic instead of needing a
+//! separate [`Updatable`] that scans the object list for their index.
+//! Provides:
+//! - [`Behavior`] trait run by the engine for every object it is attached to
+//! - A blanket implementation for closures `|obj, delta_time, active_keys| commands`
+//!
+//! [`Updatable`]: crate::engine::Updatable
+
+use std::{collections::HashSet, fmt};
+use crate::{engine::EngineCommand, game_object::GameObject, input::Key};
+
+/// Logic attached directly to a [`GameObject`]
+///
+/// Behaviors receive mutable access to their own object, so movement and
+/// animation changes can be applied in place. Commands are still returned for
+/// anything affecting the rest of the world (spawning, quitting, ...).
+///
+/// # Example
+/// ```
+/// use std::collections::HashSet;
+/// use lonely_engine::{behavior::Behavior, engine::EngineCommand, game_object::GameObject, input::Key};
+///
+/// /// Drifts right one cell every half second
+/// #[derive(Clone)]
+/// struct Drift { timer: f32 }
+///
+/// impl Behavior for Drift {
+///     fn update(&mut self, obj: &mut GameObject, delta_time: f32, _keys: &HashSet<Key>) -> Vec<EngineCommand> {
+///         self.timer += delta_time;
+///         if self.timer >= 0.5 {
+///             self.timer = 0.0;
+///             obj.x += 1;
+///         }
+///         Vec::new()
+///     }
+/// }
+///
+/// let mut cloud = GameObject::new(0, 2, '~');
+/// cloud.add_behavior(Drift { timer: 0.0 });
+/// ```
+pub trait Behavior: BehaviorClone {
+    /// Called every frame for the object this behavior is attached to
+    ///
+    /// # Arguments
+    /// * `obj` - The owning object
+    /// * `delta_time` - Time since last update in seconds
+    /// * `active_keys` - Set of currently pressed keyboard keys
+    ///
+    /// # Returns
+    /// Vector of engine commands to be processed this frame
+    fn update(&mut self, obj: &mut GameObject, delta_time: f32, active_keys: &HashSet<Key>) -> Vec<EngineCommand>;
+}
+
+/// Cloning support for boxed behaviors, implemented automatically for `Clone` types
+pub trait BehaviorClone {
+    /// Clones the behavior into a new box
+    fn clone_box(&self) -> Box<dyn Behavior>;
+}
+
+impl<T: Behavior + Clone + 'static> BehaviorClone for T {
+    fn clone_box(&self) -> Box<dyn Behavior> {
+        Box::new(self.clone())
+    }
+}
+
+impl<F> Behavior for F
+where
+    F: FnMut(&mut GameObject, f32, &HashSet<Key>) -> Vec<EngineCommand> + Clone + 'static,
+{
+    fn update(&mut self, obj: &mut GameObject, delta_time: f32, active_keys: &HashSet<Key>) -> Vec<EngineCommand> {
+        self(obj, delta_time, active_keys)
+    }
+}
+
+impl Clone for Box<dyn Behavior> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl fmt::Debug for dyn Behavior {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Behavior")
+    }
+}
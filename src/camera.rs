@@ -0,0 +1,276 @@
+//! Camera moving the view over the world
+//!
+//! Provides:
+//! - Screen shake for impact feedback, leaving HUD overlays steady
+//! - Smooth following of a tagged object with a deadzone
+//! - Zooming out over a region by skipping cells
+//!
+//! The engine owns a [`Camera`], advances it every frame, and hands its
+//! [`View`] to the renderer. Objects, effects, and the background are drawn
+//! through the view, updatable overlays stay in screen coordinates.
+
+use crate::{game_object::GameObject, renderer::View, rng::Rng};
+
+/// Frame rate follow smoothing is defined against
+const SMOOTHING_RATE: f32 = 30.0;
+
+/// Active screen shake
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Shake {
+    intensity: f32,
+    duration: f32,
+    remaining: f32,
+}
+
+/// Object tracking settings
+#[derive(Debug, Clone, PartialEq)]
+struct Follow {
+    tag: String,
+    lerp: f32,
+}
+
+/// Position, shake, follow, and zoom of the view over the world
+///
+/// # Example
+/// ```
+/// use lonely_engine::{camera::Camera, game_object::GameObject};
+///
+/// let mut camera = Camera::new();
+/// camera.set_world_size(200, 50);
+/// camera.follow("player", 1.0);
+///
+/// let mut player = GameObject::new(120, 20, '@');
+/// player.tag = "player".to_string();
+/// camera.update(1.0 / 30.0, &[player], 80, 24);
+/// assert_eq!(camera.view().x, 80); // Player centered
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Camera {
+    /// World column at the left edge of the view
+    x: f32,
+    /// World row at the top edge of the view
+    y: f32,
+    zoom: usize,
+    shake: Option<Shake>,
+    /// Offset applied by the shake this frame
+    shake_offset: (i32, i32),
+    /// Whether [`Camera::shake`] does anything, off for players sensitive to motion
+    shake_enabled: bool,
+    follow: Option<Follow>,
+    /// Size of the area around the screen center the target moves freely in
+    deadzone: (usize, usize),
+    /// World size the view is kept inside, `None` for an unbounded world
+    world_size: Option<(usize, usize)>,
+    /// Shake offsets come from their own generator so the game's random sequence
+    /// does not depend on how many frames were rendered
+    rng: Rng,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Camera {
+    /// Creates a camera showing the world from (0, 0) without zoom
+    pub fn new() -> Self {
+        Self { x: 0.0, y: 0.0, zoom: 1, shake: None, shake_offset: (0, 0), shake_enabled: true, follow: None, deadzone: (0, 0), world_size: None, rng: Rng::new(0) }
+    }
+
+    /// Top-left world cell of the view, without shake
+    pub fn position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    /// Moves the view's top-left corner to a world position
+    pub fn set_position(&mut self, x: f32, y: f32) {
+        self.x = x;
+        self.y = y;
+    }
+
+    /// Centers the view on a world cell
+    ///
+    /// # Arguments
+    /// * `screen_width`, `screen_height` - Screen size in cells
+    pub fn look_at(&mut self, x: usize, y: usize, screen_width: usize, screen_height: usize) {
+        let (x, y) = self.centered_on(x as f32, y as f32, screen_width, screen_height);
+        self.x = x;
+        self.y = y;
+    }
+
+    /// Shakes the view, replacing a weaker shake in progress
+    ///
+    /// # Arguments
+    /// * `intensity` - Largest offset in cells at the start, fading to zero
+    /// * `duration` - Seconds the shake lasts
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::camera::Camera;
+    /// let mut camera = Camera::new();
+    /// camera.shake(2.0, 0.3); // Explosion
+    /// assert!(camera.is_shaking());
+    /// ```
+    pub fn shake(&mut self, intensity: f32, duration: f32) {
+        if !self.shake_enabled || duration <= 0.0 || intensity <= 0.0 {
+            return;
+        }
+        let current = self.shake.map_or(0.0, |shake| shake.intensity * shake.remaining / shake.duration);
+        if intensity >= current {
+            self.shake = Some(Shake { intensity, duration, remaining: duration });
+        }
+    }
+
+    /// Returns whether a shake is in progress
+    pub fn is_shaking(&self) -> bool {
+        self.shake.is_some()
+    }
+
+    /// Allows or ignores shakes, stopping one in progress when turned off
+    ///
+    /// # Notes
+    /// - The engine turns shakes off for players who chose reduced flashing
+    pub fn set_shake_enabled(&mut self, enabled: bool) {
+        self.shake_enabled = enabled;
+        if !enabled {
+            self.shake = None;
+            self.shake_offset = (0, 0);
+        }
+    }
+
+    /// Keeps the first object with a tag in view
+    ///
+    /// # Arguments
+    /// * `tag` - Tag of the object to follow
+    /// * `lerp` - Share of the distance to the target covered every 1/30 second,
+    ///   `1.0` snaps to the target
+    pub fn follow(&mut self, tag: &str, lerp: f32) {
+        self.follow = Some(Follow { tag: tag.to_string(), lerp: lerp.clamp(0.0, 1.0) });
+    }
+
+    /// Stops following, leaving the view where it is
+    pub fn stop_following(&mut self) {
+        self.follow = None;
+    }
+
+    /// Sets the area around the screen center the followed object moves in without moving the view
+    ///
+    /// # Arguments
+    /// * `width`, `height` - Deadzone size in screen cells, `(0, 0)` keeps the target centered
+    pub fn set_deadzone(&mut self, width: usize, height: usize) {
+        self.deadzone = (width, height);
+    }
+
+    /// Keeps the view inside a world of this size, also limiting object movement
+    pub fn set_world_size(&mut self, width: usize, height: usize) {
+        self.world_size = Some((width, height));
+    }
+
+    /// Size of the world, `None` when unbounded
+    pub fn world_size(&self) -> Option<(usize, usize)> {
+        self.world_size
+    }
+
+    /// Sets how many world cells each screen cell covers, `1` for no zoom
+    pub fn set_zoom(&mut self, zoom: usize) {
+        self.zoom = zoom.max(1);
+    }
+
+    /// World cells per screen cell
+    pub fn zoom(&self) -> usize {
+        self.zoom
+    }
+
+    /// Zooms out just enough to show a world region, centered on it
+    ///
+    /// # Arguments
+    /// * `x`, `y`, `width`, `height` - Region in world cells
+    /// * `screen_width`, `screen_height` - Screen size in cells
+    ///
+    /// # Notes
+    /// - Following is stopped so the region stays in view
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::camera::Camera;
+    /// let mut camera = Camera::new();
+    /// camera.zoom_to_region(0, 0, 160, 40, 80, 24); // Whole level overview
+    /// assert_eq!(camera.zoom(), 2);
+    /// ```
+    pub fn zoom_to_region(&mut self, x: usize, y: usize, width: usize, height: usize, screen_width: usize, screen_height: usize) {
+        let fit_x = width.div_ceil(screen_width.max(1));
+        let fit_y = height.div_ceil(screen_height.max(1));
+        self.zoom = fit_x.max(fit_y).max(1);
+        self.follow = None;
+        self.look_at(x + width / 2, y + height / 2, screen_width, screen_height);
+    }
+
+    /// Returns to 1:1 zoom
+    pub fn reset_zoom(&mut self) {
+        self.zoom = 1;
+    }
+
+    /// Advances the shake and moves towards the followed object
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds since the last update
+    /// * `objects` - World objects, searched for the followed tag
+    /// * `screen_width`, `screen_height` - Screen size in cells
+    pub fn update(&mut self, delta_time: f32, objects: &[GameObject], screen_width: usize, screen_height: usize) {
+        if let Some(follow) = &self.follow
+            && let Some(target) = objects.iter().find(|obj| obj.tag == follow.tag)
+        {
+            let (goal_x, goal_y) = self.follow_goal(target, screen_width, screen_height);
+            let blend = 1.0 - (1.0 - follow.lerp).powf(delta_time * SMOOTHING_RATE);
+            self.x += (goal_x - self.x) * blend;
+            self.y += (goal_y - self.y) * blend;
+        }
+        self.clamp_to_world(screen_width, screen_height);
+
+        self.shake_offset = (0, 0);
+        if let Some(shake) = self.shake.as_mut() {
+            shake.remaining -= delta_time;
+            if shake.remaining <= 0.0 {
+                self.shake = None;
+            } else {
+                let strength = shake.intensity * shake.remaining / shake.duration;
+                let rng = &mut self.rng;
+                let mut offset = || ((rng.next_f32() * 2.0 - 1.0) * strength).round() as i32;
+                self.shake_offset = (offset(), offset());
+            }
+        }
+    }
+
+    /// World to screen mapping for this frame, including shake
+    pub fn view(&self) -> View {
+        View { x: self.x.round() as i32 + self.shake_offset.0, y: self.y.round() as i32 + self.shake_offset.1, zoom: self.zoom }
+    }
+
+    /// Top-left corner putting a world position at the screen center
+    fn centered_on(&self, x: f32, y: f32, screen_width: usize, screen_height: usize) -> (f32, f32) {
+        let zoom = self.zoom as f32;
+        (x - (screen_width as f32 * zoom) / 2.0, y - (screen_height as f32 * zoom) / 2.0)
+    }
+
+    /// Position the view moves to so the target is back inside the deadzone
+    fn follow_goal(&self, target: &GameObject, screen_width: usize, screen_height: usize) -> (f32, f32) {
+        let (center_x, center_y) = self.centered_on(target.x as f32, target.y as f32, screen_width, screen_height);
+        let zoom = self.zoom as f32;
+        let (half_width, half_height) = (self.deadzone.0 as f32 * zoom / 2.0, self.deadzone.1 as f32 * zoom / 2.0);
+        (self.x.clamp(center_x - half_width, center_x + half_width), self.y.clamp(center_y - half_height, center_y + half_height))
+    }
+
+    /// Keeps the view inside the world size, centering worlds smaller than the screen
+    fn clamp_to_world(&mut self, screen_width: usize, screen_height: usize) {
+        let Some((world_width, world_height)) = self.world_size else {
+            return;
+        };
+        let clamp = |position: f32, world: usize, screen: usize| {
+            let visible = (screen * self.zoom) as f32;
+            if world as f32 <= visible { (world as f32 - visible) / 2.0 } else { position.clamp(0.0, world as f32 - visible) }
+        };
+        self.x = clamp(self.x, world_width, screen_width);
+        self.y = clamp(self.y, world_height, screen_height);
+    }
+}
@@ -0,0 +1,378 @@
+//! Terminal colors
+//!
+//! Contains the [`Color`] enum covering the terminal default, the 256-color
+//! palette, and 24-bit true color, with conversion to ANSI escape codes, RGB,
+//! and HSV. Colors blend with [`Color::lerp`], and a [`Gradient`] blends
+//! between any number of stops, such as a health bar going from green through
+//! yellow to red.
+
+use crate::terminal::ColorDepth;
+
+/// A terminal foreground or background color
+///
+/// # Example
+/// ```
+/// use lonely_engine::color::Color;
+///
+/// assert_eq!(Color::RED.fg_escape(), "\x1B[38;5;1m");
+/// assert_eq!(Color::Rgb(255, 128, 0).bg_escape(), "\x1B[48;2;255;128;0m");
+/// assert_eq!(Color::Indexed(196).to_rgb(), Some((255, 0, 0)));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Color {
+    /// The terminal's own foreground or background color
+    #[default]
+    Default,
+    /// Entry of the 256-color palette, 0-15 being the standard ANSI colors
+    Indexed(u8),
+    /// 24-bit true color
+    Rgb(u8, u8, u8),
+}
+
+/// RGB values of the 16 standard colors (xterm defaults)
+const ANSI_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0), (205, 0, 0), (0, 205, 0), (205, 205, 0),
+    (0, 0, 238), (205, 0, 205), (0, 205, 205), (229, 229, 229),
+    (127, 127, 127), (255, 0, 0), (0, 255, 0), (255, 255, 0),
+    (92, 92, 255), (255, 0, 255), (0, 255, 255), (255, 255, 255),
+];
+
+/// Channel levels of the 6x6x6 color cube in the 256-color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl Color {
+    /// Standard black
+    pub const BLACK: Color = Color::Indexed(0);
+    /// Standard red
+    pub const RED: Color = Color::Indexed(1);
+    /// Standard green
+    pub const GREEN: Color = Color::Indexed(2);
+    /// Standard yellow
+    pub const YELLOW: Color = Color::Indexed(3);
+    /// Standard blue
+    pub const BLUE: Color = Color::Indexed(4);
+    /// Standard magenta
+    pub const MAGENTA: Color = Color::Indexed(5);
+    /// Standard cyan
+    pub const CYAN: Color = Color::Indexed(6);
+    /// Standard white (light grey on most terminals)
+    pub const WHITE: Color = Color::Indexed(7);
+    /// Bright black (dark grey)
+    pub const GREY: Color = Color::Indexed(8);
+    /// Bright red
+    pub const BRIGHT_RED: Color = Color::Indexed(9);
+    /// Bright green
+    pub const BRIGHT_GREEN: Color = Color::Indexed(10);
+    /// Bright yellow
+    pub const BRIGHT_YELLOW: Color = Color::Indexed(11);
+    /// Bright blue
+    pub const BRIGHT_BLUE: Color = Color::Indexed(12);
+    /// Bright magenta
+    pub const BRIGHT_MAGENTA: Color = Color::Indexed(13);
+    /// Bright cyan
+    pub const BRIGHT_CYAN: Color = Color::Indexed(14);
+    /// Bright white
+    pub const BRIGHT_WHITE: Color = Color::Indexed(15);
+
+    /// Parses a color name, `#rrggbb` hex value, or 256-color palette index
+    ///
+    /// # Notes
+    /// - Names are the constants in snake case (`"bright_red"`), plus `"gray"` and `"default"`
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::color::Color;
+    ///
+    /// assert_eq!(Color::from_name("bright_yellow"), Some(Color::BRIGHT_YELLOW));
+    /// assert_eq!(Color::from_name("#ff8000"), Some(Color::Rgb(255, 128, 0)));
+    /// assert_eq!(Color::from_name("208"), Some(Color::Indexed(208)));
+    /// assert_eq!(Color::from_name("blurple"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Color> {
+        let name = name.trim().to_ascii_lowercase();
+        if let Some(hex) = name.strip_prefix('#') {
+            let channel = |at: usize| hex.get(at..at + 2).and_then(|digits| u8::from_str_radix(digits, 16).ok());
+            return match hex.len() {
+                6 => Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?)),
+                _ => None,
+            };
+        }
+        if let Ok(index) = name.parse() {
+            return Some(Color::Indexed(index));
+        }
+        let color = match name.as_str() {
+            "default" => Color::Default,
+            "black" => Color::BLACK,
+            "red" => Color::RED,
+            "green" => Color::GREEN,
+            "yellow" => Color::YELLOW,
+            "blue" => Color::BLUE,
+            "magenta" => Color::MAGENTA,
+            "cyan" => Color::CYAN,
+            "white" => Color::WHITE,
+            "grey" | "gray" => Color::GREY,
+            "bright_red" => Color::BRIGHT_RED,
+            "bright_green" => Color::BRIGHT_GREEN,
+            "bright_yellow" => Color::BRIGHT_YELLOW,
+            "bright_blue" => Color::BRIGHT_BLUE,
+            "bright_magenta" => Color::BRIGHT_MAGENTA,
+            "bright_cyan" => Color::BRIGHT_CYAN,
+            "bright_white" => Color::BRIGHT_WHITE,
+            _ => return None,
+        };
+        Some(color)
+    }
+
+    /// ANSI escape code selecting this color as the foreground, empty for `Default`
+    pub fn fg_escape(&self) -> String {
+        match self {
+            Color::Default => String::new(),
+            Color::Indexed(index) => format!("\x1B[38;5;{}m", index),
+            Color::Rgb(r, g, b) => format!("\x1B[38;2;{};{};{}m", r, g, b),
+        }
+    }
+
+    /// ANSI escape code selecting this color as the background, empty for `Default`
+    pub fn bg_escape(&self) -> String {
+        match self {
+            Color::Default => String::new(),
+            Color::Indexed(index) => format!("\x1B[48;5;{}m", index),
+            Color::Rgb(r, g, b) => format!("\x1B[48;2;{};{};{}m", r, g, b),
+        }
+    }
+
+    /// Approximate RGB value, `None` for the terminal default
+    pub fn to_rgb(&self) -> Option<(u8, u8, u8)> {
+        match *self {
+            Color::Default => None,
+            Color::Rgb(r, g, b) => Some((r, g, b)),
+            Color::Indexed(index @ 0..=15) => Some(ANSI_RGB[index as usize]),
+            Color::Indexed(index @ 16..=231) => {
+                let cube = index - 16;
+                Some((
+                    CUBE_LEVELS[(cube / 36) as usize],
+                    CUBE_LEVELS[(cube / 6 % 6) as usize],
+                    CUBE_LEVELS[(cube % 6) as usize],
+                ))
+            },
+            Color::Indexed(index) => {
+                let level = 8 + (index - 232) * 10;
+                Some((level, level, level))
+            },
+        }
+    }
+
+    /// Nearest color a terminal of some color depth can show
+    ///
+    /// # Notes
+    /// - `ColorDepth::None` gives `Default`, and `Default` always stays as is
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{color::Color, terminal::ColorDepth};
+    ///
+    /// let orange = Color::Rgb(255, 135, 0);
+    /// assert_eq!(orange.reduce(ColorDepth::TrueColor), orange);
+    /// assert_eq!(orange.reduce(ColorDepth::Indexed256), Color::Indexed(208));
+    /// assert_eq!(orange.reduce(ColorDepth::Ansi16), Color::YELLOW);
+    /// ```
+    pub fn reduce(&self, depth: ColorDepth) -> Color {
+        let Some((r, g, b)) = self.to_rgb() else {
+            return Color::Default;
+        };
+        match (depth, *self) {
+            (ColorDepth::None, _) => Color::Default,
+            (ColorDepth::TrueColor, color) | (_, color @ Color::Indexed(0..=15)) => color,
+            (ColorDepth::Indexed256, color @ Color::Indexed(_)) => color,
+            (ColorDepth::Indexed256, _) => {
+                // The closer of the color cube and the grey ramp
+                let level = |channel: u8| CUBE_LEVELS.iter().enumerate().min_by_key(|(_, level)| level.abs_diff(channel)).map_or(0, |(index, _)| index as u8);
+                let cube = Color::Indexed(16 + 36 * level(r) + 6 * level(g) + level(b));
+                let grey_step = ((u16::from(r) + u16::from(g) + u16::from(b)) / 3).saturating_sub(3) / 10;
+                let grey = Color::Indexed(232 + grey_step.min(23) as u8);
+                [cube, grey].into_iter().min_by_key(|color| color_distance(color.to_rgb().unwrap_or_default(), (r, g, b))).unwrap_or(cube)
+            },
+            (ColorDepth::Ansi16, _) => {
+                let nearest = (0..16u8).min_by_key(|&index| color_distance(ANSI_RGB[index as usize], (r, g, b))).unwrap_or(0);
+                Color::Indexed(nearest)
+            },
+        }
+    }
+
+    /// CSS hex notation (`#rrggbb`), `None` for the terminal default
+    pub fn to_hex(&self) -> Option<String> {
+        self.to_rgb().map(|(r, g, b)| format!("#{:02x}{:02x}{:02x}", r, g, b))
+    }
+
+    /// Blends two colors in RGB
+    ///
+    /// # Arguments
+    /// * `a` - Color at `t = 0.0`
+    /// * `b` - Color at `t = 1.0`
+    /// * `t` - Position between them, clamped to `0.0..=1.0`
+    ///
+    /// # Returns
+    /// A true color, or whichever of `a` and `b` is closer when either is the terminal default
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::color::Color;
+    ///
+    /// assert_eq!(Color::lerp(Color::Rgb(0, 0, 0), Color::Rgb(255, 100, 50), 0.5), Color::Rgb(128, 50, 25));
+    /// assert_eq!(Color::lerp(Color::RED, Color::Default, 0.2), Color::RED);
+    /// ```
+    pub fn lerp(a: Color, b: Color, t: f32) -> Color {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let (Some(from), Some(to)) = (a.to_rgb(), b.to_rgb()) else {
+            return if t < 0.5 { a } else { b };
+        };
+        let channel = |from: u8, to: u8| (f32::from(from) + (f32::from(to) - f32::from(from)) * t).round() as u8;
+        Color::Rgb(channel(from.0, to.0), channel(from.1, to.1), channel(from.2, to.2))
+    }
+
+    /// Creates a true color from hue, saturation, and value
+    ///
+    /// # Arguments
+    /// * `hue` - Degrees around the color wheel, red at `0.0`, green at `120.0`, wrapped
+    /// * `saturation` - `0.0` (grey) to `1.0` (pure color), clamped
+    /// * `value` - `0.0` (black) to `1.0` (full brightness), clamped
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::color::Color;
+    ///
+    /// assert_eq!(Color::from_hsv(120.0, 1.0, 1.0), Color::Rgb(0, 255, 0));
+    /// // Cycling the hue for a rainbow title
+    /// let rainbow: Vec<Color> = (0..6).map(|step| Color::from_hsv(step as f32 * 60.0, 1.0, 1.0)).collect();
+    /// assert_eq!(rainbow[1], Color::Rgb(255, 255, 0));
+    /// ```
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32) -> Color {
+        let hue = hue.rem_euclid(360.0) / 60.0;
+        let saturation = saturation.clamp(0.0, 1.0);
+        let value = value.clamp(0.0, 1.0);
+        let chroma = value * saturation;
+        let second = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let (r, g, b) = match hue as u32 {
+            0 => (chroma, second, 0.0),
+            1 => (second, chroma, 0.0),
+            2 => (0.0, chroma, second),
+            3 => (0.0, second, chroma),
+            4 => (second, 0.0, chroma),
+            _ => (chroma, 0.0, second),
+        };
+        let offset = value - chroma;
+        let channel = |channel: f32| ((channel + offset) * 255.0).round() as u8;
+        Color::Rgb(channel(r), channel(g), channel(b))
+    }
+
+    /// Hue in degrees, saturation, and value of the color, `None` for the terminal default
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::color::Color;
+    ///
+    /// assert_eq!(Color::Rgb(255, 0, 0).to_hsv(), Some((0.0, 1.0, 1.0)));
+    /// // Darkening the background at night by lowering its value
+    /// let (hue, saturation, value) = Color::Rgb(40, 160, 60).to_hsv().unwrap();
+    /// let night = Color::from_hsv(hue, saturation, value * 0.4);
+    /// ```
+    pub fn to_hsv(&self) -> Option<(f32, f32, f32)> {
+        let (r, g, b) = self.to_rgb()?;
+        let (r, g, b) = (f32::from(r) / 255.0, f32::from(g) / 255.0, f32::from(b) / 255.0);
+        let max = r.max(g).max(b);
+        let chroma = max - r.min(g).min(b);
+        let hue = if chroma == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / chroma + 2.0)
+        } else {
+            60.0 * ((r - g) / chroma + 4.0)
+        };
+        let saturation = if max == 0.0 { 0.0 } else { chroma / max };
+        Some((hue, saturation, max))
+    }
+}
+
+/// Colors blended between stops along `0.0..=1.0`
+///
+/// # Example
+/// ```
+/// use lonely_engine::color::{Color, Gradient};
+///
+/// let health = Gradient::new()
+///     .stop(0.0, Color::Rgb(255, 0, 0))
+///     .stop(0.5, Color::Rgb(255, 255, 0))
+///     .stop(1.0, Color::Rgb(0, 255, 0));
+/// assert_eq!(health.at(0.75), Color::Rgb(128, 255, 0));
+/// assert_eq!(health.at(2.0), Color::Rgb(0, 255, 0));
+///
+/// // Five steps of the same gradient, such as a particle's color over its life
+/// let ramp = health.ramp(5);
+/// assert_eq!(ramp[2], Color::Rgb(255, 255, 0));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gradient {
+    /// Positions and colors, sorted by position
+    stops: Vec<(f32, Color)>,
+}
+
+impl Gradient {
+    /// Creates a gradient without stops, which is the terminal default everywhere
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a gradient blending evenly through colors, such as a [`FloatingText`](crate::effects::FloatingText) ramp
+    pub fn even(colors: &[Color]) -> Self {
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+        colors.iter().enumerate().fold(Self::new(), |gradient, (index, &color)| gradient.stop(index as f32 / last, color))
+    }
+
+    /// Adds a color at a position, stops may be added in any order
+    pub fn stop(mut self, position: f32, color: Color) -> Self {
+        let at = self.stops.partition_point(|&(existing, _)| existing <= position);
+        self.stops.insert(at, (position, color));
+        self
+    }
+
+    /// Positions and colors of the stops, sorted by position
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+
+    /// Color at a position, the first or last stop's color outside them
+    pub fn at(&self, position: f32) -> Color {
+        let (Some(&(first, first_color)), Some(&(last, last_color))) = (self.stops.first(), self.stops.last()) else {
+            return Color::Default;
+        };
+        if position.is_nan() || position <= first {
+            return first_color;
+        }
+        if position >= last {
+            return last_color;
+        }
+        let after = self.stops.partition_point(|&(existing, _)| existing <= position);
+        let (from, from_color) = self.stops[after - 1];
+        let (to, to_color) = self.stops[after];
+        Color::lerp(from_color, to_color, (position - from) / (to - from))
+    }
+
+    /// Colors at evenly spaced positions from `0.0` to `1.0`
+    ///
+    /// # Arguments
+    /// * `steps` - Number of colors, one gives the color at `0.0`
+    pub fn ramp(&self, steps: usize) -> Vec<Color> {
+        let last = steps.saturating_sub(1).max(1) as f32;
+        (0..steps).map(|step| self.at(step as f32 / last)).collect()
+    }
+}
+
+/// Squared distance between two RGB colors
+fn color_distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let channel = |a: u8, b: u8| u32::from(a.abs_diff(b)).pow(2);
+    channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2)
+}
@@ -0,0 +1,457 @@
+//! Typed components attached to objects and queries over them
+//!
+//! [`GameObject`] has a fixed set of fields. Game-specific data such as a
+//! velocity, a health pool, or an AI state goes in [`Components`] instead,
+//! keyed by object index like object commands. The engine owns one, keeps it in
+//! step with despawns, and hands it to updatables as `UpdateContext::components`,
+//! so a system iterates every object with the components it needs:
+//!
+//! ```
+//! use lonely_engine::components::Components;
+//!
+//! #[derive(Clone)]
+//! struct Position(f32, f32);
+//! #[derive(Clone)]
+//! struct Velocity(f32, f32);
+//!
+//! // Body of an updatable, called with `ctx.components` and `ctx.delta_time`
+//! fn gravity(components: &mut Components, dt: f32) {
+//!     for (_, (position, velocity)) in components.query::<(&mut Position, &mut Velocity)>() {
+//!         velocity.1 += 9.8 * dt;
+//!         position.1 += velocity.1 * dt;
+//!     }
+//! }
+//!
+//! let mut components = Components::new();
+//! components.insert(0, Position(10.0, 2.0));
+//! components.insert(0, Velocity(0.0, 0.0));
+//! components.insert(1, Position(4.0, 4.0)); // No velocity, stays put
+//! for _ in 0..3 {
+//!     gravity(&mut components, 0.1);
+//! }
+//! assert!(components.get::<Position>(0).unwrap().1 > 2.0);
+//! assert_eq!(components.get::<Position>(1).unwrap().1, 4.0);
+//! ```
+//!
+//! Each component type is stored in a sparse set: the values sit packed in one
+//! vector, with a table from object index to their slot, so lookups are direct
+//! and a query walks the packed values of its rarest component without allocating.
+//!
+//! # Notes
+//! - Despawning an object drops its components and shifts the ones of later objects down, like the object list
+//! - Component types must be `Clone`, snapshots copy the whole store and restoring one puts it back
+//!
+//! [`GameObject`]: crate::game_object::GameObject
+
+use std::{any::{Any, TypeId, type_name}, collections::HashMap, fmt, marker::PhantomData};
+
+/// Components of one type, type-erased so the store can hold every type together
+trait Storage: Any {
+    /// Removes the component of an object and shifts later objects down by one
+    fn object_removed(&mut self, index: usize);
+
+    /// Copies the storage, for snapshots
+    fn clone_storage(&self) -> Box<dyn Storage>;
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// Packed components of one type with a table from object index to slot
+#[derive(Clone)]
+struct SparseSet<T> {
+    dense: Vec<T>,
+    /// Object index of each entry of `dense`
+    owners: Vec<usize>,
+    /// Slot in `dense` of each object index
+    sparse: Vec<Option<usize>>,
+}
+
+impl<T> SparseSet<T> {
+    fn new() -> Self {
+        Self { dense: Vec::new(), owners: Vec::new(), sparse: Vec::new() }
+    }
+
+    fn insert(&mut self, index: usize, value: T) -> Option<T> {
+        if let Some(slot) = self.slot(index) {
+            return Some(std::mem::replace(&mut self.dense[slot], value));
+        }
+        if self.sparse.len() <= index {
+            self.sparse.resize(index + 1, None);
+        }
+        self.sparse[index] = Some(self.dense.len());
+        self.dense.push(value);
+        self.owners.push(index);
+        None
+    }
+
+    fn slot(&self, index: usize) -> Option<usize> {
+        self.sparse.get(index).copied().flatten()
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        let slot = self.slot(index)?;
+        self.sparse[index] = None;
+        self.owners.swap_remove(slot);
+        // The last entry took the removed one's slot
+        if let Some(&moved) = self.owners.get(slot) {
+            self.sparse[moved] = Some(slot);
+        }
+        Some(self.dense.swap_remove(slot))
+    }
+}
+
+impl<T: Clone + 'static> Storage for SparseSet<T> {
+    fn object_removed(&mut self, index: usize) {
+        self.remove(index);
+        if index < self.sparse.len() {
+            self.sparse.remove(index);
+        }
+        for owner in self.owners.iter_mut().filter(|owner| **owner > index) {
+            *owner -= 1;
+        }
+    }
+
+    fn clone_storage(&self) -> Box<dyn Storage> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Components of every type, keyed by object index
+///
+/// # Example
+/// ```
+/// use lonely_engine::components::Components;
+///
+/// #[derive(Clone)]
+/// struct Health(i32);
+///
+/// let mut components = Components::new();
+/// components.insert(0, Health(10));
+/// components.insert(2, Health(4));
+/// components.get_mut::<Health>(2).unwrap().0 -= 1;
+///
+/// // Object 0 despawned, object 2 becomes object 1
+/// components.object_removed(0);
+/// assert_eq!(components.get::<Health>(1).map(|health| health.0), Some(3));
+/// assert_eq!(components.count::<Health>(), 1);
+/// ```
+#[derive(Default)]
+pub struct Components {
+    /// Storages with the name of their component type, for debug output
+    storages: HashMap<TypeId, (&'static str, Box<dyn Storage>)>,
+}
+
+impl fmt::Debug for Components {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<&str> = self.storages.values().map(|(name, _)| *name).collect();
+        names.sort_unstable();
+        f.debug_set().entries(names).finish()
+    }
+}
+
+impl Clone for Components {
+    fn clone(&self) -> Self {
+        let storages = self.storages.iter().map(|(id, (name, storage))| (*id, (*name, storage.clone_storage()))).collect();
+        Self { storages }
+    }
+}
+
+impl Components {
+    /// Creates an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attaches a component to an object, replacing its component of the same type
+    ///
+    /// # Returns
+    /// The replaced component, if there was one
+    pub fn insert<T: Clone + 'static>(&mut self, index: usize, value: T) -> Option<T> {
+        let (_, storage) = self.storages.entry(TypeId::of::<T>()).or_insert_with(|| (type_name::<T>(), Box::new(SparseSet::<T>::new())));
+        storage.as_any_mut().downcast_mut::<SparseSet<T>>()?.insert(index, value)
+    }
+
+    /// Component of a type attached to an object
+    pub fn get<T: 'static>(&self, index: usize) -> Option<&T> {
+        let set = self.set::<T>()?;
+        set.slot(index).map(|slot| &set.dense[slot])
+    }
+
+    /// Mutable component of a type attached to an object
+    pub fn get_mut<T: 'static>(&mut self, index: usize) -> Option<&mut T> {
+        let set = self.set_mut::<T>()?;
+        set.slot(index).map(|slot| &mut set.dense[slot])
+    }
+
+    /// Returns whether an object has a component of a type
+    pub fn has<T: 'static>(&self, index: usize) -> bool {
+        self.get::<T>(index).is_some()
+    }
+
+    /// Detaches a component from an object
+    pub fn remove<T: 'static>(&mut self, index: usize) -> Option<T> {
+        self.set_mut::<T>()?.remove(index)
+    }
+
+    /// Number of objects with a component of a type
+    pub fn count<T: 'static>(&self) -> usize {
+        self.set::<T>().map_or(0, |set| set.dense.len())
+    }
+
+    /// Removes every component of every object
+    pub fn clear(&mut self) {
+        self.storages.clear();
+    }
+
+    /// Drops the components of a removed object and shifts later objects down by one
+    ///
+    /// # Notes
+    /// - The engine calls this on despawn, call it when managing a store alongside another object list
+    pub fn object_removed(&mut self, index: usize) {
+        for (_, storage) in self.storages.values_mut() {
+            storage.object_removed(index);
+        }
+    }
+
+    /// Iterates objects having every component of a query
+    ///
+    /// # Arguments
+    /// * `Q` - `&T` or `&mut T` for one component, or a tuple of up to four of them
+    ///
+    /// # Returns
+    /// Object index with the components, in the storage order of the rarest component
+    ///
+    /// # Notes
+    /// - Panics when a component type appears twice in the query
+    /// - Nothing is allocated, the other components are looked up by object index as the iterator advances
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::components::Components;
+    ///
+    /// #[derive(Clone)]
+    /// struct Position(i32, i32);
+    /// #[derive(Clone)]
+    /// struct Velocity(i32, i32);
+    ///
+    /// let mut components = Components::new();
+    /// components.insert(0, Position(0, 0));
+    /// components.insert(0, Velocity(1, 2));
+    /// components.insert(1, Position(5, 5)); // No velocity, left out of the first query
+    ///
+    /// for (_, (position, velocity)) in components.query::<(&mut Position, &Velocity)>() {
+    ///     position.0 += velocity.0;
+    ///     position.1 += velocity.1;
+    /// }
+    /// let positions: Vec<(usize, i32, i32)> = components.query::<&Position>().map(|(index, p)| (index, p.0, p.1)).collect();
+    /// assert_eq!(positions, vec![(0, 1, 2), (1, 5, 5)]);
+    /// ```
+    pub fn query<Q: Query>(&mut self) -> QueryIter<'_, Q> {
+        let columns = Q::columns(self);
+        let (owners, len) = columns.as_ref().map_or((std::ptr::null(), 0), Q::driver);
+        QueryIter { columns, owners, len, next: 0, _components: PhantomData }
+    }
+
+    fn set<T: 'static>(&self) -> Option<&SparseSet<T>> {
+        self.storages.get(&TypeId::of::<T>())?.1.as_any().downcast_ref()
+    }
+
+    fn set_mut<T: 'static>(&mut self) -> Option<&mut SparseSet<T>> {
+        self.storages.get_mut(&TypeId::of::<T>())?.1.as_any_mut().downcast_mut()
+    }
+}
+
+/// Raw view of one storage held by a running query
+///
+/// # Notes
+/// - Built from an exclusive borrow of the storage that [`QueryIter`] keeps for its lifetime
+#[doc(hidden)]
+pub struct Column<T> {
+    dense: *mut T,
+    owners: *const usize,
+    len: usize,
+    sparse: *const Option<usize>,
+    sparse_len: usize,
+}
+
+impl<T: 'static> Column<T> {
+    fn new(storage: &mut dyn Any) -> Option<Self> {
+        let set = storage.downcast_mut::<SparseSet<T>>()?;
+        Some(Self {
+            dense: set.dense.as_mut_ptr(),
+            owners: set.owners.as_ptr(),
+            len: set.owners.len(),
+            sparse: set.sparse.as_ptr(),
+            sparse_len: set.sparse.len(),
+        })
+    }
+
+    /// Slot of an object in `dense`
+    ///
+    /// # Safety
+    /// The storage the column was built from must still be borrowed by the query
+    unsafe fn slot(&self, index: usize) -> Option<usize> {
+        if index < self.sparse_len {
+            // SAFETY: in bounds, and the table is not modified while the query borrows it
+            unsafe { *self.sparse.add(index) }
+        } else {
+            None
+        }
+    }
+}
+
+/// One component of a query, borrowed shared with `&T` or exclusively with `&mut T`
+pub trait Fetch {
+    /// Type of the component
+    type Component: 'static;
+    /// What the query yields for the component
+    type Item<'a>;
+
+    /// Component of an object in a column
+    ///
+    /// # Safety
+    /// Each object index must be fetched at most once per query, so exclusive borrows never overlap
+    #[doc(hidden)]
+    unsafe fn fetch<'a>(column: &Column<Self::Component>, index: usize) -> Option<Self::Item<'a>>;
+}
+
+impl<T: 'static> Fetch for &T {
+    type Component = T;
+    type Item<'a> = &'a T;
+
+    unsafe fn fetch<'a>(column: &Column<T>, index: usize) -> Option<&'a T> {
+        // SAFETY: the slot is in bounds of `dense`, which the query borrows for 'a
+        unsafe { column.slot(index).map(|slot| &*column.dense.add(slot)) }
+    }
+}
+
+impl<T: 'static> Fetch for &mut T {
+    type Component = T;
+    type Item<'a> = &'a mut T;
+
+    unsafe fn fetch<'a>(column: &Column<T>, index: usize) -> Option<&'a mut T> {
+        // SAFETY: as for `&T`, and the caller yields each object once so the borrow is unique
+        unsafe { column.slot(index).map(|slot| &mut *column.dense.add(slot)) }
+    }
+}
+
+/// Components asked for by [`Components::query`], one [`Fetch`] or a tuple of up to four
+pub trait Query {
+    /// What the query yields for each object
+    type Item<'a>;
+    /// Columns of the queried storages
+    #[doc(hidden)]
+    type Columns;
+
+    /// Views the storages of every queried component, `None` when one has never been inserted
+    #[doc(hidden)]
+    fn columns(components: &mut Components) -> Option<Self::Columns>;
+
+    /// Owners of the shortest column, which the query walks
+    #[doc(hidden)]
+    fn driver(columns: &Self::Columns) -> (*const usize, usize);
+
+    /// Components of an object, `None` when it lacks one of them
+    ///
+    /// # Safety
+    /// Same contract as [`Fetch::fetch`]
+    #[doc(hidden)]
+    unsafe fn fetch<'a>(columns: &Self::Columns, index: usize) -> Option<Self::Item<'a>>;
+}
+
+impl<F: Fetch> Query for F {
+    type Item<'a> = F::Item<'a>;
+    type Columns = Column<F::Component>;
+
+    fn columns(components: &mut Components) -> Option<Self::Columns> {
+        let (_, storage) = components.storages.get_mut(&TypeId::of::<F::Component>())?;
+        Column::new(storage.as_any_mut())
+    }
+
+    fn driver(column: &Self::Columns) -> (*const usize, usize) {
+        (column.owners, column.len)
+    }
+
+    unsafe fn fetch<'a>(column: &Self::Columns, index: usize) -> Option<F::Item<'a>> {
+        // SAFETY: forwarded contract
+        unsafe { F::fetch(column, index) }
+    }
+}
+
+macro_rules! tuple_query {
+    ($(($fetch:ident, $column:ident)),+) => {
+        impl<$($fetch: Fetch),+> Query for ($($fetch,)+) {
+            type Item<'a> = ($($fetch::Item<'a>,)+);
+            type Columns = ($(Column<$fetch::Component>,)+);
+
+            fn columns(components: &mut Components) -> Option<Self::Columns> {
+                let types = [$(TypeId::of::<$fetch::Component>()),+];
+                assert!(types.iter().enumerate().all(|(i, id)| !types[..i].contains(id)), "component type queried twice");
+                let [$($column),+] = components.storages.get_disjoint_mut(types.each_ref());
+                Some(($(Column::<$fetch::Component>::new($column?.1.as_any_mut())?,)+))
+            }
+
+            fn driver(($($column,)+): &Self::Columns) -> (*const usize, usize) {
+                let mut driver = (std::ptr::null(), usize::MAX);
+                $(
+                    if $column.len < driver.1 {
+                        driver = ($column.owners, $column.len);
+                    }
+                )+
+                driver
+            }
+
+            unsafe fn fetch<'a>(($($column,)+): &Self::Columns, index: usize) -> Option<Self::Item<'a>> {
+                // SAFETY: forwarded contract, the columns belong to distinct storages
+                unsafe { Some(($($fetch::fetch($column, index)?,)+)) }
+            }
+        }
+    };
+}
+
+tuple_query!((A, a));
+tuple_query!((A, a), (B, b));
+tuple_query!((A, a), (B, b), (C, c));
+tuple_query!((A, a), (B, b), (C, c), (D, d));
+
+/// Iterator returned by [`Components::query`]
+pub struct QueryIter<'a, Q: Query> {
+    columns: Option<Q::Columns>,
+    /// Owners of the column being walked
+    owners: *const usize,
+    len: usize,
+    next: usize,
+    _components: PhantomData<&'a mut Components>,
+}
+
+impl<'a, Q: Query> Iterator for QueryIter<'a, Q> {
+    type Item = (usize, Q::Item<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let columns = self.columns.as_ref()?;
+        while self.next < self.len {
+            // SAFETY: `next` is in bounds of the owners, which stay borrowed with the store
+            let index = unsafe { *self.owners.add(self.next) };
+            self.next += 1;
+            // SAFETY: owners are distinct, so each object is fetched once
+            if let Some(item) = unsafe { Q::fetch(columns, index) } {
+                return Some((index, item));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.len - self.next))
+    }
+}
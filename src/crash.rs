@@ -0,0 +1,209 @@
+//! Crash reports written when the game panics
+//!
+//! "It crashed" is all a player can usually say about a panic. With crash
+//! reports turned on through `Engine::set_crash_reports`, the engine writes a
+//! timestamped text file while it unwinds holding the panic message and
+//! backtrace, the frame, turn, and seed, the latest events and commands, and a
+//! dump of every object, and tells the player where to find it.
+//!
+//! Independently of reports, the panic hook installed by the engine restores
+//! the terminal before the panic message is printed, so the message is not
+//! lost behind a cleared screen and a hidden cursor.
+//!
+//! # Example
+//! ```no_run
+//! use lonely_engine::engine::Engine;
+//!
+//! let mut engine = Engine::new(80, 24);
+//! engine.set_crash_reports(Some("crash_reports".into()));
+//! engine.run();
+//! ```
+
+use std::{
+    backtrace::Backtrace,
+    fmt::Write as _,
+    fs,
+    io::{self, Write},
+    panic,
+    path::{Path, PathBuf},
+    sync::{Mutex, Once, atomic::{AtomicBool, Ordering}},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use crate::game_object::GameObject;
+
+/// Commands kept for crash reports
+pub const DEFAULT_COMMAND_HISTORY: usize = 64;
+
+/// Whether the engine switched the terminal to game mode and has not restored it
+static TERMINAL_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Details of the latest panic, taken by the crash report
+static LAST_PANIC: Mutex<Option<PanicDetails>> = Mutex::new(None);
+
+static HOOK: Once = Once::new();
+
+/// What the panic hook captured
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicDetails {
+    /// Message the panic was raised with
+    pub message: String,
+    /// File, line, and column of the panic
+    pub location: Option<String>,
+    /// Name of the panicking thread
+    pub thread: String,
+    /// Backtrace of the panicking thread, captured whatever `RUST_BACKTRACE` says
+    pub backtrace: String,
+}
+
+/// Installs the engine's panic hook once, keeping the previous hook to print the message
+///
+/// # Notes
+/// - The hook restores the terminal, records [`PanicDetails`] for the crash report, then runs the previous hook
+/// - Called by the engine when it takes over the terminal and when crash reports are turned on
+pub fn install_panic_hook() {
+    HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore_terminal();
+            let message = match (info.payload().downcast_ref::<&str>(), info.payload().downcast_ref::<String>()) {
+                (Some(message), _) => message.to_string(),
+                (_, Some(message)) => message.clone(),
+                _ => "panic without a message".to_string(),
+            };
+            let details = PanicDetails {
+                message,
+                location: info.location().map(|location| location.to_string()),
+                thread: std::thread::current().name().unwrap_or("unnamed").to_string(),
+                backtrace: Backtrace::force_capture().to_string(),
+            };
+            if let Ok(mut last) = LAST_PANIC.lock() {
+                *last = Some(details);
+            }
+            previous(info);
+        }));
+    });
+}
+
+/// Takes the details of the latest panic, `None` when nothing panicked since the last call
+pub fn take_panic() -> Option<PanicDetails> {
+    LAST_PANIC.lock().ok().and_then(|mut last| last.take())
+}
+
+/// Marks the terminal as switched to game mode, so a panic restores it
+pub fn set_terminal_active(active: bool) {
+    TERMINAL_ACTIVE.store(active, Ordering::SeqCst);
+}
+
+/// Clears the screen and shows the cursor again if the engine had taken over the terminal
+fn restore_terminal() {
+    if TERMINAL_ACTIVE.swap(false, Ordering::SeqCst) {
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(b"\x1B[0m\x1B[2J\x1B[H\x1B[?25h");
+        let _ = stdout.flush();
+    }
+}
+
+/// Everything known about the game when it crashed
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CrashReport {
+    /// What went wrong, `None` for reports taken without a panic
+    pub panic: Option<PanicDetails>,
+    /// Frame the engine was in
+    pub frame: u64,
+    /// Turn counter
+    pub turn: u64,
+    /// Seed of the engine random number generator
+    pub seed: u64,
+    /// Loaded level
+    pub level: Option<String>,
+    /// Latest events, oldest first, as written by the event history
+    pub events: Vec<String>,
+    /// Latest commands applied, oldest first
+    pub commands: Vec<String>,
+    /// One line per object, see [`describe_object`]
+    pub world: Vec<String>,
+}
+
+impl CrashReport {
+    /// Formats the report as plain text with one section per part
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        let _ = writeln!(text, "lonely_engine {} crash report", env!("CARGO_PKG_VERSION"));
+        match &self.panic {
+            Some(panic) => {
+                let _ = writeln!(text, "panic: {}", panic.message);
+                let _ = writeln!(text, "at: {}", panic.location.as_deref().unwrap_or("unknown location"));
+                let _ = writeln!(text, "thread: {}", panic.thread);
+            },
+            None => text.push_str("panic: none, report taken on request\n"),
+        }
+        let _ = writeln!(text, "frame: {}\nturn: {}\nseed: {}", self.frame, self.turn, self.seed);
+        let _ = writeln!(text, "level: {}", self.level.as_deref().unwrap_or("none"));
+
+        let mut section = |title: &str, lines: &[String]| {
+            let _ = writeln!(text, "\n== {title} ({}) ==", lines.len());
+            for line in lines {
+                let _ = writeln!(text, "{line}");
+            }
+        };
+        section("events", &self.events);
+        section("commands", &self.commands);
+        section("objects", &self.world);
+        if let Some(panic) = &self.panic {
+            let _ = write!(text, "\n== backtrace ==\n{}\n", panic.backtrace.trim_end());
+        }
+        text
+    }
+
+    /// Writes the report to a new timestamped file in a directory, creating the directory
+    ///
+    /// # Returns
+    /// Path of the written file
+    pub fn write(&self, dir: impl AsRef<Path>) -> io::Result<PathBuf> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+        let path = dir.join(format!("crash-{seconds}-{}.txt", self.frame));
+        fs::write(&path, self.to_text())?;
+        Ok(path)
+    }
+}
+
+/// One line describing an object for the world dump
+///
+/// # Example
+/// ```
+/// use lonely_engine::{crash::describe_object, game_object::GameObject};
+///
+/// let player = GameObject::new(3, 4, '@').with_group("player");
+/// assert_eq!(describe_object(0, &player), "#0 '@' at (3, 4) groups [player] priority 0");
+/// ```
+pub fn describe_object(index: usize, obj: &GameObject) -> String {
+    let mut line = format!("#{index} '{}' at ({}, {})", obj.character, obj.x, obj.y);
+    if !obj.tag.is_empty() {
+        let _ = write!(line, " tag {:?}", obj.tag);
+    }
+    if !obj.groups.is_empty() {
+        let _ = write!(line, " groups [{}]", obj.groups.join(", "));
+    }
+    let _ = write!(line, " priority {}", obj.priority);
+    if !obj.active {
+        line.push_str(" inactive");
+    }
+    if !obj.visible {
+        line.push_str(" hidden");
+    }
+    if let Some(lifetime) = obj.lifetime {
+        let _ = write!(line, " lifetime {lifetime:.2}");
+    }
+    if obj.frame_count() > 1 {
+        let _ = write!(line, " frame {}/{}", obj.current_frame, obj.frame_count());
+    }
+    if !obj.behaviors.is_empty() {
+        let _ = write!(line, " behaviors {}", obj.behaviors.len());
+    }
+    if let Some(id) = obj.net_id {
+        let _ = write!(line, " net {id}");
+    }
+    line
+}
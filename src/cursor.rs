@@ -0,0 +1,154 @@
+//! Mouse cursor drawing and hit-testing
+//!
+//! Terminals do not draw a pointer in the game's cells, so the engine draws a
+//! [`Cursor`] at the mouse cell on top of everything once one is set with
+//! [`Engine::set_cursor`]. The engine also tracks the object under the mouse
+//! and emits `EngineEvent::ObjectHovered` and `EngineEvent::ObjectUnhovered`.
+//!
+//! [`Hitbox`] answers "is the mouse over this button and was it clicked" for
+//! UI drawn by updatables.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{color::Color, cursor::Cursor, engine::Engine, style::Style};
+//!
+//! let mut engine = Engine::new(80, 24);
+//! engine.set_cursor(Some(Cursor::glyph('+').style(Style::new().fg(Color::BRIGHT_YELLOW))));
+//! ```
+//!
+//! [`Engine::set_cursor`]: crate::engine::Engine::set_cursor
+
+use crate::{color::Color, input::{InputEvent, MouseAction, MouseButton}, renderer::{Cell, Renderer}, style::Style};
+
+/// How the mouse cell is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    /// Character drawn at the mouse cell, `None` keeps the cell's own character
+    glyph: Option<char>,
+    /// Style of the glyph, or attributes added to the cell when there is no glyph
+    style: Style,
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Self::highlight()
+    }
+}
+
+impl Cursor {
+    /// Draws a character at the mouse cell, bright white by default
+    pub fn glyph(glyph: char) -> Self {
+        Self { glyph: Some(glyph), style: Style::new().fg(Color::BRIGHT_WHITE).bold() }
+    }
+
+    /// Shows the mouse cell in reverse video, keeping its character
+    pub fn highlight() -> Self {
+        Self { glyph: None, style: Style::new().reverse() }
+    }
+
+    /// Sets the glyph's colors, or the attributes and colors applied to a highlighted cell
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Draws the cursor at a screen cell
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{cursor::Cursor, renderer::Renderer};
+    ///
+    /// let mut renderer = Renderer::new(10, 5);
+    /// renderer.draw_text(2, 1, "@");
+    /// Cursor::highlight().draw(&mut renderer, 2, 1);
+    /// assert!(renderer.cell(2, 1).unwrap().attrs.reverse);
+    /// ```
+    pub fn draw(&self, renderer: &mut Renderer, x: usize, y: usize) {
+        let Some(&under) = renderer.cell(x, y) else {
+            return;
+        };
+        let cell = match self.glyph {
+            Some(ch) => Cell {
+                ch,
+                fg: self.style.fg_color,
+                bg: if self.style.bg_color == Color::Default { under.bg } else { self.style.bg_color },
+                attrs: self.style.attributes,
+            },
+            None => {
+                let mut cell = under;
+                let attrs = self.style.attributes;
+                cell.attrs.bold |= attrs.bold;
+                cell.attrs.dim |= attrs.dim;
+                cell.attrs.italic |= attrs.italic;
+                cell.attrs.underline |= attrs.underline;
+                cell.attrs.blink |= attrs.blink;
+                cell.attrs.reverse ^= attrs.reverse;
+                if self.style.fg_color != Color::Default {
+                    cell.fg = self.style.fg_color;
+                }
+                if self.style.bg_color != Color::Default {
+                    cell.bg = self.style.bg_color;
+                }
+                cell
+            },
+        };
+        renderer.set_cell(x, y, cell);
+    }
+}
+
+/// Screen rectangle of a UI element, for mouse hit-testing
+///
+/// # Example
+/// ```
+/// use lonely_engine::{cursor::Hitbox, input::{InputEvent, MouseAction, MouseButton}};
+///
+/// let button = Hitbox::new(10, 4, 8, 1);
+/// assert!(button.is_hovered(Some((12, 4))));
+///
+/// let click = InputEvent::Mouse { x: 17, y: 4, action: MouseAction::Press(MouseButton::Left) };
+/// assert_eq!(button.clicked(&[click]), Some(MouseButton::Left));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hitbox {
+    /// Left column
+    pub x: usize,
+    /// Top row
+    pub y: usize,
+    /// Width in cells
+    pub width: usize,
+    /// Height in cells
+    pub height: usize,
+}
+
+impl Hitbox {
+    /// Creates a hitbox from its top-left cell and size
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self { x, y, width, height }
+    }
+
+    /// Returns whether a screen cell lies inside
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
+
+    /// Returns whether the mouse is over the hitbox, given the engine's mouse position
+    pub fn is_hovered(&self, mouse: Option<(usize, usize)>) -> bool {
+        mouse.is_some_and(|(x, y)| self.contains(x, y))
+    }
+
+    /// Button pressed inside the hitbox among this frame's input events
+    pub fn clicked(&self, events: &[InputEvent]) -> Option<MouseButton> {
+        events.iter().find_map(|event| match event {
+            InputEvent::Mouse { x, y, action: MouseAction::Press(button) } if self.contains(*x, *y) => Some(*button),
+            _ => None,
+        })
+    }
+
+    /// Scroll notches turned inside the hitbox among this frame's input events, positive away from the user
+    pub fn scrolled(&self, events: &[InputEvent]) -> i32 {
+        events.iter().map(|event| match event {
+            InputEvent::Mouse { x, y, action: MouseAction::Scroll(notches) } if self.contains(*x, *y) => *notches,
+            _ => 0,
+        }).sum()
+    }
+}
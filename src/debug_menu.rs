@@ -0,0 +1,320 @@
+//! In-game cheat menu for playtesting
+//!
+//! Available with the `debug_menu` feature, so release builds leave it out.
+//! A [`DebugMenu`] is an updatable opened with a key, backquote by default,
+//! listing:
+//! - the registered prefabs, spawned at the mouse cell
+//! - a teleport moving the player to the mouse cell
+//! - the registered flags, toggled as stat flags such as `god_mode`
+//! - the time scale, stepped with Left and Right
+//!
+//! While open it pushes the [`CONSOLE`] input context, so the game does not
+//! react to the keys used in the menu. Without a mouse, prefabs spawn on the
+//! player and the teleport does nothing.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{debug_menu::DebugMenu, engine::Engine, game_object::GameObject};
+//!
+//! let mut engine = Engine::new(80, 24);
+//! engine.add_updatable(
+//!     DebugMenu::new()
+//!         .prefab("goblin", || GameObject::new(0, 0, 'g'))
+//!         .prefab("chest", || GameObject::new(0, 0, '='))
+//!         .flag("god_mode")
+//!         .flag("no_clip"),
+//! );
+//! ```
+//!
+//! Game code reads a flag from the engine's stats:
+//! ```
+//! # use lonely_engine::engine::Engine;
+//! # let engine = Engine::new(80, 24);
+//! let damage = if engine.stats.flag("god_mode") { 0 } else { 5 };
+//! # assert_eq!(damage, 5);
+//! ```
+
+use crate::{
+    color::Color,
+    engine::{EngineCommand, Updatable, UpdateContext},
+    game_object::GameObject,
+    input::{InputEvent, Key},
+    input_context::{CONSOLE, GLOBAL, InputContext},
+    renderer::Renderer,
+    style::Style,
+};
+
+/// Creates the object a prefab spawns, its position is replaced by the spawn cell
+pub type Prefab = fn() -> GameObject;
+
+/// Time scales stepped through by the menu
+pub const TIME_SCALES: [f32; 6] = [0.0, 0.25, 0.5, 1.0, 2.0, 4.0];
+
+/// Something picked in the debug menu
+#[derive(Debug, Clone, PartialEq)]
+pub enum DebugAction {
+    /// The menu was opened
+    Open,
+    /// The menu was closed
+    Close,
+    /// Spawn a registered prefab by name
+    Spawn(String),
+    /// Move the player to the mouse cell
+    Teleport,
+    /// Set a flag on or off
+    SetFlag(String, bool),
+    /// Run game time at a scale
+    SetTimeScale(f32),
+}
+
+/// Row of the open menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Entry {
+    Spawn(usize),
+    Teleport,
+    Flag(usize),
+    TimeScale,
+}
+
+/// Cheat menu with prefab spawning, teleporting, flag toggles, and time-scale control
+///
+/// # Controls
+/// - The toggle key opens and closes the menu, Escape closes it too
+/// - Up and Down move the cursor
+/// - Enter or Space spawns, teleports, or toggles the selected row
+/// - Left and Right change the time scale
+///
+/// # Example
+/// ```
+/// use lonely_engine::{
+///     debug_menu::{DebugAction, DebugMenu},
+///     engine::EngineCommand,
+///     game_object::GameObject,
+///     input::{InputEvent, Key},
+/// };
+///
+/// let mut menu = DebugMenu::new().prefab("slime", || GameObject::new(0, 0, 's')).flag("god_mode");
+/// assert_eq!(menu.handle(&[InputEvent::KeyDown(Key::Char('`'))]), Some(DebugAction::Open));
+/// assert_eq!(menu.handle(&[InputEvent::KeyDown(Key::Enter)]), Some(DebugAction::Spawn("slime".into())));
+///
+/// let commands = menu.commands(&DebugAction::Spawn("slime".into()), Some((12, 4)), &[]);
+/// assert!(matches!(&commands[..], [EngineCommand::SpawnObject(slime)] if (slime.x, slime.y) == (12, 4)));
+///
+/// // Past the teleport row to the flag
+/// let keys = [InputEvent::KeyDown(Key::Down), InputEvent::KeyDown(Key::Down)];
+/// menu.handle(&keys);
+/// assert_eq!(menu.handle(&[InputEvent::KeyDown(Key::Enter)]), Some(DebugAction::SetFlag("god_mode".into(), true)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DebugMenu {
+    prefabs: Vec<(String, Prefab)>,
+    /// Registered flags with the state the menu last set
+    flags: Vec<(String, bool)>,
+    player_tag: String,
+    toggle_key: Key,
+    /// Index into [`TIME_SCALES`]
+    time_scale: usize,
+    open: bool,
+    cursor: usize,
+}
+
+impl Default for DebugMenu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DebugMenu {
+    /// Creates a closed menu without prefabs or flags, opened with backquote, teleporting the object tagged `"player"`
+    pub fn new() -> Self {
+        Self {
+            prefabs: Vec::new(),
+            flags: Vec::new(),
+            player_tag: "player".to_string(),
+            toggle_key: Key::Char('`'),
+            time_scale: TIME_SCALES.iter().position(|scale| *scale == 1.0).unwrap_or(0),
+            open: false,
+            cursor: 0,
+        }
+    }
+
+    /// Registers a prefab, replacing one with the same name
+    pub fn prefab(mut self, name: &str, prefab: Prefab) -> Self {
+        self.prefabs.retain(|(existing, _)| existing != name);
+        self.prefabs.push((name.to_string(), prefab));
+        self
+    }
+
+    /// Registers a stat flag the menu toggles, starting off
+    pub fn flag(mut self, name: &str) -> Self {
+        if !self.flags.iter().any(|(existing, _)| existing == name) {
+            self.flags.push((name.to_string(), false));
+        }
+        self
+    }
+
+    /// Sets the tag of the object teleported, and spawned on without a mouse
+    pub fn player_tag(mut self, tag: &str) -> Self {
+        self.player_tag = tag.to_string();
+        self
+    }
+
+    /// Sets the key opening and closing the menu
+    pub fn toggle_key(mut self, key: Key) -> Self {
+        self.toggle_key = key;
+        self
+    }
+
+    /// Returns whether the menu is shown
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Names of the registered prefabs in registration order
+    pub fn prefabs(&self) -> impl Iterator<Item = &str> {
+        self.prefabs.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Whether the menu last set a flag on, `None` for unregistered flags
+    pub fn flag_state(&self, name: &str) -> Option<bool> {
+        self.flags.iter().find(|(flag, _)| flag == name).map(|(_, on)| *on)
+    }
+
+    /// Time scale the menu last set
+    pub fn time_scale(&self) -> f32 {
+        TIME_SCALES[self.time_scale]
+    }
+
+    /// Handles input, opening the menu on its toggle key while closed
+    ///
+    /// # Returns
+    /// The first action picked, later events of the frame are ignored after it
+    pub fn handle(&mut self, events: &[InputEvent]) -> Option<DebugAction> {
+        for event in events {
+            let InputEvent::KeyDown(key) = event else {
+                continue;
+            };
+            if !self.open {
+                if *key == self.toggle_key {
+                    self.open = true;
+                    return Some(DebugAction::Open);
+                }
+                continue;
+            }
+
+            let entries = self.entries();
+            self.cursor = self.cursor.min(entries.len() - 1);
+            match key {
+                key if *key == self.toggle_key || *key == Key::Esc => {
+                    self.open = false;
+                    return Some(DebugAction::Close);
+                },
+                Key::Up => self.cursor = self.cursor.saturating_sub(1),
+                Key::Down => self.cursor = (self.cursor + 1).min(entries.len() - 1),
+                Key::Left | Key::Right if entries[self.cursor] == Entry::TimeScale => {
+                    self.time_scale = match key {
+                        Key::Left => self.time_scale.saturating_sub(1),
+                        _ => (self.time_scale + 1).min(TIME_SCALES.len() - 1),
+                    };
+                    return Some(DebugAction::SetTimeScale(self.time_scale()));
+                },
+                Key::Enter | Key::Space => match entries[self.cursor] {
+                    Entry::Spawn(prefab) => return Some(DebugAction::Spawn(self.prefabs[prefab].0.clone())),
+                    Entry::Teleport => return Some(DebugAction::Teleport),
+                    Entry::Flag(flag) => {
+                        let (name, on) = &mut self.flags[flag];
+                        *on = !*on;
+                        return Some(DebugAction::SetFlag(name.clone(), *on));
+                    },
+                    Entry::TimeScale => {},
+                },
+                _ => {},
+            }
+        }
+        None
+    }
+
+    /// Turns an action into the engine commands carrying it out
+    ///
+    /// # Arguments
+    /// * `action` - Action picked with [`handle`](Self::handle)
+    /// * `target` - World cell under the mouse, `None` without a mouse
+    /// * `objects` - Every object in the world, to find the player
+    ///
+    /// # Notes
+    /// - Opening and closing push and pop the [`CONSOLE`] input context
+    /// - Flags are set with `EngineCommand::SetStatFlag`
+    pub fn commands(&self, action: &DebugAction, target: Option<(usize, usize)>, objects: &[GameObject]) -> Vec<EngineCommand> {
+        let player = objects.iter().position(|obj| obj.tag == self.player_tag);
+        match action {
+            DebugAction::Open => vec![EngineCommand::PushInputContext(InputContext::new(CONSOLE))],
+            DebugAction::Close => vec![EngineCommand::PopInputContext],
+            DebugAction::Spawn(name) => {
+                let Some((_, prefab)) = self.prefabs.iter().find(|(prefab, _)| prefab == name) else {
+                    return Vec::new();
+                };
+                let Some((x, y)) = target.or_else(|| player.map(|index| (objects[index].x, objects[index].y))) else {
+                    return Vec::new();
+                };
+                let mut obj = prefab();
+                (obj.x, obj.y) = (x, y);
+                vec![EngineCommand::SpawnObject(obj)]
+            },
+            DebugAction::Teleport => match (player, target) {
+                (Some(index), Some((x, y))) => {
+                    let obj = &objects[index];
+                    vec![EngineCommand::MoveObject(index, x as i32 - obj.x as i32, y as i32 - obj.y as i32)]
+                },
+                _ => Vec::new(),
+            },
+            DebugAction::SetFlag(name, on) => vec![EngineCommand::SetStatFlag(name.clone(), *on)],
+            DebugAction::SetTimeScale(scale) => vec![EngineCommand::SetTimeScale(*scale)],
+        }
+    }
+
+    /// Rows of the open menu, top to bottom
+    fn entries(&self) -> Vec<Entry> {
+        let mut entries: Vec<Entry> = (0..self.prefabs.len()).map(Entry::Spawn).collect();
+        entries.push(Entry::Teleport);
+        entries.extend((0..self.flags.len()).map(Entry::Flag));
+        entries.push(Entry::TimeScale);
+        entries
+    }
+}
+
+impl Updatable for DebugMenu {
+    fn update_with_context(&mut self, ctx: &mut UpdateContext) -> Vec<EngineCommand> {
+        let Some(action) = self.handle(ctx.input_events) else {
+            return Vec::new();
+        };
+        let target = ctx.mouse.map(|(x, y)| ctx.camera.view().screen_to_world(x, y));
+        let target = target.and_then(|(x, y)| Some((usize::try_from(x).ok()?, usize::try_from(y).ok()?)));
+        self.commands(&action, target, ctx.objects)
+    }
+
+    fn input_context(&self) -> &str {
+        GLOBAL
+    }
+
+    fn draw(&self, renderer: &mut Renderer) {
+        if !self.open {
+            return;
+        }
+        renderer.draw_styled_text(0, 0, " Debug ", &Style::new().bold().reverse());
+        for (row, entry) in self.entries().into_iter().enumerate() {
+            let (line, mut style) = match entry {
+                Entry::Spawn(prefab) => (format!("Spawn {}", self.prefabs[prefab].0), Style::new()),
+                Entry::Teleport => ("Teleport player".to_string(), Style::new()),
+                Entry::Flag(flag) => {
+                    let (name, on) = &self.flags[flag];
+                    (format!("[{}] {name}", if *on { 'x' } else { ' ' }), Style::new().fg(if *on { Color::BRIGHT_GREEN } else { Color::WHITE }))
+                },
+                Entry::TimeScale => (format!("< Time scale {}x >", self.time_scale()), Style::new()),
+            };
+            if row == self.cursor {
+                style = style.reverse();
+            }
+            renderer.draw_styled_text(0, row + 1, &line, &style);
+        }
+    }
+}
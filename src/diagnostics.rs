@@ -0,0 +1,175 @@
+//! Per-frame validation of engine state
+//!
+//! Mistakes such as an object pushed off the map or a command naming a
+//! despawned object are silently ignored by the engine, which makes them hard to
+//! track down. With [`Diagnostics`] enabled, on by default in debug builds, the
+//! engine checks its objects after every update and the commands it applies,
+//! lists the problems it found in a panel in the top-right corner, and emits
+//! `EngineEvent::DiagnosticReported` the first frame each problem appears.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{diagnostics::{Diagnostic, Diagnostics}, game_object::GameObject};
+//!
+//! let mut diagnostics = Diagnostics::new(true);
+//! let mut objects = vec![GameObject::new(5, 5, '@'), GameObject::new(90, 5, '#')];
+//! objects[0].lifetime = Some(f32::NAN);
+//!
+//! let found = diagnostics.check(&objects, (80, 24));
+//! assert_eq!(found, vec![Diagnostic::InvalidNumber(0, "lifetime"), Diagnostic::OutOfBounds(1, 90, 5)]);
+//! // Problems still present are only reported once
+//! assert!(diagnostics.check(&objects, (80, 24)).is_empty());
+//! assert_eq!(diagnostics.current().len(), 2);
+//! ```
+
+use std::fmt;
+use crate::{color::Color, game_object::GameObject, renderer::Renderer, style::Style, validation::CommandError};
+
+/// Most problems listed in the panel, the rest are counted
+const PANEL_ROWS: usize = 8;
+
+/// Widest line of the panel
+const PANEL_WIDTH: usize = 48;
+
+/// Problem found in engine state
+#[derive(Debug, Clone, PartialEq)]
+pub enum Diagnostic {
+    /// Object index and position of an object outside the world
+    OutOfBounds(usize, usize, usize),
+    /// Object index and field holding NaN or infinity
+    InvalidNumber(usize, &'static str),
+    /// Object index a command referred to and the command's name, for objects that do not exist
+    DeadObject(usize, &'static str),
+    /// Object index of an object with an animation without frames
+    EmptyAnimation(usize),
+    /// Name of a command dropped by command validation and why, see [`validation`](crate::validation)
+    InvalidCommand(&'static str, CommandError),
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::OutOfBounds(index, x, y) => write!(f, "object {index} at ({x}, {y}) is outside the world"),
+            Diagnostic::InvalidNumber(index, field) => write!(f, "object {index} has an invalid {field}"),
+            Diagnostic::DeadObject(index, command) => write!(f, "{command} names missing object {index}"),
+            Diagnostic::EmptyAnimation(index) => write!(f, "object {index} has an animation without frames"),
+            Diagnostic::InvalidCommand(command, error) => write!(f, "{command} rejected, {error}"),
+        }
+    }
+}
+
+/// Validation layer run by the engine every frame
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    enabled: bool,
+    /// Problems recorded during the frame, such as by commands
+    pending: Vec<Diagnostic>,
+    /// Problems found in the latest check
+    current: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    /// Creates a validation layer, doing nothing until enabled
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled, pending: Vec::new(), current: Vec::new() }
+    }
+
+    /// Returns whether checks run
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Turns checks on or off, turning them off clears the problems found
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.pending.clear();
+            self.current.clear();
+        }
+    }
+
+    /// Records a problem found outside [`check`](Self::check), listed with the next check's results
+    pub fn record(&mut self, diagnostic: Diagnostic) {
+        if self.enabled && !self.pending.contains(&diagnostic) {
+            self.pending.push(diagnostic);
+        }
+    }
+
+    /// Checks objects and finishes the frame
+    ///
+    /// # Arguments
+    /// * `objects` - Objects to check, by index
+    /// * `bounds` - Width and height of the world in cells
+    ///
+    /// # Returns
+    /// Problems not found by the previous check, to report
+    ///
+    /// # Notes
+    /// - Returns nothing while disabled
+    pub fn check(&mut self, objects: &[GameObject], bounds: (usize, usize)) -> Vec<Diagnostic> {
+        if !self.enabled {
+            return Vec::new();
+        }
+
+        let mut found = std::mem::take(&mut self.pending);
+        for (index, obj) in objects.iter().enumerate() {
+            if obj.x >= bounds.0 || obj.y >= bounds.1 {
+                found.push(Diagnostic::OutOfBounds(index, obj.x, obj.y));
+            }
+            for (field, value) in numbers(obj) {
+                if !value.is_finite() {
+                    found.push(Diagnostic::InvalidNumber(index, field));
+                }
+            }
+            if (obj.frames.is_empty() && obj.sprite_frames.is_empty()) || obj.facing_sprites.iter().any(|(_, frames)| frames.is_empty()) {
+                found.push(Diagnostic::EmptyAnimation(index));
+            }
+        }
+
+        let new = found.iter().filter(|diagnostic| !self.current.contains(diagnostic)).cloned().collect();
+        self.current = found;
+        new
+    }
+
+    /// Problems found in the latest check
+    pub fn current(&self) -> &[Diagnostic] {
+        &self.current
+    }
+
+    /// Draws the problems found in the top-right corner of the screen, nothing when there are none
+    pub fn draw(&self, renderer: &mut Renderer) {
+        if self.current.is_empty() {
+            return;
+        }
+        let width = PANEL_WIDTH.min(renderer.get_width());
+        let x = renderer.get_width() - width;
+        let style = Style::new().fg(Color::BRIGHT_WHITE).bg(Color::RED);
+        let title = format!(" {} problem{} ", self.current.len(), if self.current.len() == 1 { "" } else { "s" });
+        renderer.draw_styled_text(x, 0, &fit(&title, width), &style);
+
+        let rows = self.current.len().min(PANEL_ROWS).min(renderer.get_height().saturating_sub(1));
+        for (row, diagnostic) in self.current.iter().take(rows).enumerate() {
+            renderer.draw_styled_text(x, row + 1, &fit(&format!(" {diagnostic}"), width), &style);
+        }
+    }
+}
+
+/// Float fields of an object that must stay finite
+fn numbers(obj: &GameObject) -> Vec<(&'static str, f32)> {
+    let mut numbers = vec![("frame_duration", obj.frame_duration), ("animation_timer", obj.animation_timer)];
+    if let Some(lifetime) = obj.lifetime {
+        numbers.push(("lifetime", lifetime));
+    }
+    if let Some(projectile) = &obj.projectile {
+        let (dx, dy) = projectile.direction();
+        // Either component being NaN or infinite makes the sum so
+        numbers.extend([("projectile direction", dx + dy), ("projectile speed", projectile.speed())]);
+    }
+    numbers
+}
+
+/// Cuts or pads a line to a width
+fn fit(line: &str, width: usize) -> String {
+    let line: String = line.chars().take(width).collect();
+    format!("{line:<width$}")
+}
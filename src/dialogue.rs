@@ -0,0 +1,544 @@
+//! Dialogue and cutscene scripts
+//!
+//! Provides:
+//! - [`Dialogue`] scripts of text box nodes with a speaker, text, and optional choices
+//! - [`DialoguePlayer`] running a script: typewriter reveal, advancing on key press,
+//!   branching on the selected choice, and emitting events for every node
+//! - Loading scripts from JSON so writers can edit dialogue outside code
+//!
+//! # File format
+//! ```json
+//! {
+//!     "start": "greeting",
+//!     "nodes": [
+//!         { "id": "greeting", "speaker": "Elder", "text": "You came at last.", "next": "ask" },
+//!         { "id": "ask", "speaker": "Elder", "text": "Will you help us?", "choices": [
+//!             { "text": "Of course.", "next": "accept" },
+//!             { "text": "Not today." }
+//!         ]},
+//!         { "id": "accept", "speaker": "Elder", "text": "Then take this sword." }
+//!     ]
+//! }
+//! ```
+//! `start` defaults to the first node. A node or choice without `next` ends the dialogue.
+
+use std::{fs, io, path::Path};
+use crate::{
+    color::Color,
+    engine::UpdateContext,
+    event::EngineEvent,
+    input::{InputEvent, Key},
+    renderer::Renderer,
+    style::Style,
+    json::JsonValue,
+};
+
+/// Characters revealed per second unless another speed is configured
+pub const DEFAULT_REVEAL_SPEED: f32 = 40.0;
+
+/// An answer the player can pick at a node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DialogueChoice {
+    /// Text shown in the choice list
+    pub text: String,
+    /// Node to continue with, `None` ends the dialogue
+    pub next: Option<String>,
+}
+
+/// One text box of a dialogue
+///
+/// # Example
+/// ```
+/// # use lonely_engine::dialogue::DialogueNode;
+/// let node = DialogueNode::new("ask", "Will you help us?")
+///     .speaker("Elder")
+///     .choice("Of course.", Some("accept"))
+///     .choice("Not today.", None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DialogueNode {
+    /// Identifier used by `next` links and dialogue events
+    pub id: String,
+    /// Name shown above the text, if any
+    pub speaker: Option<String>,
+    /// Text of the box, `\n` starts a new line
+    pub text: String,
+    /// Answers to pick from, empty for plain text boxes
+    pub choices: Vec<DialogueChoice>,
+    /// Node following a box without choices, `None` ends the dialogue
+    pub next: Option<String>,
+}
+
+impl DialogueNode {
+    /// Creates a text box without speaker, choices, or follow-up
+    pub fn new(id: &str, text: &str) -> Self {
+        Self { id: id.to_string(), speaker: None, text: text.to_string(), choices: Vec::new(), next: None }
+    }
+
+    /// Sets the speaker
+    pub fn speaker(mut self, speaker: &str) -> Self {
+        self.speaker = Some(speaker.to_string());
+        self
+    }
+
+    /// Sets the node following this one
+    pub fn next(mut self, next: &str) -> Self {
+        self.next = Some(next.to_string());
+        self
+    }
+
+    /// Adds a choice continuing with `next`, or ending the dialogue when `None`
+    pub fn choice(mut self, text: &str, next: Option<&str>) -> Self {
+        self.choices.push(DialogueChoice { text: text.to_string(), next: next.map(str::to_string) });
+        self
+    }
+}
+
+/// A dialogue script
+///
+/// # Example
+/// ```
+/// use lonely_engine::dialogue::{Dialogue, DialogueNode};
+///
+/// let mut dialogue = Dialogue::new("hello");
+/// dialogue.add_node(DialogueNode::new("hello", "Hello there!").speaker("Guard").next("bye"));
+/// dialogue.add_node(DialogueNode::new("bye", "Move along."));
+/// assert!(dialogue.validate().is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dialogue {
+    start: String,
+    nodes: Vec<DialogueNode>,
+}
+
+impl Dialogue {
+    /// Creates an empty script starting at the node `start`
+    pub fn new(start: &str) -> Self {
+        Self { start: start.to_string(), nodes: Vec::new() }
+    }
+
+    /// Adds a node, replacing any node with the same id
+    pub fn add_node(&mut self, node: DialogueNode) {
+        match self.nodes.iter_mut().find(|existing| existing.id == node.id) {
+            Some(existing) => *existing = node,
+            None => self.nodes.push(node),
+        }
+    }
+
+    /// Id of the first node
+    pub fn start(&self) -> &str {
+        &self.start
+    }
+
+    /// Looks up a node by id
+    pub fn node(&self, id: &str) -> Option<&DialogueNode> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    /// Nodes in the order they were added
+    pub fn nodes(&self) -> &[DialogueNode] {
+        &self.nodes
+    }
+
+    /// Checks that the start node and every `next` link exist
+    ///
+    /// # Returns
+    /// `Err` with [`io::ErrorKind::InvalidData`] naming the first broken link
+    pub fn validate(&self) -> io::Result<()> {
+        if self.node(&self.start).is_none() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("start node `{}` does not exist", self.start)));
+        }
+
+        for node in &self.nodes {
+            let links = node.next.iter().chain(node.choices.iter().filter_map(|choice| choice.next.as_ref()));
+            for link in links {
+                if self.node(link).is_none() {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, format!("node `{}` links to missing node `{}`", node.id, link)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses a script in the JSON format described in the [module docs](self)
+    ///
+    /// # Returns
+    /// `Err` with [`io::ErrorKind::InvalidData`] for invalid JSON, missing fields, or broken links
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::dialogue::Dialogue;
+    /// let dialogue = Dialogue::from_json(r#"{
+    ///     "nodes": [
+    ///         { "id": "sign", "text": "Beware of the dog.", "next": "dog" },
+    ///         { "id": "dog", "speaker": "Dog", "text": "Woof." }
+    ///     ]
+    /// }"#).unwrap();
+    /// assert_eq!(dialogue.start(), "sign");
+    /// ```
+    pub fn from_json(text: &str) -> io::Result<Self> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let root = JsonValue::parse(text).map_err(|error| invalid(error.to_string()))?;
+        let nodes = root.get("nodes").and_then(JsonValue::as_array).ok_or_else(|| invalid("missing `nodes` array".to_string()))?;
+
+        // Optional string fields must still be strings when present
+        let optional_str = |value: &JsonValue, key: &str| -> io::Result<Option<String>> {
+            match value.get(key) {
+                None | Some(JsonValue::Null) => Ok(None),
+                Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+                Some(_) => Err(invalid(format!("`{}` must be a string", key))),
+            }
+        };
+
+        let mut parsed = Vec::with_capacity(nodes.len());
+        for (index, node) in nodes.iter().enumerate() {
+            let id = optional_str(node, "id")?.ok_or_else(|| invalid(format!("node {} is missing an `id`", index)))?;
+            let text = optional_str(node, "text")?.ok_or_else(|| invalid(format!("node `{}` is missing its `text`", id)))?;
+            let mut choices = Vec::new();
+            for choice in node.get("choices").and_then(JsonValue::as_array).unwrap_or(&[]) {
+                let text = optional_str(choice, "text")?.ok_or_else(|| invalid(format!("a choice of node `{}` is missing its `text`", id)))?;
+                choices.push(DialogueChoice { text, next: optional_str(choice, "next")? });
+            }
+            parsed.push(DialogueNode { speaker: optional_str(node, "speaker")?, next: optional_str(node, "next")?, id, text, choices });
+        }
+
+        let start = match optional_str(&root, "start")? {
+            Some(start) => start,
+            None => parsed.first().map(|node| node.id.clone()).ok_or_else(|| invalid("dialogue has no nodes".to_string()))?,
+        };
+        let mut dialogue = Self::new(&start);
+        for node in parsed {
+            if dialogue.node(&node.id).is_some() {
+                return Err(invalid(format!("duplicate node id `{}`", node.id)));
+            }
+            dialogue.add_node(node);
+        }
+        dialogue.validate()?;
+        Ok(dialogue)
+    }
+
+    /// Loads a JSON script from a file
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::dialogue::{Dialogue, DialoguePlayer};
+    /// let dialogue = Dialogue::load("assets/dialogue/elder.json").expect("Broken dialogue file");
+    /// let mut player = DialoguePlayer::new(dialogue);
+    /// player.start();
+    /// ```
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+}
+
+/// Runs a [`Dialogue`], one text box at a time
+///
+/// Call [`update`](Self::update) from an updatable every frame and
+/// [`draw`](Self::draw) from its draw method.
+///
+/// # Controls
+/// - Enter or Space reveals the whole text, then advances or confirms the selected choice
+/// - Up and Down move between choices
+///
+/// # Events
+/// - `EngineEvent::DialogueNodeEntered` with the node id whenever a box opens
+/// - `EngineEvent::DialogueChoiceMade` with the node id and choice index
+/// - `EngineEvent::DialogueEnded` once the last box is closed
+///
+/// # Example
+/// ```
+/// use lonely_engine::{
+///     dialogue::{Dialogue, DialogueNode, DialoguePlayer},
+///     engine::{EngineCommand, Updatable, UpdateContext},
+///     renderer::Renderer,
+/// };
+///
+/// struct Village {
+///     talk: DialoguePlayer,
+/// }
+///
+/// impl Updatable for Village {
+///     fn update_with_context(&mut self, ctx: &mut UpdateContext) -> Vec<EngineCommand> {
+///         if self.talk.is_active() {
+///             self.talk.update(ctx);
+///             return Vec::new(); // The world waits while people talk
+///         }
+///         Vec::new()
+///     }
+///
+///     fn draw(&self, renderer: &mut Renderer) {
+///         self.talk.draw(renderer, 2, 16, 60, 7);
+///     }
+/// }
+///
+/// let mut dialogue = Dialogue::new("hi");
+/// dialogue.add_node(DialogueNode::new("hi", "Nice weather today.").speaker("Farmer"));
+/// let mut talk = DialoguePlayer::new(dialogue);
+/// talk.start();
+/// ```
+#[derive(Debug, Clone)]
+pub struct DialoguePlayer {
+    dialogue: Dialogue,
+    /// Id of the open node
+    current: Option<String>,
+    /// Characters of the current text shown so far
+    revealed: f32,
+    /// Characters revealed per second, `0.0` shows text at once
+    speed: f32,
+    selected: usize,
+    /// Events waiting for the next update to reach the event bus
+    pending: Vec<EngineEvent>,
+}
+
+impl DialoguePlayer {
+    /// Creates a player for a script, idle until [`start`](Self::start)
+    pub fn new(dialogue: Dialogue) -> Self {
+        Self { dialogue, current: None, revealed: 0.0, speed: DEFAULT_REVEAL_SPEED, selected: 0, pending: Vec::new() }
+    }
+
+    /// Sets how many characters are revealed per second, `0.0` to show text at once
+    pub fn with_speed(mut self, chars_per_second: f32) -> Self {
+        self.speed = chars_per_second.max(0.0);
+        self
+    }
+
+    /// The script being played
+    pub fn dialogue(&self) -> &Dialogue {
+        &self.dialogue
+    }
+
+    /// Opens the start node, restarting if already running
+    pub fn start(&mut self) {
+        let start = self.dialogue.start.clone();
+        self.enter(Some(start));
+    }
+
+    /// Jumps to a node, ignoring unknown ids
+    pub fn jump_to(&mut self, id: &str) {
+        if self.dialogue.node(id).is_some() {
+            self.enter(Some(id.to_string()));
+        }
+    }
+
+    /// Closes the dialogue early
+    pub fn stop(&mut self) {
+        if self.current.is_some() {
+            self.enter(None);
+        }
+    }
+
+    /// Returns whether a text box is open
+    pub fn is_active(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// The open node
+    pub fn current(&self) -> Option<&DialogueNode> {
+        self.current.as_deref().and_then(|id| self.dialogue.node(id))
+    }
+
+    /// Part of the current text revealed so far
+    pub fn visible_text(&self) -> &str {
+        let Some(node) = self.current() else {
+            return "";
+        };
+        match node.text.char_indices().nth(self.revealed as usize) {
+            Some((end, _)) => &node.text[..end],
+            None => &node.text,
+        }
+    }
+
+    /// Returns whether the whole current text is shown
+    pub fn is_revealed(&self) -> bool {
+        self.current().is_none_or(|node| self.revealed as usize >= node.text.chars().count())
+    }
+
+    /// Index of the highlighted choice
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Reveals text and handles this frame's input
+    ///
+    /// # Arguments
+    /// * `ctx` - Update context providing delta time, input events, and the event bus
+    pub fn update(&mut self, ctx: &UpdateContext) {
+        self.advance(ctx.delta_time, ctx.input_events);
+        for event in self.pending.drain(..) {
+            ctx.event_bus.emit(event);
+        }
+    }
+
+    /// Reveals text and handles input without an engine, returning the events produced
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{dialogue::{Dialogue, DialogueNode, DialoguePlayer}, event::EngineEvent, input::{InputEvent, Key}};
+    ///
+    /// let mut dialogue = Dialogue::new("q");
+    /// dialogue.add_node(DialogueNode::new("q", "Tea?").choice("Yes", Some("yes")).choice("No", None));
+    /// dialogue.add_node(DialogueNode::new("yes", "Here you go."));
+    ///
+    /// let mut player = DialoguePlayer::new(dialogue).with_speed(0.0);
+    /// player.start();
+    /// player.advance(0.1, &[]);
+    /// let events = player.advance(0.1, &[InputEvent::KeyDown(Key::Enter)]);
+    ///
+    /// assert!(matches!(&events[0], EngineEvent::DialogueChoiceMade(id, 0) if id == "q"));
+    /// assert_eq!(player.visible_text(), "Here you go.");
+    /// ```
+    pub fn advance(&mut self, delta_time: f32, events: &[InputEvent]) -> Vec<EngineEvent> {
+        if self.current.is_some() {
+            self.revealed += delta_time * self.speed;
+
+            for event in events {
+                match event {
+                    InputEvent::KeyDown(Key::Up) => self.move_selection(-1),
+                    InputEvent::KeyDown(Key::Down) => self.move_selection(1),
+                    InputEvent::KeyDown(Key::Enter | Key::Space) => {
+                        self.confirm();
+                        // One box per frame, so a held key does not skip through a conversation
+                        break;
+                    },
+                    _ => {},
+                }
+            }
+        }
+        std::mem::take(&mut self.pending)
+    }
+
+    /// Draws the open text box, nothing when the dialogue is idle
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Top-left corner of the box
+    /// * `width`, `height` - Box size including its border
+    ///
+    /// # Notes
+    /// - Text is word wrapped to the box, lines beyond its height are clipped
+    /// - Choices are listed under the text once it is fully revealed
+    pub fn draw(&self, renderer: &mut Renderer, x: usize, y: usize, width: usize, height: usize) {
+        let Some(node) = self.current() else {
+            return;
+        };
+        if width < 4 || height < 3 {
+            return;
+        }
+
+        let border = Style::new().fg(Color::GREY);
+        let inner = width - 2;
+        renderer.draw_styled_text(x, y, &format!("┌{}┐", "─".repeat(inner)), &border);
+        for row in y + 1..y + height - 1 {
+            renderer.draw_styled_text(x, row, "│", &border);
+            renderer.draw_text(x + 1, row, &" ".repeat(inner));
+            renderer.draw_styled_text(x + width - 1, row, "│", &border);
+        }
+        renderer.draw_styled_text(x, y + height - 1, &format!("└{}┘", "─".repeat(inner)), &border);
+        if let Some(speaker) = &node.speaker {
+            renderer.draw_styled_text(x + 2, y, &format!(" {} ", speaker), &Style::new().fg(Color::BRIGHT_YELLOW).bold());
+        }
+
+        // Wrap the full text so words do not jump between lines while revealing
+        let text_width = inner.saturating_sub(2).max(1);
+        let mut remaining = self.visible_text().chars().count();
+        let mut row = y + 1;
+        for (line, consumed) in wrap(&node.text, text_width) {
+            if row >= y + height - 1 || remaining == 0 {
+                break;
+            }
+            let shown: String = line.chars().take(remaining).collect();
+            remaining = remaining.saturating_sub(consumed);
+            renderer.draw_text(x + 2, row, &shown);
+            row += 1;
+        }
+
+        if !self.is_revealed() {
+            return;
+        }
+        for (index, choice) in node.choices.iter().enumerate() {
+            if row >= y + height - 1 {
+                break;
+            }
+            if index == self.selected {
+                renderer.draw_styled_text(x + 2, row, &format!("> {}", choice.text), &Style::new().fg(Color::BRIGHT_CYAN).bold());
+            } else {
+                renderer.draw_text(x + 2, row, &format!("  {}", choice.text));
+            }
+            row += 1;
+        }
+        if node.choices.is_empty() {
+            renderer.draw_styled_text(x + width - 3, y + height - 1, "▼", &border);
+        }
+    }
+
+    fn move_selection(&mut self, step: i32) {
+        let count = self.current().map_or(0, |node| node.choices.len());
+        if count > 0 && self.is_revealed() {
+            self.selected = (self.selected as i32 + step).rem_euclid(count as i32) as usize;
+        }
+    }
+
+    /// Reveals the rest of the text, or leaves the box once it is fully shown
+    fn confirm(&mut self) {
+        let Some(node) = self.current() else {
+            return;
+        };
+        if !self.is_revealed() {
+            self.revealed = f32::MAX;
+            return;
+        }
+
+        let (id, next) = match node.choices.get(self.selected) {
+            Some(choice) => (Some(node.id.clone()), choice.next.clone()),
+            None => (None, node.next.clone()),
+        };
+        if let Some(id) = id {
+            self.pending.push(EngineEvent::DialogueChoiceMade(id, self.selected));
+        }
+        self.enter(next);
+    }
+
+    /// Opens a node, or ends the dialogue for `None`
+    fn enter(&mut self, id: Option<String>) {
+        self.revealed = if self.speed == 0.0 { f32::MAX } else { 0.0 };
+        self.selected = 0;
+        match id {
+            Some(id) => {
+                self.pending.push(EngineEvent::DialogueNodeEntered(id.clone()));
+                self.current = Some(id);
+            },
+            None => {
+                self.pending.push(EngineEvent::DialogueEnded);
+                self.current = None;
+            },
+        }
+    }
+}
+
+/// Splits text into lines of at most `width` characters, breaking between words
+///
+/// Each line comes with the number of source characters it covers, including
+/// the space or newline dropped at its break, so reveal counts map onto lines.
+fn wrap(text: &str, width: usize) -> Vec<(String, usize)> {
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        for word in paragraph.split(' ') {
+            let line_len = line.chars().count();
+            if line_len > 0 && line_len + 1 + word.chars().count() > width {
+                lines.push((std::mem::take(&mut line), line_len + 1));
+            } else if line_len > 0 {
+                line.push(' ');
+            }
+            line.push_str(word);
+            // Words longer than a line are split wherever they overflow
+            while line.chars().count() > width {
+                let split = line.char_indices().nth(width).map_or(line.len(), |(index, _)| index);
+                let rest = line.split_off(split);
+                lines.push((std::mem::replace(&mut line, rest), width));
+            }
+        }
+        let line_len = line.chars().count();
+        lines.push((line, line_len + 1));
+    }
+    lines
+}
@@ -0,0 +1,208 @@
+//! Eight-way directions for movement and facing
+//!
+//! [`Direction`] names the four arrow directions and the four diagonals
+//! between them, in screen terms: up is towards the top row. Every
+//! [`GameObject`](crate::game_object::GameObject) has a `facing` that the
+//! engine turns towards each move, which picks its directional sprites.
+//!
+//! Diagonal input moves one cell on both axes per step on the grid. For smooth
+//! movement with a [`steering::Agent`](crate::steering::Agent), use
+//! [`Direction::velocity`], which normalizes diagonals so holding two arrows is
+//! not faster than holding one.
+//!
+//! # Example
+//! ```
+//! use std::collections::HashSet;
+//! use lonely_engine::{direction::Direction, engine::EngineCommand, input::Key, steering::Agent};
+//!
+//! let held: HashSet<Key> = [Key::Up, Key::Right].into();
+//! let direction = Direction::from_keys(&held).unwrap();
+//! assert_eq!(direction, Direction::UpRight);
+//!
+//! let mut player = Agent::new(10.0, 10.0, 8.0);
+//! let (dx, dy) = player.integrate(direction.velocity(8.0), 0.25);
+//! assert_eq!((dx, dy), (1, -1)); // 1.41 cells on each axis, not 2
+//! let command = EngineCommand::MoveObject(0, dx, dy);
+//! ```
+
+use std::collections::HashSet;
+use std::f32::consts::FRAC_1_SQRT_2;
+use crate::input::Key;
+
+/// One of eight directions on the screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    /// Towards the top row
+    Up,
+    /// Up and right
+    UpRight,
+    /// Towards the last column
+    Right,
+    /// Down and right
+    DownRight,
+    /// Towards the bottom row, facing the player, the default
+    #[default]
+    Down,
+    /// Down and left
+    DownLeft,
+    /// Towards the first column
+    Left,
+    /// Up and left
+    UpLeft,
+}
+
+impl Direction {
+    /// Every direction, clockwise from up
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// The four arrow directions, clockwise from up
+    pub const CARDINAL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    /// Direction of a move, from the signs of its deltas
+    ///
+    /// # Returns
+    /// `None` for `(0, 0)`
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::direction::Direction;
+    /// assert_eq!(Direction::from_delta(3, 0), Some(Direction::Right));
+    /// assert_eq!(Direction::from_delta(-1, 2), Some(Direction::DownLeft));
+    /// assert_eq!(Direction::from_delta(0, 0), None);
+    /// ```
+    pub fn from_delta(dx: i32, dy: i32) -> Option<Self> {
+        match (dx.signum(), dy.signum()) {
+            (0, -1) => Some(Direction::Up),
+            (1, -1) => Some(Direction::UpRight),
+            (1, 0) => Some(Direction::Right),
+            (1, 1) => Some(Direction::DownRight),
+            (0, 1) => Some(Direction::Down),
+            (-1, 1) => Some(Direction::DownLeft),
+            (-1, 0) => Some(Direction::Left),
+            (-1, -1) => Some(Direction::UpLeft),
+            _ => None,
+        }
+    }
+
+    /// Nearest of the eight directions to a vector, such as an agent's velocity
+    ///
+    /// # Returns
+    /// `None` for a zero vector
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::direction::Direction;
+    /// assert_eq!(Direction::from_vector((2.0, 0.3)), Some(Direction::Right));
+    /// assert_eq!(Direction::from_vector((-1.0, -1.2)), Some(Direction::UpLeft));
+    /// ```
+    pub fn from_vector(vector: (f32, f32)) -> Option<Self> {
+        if vector.0.hypot(vector.1) <= f32::EPSILON {
+            return None;
+        }
+        // Clockwise angle from up in eighths of a turn, y grows downwards
+        let eighths = (vector.0.atan2(-vector.1) / std::f32::consts::FRAC_PI_4).round() as i32;
+        Some(Self::ALL[eighths.rem_euclid(8) as usize])
+    }
+
+    /// Direction held on a set of arrows, opposite arrows cancel out
+    ///
+    /// # Arguments
+    /// * `up`, `down`, `left`, `right` - Whether each arrow is held
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::direction::Direction;
+    /// assert_eq!(Direction::from_held(true, false, true, false), Some(Direction::UpLeft));
+    /// assert_eq!(Direction::from_held(true, true, false, true), Some(Direction::Right));
+    /// ```
+    pub fn from_held(up: bool, down: bool, left: bool, right: bool) -> Option<Self> {
+        Self::from_delta(right as i32 - left as i32, down as i32 - up as i32)
+    }
+
+    /// Direction held on the arrow keys, see [`from_held`](Self::from_held)
+    ///
+    /// # Notes
+    /// - Use [`ActionMap::direction`](crate::keybindings::ActionMap::direction) to honor rebound controls
+    pub fn from_keys(active_keys: &HashSet<Key>) -> Option<Self> {
+        Self::from_held(
+            active_keys.contains(&Key::Up),
+            active_keys.contains(&Key::Down),
+            active_keys.contains(&Key::Left),
+            active_keys.contains(&Key::Right),
+        )
+    }
+
+    /// Whole-cell step, `-1`, `0`, or `1` per axis
+    pub fn delta(self) -> (i32, i32) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::UpRight => (1, -1),
+            Direction::Right => (1, 0),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (0, 1),
+            Direction::DownLeft => (-1, 1),
+            Direction::Left => (-1, 0),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    /// Unit vector, diagonals are `1/√2` on both axes
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::direction::Direction;
+    /// let (x, y) = Direction::DownRight.vector();
+    /// assert!((x.hypot(y) - 1.0).abs() < 1e-6);
+    /// ```
+    pub fn vector(self) -> (f32, f32) {
+        let (dx, dy) = self.delta();
+        let scale = if self.is_diagonal() { FRAC_1_SQRT_2 } else { 1.0 };
+        (dx as f32 * scale, dy as f32 * scale)
+    }
+
+    /// Velocity of `speed` cells per second in this direction, at the same speed on diagonals
+    pub fn velocity(self, speed: f32) -> (f32, f32) {
+        let (x, y) = self.vector();
+        (x * speed, y * speed)
+    }
+
+    /// Returns whether the direction moves on both axes
+    pub fn is_diagonal(self) -> bool {
+        let (dx, dy) = self.delta();
+        dx != 0 && dy != 0
+    }
+
+    /// Direction pointing the other way
+    pub fn opposite(self) -> Self {
+        self.rotated(4)
+    }
+
+    /// Direction turned clockwise in eighths of a turn, negative turns counter-clockwise
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::direction::Direction;
+    /// assert_eq!(Direction::Up.rotated(2), Direction::Right);
+    /// assert_eq!(Direction::Up.rotated(-1), Direction::UpLeft);
+    /// ```
+    pub fn rotated(self, eighths: i32) -> Self {
+        let index = Self::ALL.iter().position(|&direction| direction == self).unwrap_or(0) as i32;
+        Self::ALL[(index + eighths).rem_euclid(8) as usize]
+    }
+
+    /// Horizontal and vertical arrow directions making up this one, one of them `None` for cardinals
+    pub fn components(self) -> (Option<Self>, Option<Self>) {
+        let (dx, dy) = self.delta();
+        (Self::from_delta(dx, 0), Self::from_delta(0, dy))
+    }
+}
@@ -0,0 +1,216 @@
+//! Short-lived visual effects
+//!
+//! Effects are owned by the engine's [`Effects`] system rather than spawned as
+//! game objects: they never show up in the objects list, shift object indices,
+//! or collide, and disappear on their own once their duration ends.
+//!
+//! Contains:
+//! - [`FloatingText`] rising, fading text such as damage numbers and pickups
+//! - [`Effects`] updating and drawing every live effect
+//! - [`floating_text`] shortcut spawning text with default motion
+
+use crate::{color::Color, engine::Engine, renderer::Renderer, style::Style};
+
+/// Seconds floating text stays visible unless configured otherwise
+pub const DEFAULT_TEXT_DURATION: f32 = 1.0;
+/// Cells per second floating text rises unless configured otherwise
+pub const DEFAULT_TEXT_RISE: f32 = 3.0;
+
+/// Text that drifts away from a spot and fades out
+///
+/// # Notes
+/// - With a color ramp the foreground steps through the colors over the duration
+/// - Without one the text keeps its style and turns dim for its last third
+///
+/// # Example
+/// ```
+/// use lonely_engine::{color::Color, effects::FloatingText, style::Style};
+///
+/// let crit = FloatingText::new(10, 5, "CRIT!", Style::new().bold())
+///     .duration(1.5)
+///     .drift(1.0, -2.0)
+///     .color_ramp(vec![Color::BRIGHT_YELLOW, Color::YELLOW, Color::RED]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloatingText {
+    /// Characters drawn
+    pub text: String,
+    /// Current column, fractional while drifting
+    pub x: f32,
+    /// Current row, fractional while drifting
+    pub y: f32,
+    /// Colors and attributes of the text
+    pub style: Style,
+    /// Seconds before the text disappears
+    pub duration: f32,
+    /// Movement in cells per second, negative `y` rises
+    pub drift: (f32, f32),
+    /// Foreground colors stepped through from spawn to end
+    pub color_ramp: Vec<Color>,
+    /// Seconds since spawn
+    age: f32,
+}
+
+impl FloatingText {
+    /// Creates text rising from a cell with the default duration
+    pub fn new(x: usize, y: usize, text: &str, style: Style) -> Self {
+        Self {
+            text: text.to_string(),
+            x: x as f32,
+            y: y as f32,
+            style,
+            duration: DEFAULT_TEXT_DURATION,
+            drift: (0.0, -DEFAULT_TEXT_RISE),
+            color_ramp: Vec::new(),
+            age: 0.0,
+        }
+    }
+
+    /// Sets how many seconds the text stays visible
+    pub fn duration(mut self, seconds: f32) -> Self {
+        self.duration = seconds.max(0.0);
+        self
+    }
+
+    /// Sets the movement in cells per second
+    pub fn drift(mut self, dx: f32, dy: f32) -> Self {
+        self.drift = (dx, dy);
+        self
+    }
+
+    /// Sets the foreground colors stepped through over the duration
+    pub fn color_ramp(mut self, colors: Vec<Color>) -> Self {
+        self.color_ramp = colors;
+        self
+    }
+
+    /// Fraction of the duration elapsed, from `0.0` to `1.0`
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 { 1.0 } else { (self.age / self.duration).min(1.0) }
+    }
+
+    /// Returns whether the duration has run out
+    pub fn is_finished(&self) -> bool {
+        self.age >= self.duration
+    }
+
+    /// Style for the current point of the fade
+    pub fn current_style(&self) -> Style {
+        let progress = self.progress();
+        if self.color_ramp.is_empty() {
+            return if progress >= 2.0 / 3.0 { self.style.dim() } else { self.style };
+        }
+
+        let step = ((progress * self.color_ramp.len() as f32) as usize).min(self.color_ramp.len() - 1);
+        self.style.fg(self.color_ramp[step])
+    }
+
+    fn update(&mut self, delta_time: f32) {
+        self.age += delta_time;
+        self.x += self.drift.0 * delta_time;
+        self.y += self.drift.1 * delta_time;
+    }
+
+    fn draw(&self, renderer: &mut Renderer) {
+        // Text drifting off the top or left edge is clipped rather than wrapped around
+        if self.x < 0.0 || self.y < 0.0 {
+            return;
+        }
+        renderer.draw_styled_text(self.x.round() as usize, self.y.round() as usize, &self.text, &self.current_style());
+    }
+}
+
+/// Live effects updated and drawn by the engine every frame
+///
+/// # Notes
+/// - Effects are drawn over objects and under updatable overlays
+/// - The engine advances effects every frame, also between turns in turn-based
+///   mode, but not while paused
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Effects {
+    texts: Vec<FloatingText>,
+}
+
+impl Effects {
+    /// Creates an empty effects system
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts showing floating text
+    pub fn spawn_text(&mut self, text: FloatingText) {
+        self.texts.push(text);
+    }
+
+    /// Removes the oldest effect
+    ///
+    /// # Returns
+    /// `false` when no effect was live
+    pub fn remove_oldest(&mut self) -> bool {
+        if self.texts.is_empty() {
+            return false;
+        }
+        self.texts.remove(0);
+        true
+    }
+
+    /// Floating texts currently shown, oldest first
+    pub fn texts(&self) -> &[FloatingText] {
+        &self.texts
+    }
+
+    /// Number of live effects
+    pub fn len(&self) -> usize {
+        self.texts.len()
+    }
+
+    /// Returns whether no effect is live
+    pub fn is_empty(&self) -> bool {
+        self.texts.is_empty()
+    }
+
+    /// Removes every effect
+    pub fn clear(&mut self) {
+        self.texts.clear();
+    }
+
+    /// Advances every effect, removing finished ones
+    pub fn update(&mut self, delta_time: f32) {
+        for text in &mut self.texts {
+            text.update(delta_time);
+        }
+        self.texts.retain(|text| !text.is_finished());
+    }
+
+    /// Draws every live effect
+    pub fn draw(&self, renderer: &mut Renderer) {
+        for text in &self.texts {
+            text.draw(renderer);
+        }
+    }
+}
+
+/// Shows text rising from a cell and fading out, such as damage numbers
+///
+/// # Arguments
+/// * `engine` - Engine whose effects system shows the text
+/// * `x`, `y` - Cell the text starts at
+/// * `text` - Characters to show
+/// * `style` - Colors and attributes of the text
+///
+/// # Notes
+/// - Use [`FloatingText`] with [`Engine::spawn_floating_text`] or
+///   `EngineCommand::SpawnFloatingText` to configure duration, drift, and colors
+/// - Counts towards the engine's effect cap, see [`Limits`](crate::limits::Limits)
+///
+/// # Example
+/// ```
+/// use lonely_engine::{color::Color, effects, engine::Engine, style::Style};
+///
+/// let mut engine = Engine::new(80, 24);
+/// effects::floating_text(&mut engine, 12, 6, "-5", Style::new().fg(Color::RED));
+/// assert_eq!(engine.effects.len(), 1);
+/// ```
+pub fn floating_text(engine: &mut Engine, x: usize, y: usize, text: &str, style: Style) {
+    engine.spawn_floating_text(FloatingText::new(x, y, text, style));
+}
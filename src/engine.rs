@@ -1,14 +1,16 @@
 //! Core game engine implementation containing the main loop, Object management,
 //! and systems for input processing, rendering, and event handling.
 
-use std::{collections::HashSet, io::Write, time::{Duration, Instant}};
-use crate::{event::{EngineEvent, EventBus}, game_object::GameObject, input, renderer::Renderer};
+use std::{cell::RefCell, collections::{HashMap, HashSet, VecDeque}, future::Future, hash::{Hash, Hasher}, io::{self, Write}, net::ToSocketAddrs, path::PathBuf, rc::Rc, sync::{Arc, Mutex}, time::{Duration, Instant}};
+use crate::{audio::{AudioEngine, AudioGroup}, camera::Camera, color::Color, components::{Components, Query}, crash::{self, CrashReport, DEFAULT_COMMAND_HISTORY}, cursor::Cursor, diagnostics::{Diagnostic, Diagnostics}, direction::Direction, effects::{Effects, FloatingText}, event::{EngineEvent, EventBus, EventSender}, fog::FogOfWar, game_object::GameObject, hash::StableHasher, locale, input, input_context::{self, InputContext, InputContexts}, input_log::InputLog, inventory::{ItemEffect, ItemRegistry}, keybindings::ComboMatcher, level::Levels, limits::{LimitKind, LimitPolicy, Limits}, metrics::{DEFAULT_DUMP_INTERVAL, Metrics, MetricsDump, MetricsRecorder, MetricsServer}, narration::{NarrationOutput, Narrator}, occupancy::{MovePolicy, OccupancyMap}, pacing::{FramePacer, FramePacing}, page::Page, pause_menu::{PauseAction, PauseMenu}, path_follower::{PathFollower, PathStep}, profiler::{FrameProfile, FrameTimings, Profiler}, projectiles::{HitOutcome, ProjectileStep}, quests::{QuestEvent, QuestLog}, recorder::Recorder, renderer::Renderer, resources::Resources, rng::Rng, score::{Score, ScoreEvent}, server, settings::{self, Settings}, stat_block::Modifier, stats::Stats, style::Style, status::StatusEffect, task::{TaskHandle, Tasks}, terminal::Capabilities, testing::CommandRecord, time::GameTime, transition::{Transition, TransitionDirection}, turn::TurnScheduler, validation::{CommandError, Strictness, Validation}, weather::Weather, zone::{TriggerZone, TriggerZones}};
 use windows::Win32::{Foundation::INVALID_HANDLE_VALUE, System::Console:: {
     GetConsoleMode, GetStdHandle, SetConsoleMode, CONSOLE_MODE, ENABLE_VIRTUAL_TERMINAL_PROCESSING, STD_OUTPUT_HANDLE
 }};
 
 /// Commands that can be issued to advise the engine what to do.
 #[derive(Debug)]
+// Spawning is the common case, boxing the object would only add an allocation per spawn
+#[allow(clippy::large_enum_variant)]
 pub enum EngineCommand {
     /// Spawn a new game object into the scene
     SpawnObject(GameObject),
@@ -16,11 +18,499 @@ pub enum EngineCommand {
     DespawnObject(usize),
     /// Move an existing game object by specified delta coordinates
     MoveObject(usize, i32, i32),
+    /// Move every object in a group by specified delta coordinates
+    MoveGroup(GroupSelector, i32, i32),
+    /// Turn an object to face a direction without moving it
+    FaceObject(usize, Direction),
+    /// Remove every object in a group
+    DespawnGroup(GroupSelector),
+    /// Show or hide every object in a group
+    SetGroupVisible(GroupSelector, bool),
+    /// Show or hide an object, keeping it in the world
+    SetVisible(usize, bool),
+    /// Freeze or resume an object, keeping its state
+    SetActive(usize, bool),
+    /// Freeze or resume every object in a group
+    SetGroupActive(GroupSelector, bool),
+    /// Apply a status effect to an object, refreshing one with the same name
+    ApplyEffect(usize, StatusEffect),
+    /// Remove a status effect from an object by name
+    RemoveEffect(usize, String),
+    /// Set the base value of an object's stat, see [`stat_block`](crate::stat_block)
+    SetStat(usize, String, f32),
+    /// Add a modifier to an object's stats
+    AddModifier(usize, Modifier),
+    /// Remove every modifier an object's stats got from a source
+    RemoveModifiers(usize, String),
+    /// Put an item of [`Engine::items`] in an object's matching slot, ignored when it does not fit, see [`equipment`](crate::equipment)
+    EquipItem(usize, String),
+    /// Empty an object's equipment slot
+    UnequipItem(usize, String),
+    /// Apply the use effects of an item of [`Engine::items`] to an object, ignored for unknown items
+    UseItem(usize, String),
+    /// Walk an object along a path, replacing its current one
+    FollowPath(usize, PathFollower),
+    /// Stop an object where it stands, discarding its path
+    StopPath(usize),
+    /// Show a full-screen page over the scene, pausing the world until it is dismissed
+    PushPage(Page),
+    /// Dismiss the topmost page
+    PopPage,
+    /// Open the pause menu, ignored when none is set
+    OpenPauseMenu,
+    /// Close the pause menu and resume the game
+    ClosePauseMenu,
+    /// Switch the installed locale to a language, ignored when it has no strings for it
+    SetLanguage(String),
+    /// Register a trigger zone, replacing one with the same id
+    AddZone(TriggerZone),
+    /// Unregister a trigger zone by id
+    RemoveZone(String),
+    /// Play a preloaded sound, ignored when the sound is missing
+    PlaySound(String),
+    /// Set the ambient effect covering the screen, `None` clears it
+    SetWeather(Option<Weather>),
+    /// Set how fast game time runs, `0.5` for slow motion
+    SetTimeScale(f32),
+    /// Restart the scene clock from zero
+    ResetSceneTime,
+    /// Show rising, fading text through the effects system
+    SpawnFloatingText(FloatingText),
+    /// Shake the camera with an intensity in cells for a number of seconds
+    ShakeCamera(f32, f32),
+    /// Follow the first object with a tag, covering a share of the distance every 1/30 second
+    CameraFollow(String, f32),
+    /// Zoom out to show a world region given as x, y, width, and height
+    CameraZoomToRegion(usize, usize, usize, usize),
+    /// Return the camera to 1:1 zoom
+    CameraResetZoom,
+    /// Add an amount to a stat counter
+    IncrementStat(String, i64),
+    /// Set or clear a stat flag
+    SetStatFlag(String, bool),
+    /// Score points with a source tag, extending the combo
+    AddScore(i64, String),
+    /// End the current combo
+    BreakCombo,
+    /// Start a registered quest, ignored when it is unknown or was started before
+    StartQuest(String),
+    /// Finish the current stage of an active quest whatever its objectives
+    AdvanceQuest(String),
+    /// Fail an active quest
+    FailQuest(String),
+    /// Load a registered level at its spawn point, ignored when no level has the name
+    LoadLevel(String),
+    /// Capture the world into a named slot, replacing the previous snapshot in it
+    SaveSnapshot(String),
+    /// Put back the world saved in a named slot, ignored when the slot is empty
+    RestoreSnapshot(String),
+    /// Put an input context on top of the stack, see [`input_context`]
+    PushInputContext(InputContext),
+    /// Remove the topmost input context, ignored when only gameplay is left
+    PopInputContext,
+    /// Read a line to screen reader players, ignored without a narrator, see [`narration`](crate::narration)
+    Narrate(String),
+    /// End an actor's turn of [`Engine::turns`] spending energy, ignored when it is not the actor's turn
+    EndTurn(usize, i32),
+    /// End an actor's turn of [`Engine::turns`] by starting an action lasting that many of its turns
+    BeginLongAction(usize, u32),
     /// Signal the engine to begin shutdown process
     Quit,
 }
 
+impl EngineCommand {
+    /// Index of the object the command applies to, `None` for commands not naming a single object
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::engine::EngineCommand;
+    ///
+    /// assert_eq!(EngineCommand::MoveObject(3, 1, 0).target(), Some(3));
+    /// assert_eq!(EngineCommand::Quit.target(), None);
+    /// ```
+    pub fn target(&self) -> Option<usize> {
+        match self {
+            EngineCommand::DespawnObject(index)
+            | EngineCommand::MoveObject(index, _, _)
+            | EngineCommand::FaceObject(index, _)
+            | EngineCommand::SetVisible(index, _)
+            | EngineCommand::SetActive(index, _)
+            | EngineCommand::ApplyEffect(index, _)
+            | EngineCommand::RemoveEffect(index, _)
+            | EngineCommand::SetStat(index, _, _)
+            | EngineCommand::AddModifier(index, _)
+            | EngineCommand::RemoveModifiers(index, _)
+            | EngineCommand::EquipItem(index, _)
+            | EngineCommand::UnequipItem(index, _)
+            | EngineCommand::UseItem(index, _)
+            | EngineCommand::FollowPath(index, _)
+            | EngineCommand::StopPath(index)
+            | EngineCommand::EndTurn(index, _)
+            | EngineCommand::BeginLongAction(index, _) => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// Name of the variant, for logs and diagnostics
+    pub fn name(&self) -> &'static str {
+        match self {
+            EngineCommand::SpawnObject(_) => "SpawnObject",
+            EngineCommand::DespawnObject(_) => "DespawnObject",
+            EngineCommand::MoveObject(_, _, _) => "MoveObject",
+            EngineCommand::MoveGroup(_, _, _) => "MoveGroup",
+            EngineCommand::FaceObject(_, _) => "FaceObject",
+            EngineCommand::DespawnGroup(_) => "DespawnGroup",
+            EngineCommand::SetGroupVisible(_, _) => "SetGroupVisible",
+            EngineCommand::SetVisible(_, _) => "SetVisible",
+            EngineCommand::SetActive(_, _) => "SetActive",
+            EngineCommand::SetGroupActive(_, _) => "SetGroupActive",
+            EngineCommand::ApplyEffect(_, _) => "ApplyEffect",
+            EngineCommand::RemoveEffect(_, _) => "RemoveEffect",
+            EngineCommand::SetStat(_, _, _) => "SetStat",
+            EngineCommand::AddModifier(_, _) => "AddModifier",
+            EngineCommand::RemoveModifiers(_, _) => "RemoveModifiers",
+            EngineCommand::EquipItem(_, _) => "EquipItem",
+            EngineCommand::UnequipItem(_, _) => "UnequipItem",
+            EngineCommand::UseItem(_, _) => "UseItem",
+            EngineCommand::FollowPath(_, _) => "FollowPath",
+            EngineCommand::StopPath(_) => "StopPath",
+            EngineCommand::PushPage(_) => "PushPage",
+            EngineCommand::PopPage => "PopPage",
+            EngineCommand::OpenPauseMenu => "OpenPauseMenu",
+            EngineCommand::ClosePauseMenu => "ClosePauseMenu",
+            EngineCommand::SetLanguage(_) => "SetLanguage",
+            EngineCommand::AddZone(_) => "AddZone",
+            EngineCommand::RemoveZone(_) => "RemoveZone",
+            EngineCommand::PlaySound(_) => "PlaySound",
+            EngineCommand::SetWeather(_) => "SetWeather",
+            EngineCommand::SetTimeScale(_) => "SetTimeScale",
+            EngineCommand::ResetSceneTime => "ResetSceneTime",
+            EngineCommand::SpawnFloatingText(_) => "SpawnFloatingText",
+            EngineCommand::ShakeCamera(_, _) => "ShakeCamera",
+            EngineCommand::CameraFollow(_, _) => "CameraFollow",
+            EngineCommand::CameraZoomToRegion(_, _, _, _) => "CameraZoomToRegion",
+            EngineCommand::CameraResetZoom => "CameraResetZoom",
+            EngineCommand::IncrementStat(_, _) => "IncrementStat",
+            EngineCommand::SetStatFlag(_, _) => "SetStatFlag",
+            EngineCommand::AddScore(_, _) => "AddScore",
+            EngineCommand::BreakCombo => "BreakCombo",
+            EngineCommand::StartQuest(_) => "StartQuest",
+            EngineCommand::AdvanceQuest(_) => "AdvanceQuest",
+            EngineCommand::FailQuest(_) => "FailQuest",
+            EngineCommand::LoadLevel(_) => "LoadLevel",
+            EngineCommand::SaveSnapshot(_) => "SaveSnapshot",
+            EngineCommand::RestoreSnapshot(_) => "RestoreSnapshot",
+            EngineCommand::PushInputContext(_) => "PushInputContext",
+            EngineCommand::PopInputContext => "PopInputContext",
+            EngineCommand::Narrate(_) => "Narrate",
+            EngineCommand::EndTurn(_, _) => "EndTurn",
+            EngineCommand::BeginLongAction(_, _) => "BeginLongAction",
+            EngineCommand::Quit => "Quit",
+        }
+    }
+}
+
+/// Commands queued by event handlers for the engine to apply
+///
+/// Handed to handlers registered with [`EventBus::subscribe_with_commands`],
+/// since event handlers run while the world is borrowed and cannot change it directly.
+///
+/// # Example
+/// ```
+/// use lonely_engine::engine::{CommandQueue, EngineCommand};
+///
+/// let mut queue = CommandQueue::new();
+/// queue.push(EngineCommand::PlaySound("door_open".into()));
+/// assert_eq!(queue.len(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct CommandQueue {
+    commands: Vec<EngineCommand>,
+}
+
+impl CommandQueue {
+    /// Creates an empty queue
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a command
+    pub fn push(&mut self, command: EngineCommand) {
+        self.commands.push(command);
+    }
+
+    /// Queues several commands in order
+    pub fn extend(&mut self, commands: impl IntoIterator<Item = EngineCommand>) {
+        self.commands.extend(commands);
+    }
+
+    /// Number of queued commands
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns whether no command is queued
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Removes and returns every queued command, oldest first
+    pub fn take(&mut self) -> Vec<EngineCommand> {
+        std::mem::take(&mut self.commands)
+    }
+}
+
+/// World state captured by [`Engine::snapshot`] and put back by [`Engine::restore`]
+///
+/// # Notes
+/// - Covers every object with its behaviors and timers (animation, lifetime,
+///   status effects, paths), the typed [`components`](crate::components), the RNG state,
+///   the turn counter and scheduler, effects, the camera, trigger zones, fog of war,
+///   and which level is loaded
+/// - Settings, stats, audio, pages, and input are left out, so undoing a move never
+///   takes back an achievement or an options change
+/// - Taking one clones the objects and components, cheap enough for every turn of a turn-based game
+#[derive(Debug, Clone)]
+pub struct WorldSnapshot {
+    objects: Vec<GameObject>,
+    components: Components,
+    rng: Rng,
+    turn: u64,
+    frame: u64,
+    update_accumulator: f32,
+    effects: Effects,
+    camera: Camera,
+    zones: TriggerZones,
+    turns: TurnScheduler<usize>,
+    fog: Option<FogOfWar>,
+    level: Option<String>,
+}
+
+impl WorldSnapshot {
+    /// Objects as they were, indexed like object commands
+    pub fn objects(&self) -> &[GameObject] {
+        &self.objects
+    }
+
+    /// Turn counter when the snapshot was taken
+    pub fn turn(&self) -> u64 {
+        self.turn
+    }
+
+    /// Frame the snapshot was taken in
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Hash of the captured state, equal to [`Engine::world_hash`] when it was taken
+    pub fn world_hash(&self) -> u64 {
+        hash_world(&self.objects, &self.rng, self.turn)
+    }
+}
+
+/// Hashes objects, RNG state, and turn counter, see [`Engine::world_hash`]
+fn hash_world(objects: &[GameObject], rng: &Rng, turn: u64) -> u64 {
+    let mut hasher = StableHasher::new();
+    objects.len().hash(&mut hasher);
+    for obj in objects {
+        obj.hash_state(&mut hasher);
+    }
+    rng.clone().next_u64().hash(&mut hasher);
+    turn.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Timestep used by deterministic mode unless another is configured (30 updates per second)
+pub const DETERMINISTIC_TIMESTEP: f32 = 1.0 / 30.0;
+
+/// Events kept in the history when the event overlay or dump turns it on
+pub const DEFAULT_EVENT_HISTORY: usize = 256;
+
+/// Most events listed by the event overlay
+const EVENT_OVERLAY_ROWS: usize = 12;
+
+/// Input source of events fed back by [`Engine::replay_input`]
+pub const REPLAY_SOURCE: &str = "replay";
+
+/// Frames presented per second unless another rate is configured
+pub const DEFAULT_RENDER_RATE: f32 = 30.0;
+
+/// Most simulated time a fixed update rate catches up on in one frame, so a
+/// stalled frame skips ahead instead of running hundreds of updates
+const MAX_CATCH_UP: f32 = 0.25;
+
+/// How the main loop advances the world
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EngineMode {
+    /// Update every frame, or at the fixed update rate when one is set
+    #[default]
+    RealTime,
+    /// Render every frame but only update when a key is pressed, one turn per input
+    ///
+    /// Holding a key takes a single turn until the terminal auto-repeats it
+    TurnBased,
+}
+
+/// Selects a set of objects for group commands
+///
+/// # Example
+/// ```
+/// # use lonely_engine::engine::{EngineCommand, GroupSelector};
+/// // Hide the whole HUD and slide the boss left
+/// let commands = vec![
+///     EngineCommand::SetGroupVisible(GroupSelector::group("hud"), false),
+///     EngineCommand::MoveGroup(GroupSelector::tag("boss"), -1, 0),
+/// ];
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupSelector {
+    /// Objects whose `tag` equals the value
+    Tag(String),
+    /// Objects registered in the named group
+    Group(String),
+}
+
+impl GroupSelector {
+    /// Selects objects by tag
+    pub fn tag(tag: &str) -> Self {
+        GroupSelector::Tag(tag.to_string())
+    }
+
+    /// Selects objects by explicit group membership
+    pub fn group(name: &str) -> Self {
+        GroupSelector::Group(name.to_string())
+    }
+
+    /// Returns whether an object belongs to the selection
+    pub fn matches(&self, obj: &GameObject) -> bool {
+        match self {
+            GroupSelector::Tag(tag) => obj.tag == *tag,
+            GroupSelector::Group(name) => obj.in_group(name),
+        }
+    }
+}
+
+/// Read access to the world handed to updatables each frame
+///
+/// Objects are read-only here, changes go through the returned [`EngineCommand`]s.
+///
+/// # Example
+/// ```
+/// use lonely_engine::engine::{EngineCommand, Updatable, UpdateContext};
+///
+/// /// Ends the game once every coin is collected
+/// struct CoinCounter;
+///
+/// impl Updatable for CoinCounter {
+///     fn update_with_context(&mut self, ctx: &mut UpdateContext) -> Vec<EngineCommand> {
+///         if ctx.with_tag("coin").next().is_none() {
+///             return vec![EngineCommand::Quit];
+///         }
+///         Vec::new()
+///     }
+/// }
+/// ```
+pub struct UpdateContext<'a> {
+    /// Time since last update in seconds
+    pub delta_time: f32,
+    /// Set of currently pressed keyboard keys
+    pub active_keys: &'a HashSet<input::Key>,
+    /// Input events read this frame, in order
+    pub input_events: &'a [input::InputEvent],
+    /// Source of each entry in `input_events`, at the same index
+    pub input_sources: &'a [input::InputSource],
+    /// Every object in the world, indexed like object commands
+    pub objects: &'a [GameObject],
+    /// Engine random number generator, shared so deterministic runs stay reproducible
+    pub rng: &'a mut Rng,
+    /// Event bus, for emitting events directly
+    pub event_bus: &'a EventBus,
+    /// Camera, for converting between screen and world coordinates
+    pub camera: &'a Camera,
+    /// Play field width in cells
+    pub width: usize,
+    /// Play field height in cells
+    pub height: usize,
+    /// Turns taken in turn-based mode
+    pub turn: u64,
+    /// Screen cell under the mouse, `None` before the mouse was used
+    pub mouse: Option<(usize, usize)>,
+    /// Index of the topmost visible object under the mouse
+    pub hovered: Option<usize>,
+    /// Cells covered by solid objects, as of the start of the update
+    pub occupancy: &'a OccupancyMap,
+    /// Player settings, including their key bindings
+    pub settings: &'a Settings,
+    /// Typed components of objects, see [`query`](Self::query)
+    pub components: &'a mut Components,
+    /// Points and combo of the current session
+    pub score: &'a Score,
+    /// Typed global state, see [`resource`](Self::resource)
+    pub resources: &'a mut Resources,
+    /// Game, scene, and real time clocks
+    pub time: &'a GameTime,
+    /// Stack of input contexts, see [`input_context`]
+    pub input_contexts: &'a InputContexts,
+}
+
+impl<'a> UpdateContext<'a> {
+    /// Shared value of a type inserted with [`Engine::insert_resource`]
+    pub fn resource<T: 'static>(&self) -> Option<&T> {
+        self.resources.get()
+    }
+
+    /// Mutable shared value of a type inserted with [`Engine::insert_resource`]
+    ///
+    /// # Notes
+    /// - Borrows the whole context, use `ctx.resources.get_mut()` to keep other fields usable alongside
+    pub fn resource_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.resources.get_mut()
+    }
+
+    /// Iterates objects having every component of a query, with their index, see [`Components::query`]
+    ///
+    /// # Notes
+    /// - Borrows the whole context, use `ctx.components.query()` to keep other fields usable alongside
+    pub fn query<Q: Query>(&mut self) -> impl Iterator<Item = (usize, Q::Item<'_>)> {
+        self.components.query::<Q>()
+    }
+
+    /// Returns the object at an index
+    pub fn object(&self, index: usize) -> Option<&'a GameObject> {
+        self.objects.get(index)
+    }
+
+    /// Iterates `(index, object)` pairs whose tag equals `tag`
+    pub fn with_tag<'t>(&self, tag: &'t str) -> impl Iterator<Item = (usize, &'a GameObject)> + 't
+    where
+        'a: 't,
+    {
+        self.objects.iter().enumerate().filter(move |(_, obj)| obj.tag == tag)
+    }
+
+    /// First object with a tag, such as the player
+    pub fn find_tag(&self, tag: &str) -> Option<(usize, &'a GameObject)> {
+        self.with_tag(tag).next()
+    }
+
+    /// Iterates `(index, object)` pairs matching a group selector
+    pub fn selected<'s>(&self, selector: &'s GroupSelector) -> impl Iterator<Item = (usize, &'a GameObject)> + 's
+    where
+        'a: 's,
+    {
+        self.objects.iter().enumerate().filter(move |(_, obj)| selector.matches(obj))
+    }
+
+    /// Iterates `(index, object)` pairs at a grid cell
+    pub fn objects_at(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, &'a GameObject)> + 'a {
+        self.objects.iter().enumerate().filter(move |(_, obj)| obj.x == x && obj.y == y)
+    }
+}
+
 /// Trait for systems that can update game state each frame
+///
+/// Implement [`update`](Self::update) for systems that only need time and keys,
+/// or [`update_with_context`](Self::update_with_context) to also read the world.
 pub trait Updatable {
     /// Main update method called every frame
     /// 
@@ -30,7 +520,67 @@ pub trait Updatable {
     ///
     /// # Returns
     /// Vector of engine commands to be processed this frame
-    fn update(&mut self, delta_time: f32, active_keys: &HashSet<input::Key>) ->Vec<EngineCommand>;
+    fn update(&mut self, _delta_time: f32, _active_keys: &HashSet<input::Key>) -> Vec<EngineCommand> {
+        Vec::new()
+    }
+
+    /// Update method receiving the whole [`UpdateContext`], called by the engine every frame
+    ///
+    /// # Notes
+    /// - Defaults to calling [`update`](Self::update)
+    ///
+    /// # Returns
+    /// Vector of engine commands to be processed this frame
+    fn update_with_context(&mut self, ctx: &mut UpdateContext) -> Vec<EngineCommand> {
+        self.update(ctx.delta_time, ctx.active_keys)
+    }
+
+    /// Input context the updatable belongs to, see [`input_context`]
+    ///
+    /// # Notes
+    /// - Defaults to gameplay, updatables outside the active contexts see no keys or input events
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{engine::Updatable, input_context::MENU};
+    ///
+    /// struct ShopMenu;
+    ///
+    /// impl Updatable for ShopMenu {
+    ///     fn input_context(&self) -> &str {
+    ///         MENU
+    ///     }
+    /// }
+    /// ```
+    fn input_context(&self) -> &str {
+        input_context::GAMEPLAY
+    }
+
+    /// Name shown in profiler output, defaults to the implementing type's name
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Draws on top of the frame's objects, called once per rendered frame
+    ///
+    /// # Notes
+    /// - Use for overlays such as titles, scores, and menus
+    /// - Drawn before transitions so fades cover it too
+    fn draw(&self, _renderer: &mut Renderer) {}
+}
+
+/// When the game loop last updated the world, advanced effects, and updated audio
+struct FrameClock {
+    last_update: Instant,
+    last_frame: Instant,
+    last_audio_update: Instant,
+}
+
+impl FrameClock {
+    fn now() -> Self {
+        let now = Instant::now();
+        Self { last_update: now, last_frame: now, last_audio_update: now }
+    }
 }
 
 /// Main game engine managing all game state and systems
@@ -51,6 +601,177 @@ pub struct Engine {
     previous_keys: HashSet<input::Key>,
      /// Current keyboard state
     active_keys: HashSet<input::Key>,
+    /// Ordered input events read this frame
+    input_events: Vec<input::InputEvent>,
+    /// Source of each input event
+    input_sources: Vec<input::InputSource>,
+    /// Events queued by input injectors
+    injected_input: input::InjectedQueue,
+    /// Keys held down by input injectors
+    injected_keys: HashSet<input::Key>,
+    /// Whether an update has seen `input_events`, later updates in the same frame get none
+    input_handled: bool,
+    /// Per-phase timing collection
+    profiler: Profiler,
+    /// Timings of the frame in progress
+    frame_timings: FrameTimings,
+    /// Draw the profiler breakdown over the scene
+    profiler_overlay: bool,
+    /// Rolling frame statistics behind [`Engine::metrics`]
+    metrics: MetricsRecorder,
+    /// File metrics are written to periodically
+    metrics_dump: Option<MetricsDump>,
+    /// HTTP endpoint answering with metrics
+    metrics_server: Option<MetricsServer>,
+    /// Engine random number generator
+    pub rng: Rng,
+    /// Active screen transition
+    transition: Option<Transition>,
+    /// Preloaded sound bank
+    pub audio: AudioEngine,
+    /// Persistent counters, flags, and achievements
+    pub stats: Stats,
+    /// Item definitions equipped and used by command, see [`equipment`](crate::equipment)
+    pub items: ItemRegistry,
+    /// Points, combo, and multipliers of the current session
+    pub score: Score,
+    /// Quest definitions and the player's progress, see [`quests`](crate::quests)
+    pub quests: QuestLog,
+    /// Typed global state shared between systems
+    resources: Resources,
+    /// Typed components of objects, see [`components`](crate::components)
+    pub components: Components,
+    /// Short-lived visual effects drawn over objects
+    pub effects: Effects,
+    /// View over the world: shake, follow, and zoom
+    pub camera: Camera,
+    /// Regions reporting objects moving in and out
+    pub zones: TriggerZones,
+    /// Actors taking turns in turn-based mode, keyed by object index
+    pub turns: TurnScheduler<usize>,
+    /// Fog of war drawn over the world, the loaded level's while a level with one is loaded
+    pub fog: Option<FogOfWar>,
+    /// Maps connected by exits and the tags of the objects carried between them
+    pub levels: Levels,
+    /// Checks for invalid state every frame, on by default in debug builds
+    pub diagnostics: Diagnostics,
+    /// Which updatables receive input, gameplay unless a menu or text field took over
+    pub input_contexts: InputContexts,
+    /// Progress through the combos of the player's bindings
+    combo_matcher: ComboMatcher,
+    /// Plain-text output for screen readers, subscribed to `event_bus`
+    narrator: Option<Narrator>,
+    /// Whether frames are drawn, off for players relying on the narrator alone
+    display: bool,
+    /// Full-screen pages shown over the scene, topmost last
+    pages: Vec<Page>,
+    /// Ambient effect covering the screen
+    weather: Option<Weather>,
+    /// Game, scene, and real time clocks
+    time: GameTime,
+    /// Screen cell of the last mouse event
+    mouse_position: Option<(usize, usize)>,
+    /// Drawn at the mouse cell on top of everything
+    cursor: Option<Cursor>,
+    /// Topmost visible object under the mouse
+    hovered: Option<usize>,
+    /// Cells covered by solid objects
+    occupancy: OccupancyMap,
+    /// Set when objects were added, removed, or changed outside of moves, the map is rebuilt before its next use
+    occupancy_dirty: bool,
+    /// How moves into occupied cells resolve
+    move_policy: MovePolicy,
+    /// Player settings, changes are applied at the start of the next frame
+    pub settings: Settings,
+    /// File the settings are saved to, see [`Engine::persist_settings`]
+    settings_path: Option<PathBuf>,
+    /// Whether settings changed since they were last saved
+    settings_unsaved: bool,
+    /// Sound stays muted regardless of settings and focus
+    audio_disabled: bool,
+    /// Menu opened by its toggle key, `None` disables pausing
+    pause_menu: Option<PauseMenu>,
+    /// Whether the pause menu is shown
+    pause_menu_open: bool,
+    /// Caps on objects, effects, and events
+    limits: Limits,
+    /// Caps already reported this frame
+    reported_limits: HashSet<LimitKind>,
+    /// Checks commands pass before being applied
+    validation: Validation,
+    /// Real-time or turn-based stepping
+    mode: EngineMode,
+    /// Turns taken in turn-based mode
+    turn: u64,
+    /// Whether the console window has keyboard focus
+    focused: bool,
+    /// Skip updates while the window is unfocused
+    pause_when_unfocused: bool,
+    /// Mute audio while the window is unfocused
+    mute_when_unfocused: bool,
+    /// Delta time used for every update in deterministic mode
+    fixed_timestep: Option<f32>,
+    /// Updates per second at a fixed update rate, `None` updates once per frame
+    update_rate: Option<f32>,
+    /// Real time not yet simulated at the fixed update rate
+    update_accumulator: f32,
+    /// Waits out the time between presented frames
+    pacer: FramePacer,
+    /// Recording in progress
+    recorder: Option<Recorder>,
+    /// Key toggling recording and the cast file written when it stops
+    recording_hotkey: Option<(input::Key, PathBuf)>,
+    /// Whether the recording hotkey was held last frame
+    recording_hotkey_held: bool,
+    /// Frames run so far
+    frame: u64,
+    /// Input log being recorded and the file it is written to when the engine stops
+    input_recording: Option<(InputLog, PathBuf)>,
+    /// Input log fed back frame by frame
+    input_replay: Option<InputLog>,
+    /// Snapshots saved by `EngineCommand::SaveSnapshot`, by slot name
+    snapshots: HashMap<String, WorldSnapshot>,
+    /// Draw the latest events over the scene
+    event_overlay: bool,
+    /// File the event history is written to on panic
+    event_dump: Option<PathBuf>,
+    /// Directory crash reports are written to on panic
+    crash_dir: Option<PathBuf>,
+    /// Latest commands applied, kept while crash reports are on
+    recent_commands: VecDeque<String>,
+    /// Every command applied, kept while a [`TestEngine`](crate::testing::TestEngine) watches
+    command_log: Option<Vec<CommandRecord>>,
+    /// Timing of the game loop between frames
+    clock: FrameClock,
+    /// Seed `rng` was created from
+    seed: u64,
+    /// Dedicated server: simulation only, stopped by console signals
+    server: bool,
+    /// Coroutines polled every update
+    tasks: Tasks,
+    /// Events collected for tasks since their last poll, subscribed when the first task is spawned
+    task_events: Option<Rc<RefCell<Vec<EngineEvent>>>>,
+    /// Events collected for quests since they last observed them, subscribed once a quest is registered
+    quest_events: Option<Rc<RefCell<Vec<EngineEvent>>>>,
+}
+
+impl Drop for Engine {
+    fn drop(&mut self) {
+        if !std::thread::panicking() {
+            return;
+        }
+        if let Some(path) = &self.event_dump {
+            let _ = self.event_bus.dump_history(path);
+        }
+        if let Some(dir) = &self.crash_dir {
+            let mut report = self.crash_report();
+            report.panic = crash::take_panic();
+            match report.write(dir) {
+                Ok(path) => eprintln!("crash report written to {}", path.display()),
+                Err(error) => eprintln!("could not write crash report: {error}"),
+            }
+        }
+    }
 }
 
 impl Engine {
@@ -63,21 +784,159 @@ impl Engine {
     ///
     /// # Example
     /// ```
+    /// # use lonely_engine::engine::Engine;
     /// let mut engine = Engine::new(80, 24);
     /// ```
     pub fn new(width: usize, height: usize) -> Self {
+        let mut renderer = Renderer::new(width, height);
+        renderer.set_capabilities(Capabilities::detect());
+        // A random seed that is known, so crash reports can name it
+        let seed = Rng::default().next_u64();
+
         Self { 
             running: true,
-            renderer: Renderer::new(width, height),
+            renderer,
             objects: Vec::new(),
             updatables: Vec::new(),
             commands: Vec::new(),
             event_bus: EventBus::new(),
             previous_keys: HashSet::new(),
             active_keys: HashSet::new(),
+            input_events: Vec::new(),
+            input_sources: Vec::new(),
+            injected_input: Arc::new(Mutex::new(Vec::new())),
+            injected_keys: HashSet::new(),
+            input_handled: true,
+            profiler: Profiler::default(),
+            frame_timings: FrameTimings::default(),
+            profiler_overlay: false,
+            metrics: MetricsRecorder::default(),
+            metrics_dump: None,
+            metrics_server: None,
+            rng: Rng::new(seed),
+            transition: None,
+            audio: AudioEngine::new(),
+            stats: Stats::new(),
+            items: ItemRegistry::new(),
+            score: Score::new(),
+            quests: QuestLog::new(),
+            resources: Resources::new(),
+            components: Components::new(),
+            effects: Effects::new(),
+            camera: Camera::new(),
+            zones: TriggerZones::new(),
+            turns: TurnScheduler::new(),
+            fog: None,
+            levels: Levels::new(),
+            diagnostics: Diagnostics::new(cfg!(debug_assertions)),
+            input_contexts: InputContexts::new(),
+            combo_matcher: ComboMatcher::new(),
+            narrator: None,
+            display: true,
+            pages: Vec::new(),
+            weather: None,
+            time: GameTime::new(),
+            mouse_position: None,
+            cursor: None,
+            hovered: None,
+            occupancy: OccupancyMap::new(),
+            occupancy_dirty: false,
+            move_policy: MovePolicy::Overlap,
+            settings: Settings::new(),
+            settings_path: None,
+            settings_unsaved: false,
+            audio_disabled: false,
+            pause_menu: None,
+            pause_menu_open: false,
+            limits: Limits::new(),
+            reported_limits: HashSet::new(),
+            validation: Validation::new(),
+            mode: EngineMode::RealTime,
+            turn: 0,
+            focused: true,
+            pause_when_unfocused: false,
+            mute_when_unfocused: false,
+            fixed_timestep: None,
+            update_rate: None,
+            update_accumulator: 0.0,
+            pacer: FramePacer::new(FramePacing::default(), Duration::from_millis(33)),
+            recorder: None,
+            recording_hotkey: None,
+            recording_hotkey_held: false,
+            frame: 0,
+            input_recording: None,
+            input_replay: None,
+            snapshots: HashMap::new(),
+            event_overlay: false,
+            event_dump: None,
+            crash_dir: None,
+            recent_commands: VecDeque::new(),
+            command_log: None,
+            clock: FrameClock::now(),
+            seed,
+            server: false,
+            tasks: Tasks::new(),
+            task_events: None,
+            quest_events: None,
         }
     }
 
+    /// Starts configuring an engine with the [`EngineBuilder`]
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::engine::Engine;
+    /// let engine = Engine::builder(80, 24)
+    ///     .pause_when_unfocused(true)
+    ///     .mute_when_unfocused(true)
+    ///     .build();
+    /// ```
+    pub fn builder(width: usize, height: usize) -> EngineBuilder {
+        EngineBuilder::new(width, height)
+    }
+
+    /// Shares a value with every system, replacing the value of the same type
+    ///
+    /// # Returns
+    /// The replaced value, if there was one
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::engine::Engine;
+    ///
+    /// struct GameConfig {
+    ///     lives: u32,
+    /// }
+    ///
+    /// let mut engine = Engine::new(80, 24);
+    /// engine.insert_resource(GameConfig { lives: 3 });
+    /// engine.resource_mut::<GameConfig>().unwrap().lives -= 1;
+    /// assert_eq!(engine.resource::<GameConfig>().unwrap().lives, 2);
+    /// ```
+    pub fn insert_resource<T: 'static>(&mut self, value: T) -> Option<T> {
+        self.resources.insert(value)
+    }
+
+    /// Shared value of a type, `None` when none was inserted
+    pub fn resource<T: 'static>(&self) -> Option<&T> {
+        self.resources.get()
+    }
+
+    /// Mutable shared value of a type, `None` when none was inserted
+    pub fn resource_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.resources.get_mut()
+    }
+
+    /// Takes a shared value out of the engine
+    pub fn remove_resource<T: 'static>(&mut self) -> Option<T> {
+        self.resources.remove()
+    }
+
+    /// Every shared value
+    pub fn resources(&mut self) -> &mut Resources {
+        &mut self.resources
+    }
+
     /// Registers a new updatable system
     ///
     /// # Arguments
@@ -88,31 +947,177 @@ impl Engine {
 
     /// Main game loop entry point
     ///
-    /// Handles initialization, runs the game loop at ~30 FPS,
-    /// and performs cleanup when finished
+    /// Handles initialization, runs the game loop at the render rate
+    /// (~30 FPS by default), and performs cleanup when finished
     pub fn run(&mut self) {
-        self.init_terminal();
+        if !self.renderer.is_headless() {
+            self.init_terminal();
+        }
+        if self.server {
+            server::install_shutdown_handler();
+        }
 
-        let mut last_update = Instant::now();
+        self.clock = FrameClock::now();
         while self.is_running() {
-            self.process_input();
+            self.run_frame(true);
+        }
+
+        if let Some((log, path)) = self.input_recording.take() {
+            let _ = log.save(path);
+        }
+        if let Some(dump) = &self.metrics_dump {
+            let _ = dump.write(&self.metrics.metrics());
+        }
+        self.save_settings();
+        self.cleanup_terminal();
+        if self.server {
+            server::finish_shutdown();
+        }
+    }
+
+    /// Runs one frame of the game loop: input, updates, effects, audio, and drawing
+    ///
+    /// # Arguments
+    /// * `paced` - Whether to wait out the rest of the frame interval, off for tests stepping frames back to back
+    pub(crate) fn run_frame(&mut self, paced: bool) {
+        if self.server && server::shutdown_requested() {
+            self.event_bus.emit(EngineEvent::ShutdownRequested);
+            self.stop();
+        }
+        self.reported_limits.clear();
+        self.event_bus.set_frame(self.frame);
+        if self.event_bus.begin_frame() > 0 {
+            self.report_limit(LimitKind::Events);
+        }
+        self.event_bus.dispatch_remote();
+        self.apply_settings();
+
+        let input_start = Instant::now();
+        self.process_input();
+        self.frame_timings.input = input_start.elapsed();
 
+        // In turn-based mode the world waits for input before advancing
+        let frame_start = Instant::now();
+        let paused = (self.pause_when_unfocused && !self.focused) || self.handle_page_input() || self.handle_pause_menu_input();
+        let mut simulated = 0.0;
+        let actor_turn = !self.turns.is_empty() && !self.turns.waiting_for_input();
+        // Only a press counts, a key held across frames takes one turn and auto-repeats take the next ones
+        let key_pressed = self.input_events.iter().any(|event| matches!(event, input::InputEvent::KeyDown(_)));
+        if !paused && (self.mode == EngineMode::RealTime || key_pressed || actor_turn) {
             // Calculate delta time
-            let delta_time = last_update.elapsed().as_secs_f32();
-            last_update = Instant::now();
+            let elapsed = self.clock.last_update.elapsed().as_secs_f32();
+            self.clock.last_update = Instant::now();
 
-            self.update(delta_time);
-            self.render();
+            if self.mode == EngineMode::TurnBased {
+                self.update(self.fixed_timestep.unwrap_or(elapsed));
+                self.turn += 1;
+                self.event_bus.emit(EngineEvent::TurnAdvanced(self.turn));
+            } else {
+                simulated = self.simulate(elapsed);
+            }
+        }
 
-            // Limit to ~30FPS
-            let frame_duration = Duration::from_millis(33);
-            let elapsed = Instant::now().duration_since(last_update);
-            if elapsed < frame_duration {
-                std::thread::sleep(frame_duration - elapsed);
+        // Effects follow simulated time in real-time mode and animate between turns in turn-based mode
+        let frame_delta = match self.mode {
+            EngineMode::RealTime => simulated,
+            EngineMode::TurnBased => self.fixed_timestep.unwrap_or_else(|| self.clock.last_frame.elapsed().as_secs_f32()),
+        };
+        let frame_delta = self.time.scaled(frame_delta);
+        self.clock.last_frame = Instant::now();
+        if !paused && !self.server {
+            self.effects.update(frame_delta);
+            if let Some(weather) = self.weather.as_mut() {
+                weather.update(frame_delta, self.renderer.get_width(), self.renderer.get_height());
             }
+            self.camera.update(frame_delta, &self.objects, self.renderer.get_width(), self.renderer.get_height());
         }
 
-        self.cleanup_terminal();
+        if !self.server {
+            // Sounds play in real time, so completion and ducking are updated even while paused
+            self.audio.update(self.clock.last_audio_update.elapsed().as_secs_f32());
+            self.clock.last_audio_update = Instant::now();
+            for (handle, name) in self.audio.poll_finished() {
+                self.event_bus.emit(EngineEvent::SoundFinished(handle, name));
+            }
+
+            if !paused {
+                self.update_hover();
+            }
+
+            let render_start = Instant::now();
+            if self.display {
+                self.render();
+            }
+            self.frame_timings.render = render_start.elapsed();
+        }
+        if let Some(narrator) = &self.narrator {
+            // A missing speech program or full disk should not stop the game
+            let _ = narrator.flush();
+        }
+        let busy = input_start.elapsed();
+
+        // Limit to the render rate
+        if paced {
+            self.pacer.wait(frame_start);
+        }
+        self.finish_frame_profile(busy);
+        self.frame += 1;
+        self.time.count_frame();
+    }
+
+    /// Starts or stops keeping every applied command, for [`TestEngine`](crate::testing::TestEngine)
+    pub(crate) fn set_command_log(&mut self, enabled: bool) {
+        self.command_log = enabled.then(Vec::new);
+    }
+
+    /// Commands applied since the command log was started
+    pub(crate) fn command_log(&self) -> &[CommandRecord] {
+        self.command_log.as_deref().unwrap_or(&[])
+    }
+
+    /// Returns whether the engine runs as a dedicated server, see [`server`](crate::server)
+    pub fn is_server(&self) -> bool {
+        self.server
+    }
+
+    /// Narrates game events as plain lines for screen readers, replacing any previous narrator
+    ///
+    /// # Notes
+    /// - The narrator hears every event emitted from now on, a replaced one keeps listening but is no longer flushed
+    /// - Queued lines are written at the end of every frame
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{engine::Engine, narration::{NarrationOutput, Narrator}};
+    ///
+    /// let mut engine = Engine::new(80, 24);
+    /// engine.set_narrator(Narrator::new(NarrationOutput::Stderr));
+    /// engine.set_display(false); // Screen reader players only hear the game
+    /// if let Some(narrator) = engine.narrator() {
+    ///     narrator.say("A goblin blocks the corridor");
+    /// }
+    /// ```
+    pub fn set_narrator(&mut self, narrator: Narrator) {
+        narrator.subscribe(&mut self.event_bus);
+        self.narrator = Some(narrator);
+    }
+
+    /// Narrator writing lines for screen readers, see [`set_narrator`](Self::set_narrator)
+    pub fn narrator(&self) -> Option<&Narrator> {
+        self.narrator.as_ref()
+    }
+
+    /// Turns drawing frames on or off, leaving the terminal to the narrator while off
+    ///
+    /// # Notes
+    /// - Unlike headless mode, input is still read from the console
+    pub fn set_display(&mut self, enabled: bool) {
+        self.display = enabled;
+    }
+
+    /// Returns whether frames are drawn
+    pub fn is_display_enabled(&self) -> bool {
+        self.display
     }
 
     fn init_terminal(&self) {
@@ -129,117 +1134,2117 @@ impl Engine {
             }
         }
 
-        // Clear screen and hide cursor
-        print!("\x1B[2J\x1B[?25l");
-        let _ = std::io::stdout().flush();
-    }
+        // Mouse events are optional, consoles without them still deliver keys
+        let _ = input::enable_mouse_input();
+        crash::install_panic_hook();
+        crash::set_terminal_active(self.display);
 
-    fn process_input(&mut self) {
-        self.active_keys = input::read_active_keys().unwrap_or_default();
+        // Clear screen and hide cursor, leaving standard output to the narrator without a display
+        if self.display {
+            print!("\x1B[2J\x1B[?25l");
+            let _ = std::io::stdout().flush();
+        }
     }
 
-    fn detect_key_transitions(&mut self) {
-        // Detect pressed key
-        for key in &self.active_keys {
-            if !self.previous_keys.contains(key) {
-                self.event_bus.emit(EngineEvent::KeyPressed(key.clone()));
-            }
+    fn process_input(&mut self) {
+        let console_input = match self.renderer.is_headless() {
+            true => input::ConsoleInput::default(),
+            false => input::read_console_input().unwrap_or_default(),
+        };
+        self.active_keys = console_input.keys;
+        // A fixed update rate slower than the render rate leaves frames without
+        // an update, their events wait for the next one instead of being dropped
+        if self.input_handled || self.update_rate.is_none() || self.mode == EngineMode::TurnBased {
+            self.input_events.clear();
+            self.input_sources.clear();
         }
+        self.input_sources.extend(console_input.events.iter().map(|_| input::InputSource::Hardware));
+        let first_new = self.input_events.len();
+        self.input_events.extend(console_input.events);
+        self.queue_replayed_input();
+        self.merge_injected_input();
+        self.input_handled = false;
 
-        // Detect key being held
-        for key in self.active_keys.intersection(&self.previous_keys) {
-            self.event_bus.emit(EngineEvent::KeyHeld(key.clone()));
+        if let Some((log, _)) = self.input_recording.as_mut() {
+            log.record(self.frame, &self.input_events[first_new..]);
         }
-
-        // Detect released keys
-        for key in &self.previous_keys {
-            if !self.active_keys.contains(key) {
-                self.event_bus.emit(EngineEvent::KeyReleased(key.clone()));
-            }
+        if let Some((x, y)) = self.input_events[first_new..].iter().rev().find_map(|event| match event {
+            input::InputEvent::Mouse { x, y, .. } => Some((*x, *y)),
+            _ => None,
+        }) {
+            self.mouse_position = Some((x, y));
         }
-    }
 
-    fn update(&mut self, delta_time: f32) {
-        self.detect_key_transitions();
-        self.previous_keys = self.active_keys.clone();
-        
-        // Clear previous commands
-        self.commands.clear();
+        if let Some(focused) = console_input.focus {
+            self.set_focused(focused);
+        }
 
-        // Process animations.
-        for obj in &mut self.objects {
-            if obj.frames.len() > 1 {
-                obj.animation_timer += delta_time;
-                if obj.animation_timer >= obj.frame_duration {
-                    obj.current_frame = (obj.current_frame +1) % obj.frames.len();
-                    obj.character = obj.frames[obj.current_frame];
-                    obj.animation_timer = 0.0;
+        if let Some((key, path)) = &self.recording_hotkey {
+            let held = self.active_keys.contains(key);
+            if held && !self.recording_hotkey_held {
+                let path = path.clone();
+                match self.stop_recording() {
+                    Some(recorder) => {
+                        let _ = recorder.save_asciicast(path);
+                    },
+                    None => self.start_recording(),
                 }
             }
+            self.recording_hotkey_held = held;
         }
+    }
 
-        // Run all registered updatable system.
-        for updatable in &mut self.updatables {
-            let new_commands = updatable.update(delta_time, &self.active_keys);
-            self.commands.extend(new_commands);
+    /// Injects the replayed events of this frame, stopping a headless run after the last one
+    fn queue_replayed_input(&mut self) {
+        let Some(replay) = &self.input_replay else {
+            return;
+        };
+        if self.renderer.is_headless() && replay.last_frame().is_none_or(|last| self.frame > last) {
+            self.stop();
+            return;
         }
+        let source = input::InputSource::Injected(REPLAY_SOURCE.to_string());
+        let mut queue = self.injected_input.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        queue.extend(replay.events_at(self.frame).map(|event| (source.clone(), event.clone())));
+    }
 
-        // Process all queued commands
-        let commands = std::mem::take(&mut self.commands);
-        for command in commands {
-            match command {
-                EngineCommand::SpawnObject(obj) => self.add_object(obj),
-                EngineCommand::DespawnObject(index) => {
-                    if index < self.objects.len() {
-                        self.objects.remove(index);
-                    }
+    /// Appends injected events and adds injected keys to the active set
+    fn merge_injected_input(&mut self) {
+        let injected = std::mem::take(&mut *self.injected_input.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+        for (source, event) in injected {
+            match &event {
+                input::InputEvent::KeyDown(key) => {
+                    self.injected_keys.insert(key.clone());
+                    // Taps release within the frame but still count as pressed for it
+                    self.active_keys.insert(key.clone());
                 },
-                EngineCommand::MoveObject(index, dx, dy) => {
-                    if let Some(obj) = self.objects.get_mut(index) {
-                        let new_x= (obj.x as i32 + dx).clamp(0, self.renderer.get_width() as i32 - 1) as usize;
-                        let new_y = (obj.y as i32 + dy).clamp(0, self.renderer.get_height() as i32 - 1) as usize;
-
-                        obj.x = new_x;
-                        obj.y = new_y;
-
-                        self.event_bus.emit(EngineEvent::ObjectMoved(index, new_x, new_y));
-                    }
+                input::InputEvent::KeyUp(key) => {
+                    self.injected_keys.remove(key);
                 },
-                EngineCommand::Quit => self.stop(),
+                _ => {},
             }
+            self.input_sources.push(source);
+            self.input_events.push(event);
         }
+        self.active_keys.extend(self.injected_keys.iter().cloned());
     }
 
-    fn render(&mut self) {
-        self.renderer.clear_back_buffer();
-
-        for obj in &self.objects {
-            self.renderer.set_char(obj.x, obj.y, obj);
-        }
-
-        let _ = self.renderer.present();
+    /// Returns a handle other threads can feed input through
+    ///
+    /// # Arguments
+    /// * `source` - Name events from this handle are tagged with, such as `"network"` or `"replay"`
+    ///
+    /// # Notes
+    /// - Injected events join the console input at the start of the next frame
+    /// - See [`InputInjector`](input::InputInjector) for holding and tapping keys
+    pub fn input_injector(&self, source: &str) -> input::InputInjector {
+        input::InputInjector::new(source, Arc::clone(&self.injected_input))
     }
 
-    /// Adds a game object to the engine's object collection
-    /// 
-    /// # Arguments
-    /// * `obj` - The [`GameObject`] to add to the scene
-    /// 
+    /// Returns a handle other threads can emit events through
+    ///
     /// # Notes
-    /// - The object will be rendered starting on the next frame
-    /// - Object will participate in animation system updates
-    /// - Object index is determined by insertion order
-    /// 
+    /// - Sent events are dispatched on the main thread at the start of the next frame
+    ///
     /// # Example
     /// ```
-    /// let mut engine = Engine::new(80, 24);
-    /// let player = GameObject::new(10, 5, '@');
-    /// engine.add_object(player);
+    /// use lonely_engine::{engine::Engine, event::EngineEvent};
+    ///
+    /// let engine = Engine::new(80, 24);
+    /// let network = engine.event_sender();
+    /// std::thread::spawn(move || network.send(EngineEvent::Custom("PeerJoined".into())));
     /// ```
-    /// 
-    /// [`GameObject`]: crate::game_object::GameObject
+    pub fn event_sender(&self) -> EventSender {
+        self.event_bus.sender()
+    }
+
+    /// Records every input event to a file written when [`run`](Self::run) returns
+    ///
+    /// # Notes
+    /// - The log includes injected and replayed events, see [`InputLog`]
+    pub fn record_input(&mut self, path: impl Into<PathBuf>) {
+        self.input_recording = Some((InputLog::new(), path.into()));
+    }
+
+    /// Feeds a recorded input log back, each event in the frame it was recorded in
+    ///
+    /// # Notes
+    /// - Replayed events carry the [`REPLAY_SOURCE`] input source
+    /// - A headless engine stops after the last logged frame
+    /// - Replays only line up with the recorded session in deterministic mode
+    pub fn replay_input(&mut self, log: InputLog) {
+        self.input_replay = Some(log);
+    }
+
+    /// Number of frames run so far, the frame numbers of input logs
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Source of each entry in [`input_events`](Self::input_events), at the same index
+    pub fn input_sources(&self) -> &[input::InputSource] {
+        &self.input_sources
+    }
+
+    /// Applies a focus change, emitting events and muting audio when configured
+    fn set_focused(&mut self, focused: bool) {
+        if focused == self.focused {
+            return;
+        }
+        self.focused = focused;
+
+        self.sync_muted();
+        self.event_bus.emit(if focused { EngineEvent::FocusGained } else { EngineEvent::FocusLost });
+    }
+
+    /// Switches to deterministic simulation
+    ///
+    /// # Arguments
+    /// * `seed` - Seed for the engine RNG
+    /// * `timestep` - Seconds passed to every update, see [`DETERMINISTIC_TIMESTEP`]
+    ///
+    /// # Notes
+    /// - Updates use the fixed timestep regardless of real elapsed time
+    /// - Key events are always dispatched in sorted order and commands in the order issued
+    /// - Systems must draw randomness from `engine.rng` for runs to stay identical
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::engine::{Engine, DETERMINISTIC_TIMESTEP};
+    ///
+    /// let mut engine = Engine::new(80, 24);
+    /// engine.set_deterministic(1234, DETERMINISTIC_TIMESTEP);
+    /// assert!(engine.is_deterministic());
+    /// ```
+    pub fn set_deterministic(&mut self, seed: u64, timestep: f32) {
+        self.reseed(seed);
+        self.fixed_timestep = Some(timestep);
+    }
+
+    /// Replaces `rng` with a generator created from a seed, remembered for crash reports
+    pub fn reseed(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+        self.seed = seed;
+    }
+
+    /// Seed `rng` was created from, random unless set with [`reseed`](Self::reseed), [`set_deterministic`](Self::set_deterministic), or `--seed`
+    ///
+    /// # Notes
+    /// - A generator assigned to `rng` directly is not tracked
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns whether updates use a fixed timestep
+    pub fn is_deterministic(&self) -> bool {
+        self.fixed_timestep.is_some()
+    }
+
+    /// Fixed delta time of deterministic mode, `None` in real-time stepping
+    pub fn fixed_timestep(&self) -> Option<f32> {
+        self.fixed_timestep
+    }
+
+    /// Runs real-time updates at a fixed rate, independent of the render rate
+    ///
+    /// # Arguments
+    /// * `updates_per_second` - Update rate, `None` updates once per frame with the real elapsed time
+    ///
+    /// # Notes
+    /// - Several updates run in one frame when the update rate is higher than the render rate,
+    ///   and frames without an update keep their input for the next one when it is lower
+    /// - Input events of a frame are only passed to the first update in it
+    /// - Deterministic mode keeps one update per frame and ignores this rate
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::engine::Engine;
+    /// let mut engine = Engine::new(80, 24);
+    /// engine.set_update_rate(Some(120.0)); // Physics at 120 Hz
+    /// engine.set_render_rate(30.0); // Terminal output at 30 Hz
+    /// assert_eq!(engine.update_rate(), Some(120.0));
+    /// ```
+    pub fn set_update_rate(&mut self, updates_per_second: Option<f32>) {
+        self.update_rate = updates_per_second.filter(|rate| *rate > 0.0);
+        self.update_accumulator = 0.0;
+    }
+
+    /// Fixed real-time update rate, `None` when updating once per frame
+    pub fn update_rate(&self) -> Option<f32> {
+        self.update_rate
+    }
+
+    /// Sets how many frames are presented per second, see [`DEFAULT_RENDER_RATE`]
+    pub fn set_render_rate(&mut self, frames_per_second: f32) {
+        if frames_per_second > 0.0 {
+            self.pacer.set_interval(Duration::from_secs_f32(1.0 / frames_per_second));
+        }
+    }
+
+    /// Frames presented per second
+    pub fn render_rate(&self) -> f32 {
+        1.0 / self.pacer.interval().as_secs_f32()
+    }
+
+    /// Sets how the engine waits between frames, see [`pacing`](crate::pacing)
+    pub fn set_frame_pacing(&mut self, pacing: FramePacing) {
+        self.pacer.set_pacing(pacing);
+    }
+
+    /// How the engine waits between frames
+    pub fn frame_pacing(&self) -> FramePacing {
+        self.pacer.pacing()
+    }
+
+    /// Hashes the simulation state for divergence detection
+    ///
+    /// # Returns
+    /// A value identical across machines and builds when the worlds match
+    ///
+    /// # Notes
+    /// - Covers every object (see [`GameObject::hash_state`]), the RNG state, and the turn counter
+    /// - Compare hashes each frame in lockstep games or against a recorded replay
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{engine::Engine, game_object::GameObject};
+    ///
+    /// let mut local = Engine::new(20, 10);
+    /// let mut remote = Engine::new(20, 10);
+    /// for engine in [&mut local, &mut remote] {
+    ///     engine.set_deterministic(99, 1.0 / 30.0);
+    ///     engine.add_object(GameObject::new(3, 4, '@'));
+    /// }
+    /// assert_eq!(local.world_hash(), remote.world_hash());
+    ///
+    /// remote.objects[0].x += 1;
+    /// assert_ne!(local.world_hash(), remote.world_hash());
+    /// ```
+    pub fn world_hash(&self) -> u64 {
+        hash_world(&self.objects, &self.rng, self.turn)
+    }
+
+    /// Captures the world so it can be put back later with [`restore`](Self::restore)
+    ///
+    /// # Notes
+    /// - See [`WorldSnapshot`] for what is captured
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{engine::Engine, game_object::GameObject};
+    ///
+    /// let mut engine = Engine::new(20, 10);
+    /// engine.add_object(GameObject::new(3, 4, '@'));
+    /// let before_move = engine.snapshot();
+    ///
+    /// engine.objects[0].x = 9;
+    /// engine.rng.next_u64();
+    /// engine.restore(&before_move);
+    /// assert_eq!(engine.objects[0].x, 3);
+    /// assert_eq!(engine.world_hash(), before_move.world_hash());
+    /// ```
+    pub fn snapshot(&self) -> WorldSnapshot {
+        WorldSnapshot {
+            objects: self.objects.clone(),
+            components: self.components.clone(),
+            rng: self.rng.clone(),
+            turn: self.turn,
+            frame: self.frame,
+            update_accumulator: self.update_accumulator,
+            effects: self.effects.clone(),
+            camera: self.camera.clone(),
+            zones: self.zones.clone(),
+            turns: self.turns.clone(),
+            fog: self.fog.clone(),
+            level: self.levels.current().map(str::to_string),
+        }
+    }
+
+    /// Puts back a world captured by [`snapshot`](Self::snapshot) and emits `WorldRestored`
+    ///
+    /// # Notes
+    /// - Commands still queued are dropped, their object indices belong to the replaced world
+    /// - The frame counter keeps running, so input recording and replay are unaffected
+    /// - A snapshot can be restored any number of times
+    pub fn restore(&mut self, snapshot: &WorldSnapshot) {
+        self.objects = snapshot.objects.clone();
+        self.components = snapshot.components.clone();
+        self.occupancy_dirty = true;
+        self.rng = snapshot.rng.clone();
+        self.turn = snapshot.turn;
+        self.update_accumulator = snapshot.update_accumulator;
+        self.effects = snapshot.effects.clone();
+        self.camera = snapshot.camera.clone();
+        self.zones = snapshot.zones.clone();
+        self.turns = snapshot.turns.clone();
+        self.fog = snapshot.fog.clone();
+        if self.levels.current() != snapshot.level.as_deref()
+            && let Some(level) = self.levels.set_current(snapshot.level.as_deref())
+        {
+            self.renderer.set_background(level.tilemap.clone());
+        }
+        self.commands.clear();
+        self.event_bus.emit(EngineEvent::WorldRestored(snapshot.turn));
+    }
+
+    /// Starts a coroutine polled once per update, see [`task`](crate::task)
+    ///
+    /// # Returns
+    /// A handle for [`cancel_task`](Self::cancel_task) and [`is_task_running`](Self::is_task_running)
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{engine::{Engine, EngineCommand}, event::EventKind, task};
+    ///
+    /// let mut engine = Engine::new(80, 24);
+    /// let trap = engine.spawn_task(async {
+    ///     task::wait_event(EventKind::ZoneEntered).await;
+    ///     for _ in 0..3 {
+    ///         task::command(EngineCommand::ShakeCamera(1.0, 0.2));
+    ///         task::wait_secs(0.5).await;
+    ///     }
+    /// });
+    /// assert!(engine.is_task_running(trap));
+    /// ```
+    pub fn spawn_task(&mut self, task: impl Future<Output = ()> + 'static) -> TaskHandle {
+        if self.task_events.is_none() {
+            let events = Rc::new(RefCell::new(Vec::new()));
+            let sink = Rc::clone(&events);
+            self.event_bus.subscribe(move |event| sink.borrow_mut().push(event.clone()));
+            self.task_events = Some(events);
+        }
+        self.tasks.spawn(task)
+    }
+
+    /// Stops a task without running the rest of it
+    ///
+    /// # Returns
+    /// `false` when the task already finished or was cancelled
+    pub fn cancel_task(&mut self, handle: TaskHandle) -> bool {
+        self.tasks.cancel(handle)
+    }
+
+    /// Returns whether a task has neither finished nor been cancelled
+    pub fn is_task_running(&self, handle: TaskHandle) -> bool {
+        self.tasks.is_running(handle)
+    }
+
+    /// Number of running tasks
+    pub fn task_count(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Snapshot saved in a slot by `EngineCommand::SaveSnapshot`
+    ///
+    /// # Notes
+    /// - Slots let updatables and event handlers, which only queue commands, implement undo
+    pub fn saved_snapshot(&self, slot: &str) -> Option<&WorldSnapshot> {
+        self.snapshots.get(slot)
+    }
+
+    /// Input events read this frame, in the order they happened
+    ///
+    /// # Notes
+    /// - Unlike the active key set, repeated presses of one key within a frame stay separate
+    /// - Includes key releases, typed characters, and mouse input
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::{engine::Engine, input::InputEvent};
+    /// # let engine = Engine::new(80, 24);
+    /// let typed: String = engine.input_events().iter().filter_map(InputEvent::as_char).collect();
+    /// assert!(typed.is_empty()); // Nothing read before the first frame
+    /// ```
+    pub fn input_events(&self) -> &[input::InputEvent] {
+        &self.input_events
+    }
+
+    /// Returns whether the console window has keyboard focus
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn detect_key_transitions(&mut self) {
+        // Sorted so events are dispatched in the same order on every run
+        let mut active: Vec<&input::Key> = self.active_keys.iter().collect();
+        active.sort();
+        let mut previous: Vec<&input::Key> = self.previous_keys.iter().collect();
+        previous.sort();
+
+        // Detect pressed and held keys
+        for key in active {
+            if self.previous_keys.contains(key) {
+                self.event_bus.emit(EngineEvent::KeyHeld(key.clone()));
+            } else {
+                self.event_bus.emit(EngineEvent::KeyPressed(key.clone()));
+            }
+        }
+
+        // Detect released keys
+        for key in previous {
+            if !self.active_keys.contains(key) {
+                self.event_bus.emit(EngineEvent::KeyReleased(key.clone()));
+            }
+        }
+    }
+
+    /// Feeds this update's key presses to the combos of the player's bindings
+    fn match_combos(&mut self, delta_time: f32) {
+        let bindings = self.settings.bindings();
+        if bindings.combos().is_empty() {
+            return;
+        }
+        let mut pressed: Vec<input::Key> = self.active_keys.difference(&self.previous_keys).cloned().collect();
+        pressed.sort();
+        for action in self.combo_matcher.update(bindings, &pressed, &self.active_keys, delta_time) {
+            self.event_bus.emit(EngineEvent::ComboMatched(action));
+        }
+    }
+
+    /// Advances real-time simulation by the real time elapsed since the last frame
+    ///
+    /// # Returns
+    /// Seconds simulated, which effects and the camera advance by
+    fn simulate(&mut self, elapsed: f32) -> f32 {
+        if let Some(timestep) = self.fixed_timestep {
+            // Deterministic runs take exactly one update per frame so input lines up on replay
+            self.update(timestep);
+            return timestep;
+        }
+        let Some(rate) = self.update_rate else {
+            self.update(elapsed);
+            return elapsed;
+        };
+        let step = 1.0 / rate;
+
+        self.update_accumulator = (self.update_accumulator + elapsed).min(MAX_CATCH_UP);
+        let mut simulated = 0.0;
+        while self.update_accumulator >= step {
+            self.update(step);
+            self.update_accumulator -= step;
+            simulated += step;
+        }
+        simulated
+    }
+
+    fn update(&mut self, delta_time: f32) {
+        let delta_time = self.time.scaled(delta_time);
+        if self.quest_events.is_none() && !self.quests.is_empty() {
+            let events = Rc::new(RefCell::new(Vec::new()));
+            let sink = Rc::clone(&events);
+            self.event_bus.subscribe(move |event| sink.borrow_mut().push(event.clone()));
+            self.quest_events = Some(events);
+        }
+        self.time.advance(delta_time);
+        self.detect_key_transitions();
+        self.match_combos(delta_time);
+        self.previous_keys = self.active_keys.clone();
+        // Games may change objects directly between updates
+        self.occupancy_dirty = true;
+        
+        // Clear previous commands
+        self.commands.clear();
+
+        // Process animations.
+        let animation_start = Instant::now();
+        let min_frame_duration = self.renderer.accessibility().min_frame_duration;
+        for (index, obj) in self.objects.iter_mut().enumerate().filter(|(_, obj)| obj.active) {
+            if obj.advance_animation_paced(delta_time, min_frame_duration) {
+                self.event_bus.emit(EngineEvent::AnimationLooped(index));
+            }
+        }
+        self.frame_timings.animation = animation_start.elapsed();
+
+        // Despawn objects whose lifetime ran out, highest index first so earlier indices stay valid.
+        let expired: Vec<usize> = self.objects
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, obj)| (obj.active && obj.tick_lifetime(delta_time)).then_some(index))
+            .collect();
+        for index in expired.into_iter().rev() {
+            self.despawn_object(index);
+        }
+
+        // Count down the combo window.
+        self.score.update(delta_time);
+        self.emit_score_events();
+
+        // Tick status effects and stat modifiers.
+        for (index, obj) in self.objects.iter_mut().enumerate().filter(|(_, obj)| obj.active) {
+            for name in obj.status_effects.tick(delta_time) {
+                self.event_bus.emit(EngineEvent::EffectExpired(index, name));
+            }
+            obj.stats.tick(delta_time);
+        }
+
+        // Walk objects along their paths, stunned objects stand still.
+        for index in 0..self.objects.len() {
+            let obj = &mut self.objects[index];
+            if !obj.active || obj.status_effects.is_stunned() {
+                continue;
+            }
+            let Some(path) = obj.path.as_mut() else {
+                continue;
+            };
+            let steps = path.advance(obj.x, obj.y, delta_time * obj.status_effects.speed_multiplier());
+            if path.is_finished() {
+                obj.path = None;
+            }
+            for step in steps {
+                match step {
+                    PathStep::Move(dx, dy) => self.move_object(index, dx, dy),
+                    PathStep::WaypointReached(waypoint) => self.event_bus.emit(EngineEvent::WaypointReached(index, waypoint)),
+                    PathStep::Completed => self.event_bus.emit(EngineEvent::PathCompleted(index)),
+                }
+            }
+        }
+
+        // Fly projectiles, highest index first so despawns keep the indices still to visit valid.
+        for index in (0..self.objects.len()).rev() {
+            let obj = &mut self.objects[index];
+            if !obj.active {
+                continue;
+            }
+            let Some(projectile) = obj.projectile.as_mut() else {
+                continue;
+            };
+            for step in projectile.advance(delta_time) {
+                if !self.step_projectile(index, step) {
+                    break;
+                }
+            }
+        }
+
+        // Place network-controlled objects along their buffered positions.
+        for obj in self.objects.iter_mut().filter(|obj| obj.active) {
+            let Some((x, y)) = obj.interpolation.as_mut().and_then(|interpolation| interpolation.advance(delta_time)) else {
+                continue;
+            };
+            if (obj.x, obj.y) != (x, y) {
+                obj.x = x;
+                obj.y = y;
+                self.occupancy_dirty = true;
+            }
+        }
+
+        // Run all registered updatable system.
+        self.frame_timings.updatables.clear();
+        self.refresh_occupancy();
+        let no_keys = HashSet::new();
+        for updatable in &mut self.updatables {
+            let updatable_start = Instant::now();
+            let receives_input = self.input_contexts.is_active(updatable.input_context());
+            let fresh_input = receives_input && !self.input_handled;
+            let mut ctx = UpdateContext {
+                delta_time,
+                active_keys: if receives_input { &self.active_keys } else { &no_keys },
+                input_events: if fresh_input { &self.input_events } else { &[] },
+                input_sources: if fresh_input { &self.input_sources } else { &[] },
+                objects: &self.objects,
+                rng: &mut self.rng,
+                event_bus: &self.event_bus,
+                camera: &self.camera,
+                width: self.renderer.get_width(),
+                height: self.renderer.get_height(),
+                turn: self.turn,
+                settings: &self.settings,
+                components: &mut self.components,
+                mouse: self.mouse_position,
+                hovered: self.hovered,
+                occupancy: &self.occupancy,
+                score: &self.score,
+                resources: &mut self.resources,
+                time: &self.time,
+                input_contexts: &self.input_contexts,
+            };
+            let new_commands = updatable.update_with_context(&mut ctx);
+            self.frame_timings.updatables.push((updatable.name().to_string(), updatable_start.elapsed()));
+            self.commands.extend(new_commands);
+        }
+
+        // Run per-object behaviors.
+        let behaviors_start = Instant::now();
+        let behavior_keys = if self.input_contexts.is_active(input_context::GAMEPLAY) { &self.active_keys } else { &no_keys };
+        for obj in self.objects.iter_mut().filter(|obj| obj.active && !obj.status_effects.is_stunned()) {
+            let mut behaviors = std::mem::take(&mut obj.behaviors);
+            for behavior in &mut behaviors {
+                let new_commands = behavior.update(obj, delta_time, behavior_keys);
+                self.commands.extend(new_commands);
+            }
+            // Keep any behaviors attached during the update.
+            behaviors.append(&mut obj.behaviors);
+            obj.behaviors = behaviors;
+        }
+        self.frame_timings.updatables.push(("behaviors".to_string(), behaviors_start.elapsed()));
+
+        // Advance tasks, their commands are applied with the rest
+        if let Some(events) = &self.task_events {
+            let tasks_start = Instant::now();
+            let events = events.take();
+            if !self.tasks.is_empty() {
+                self.commands.extend(self.tasks.poll(delta_time, events));
+                self.frame_timings.updatables.push(("tasks".to_string(), tasks_start.elapsed()));
+            }
+        }
+
+        // Process all queued commands, including those queued by event handlers since the last update
+        let commands_start = Instant::now();
+        let mut commands = self.event_bus.take_commands();
+        commands.append(&mut self.commands);
+        for command in commands {
+            self.apply_command(command);
+        }
+        for (index, obj) in self.objects.iter_mut().enumerate() {
+            for (stat, value) in obj.stats.take_changes() {
+                self.event_bus.emit(EngineEvent::StatChanged(index, stat, value));
+            }
+        }
+        for event in self.zones.evaluate(&self.objects) {
+            self.event_bus.emit(event);
+        }
+        if self.turns.current().is_none() && let Some(actor) = self.turns.begin_turn() {
+            self.event_bus.emit(EngineEvent::TurnStarted(actor));
+        }
+        if let Some(exit) = self.levels.triggered_exit(&self.objects) {
+            self.enter_level(&exit.target, Some(exit.spawn));
+        }
+        self.refresh_fog();
+        // Quest updates emitted here are observed in turn by the next update
+        if let Some(events) = &self.quest_events {
+            for event in events.take() {
+                self.quests.observe(&event);
+            }
+            self.emit_quest_events();
+        }
+        let bounds = self.camera.world_size().unwrap_or((self.renderer.get_width(), self.renderer.get_height()));
+        for diagnostic in self.diagnostics.check(&self.objects, bounds) {
+            self.event_bus.emit(EngineEvent::DiagnosticReported(diagnostic));
+        }
+        self.frame_timings.commands = commands_start.elapsed();
+        self.input_handled = true;
+    }
+
+    fn apply_command(&mut self, command: EngineCommand) {
+        let bounds = self.camera.world_size().unwrap_or((self.renderer.get_width(), self.renderer.get_height()));
+        if let Err(error) = self.validation.check(&command, &self.objects, bounds) {
+            self.reject_command(command.name(), error);
+            return;
+        }
+        if self.crash_dir.is_some() {
+            if self.recent_commands.len() == DEFAULT_COMMAND_HISTORY {
+                self.recent_commands.pop_front();
+            }
+            let target = command.target().map(|index| format!(" {index}")).unwrap_or_default();
+            self.recent_commands.push_back(format!("frame {} {}{target}", self.frame, command.name()));
+        }
+        if let Some(log) = &mut self.command_log {
+            log.push(CommandRecord { frame: self.frame, name: command.name(), target: command.target(), text: format!("{command:?}") });
+        }
+        match command {
+            EngineCommand::SpawnObject(obj) => self.add_object(obj),
+            EngineCommand::DespawnObject(index) => self.despawn_object(index),
+            EngineCommand::MoveObject(index, dx, dy) => self.move_object(index, dx, dy),
+            EngineCommand::MoveGroup(selector, dx, dy) => {
+                for index in self.group_members(&selector) {
+                    self.move_object(index, dx, dy);
+                }
+            },
+            EngineCommand::FaceObject(index, direction) => {
+                if let Some(obj) = self.objects.get_mut(index) {
+                    obj.face(direction);
+                }
+            },
+            EngineCommand::DespawnGroup(selector) => {
+                // Highest index first so earlier indices stay valid
+                for index in self.group_members(&selector).into_iter().rev() {
+                    self.despawn_object(index);
+                }
+            },
+            EngineCommand::SetGroupVisible(selector, visible) => {
+                for obj in self.objects.iter_mut().filter(|obj| selector.matches(obj)) {
+                    obj.visible = visible;
+                }
+            },
+            EngineCommand::SetVisible(index, visible) => {
+                if let Some(obj) = self.objects.get_mut(index) {
+                    obj.visible = visible;
+                }
+            },
+            EngineCommand::SetActive(index, active) => {
+                if let Some(obj) = self.objects.get_mut(index) {
+                    obj.active = active;
+                    self.occupancy_dirty = true;
+                }
+            },
+            EngineCommand::SetGroupActive(selector, active) => {
+                for obj in self.objects.iter_mut().filter(|obj| selector.matches(obj)) {
+                    obj.active = active;
+                }
+                self.occupancy_dirty = true;
+            },
+            EngineCommand::ApplyEffect(index, effect) => {
+                if let Some(obj) = self.objects.get_mut(index) {
+                    let name = effect.name.clone();
+                    obj.status_effects.apply(effect);
+                    self.event_bus.emit(EngineEvent::EffectApplied(index, name));
+                }
+            },
+            EngineCommand::RemoveEffect(index, name) => {
+                if self.objects.get_mut(index).is_some_and(|obj| obj.status_effects.remove(&name)) {
+                    self.event_bus.emit(EngineEvent::EffectRemoved(index, name));
+                }
+            },
+            EngineCommand::SetStat(index, stat, value) => {
+                if let Some(obj) = self.objects.get_mut(index) {
+                    obj.stats.set_base(&stat, value);
+                }
+            },
+            EngineCommand::AddModifier(index, modifier) => {
+                if let Some(obj) = self.objects.get_mut(index) {
+                    obj.stats.add_modifier(modifier);
+                }
+            },
+            EngineCommand::RemoveModifiers(index, source) => {
+                if let Some(obj) = self.objects.get_mut(index) {
+                    obj.stats.remove_source(&source);
+                }
+            },
+            EngineCommand::EquipItem(index, item) => {
+                let Some(obj) = self.objects.get_mut(index) else {
+                    return;
+                };
+                if let Ok((slot, previous)) = obj.equipment.equip(&item, &self.items, &mut obj.stats) {
+                    if let Some(previous) = previous {
+                        self.event_bus.emit(EngineEvent::ItemUnequipped(index, slot.clone(), previous));
+                    }
+                    self.event_bus.emit(EngineEvent::ItemEquipped(index, slot, item));
+                }
+            },
+            EngineCommand::UnequipItem(index, slot) => {
+                let Some(obj) = self.objects.get_mut(index) else {
+                    return;
+                };
+                if let Some(item) = obj.equipment.unequip(&slot, &mut obj.stats) {
+                    self.event_bus.emit(EngineEvent::ItemUnequipped(index, slot, item));
+                }
+            },
+            EngineCommand::UseItem(index, item) => {
+                let Some(def) = self.items.get(&item) else {
+                    return;
+                };
+                for effect in def.use_effects.clone() {
+                    match effect {
+                        ItemEffect::Status(effect) => self.apply_command(EngineCommand::ApplyEffect(index, effect)),
+                        ItemEffect::Modifier(modifier) => self.apply_command(EngineCommand::AddModifier(index, modifier)),
+                        ItemEffect::Event(text) => self.event_bus.emit(EngineEvent::Custom(text)),
+                    }
+                }
+                self.event_bus.emit(EngineEvent::ItemActivated(index, item));
+            },
+            EngineCommand::FollowPath(index, path) => {
+                if let Some(obj) = self.objects.get_mut(index) {
+                    obj.path = Some(path);
+                }
+            },
+            EngineCommand::StopPath(index) => {
+                if let Some(obj) = self.objects.get_mut(index) {
+                    obj.path = None;
+                }
+            },
+            EngineCommand::PushPage(page) => self.push_page(page),
+            EngineCommand::PopPage => {
+                self.pop_page();
+            },
+            EngineCommand::OpenPauseMenu => self.open_pause_menu(),
+            EngineCommand::ClosePauseMenu => self.close_pause_menu(),
+            EngineCommand::SetLanguage(language) => {
+                self.set_language(&language);
+            },
+            EngineCommand::AddZone(zone) => self.zones.add(zone),
+            EngineCommand::RemoveZone(id) => {
+                self.zones.remove(&id);
+            },
+            EngineCommand::PlaySound(name) => {
+                // A missing or failed sound should never interrupt the game
+                let _ = self.audio.play(&name);
+            },
+            EngineCommand::SetWeather(weather) => self.set_weather(weather),
+            EngineCommand::SetTimeScale(scale) => self.set_time_scale(scale),
+            EngineCommand::ResetSceneTime => self.reset_scene_time(),
+            EngineCommand::SpawnFloatingText(text) => self.spawn_floating_text(text),
+            EngineCommand::ShakeCamera(intensity, duration) => self.camera.shake(intensity, duration),
+            EngineCommand::CameraFollow(tag, lerp) => self.camera.follow(&tag, lerp),
+            EngineCommand::CameraZoomToRegion(x, y, width, height) => {
+                self.camera.zoom_to_region(x, y, width, height, self.renderer.get_width(), self.renderer.get_height());
+            },
+            EngineCommand::CameraResetZoom => self.camera.reset_zoom(),
+            EngineCommand::IncrementStat(name, amount) => {
+                self.stats.add(&name, amount);
+                self.emit_unlocked_achievements();
+            },
+            EngineCommand::SetStatFlag(name, value) => {
+                self.stats.set_flag(&name, value);
+                self.emit_unlocked_achievements();
+            },
+            EngineCommand::AddScore(points, source) => {
+                self.score.add(points, &source);
+                self.emit_score_events();
+            },
+            EngineCommand::BreakCombo => {
+                self.score.break_combo();
+                self.emit_score_events();
+            },
+            EngineCommand::StartQuest(id) => {
+                self.quests.start(&id);
+                self.emit_quest_events();
+            },
+            EngineCommand::AdvanceQuest(id) => {
+                self.quests.advance(&id);
+                self.emit_quest_events();
+            },
+            EngineCommand::FailQuest(id) => {
+                self.quests.fail(&id);
+                self.emit_quest_events();
+            },
+            EngineCommand::LoadLevel(name) => {
+                self.load_level(&name);
+            },
+            EngineCommand::SaveSnapshot(slot) => {
+                let snapshot = self.snapshot();
+                self.snapshots.insert(slot, snapshot);
+            },
+            EngineCommand::RestoreSnapshot(slot) => {
+                if let Some(snapshot) = self.snapshots.remove(&slot) {
+                    self.restore(&snapshot);
+                    self.snapshots.insert(slot, snapshot);
+                }
+            },
+            EngineCommand::PushInputContext(context) => self.input_contexts.push(context),
+            EngineCommand::PopInputContext => {
+                self.input_contexts.pop();
+            },
+            EngineCommand::Narrate(line) => {
+                if let Some(narrator) = &self.narrator {
+                    narrator.say(&line);
+                }
+            },
+            EngineCommand::EndTurn(index, cost) => {
+                if self.turns.current() == Some(&index) {
+                    self.turns.end_turn(cost);
+                    self.event_bus.emit(EngineEvent::TurnEnded(index));
+                }
+            },
+            EngineCommand::BeginLongAction(index, turns) => {
+                if self.turns.current() == Some(&index) {
+                    self.turns.end_turn(0);
+                    self.turns.spend_over(&index, turns);
+                    self.event_bus.emit(EngineEvent::TurnEnded(index));
+                }
+            },
+            EngineCommand::Quit => self.stop(),
+        }
+    }
+
+    /// Emits `AchievementUnlocked` for achievements unlocked since the last check
+    fn emit_unlocked_achievements(&mut self) {
+        for id in self.stats.take_unlocked() {
+            self.event_bus.emit(EngineEvent::AchievementUnlocked(id));
+        }
+    }
+
+    /// Emits `ScoreAdded`, `ScoreMilestone`, and `ComboEnded` for what happened to the score since the last check
+    fn emit_score_events(&mut self) {
+        for event in self.score.take_events() {
+            self.event_bus.emit(match event {
+                ScoreEvent::Added(points, source) => EngineEvent::ScoreAdded(points, source),
+                ScoreEvent::Milestone(milestone) => EngineEvent::ScoreMilestone(milestone),
+                ScoreEvent::ComboEnded(combo) => EngineEvent::ComboEnded(combo),
+            });
+        }
+    }
+
+    /// Emits `QuestStarted`, `QuestAdvanced`, `QuestCompleted`, and `QuestFailed` for what happened to quests since the last check
+    fn emit_quest_events(&mut self) {
+        for event in self.quests.take_events() {
+            self.event_bus.emit(match event {
+                QuestEvent::Started(id) => EngineEvent::QuestStarted(id),
+                QuestEvent::Advanced(id, stage) => EngineEvent::QuestAdvanced(id, stage),
+                QuestEvent::Completed(id) => EngineEvent::QuestCompleted(id),
+                QuestEvent::Failed(id) => EngineEvent::QuestFailed(id),
+            });
+        }
+    }
+
+    /// Loads a registered level at its spawn point and emits `LevelChanged`
+    ///
+    /// # Returns
+    /// Whether a level with the name was registered
+    ///
+    /// # Notes
+    /// - Objects without a tracked tag are despawned, highest index first, each emitting `ObjectDespawned`
+    /// - Tracked objects keep their order at the front of the object list, all placed on the spawn point,
+    ///   and the level's objects are spawned after them
+    /// - The level's tilemap becomes the background and its size the camera's world
+    pub fn load_level(&mut self, name: &str) -> bool {
+        self.enter_level(name, None)
+    }
+
+    /// Swaps in a level, placing tracked objects on `spawn` or else the level's spawn point
+    fn enter_level(&mut self, name: &str, spawn: Option<(usize, usize)>) -> bool {
+        let previous = self.levels.current().map(str::to_string);
+        let Some(level) = self.levels.enter(name).cloned() else {
+            return false;
+        };
+        // Each level keeps its explored cells while another one is loaded
+        if let Some(previous) = previous.and_then(|previous| self.levels.get_mut(&previous)) {
+            previous.fog = self.fog.take();
+        }
+        self.fog = self.levels.get_mut(name).and_then(|current| current.fog.take());
+        let (spawn_x, spawn_y) = spawn.unwrap_or(level.spawn);
+        self.levels.set_arrival((spawn_x, spawn_y));
+
+        for index in (0..self.objects.len()).rev() {
+            if !self.levels.is_tracked(&self.objects[index].tag) {
+                self.despawn_object(index);
+            }
+        }
+        for obj in &mut self.objects {
+            obj.x = spawn_x;
+            obj.y = spawn_y;
+        }
+        self.occupancy_dirty = true;
+
+        self.camera.set_world_size(level.tilemap.width(), level.tilemap.height());
+        self.renderer.set_background(level.tilemap);
+        for obj in level.objects {
+            self.add_object(obj);
+        }
+        self.event_bus.emit(EngineEvent::LevelChanged(level.name));
+        true
+    }
+
+    /// Moves an object by a delta, clamped to the camera's world or else the render area, and emits `ObjectMoved`
+    ///
+    /// # Notes
+    /// - Diagonal deltas move on both axes at once, the object turns to face the move even when clamped or blocked
+    /// - Under a blocking move policy, solid objects push or are stopped by other solid objects, see [`crate::occupancy`]
+    fn move_object(&mut self, index: usize, dx: i32, dy: i32) {
+        let Some(obj) = self.objects.get_mut(index) else {
+            return;
+        };
+        if let Some(direction) = Direction::from_delta(dx, dy) {
+            obj.face(direction);
+        }
+        if self.move_policy == MovePolicy::Overlap || !obj.solid || !obj.active {
+            let (x, y) = self.destination(index, dx, dy);
+            self.place_object(index, x, y);
+            return;
+        }
+
+        if self.try_move(index, dx, dy, true) {
+            return;
+        }
+        let slid = self.move_policy == MovePolicy::Slide
+            && dx != 0
+            && dy != 0
+            && (self.try_move(index, dx, 0, true) || self.try_move(index, 0, dy, true));
+        if !slid {
+            let (x, y) = self.destination(index, dx, dy);
+            self.event_bus.emit(EngineEvent::MoveBlocked(index, x, y));
+        }
+    }
+
+    /// Cell an existing object would move to, clamped to the camera's world or else the render area
+    fn destination(&self, index: usize, dx: i32, dy: i32) -> (usize, usize) {
+        let (width, height) = self.camera.world_size().unwrap_or((self.renderer.get_width(), self.renderer.get_height()));
+        let obj = &self.objects[index];
+        let x = (obj.x as i32 + dx).clamp(0, width as i32 - 1) as usize;
+        let y = (obj.y as i32 + dy).clamp(0, height as i32 - 1) as usize;
+        (x, y)
+    }
+
+    /// Moves a solid object unless other solid objects hold the destination
+    ///
+    /// # Arguments
+    /// * `push` - Push a single pushable blocker one step along, pushed objects do not push further
+    ///
+    /// # Returns
+    /// Whether the object moved
+    fn try_move(&mut self, index: usize, dx: i32, dy: i32, push: bool) -> bool {
+        self.refresh_occupancy();
+        let (x, y) = self.destination(index, dx, dy);
+        let blockers = self.occupancy.blockers(index, &self.objects[index], x, y);
+        match blockers[..] {
+            [] => {},
+            [blocker] if push && self.objects[blocker].pushable => {
+                // A block against the edge of the world stays put
+                let blocker_at = (self.objects[blocker].x, self.objects[blocker].y);
+                if self.destination(blocker, dx, dy) == blocker_at || !self.try_move(blocker, dx, dy, false) {
+                    return false;
+                }
+            },
+            _ => return false,
+        }
+        self.place_object(index, x, y);
+        true
+    }
+
+    /// Sets an object's position, keeping the occupancy map current, and emits `ObjectMoved`
+    fn place_object(&mut self, index: usize, x: usize, y: usize) {
+        let obj = &mut self.objects[index];
+        if !self.occupancy_dirty {
+            self.occupancy.remove(index, obj);
+        }
+        obj.x = x;
+        obj.y = y;
+        if !self.occupancy_dirty {
+            self.occupancy.insert(index, obj);
+        }
+        self.event_bus.emit(EngineEvent::ObjectMoved(index, x, y));
+    }
+
+    /// Applies one step of a projectile, hitting solid objects and bouncing off world edges
+    ///
+    /// # Returns
+    /// Whether the projectile keeps flying this update
+    fn step_projectile(&mut self, index: usize, step: ProjectileStep) -> bool {
+        let ProjectileStep::Move(dx, dy) = step else {
+            self.expire_projectile(index);
+            return false;
+        };
+        let (x, y) = self.destination(index, dx, dy);
+        let (from_x, from_y) = (self.objects[index].x, self.objects[index].y);
+        let (wanted_x, wanted_y) = (from_x as i32 + dx, from_y as i32 + dy);
+        if (x as i32, y as i32) != (wanted_x, wanted_y) {
+            let bounced = self.objects[index]
+                .projectile
+                .as_mut()
+                .is_some_and(|projectile| projectile.hit_edge(x as i32 != wanted_x, y as i32 != wanted_y));
+            if !bounced {
+                self.expire_projectile(index);
+            }
+            return false;
+        }
+
+        self.refresh_occupancy();
+        let obj = &self.objects[index];
+        let Some(projectile) = obj.projectile.as_ref() else {
+            return false;
+        };
+        let hittable = |blockers: Vec<usize>| blockers.into_iter().find(|&blocker| projectile.can_hit(blocker));
+        let Some(target) = hittable(self.occupancy.blockers(index, obj, x, y)) else {
+            self.place_object(index, x, y);
+            return true;
+        };
+        // A bounce reverses the axes whose neighbouring cell is blocked, both for a corner
+        let flip_x = dx != 0 && hittable(self.occupancy.blockers(index, obj, x, from_y)).is_some();
+        let flip_y = dy != 0 && hittable(self.occupancy.blockers(index, obj, from_x, y)).is_some();
+
+        self.event_bus.emit(EngineEvent::ProjectileHit(index, target));
+        let outcome = self.objects[index].projectile.as_mut().map_or(HitOutcome::Despawn, |projectile| projectile.hit(target, flip_x, flip_y));
+        match outcome {
+            HitOutcome::Despawn => {
+                self.despawn_object(index);
+                false
+            },
+            HitOutcome::PassThrough => {
+                self.place_object(index, x, y);
+                true
+            },
+            HitOutcome::Bounced => false,
+        }
+    }
+
+    /// Emits `ProjectileExpired` and despawns the projectile
+    fn expire_projectile(&mut self, index: usize) {
+        self.event_bus.emit(EngineEvent::ProjectileExpired(index));
+        self.despawn_object(index);
+    }
+
+    /// Rebuilds the occupancy map if objects changed since it was last built
+    fn refresh_occupancy(&mut self) {
+        if self.occupancy_dirty {
+            self.occupancy = OccupancyMap::from_objects(&self.objects);
+            self.occupancy_dirty = false;
+        }
+    }
+
+    /// Cells covered by solid objects, rebuilt first when objects changed
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{engine::Engine, game_object::GameObject};
+    ///
+    /// let mut engine = Engine::new(20, 10);
+    /// engine.add_object(GameObject::new(5, 5, '#').with_solid(true));
+    /// assert!(!engine.occupancy().is_free(5, 5));
+    /// ```
+    pub fn occupancy(&mut self) -> &OccupancyMap {
+        self.refresh_occupancy();
+        &self.occupancy
+    }
+
+    /// Recomputes what the fog's viewers see, done after each update and before each frame
+    ///
+    /// # Notes
+    /// - Call it after moving viewers outside an update, to query the fog right away
+    pub fn refresh_fog(&mut self) {
+        if let Some(fog) = &mut self.fog {
+            fog.refresh(&self.objects);
+        }
+    }
+
+    /// Sets how moves of solid objects into occupied cells resolve
+    pub fn set_move_policy(&mut self, policy: MovePolicy) {
+        self.move_policy = policy;
+    }
+
+    /// Returns how moves of solid objects into occupied cells resolve
+    pub fn move_policy(&self) -> MovePolicy {
+        self.move_policy
+    }
+
+    fn render(&mut self) {
+        self.renderer.set_view(self.camera.view());
+        self.renderer.clear_back_buffer();
+
+        // Objects and effects live in the world, overlays are drawn on the screen
+        self.renderer.set_world_space(true);
+        for obj in self.objects.iter().filter(|obj| obj.visible) {
+            match obj.current_sprite() {
+                Some(sprite) => self.renderer.draw_sprite(obj.x, obj.y, sprite),
+                None => self.renderer.set_char(obj.x, obj.y, obj),
+            }
+        }
+        self.effects.draw(&mut self.renderer);
+        self.refresh_fog();
+        if let Some(fog) = &self.fog {
+            self.renderer.draw_fog(fog);
+        }
+        self.renderer.set_world_space(false);
+        if let Some(weather) = &self.weather {
+            weather.draw(&mut self.renderer);
+        }
+
+        for updatable in &self.updatables {
+            updatable.draw(&mut self.renderer);
+        }
+        if let Some(menu) = self.pause_menu.as_ref().filter(|_| self.pause_menu_open) {
+            menu.draw(&mut self.renderer);
+        }
+        if let Some(page) = self.pages.last() {
+            page.draw(&mut self.renderer);
+        }
+
+        self.apply_transition();
+
+        if self.profiler_overlay {
+            for (row, line) in self.profiler.profile().breakdown_lines().iter().enumerate() {
+                self.renderer.draw_text(0, row, line);
+            }
+        }
+        if self.event_overlay {
+            self.draw_event_overlay();
+        }
+        self.diagnostics.draw(&mut self.renderer);
+        if let (Some(cursor), Some((x, y))) = (&self.cursor, self.mouse_position) {
+            cursor.draw(&mut self.renderer, x, y);
+        }
+
+        let _ = self.renderer.present();
+
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.capture(self.renderer.screenshot());
+        }
+    }
+
+    /// Starts capturing every presented frame, discarding any recording in progress
+    pub fn start_recording(&mut self) {
+        self.recorder = Some(Recorder::new());
+    }
+
+    /// Stops capturing frames
+    ///
+    /// # Returns
+    /// The finished recording, `None` when not recording
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::engine::Engine;
+    /// # let mut engine = Engine::new(80, 24);
+    /// engine.start_recording();
+    /// // ... play ...
+    /// if let Some(clip) = engine.stop_recording() {
+    ///     clip.save_asciicast("gameplay.cast").expect("Could not save recording");
+    /// }
+    /// ```
+    pub fn stop_recording(&mut self) -> Option<Recorder> {
+        self.recorder.take()
+    }
+
+    /// Returns whether frames are being recorded
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Binds a key that starts recording and, on the next press, saves an asciinema cast
+    ///
+    /// # Arguments
+    /// * `key` - Toggle key, pick one the game does not use
+    /// * `path` - Cast file written when recording stops, overwritten each time
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::{engine::Engine, input::Key};
+    /// # let mut engine = Engine::new(80, 24);
+    /// engine.set_recording_hotkey(Key::Char('`'), "clip.cast");
+    /// ```
+    pub fn set_recording_hotkey(&mut self, key: input::Key, path: impl Into<PathBuf>) {
+        self.recording_hotkey = Some((key, path.into()));
+    }
+
+    /// Removes the recording hotkey
+    pub fn clear_recording_hotkey(&mut self) {
+        self.recording_hotkey = None;
+    }
+
+    /// Applies the active transition to the composed frame
+    fn apply_transition(&mut self) {
+        let Some(transition) = self.transition.as_mut() else {
+            return;
+        };
+
+        let was_finished = transition.is_finished();
+        transition.apply(&mut self.renderer, &mut self.rng);
+
+        if !was_finished && transition.is_finished() {
+            self.event_bus.emit(EngineEvent::TransitionFinished);
+            // Finished fade-outs keep the screen covered until the next transition
+            if transition.direction() == TransitionDirection::In {
+                self.transition = None;
+            }
+        }
+    }
+
+    /// Starts a full-screen transition, replacing any active one
+    ///
+    /// # Arguments
+    /// * `transition` - Effect to run, see [`Transition`]
+    ///
+    /// # Notes
+    /// - `EngineEvent::TransitionFinished` is emitted after the last frame
+    /// - After an `Out` transition the screen stays covered until another transition
+    ///   starts or [`Engine::clear_transition`] is called
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::{engine::Engine, transition::Transition};
+    /// # let mut engine = Engine::new(80, 24);
+    /// engine.start_transition(Transition::dissolve_out(20));
+    /// ```
+    pub fn start_transition(&mut self, transition: Transition) {
+        self.transition = Some(transition);
+    }
+
+    /// Removes any active or held transition, showing the scene immediately
+    pub fn clear_transition(&mut self) {
+        self.transition = None;
+    }
+
+    /// Returns whether a transition is still animating
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.as_ref().is_some_and(|transition| !transition.is_finished())
+    }
+
+    /// Records the finished frame's timings into the profiler
+    ///
+    /// # Arguments
+    /// * `total` - Time the frame took before the frame limiter wait
+    fn finish_frame_profile(&mut self, total: Duration) {
+        let mut timings = std::mem::take(&mut self.frame_timings);
+        timings.events = self.event_bus.take_dispatch_time();
+        timings.total = total;
+        timings.frame_time = self.pacer.last_frame_time();
+        timings.jitter = self.pacer.last_jitter();
+
+        let active = self.objects.iter().filter(|obj| obj.active).count();
+        self.metrics.record(timings.frame_time, total, self.objects.len(), active, self.event_bus.frame_events() as u64);
+        self.export_metrics(timings.frame_time.as_secs_f32());
+        self.profiler.record(timings);
+    }
+
+    /// Writes metrics when the dump interval elapsed and answers waiting metrics requests
+    fn export_metrics(&mut self, delta_time: f32) {
+        if let Some(dump) = self.metrics_dump.as_mut() && dump.tick(delta_time) {
+            // Monitoring must not stop the game, a failed write is retried next interval
+            let _ = dump.write(&self.metrics.metrics());
+        }
+        if let Some(server) = &self.metrics_server {
+            server.publish(self.metrics.metrics());
+        }
+    }
+
+    /// Frame rate, frame time percentiles, object counts, and event throughput over recent frames
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::engine::Engine;
+    /// let engine = Engine::new(80, 24);
+    /// let metrics = engine.metrics();
+    /// println!("{:.1} fps, p99 {:.2}ms", metrics.fps, metrics.frame_time_p99_ms);
+    /// ```
+    pub fn metrics(&self) -> Metrics {
+        self.metrics.metrics()
+    }
+
+    /// Writes metrics to a file every `interval` seconds and when [`run`](Self::run) returns
+    ///
+    /// # Arguments
+    /// * `path` - File replaced on every write, Prometheus text for `.prom` and `.txt`, JSON otherwise
+    /// * `interval` - Seconds between writes
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::{engine::Engine, metrics::DEFAULT_DUMP_INTERVAL};
+    /// let mut engine = Engine::builder(80, 24).server(20.0).build();
+    /// // Picked up by the node exporter's textfile collector
+    /// engine.dump_metrics("/var/lib/node_exporter/game.prom", DEFAULT_DUMP_INTERVAL);
+    /// engine.run();
+    /// ```
+    pub fn dump_metrics(&mut self, path: impl Into<PathBuf>, interval: f32) {
+        self.metrics_dump = Some(MetricsDump::new(path, interval));
+    }
+
+    /// Answers HTTP requests for metrics on an address from a background thread, see [`MetricsServer`]
+    ///
+    /// # Notes
+    /// - The snapshot served is refreshed every frame
+    /// - Metrics reveal nothing secret, but binding to `0.0.0.0` lets anyone on the network scrape them
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::engine::Engine;
+    /// let mut engine = Engine::builder(80, 24).server(20.0).build();
+    /// engine.serve_metrics("127.0.0.1:9100").expect("metrics port in use");
+    /// engine.run();
+    /// ```
+    pub fn serve_metrics(&mut self, address: impl ToSocketAddrs) -> io::Result<()> {
+        self.metrics_server = Some(MetricsServer::bind(address)?);
+        Ok(())
+    }
+
+    /// Returns per-phase timings for the last frame and a rolling average
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::engine::Engine;
+    /// # let engine = Engine::new(80, 24);
+    /// let profile = engine.frame_profile();
+    /// for (name, time) in &profile.average.updatables {
+    ///     println!("{name}: {time:?}");
+    /// }
+    /// ```
+    pub fn frame_profile(&self) -> &FrameProfile {
+        self.profiler.profile()
+    }
+
+    /// Game, scene, and real time clocks, see [`time`](crate::time)
+    pub fn time(&self) -> &GameTime {
+        &self.time
+    }
+
+    /// Sets how fast game time runs, `0.5` for slow motion and `0.0` to freeze
+    ///
+    /// # Notes
+    /// - Scales the delta time of every update and of effects, audio and real time are unaffected
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::engine::Engine;
+    /// let mut engine = Engine::new(80, 24);
+    /// engine.set_time_scale(0.25);
+    /// assert_eq!(engine.time().scale(), 0.25);
+    /// ```
+    pub fn set_time_scale(&mut self, scale: f32) {
+        self.time.set_scale(scale);
+    }
+
+    /// Restarts the scene clock from zero, typically when a level starts
+    pub fn reset_scene_time(&mut self) {
+        self.time.reset_scene();
+    }
+
+    /// What the terminal can show, detected when the engine was created
+    ///
+    /// # Notes
+    /// - Output is adapted automatically, this is for styling decisions such as picking
+    ///   RGB or palette colors, or a layout fitting the terminal size
+    /// - Override with `engine.renderer.set_capabilities`
+    pub fn capabilities(&self) -> Capabilities {
+        self.renderer.capabilities()
+    }
+
+    /// Sets the cursor drawn at the mouse cell over everything else, `None` hides it
+    pub fn set_cursor(&mut self, cursor: Option<Cursor>) {
+        self.cursor = cursor;
+    }
+
+    /// Screen cell of the last mouse event, `None` before the mouse was used
+    pub fn mouse_position(&self) -> Option<(usize, usize)> {
+        self.mouse_position
+    }
+
+    /// Index of the topmost visible object under the mouse, as of the last frame
+    pub fn hovered_object(&self) -> Option<usize> {
+        self.hovered
+    }
+
+    /// Topmost visible object drawn at a screen cell
+    ///
+    /// # Notes
+    /// - The camera is taken into account, sprites are hit on their visible cells only
+    /// - Objects drawn later, with higher indices, are on top
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{engine::Engine, game_object::GameObject};
+    ///
+    /// let mut engine = Engine::new(20, 10);
+    /// engine.add_object(GameObject::new(4, 2, 'a'));
+    /// engine.add_object(GameObject::new(4, 2, 'b'));
+    /// assert_eq!(engine.object_at(4, 2), Some(1));
+    /// assert_eq!(engine.object_at(5, 2), None);
+    /// ```
+    pub fn object_at(&self, x: usize, y: usize) -> Option<usize> {
+        let (world_x, world_y) = self.camera.view().screen_to_world(x, y);
+        if world_x < 0 || world_y < 0 {
+            return None;
+        }
+        let (world_x, world_y) = (world_x as usize, world_y as usize);
+        self.objects.iter().enumerate().rev().find(|(_, obj)| {
+            obj.visible && match obj.current_sprite() {
+                Some(sprite) => world_x >= obj.x && world_y >= obj.y && sprite.get(world_x - obj.x, world_y - obj.y).is_some(),
+                None => (obj.x, obj.y) == (world_x, world_y),
+            }
+        }).map(|(index, _)| index)
+    }
+
+    /// Tracks the object under the mouse, emitting `ObjectUnhovered` and `ObjectHovered` when it changes
+    fn update_hover(&mut self) {
+        let hovered = self.mouse_position.and_then(|(x, y)| self.object_at(x, y));
+        if hovered == self.hovered {
+            return;
+        }
+        if let Some(previous) = std::mem::replace(&mut self.hovered, hovered) {
+            self.event_bus.emit(EngineEvent::ObjectUnhovered(previous));
+        }
+        if let Some(index) = hovered {
+            self.event_bus.emit(EngineEvent::ObjectHovered(index));
+        }
+    }
+
+    /// Sets the ambient effect covering the screen, `None` clears the sky
+    pub fn set_weather(&mut self, weather: Option<Weather>) {
+        self.weather = weather;
+    }
+
+    /// Ambient effect covering the screen
+    pub fn weather(&self) -> Option<&Weather> {
+        self.weather.as_ref()
+    }
+
+    /// Ambient effect covering the screen, for changing its density or wind while it runs
+    pub fn weather_mut(&mut self) -> Option<&mut Weather> {
+        self.weather.as_mut()
+    }
+
+    /// Toggles drawing the profiler breakdown in the top-left corner of the screen
+    pub fn set_profiler_overlay(&mut self, enabled: bool) {
+        self.profiler_overlay = enabled;
+    }
+
+    /// Toggles drawing the latest events at the bottom of the screen
+    ///
+    /// # Notes
+    /// - Turns on event history with [`DEFAULT_EVENT_HISTORY`] entries when it is off
+    /// - Each line shows the frame, the number of handlers called, and the event
+    pub fn set_event_overlay(&mut self, enabled: bool) {
+        self.event_overlay = enabled;
+        if enabled && self.event_bus.history_capacity() == 0 {
+            self.event_bus.set_history_capacity(DEFAULT_EVENT_HISTORY);
+        }
+    }
+
+    /// Writes the event history to a file if the game panics
+    ///
+    /// # Notes
+    /// - Turns on event history with [`DEFAULT_EVENT_HISTORY`] entries when it is off
+    /// - The file is written while the engine is dropped during unwinding
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::engine::Engine;
+    /// let mut engine = Engine::new(80, 24);
+    /// engine.set_event_dump(Some("crash_events.log".into()));
+    /// ```
+    pub fn set_event_dump(&mut self, path: Option<PathBuf>) {
+        if path.is_some() && self.event_bus.history_capacity() == 0 {
+            self.event_bus.set_history_capacity(DEFAULT_EVENT_HISTORY);
+        }
+        self.event_dump = path;
+    }
+
+    /// Writes a crash report to a new file in a directory if the game panics, see [`crash`](crate::crash)
+    ///
+    /// # Notes
+    /// - Turns on event history with [`DEFAULT_EVENT_HISTORY`] entries when it is off
+    /// - Keeps the last [`DEFAULT_COMMAND_HISTORY`] commands applied from now on
+    /// - The report is written while the engine is dropped during unwinding
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::engine::Engine;
+    /// let mut engine = Engine::new(80, 24);
+    /// engine.set_crash_reports(Some("crash_reports".into()));
+    /// ```
+    pub fn set_crash_reports(&mut self, dir: Option<PathBuf>) {
+        if dir.is_some() {
+            crash::install_panic_hook();
+            if self.event_bus.history_capacity() == 0 {
+                self.event_bus.set_history_capacity(DEFAULT_EVENT_HISTORY);
+            }
+        } else {
+            self.recent_commands.clear();
+        }
+        self.crash_dir = dir;
+    }
+
+    /// Describes the current state the way a crash report does, such as for a bug report hotkey
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{engine::Engine, game_object::GameObject};
+    ///
+    /// let mut engine = Engine::new(80, 24);
+    /// engine.reseed(42);
+    /// engine.add_object(GameObject::new(1, 2, '@'));
+    /// let report = engine.crash_report();
+    /// assert_eq!((report.seed, report.world.len()), (42, 1));
+    /// assert!(report.to_text().contains("#0 '@' at (1, 2)"));
+    /// ```
+    pub fn crash_report(&self) -> CrashReport {
+        CrashReport {
+            panic: None,
+            frame: self.frame,
+            turn: self.turn,
+            seed: self.seed,
+            level: self.levels.current().map(str::to_string),
+            events: self.event_bus.history().iter().map(ToString::to_string).collect(),
+            commands: self.recent_commands.iter().cloned().collect(),
+            world: self.objects.iter().enumerate().map(|(index, obj)| crash::describe_object(index, obj)).collect(),
+        }
+    }
+
+    /// Draws the most recent events, newest on the bottom row
+    fn draw_event_overlay(&mut self) {
+        let (width, height) = (self.renderer.get_width(), self.renderer.get_height());
+        let history = self.event_bus.history();
+        let rows = history.len().min(height / 3).min(EVENT_OVERLAY_ROWS);
+        let style = Style::new().fg(Color::BRIGHT_CYAN);
+        for (row, record) in history[history.len() - rows..].iter().enumerate() {
+            let handlers = if record.dropped { "x".to_string() } else { record.handlers.to_string() };
+            let line: String = format!("{:>6} {:>2} {:?}", record.frame, handlers, record.event).chars().take(width).collect();
+            self.renderer.draw_styled_text(0, height - rows + row, &format!("{line:<width$}"), &style);
+        }
+    }
+
+    /// Adds a game object to the engine's object collection
+    /// 
+    /// # Arguments
+    /// * `obj` - The [`GameObject`] to add to the scene
+    /// 
+    /// # Notes
+    /// - The object will be rendered starting on the next frame
+    /// - Object will participate in animation system updates
+    /// - Object index is determined by insertion order
+    /// 
+    /// # Example
+    /// ```
+    /// # use lonely_engine::{engine::Engine, game_object::GameObject};
+    /// let mut engine = Engine::new(80, 24);
+    /// let player = GameObject::new(10, 5, '@');
+    /// engine.add_object(player);
+    /// ```
+    /// 
+    /// [`GameObject`]: crate::game_object::GameObject
     pub fn add_object(&mut self, obj: GameObject) {
+        if let Some(max) = self.limits.objects {
+            while self.objects.len() >= max {
+                self.report_limit(LimitKind::Objects);
+                let victim = match self.limits.object_policy {
+                    LimitPolicy::Reject => None,
+                    LimitPolicy::EvictOldest => (!self.objects.is_empty()).then_some(0),
+                    LimitPolicy::EvictByPriority => self.objects
+                        .iter()
+                        .enumerate()
+                        .min_by_key(|(_, existing)| existing.priority)
+                        .filter(|(_, existing)| existing.priority <= obj.priority)
+                        .map(|(index, _)| index),
+                };
+                match victim {
+                    Some(index) => self.despawn_object(index),
+                    None => return,
+                }
+            }
+        }
         self.objects.push(obj);
+        self.occupancy_dirty = true;
+    }
+
+    /// Shows floating text through the effects system, respecting the effect cap
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{effects::FloatingText, engine::Engine, style::Style};
+    ///
+    /// let mut engine = Engine::new(80, 24);
+    /// engine.spawn_floating_text(FloatingText::new(4, 4, "+10", Style::new()).duration(2.0));
+    /// assert_eq!(engine.effects.len(), 1);
+    /// ```
+    pub fn spawn_floating_text(&mut self, text: FloatingText) {
+        if let Some(max) = self.limits.effects {
+            while self.effects.len() >= max {
+                self.report_limit(LimitKind::Effects);
+                if self.limits.effect_policy == LimitPolicy::Reject || !self.effects.remove_oldest() {
+                    return;
+                }
+            }
+        }
+        self.effects.spawn_text(text);
+    }
+
+    /// Shows a full-screen page over the scene
+    ///
+    /// # Notes
+    /// - The world does not update while a page is shown, effects and the camera wait too
+    /// - Pages stack: the newest one is shown and dismissing it reveals the one below
+    /// - See [`Page`] for the scroll and dismiss keys
+    pub fn push_page(&mut self, page: Page) {
+        self.pages.push(page);
+    }
+
+    /// Dismisses the topmost page and emits `PageClosed`
+    pub fn pop_page(&mut self) -> Option<Page> {
+        let page = self.pages.pop()?;
+        self.event_bus.emit(EngineEvent::PageClosed(page.title().to_string()));
+        Some(page)
+    }
+
+    /// Topmost page, `None` when the scene is shown
+    pub fn page(&self) -> Option<&Page> {
+        self.pages.last()
+    }
+
+    /// Passes this frame's input to the topmost page
+    ///
+    /// # Returns
+    /// `true` while a page is shown, so the world waits
+    fn handle_page_input(&mut self) -> bool {
+        let view_height = Page::content_height(self.renderer.get_height());
+        let Some(page) = self.pages.last_mut() else {
+            return false;
+        };
+        if page.handle(&self.input_events, view_height) {
+            self.pop_page();
+        }
+        // The frame that closed the page still belongs to it
+        true
+    }
+
+    /// Replaces the settings and applies all of them right away
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lonely_engine::{engine::Engine, settings::{self, Settings}};
+    ///
+    /// let path = settings::config_dir("my_game").unwrap_or_default().join(settings::SETTINGS_FILE);
+    /// let mut engine = Engine::new(80, 24);
+    /// engine.set_settings(Settings::load_or_default(&path, Settings::new()).unwrap_or_default());
+    /// engine.persist_settings(path);
+    ///
+    /// // Later, from an options screen
+    /// engine.settings.set(settings::VOLUME, 0.4); // Quieter from the next frame on
+    /// ```
+    pub fn set_settings(&mut self, settings: Settings) {
+        self.settings = settings;
+        self.settings.take_changes();
+        // Any group volume key applies all of them, as does any accessibility key
+        for key in [settings::VOLUME, settings::MUSIC_VOLUME, settings::MUTED, settings::FPS_CAP, settings::THEME, settings::HIGH_CONTRAST, settings::BINDINGS] {
+            self.apply_setting(key);
+        }
+    }
+
+    /// Saves changed settings to a file when the pause menu closes and when [`run`](Self::run) returns
+    ///
+    /// # Notes
+    /// - Save errors are ignored, a read-only config directory should not stop the game
+    pub fn persist_settings(&mut self, path: impl Into<PathBuf>) {
+        self.settings_path = Some(path.into());
+    }
+
+    /// Applies the settings changed since the previous call and emits `SettingChanged` for each
+    fn apply_settings(&mut self) {
+        for key in self.settings.take_changes() {
+            self.apply_setting(&key);
+            self.settings_unsaved = true;
+            self.event_bus.emit(EngineEvent::SettingChanged(key));
+        }
+    }
+
+    /// Makes one setting take effect on the engine's systems
+    fn apply_setting(&mut self, key: &str) {
+        match key {
+            settings::VOLUME => self.audio.set_volume(self.settings.volume()),
+            settings::MUTED => self.sync_muted(),
+            settings::MUSIC_VOLUME | settings::SFX_VOLUME | settings::UI_VOLUME | settings::VOICE_VOLUME => {
+                for group in AudioGroup::ALL {
+                    self.audio.set_group_volume(group, self.settings.group_volume(group));
+                }
+            },
+            settings::FPS_CAP => self.set_render_rate(self.settings.fps_cap()),
+            settings::THEME => {
+                let theme = self.settings.theme();
+                self.renderer.set_clear_style(' ', theme.foreground, theme.background);
+            },
+            settings::HIGH_CONTRAST | settings::REDUCE_FLASHING | settings::MIN_FRAME_DURATION => {
+                let accessibility = self.settings.accessibility();
+                self.renderer.set_accessibility(accessibility);
+                self.camera.set_shake_enabled(!accessibility.reduce_flashing);
+            },
+            settings::BINDINGS => {
+                // Games without player bindings keep the menu's own controls
+                if let Some(menu) = self.pause_menu.as_mut() && !self.settings.bindings().actions().is_empty() {
+                    menu.set_controls(self.settings.bindings().clone());
+                }
+            },
+            _ => {},
+        }
+    }
+
+    /// Writes the settings to their file when they changed since the last save
+    fn save_settings(&mut self) {
+        if let Some(path) = &self.settings_path && std::mem::take(&mut self.settings_unsaved) {
+            let _ = self.settings.save(path);
+        }
+    }
+
+    /// Mutes audio when disabled, muted in the settings, or unfocused with muting on focus loss
+    fn sync_muted(&mut self) {
+        let unfocused = self.mute_when_unfocused && !self.focused;
+        self.audio.set_muted(self.audio_disabled || self.settings.muted() || unfocused);
+    }
+
+    /// Switches the installed locale to a language and emits `LanguageChanged`
+    ///
+    /// # Returns
+    /// `false` when no locale is installed or it has no strings for the language
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{engine::Engine, locale::{self, Locale, StringTable}};
+    ///
+    /// locale::install(Locale::new("en").with_table(StringTable::new("en")).with_table(StringTable::new("fr")));
+    /// let mut engine = Engine::new(80, 24);
+    /// assert!(engine.set_language("fr"));
+    /// assert!(!engine.set_language("xx"));
+    /// ```
+    pub fn set_language(&mut self, language: &str) -> bool {
+        let switched = locale::set_language(language);
+        if switched {
+            self.event_bus.emit(EngineEvent::LanguageChanged(language.to_string()));
+        }
+        switched
+    }
+
+    /// Sets the pause menu opened by its toggle key, `None` disables pausing
+    ///
+    /// # Notes
+    /// - Replacing the menu while it is open keeps it open with the new entries
+    /// - See [`pause_menu`](crate::pause_menu) for what the default entries do
+    /// - Key bindings from the settings replace the menu's controls
+    pub fn set_pause_menu(&mut self, menu: Option<PauseMenu>) {
+        self.pause_menu_open &= menu.is_some();
+        self.pause_menu = menu;
+        self.apply_setting(settings::BINDINGS);
+    }
+
+    /// Shows the pause menu with its first entry selected and emits `GamePaused`
+    ///
+    /// # Notes
+    /// - Does nothing when no menu is set or it is already open
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{engine::Engine, pause_menu::PauseMenu};
+    ///
+    /// let mut engine = Engine::new(80, 24);
+    /// engine.open_pause_menu();
+    /// assert!(!engine.is_pause_menu_open()); // No menu set
+    ///
+    /// engine.set_pause_menu(Some(PauseMenu::new()));
+    /// engine.open_pause_menu();
+    /// assert!(engine.is_pause_menu_open());
+    /// ```
+    pub fn open_pause_menu(&mut self) {
+        let Some(menu) = self.pause_menu.as_mut() else {
+            return;
+        };
+        if !self.pause_menu_open {
+            menu.reset();
+            self.pause_menu_open = true;
+            self.event_bus.emit(EngineEvent::GamePaused);
+        }
+    }
+
+    /// Hides the pause menu and emits `GameResumed`
+    ///
+    /// # Notes
+    /// - Changed settings are saved when a file is set with [`persist_settings`](Self::persist_settings)
+    pub fn close_pause_menu(&mut self) {
+        if std::mem::take(&mut self.pause_menu_open) {
+            self.apply_settings();
+            self.save_settings();
+            self.event_bus.emit(EngineEvent::GameResumed);
+        }
+    }
+
+    /// Returns whether the pause menu is shown
+    pub fn is_pause_menu_open(&self) -> bool {
+        self.pause_menu_open
+    }
+
+    /// Opens the pause menu on its toggle key, or passes this frame's input to it
+    ///
+    /// # Returns
+    /// `true` while the menu is shown, so the world waits
+    fn handle_pause_menu_input(&mut self) -> bool {
+        let Some(menu) = self.pause_menu.as_mut() else {
+            return false;
+        };
+        if !self.pause_menu_open {
+            let toggled = self.input_events.iter().any(|event| matches!(event, input::InputEvent::KeyDown(key) if menu.is_toggle(key)));
+            if toggled {
+                self.open_pause_menu();
+            }
+            return toggled;
+        }
+
+        let Some(action) = menu.handle(&self.input_events) else {
+            return true;
+        };
+        let options = menu.options();
+        self.event_bus.emit(EngineEvent::PauseMenuSelected(action.clone()));
+        match action {
+            PauseAction::Resume => self.close_pause_menu(),
+            PauseAction::Options => {
+                if let Some(page) = options {
+                    self.push_page(page);
+                }
+            },
+            PauseAction::Quit => self.stop(),
+            PauseAction::Custom(_) => {},
+        }
+        // The frame that closed the menu still belongs to it
+        true
+    }
+
+    /// Sets caps on objects, effects, and events
+    ///
+    /// # Notes
+    /// - Caps apply to new spawns, lowering a cap does not remove what is already alive
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{engine::Engine, game_object::GameObject, limits::{LimitPolicy, Limits}};
+    ///
+    /// let mut engine = Engine::new(80, 24);
+    /// engine.set_limits(Limits::new().max_objects(2, LimitPolicy::EvictByPriority));
+    ///
+    /// engine.add_object(GameObject::new(1, 1, '@').with_priority(10));
+    /// engine.add_object(GameObject::new(2, 2, '*'));
+    /// engine.add_object(GameObject::new(3, 3, '*')); // Evicts the first spark
+    /// engine.add_object(GameObject::new(4, 4, '.').with_priority(-1)); // Rejected
+    ///
+    /// let glyphs: Vec<(char, usize)> = engine.objects.iter().map(|obj| (obj.character, obj.x)).collect();
+    /// assert_eq!(glyphs, vec![('@', 1), ('*', 3)]);
+    /// ```
+    pub fn set_limits(&mut self, limits: Limits) {
+        self.event_bus.set_frame_limit(limits.events_per_frame);
+        self.limits = limits;
+    }
+
+    /// Configured caps
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    /// Sets the checks commands pass before being applied, see [`validation`](crate::validation)
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{
+    ///     engine::{EngineCommand, Updatable, UpdateContext},
+    ///     event::{EngineEvent, EventKind},
+    ///     game_object::GameObject,
+    ///     testing::TestEngine,
+    ///     validation::{CommandError, Strictness, Validation},
+    /// };
+    ///
+    /// /// Sends garbage, as a buggy script might
+    /// struct Garbage;
+    ///
+    /// impl Updatable for Garbage {
+    ///     fn update_with_context(&mut self, _ctx: &mut UpdateContext) -> Vec<EngineCommand> {
+    ///         vec![EngineCommand::MoveObject(0, i32::MAX, 0), EngineCommand::DespawnObject(7)]
+    ///     }
+    /// }
+    ///
+    /// let mut test = TestEngine::new(80, 24);
+    /// test.set_validation(Validation::new().strictness(Strictness::Event));
+    /// test.add_object(GameObject::new(5, 5, '@'));
+    /// test.add_updatable(Garbage);
+    /// test.run(1);
+    ///
+    /// test.assert_position(0, 5, 5);
+    /// let rejected: Vec<&EngineEvent> = test.events_of(EventKind::CommandRejected).collect();
+    /// assert!(matches!(rejected[..], [
+    ///     EngineEvent::CommandRejected("MoveObject", CommandError::InvalidDelta(i32::MAX, 0)),
+    ///     EngineEvent::CommandRejected("DespawnObject", CommandError::DeadObject(7)),
+    /// ]));
+    /// ```
+    pub fn set_validation(&mut self, validation: Validation) {
+        self.validation = validation;
+    }
+
+    /// Checks commands pass before being applied
+    pub fn validation(&self) -> &Validation {
+        &self.validation
+    }
+
+    /// Reports a command dropped by validation as its strictness says
+    fn reject_command(&mut self, name: &'static str, error: CommandError) {
+        if self.validation.strictness == Strictness::Panic && cfg!(debug_assertions) {
+            panic!("invalid {name} command: {error}");
+        }
+        let diagnostic = match &error {
+            CommandError::DeadObject(index) => Diagnostic::DeadObject(*index, name),
+            _ => Diagnostic::InvalidCommand(name, error.clone()),
+        };
+        self.diagnostics.record(diagnostic);
+        if self.validation.strictness != Strictness::Warn {
+            self.event_bus.emit(EngineEvent::CommandRejected(name, error));
+        }
+    }
+
+    /// Emits `LimitReached` the first time a cap is hit in a frame
+    fn report_limit(&mut self, kind: LimitKind) {
+        if self.reported_limits.insert(kind) {
+            self.event_bus.emit(EngineEvent::LimitReached(kind));
+        }
+    }
+
+    /// Returns indices of all objects matching a selector, in ascending order
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::{engine::{Engine, GroupSelector}, game_object::GameObject};
+    /// let mut engine = Engine::new(80, 24);
+    /// engine.add_object(GameObject::new(1, 1, 'B').with_group("boss"));
+    /// engine.add_object(GameObject::new(2, 1, 'B').with_group("boss"));
+    /// assert_eq!(engine.group_members(&GroupSelector::group("boss")), vec![0, 1]);
+    /// ```
+    pub fn group_members(&self, selector: &GroupSelector) -> Vec<usize> {
+        self.objects
+            .iter()
+            .enumerate()
+            .filter(|(_, obj)| selector.matches(obj))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Registers the object at `index` in a named group
+    ///
+    /// # Notes
+    /// - Membership is stored on the object, so it survives index shifts from despawns
+    pub fn add_to_group(&mut self, index: usize, group: &str) {
+        if let Some(obj) = self.objects.get_mut(index) {
+            obj.add_group(group);
+        }
+    }
+
+    /// Removes the object at `index` from a named group
+    pub fn remove_from_group(&mut self, index: usize, group: &str) {
+        if let Some(obj) = self.objects.get_mut(index) {
+            obj.groups.retain(|existing| existing != group);
+        }
+    }
+
+    /// Removes the object at `index` and emits `ObjectDespawned`
+    ///
+    /// # Notes
+    /// - Objects after `index` shift down by one
+    /// - Out of range indices are ignored
+    fn despawn_object(&mut self, index: usize) {
+        if index < self.objects.len() {
+            self.objects.remove(index);
+            self.components.object_removed(index);
+            self.occupancy_dirty = true;
+            for projectile in self.objects.iter_mut().filter_map(|obj| obj.projectile.as_mut()) {
+                projectile.object_removed(index);
+            }
+            for event in self.zones.object_removed(index) {
+                self.event_bus.emit(event);
+            }
+            self.turns.object_removed(index);
+            self.event_bus.emit(EngineEvent::ObjectDespawned(index));
+        }
+    }
+
+    /// Switches between real-time and turn-based stepping
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::engine::{Engine, EngineMode};
+    /// let mut engine = Engine::new(80, 24);
+    /// // Roguelike: nothing moves until the player presses a key
+    /// engine.set_mode(EngineMode::TurnBased);
+    /// ```
+    ///
+    /// A key held over several frames takes one turn:
+    /// ```
+    /// # use lonely_engine::{engine::EngineMode, input::Key, testing::TestEngine};
+    /// let mut test = TestEngine::new(80, 24);
+    /// test.set_mode(EngineMode::TurnBased);
+    /// test.key_down(0, Key::Right);
+    /// test.run(5);
+    /// assert_eq!(test.turn(), 1);
+    /// test.key_up(5, Key::Right);
+    /// test.tap(6, Key::Right);
+    /// test.run(3);
+    /// assert_eq!(test.turn(), 2);
+    /// ```
+    pub fn set_mode(&mut self, mode: EngineMode) {
+        self.mode = mode;
+    }
+
+    /// Returns the current stepping mode
+    pub fn mode(&self) -> EngineMode {
+        self.mode
+    }
+
+    /// Number of turns taken in turn-based mode
+    pub fn turn(&self) -> u64 {
+        self.turn
     }
 
     /// Returns whether the egnie is still running.
@@ -252,9 +3257,462 @@ impl Engine {
         self.running = false;
     }
 
-    fn cleanup_terminal(&self) {
+    fn cleanup_terminal(&mut self) {
+        // Let the render thread finish writing before resetting the screen
+        self.renderer.stop_render_thread();
+        if self.renderer.is_headless() || !self.display {
+            return;
+        }
+        crash::set_terminal_active(false);
+
         // Reset terminal state
         print!("\x1B[2J\x1B[?25h");
         let _ = std::io::stdout().flush();
     }
 }
+
+/// Configures an [`Engine`] before creation
+///
+/// # Example
+/// ```
+/// use lonely_engine::engine::{EngineBuilder, EngineMode};
+///
+/// let engine = EngineBuilder::new(60, 20)
+///     .mode(EngineMode::TurnBased)
+///     .pause_when_unfocused(true)
+///     .build();
+/// ```
+///
+/// # Command line
+/// [`EngineBuilder::from_args`] gives every game the same debugging and automation switches:
+///
+/// | Flag | Effect |
+/// |------|--------|
+/// | `--fps N` | Presents N frames per second, see [`EngineBuilder::render_rate`] |
+/// | `--pacing NAME` | Waits between frames with `sleep`, `hybrid`, or `adaptive` pacing, see [`EngineBuilder::frame_pacing`] |
+/// | `--size WxH` | Render surface size in characters, such as `--size 120x40` |
+/// | `--headless` | Runs without reading the console or writing frames, see [`EngineBuilder::headless`] |
+/// | `--server N` | Runs as a dedicated server at N ticks per second, see [`EngineBuilder::server`] |
+/// | `--record FILE` | Writes every input event to an [`InputLog`] when the engine stops |
+/// | `--replay FILE` | Feeds a recorded [`InputLog`] back frame by frame |
+/// | `--seed N` | Seeds `engine.rng` |
+/// | `--no-audio` | Mutes all sounds |
+/// | `--ascii` | Writes plain ASCII without colors, see [`EngineBuilder::ascii_only`] |
+/// | `--diagnostics` | Checks for invalid state every frame in release builds too, see [`EngineBuilder::diagnostics`] |
+/// | `--crash-reports DIR` | Writes a crash report to DIR if the game panics, see [`Engine::set_crash_reports`] |
+/// | `--metrics FILE` | Writes [`Metrics`] to a JSON or `.prom` file every 10 seconds and on exit, see [`Engine::dump_metrics`] |
+/// | `--narrate OUTPUT` | Reads game events to `stdout`, `stderr`, `speech`, or a file, see [`EngineBuilder::narration`] |
+/// | `--no-display` | Reads input without drawing frames, see [`EngineBuilder::display`] |
+///
+/// Values are given as the next argument or after `=`, as in `--seed=42`.
+#[derive(Debug, Clone)]
+pub struct EngineBuilder {
+    width: usize,
+    height: usize,
+    mode: EngineMode,
+    move_policy: MovePolicy,
+    pause_when_unfocused: bool,
+    mute_when_unfocused: bool,
+    deterministic: Option<(u64, f32)>,
+    threaded_rendering: bool,
+    limits: Limits,
+    validation: Validation,
+    update_rate: Option<f32>,
+    render_rate: Option<f32>,
+    frame_pacing: Option<FramePacing>,
+    headless: bool,
+    /// Tick rate of a dedicated server
+    server: Option<f32>,
+    audio: bool,
+    seed: Option<u64>,
+    record_input: Option<PathBuf>,
+    replay_input: Option<InputLog>,
+    pause_menu: Option<PauseMenu>,
+    settings: Option<Settings>,
+    /// `None` detects dumb terminals
+    ascii_only: Option<bool>,
+    /// `None` checks in debug builds only
+    diagnostics: Option<bool>,
+    narration: Option<NarrationOutput>,
+    display: bool,
+    metrics_dump: Option<PathBuf>,
+    crash_reports: Option<PathBuf>,
+}
+
+impl EngineBuilder {
+    /// Starts a builder with the render surface dimensions
+    ///
+    /// # Arguments
+    /// * `width` - Width of the render surface in characters
+    /// * `height` - Height of the render surface in characters
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            mode: EngineMode::RealTime,
+            move_policy: MovePolicy::Overlap,
+            pause_when_unfocused: false,
+            mute_when_unfocused: false,
+            deterministic: None,
+            threaded_rendering: false,
+            limits: Limits::new(),
+            validation: Validation::new(),
+            update_rate: None,
+            render_rate: None,
+            frame_pacing: None,
+            headless: false,
+            server: None,
+            audio: true,
+            seed: None,
+            record_input: None,
+            replay_input: None,
+            pause_menu: None,
+            settings: None,
+            ascii_only: None,
+            diagnostics: None,
+            narration: None,
+            display: true,
+            metrics_dump: None,
+            crash_reports: None,
+        }
+    }
+
+    /// Starts a builder configured by the process's command line, see [Command line](EngineBuilder#command-line)
+    ///
+    /// # Arguments
+    /// * `width` - Width of the render surface unless `--size` is given
+    /// * `height` - Height of the render surface unless `--size` is given
+    ///
+    /// # Returns
+    /// `InvalidInput` for a flag with a missing or malformed value, or the error reading a `--replay` file
+    ///
+    /// # Notes
+    /// - Arguments the engine does not know are left for the game to handle
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lonely_engine::engine::EngineBuilder;
+    ///
+    /// // my_game --size 100x30 --seed 7 --record session.log
+    /// let mut engine = EngineBuilder::from_args(80, 24)
+    ///     .unwrap_or_else(|error| panic!("{error}"))
+    ///     .threaded_rendering(true)
+    ///     .build();
+    /// engine.run();
+    /// ```
+    pub fn from_args(width: usize, height: usize) -> io::Result<Self> {
+        Self::new(width, height).args(std::env::args().skip(1))
+    }
+
+    /// Applies command line flags, see [Command line](EngineBuilder#command-line)
+    ///
+    /// # Arguments
+    /// * `args` - Arguments without the program name
+    ///
+    /// # Returns
+    /// `InvalidInput` for a flag with a missing or malformed value, or the error reading a `--replay` file
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::engine::EngineBuilder;
+    ///
+    /// let engine = EngineBuilder::new(80, 24)
+    ///     .args(["--size=40x12", "--fps", "60", "--headless", "--level", "3"])
+    ///     .unwrap()
+    ///     .build();
+    /// assert_eq!((engine.renderer.get_width(), engine.renderer.get_height()), (40, 12));
+    /// assert!(engine.renderer.is_headless());
+    ///
+    /// assert!(EngineBuilder::new(80, 24).args(["--size", "wide"]).is_err());
+    /// ```
+    pub fn args<S: AsRef<str>>(mut self, args: impl IntoIterator<Item = S>) -> io::Result<Self> {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, Some(value.to_string())),
+                None => (arg, None),
+            };
+            let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidInput, format!("{flag}: {message}"));
+            let mut value = || inline.clone().or_else(|| args.next().map(|value| value.as_ref().to_string())).ok_or_else(|| invalid("missing value"));
+
+            match flag {
+                "--fps" => {
+                    let fps: f32 = value()?.parse().map_err(|_| invalid("expected frames per second"))?;
+                    if !(fps > 0.0 && fps.is_finite()) {
+                        return Err(invalid("expected frames per second"));
+                    }
+                    self.render_rate = Some(fps);
+                },
+                "--pacing" => {
+                    let name = value()?;
+                    self.frame_pacing = Some(FramePacing::from_name(&name).ok_or_else(|| invalid("expected sleep, hybrid, or adaptive"))?);
+                },
+                "--size" => {
+                    let size = value()?;
+                    let (width, height) = size.split_once(['x', 'X']).ok_or_else(|| invalid("expected WIDTHxHEIGHT"))?;
+                    match (width.parse(), height.parse()) {
+                        (Ok(width), Ok(height)) if width > 0 && height > 0 => (self.width, self.height) = (width, height),
+                        _ => return Err(invalid("expected WIDTHxHEIGHT")),
+                    }
+                },
+                "--headless" => self.headless = true,
+                "--server" => {
+                    let rate: f32 = value()?.parse().map_err(|_| invalid("expected ticks per second"))?;
+                    if !(rate > 0.0 && rate.is_finite()) {
+                        return Err(invalid("expected ticks per second"));
+                    }
+                    self.server = Some(rate);
+                },
+                "--record" => self.record_input = Some(PathBuf::from(value()?)),
+                "--replay" => self.replay_input = Some(InputLog::load(value()?)?),
+                "--seed" => self.seed = Some(value()?.parse().map_err(|_| invalid("expected a number"))?),
+                "--no-audio" => self.audio = false,
+                "--ascii" => self.ascii_only = Some(true),
+                "--diagnostics" => self.diagnostics = Some(true),
+                "--narrate" => self.narration = Some(NarrationOutput::parse(&value()?)),
+                "--no-display" => self.display = false,
+                "--metrics" => self.metrics_dump = Some(PathBuf::from(value()?)),
+                "--crash-reports" => self.crash_reports = Some(PathBuf::from(value()?)),
+                _ => {},
+            }
+        }
+        Ok(self)
+    }
+
+    /// Sets real-time or turn-based stepping
+    pub fn mode(mut self, mode: EngineMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets how the engine waits between frames, `FramePacing::Adaptive` by default
+    pub fn frame_pacing(mut self, pacing: FramePacing) -> Self {
+        self.frame_pacing = Some(pacing);
+        self
+    }
+
+    /// Sets how moves of solid objects into occupied cells resolve, `MovePolicy::Overlap` by default
+    pub fn move_policy(mut self, policy: MovePolicy) -> Self {
+        self.move_policy = policy;
+        self
+    }
+
+    /// Skips world updates while the console window is unfocused (rendering continues)
+    pub fn pause_when_unfocused(mut self, enabled: bool) -> Self {
+        self.pause_when_unfocused = enabled;
+        self
+    }
+
+    /// Mutes audio while the console window is unfocused
+    pub fn mute_when_unfocused(mut self, enabled: bool) -> Self {
+        self.mute_when_unfocused = enabled;
+        self
+    }
+
+    /// Runs the simulation deterministically, see [`Engine::set_deterministic`]
+    pub fn deterministic(mut self, seed: u64, timestep: f32) -> Self {
+        self.deterministic = Some((seed, timestep));
+        self
+    }
+
+    /// Caps objects, effects, and events, see [`Engine::set_limits`]
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets the checks commands pass before being applied, see [`Engine::set_validation`]
+    pub fn validation(mut self, validation: Validation) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Starts with player settings applied, see [`Engine::set_settings`]
+    pub fn settings(mut self, settings: Settings) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    /// Opens a pause menu on its toggle key, see [`Engine::set_pause_menu`]
+    pub fn pause_menu(mut self, menu: PauseMenu) -> Self {
+        self.pause_menu = Some(menu);
+        self
+    }
+
+    /// Runs real-time updates at a fixed rate, see [`Engine::set_update_rate`]
+    pub fn update_rate(mut self, updates_per_second: f32) -> Self {
+        self.update_rate = Some(updates_per_second);
+        self
+    }
+
+    /// Presents frames at a rate, see [`Engine::set_render_rate`]
+    pub fn render_rate(mut self, frames_per_second: f32) -> Self {
+        self.render_rate = Some(frames_per_second);
+        self
+    }
+
+    /// Writes frames to the terminal from a background thread
+    ///
+    /// # Notes
+    /// - Recommended for slow terminals (Windows conhost especially), where output otherwise stalls updates
+    pub fn threaded_rendering(mut self, enabled: bool) -> Self {
+        self.threaded_rendering = enabled;
+        self
+    }
+
+    /// Forces or prevents ASCII-only output, see [`Renderer::set_ascii_only`]
+    ///
+    /// # Notes
+    /// - Without this, ASCII-only output follows the detected [`Capabilities`], as for `TERM=dumb`
+    pub fn ascii_only(mut self, enabled: bool) -> Self {
+        self.ascii_only = Some(enabled);
+        self
+    }
+
+    /// Runs without reading console input or writing frames to the terminal
+    ///
+    /// # Notes
+    /// - For CI and automated runs, input comes from injectors or a replayed log
+    /// - Frames are still drawn, so screenshots and recordings work
+    pub fn headless(mut self, enabled: bool) -> Self {
+        self.headless = enabled;
+        self
+    }
+
+    /// Runs as a dedicated server simulating `tick_rate` updates per second, see [`server`](crate::server)
+    ///
+    /// # Notes
+    /// - Implies headless mode and disabled audio, and replaces the update and render rates
+    /// - Nothing is drawn: effects, weather, the camera, and the mouse are not updated either
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::engine::Engine;
+    /// let engine = Engine::builder(80, 24).server(20.0).build();
+    /// assert!(engine.is_server() && engine.renderer.is_headless());
+    /// ```
+    pub fn server(mut self, tick_rate: f32) -> Self {
+        self.server = Some(tick_rate);
+        self
+    }
+
+    /// Enables or disables sound, disabled audio stays muted even when focus returns
+    pub fn audio(mut self, enabled: bool) -> Self {
+        self.audio = enabled;
+        self
+    }
+
+    /// Turns the per-frame state checks of [`diagnostics`](crate::diagnostics) on or off, on in debug builds by default
+    pub fn diagnostics(mut self, enabled: bool) -> Self {
+        self.diagnostics = Some(enabled);
+        self
+    }
+
+    /// Narrates game events for screen readers with the built-in rules, see [`Engine::set_narrator`]
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::{engine::Engine, narration::NarrationOutput};
+    /// let engine = Engine::builder(80, 24).narration(NarrationOutput::speech()).display(false).build();
+    /// assert!(engine.narrator().is_some() && !engine.is_display_enabled());
+    /// ```
+    pub fn narration(mut self, output: NarrationOutput) -> Self {
+        self.narration = Some(output);
+        self
+    }
+
+    /// Draws frames or leaves the terminal to the narrator, see [`Engine::set_display`]
+    pub fn display(mut self, enabled: bool) -> Self {
+        self.display = enabled;
+        self
+    }
+
+    /// Writes a crash report to a directory if the game panics, see [`Engine::set_crash_reports`]
+    pub fn crash_reports(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.crash_reports = Some(dir.into());
+        self
+    }
+
+    /// Seeds the engine random number generator, overriding the seed of [`EngineBuilder::deterministic`]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Records every input event to a file, see [`Engine::record_input`]
+    pub fn record_input(mut self, path: impl Into<PathBuf>) -> Self {
+        self.record_input = Some(path.into());
+        self
+    }
+
+    /// Feeds a recorded input log back, see [`Engine::replay_input`]
+    pub fn replay_input(mut self, log: InputLog) -> Self {
+        self.replay_input = Some(log);
+        self
+    }
+
+    /// Creates the configured engine
+    pub fn build(mut self) -> Engine {
+        if let Some(tick_rate) = self.server {
+            self.headless = true;
+            self.audio = false;
+            self.update_rate = Some(tick_rate);
+            self.render_rate = Some(tick_rate);
+        }
+        let mut engine = Engine::new(self.width, self.height);
+        engine.server = self.server.is_some();
+        engine.mode = self.mode;
+        engine.move_policy = self.move_policy;
+        engine.pause_when_unfocused = self.pause_when_unfocused;
+        engine.mute_when_unfocused = self.mute_when_unfocused;
+        engine.audio_disabled = !self.audio;
+        if let Some(settings) = self.settings {
+            engine.set_settings(settings);
+        }
+        engine.sync_muted();
+        if let Some((seed, timestep)) = self.deterministic {
+            engine.set_deterministic(seed, timestep);
+        }
+        if let Some(seed) = self.seed {
+            engine.reseed(seed);
+        }
+        engine.renderer.set_headless(self.headless);
+        if let Some(ascii_only) = self.ascii_only {
+            engine.renderer.set_ascii_only(ascii_only);
+        }
+        if let Some(enabled) = self.diagnostics {
+            engine.diagnostics.set_enabled(enabled);
+        }
+        if let Some(output) = self.narration {
+            engine.set_narrator(Narrator::new(output));
+        }
+        engine.set_display(self.display);
+        if let Some(path) = self.metrics_dump {
+            engine.dump_metrics(path, DEFAULT_DUMP_INTERVAL);
+        }
+        if self.crash_reports.is_some() {
+            engine.set_crash_reports(self.crash_reports);
+        }
+        if let Some(path) = self.record_input {
+            engine.record_input(path);
+        }
+        if let Some(log) = self.replay_input {
+            engine.replay_input(log);
+        }
+        engine.set_limits(self.limits);
+        engine.set_validation(self.validation);
+        engine.set_pause_menu(self.pause_menu);
+        engine.set_update_rate(self.update_rate);
+        if let Some(rate) = self.render_rate {
+            engine.set_render_rate(rate);
+        }
+        if let Some(pacing) = self.frame_pacing {
+            engine.set_frame_pacing(pacing);
+        }
+        if self.threaded_rendering && !self.headless {
+            engine.renderer.start_render_thread();
+        }
+        engine
+    }
+}
@@ -0,0 +1,224 @@
+//! Equipment slots and the stat modifiers of equipped items
+//!
+//! Provides:
+//! - [`Equipment`] component stored on every [`GameObject`], naming the slots
+//!   the object has and the item in each
+//! - [`EquipError`] explaining why an item could not be equipped
+//!
+//! Equipping an item adds the modifiers of its [`ItemDef`] to the object's
+//! [`StatBlock`] with [`equipment_source`] as their source, and unequipping
+//! removes them again. The engine equips, unequips, and uses items with
+//! `EngineCommand::EquipItem`, `EngineCommand::UnequipItem`, and
+//! `EngineCommand::UseItem`, looking definitions up in `Engine::items`, and
+//! emits `ItemEquipped`, `ItemUnequipped`, and `ItemActivated`.
+//!
+//! With the `serde` feature, equipment and inventories serialize with a save.
+//! Stat blocks do not, so after loading one call [`Equipment::reapply`] to
+//! put the equipped items' modifiers back.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{
+//!     equipment::Equipment,
+//!     inventory::{Inventory, ItemDef, ItemRegistry},
+//!     stat_block::{ModifierKind, StatBlock},
+//! };
+//!
+//! let mut items = ItemRegistry::new();
+//! items.register(ItemDef::new("sword", "Iron Sword", '|').equip_slot("hand").modifier("strength", ModifierKind::Add(3.0)));
+//! items.register(ItemDef::new("axe", "Battle Axe", 'P').equip_slot("hand").modifier("strength", ModifierKind::Add(5.0)));
+//!
+//! let mut stats = StatBlock::new().with_base("strength", 10.0);
+//! let mut gear = Equipment::with_slots(&["head", "hand"]);
+//! let mut bag = Inventory::new(4);
+//! bag.add(&items, "sword", 1);
+//! bag.add(&items, "axe", 1);
+//!
+//! gear.equip_from_inventory(&mut bag, 0, &items, &mut stats).unwrap();
+//! assert_eq!(stats.get("strength"), 13.0);
+//!
+//! // The axe takes the sword's place, which goes back in the bag
+//! gear.equip_from_inventory(&mut bag, 1, &items, &mut stats).unwrap();
+//! assert_eq!(gear.get("hand"), Some("axe"));
+//! assert_eq!(stats.get("strength"), 15.0);
+//! assert_eq!(bag.count("sword"), 1);
+//! ```
+//!
+//! [`GameObject`]: crate::game_object::GameObject
+
+use std::fmt;
+use crate::{
+    inventory::{Inventory, ItemDef, ItemRegistry},
+    stat_block::{Modifier, StatBlock},
+};
+
+/// Source of the stat modifiers granted by the item in a slot
+///
+/// # Example
+/// ```
+/// # use lonely_engine::equipment::equipment_source;
+/// assert_eq!(equipment_source("head"), "equipment:head");
+/// ```
+pub fn equipment_source(slot: &str) -> String {
+    format!("equipment:{slot}")
+}
+
+/// Why an item could not be equipped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EquipError {
+    /// No definition has the item id
+    UnknownItem(String),
+    /// The item has no equipment slot
+    NotEquippable(String),
+    /// The object has no slot the item goes in, contains the slot
+    MissingSlot(String),
+    /// The inventory slot is empty
+    EmptySlot(usize),
+}
+
+impl fmt::Display for EquipError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EquipError::UnknownItem(item) => write!(f, "unknown item `{}`", item),
+            EquipError::NotEquippable(item) => write!(f, "item `{}` cannot be equipped", item),
+            EquipError::MissingSlot(slot) => write!(f, "no `{}` slot to equip into", slot),
+            EquipError::EmptySlot(slot) => write!(f, "inventory slot {} is empty", slot),
+        }
+    }
+}
+
+impl std::error::Error for EquipError {}
+
+/// Named equipment slots of an object and the item in each
+///
+/// # Notes
+/// - Slots keep the order they were added in, such as for an equipment screen
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Equipment {
+    slots: Vec<(String, Option<String>)>,
+}
+
+impl Equipment {
+    /// Creates equipment without slots, for objects that wear nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates empty equipment with named slots
+    pub fn with_slots(slots: &[&str]) -> Self {
+        let mut equipment = Self::new();
+        for slot in slots {
+            equipment.add_slot(slot);
+        }
+        equipment
+    }
+
+    /// Adds an empty slot, ignored when the slot exists
+    pub fn add_slot(&mut self, slot: &str) {
+        if !self.has_slot(slot) {
+            self.slots.push((slot.to_string(), None));
+        }
+    }
+
+    /// Returns whether the object has a slot
+    pub fn has_slot(&self, slot: &str) -> bool {
+        self.slots.iter().any(|(name, _)| name == slot)
+    }
+
+    /// Item in a slot, `None` for empty or missing slots
+    pub fn get(&self, slot: &str) -> Option<&str> {
+        self.slots.iter().find(|(name, _)| name == slot).and_then(|(_, item)| item.as_deref())
+    }
+
+    /// Slots with the item in each, in the order they were added
+    pub fn slots(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.slots.iter().map(|(slot, item)| (slot.as_str(), item.as_deref()))
+    }
+
+    /// Equipped items with their slots
+    pub fn equipped(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.slots.iter().filter_map(|(slot, item)| item.as_deref().map(|item| (slot.as_str(), item)))
+    }
+
+    /// Puts an item in its slot, replacing the item there
+    ///
+    /// # Arguments
+    /// * `item` - Id of the item, its definition says which slot it goes in
+    /// * `registry` - Item definitions
+    /// * `stats` - Stats of the object, gaining the item's modifiers and losing the replaced item's
+    ///
+    /// # Returns
+    /// The slot used and the item it held before
+    pub fn equip(&mut self, item: &str, registry: &ItemRegistry, stats: &mut StatBlock) -> Result<(String, Option<String>), EquipError> {
+        let def = registry.get(item).ok_or_else(|| EquipError::UnknownItem(item.to_string()))?;
+        let slot = def.slot.clone().ok_or_else(|| EquipError::NotEquippable(item.to_string()))?;
+        let held = &mut self.slots.iter_mut().find(|(name, _)| *name == slot).ok_or_else(|| EquipError::MissingSlot(slot.clone()))?.1;
+        let previous = held.replace(item.to_string());
+
+        let source = equipment_source(&slot);
+        stats.remove_source(&source);
+        add_modifiers(def, &source, stats);
+        Ok((slot, previous))
+    }
+
+    /// Empties a slot, removing its item's modifiers
+    ///
+    /// # Returns
+    /// The item that was in the slot
+    pub fn unequip(&mut self, slot: &str, stats: &mut StatBlock) -> Option<String> {
+        let item = self.slots.iter_mut().find(|(name, _)| name == slot)?.1.take()?;
+        stats.remove_source(&equipment_source(slot));
+        Some(item)
+    }
+
+    /// Equips one item from an inventory slot, putting the replaced item back in the inventory
+    ///
+    /// # Returns
+    /// The equipment slot used and the item it held before
+    ///
+    /// # Notes
+    /// - The item stays in the inventory when it cannot be equipped
+    /// - A replaced item that does not fit in the inventory is still unequipped, it is up to the game to drop it
+    pub fn equip_from_inventory(&mut self, inventory: &mut Inventory, slot: usize, registry: &ItemRegistry, stats: &mut StatBlock) -> Result<(String, Option<String>), EquipError> {
+        let item = inventory.slot(slot).map(|stack| stack.item.clone()).ok_or(EquipError::EmptySlot(slot))?;
+        let result = self.equip(&item, registry, stats)?;
+        inventory.take_from_slot(slot, 1);
+        if let Some(previous) = &result.1 {
+            inventory.add(registry, previous, 1);
+        }
+        Ok(result)
+    }
+
+    /// Unequips a slot into an inventory
+    ///
+    /// # Returns
+    /// The item unequipped, `None` when the slot is empty or the inventory is full
+    pub fn unequip_to_inventory(&mut self, slot: &str, inventory: &mut Inventory, registry: &ItemRegistry, stats: &mut StatBlock) -> Option<String> {
+        let item = self.get(slot)?.to_string();
+        if inventory.add(registry, &item, 1) > 0 {
+            return None;
+        }
+        self.unequip(slot, stats)
+    }
+
+    /// Adds the modifiers of every equipped item again, such as after loading a save
+    ///
+    /// # Notes
+    /// - Modifiers from the equipment already in the stat block are replaced, never doubled
+    pub fn reapply(&self, registry: &ItemRegistry, stats: &mut StatBlock) {
+        for (slot, item) in self.equipped() {
+            let source = equipment_source(slot);
+            stats.remove_source(&source);
+            if let Some(def) = registry.get(item) {
+                add_modifiers(def, &source, stats);
+            }
+        }
+    }
+}
+
+fn add_modifiers(def: &ItemDef, source: &str, stats: &mut StatBlock) {
+    for (stat, kind) in &def.modifiers {
+        stats.add_modifier(Modifier { stat: stat.clone(), kind: *kind, source: source.to_string(), remaining: None });
+    }
+}
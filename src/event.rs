@@ -4,8 +4,11 @@
 //! Contains:
 //! - [`EngineEvent`] enum defining all engine event types
 //! - [`EventBus`] struct for managing event subscribers and dispatching
+//! - [`EventSender`] handle emitting events from other threads
+//! - [`EventFilter`] limiting a subscriber to some [`EventKind`]s or `Custom` patterns
 
-use crate::input::Key;
+use std::{cell::{Cell, RefCell}, collections::VecDeque, fmt, fs, io, path::Path, sync::mpsc, time::{Duration, Instant}};
+use crate::{audio::SoundHandle, diagnostics::Diagnostic, engine::{CommandQueue, EngineCommand}, input::Key, limits::LimitKind, pause_menu::PauseAction, validation::CommandError};
 
 /// Enum representing all possible engine events
 #[derive(Debug, Clone)]
@@ -19,6 +22,15 @@ pub enum EngineEvent {
     /// ```
     ObjectSpawned(usize),
 
+    /// Emitted when a game object is removed, either by command or when its lifetime expires.  
+    /// Contains the index the object had at the moment of removal.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ObjectDespawned(4);
+    /// ```
+    ObjectDespawned(usize),
+
     /// Emitted when an object changes position.  
     /// Contains (object index, new x, new y).  
     /// # Example
@@ -28,6 +40,42 @@ pub enum EngineEvent {
     /// ```
     ObjectMoved(usize, usize, usize),
 
+    /// Emitted when a solid object's move fails because a solid object holds the destination.  
+    /// Contains (object index, blocked x, blocked y).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::MoveBlocked(0, 6, 10);
+    /// ```
+    MoveBlocked(usize, usize, usize),
+
+    /// Emitted when a projectile runs into a solid object other than its owner.  
+    /// Contains (projectile index, index of the object hit).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ProjectileHit(7, 2);
+    /// ```
+    ProjectileHit(usize, usize),
+
+    /// Emitted right before a projectile that flew its range or left the world is despawned.  
+    /// Contains the projectile index.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ProjectileExpired(7);
+    /// ```
+    ProjectileExpired(usize),
+
+    /// Emitted when an object's animation wraps back to its first frame.  
+    /// Contains the object's index in the engine's objects list.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::AnimationLooped(3);
+    /// ```
+    AnimationLooped(usize),
+
     /// Emitted when any input is received (catch-all variant)
     /// # Example
     /// ```rust
@@ -60,6 +108,468 @@ pub enum EngineEvent {
     /// ```
     KeyReleased(Key),
 
+    /// Emitted when a chord or key sequence bound in the player's key bindings is completed.  
+    /// Contains the combo's action name.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ComboMatched("fireball".into());
+    /// ```
+    ComboMatched(String),
+
+    /// Emitted once when a screen transition has shown its last frame.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::TransitionFinished;
+    /// ```
+    TransitionFinished,
+
+    /// Emitted in turn-based mode after the world advances one turn.  
+    /// Contains the number of turns taken so far.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::TurnAdvanced(12);
+    /// ```
+    TurnAdvanced(u64),
+
+    /// Emitted when the engine's turn scheduler hands an actor its turn.  
+    /// Contains the actor's object index.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::TurnStarted(3);
+    /// ```
+    TurnStarted(usize),
+
+    /// Emitted when an actor of the engine's turn scheduler ends its turn.  
+    /// Contains the actor's object index.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::TurnEnded(3);
+    /// ```
+    TurnEnded(usize),
+
+    /// Emitted when the console window loses keyboard focus.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::FocusLost;
+    /// ```
+    FocusLost,
+
+    /// Emitted when the console window regains keyboard focus.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::FocusGained;
+    /// ```
+    FocusGained,
+
+    /// Emitted when a status effect is applied to an object by command.  
+    /// Contains (object index, effect name).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::EffectApplied(1, "poison".into());
+    /// ```
+    EffectApplied(usize, String),
+
+    /// Emitted when a status effect runs out of time.  
+    /// Contains (object index, effect name).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::EffectExpired(1, "poison".into());
+    /// ```
+    EffectExpired(usize, String),
+
+    /// Emitted when an active status effect is removed by command.  
+    /// Contains (object index, effect name).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::EffectRemoved(1, "poison".into());
+    /// ```
+    EffectRemoved(usize, String),
+
+    /// Emitted when the value of an object's stat changes, by command or when a modifier expires.  
+    /// Contains (object index, stat name, new value).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::StatChanged(0, "strength".into(), 14.0);
+    /// ```
+    StatChanged(usize, String, f32),
+
+    /// Emitted when an object following a path arrives at a waypoint.  
+    /// Contains (object index, waypoint index).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::WaypointReached(3, 1);
+    /// ```
+    WaypointReached(usize, usize),
+
+    /// Emitted when an object reaches the last waypoint of a one-shot path.  
+    /// Contains the object index.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::PathCompleted(3);
+    /// ```
+    PathCompleted(usize),
+
+    /// Emitted when a watched object moves into a trigger zone.  
+    /// Contains (object index, zone id).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ZoneEntered(0, "pressure_plate".into());
+    /// ```
+    ZoneEntered(usize, String),
+
+    /// Emitted when a watched object leaves a trigger zone or is despawned inside it.  
+    /// Contains (object index, zone id).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ZoneExited(0, "pressure_plate".into());
+    /// ```
+    ZoneExited(usize, String),
+
+    /// Emitted when the mouse moves onto a visible object, or the object moves under the mouse.  
+    /// Contains the object index.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ObjectHovered(4);
+    /// ```
+    ObjectHovered(usize),
+
+    /// Emitted when the mouse leaves the object reported by the last `ObjectHovered`.  
+    /// Contains the object index.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ObjectUnhovered(4);
+    /// ```
+    ObjectUnhovered(usize),
+
+    /// Emitted once per frame for each configured cap that was hit.  
+    /// For events, sent at the start of the frame after events were dropped.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::{event::EngineEvent, limits::LimitKind};
+    /// let event = EngineEvent::LimitReached(LimitKind::Objects);
+    /// ```
+    LimitReached(LimitKind),
+
+    /// Emitted the first frame the engine's diagnostics find a problem, such as an object outside the world.  
+    /// Contains the problem, which stays listed in the diagnostics panel while it lasts.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::{diagnostics::Diagnostic, event::EngineEvent};
+    /// let event = EngineEvent::DiagnosticReported(Diagnostic::OutOfBounds(4, 120, 3));
+    /// ```
+    DiagnosticReported(Diagnostic),
+
+    /// Emitted when command validation drops a command with [`Strictness::Event`](crate::validation::Strictness::Event) or stricter.  
+    /// Contains the command's name and why it was rejected.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::{event::EngineEvent, validation::CommandError};
+    /// let event = EngineEvent::CommandRejected("MoveObject", CommandError::DeadObject(12));
+    /// ```
+    CommandRejected(&'static str, CommandError),
+
+    /// Emitted when the topmost page is dismissed by key or command.  
+    /// Contains the page title.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::PageClosed("Credits".into());
+    /// ```
+    PageClosed(String),
+
+    /// Emitted when the pause menu opens and the world stops updating.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::GamePaused;
+    /// ```
+    GamePaused,
+
+    /// Emitted when the pause menu closes and the world updates again.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::GameResumed;
+    /// ```
+    GameResumed,
+
+    /// Emitted when a pause menu entry is chosen, before the engine acts on it.  
+    /// Contains the entry's action.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::{event::EngineEvent, pause_menu::PauseAction};
+    /// let event = EngineEvent::PauseMenuSelected(PauseAction::Custom("save".into()));
+    /// ```
+    PauseMenuSelected(PauseAction),
+
+    /// Emitted at the start of a frame for each setting changed since the previous one, after it took effect.  
+    /// Contains the setting key, such as `"audio.volume"`.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::{event::EngineEvent, settings};
+    /// let event = EngineEvent::SettingChanged(settings::VOLUME.into());
+    /// ```
+    SettingChanged(String),
+
+    /// Emitted when the installed locale switches language, so cached text can be rebuilt.  
+    /// Contains the new language code.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::LanguageChanged("de".into());
+    /// ```
+    LanguageChanged(String),
+
+    /// Emitted when a world snapshot is restored, for undo or debugging.  
+    /// Contains the restored turn counter.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::WorldRestored(12);
+    /// ```
+    WorldRestored(u64),
+
+    /// Emitted when a dedicated server is asked to stop by the console or `server::request_shutdown`.  
+    /// The engine stops after the current tick, handlers can notify clients and save.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ShutdownRequested;
+    /// ```
+    ShutdownRequested,
+
+    /// Emitted when a sound finishes playing, is stopped, or is replaced by another sound.  
+    /// Contains (playback handle, sound name).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::{audio::SoundHandle, event::EngineEvent};
+    /// let event = EngineEvent::SoundFinished(SoundHandle(3), "explosion".into());
+    /// ```
+    SoundFinished(SoundHandle, String),
+
+    /// Emitted when a stat command unlocks an achievement.  
+    /// Contains the achievement id.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::AchievementUnlocked("first_blood".into());
+    /// ```
+    AchievementUnlocked(String),
+
+    /// Emitted when `EngineCommand::AddScore` awards points.  
+    /// Contains (points awarded after multipliers, source tag).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ScoreAdded(300, "enemy".into());
+    /// ```
+    ScoreAdded(i64, String),
+
+    /// Emitted when the score reaches one of its milestones.  
+    /// Contains the milestone.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ScoreMilestone(10_000);
+    /// ```
+    ScoreMilestone(i64),
+
+    /// Emitted when a combo breaks, by timing out or `EngineCommand::BreakCombo`.  
+    /// Contains the number of scores the combo chained.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ComboEnded(12);
+    /// ```
+    ComboEnded(u32),
+
+    /// Emitted when a quest is started.  
+    /// Contains the quest id.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::QuestStarted("wolves".into());
+    /// ```
+    QuestStarted(String),
+
+    /// Emitted when a quest moves on to its next stage.  
+    /// Contains (quest id, index of the new stage).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::QuestAdvanced("wolves".into(), 1);
+    /// ```
+    QuestAdvanced(String, usize),
+
+    /// Emitted when the last stage of a quest is finished.  
+    /// Contains the quest id.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::QuestCompleted("wolves".into());
+    /// ```
+    QuestCompleted(String),
+
+    /// Emitted when a quest fails.  
+    /// Contains the quest id.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::QuestFailed("wolves".into());
+    /// ```
+    QuestFailed(String),
+
+    /// Emitted when a level is loaded, by a tracked object reaching an exit or by command.  
+    /// Contains the level name.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::LevelChanged("cellar".into());
+    /// ```
+    LevelChanged(String),
+
+    /// Emitted when a dialogue opens a text box.  
+    /// Contains the node id.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::DialogueNodeEntered("greeting".into());
+    /// ```
+    DialogueNodeEntered(String),
+
+    /// Emitted when the player picks a dialogue choice.  
+    /// Contains (node id, choice index).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::DialogueChoiceMade("ask".into(), 1);
+    /// ```
+    DialogueChoiceMade(String, usize),
+
+    /// Emitted when a dialogue closes its last text box or is stopped.  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::DialogueEnded;
+    /// ```
+    DialogueEnded,
+
+    /// Emitted when the inventory screen uses an item.  
+    /// Contains (slot index, item id).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ItemUsed(0, "potion".into());
+    /// ```
+    ItemUsed(usize, String),
+
+    /// Emitted when the inventory screen drops a stack.  
+    /// Contains (item id, count).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ItemDropped("arrow".into(), 12);
+    /// ```
+    ItemDropped(String, u32),
+
+    /// Emitted when an object equips an item by command.  
+    /// Contains (object index, slot, item id).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ItemEquipped(0, "hand".into(), "sword".into());
+    /// ```
+    ItemEquipped(usize, String, String),
+
+    /// Emitted when an item leaves an object's slot, unequipped by command or replaced.  
+    /// Contains (object index, slot, item id).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ItemUnequipped(0, "hand".into(), "dagger".into());
+    /// ```
+    ItemUnequipped(usize, String, String),
+
+    /// Emitted when an object uses an item by command, after its effects are applied.  
+    /// Contains (object index, item id).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ItemActivated(0, "potion".into());
+    /// ```
+    ItemActivated(usize, String),
+
+    /// Emitted when the shop screen buys an item.  
+    /// Contains (item id, coins paid).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ItemBought("sword".into(), 30);
+    /// ```
+    ItemBought(String, u64),
+
+    /// Emitted when the shop screen sells an item.  
+    /// Contains (item id, coins received).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ItemSold("sword".into(), 15);
+    /// ```
+    ItemSold(String, u64),
+
+    /// Emitted when a player joins a multiplayer lobby.  
+    /// Contains (player id, display name).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::PlayerJoined(2, "Ada".into());
+    /// ```
+    PlayerJoined(u32, String),
+
+    /// Emitted when a player leaves a multiplayer lobby.  
+    /// Contains (player id, display name).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::PlayerLeft(2, "Ada".into());
+    /// ```
+    PlayerLeft(u32, String),
+
+    /// Emitted when a lobby player changes their ready state.  
+    /// Contains (player id, whether they are ready).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::PlayerReadyChanged(2, true);
+    /// ```
+    PlayerReadyChanged(u32, bool),
+
+    /// Emitted when a lobby chat message arrives, including the local player's own.  
+    /// Contains (player id, text).  
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::EngineEvent;
+    /// let event = EngineEvent::ChatReceived(2, "gl hf".into());
+    /// ```
+    ChatReceived(u32, String),
+
     /// Custom user-defined event payload.  
     /// # Example
     /// ```rust
@@ -69,6 +579,301 @@ pub enum EngineEvent {
     Custom(String),
 }
 
+impl EngineEvent {
+    /// Variant of the event, for filtering without matching on its data
+    ///
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::{event::{EngineEvent, EventKind}, input::Key};
+    /// assert_eq!(EngineEvent::KeyPressed(Key::Space).kind(), EventKind::KeyPressed);
+    /// ```
+    pub fn kind(&self) -> EventKind {
+        match self {
+            EngineEvent::ObjectSpawned(_) => EventKind::ObjectSpawned,
+            EngineEvent::ObjectDespawned(_) => EventKind::ObjectDespawned,
+            EngineEvent::ObjectMoved(_, _, _) => EventKind::ObjectMoved,
+            EngineEvent::MoveBlocked(_, _, _) => EventKind::MoveBlocked,
+            EngineEvent::ProjectileHit(_, _) => EventKind::ProjectileHit,
+            EngineEvent::ProjectileExpired(_) => EventKind::ProjectileExpired,
+            EngineEvent::AnimationLooped(_) => EventKind::AnimationLooped,
+            EngineEvent::InputRecieved(_) => EventKind::InputRecieved,
+            EngineEvent::KeyPressed(_) => EventKind::KeyPressed,
+            EngineEvent::KeyHeld(_) => EventKind::KeyHeld,
+            EngineEvent::KeyReleased(_) => EventKind::KeyReleased,
+            EngineEvent::ComboMatched(_) => EventKind::ComboMatched,
+            EngineEvent::TransitionFinished => EventKind::TransitionFinished,
+            EngineEvent::TurnAdvanced(_) => EventKind::TurnAdvanced,
+            EngineEvent::TurnStarted(_) => EventKind::TurnStarted,
+            EngineEvent::TurnEnded(_) => EventKind::TurnEnded,
+            EngineEvent::FocusLost => EventKind::FocusLost,
+            EngineEvent::FocusGained => EventKind::FocusGained,
+            EngineEvent::EffectApplied(_, _) => EventKind::EffectApplied,
+            EngineEvent::EffectExpired(_, _) => EventKind::EffectExpired,
+            EngineEvent::EffectRemoved(_, _) => EventKind::EffectRemoved,
+            EngineEvent::StatChanged(_, _, _) => EventKind::StatChanged,
+            EngineEvent::WaypointReached(_, _) => EventKind::WaypointReached,
+            EngineEvent::PathCompleted(_) => EventKind::PathCompleted,
+            EngineEvent::ZoneEntered(_, _) => EventKind::ZoneEntered,
+            EngineEvent::ZoneExited(_, _) => EventKind::ZoneExited,
+            EngineEvent::ObjectHovered(_) => EventKind::ObjectHovered,
+            EngineEvent::ObjectUnhovered(_) => EventKind::ObjectUnhovered,
+            EngineEvent::LimitReached(_) => EventKind::LimitReached,
+            EngineEvent::DiagnosticReported(_) => EventKind::DiagnosticReported,
+            EngineEvent::CommandRejected(_, _) => EventKind::CommandRejected,
+            EngineEvent::PageClosed(_) => EventKind::PageClosed,
+            EngineEvent::GamePaused => EventKind::GamePaused,
+            EngineEvent::GameResumed => EventKind::GameResumed,
+            EngineEvent::PauseMenuSelected(_) => EventKind::PauseMenuSelected,
+            EngineEvent::SettingChanged(_) => EventKind::SettingChanged,
+            EngineEvent::LanguageChanged(_) => EventKind::LanguageChanged,
+            EngineEvent::WorldRestored(_) => EventKind::WorldRestored,
+            EngineEvent::ShutdownRequested => EventKind::ShutdownRequested,
+            EngineEvent::SoundFinished(_, _) => EventKind::SoundFinished,
+            EngineEvent::AchievementUnlocked(_) => EventKind::AchievementUnlocked,
+            EngineEvent::ScoreAdded(_, _) => EventKind::ScoreAdded,
+            EngineEvent::ScoreMilestone(_) => EventKind::ScoreMilestone,
+            EngineEvent::ComboEnded(_) => EventKind::ComboEnded,
+            EngineEvent::QuestStarted(_) => EventKind::QuestStarted,
+            EngineEvent::QuestAdvanced(_, _) => EventKind::QuestAdvanced,
+            EngineEvent::QuestCompleted(_) => EventKind::QuestCompleted,
+            EngineEvent::QuestFailed(_) => EventKind::QuestFailed,
+            EngineEvent::LevelChanged(_) => EventKind::LevelChanged,
+            EngineEvent::DialogueNodeEntered(_) => EventKind::DialogueNodeEntered,
+            EngineEvent::DialogueChoiceMade(_, _) => EventKind::DialogueChoiceMade,
+            EngineEvent::DialogueEnded => EventKind::DialogueEnded,
+            EngineEvent::ItemUsed(_, _) => EventKind::ItemUsed,
+            EngineEvent::ItemDropped(_, _) => EventKind::ItemDropped,
+            EngineEvent::ItemEquipped(_, _, _) => EventKind::ItemEquipped,
+            EngineEvent::ItemUnequipped(_, _, _) => EventKind::ItemUnequipped,
+            EngineEvent::ItemActivated(_, _) => EventKind::ItemActivated,
+            EngineEvent::ItemBought(_, _) => EventKind::ItemBought,
+            EngineEvent::ItemSold(_, _) => EventKind::ItemSold,
+            EngineEvent::PlayerJoined(_, _) => EventKind::PlayerJoined,
+            EngineEvent::PlayerLeft(_, _) => EventKind::PlayerLeft,
+            EngineEvent::PlayerReadyChanged(_, _) => EventKind::PlayerReadyChanged,
+            EngineEvent::ChatReceived(_, _) => EventKind::ChatReceived,
+            EngineEvent::Custom(_) => EventKind::Custom,
+        }
+    }
+}
+
+/// Variant of an [`EngineEvent`] without its data, used to filter subscriptions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    /// [`EngineEvent::ObjectSpawned`]
+    ObjectSpawned,
+    /// [`EngineEvent::ObjectDespawned`]
+    ObjectDespawned,
+    /// [`EngineEvent::ObjectMoved`]
+    ObjectMoved,
+    /// [`EngineEvent::MoveBlocked`]
+    MoveBlocked,
+    /// [`EngineEvent::ProjectileHit`]
+    ProjectileHit,
+    /// [`EngineEvent::ProjectileExpired`]
+    ProjectileExpired,
+    /// [`EngineEvent::AnimationLooped`]
+    AnimationLooped,
+    /// [`EngineEvent::InputRecieved`]
+    InputRecieved,
+    /// [`EngineEvent::KeyPressed`]
+    KeyPressed,
+    /// [`EngineEvent::KeyHeld`]
+    KeyHeld,
+    /// [`EngineEvent::KeyReleased`]
+    KeyReleased,
+    /// [`EngineEvent::ComboMatched`]
+    ComboMatched,
+    /// [`EngineEvent::TransitionFinished`]
+    TransitionFinished,
+    /// [`EngineEvent::TurnAdvanced`]
+    TurnAdvanced,
+    /// [`EngineEvent::TurnStarted`]
+    TurnStarted,
+    /// [`EngineEvent::TurnEnded`]
+    TurnEnded,
+    /// [`EngineEvent::FocusLost`]
+    FocusLost,
+    /// [`EngineEvent::FocusGained`]
+    FocusGained,
+    /// [`EngineEvent::EffectApplied`]
+    EffectApplied,
+    /// [`EngineEvent::EffectExpired`]
+    EffectExpired,
+    /// [`EngineEvent::EffectRemoved`]
+    EffectRemoved,
+    /// [`EngineEvent::StatChanged`]
+    StatChanged,
+    /// [`EngineEvent::WaypointReached`]
+    WaypointReached,
+    /// [`EngineEvent::PathCompleted`]
+    PathCompleted,
+    /// [`EngineEvent::ZoneEntered`]
+    ZoneEntered,
+    /// [`EngineEvent::ZoneExited`]
+    ZoneExited,
+    /// [`EngineEvent::ObjectHovered`]
+    ObjectHovered,
+    /// [`EngineEvent::ObjectUnhovered`]
+    ObjectUnhovered,
+    /// [`EngineEvent::LimitReached`]
+    LimitReached,
+    /// [`EngineEvent::DiagnosticReported`]
+    DiagnosticReported,
+    /// [`EngineEvent::CommandRejected`]
+    CommandRejected,
+    /// [`EngineEvent::PageClosed`]
+    PageClosed,
+    /// [`EngineEvent::GamePaused`]
+    GamePaused,
+    /// [`EngineEvent::GameResumed`]
+    GameResumed,
+    /// [`EngineEvent::PauseMenuSelected`]
+    PauseMenuSelected,
+    /// [`EngineEvent::SettingChanged`]
+    SettingChanged,
+    /// [`EngineEvent::LanguageChanged`]
+    LanguageChanged,
+    /// [`EngineEvent::WorldRestored`]
+    WorldRestored,
+    /// [`EngineEvent::ShutdownRequested`]
+    ShutdownRequested,
+    /// [`EngineEvent::SoundFinished`]
+    SoundFinished,
+    /// [`EngineEvent::AchievementUnlocked`]
+    AchievementUnlocked,
+    /// [`EngineEvent::ScoreAdded`]
+    ScoreAdded,
+    /// [`EngineEvent::ScoreMilestone`]
+    ScoreMilestone,
+    /// [`EngineEvent::ComboEnded`]
+    ComboEnded,
+    /// [`EngineEvent::QuestStarted`]
+    QuestStarted,
+    /// [`EngineEvent::QuestAdvanced`]
+    QuestAdvanced,
+    /// [`EngineEvent::QuestCompleted`]
+    QuestCompleted,
+    /// [`EngineEvent::QuestFailed`]
+    QuestFailed,
+    /// [`EngineEvent::LevelChanged`]
+    LevelChanged,
+    /// [`EngineEvent::DialogueNodeEntered`]
+    DialogueNodeEntered,
+    /// [`EngineEvent::DialogueChoiceMade`]
+    DialogueChoiceMade,
+    /// [`EngineEvent::DialogueEnded`]
+    DialogueEnded,
+    /// [`EngineEvent::ItemUsed`]
+    ItemUsed,
+    /// [`EngineEvent::ItemDropped`]
+    ItemDropped,
+    /// [`EngineEvent::ItemEquipped`]
+    ItemEquipped,
+    /// [`EngineEvent::ItemUnequipped`]
+    ItemUnequipped,
+    /// [`EngineEvent::ItemActivated`]
+    ItemActivated,
+    /// [`EngineEvent::ItemBought`]
+    ItemBought,
+    /// [`EngineEvent::ItemSold`]
+    ItemSold,
+    /// [`EngineEvent::PlayerJoined`]
+    PlayerJoined,
+    /// [`EngineEvent::PlayerLeft`]
+    PlayerLeft,
+    /// [`EngineEvent::PlayerReadyChanged`]
+    PlayerReadyChanged,
+    /// [`EngineEvent::ChatReceived`]
+    ChatReceived,
+    /// [`EngineEvent::Custom`]
+    Custom,
+}
+
+/// Selects which events a subscriber is called for
+///
+/// # Example
+/// ```rust
+/// use lonely_engine::event::{EngineEvent, EventFilter, EventKind};
+///
+/// let keys = EventFilter::Kinds(vec![EventKind::KeyPressed, EventKind::KeyReleased]);
+/// let powerups = EventFilter::custom("Powerup*");
+/// assert!(powerups.matches(&EngineEvent::Custom("PowerupCollected:Fireball".into())));
+/// assert!(!powerups.matches(&EngineEvent::Custom("GameSaved".into())));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventFilter {
+    /// Every event
+    Any,
+    /// Events of one variant
+    Kind(EventKind),
+    /// Events of any of several variants
+    Kinds(Vec<EventKind>),
+    /// `Custom` events whose text matches a pattern, where `*` matches any run of characters
+    Custom(String),
+}
+
+impl EventFilter {
+    /// Filters `Custom` events by a pattern such as `"Powerup*"` or `"*:Boss"`
+    pub fn custom(pattern: &str) -> Self {
+        EventFilter::Custom(pattern.to_string())
+    }
+
+    /// Returns whether an event passes the filter
+    pub fn matches(&self, event: &EngineEvent) -> bool {
+        match self {
+            EventFilter::Any => true,
+            EventFilter::Kind(kind) => event.kind() == *kind,
+            EventFilter::Kinds(kinds) => kinds.contains(&event.kind()),
+            EventFilter::Custom(pattern) => matches!(event, EngineEvent::Custom(text) if matches_pattern(pattern, text)),
+        }
+    }
+}
+
+impl From<EventKind> for EventFilter {
+    fn from(kind: EventKind) -> Self {
+        EventFilter::Kind(kind)
+    }
+}
+
+/// Matches text against a pattern where `*` stands for any run of characters
+fn matches_pattern(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No `*` at all, the whole text must match
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Handler that responds to events by queueing engine commands
+type Responder = Box<dyn Fn(&EngineEvent, &mut CommandQueue)>;
+
+/// Callback registered on an [`EventBus`]
+enum Handler {
+    /// Only observes events
+    Observer(Box<dyn Fn(&EngineEvent)>),
+    /// Responds to events with engine commands
+    Responder(Responder),
+}
+
+/// Handler with the filter checked before calling it
+struct Subscriber {
+    filter: EventFilter,
+    handler: Handler,
+}
+
 /// Central event bus for publish-subscribe communication.  
 /// # Examples
 /// 
@@ -112,13 +917,55 @@ pub struct EventBus {
     /// # use lonely_engine::event::EventBus;
     /// let bus = EventBus::new();
     /// ```
-    subscribers: Vec<Box<dyn Fn(&EngineEvent) -> ()>>,
+    subscribers: Vec<Subscriber>,
+    /// Commands queued by responders since the last [`EventBus::take_commands`]
+    queued: RefCell<CommandQueue>,
+    /// Most events dispatched per frame, `None` for unlimited
+    frame_limit: Option<usize>,
+    /// Events dispatched since the last [`EventBus::begin_frame`]
+    frame_count: Cell<usize>,
+    /// Events dropped since the last [`EventBus::begin_frame`]
+    dropped: Cell<usize>,
+    /// Time spent inside subscribers since the last [`EventBus::take_dispatch_time`]
+    dispatch_time: Cell<Duration>,
+    /// Cloned into every [`EventSender`]
+    remote_sender: mpsc::Sender<EngineEvent>,
+    /// Events sent from other threads, waiting for [`EventBus::dispatch_remote`]
+    remote: mpsc::Receiver<EngineEvent>,
+    /// Most recent events, oldest first, see [`EventBus::set_history_capacity`]
+    history: RefCell<VecDeque<EventRecord>>,
+    /// Events kept in the history, `0` disables recording
+    history_capacity: usize,
+    /// Frame number stamped on recorded events
+    frame: Cell<u64>,
+    /// When the bus was created, recorded times are relative to it
+    started: Instant,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl EventBus {
     /// Creates a new empty EventBus
     pub fn new() -> Self {
-        Self { subscribers: Vec::new() }
+        let (remote_sender, remote) = mpsc::channel();
+        Self {
+            subscribers: Vec::new(),
+            queued: RefCell::new(CommandQueue::new()),
+            frame_limit: None,
+            frame_count: Cell::new(0),
+            dropped: Cell::new(0),
+            dispatch_time: Cell::new(Duration::ZERO),
+            remote_sender,
+            remote,
+            history: RefCell::new(VecDeque::new()),
+            history_capacity: 0,
+            frame: Cell::new(0),
+            started: Instant::now(),
+        }
     }
 
     /// Registers an event handler.  
@@ -133,8 +980,54 @@ impl EventBus {
     ///     }
     /// });
     /// ```
-    pub fn subscribe(&mut self, callback: impl Fn(&EngineEvent) -> () + 'static) {
-        self.subscribers.push(Box::new(callback));
+    pub fn subscribe(&mut self, callback: impl Fn(&EngineEvent) + 'static) {
+        self.subscribe_filtered(EventFilter::Any, callback);
+    }
+
+    /// Registers an event handler called only for events passing a filter.  
+    /// The bus checks the filter, so handlers skip the events they do not care about
+    /// without being called.
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::{EventBus, EngineEvent, EventFilter, EventKind};
+    /// let mut bus = EventBus::new();
+    ///
+    /// bus.subscribe_filtered(EventKind::KeyPressed, |event| println!("{event:?}"));
+    /// bus.subscribe_filtered(EventFilter::custom("Quest:*"), |event| {
+    ///     if let EngineEvent::Custom(text) = event {
+    ///         println!("Quest update: {}", &text[6..]);
+    ///     }
+    /// });
+    /// ```
+    pub fn subscribe_filtered(&mut self, filter: impl Into<EventFilter>, callback: impl Fn(&EngineEvent) + 'static) {
+        self.subscribers.push(Subscriber { filter: filter.into(), handler: Handler::Observer(Box::new(callback)) });
+    }
+
+    /// Registers an event handler that can change the world by queueing commands.  
+    /// The engine applies queued commands at its next command-processing point,
+    /// together with the commands returned by updatables and behaviors.
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::{engine::{EngineCommand, GroupSelector}, event::{EventBus, EngineEvent}};
+    /// let mut bus = EventBus::new();
+    ///
+    /// // Door opens when anything steps on the plate
+    /// bus.subscribe_with_commands(|event, commands| {
+    ///     if let EngineEvent::ZoneEntered(_, zone) = event && zone == "plate" {
+    ///         commands.push(EngineCommand::DespawnGroup(GroupSelector::tag("door")));
+    ///     }
+    /// });
+    ///
+    /// bus.emit(EngineEvent::ZoneEntered(0, "plate".into()));
+    /// assert_eq!(bus.take_commands().len(), 1);
+    /// ```
+    pub fn subscribe_with_commands(&mut self, callback: impl Fn(&EngineEvent, &mut CommandQueue) + 'static) {
+        self.subscribe_filtered_with_commands(EventFilter::Any, callback);
+    }
+
+    /// Registers a command-queueing handler called only for events passing a filter
+    pub fn subscribe_filtered_with_commands(&mut self, filter: impl Into<EventFilter>, callback: impl Fn(&EngineEvent, &mut CommandQueue) + 'static) {
+        self.subscribers.push(Subscriber { filter: filter.into(), handler: Handler::Responder(Box::new(callback)) });
     }
 
     /// Broadcasts an event to all subscribers.  
@@ -146,8 +1039,229 @@ impl EventBus {
     /// bus.emit(EngineEvent::Custom("GameQuit".into()));
     /// ```
     pub fn emit(&self, event: EngineEvent) {
-        for callback in &self.subscribers {
-            callback(&event);
+        if self.frame_limit.is_some_and(|limit| self.frame_count.get() >= limit) {
+            self.dropped.set(self.dropped.get() + 1);
+            self.record(&event, 0, true);
+            return;
+        }
+        self.frame_count.set(self.frame_count.get() + 1);
+
+        let start = Instant::now();
+        let mut handlers = 0;
+        for subscriber in self.subscribers.iter().filter(|subscriber| subscriber.filter.matches(&event)) {
+            match &subscriber.handler {
+                Handler::Observer(callback) => callback(&event),
+                Handler::Responder(callback) => callback(&event, &mut self.queued.borrow_mut()),
+            }
+            handlers += 1;
+        }
+        self.dispatch_time.set(self.dispatch_time.get() + start.elapsed());
+        self.record(&event, handlers, false);
+    }
+
+    /// Keeps the last `capacity` events in a ring buffer, `0` (the default) stops recording
+    ///
+    /// # Notes
+    /// - Events dropped by the frame limit are recorded too, marked as dropped
+    /// - Lowering the capacity discards the oldest records
+    ///
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::{EventBus, EngineEvent, EventKind};
+    /// let mut bus = EventBus::new();
+    /// bus.set_history_capacity(2);
+    /// bus.subscribe_filtered(EventKind::Custom, |_| {});
+    ///
+    /// bus.set_frame(7);
+    /// bus.emit(EngineEvent::Custom("a".into()));
+    /// bus.emit(EngineEvent::Custom("b".into()));
+    /// bus.emit(EngineEvent::GamePaused);
+    ///
+    /// let history = bus.history();
+    /// assert_eq!(history.len(), 2);
+    /// assert_eq!((history[0].frame, history[0].handlers), (7, 1));
+    /// assert_eq!(history[1].handlers, 0);
+    /// ```
+    pub fn set_history_capacity(&mut self, capacity: usize) {
+        self.history_capacity = capacity;
+        let history = self.history.get_mut();
+        while history.len() > capacity {
+            history.pop_front();
         }
     }
-}
\ No newline at end of file
+
+    /// Number of events kept in the history, `0` when recording is off
+    pub fn history_capacity(&self) -> usize {
+        self.history_capacity
+    }
+
+    /// Recorded events, oldest first
+    pub fn history(&self) -> Vec<EventRecord> {
+        self.history.borrow().iter().cloned().collect()
+    }
+
+    /// Forgets every recorded event
+    pub fn clear_history(&self) {
+        self.history.borrow_mut().clear();
+    }
+
+    /// Writes the recorded events to a text file, one per line, oldest first
+    pub fn dump_history(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut text = String::new();
+        for record in self.history.borrow().iter() {
+            text.push_str(&format!("{record}\n"));
+        }
+        fs::write(path, text)
+    }
+
+    /// Sets the frame number stamped on events recorded from now on
+    ///
+    /// The engine calls this at the start of every frame.
+    pub fn set_frame(&self, frame: u64) {
+        self.frame.set(frame);
+    }
+
+    /// Appends an event to the history when recording is on
+    fn record(&self, event: &EngineEvent, handlers: usize, dropped: bool) {
+        if self.history_capacity == 0 {
+            return;
+        }
+        let mut history = self.history.borrow_mut();
+        if history.len() >= self.history_capacity {
+            history.pop_front();
+        }
+        history.push_back(EventRecord {
+            frame: self.frame.get(),
+            time: self.started.elapsed(),
+            event: event.clone(),
+            handlers,
+            dropped,
+        });
+    }
+
+    /// Caps the events dispatched per frame, later events are dropped until [`EventBus::begin_frame`]
+    ///
+    /// # Example
+    /// ```rust
+    /// # use lonely_engine::event::{EventBus, EngineEvent};
+    /// let mut bus = EventBus::new();
+    /// bus.set_frame_limit(Some(2));
+    /// for _ in 0..5 {
+    ///     bus.emit(EngineEvent::Custom("spam".into()));
+    /// }
+    /// assert_eq!(bus.begin_frame(), 3);
+    /// ```
+    pub fn set_frame_limit(&mut self, limit: Option<usize>) {
+        self.frame_limit = limit;
+    }
+
+    /// Starts counting events for a new frame
+    ///
+    /// # Returns
+    /// Number of events dropped by the frame limit since the previous call
+    pub fn begin_frame(&self) -> usize {
+        self.frame_count.set(0);
+        self.dropped.replace(0)
+    }
+
+    /// Events dispatched since the last [`EventBus::begin_frame`], not counting dropped ones
+    pub fn frame_events(&self) -> usize {
+        self.frame_count.get()
+    }
+
+    /// Returns a handle other threads can emit events through
+    pub fn sender(&self) -> EventSender {
+        EventSender { sender: self.remote_sender.clone() }
+    }
+
+    /// Broadcasts the events sent through [`EventSender`]s, in the order they were sent
+    ///
+    /// The engine calls this at the start of every frame, so subscribers always
+    /// run on the main thread.
+    ///
+    /// # Returns
+    /// Number of events dispatched
+    pub fn dispatch_remote(&self) -> usize {
+        let mut count = 0;
+        for event in self.remote.try_iter() {
+            self.emit(event);
+            count += 1;
+        }
+        count
+    }
+
+    /// Removes and returns the commands queued by responders, oldest first
+    pub fn take_commands(&self) -> Vec<EngineCommand> {
+        self.queued.borrow_mut().take()
+    }
+
+    /// Returns the time spent dispatching events since the previous call and resets it
+    ///
+    /// Used by the engine profiler to attribute frame time to event handlers.
+    pub fn take_dispatch_time(&self) -> Duration {
+        self.dispatch_time.replace(Duration::ZERO)
+    }
+}
+
+/// Event kept in the [`EventBus`] history
+///
+/// # Notes
+/// - Displays as one line, the format of [`EventBus::dump_history`]:
+///   `frame 42 +3.250s 2 handlers KeyPressed(Space)`
+#[derive(Debug, Clone)]
+pub struct EventRecord {
+    /// Frame the event was emitted in
+    pub frame: u64,
+    /// Time since the bus was created
+    pub time: Duration,
+    /// The event itself
+    pub event: EngineEvent,
+    /// Subscribers whose filter matched and were called
+    pub handlers: usize,
+    /// Whether the per-frame event limit dropped the event before dispatch
+    pub dropped: bool,
+}
+
+impl fmt::Display for EventRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let outcome = if self.dropped { "dropped".to_string() } else { format!("{} handlers", self.handlers) };
+        write!(f, "frame {} +{:.3}s {} {:?}", self.frame, self.time.as_secs_f64(), outcome, self.event)
+    }
+}
+
+/// Handle emitting events into an [`EventBus`] from other threads
+///
+/// Subscribers are not thread-safe, so sent events wait in a channel until the
+/// bus dispatches them on the main thread at the next frame boundary.
+///
+/// # Notes
+/// - Handles are cheap to clone and can be moved to loaders, network threads, or audio callbacks
+/// - Sent events count towards the frame they are dispatched in
+///
+/// # Example
+/// ```rust
+/// use lonely_engine::event::{EventBus, EngineEvent};
+///
+/// let bus = EventBus::new();
+/// let loader = bus.sender();
+/// std::thread::spawn(move || {
+///     // ... load the level ...
+///     loader.send(EngineEvent::Custom("LevelLoaded:forest".into()));
+/// }).join().unwrap();
+///
+/// assert_eq!(bus.dispatch_remote(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct EventSender {
+    sender: mpsc::Sender<EngineEvent>,
+}
+
+impl EventSender {
+    /// Queues an event for the bus
+    ///
+    /// # Returns
+    /// `false` when the bus no longer exists
+    pub fn send(&self, event: EngineEvent) -> bool {
+        self.sender.send(event).is_ok()
+    }
+}
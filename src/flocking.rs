@@ -0,0 +1,228 @@
+//! Flocking for fish, birds, and swarms
+//!
+//! A [`Flock`] steers every active object with a tag like a boid: each member
+//! looks at the others within its perception radius and blends three urges,
+//! weighted to taste:
+//! - cohesion, heading for the center of its neighbors
+//! - alignment, matching their heading
+//! - separation, keeping out of their personal space
+//!
+//! Members keep fractional positions in [`Agent`]s and move in whole cells
+//! through `EngineCommand::MoveObject`. Neighbors are found through a
+//! [`SpatialHash`] rebuilt every update, so large swarms stay cheap.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{engine::Engine, flocking::Flock, game_object::GameObject};
+//!
+//! let mut engine = Engine::new(80, 24);
+//! for i in 0..12 {
+//!     let mut fish = GameObject::new(10 + i % 4, 8 + i / 4, '>');
+//!     fish.tag = "fish".into();
+//!     engine.add_object(fish);
+//! }
+//! engine.add_updatable(Flock::new("fish", 6.0).weights(1.0, 0.8, 1.5).perception(5.0).wander(0.4));
+//! ```
+
+use std::collections::HashMap;
+use crate::{
+    engine::{EngineCommand, Updatable, UpdateContext},
+    game_object::GameObject,
+    rng::Rng,
+    spatial_hash::SpatialHash,
+    steering::{self, Agent, Steering, Wander},
+};
+
+/// Distance members see each other at unless configured otherwise
+pub const DEFAULT_PERCEPTION: f32 = 6.0;
+
+/// Distance members keep from each other unless configured otherwise
+pub const DEFAULT_SEPARATION_RADIUS: f32 = 2.0;
+
+/// Distance from the edge of the play field at which members turn back
+pub const EDGE_MARGIN: f32 = 2.0;
+
+/// Largest heading change of the wander urge per update, in radians
+const WANDER_JITTER: f32 = 0.4;
+
+/// Boids steering of every active object with a tag
+///
+/// # Notes
+/// - Members are tracked by object index, a member whose index changed is resynced to its new object's cell
+/// - Add it with `Engine::add_updatable`, or call [`step`](Self::step) from a system of your own
+#[derive(Debug, Clone)]
+pub struct Flock {
+    /// Tag of the objects in the flock
+    pub tag: String,
+    /// Top speed in cells per second
+    pub max_speed: f32,
+    /// Distance members see each other at
+    pub perception: f32,
+    /// Distance members keep from each other
+    pub separation_radius: f32,
+    /// Weight of heading for the neighbors' center
+    pub cohesion: f32,
+    /// Weight of matching the neighbors' heading
+    pub alignment: f32,
+    /// Weight of keeping away from close neighbors
+    pub separation: f32,
+    /// Weight of random drifting, so a lone member keeps moving
+    pub wander: f32,
+    /// Whether members turn back at the edges of the play field
+    pub contained: bool,
+    members: HashMap<usize, (Agent, Wander)>,
+    grid: SpatialHash,
+}
+
+impl Flock {
+    /// Creates a flock with equal weights and no wandering
+    ///
+    /// # Arguments
+    /// * `tag` - Tag of the objects in the flock
+    /// * `max_speed` - Top speed in cells per second
+    pub fn new(tag: &str, max_speed: f32) -> Self {
+        Self {
+            tag: tag.to_string(),
+            max_speed,
+            perception: DEFAULT_PERCEPTION,
+            separation_radius: DEFAULT_SEPARATION_RADIUS,
+            cohesion: 1.0,
+            alignment: 1.0,
+            separation: 1.0,
+            wander: 0.0,
+            contained: true,
+            members: HashMap::new(),
+            grid: SpatialHash::new(DEFAULT_PERCEPTION),
+        }
+    }
+
+    /// Sets the weights of cohesion, alignment, and separation
+    pub fn weights(mut self, cohesion: f32, alignment: f32, separation: f32) -> Self {
+        (self.cohesion, self.alignment, self.separation) = (cohesion, alignment, separation);
+        self
+    }
+
+    /// Sets the distance members see each other at
+    pub fn perception(mut self, radius: f32) -> Self {
+        self.perception = radius.max(0.0);
+        self
+    }
+
+    /// Sets the distance members keep from each other
+    pub fn separation_radius(mut self, radius: f32) -> Self {
+        self.separation_radius = radius.max(0.0);
+        self
+    }
+
+    /// Sets the weight of random drifting
+    pub fn wander(mut self, weight: f32) -> Self {
+        self.wander = weight;
+        self
+    }
+
+    /// Sets whether members turn back at the edges of the play field
+    pub fn contained(mut self, contained: bool) -> Self {
+        self.contained = contained;
+        self
+    }
+
+    /// Number of members found by the last step
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Returns whether the last step found no members
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Steers every member once
+    ///
+    /// # Arguments
+    /// * `objects` - Every object in the world, members are the active ones with the tag
+    /// * `rng` - Source of the wander urge
+    /// * `bounds` - Width and height of the play field members stay in, `None` to roam freely
+    /// * `delta_time` - Seconds since the previous step
+    ///
+    /// # Returns
+    /// Moves of the members that crossed into a new cell
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{engine::EngineCommand, flocking::Flock, game_object::GameObject, rng::Rng};
+    ///
+    /// let mut objects: Vec<GameObject> = (0..2).map(|i| GameObject::new(10 + i, 10, 'o')).collect();
+    /// objects.iter_mut().for_each(|bird| bird.tag = "bird".into());
+    /// let mut flock = Flock::new("bird", 10.0).weights(0.0, 0.0, 1.0);
+    ///
+    /// // Too close: the two push apart
+    /// let moves = flock.step(&objects, &mut Rng::new(1), None, 0.2);
+    /// assert!(matches!(moves[..], [EngineCommand::MoveObject(0, -1, 0), EngineCommand::MoveObject(1, 1, 0)]));
+    /// ```
+    pub fn step(&mut self, objects: &[GameObject], rng: &mut Rng, bounds: Option<(usize, usize)>, delta_time: f32) -> Vec<EngineCommand> {
+        let indices: Vec<usize> = objects.iter().enumerate().filter(|(_, obj)| obj.active && obj.tag == self.tag).map(|(index, _)| index).collect();
+        self.members.retain(|index, _| indices.binary_search(index).is_ok());
+        if self.grid.cell_size() == self.perception.max(1.0) {
+            self.grid.clear();
+        } else {
+            self.grid = SpatialHash::new(self.perception.max(1.0));
+        }
+        for &index in &indices {
+            let max_speed = self.max_speed;
+            let (agent, _) = self.members.entry(index).or_insert_with(|| (Agent::at(&objects[index], max_speed), Wander::new(WANDER_JITTER)));
+            agent.sync(&objects[index]);
+            agent.max_speed = max_speed;
+            self.grid.insert(index, agent.position());
+        }
+
+        // Every member decides on the positions and velocities of the same moment before any moves
+        let mut velocities = Vec::with_capacity(indices.len());
+        for &index in &indices {
+            let (agent, wander) = &self.members[&index];
+            let position = agent.position();
+            let mut positions = Vec::new();
+            let mut headings = Vec::new();
+            self.grid.for_each_near(position, self.perception, |other, other_position| {
+                if other != index {
+                    positions.push(other_position);
+                    headings.push(self.members[&other].0.velocity);
+                }
+            });
+
+            let mut wander = *wander;
+            let mut blend = Steering::new()
+                .add(steering::cohesion(position, &positions, self.max_speed), self.cohesion)
+                .add(steering::alignment(&headings, self.max_speed), self.alignment)
+                .add(steering::separation(position, &positions, self.separation_radius, self.max_speed), self.separation);
+            if self.wander != 0.0 {
+                blend = blend.add(wander.next(rng, self.max_speed), self.wander);
+            }
+            if let Some((width, height)) = bounds.filter(|_| self.contained) {
+                let inside = (
+                    position.0.clamp(EDGE_MARGIN, (width as f32 - 1.0 - EDGE_MARGIN).max(EDGE_MARGIN)),
+                    position.1.clamp(EDGE_MARGIN, (height as f32 - 1.0 - EDGE_MARGIN).max(EDGE_MARGIN)),
+                );
+                blend = blend.add(steering::seek(position, inside, self.max_speed), 2.0);
+            }
+            velocities.push((index, wander, blend.result(self.max_speed)));
+        }
+
+        let mut moves = Vec::new();
+        for (index, wander, velocity) in velocities {
+            if let Some((agent, heading)) = self.members.get_mut(&index) {
+                *heading = wander;
+                let (dx, dy) = agent.integrate(velocity, delta_time);
+                if (dx, dy) != (0, 0) {
+                    moves.push(EngineCommand::MoveObject(index, dx, dy));
+                }
+            }
+        }
+        moves
+    }
+}
+
+impl Updatable for Flock {
+    fn update_with_context(&mut self, ctx: &mut UpdateContext) -> Vec<EngineCommand> {
+        self.step(ctx.objects, ctx.rng, Some((ctx.width, ctx.height)), ctx.delta_time)
+    }
+}
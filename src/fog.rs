@@ -0,0 +1,223 @@
+//! Fog of war and explored-tiles tracking
+//!
+//! A [`FogOfWar`] covers a tilemap and gives every cell a [`Visibility`]:
+//! - Hidden, never seen, drawn as the renderer's clear style
+//! - Explored, seen before but not now, drawn as the remembered tile dimmed
+//!   and grey, without the objects standing on it
+//! - Visible, in sight of a viewer this turn, drawn as usual
+//!
+//! Sight is worked out with [`field_of_view`] from every active object
+//! carrying the fog's viewer tag, stopped by the cells marked opaque. The
+//! engine refreshes it after each update and before each frame, so setting
+//! `Engine::fog`, or giving a level one with [`Level::with_fog`], is all it
+//! takes. Each level keeps its own explored cells while another level is
+//! loaded, and world snapshots capture them.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{engine::Engine, fog::{FogOfWar, Visibility}, game_object::GameObject, tilemap::Tilemap};
+//!
+//! let map = Tilemap::from_str("#######\n#..#..#\n#######");
+//! let mut engine = Engine::new(80, 24);
+//! engine.renderer.set_background(map.clone());
+//! engine.fog = Some(FogOfWar::from_tilemap(&map, |cell| cell.ch == '#').viewer("player", 8.0));
+//!
+//! let mut player = GameObject::new(1, 1, '@');
+//! player.tag = "player".into();
+//! engine.add_object(player);
+//! engine.refresh_fog();
+//!
+//! let fog = engine.fog.as_ref().unwrap();
+//! assert_eq!(fog.visibility(2, 1), Visibility::Visible);
+//! assert_eq!(fog.visibility(3, 1), Visibility::Visible); // The wall in between
+//! assert_eq!(fog.visibility(5, 1), Visibility::Hidden); // The room behind it
+//! ```
+//!
+//! [`field_of_view`]: crate::helpers::field_of_view
+//! [`Level::with_fog`]: crate::level::Level::with_fog
+
+use crate::{game_object::GameObject, helpers::field_of_view, renderer::Cell, tilemap::Tilemap};
+
+/// Sight radius of viewers unless configured otherwise
+pub const DEFAULT_SIGHT_RADIUS: f32 = 8.0;
+
+/// What the player knows about a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Visibility {
+    /// Never seen
+    Hidden,
+    /// Seen before, not in sight now
+    Explored,
+    /// In sight
+    Visible,
+}
+
+/// Explored and visible cells of a tilemap
+///
+/// # Notes
+/// - Cells outside the fog are left alone by the renderer and reported as [`Visibility::Hidden`]
+/// - With the `serde` feature it serializes with the rest of a save
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FogOfWar {
+    width: usize,
+    height: usize,
+    /// Cells seen at least once
+    explored: Vec<bool>,
+    /// Cells in sight since the last refresh
+    visible: Vec<bool>,
+    /// Cells stopping sight
+    opaque: Vec<bool>,
+    /// Tag of the objects whose sight clears the fog
+    pub viewer: String,
+    /// Distance viewers see, in cells
+    pub radius: f32,
+}
+
+impl FogOfWar {
+    /// Creates a fog over an area where nothing stops sight, seen by objects tagged `"player"`
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            explored: vec![false; width * height],
+            visible: vec![false; width * height],
+            opaque: vec![false; width * height],
+            viewer: "player".to_string(),
+            radius: DEFAULT_SIGHT_RADIUS,
+        }
+    }
+
+    /// Creates a fog covering a tilemap, with the cells stopping sight picked by a function
+    ///
+    /// # Arguments
+    /// * `tilemap` - Map covered, usually the level's background
+    /// * `opaque` - Returns whether a tile stops sight, such as a wall
+    pub fn from_tilemap(tilemap: &Tilemap, opaque: impl Fn(&Cell) -> bool) -> Self {
+        let mut fog = Self::new(tilemap.width(), tilemap.height());
+        for (y, row) in tilemap.rows().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                fog.opaque[y * fog.width + x] = opaque(cell);
+            }
+        }
+        fog
+    }
+
+    /// Sets the tag of the objects whose sight clears the fog, and how far they see
+    pub fn viewer(mut self, tag: &str, radius: f32) -> Self {
+        self.viewer = tag.to_string();
+        self.radius = radius;
+        self
+    }
+
+    /// Width in cells
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height in cells
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// What is known about a cell
+    pub fn visibility(&self, x: usize, y: usize) -> Visibility {
+        match self.index(x, y) {
+            Some(index) if self.visible[index] => Visibility::Visible,
+            Some(index) if self.explored[index] => Visibility::Explored,
+            _ => Visibility::Hidden,
+        }
+    }
+
+    /// Returns whether a cell is in sight
+    pub fn is_visible(&self, x: usize, y: usize) -> bool {
+        self.visibility(x, y) == Visibility::Visible
+    }
+
+    /// Returns whether a cell was ever seen
+    pub fn is_explored(&self, x: usize, y: usize) -> bool {
+        self.visibility(x, y) != Visibility::Hidden
+    }
+
+    /// Returns whether a cell stops sight
+    pub fn is_opaque(&self, x: usize, y: usize) -> bool {
+        self.index(x, y).is_some_and(|index| self.opaque[index])
+    }
+
+    /// Changes whether a cell stops sight, such as a door opening
+    ///
+    /// # Notes
+    /// - Positions outside the fog are ignored
+    pub fn set_opaque(&mut self, x: usize, y: usize, opaque: bool) {
+        if let Some(index) = self.index(x, y) {
+            self.opaque[index] = opaque;
+        }
+    }
+
+    /// Number of cells ever seen, for exploration percentages
+    pub fn explored_count(&self) -> usize {
+        self.explored.iter().filter(|explored| **explored).count()
+    }
+
+    /// Marks every cell explored, such as when reading a magic map
+    pub fn explore_all(&mut self) {
+        self.explored.fill(true);
+    }
+
+    /// Forgets every explored cell, such as after an amnesia potion
+    pub fn forget(&mut self) {
+        self.explored.fill(false);
+        self.visible.fill(false);
+    }
+
+    /// Puts every cell out of sight, keeping what was explored
+    pub fn clear_visible(&mut self) {
+        self.visible.fill(false);
+    }
+
+    /// Marks the cells seen from a cell visible and explored
+    ///
+    /// # Arguments
+    /// * `origin` - Cell looking
+    /// * `radius` - Distance seen, in cells
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::fog::{FogOfWar, Visibility};
+    ///
+    /// let mut fog = FogOfWar::new(20, 5);
+    /// fog.reveal((2, 2), 3.0);
+    /// fog.clear_visible();
+    /// fog.reveal((15, 2), 3.0);
+    ///
+    /// assert_eq!(fog.visibility(2, 2), Visibility::Explored);
+    /// assert_eq!(fog.visibility(15, 2), Visibility::Visible);
+    /// assert_eq!(fog.visibility(9, 2), Visibility::Hidden);
+    /// ```
+    pub fn reveal(&mut self, origin: (usize, usize), radius: f32) {
+        let seen = field_of_view(origin, radius, |x, y| self.is_opaque(x, y));
+        for (x, y) in seen {
+            if let Some(index) = self.index(x, y) {
+                self.visible[index] = true;
+                self.explored[index] = true;
+            }
+        }
+    }
+
+    /// Replaces what is in sight by what the viewers among objects see
+    ///
+    /// # Notes
+    /// - Called by the engine after each update and before each frame
+    pub fn refresh(&mut self, objects: &[GameObject]) {
+        self.clear_visible();
+        let viewers: Vec<(usize, usize)> = objects.iter().filter(|obj| obj.active && obj.tag == self.viewer).map(|obj| (obj.x, obj.y)).collect();
+        for origin in viewers {
+            self.reveal(origin, self.radius);
+        }
+    }
+
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+}
@@ -0,0 +1,327 @@
+//! Large multi-cell text fonts
+//!
+//! Renders strings as big figlet-style glyphs for title screens and score displays.
+//! Provides:
+//! - Built-in 3x5 ([`Font::small`]) and 5x7 ([`Font::standard`]) block fonts
+//! - Loading FIGlet `.flf` font files ([`Font::from_flf`])
+//! - Text measurement for centering
+//!
+//! Drawing is done by [`Renderer::draw_big_text`].
+//!
+//! [`Renderer::draw_big_text`]: crate::renderer::Renderer::draw_big_text
+
+use std::{collections::HashMap, fs, io, path::Path};
+
+/// Character used for lit pixels of the built-in fonts
+const BLOCK: char = '█';
+
+/// 3x5 glyphs, rows separated by spaces, `#` lit and `.` unlit
+const SMALL_GLYPHS: &[(char, &str)] = &[
+    ('A', ".#. #.# ### #.# #.#"),
+    ('B', "##. #.# ##. #.# ##."),
+    ('C', ".## #.. #.. #.. .##"),
+    ('D', "##. #.# #.# #.# ##."),
+    ('E', "### #.. ##. #.. ###"),
+    ('F', "### #.. ##. #.. #.."),
+    ('G', ".## #.. #.# #.# .##"),
+    ('H', "#.# #.# ### #.# #.#"),
+    ('I', "### .#. .#. .#. ###"),
+    ('J', "..# ..# ..# #.# .#."),
+    ('K', "#.# #.# ##. #.# #.#"),
+    ('L', "#.. #.. #.. #.. ###"),
+    ('M', "#.# ### ### #.# #.#"),
+    ('N', "### #.# #.# #.# #.#"),
+    ('O', ".#. #.# #.# #.# .#."),
+    ('P', "##. #.# ##. #.. #.."),
+    ('Q', ".#. #.# #.# ##. .##"),
+    ('R', "##. #.# ##. #.# #.#"),
+    ('S', ".## #.. .#. ..# ##."),
+    ('T', "### .#. .#. .#. .#."),
+    ('U', "#.# #.# #.# #.# ###"),
+    ('V', "#.# #.# #.# #.# .#."),
+    ('W', "#.# #.# ### ### #.#"),
+    ('X', "#.# #.# .#. #.# #.#"),
+    ('Y', "#.# #.# .#. .#. .#."),
+    ('Z', "### ..# .#. #.. ###"),
+    ('0', "### #.# #.# #.# ###"),
+    ('1', ".#. ##. .#. .#. ###"),
+    ('2', "##. ..# .#. #.. ###"),
+    ('3', "##. ..# .#. ..# ##."),
+    ('4', "#.# #.# ### ..# ..#"),
+    ('5', "### #.. ##. ..# ##."),
+    ('6', ".## #.. ### #.# ###"),
+    ('7', "### ..# .#. .#. .#."),
+    ('8', "### #.# ### #.# ###"),
+    ('9', "### #.# ### ..# ##."),
+    (' ', "... ... ... ... ..."),
+    ('!', ".#. .#. .#. ... .#."),
+    ('?', "##. ..# .#. ... .#."),
+    ('.', "... ... ... ... .#."),
+    (',', "... ... ... .#. #.."),
+    (':', "... .#. ... .#. ..."),
+    ('-', "... ... ### ... ..."),
+    ('+', "... .#. ### .#. ..."),
+    ('/', "..# ..# .#. #.. #.."),
+    ('\'', ".#. .#. ... ... ..."),
+    ('(', ".#. #.. #.. #.. .#."),
+    (')', ".#. ..# ..# ..# .#."),
+    ('=', "... ### ... ### ..."),
+    ('%', "#.# ..# .#. #.. #.#"),
+];
+
+/// 5x7 glyphs, rows separated by spaces, `#` lit and `.` unlit
+const STANDARD_GLYPHS: &[(char, &str)] = &[
+    ('A', ".###. #...# #...# ##### #...# #...# #...#"),
+    ('B', "####. #...# #...# ####. #...# #...# ####."),
+    ('C', ".###. #...# #.... #.... #.... #...# .###."),
+    ('D', "####. #...# #...# #...# #...# #...# ####."),
+    ('E', "##### #.... #.... ####. #.... #.... #####"),
+    ('F', "##### #.... #.... ####. #.... #.... #...."),
+    ('G', ".###. #...# #.... #.### #...# #...# .####"),
+    ('H', "#...# #...# #...# ##### #...# #...# #...#"),
+    ('I', ".###. ..#.. ..#.. ..#.. ..#.. ..#.. .###."),
+    ('J', "..### ...#. ...#. ...#. ...#. #..#. .##.."),
+    ('K', "#...# #..#. #.#.. ##... #.#.. #..#. #...#"),
+    ('L', "#.... #.... #.... #.... #.... #.... #####"),
+    ('M', "#...# ##.## #.#.# #.#.# #...# #...# #...#"),
+    ('N', "#...# #...# ##..# #.#.# #..## #...# #...#"),
+    ('O', ".###. #...# #...# #...# #...# #...# .###."),
+    ('P', "####. #...# #...# ####. #.... #.... #...."),
+    ('Q', ".###. #...# #...# #...# #.#.# #..#. .##.#"),
+    ('R', "####. #...# #...# ####. #.#.. #..#. #...#"),
+    ('S', ".#### #.... #.... .###. ....# ....# ####."),
+    ('T', "##### ..#.. ..#.. ..#.. ..#.. ..#.. ..#.."),
+    ('U', "#...# #...# #...# #...# #...# #...# .###."),
+    ('V', "#...# #...# #...# #...# #...# .#.#. ..#.."),
+    ('W', "#...# #...# #...# #.#.# #.#.# #.#.# .#.#."),
+    ('X', "#...# #...# .#.#. ..#.. .#.#. #...# #...#"),
+    ('Y', "#...# #...# .#.#. ..#.. ..#.. ..#.. ..#.."),
+    ('Z', "##### ....# ...#. ..#.. .#... #.... #####"),
+    ('0', ".###. #...# #..## #.#.# ##..# #...# .###."),
+    ('1', "..#.. .##.. ..#.. ..#.. ..#.. ..#.. .###."),
+    ('2', ".###. #...# ....# ...#. ..#.. .#... #####"),
+    ('3', "####. ....# ....# .###. ....# ....# ####."),
+    ('4', "...#. ..##. .#.#. #..#. ##### ...#. ...#."),
+    ('5', "##### #.... ####. ....# ....# #...# .###."),
+    ('6', ".###. #.... #.... ####. #...# #...# .###."),
+    ('7', "##### ....# ...#. ..#.. .#... .#... .#..."),
+    ('8', ".###. #...# #...# .###. #...# #...# .###."),
+    ('9', ".###. #...# #...# .#### ....# ....# .###."),
+    (' ', "... ... ... ... ... ... ..."),
+    ('!', "..#.. ..#.. ..#.. ..#.. ..#.. ..... ..#.."),
+    ('?', ".###. #...# ....# ...#. ..#.. ..... ..#.."),
+    ('.', "..... ..... ..... ..... ..... ..... ..#.."),
+    (',', "..... ..... ..... ..... ..... ..#.. .#..."),
+    (':', "..... ..#.. ..... ..... ..... ..#.. ....."),
+    ('-', "..... ..... ..... ##### ..... ..... ....."),
+    ('+', "..... ..#.. ..#.. ##### ..#.. ..#.. ....."),
+    ('/', "....# ....# ...#. ..#.. .#... #.... #...."),
+    ('\'', "..#.. ..#.. ..... ..... ..... ..... ....."),
+    ('(', "...#. ..#.. .#... .#... .#... ..#.. ...#."),
+    (')', ".#... ..#.. ...#. ...#. ...#. ..#.. .#..."),
+    ('=', "..... ..... ##### ..... ##### ..... ....."),
+    ('%', "##..# ##..# ...#. ..#.. .#... #..## #..##"),
+];
+
+/// Code points of the German characters every FIGlet font defines after ASCII
+const FLF_GERMAN_CHARS: [u32; 7] = [196, 214, 220, 228, 246, 252, 223];
+
+/// Tallest FIGlet font accepted, guards against corrupt headers
+const MAX_FLF_HEIGHT: usize = 64;
+
+/// A multi-cell font mapping characters to glyph rows
+///
+/// # Notes
+/// - Spaces inside glyphs are transparent when drawn
+/// - Lowercase letters fall back to uppercase glyphs when the font has none
+/// - Characters without any glyph are skipped
+///
+/// # Example
+/// ```
+/// use lonely_engine::font::Font;
+///
+/// let font = Font::standard();
+/// assert_eq!(font.measure("HI"), (11, 7));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Font {
+    height: usize,
+    spacing: usize,
+    glyphs: HashMap<char, Vec<String>>,
+}
+
+impl Font {
+    /// Built-in 3x5 block font
+    pub fn small() -> Self {
+        Self::from_bitmap(SMALL_GLYPHS, 5)
+    }
+
+    /// Built-in 5x7 block font
+    pub fn standard() -> Self {
+        Self::from_bitmap(STANDARD_GLYPHS, 7)
+    }
+
+    /// Loads a FIGlet `.flf` font file
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lonely_engine::font::Font;
+    ///
+    /// let banner = Font::from_flf("fonts/big.flf").expect("Font missing");
+    /// ```
+    pub fn from_flf(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_flf_str(&fs::read_to_string(path)?)
+    }
+
+    /// Parses FIGlet font text
+    ///
+    /// # Notes
+    /// - Smushing rules are ignored, glyphs are placed at full width
+    /// - Code-tagged glyphs after the required set are loaded when present
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::font::Font;
+    ///
+    /// // One-row font where every printable ASCII glyph is "[]"
+    /// let mut flf = String::from("flf2a$ 1 1 2 -1 0\n");
+    /// for _ in 32..127 {
+    ///     flf.push_str("[]@@\n");
+    /// }
+    ///
+    /// let font = Font::from_flf_str(&flf).expect("Invalid font");
+    /// assert_eq!(font.glyph('A').unwrap(), ["[]"]);
+    /// assert_eq!(font.measure("OK"), (4, 1));
+    /// ```
+    pub fn from_flf_str(text: &str) -> io::Result<Self> {
+        let mut lines = text.lines();
+        let header = lines.next().ok_or_else(|| invalid_font("empty font file"))?;
+        if !header.starts_with("flf2a") {
+            return Err(invalid_font("missing flf2a signature"));
+        }
+
+        let hardblank = header.chars().nth(5).ok_or_else(|| invalid_font("missing hardblank"))?;
+        let params: Vec<i64> = header[5 + hardblank.len_utf8()..]
+            .split_whitespace()
+            .map_while(|part| part.parse().ok())
+            .collect();
+        let (height, comment_lines) = match params.as_slice() {
+            [height, _, _, _, comment_lines, ..] => (
+                usize::try_from(*height).map_err(|_| invalid_font("negative height"))?,
+                usize::try_from(*comment_lines).map_err(|_| invalid_font("negative comment line count"))?,
+            ),
+            _ => return Err(invalid_font("incomplete header")),
+        };
+        if height == 0 || height > MAX_FLF_HEIGHT {
+            return Err(invalid_font("unsupported font height"));
+        }
+
+        for _ in 0..comment_lines {
+            lines.next();
+        }
+
+        let read_glyph = |lines: &mut std::str::Lines| -> io::Result<Vec<String>> {
+            (0..height)
+                .map(|_| {
+                    let line = lines.next().ok_or_else(|| invalid_font("truncated glyph"))?;
+                    let endmark = line.chars().last().unwrap_or('@');
+                    Ok(line.trim_end_matches(endmark).replace(hardblank, " "))
+                })
+                .collect()
+        };
+
+        let mut glyphs = HashMap::new();
+        let required = (32u32..127).chain(FLF_GERMAN_CHARS);
+        for (i, code) in required.enumerate() {
+            match read_glyph(&mut lines) {
+                Ok(rows) => {
+                    if let Some(c) = char::from_u32(code) {
+                        glyphs.insert(c, rows);
+                    }
+                },
+                // Older fonts stop after ASCII
+                Err(_) if i >= 95 => break,
+                Err(error) => return Err(error),
+            }
+        }
+
+        // Code-tagged glyphs: "<code> [comment]" followed by the glyph rows
+        while let Some(tag) = lines.next() {
+            let code = tag.split_whitespace().next().and_then(parse_flf_code);
+            let rows = read_glyph(&mut lines)?;
+            if let Some(c) = code.and_then(char::from_u32) {
+                glyphs.insert(c, rows);
+            }
+        }
+
+        Ok(Self { height, spacing: 0, glyphs })
+    }
+
+    /// Height of every glyph in rows
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Blank columns inserted between glyphs
+    pub fn spacing(&self) -> usize {
+        self.spacing
+    }
+
+    /// Changes the blank columns inserted between glyphs
+    pub fn with_spacing(mut self, spacing: usize) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Returns the rows of a character's glyph, falling back to uppercase
+    pub fn glyph(&self, c: char) -> Option<&[String]> {
+        self.glyphs
+            .get(&c)
+            .or_else(|| self.glyphs.get(&c.to_ascii_uppercase()))
+            .map(Vec::as_slice)
+    }
+
+    /// Width of a glyph in columns
+    pub fn glyph_width(&self, c: char) -> usize {
+        self.glyph(c)
+            .map(|rows| rows.iter().map(|row| row.chars().count()).max().unwrap_or(0))
+            .unwrap_or(0)
+    }
+
+    /// Size of rendered text as `(width, height)` in cells
+    pub fn measure(&self, text: &str) -> (usize, usize) {
+        let widths: Vec<usize> = text.chars().filter(|c| self.glyph(*c).is_some()).map(|c| self.glyph_width(c)).collect();
+        let width = widths.iter().sum::<usize>() + self.spacing * widths.len().saturating_sub(1);
+        (width, self.height)
+    }
+
+    fn from_bitmap(glyphs: &[(char, &str)], height: usize) -> Self {
+        let glyphs = glyphs
+            .iter()
+            .map(|(c, rows)| {
+                let rows = rows
+                    .split_whitespace()
+                    .map(|row| row.chars().map(|pixel| if pixel == '#' { BLOCK } else { ' ' }).collect())
+                    .collect();
+                (*c, rows)
+            })
+            .collect();
+
+        Self { height, spacing: 1, glyphs }
+    }
+}
+
+/// Parses decimal, `0x` hex, or `0` octal FIGlet character codes
+fn parse_flf_code(code: &str) -> Option<u32> {
+    if let Some(hex) = code.strip_prefix("0x").or_else(|| code.strip_prefix("0X")) {
+        u32::from_str_radix(hex, 16).ok()
+    } else if code.len() > 1 && code.starts_with('0') {
+        u32::from_str_radix(&code[1..], 8).ok()
+    } else {
+        code.parse().ok()
+    }
+}
+
+fn invalid_font(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("invalid FIGlet font: {}", message))
+}
@@ -3,6 +3,9 @@
 //! Contains the [`GameObject`] struct that represents entities in the game world,
 //! including their visual representation, animation, and positioning.
 
+use std::{collections::{HashMap, HashSet}, hash::{Hash, Hasher}};
+use crate::{behavior::Behavior, color::Color, direction::Direction, engine::EngineCommand, equipment::Equipment, input::Key, interpolation::Interpolation, metadata::Value, path_follower::PathFollower, projectiles::Projectile, sprite::Sprite, stat_block::StatBlock, status::{EffectKind, StatusEffects}, style::Attributes};
+
 /// Represents an entity in the game world with visual and spatial properties
 ///
 /// # Fields
@@ -13,21 +16,41 @@
 /// - `current_frame`: Index of current animation frame
 /// - `frame_duration`: Time (seconds) between frame changes
 /// - `animation_timer`: Accumulated time since last frame change
-/// - `fg_color`: Optional ANSI foreground color code
-/// - `bg_color`: Optional ANSI background color code
+/// - `animation_paused`: Whether automatic frame changes are suspended
+/// - `fg_color`: Foreground color, `Color::Default` for the terminal's
+/// - `bg_color`: Background color, `Color::Default` for the terminal's
+/// - `attributes`: Bold, underline, and other text attributes
+/// - `sprite_frames`: Optional multi-cell sprites drawn instead of `character`
+/// - `behaviors`: Per-frame logic run by the engine for this object
+/// - `lifetime`: Optional seconds left before the engine despawns the object
+/// - `visible`: Whether the object is drawn
+/// - `active`: Whether the engine animates, updates, and collides the object
+/// - `groups`: Named groups the object belongs to
+/// - `status_effects`: Timed buffs and debuffs ticked by the engine
+/// - `stats`: RPG stats with modifiers from equipment and buffs
+/// - `equipment`: Equipment slots and the items in them
+/// - `path`: Waypoints the engine walks the object along
+/// - `priority`: Eviction order when the engine's object cap evicts by priority
+/// - `facing`: Direction of the last move, turned by the engine
+/// - `solid`: Whether the object blocks other solid objects' moves
+/// - `pushable`: Whether blocked solid movers push the object instead
+/// - `projectile`: Straight-line flight the engine moves the object along
+/// - `metadata`: Free-form key-value data for level files and scripts
+/// - `net_id`: Identity shared with other machines when the object is replicated
+/// - `interpolation`: Buffered network positions the engine plays back smoothly
+/// - `facing_sprites`: Sprite animations swapped in when `facing` changes
 ///
 /// # Examples
 /// ```
-/// use lonely_engine::game_object::GameObject;
+/// use lonely_engine::{color::Color, game_object::GameObject};
 ///
 /// // Create a basic stationary object
 /// let player = GameObject::new(5, 10, '@');
 ///
 /// // Create an animated object with colors
 /// let mut torch = GameObject::new(8, 3, '|');
-/// torch.frames = vec!['|', '/', '─', '\\'];
-/// torch.frame_duration = 0.2;
-/// torch.fg_color = Some("\x1B[38;5;208m".to_string()); // Orange
+/// torch.set_frames(vec!['|', '/', '─', '\\'], 0.2);
+/// torch.fg_color = Color::Indexed(208); // Orange
 /// ```
 #[derive(Debug, Clone)]
 pub struct GameObject {
@@ -47,10 +70,52 @@ pub struct GameObject {
     pub frame_duration: f32,
     /// Accumulated time since last frame change
     pub animation_timer: f32,
-    /// ANSI foreground color escape code
-    pub fg_color: Option<String>,
-    /// ANSI background color escape code
-    pub bg_color: Option<String>,
+    /// Suspends automatic frame changes while `true`
+    pub animation_paused: bool,
+    /// Foreground color
+    pub fg_color: Color,
+    /// Background color
+    pub bg_color: Color,
+    /// Text attributes of `character`
+    pub attributes: Attributes,
+    /// Multi-cell sprite animation, drawn with its top-left cell at (`x`, `y`)
+    pub sprite_frames: Vec<Sprite>,
+    /// Logic run by the engine every frame for this object
+    pub behaviors: Vec<Box<dyn Behavior>>,
+    /// Seconds remaining before automatic despawn, `None` lives forever
+    pub lifetime: Option<f32>,
+    /// Skipped by rendering when `false`
+    pub visible: bool,
+    /// Frozen when `false`: no animation, lifetime, status effects, behaviors, or collisions
+    pub active: bool,
+    /// Named groups targeted by group commands
+    pub groups: Vec<String>,
+    /// Active buffs and debuffs
+    pub status_effects: StatusEffects,
+    /// Base and derived stats with their modifiers, see [`stat_block`](crate::stat_block)
+    pub stats: StatBlock,
+    /// Equipment slots and the items in them, see [`equipment`](crate::equipment)
+    pub equipment: Equipment,
+    /// Waypoint movement, cleared by the engine once a one-shot path completes
+    pub path: Option<PathFollower>,
+    /// Kept over lower priorities by `LimitPolicy::EvictByPriority`, `0` by default
+    pub priority: i32,
+    /// Direction the object last moved in, `Direction::Down` until it first moves
+    pub facing: Direction,
+    /// Sprite animation for each facing, see [`set_facing_sprites`](Self::set_facing_sprites)
+    pub facing_sprites: Vec<(Direction, Vec<Sprite>)>,
+    /// Blocks other solid objects under a blocking `MovePolicy`, see [`occupancy`](crate::occupancy)
+    pub solid: bool,
+    /// Pushed one cell along by a solid object moving into it, instead of blocking it
+    pub pushable: bool,
+    /// Straight-line flight with hit detection, see [`projectiles`](crate::projectiles)
+    pub projectile: Option<Projectile>,
+    /// Free-form data such as a door's target level, see [`metadata`](crate::metadata)
+    pub metadata: HashMap<String, Value>,
+    /// Identity across the network, see [`replication`](crate::replication)
+    pub net_id: Option<u32>,
+    /// Smooths network position updates, see [`interpolation`](crate::interpolation)
+    pub interpolation: Option<Interpolation>,
 }
 
 impl GameObject {
@@ -69,16 +134,15 @@ impl GameObject {
     ///
     /// # Example
     /// ```
-    /// use lonely_engine::game_object::GameObject;
+    /// use lonely_engine::{color::Color, game_object::GameObject};
     ///
     /// // Create a player object at position (5, 10)
     /// let player = GameObject::new(5, 10, '@');
     ///
     /// // Create a colored projectile
     /// let mut fireball = GameObject::new(8, 3, '*');
-    /// fireball.fg_color = Some("\x1B[31m".to_string()); // Red
-    /// fireball.frames = vec!['*', '●', '○']; // 3-frame animation
-    /// fireball.frame_duration = 0.05; // Fast animation
+    /// fireball.fg_color = Color::RED;
+    /// fireball.set_frames(vec!['*', '●', '○'], 0.05); // Fast 3-frame animation
     /// ```
     pub fn new(x: usize, y: usize, character: char) -> Self {
         Self { 
@@ -89,8 +153,566 @@ impl GameObject {
             current_frame: 0,
             frame_duration: 0.1,
             animation_timer: 0.0,
-            fg_color: None,
-            bg_color: None,
+            animation_paused: false,
+            fg_color: Color::Default,
+            bg_color: Color::Default,
+            attributes: Attributes::NONE,
+            sprite_frames: Vec::new(),
+            behaviors: Vec::new(),
+            lifetime: None,
+            visible: true,
+            active: true,
+            groups: Vec::new(),
+            status_effects: StatusEffects::new(),
+            stats: StatBlock::new(),
+            equipment: Equipment::new(),
+            path: None,
+            priority: 0,
+            facing: Direction::Down,
+            facing_sprites: Vec::new(),
+            solid: false,
+            pushable: false,
+            projectile: None,
+            metadata: HashMap::new(),
+            net_id: None,
+            interpolation: None,
         }
     }
-}
\ No newline at end of file
+
+    /// Creates a GameObject displayed as a multi-cell sprite
+    ///
+    /// # Arguments
+    /// * `x` - Initial grid X position of the sprite's top-left cell
+    /// * `y` - Initial grid Y position of the sprite's top-left cell
+    /// * `sprite` - Sprite to display
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{game_object::GameObject, sprite::Sprite};
+    ///
+    /// let tree = GameObject::with_sprite(4, 2, Sprite::from_str(" ^ \n/^\\\n | "));
+    /// ```
+    pub fn with_sprite(x: usize, y: usize, sprite: Sprite) -> Self {
+        Self::with_sprite_frames(x, y, vec![sprite], 0.1)
+    }
+
+    /// Creates a GameObject animated through a sequence of sprites
+    ///
+    /// # Arguments
+    /// * `x` - Initial grid X position of the sprite's top-left cell
+    /// * `y` - Initial grid Y position of the sprite's top-left cell
+    /// * `frames` - Sprite animation frames
+    /// * `frame_duration` - Time (seconds) between frame changes
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lonely_engine::{game_object::GameObject, sprite::Sprite};
+    ///
+    /// let frames = Sprite::frames_from_file("assets/flag.txt").unwrap();
+    /// let flag = GameObject::with_sprite_frames(10, 3, frames, 0.25);
+    /// ```
+    pub fn with_sprite_frames(x: usize, y: usize, frames: Vec<Sprite>, frame_duration: f32) -> Self {
+        let mut obj = Self::new(x, y, ' ');
+        obj.sprite_frames = frames;
+        obj.frame_duration = frame_duration;
+        obj
+    }
+
+    /// Attaches a behavior closure and returns the object, for builder-style construction
+    ///
+    /// # Arguments
+    /// * `behavior` - Closure `|obj, delta_time, active_keys| commands` run every frame
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{game_object::GameObject, input::Key};
+    ///
+    /// let player = GameObject::new(5, 5, '@').with_behavior(|obj, _dt, keys| {
+    ///     if keys.contains(&Key::Right) {
+    ///         obj.x += 1;
+    ///     }
+    ///     Vec::new()
+    /// });
+    /// ```
+    pub fn with_behavior<F>(mut self, behavior: F) -> Self
+    where
+        F: FnMut(&mut GameObject, f32, &HashSet<Key>) -> Vec<EngineCommand> + Clone + 'static,
+    {
+        self.add_behavior(behavior);
+        self
+    }
+
+    /// Attaches a behavior to an existing object
+    ///
+    /// # Arguments
+    /// * `behavior` - Any [`Behavior`] implementation, including closures
+    pub fn add_behavior(&mut self, behavior: impl Behavior + 'static) {
+        self.behaviors.push(Box::new(behavior));
+    }
+
+    /// Gives the object a limited lifetime and returns it, for builder-style construction
+    ///
+    /// # Arguments
+    /// * `seconds` - Time until the engine despawns the object
+    ///
+    /// # Notes
+    /// - Expired objects are removed during the engine update and emit `ObjectDespawned`
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::game_object::GameObject;
+    ///
+    /// // Explosion flash that disappears after 0.3 seconds
+    /// let flash = GameObject::new(12, 4, '*').with_lifetime(0.3);
+    /// ```
+    pub fn with_lifetime(mut self, seconds: f32) -> Self {
+        self.lifetime = Some(seconds);
+        self
+    }
+
+    /// Counts down the lifetime
+    ///
+    /// # Returns
+    /// `true` once the lifetime has run out
+    pub fn tick_lifetime(&mut self, delta_time: f32) -> bool {
+        match self.lifetime.as_mut() {
+            Some(remaining) => {
+                *remaining -= delta_time;
+                *remaining <= 0.0
+            },
+            None => false,
+        }
+    }
+
+    /// Starts the object on a path and returns it, for builder-style construction
+    ///
+    /// # Notes
+    /// - Use `EngineCommand::FollowPath` to give an object in the world a new path
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{game_object::GameObject, path_follower::{PathFollower, PathMode}};
+    ///
+    /// let patrol = PathFollower::new(vec![(5, 5), (15, 5), (15, 10), (5, 10)], 3.0).mode(PathMode::Loop);
+    /// let sentry = GameObject::new(5, 5, 'S').with_path(patrol);
+    /// assert!(sentry.path.is_some());
+    /// ```
+    pub fn with_path(mut self, path: PathFollower) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    /// Launches the object as a projectile and returns it, for builder-style construction
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{game_object::GameObject, projectiles::Projectile};
+    ///
+    /// let fireball = GameObject::new(10, 4, '*').with_projectile(Projectile::new((0.0, 1.0), 12.0).range(8.0));
+    /// assert!(fireball.projectile.is_some());
+    /// ```
+    pub fn with_projectile(mut self, projectile: Projectile) -> Self {
+        self.projectile = Some(projectile);
+        self
+    }
+
+    /// Sets the net id and returns the object, for objects whose id is agreed on ahead of time
+    ///
+    /// # Notes
+    /// - Servers usually hand out ids with [`ReplicationServer::replicate`](crate::replication::ReplicationServer::replicate) instead
+    pub fn with_net_id(mut self, id: u32) -> Self {
+        self.net_id = Some(id);
+        self
+    }
+
+    /// Attaches an interpolation and returns the object, for objects moved by another machine
+    ///
+    /// # Notes
+    /// - The engine then places the object from the interpolation every update, moves made directly are overwritten
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = Some(interpolation);
+        self
+    }
+
+    /// Sets the eviction priority and returns the object, for builder-style construction
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::game_object::GameObject;
+    ///
+    /// // Debris is the first to go when the object cap is reached
+    /// let debris = GameObject::new(3, 3, '.').with_priority(-10);
+    /// ```
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets whether the object is solid and returns it, for builder-style construction
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::game_object::GameObject;
+    ///
+    /// let wall = GameObject::new(0, 0, '#').with_solid(true);
+    /// ```
+    pub fn with_solid(mut self, solid: bool) -> Self {
+        self.solid = solid;
+        self
+    }
+
+    /// Makes the object a solid, pushable block and returns it, for builder-style construction
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::game_object::GameObject;
+    ///
+    /// let crate_box = GameObject::new(4, 3, '□').with_pushable(true);
+    /// assert!(crate_box.solid);
+    /// ```
+    pub fn with_pushable(mut self, pushable: bool) -> Self {
+        self.pushable = pushable;
+        self.solid |= pushable;
+        self
+    }
+
+    /// Sets the object's stats and returns it, for builder-style construction
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{game_object::GameObject, stat_block::StatBlock};
+    ///
+    /// let orc = GameObject::new(3, 3, 'o').with_stats(StatBlock::new().with_base("strength", 14.0));
+    /// assert_eq!(orc.stats.get("strength"), 14.0);
+    /// ```
+    pub fn with_stats(mut self, stats: StatBlock) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Gives the object equipment slots and returns it, for builder-style construction
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{equipment::Equipment, game_object::GameObject};
+    ///
+    /// let knight = GameObject::new(3, 3, 'K').with_equipment(Equipment::with_slots(&["head", "body", "hand"]));
+    /// assert!(knight.equipment.has_slot("body"));
+    /// ```
+    pub fn with_equipment(mut self, equipment: Equipment) -> Self {
+        self.equipment = equipment;
+        self
+    }
+
+    /// Adds the object to a named group and returns it, for builder-style construction
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::game_object::GameObject;
+    ///
+    /// let health_label = GameObject::new(0, 0, 'H').with_group("hud");
+    /// assert!(health_label.in_group("hud"));
+    /// ```
+    pub fn with_group(mut self, group: &str) -> Self {
+        self.add_group(group);
+        self
+    }
+
+    /// Adds the object to a named group
+    pub fn add_group(&mut self, group: &str) {
+        if !self.in_group(group) {
+            self.groups.push(group.to_string());
+        }
+    }
+
+    /// Returns whether the object belongs to a named group
+    pub fn in_group(&self, group: &str) -> bool {
+        self.groups.iter().any(|existing| existing == group)
+    }
+
+    /// Sets a metadata value and returns the object, for builder-style construction
+    pub fn with_meta(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.set_meta(key, value);
+        self
+    }
+
+    /// Sets a metadata value, replacing the value under the same key
+    pub fn set_meta(&mut self, key: &str, value: impl Into<Value>) {
+        self.metadata.insert(key.to_string(), value.into());
+    }
+
+    /// Metadata value under a key
+    pub fn meta(&self, key: &str) -> Option<&Value> {
+        self.metadata.get(key)
+    }
+
+    /// Text metadata under a key, `None` when missing or not text
+    pub fn meta_str(&self, key: &str) -> Option<&str> {
+        self.meta(key).and_then(Value::as_str)
+    }
+
+    /// Numeric metadata under a key, `None` when missing or not a number
+    pub fn meta_number(&self, key: &str) -> Option<f64> {
+        self.meta(key).and_then(Value::as_number)
+    }
+
+    /// Flag metadata under a key, `None` when missing or not a flag
+    pub fn meta_bool(&self, key: &str) -> Option<bool> {
+        self.meta(key).and_then(Value::as_bool)
+    }
+
+    /// Removes a metadata value
+    pub fn remove_meta(&mut self, key: &str) -> Option<Value> {
+        self.metadata.remove(key)
+    }
+
+    /// Replaces the character animation and restarts it from the first frame
+    ///
+    /// # Arguments
+    /// * `frames` - Characters to cycle through (an empty list keeps the current character)
+    /// * `frame_duration` - Time (seconds) between frame changes
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::game_object::GameObject;
+    /// let mut coin = GameObject::new(3, 3, 'o');
+    /// coin.set_frames(vec!['o', 'O', '0'], 0.15);
+    /// assert_eq!(coin.character, 'o');
+    /// ```
+    pub fn set_frames(&mut self, frames: Vec<char>, frame_duration: f32) {
+        self.frames = frames;
+        self.frame_duration = frame_duration;
+        self.set_frame(0);
+    }
+
+    /// Replaces the sprite animation and restarts it from the first frame
+    ///
+    /// # Arguments
+    /// * `frames` - Sprites to cycle through
+    /// * `frame_duration` - Time (seconds) between frame changes
+    pub fn set_sprite_frames(&mut self, frames: Vec<Sprite>, frame_duration: f32) {
+        self.sprite_frames = frames;
+        self.frame_duration = frame_duration;
+        self.set_frame(0);
+    }
+
+    /// Sets the sprite animation shown while facing a direction
+    ///
+    /// # Arguments
+    /// * `direction` - Facing the animation belongs to, replacing an earlier one
+    /// * `frames` - Sprites to cycle through, at the object's `frame_duration`
+    ///
+    /// # Notes
+    /// - A diagonal facing without its own animation uses its horizontal direction, then its vertical one
+    /// - The animation for the current facing is swapped in immediately
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{direction::Direction, game_object::GameObject, sprite::Sprite};
+    ///
+    /// let mut hero = GameObject::new(5, 5, '@');
+    /// for (direction, art) in [(Direction::Up, "^"), (Direction::Down, "v"), (Direction::Left, "<"), (Direction::Right, ">")] {
+    ///     hero.set_facing_sprites(direction, vec![Sprite::from_str(art)]);
+    /// }
+    /// hero.face(Direction::UpRight);
+    /// assert_eq!(hero.current_sprite().unwrap().get(0, 0).unwrap().character, '>');
+    /// ```
+    pub fn set_facing_sprites(&mut self, direction: Direction, frames: Vec<Sprite>) {
+        match self.facing_sprites.iter_mut().find(|(existing, _)| *existing == direction) {
+            Some((_, existing)) => *existing = frames,
+            None => self.facing_sprites.push((direction, frames)),
+        }
+        self.apply_facing_sprites();
+    }
+
+    /// Turns the object, swapping in the animation set for the new facing
+    ///
+    /// # Notes
+    /// - Called by the engine for every move, with the direction of the move
+    /// - The animation keeps its frame index so walk cycles continue smoothly
+    pub fn face(&mut self, direction: Direction) {
+        if self.facing != direction {
+            self.facing = direction;
+            self.apply_facing_sprites();
+        }
+    }
+
+    /// Replaces `sprite_frames` with the animation for the current facing, if any fits
+    fn apply_facing_sprites(&mut self) {
+        let (horizontal, vertical) = self.facing.components();
+        let frames = [Some(self.facing), horizontal, vertical]
+            .into_iter()
+            .flatten()
+            .find_map(|direction| self.facing_sprites.iter().find(|(existing, _)| *existing == direction));
+        if let Some((_, frames)) = frames {
+            self.sprite_frames = frames.clone();
+            self.current_frame %= self.sprite_frames.len().max(1);
+        }
+    }
+
+    /// Jumps to a specific animation frame and restarts its timer
+    ///
+    /// # Arguments
+    /// * `index` - Frame index, wrapped around the frame count
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::game_object::GameObject;
+    /// let mut light = GameObject::new(0, 0, '.');
+    /// light.set_frames(vec!['.', 'o', 'O'], 0.1);
+    /// light.set_frame(2);
+    /// assert_eq!(light.character, 'O');
+    /// ```
+    pub fn set_frame(&mut self, index: usize) {
+        self.current_frame = index % self.frame_count().max(1);
+        self.animation_timer = 0.0;
+        if let Some(&character) = self.frames.get(self.current_frame) {
+            self.character = character;
+        }
+    }
+
+    /// Suspends automatic frame changes, keeping the current frame visible
+    pub fn pause_animation(&mut self) {
+        self.animation_paused = true;
+    }
+
+    /// Resumes automatic frame changes from the current frame
+    pub fn resume_animation(&mut self) {
+        self.animation_paused = false;
+    }
+
+    /// Returns whether automatic frame changes are suspended
+    pub fn is_animation_paused(&self) -> bool {
+        self.animation_paused
+    }
+
+    /// Advances the animation timer, changing frames when `frame_duration` elapses
+    ///
+    /// # Arguments
+    /// * `delta_time` - Time since last update in seconds
+    ///
+    /// # Returns
+    /// `true` when the animation wrapped back to its first frame
+    ///
+    /// # Notes
+    /// - Called by the engine every frame; paused and single-frame animations are skipped
+    pub fn advance_animation(&mut self, delta_time: f32) -> bool {
+        self.advance_animation_paced(delta_time, 0.0)
+    }
+
+    /// Advances the animation timer like [`advance_animation`](Self::advance_animation), holding every frame at least a minimum time
+    ///
+    /// # Arguments
+    /// * `delta_time` - Time since last update in seconds
+    /// * `min_frame_duration` - Seconds a frame is shown at least, whatever `frame_duration` says
+    ///
+    /// # Notes
+    /// - The engine passes the player's accessibility minimum, see [`Accessibility`](crate::accessibility::Accessibility)
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::game_object::GameObject;
+    ///
+    /// let mut torch = GameObject::new(0, 0, '*');
+    /// torch.set_frames(vec!['*', '+'], 0.05);
+    /// torch.advance_animation_paced(0.1, 0.25);
+    /// assert_eq!(torch.current_frame, 0);
+    /// torch.advance_animation_paced(0.2, 0.25);
+    /// assert_eq!(torch.current_frame, 1);
+    /// ```
+    pub fn advance_animation_paced(&mut self, delta_time: f32, min_frame_duration: f32) -> bool {
+        if self.animation_paused || self.frame_count() <= 1 {
+            return false;
+        }
+
+        self.animation_timer += delta_time;
+        if self.animation_timer < self.frame_duration.max(min_frame_duration) {
+            return false;
+        }
+
+        let next_frame = (self.current_frame + 1) % self.frame_count();
+        self.set_frame(next_frame);
+        next_frame == 0
+    }
+
+    /// Number of frames in the active animation (sprite frames take precedence)
+    pub fn frame_count(&self) -> usize {
+        if self.sprite_frames.is_empty() {
+            self.frames.len()
+        } else {
+            self.sprite_frames.len()
+        }
+    }
+
+    /// Sprite for the current animation frame, if the object is sprite-based
+    pub fn current_sprite(&self) -> Option<&Sprite> {
+        self.sprite_frames.get(self.current_frame % self.sprite_frames.len().max(1))
+    }
+
+    /// Feeds the object's simulation state into a hasher
+    ///
+    /// # Notes
+    /// - Covers position, appearance, animation, lifetime, visibility, activity, groups, priority, status effects, path progress, and metadata
+    /// - Behaviors are not hashed, their effects show up in the hashed state
+    /// - Floats are hashed by bit pattern
+    pub fn hash_state(&self, hasher: &mut impl Hasher) {
+        self.x.hash(hasher);
+        self.y.hash(hasher);
+        self.character.hash(hasher);
+        self.tag.hash(hasher);
+        self.current_frame.hash(hasher);
+        self.animation_timer.to_bits().hash(hasher);
+        self.animation_paused.hash(hasher);
+        self.lifetime.map(f32::to_bits).hash(hasher);
+        self.fg_color.hash(hasher);
+        self.bg_color.hash(hasher);
+        self.attributes.hash(hasher);
+        self.visible.hash(hasher);
+        self.active.hash(hasher);
+        self.groups.hash(hasher);
+        self.priority.hash(hasher);
+        self.facing.hash(hasher);
+        self.solid.hash(hasher);
+        self.pushable.hash(hasher);
+        self.projectile.as_ref().map(|projectile| projectile.traveled().to_bits()).hash(hasher);
+        self.net_id.hash(hasher);
+
+        for effect in self.status_effects.iter() {
+            effect.name.hash(hasher);
+            effect.remaining.map(f32::to_bits).hash(hasher);
+            match effect.kind {
+                EffectKind::Speed(multiplier) => (0u8, multiplier.to_bits()).hash(hasher),
+                EffectKind::DamageOverTime(damage) => (1u8, damage.to_bits()).hash(hasher),
+                EffectKind::Stun => 2u8.hash(hasher),
+                EffectKind::Custom => 3u8.hash(hasher),
+            }
+        }
+
+        for (stat, value) in self.stats.iter() {
+            stat.hash(hasher);
+            value.to_bits().hash(hasher);
+        }
+        for modifier in self.stats.modifiers() {
+            modifier.source.hash(hasher);
+            modifier.remaining.map(f32::to_bits).hash(hasher);
+        }
+        self.equipment.hash(hasher);
+
+        self.path.is_some().hash(hasher);
+        if let Some(path) = &self.path {
+            path.hash_state(hasher);
+        }
+
+        // Sorted, map order differs between runs
+        let mut metadata: Vec<(&String, &Value)> = self.metadata.iter().collect();
+        metadata.sort_by(|a, b| a.0.cmp(b.0));
+        for (key, value) in metadata {
+            key.hash(hasher);
+            match value {
+                Value::String(text) => (0u8, text).hash(hasher),
+                Value::Number(number) => (1u8, number.to_bits()).hash(hasher),
+                Value::Bool(flag) => (2u8, flag).hash(hasher),
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Stable hashing for determinism checks
+//!
+//! The standard library's default hasher may change between Rust releases, so
+//! state hashes compared across machines (lockstep networking, replay
+//! verification) use [`StableHasher`], a 64-bit FNV-1a implementation whose
+//! output depends only on the bytes written.
+
+use std::hash::Hasher;
+
+const FNV_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+/// FNV-1a hasher producing the same value on every platform and compiler version
+///
+/// # Notes
+/// - Integers are hashed as little-endian bytes
+/// - Hash floats through `to_bits()` so identical values always match
+///
+/// # Example
+/// ```
+/// use std::hash::Hasher;
+/// use lonely_engine::hash::StableHasher;
+///
+/// let mut hasher = StableHasher::new();
+/// hasher.write(b"lonely");
+/// assert_eq!(hasher.finish(), 0xAFBA_D073_994B_35C2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StableHasher {
+    state: u64,
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StableHasher {
+    /// Creates a hasher with the FNV offset basis
+    pub fn new() -> Self {
+        Self { state: FNV_OFFSET_BASIS }
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, value: u128) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        // Platform independent: hash every usize as 64 bits
+        self.write_u64(value as u64);
+    }
+
+    fn write_isize(&mut self, value: isize) {
+        self.write_u64(value as i64 as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.state
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Provides helper methods for:
 //! - Collision detection
+//! - Line of sight, field of view, and raycasts over the grid
 //! - Text rendering
 //! - UI elements
 
@@ -15,11 +16,12 @@ use crate::{engine::Engine, game_object::GameObject};
 /// * `ignore_tags` - Tags to exclude from collision checks
 ///
 /// # Returns
-/// `true` if objects collide and neither has an ignored tag
+/// `true` if objects collide, both are active, and neither has an ignored tag
 ///
 /// # Notes
 /// - Uses grid-based collision (same coordinates)
 /// - Tags are case-sensitive
+/// - Inactive objects never collide
 ///
 /// # Example
 /// ```
@@ -32,10 +34,13 @@ use crate::{engine::Engine, game_object::GameObject};
 ///
 /// assert!(check_collision(&obj1, &obj2, &[]));
 /// assert!(!check_collision(&obj1, &obj2, &["player"]));
+///
+/// obj2.active = false;
+/// assert!(!check_collision(&obj1, &obj2, &[]));
 /// ```
 pub fn check_collision(a: &GameObject, b: &GameObject, ignore_tags: &[&str]) -> bool {
-    // Skip collision if either object has an ignored tag
-    if ignore_tags.contains(&a.tag.as_str()) || ignore_tags.contains(&b.tag.as_str()) {
+    // Skip collision if either object is inactive or has an ignored tag
+    if !a.active || !b.active || ignore_tags.contains(&a.tag.as_str()) || ignore_tags.contains(&b.tag.as_str()) {
         return false;
     }
 
@@ -95,4 +100,155 @@ pub fn draw_progress_bar(engine: &mut Engine, x: usize, y: usize, width: usize,
         let c = if i < filled { '#' } else { '-' };
         engine.add_object(GameObject::new(x + i, y, c));
     }
-}
\ No newline at end of file
+}
+/// Checks whether anything blocks the straight line between two cells
+///
+/// # Arguments
+/// * `a` - Cell looking
+/// * `b` - Cell looked at
+/// * `blocked` - Returns whether a cell stops sight, such as a wall
+///
+/// # Returns
+/// `true` if no cell strictly between `a` and `b` is blocked
+///
+/// # Notes
+/// - A blocking cell is itself visible and hides what is behind it, so `a`
+///   and `b` are never tested: a guard standing in a doorway sees the wall it looks at
+/// - The line is traced with Bresenham's algorithm, swapping `a` and `b` can
+///   pick different cells around corners
+///
+/// # Example
+/// ```
+/// # use lonely_engine::helpers::line_of_sight;
+/// let wall = |x: usize, y: usize| x == 5 && y < 10;
+///
+/// assert!(!line_of_sight((0, 2), (9, 2), wall)); // Wall in between
+/// assert!(line_of_sight((0, 12), (9, 12), wall)); // Past the end of the wall
+/// assert!(line_of_sight((0, 2), (5, 2), wall)); // The wall itself is seen
+/// ```
+pub fn line_of_sight(a: (usize, usize), b: (usize, usize), blocked: impl Fn(usize, usize) -> bool) -> bool {
+    let (mut x, mut y) = (a.0 as i64, a.1 as i64);
+    let (target_x, target_y) = (b.0 as i64, b.1 as i64);
+    let (dx, dy) = ((target_x - x).abs(), -(target_y - y).abs());
+    let (step_x, step_y) = ((target_x - x).signum(), (target_y - y).signum());
+    let mut error = dx + dy;
+
+    loop {
+        if (x, y) == (target_x, target_y) {
+            return true;
+        }
+        let doubled = 2 * error;
+        if doubled >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled <= dx {
+            error += dx;
+            y += step_y;
+        }
+        if (x, y) != (target_x, target_y) && blocked(x as usize, y as usize) {
+            return false;
+        }
+    }
+}
+
+/// Lists the cells seen from a cell within a distance
+///
+/// # Arguments
+/// * `origin` - Cell looking, always seen
+/// * `radius` - Distance in cells, measured from cell center to cell center
+/// * `blocked` - Returns whether a cell stops sight, such as a wall
+///
+/// # Returns
+/// Seen cells, row by row, including blocking cells in sight
+///
+/// # Notes
+/// - Every cell in range is tested with [`line_of_sight`], fine for the sight
+///   radius of a few actors per turn
+///
+/// # Example
+/// ```
+/// # use lonely_engine::helpers::field_of_view;
+/// let wall = |x: usize, y: usize| x == 5 && y < 10;
+/// let seen = field_of_view((3, 4), 4.0, wall);
+///
+/// assert!(seen.contains(&(3, 4)));
+/// assert!(seen.contains(&(5, 4))); // The wall
+/// assert!(!seen.contains(&(6, 4))); // Behind it
+/// assert!(!seen.contains(&(3, 9))); // Out of range
+/// ```
+pub fn field_of_view(origin: (usize, usize), radius: f32, blocked: impl Fn(usize, usize) -> bool) -> Vec<(usize, usize)> {
+    let radius = radius.max(0.0);
+    let reach = radius.floor() as usize;
+    let mut seen = Vec::new();
+    for y in origin.1.saturating_sub(reach)..=origin.1.saturating_add(reach) {
+        for x in origin.0.saturating_sub(reach)..=origin.0.saturating_add(reach) {
+            let (dx, dy) = (x.abs_diff(origin.0) as f32, y.abs_diff(origin.1) as f32);
+            if dx.hypot(dy) <= radius && line_of_sight(origin, (x, y), &blocked) {
+                seen.push((x, y));
+            }
+        }
+    }
+    seen
+}
+
+/// Casts a ray from a cell and returns the first blocked cell it enters
+///
+/// # Arguments
+/// * `origin` - Cell the ray starts from, never reported as a hit
+/// * `direction` - Direction of travel in cells, any non-zero length
+/// * `max_distance` - Distance in cells after which the ray gives up
+/// * `blocked` - Returns whether a cell stops the ray
+///
+/// # Returns
+/// The cell hit, or `None` when the ray travels `max_distance`, leaves the grid
+/// through the top or left edge, or `direction` is zero
+///
+/// # Notes
+/// - The ray starts at the center of `origin` and visits every cell it passes
+///   through (DDA), so it cannot slip between diagonal walls
+/// - Blocking follows [`line_of_sight`]: the hit cell is the first one that hides what is behind it
+///
+/// # Example
+/// ```
+/// # use lonely_engine::helpers::raycast;
+/// let wall = |x: usize, _y: usize| x == 8;
+///
+/// assert_eq!(raycast((2, 3), (1.0, 0.0), 20.0, wall), Some((8, 3))); // Hitscan shot hits the wall
+/// assert_eq!(raycast((2, 3), (1.0, 0.0), 4.0, wall), None); // Out of range
+/// assert_eq!(raycast((2, 3), (-1.0, 0.0), 20.0, wall), None); // Leaves the grid
+/// ```
+pub fn raycast(origin: (usize, usize), direction: (f32, f32), max_distance: f32, blocked: impl Fn(usize, usize) -> bool) -> Option<(usize, usize)> {
+    let length = direction.0.hypot(direction.1);
+    if length <= f32::EPSILON {
+        return None;
+    }
+    let (dir_x, dir_y) = (direction.0 / length, direction.1 / length);
+    let (mut x, mut y) = (origin.0 as i64, origin.1 as i64);
+    let (step_x, step_y) = (dir_x.signum() as i64, dir_y.signum() as i64);
+
+    // Distance along the ray to cross one cell on each axis, and to the first crossing from the cell center
+    let (delta_x, delta_y) = ((1.0 / dir_x).abs(), (1.0 / dir_y).abs());
+    let (mut next_x, mut next_y) = (delta_x / 2.0, delta_y / 2.0);
+
+    loop {
+        let travelled = if next_x < next_y {
+            x += step_x;
+            let travelled = next_x;
+            next_x += delta_x;
+            travelled
+        } else {
+            y += step_y;
+            let travelled = next_y;
+            next_y += delta_y;
+            travelled
+        };
+
+        if travelled > max_distance || x < 0 || y < 0 {
+            return None;
+        }
+        if blocked(x as usize, y as usize) {
+            return Some((x as usize, y as usize));
+        }
+    }
+}
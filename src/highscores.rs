@@ -0,0 +1,262 @@
+//! High score tables
+//!
+//! Provides:
+//! - [`HighScores`] keeping the best scores in rank order, saved between sessions as TOML
+//! - [`NameEntry`] collecting the player's name for a new score through a [`TextInput`]
+//! - Drawing of the table and the name prompt for game over screens
+//!
+//! # File format
+//! ```toml
+//! [scores]
+//! names = ["ACE", "BOB"]
+//! points = [1200, 800]
+//! ```
+
+use std::{fs, io, path::Path};
+use crate::{
+    color::Color,
+    input::{InputEvent, TextInput, TextInputStatus},
+    renderer::Renderer,
+    style::Style,
+    toml::{TomlDocument, TomlValue},
+};
+
+/// Name used when a player submits an empty name
+pub const ANONYMOUS: &str = "???";
+
+/// One row of a high score table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreEntry {
+    /// Player name
+    pub name: String,
+    /// Points scored
+    pub score: i64,
+}
+
+/// Best scores, highest first
+///
+/// # Notes
+/// - A new score tying an existing one ranks below it
+/// - Scores beyond the capacity drop off the bottom
+///
+/// # Example
+/// ```
+/// # use lonely_engine::highscores::HighScores;
+/// let mut scores = HighScores::new(3);
+/// scores.insert("ACE", 900);
+/// scores.insert("BOB", 400);
+/// assert_eq!(scores.insert("CAT", 700), Some(1)); // Second place
+///
+/// assert!(!scores.qualifies(100));
+/// assert_eq!(scores.entries()[0].name, "ACE");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HighScores {
+    entries: Vec<ScoreEntry>,
+    capacity: usize,
+}
+
+impl HighScores {
+    /// Creates an empty table keeping up to `capacity` scores
+    pub fn new(capacity: usize) -> Self {
+        Self { entries: Vec::new(), capacity }
+    }
+
+    /// Entries highest first
+    pub fn entries(&self) -> &[ScoreEntry] {
+        &self.entries
+    }
+
+    /// Maximum number of entries kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Highest score, if any
+    pub fn best(&self) -> Option<&ScoreEntry> {
+        self.entries.first()
+    }
+
+    /// Zero-based rank a score would take, `None` if it would not make the table
+    pub fn rank_of(&self, score: i64) -> Option<usize> {
+        let rank = self.entries.iter().take_while(|entry| entry.score >= score).count();
+        (rank < self.capacity).then_some(rank)
+    }
+
+    /// Returns whether a score would make the table
+    pub fn qualifies(&self, score: i64) -> bool {
+        self.rank_of(score).is_some()
+    }
+
+    /// Adds a score in rank order
+    ///
+    /// # Returns
+    /// Zero-based rank of the new entry, `None` if it did not make the table
+    pub fn insert(&mut self, name: &str, score: i64) -> Option<usize> {
+        let rank = self.rank_of(score)?;
+        self.entries.insert(rank, ScoreEntry { name: name.to_string(), score });
+        self.entries.truncate(self.capacity);
+        Some(rank)
+    }
+
+    /// Removes every entry
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Formats the table as TOML
+    pub fn to_toml(&self) -> String {
+        let mut doc = TomlDocument::new();
+        doc.set("scores", "names", TomlValue::Array(self.entries.iter().map(|entry| entry.name.clone().into()).collect()));
+        doc.set("scores", "points", TomlValue::Array(self.entries.iter().map(|entry| entry.score.into()).collect()));
+        doc.to_string()
+    }
+
+    /// Parses a table saved by [`to_toml`](Self::to_toml)
+    ///
+    /// # Notes
+    /// - Entries are re-ranked, so hand-edited files load correctly
+    ///
+    /// # Returns
+    /// `Err` with [`io::ErrorKind::InvalidData`] for invalid TOML or mismatched name and point lists
+    pub fn from_toml(text: &str, capacity: usize) -> io::Result<Self> {
+        let doc = TomlDocument::parse(text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        let names = doc.get("scores", "names").and_then(TomlValue::as_array).unwrap_or(&[]);
+        let points = doc.get("scores", "points").and_then(TomlValue::as_array).unwrap_or(&[]);
+        if names.len() != points.len() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "score names and points differ in length"));
+        }
+
+        let mut scores = Self::new(capacity);
+        for (name, score) in names.iter().zip(points) {
+            match (name.as_str(), score.as_integer()) {
+                (Some(name), Some(score)) => {
+                    scores.insert(name, score);
+                },
+                _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "scores must be name strings and integer points")),
+            }
+        }
+        Ok(scores)
+    }
+
+    /// Loads a table from a file
+    ///
+    /// # Returns
+    /// * An empty table when the file does not exist yet
+    /// * `Err` if the file is unreadable or malformed
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::highscores::HighScores;
+    /// let mut scores = HighScores::load("scores.toml", 10).unwrap_or_else(|_| HighScores::new(10));
+    /// scores.insert("ACE", 1200);
+    /// scores.save("scores.toml").expect("Could not save scores");
+    /// ```
+    pub fn load(path: impl AsRef<Path>, capacity: usize) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::from_toml(&text, capacity),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::new(capacity)),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Writes the table to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_toml())
+    }
+
+    /// Draws the table as ranked rows
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Top-left cell of the table
+    /// * `width` - Row width, names are padded with dots up to the score
+    /// * `highlight` - Rank to draw highlighted, such as the score just entered
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::{highscores::HighScores, renderer::Renderer};
+    /// let mut scores = HighScores::new(5);
+    /// let rank = scores.insert("ACE", 1200);
+    ///
+    /// let mut renderer = Renderer::new(30, 10);
+    /// scores.draw(&mut renderer, 2, 1, 20, rank);
+    /// renderer.present().unwrap();
+    ///
+    /// let text = renderer.screenshot().to_text();
+    /// assert_eq!(text.lines().nth(1).map(str::trim_end), Some("  1. ACE..........1200"));
+    /// ```
+    pub fn draw(&self, renderer: &mut Renderer, x: usize, y: usize, width: usize, highlight: Option<usize>) {
+        let rank_width = self.capacity.max(1).to_string().len();
+        for (rank, entry) in self.entries.iter().enumerate() {
+            let prefix = format!("{:>rank_width$}. {}", rank + 1, entry.name);
+            let score = entry.score.to_string();
+            let dots = width.saturating_sub(prefix.chars().count() + score.len());
+            let row = format!("{}{}{}", prefix, ".".repeat(dots), score);
+
+            let style = if highlight == Some(rank) { Style::new().fg(Color::BRIGHT_YELLOW).bold() } else { Style::new() };
+            renderer.draw_styled_text(x, y + rank, &row, &style);
+        }
+    }
+}
+
+/// Name prompt shown after a score makes the table
+///
+/// # Example
+/// ```
+/// use lonely_engine::{highscores::{HighScores, NameEntry}, input::{InputEvent, Key}};
+///
+/// let mut scores = HighScores::new(10);
+/// let mut entry = NameEntry::new(500, 3);
+/// let events = [InputEvent::Char('Z'), InputEvent::Char('O'), InputEvent::Char('E'), InputEvent::KeyDown(Key::Enter)];
+///
+/// assert_eq!(entry.update(&events, &mut scores), Some(Some(0)));
+/// assert_eq!(scores.entries()[0].name, "ZOE");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NameEntry {
+    score: i64,
+    input: TextInput,
+}
+
+impl NameEntry {
+    /// Starts entering a name of up to `max_len` characters for `score`
+    pub fn new(score: i64, max_len: usize) -> Self {
+        Self { score, input: TextInput::new(max_len) }
+    }
+
+    /// Score being entered
+    pub fn score(&self) -> i64 {
+        self.score
+    }
+
+    /// Name typed so far
+    pub fn name(&self) -> &str {
+        self.input.text()
+    }
+
+    /// Applies a frame's input, inserting the score once the name is submitted
+    ///
+    /// # Returns
+    /// * `None` while the player is still typing
+    /// * `Some(rank)` once finished, `rank` being `None` if the entry was cancelled
+    ///
+    /// # Notes
+    /// - Submitting an empty name records the score as [`ANONYMOUS`]
+    pub fn update(&mut self, events: &[InputEvent], scores: &mut HighScores) -> Option<Option<usize>> {
+        match self.input.handle(events) {
+            TextInputStatus::Editing => None,
+            TextInputStatus::Cancelled => Some(None),
+            TextInputStatus::Submitted => {
+                let name = if self.input.text().trim().is_empty() { ANONYMOUS } else { self.input.text() };
+                Some(scores.insert(name, self.score))
+            },
+        }
+    }
+
+    /// Draws the prompt with a cursor after the typed name
+    pub fn draw(&self, renderer: &mut Renderer, x: usize, y: usize) {
+        renderer.draw_styled_text(x, y, "NEW HIGH SCORE!", &Style::new().fg(Color::BRIGHT_YELLOW).bold());
+        let padding = "_".repeat(self.input.max_len().saturating_sub(self.input.text().chars().count()));
+        renderer.draw_text(x, y + 1, &format!("Name: {}{}", self.input.text(), padding));
+    }
+}
@@ -0,0 +1,265 @@
+//! Sprite import from PNG and BMP images
+//!
+//! Available with the `image` feature. Lets artists draw sprites in an image
+//! editor: [`Image`] decodes PNG and BMP files with the `image` crate and
+//! [`ImageImport`] turns their pixels into a colored [`Sprite`].
+//!
+//! # Conversion
+//! - Every pixel becomes one cell, or two stacked pixels one `▀` cell with [`ImageImport::half_blocks`]
+//! - The character comes from a luminance ramp, brighter pixels picking denser characters
+//! - The color is the nearest entry of a palette, the 16 standard colors by default
+//! - Pixels more transparent than the alpha threshold become transparent cells
+//!
+//! # Example
+//! ```no_run
+//! use lonely_engine::{image::{Image, ImageImport}, sprite::Sprite};
+//!
+//! let image = Image::load("assets/hero.png").expect("Missing image");
+//! let hero: Sprite = ImageImport::new().half_blocks().to_sprite(&image);
+//! ```
+//!
+//! # Notes
+//! - Only the PNG and BMP decoders of the `image` crate are built, other formats are rejected
+
+use std::{fs, io, path::Path};
+use crate::{color::Color, sprite::{Sprite, SpriteCell}, style::Attributes};
+
+/// Characters from darkest to brightest used by default
+pub const DEFAULT_RAMP: &str = " .:-=+*#%@";
+
+/// Decoded image with 8-bit RGBA pixels
+///
+/// # Example
+/// ```
+/// use lonely_engine::image::Image;
+///
+/// // 2x1 24-bit BMP, rows are bottom-up BGR padded to 4 bytes
+/// let mut bmp = b"BM".to_vec();
+/// bmp.extend_from_slice(&62u32.to_le_bytes());
+/// bmp.extend_from_slice(&[0, 0, 0, 0]);
+/// bmp.extend_from_slice(&54u32.to_le_bytes());
+/// bmp.extend_from_slice(&40u32.to_le_bytes());
+/// bmp.extend_from_slice(&2i32.to_le_bytes());
+/// bmp.extend_from_slice(&1i32.to_le_bytes());
+/// bmp.extend_from_slice(&1u16.to_le_bytes());
+/// bmp.extend_from_slice(&24u16.to_le_bytes());
+/// bmp.extend_from_slice(&[0; 24]);
+/// bmp.extend_from_slice(&[0, 0, 255, 255, 0, 0, 0, 0]);
+///
+/// let image = Image::decode(&bmp).unwrap();
+/// assert_eq!((image.width, image.height), (2, 1));
+/// assert_eq!(image.pixel(0, 0), Some([255, 0, 0, 255]));
+/// assert_eq!(image.pixel(1, 0), Some([0, 0, 255, 255]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    /// Width in pixels
+    pub width: usize,
+    /// Height in pixels
+    pub height: usize,
+    /// Row-major pixels as `[r, g, b, a]`
+    pixels: Vec<[u8; 4]>,
+}
+
+impl Image {
+    /// Creates an image from row-major RGBA pixels
+    ///
+    /// # Notes
+    /// - Missing pixels are transparent, extra pixels are dropped
+    pub fn new(width: usize, height: usize, mut pixels: Vec<[u8; 4]>) -> Self {
+        pixels.resize(width * height, [0; 4]);
+        Self { width, height, pixels }
+    }
+
+    /// Decodes a PNG or BMP file, recognized by its signature
+    ///
+    /// # Returns
+    /// `InvalidData` for other formats, unsupported variants, and corrupt files
+    ///
+    /// # Example
+    /// ```
+    /// use std::io::ErrorKind;
+    /// use lonely_engine::image::Image;
+    ///
+    /// // A few bytes claiming a 4294967295 x 4294967295 image
+    /// let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+    /// png.extend([0, 0, 0, 13]);
+    /// png.extend(b"IHDR");
+    /// png.extend([0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 16, 6, 0, 0, 0, 0, 0, 0, 0]);
+    /// png.extend([0, 0, 0, 7]);
+    /// png.extend(b"IDAT");
+    /// png.extend([0x78, 0x01, 0x01, 0x00, 0x00, 0xFF, 0xFF, 0, 0, 0, 0]);
+    ///
+    /// assert_eq!(Image::decode(&png).unwrap_err().kind(), ErrorKind::InvalidData);
+    /// ```
+    pub fn decode(bytes: &[u8]) -> io::Result<Self> {
+        let format = ::image::guess_format(bytes).map_err(|_| invalid("unrecognized image format, expected png or bmp"))?;
+        let decoded = ::image::load_from_memory_with_format(bytes, format).map_err(|error| invalid(&format!("failed to decode image: {error}")))?;
+        let rgba = decoded.into_rgba8();
+        let (width, height) = (rgba.width() as usize, rgba.height() as usize);
+        Ok(Self { width, height, pixels: rgba.pixels().map(|pixel| pixel.0).collect() })
+    }
+
+    /// Loads and decodes a PNG or BMP file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::decode(&fs::read(path)?)
+    }
+
+    /// Pixel as `[r, g, b, a]`, `None` out of range
+    pub fn pixel(&self, x: usize, y: usize) -> Option<[u8; 4]> {
+        if x < self.width && y < self.height {
+            Some(self.pixels[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Row-major pixels as `[r, g, b, a]`
+    pub fn pixels(&self) -> &[[u8; 4]] {
+        &self.pixels
+    }
+}
+
+/// Settings for converting an [`Image`] into a [`Sprite`]
+///
+/// # Example
+/// ```
+/// use lonely_engine::{color::Color, image::{Image, ImageImport}};
+///
+/// let image = Image::new(3, 1, vec![[255, 255, 255, 255], [200, 0, 0, 255], [0, 0, 0, 0]]);
+/// let sprite = ImageImport::new().to_sprite(&image);
+/// assert_eq!(sprite.get(0, 0).unwrap().character, '@');
+/// assert_eq!(sprite.get(0, 0).unwrap().fg_color, Color::BRIGHT_WHITE);
+/// assert_eq!(sprite.get(1, 0).unwrap().fg_color, Color::RED);
+/// assert!(sprite.get(2, 0).is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageImport {
+    ramp: Vec<char>,
+    palette: Vec<Color>,
+    alpha_threshold: u8,
+    half_blocks: bool,
+}
+
+impl Default for ImageImport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImageImport {
+    /// Creates settings using [`DEFAULT_RAMP`] and the 16 standard colors
+    pub fn new() -> Self {
+        Self {
+            ramp: DEFAULT_RAMP.chars().collect(),
+            palette: (0..16).map(Color::Indexed).collect(),
+            alpha_threshold: 128,
+            half_blocks: false,
+        }
+    }
+
+    /// Sets the characters used from darkest to brightest
+    ///
+    /// # Notes
+    /// - Spaces make transparent cells, like in text sprites
+    /// - A single character such as `"█"` draws every pixel as a solid block
+    /// - An empty ramp is ignored
+    pub fn ramp(mut self, ramp: &str) -> Self {
+        if !ramp.is_empty() {
+            self.ramp = ramp.chars().collect();
+        }
+        self
+    }
+
+    /// Sets the colors pixels are matched against
+    ///
+    /// # Notes
+    /// - [`Color::Default`] entries are skipped, an empty palette keeps the exact pixel colors
+    pub fn palette(mut self, palette: Vec<Color>) -> Self {
+        self.palette = palette.into_iter().filter(|color| color.to_rgb().is_some()).collect();
+        self
+    }
+
+    /// Matches pixels against the full 256-color palette
+    pub fn palette_256(self) -> Self {
+        self.palette((0..=255).map(Color::Indexed).collect())
+    }
+
+    /// Keeps exact pixel colors as 24-bit true color
+    pub fn true_color(self) -> Self {
+        self.palette(Vec::new())
+    }
+
+    /// Sets the alpha below which pixels become transparent, 128 by default
+    pub fn alpha_threshold(mut self, alpha: u8) -> Self {
+        self.alpha_threshold = alpha;
+        self
+    }
+
+    /// Draws two pixel rows per cell with `▀`, the top pixel in the foreground and the bottom in the background
+    ///
+    /// # Notes
+    /// - Terminal cells are about twice as tall as wide, so pixel art keeps its proportions
+    /// - The ramp is not used
+    pub fn half_blocks(mut self) -> Self {
+        self.half_blocks = true;
+        self
+    }
+
+    /// Converts an image into a sprite
+    pub fn to_sprite(&self, image: &Image) -> Sprite {
+        if self.half_blocks {
+            return self.half_block_sprite(image);
+        }
+        let cells = image.pixels.iter().map(|&pixel| {
+            if pixel[3] < self.alpha_threshold {
+                return None;
+            }
+            let character = self.ramp[usize::from(luminance(pixel)) * (self.ramp.len() - 1) / 255];
+            (character != ' ').then(|| SpriteCell {
+                character,
+                fg_color: self.nearest(pixel),
+                bg_color: Color::Default,
+                attributes: Attributes::NONE,
+            })
+        });
+        Sprite::from_cells(image.width, image.height, cells.collect())
+    }
+
+    /// Converts an image with two pixel rows per cell
+    fn half_block_sprite(&self, image: &Image) -> Sprite {
+        let height = image.height.div_ceil(2);
+        let mut cells = Vec::with_capacity(image.width * height);
+        for row in 0..height {
+            for x in 0..image.width {
+                let visible = |y: usize| image.pixel(x, y).filter(|pixel| pixel[3] >= self.alpha_threshold);
+                let cell = match (visible(row * 2), visible(row * 2 + 1)) {
+                    (None, None) => None,
+                    (Some(top), bottom) => Some(('▀', self.nearest(top), bottom.map_or(Color::Default, |bottom| self.nearest(bottom)))),
+                    (None, Some(bottom)) => Some(('▄', self.nearest(bottom), Color::Default)),
+                };
+                cells.push(cell.map(|(character, fg_color, bg_color)| SpriteCell { character, fg_color, bg_color, attributes: Attributes::NONE }));
+            }
+        }
+        Sprite::from_cells(image.width, height, cells)
+    }
+
+    /// Palette entry closest to a pixel, the exact color without a palette
+    fn nearest(&self, [r, g, b, _]: [u8; 4]) -> Color {
+        let distance = |color: &&Color| {
+            let (pr, pg, pb) = color.to_rgb().unwrap_or_default();
+            let (dr, dg, db) = (i32::from(pr) - i32::from(r), i32::from(pg) - i32::from(g), i32::from(pb) - i32::from(b));
+            dr * dr + dg * dg + db * db
+        };
+        self.palette.iter().min_by_key(distance).copied().unwrap_or(Color::Rgb(r, g, b))
+    }
+}
+
+/// Perceived brightness of a pixel (Rec. 601 weights), 0 to 255
+fn luminance([r, g, b, _]: [u8; 4]) -> u8 {
+    ((299 * u32::from(r) + 587 * u32::from(g) + 114 * u32::from(b)) / 1000) as u8
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
@@ -3,38 +3,499 @@
 //! Provides keyboard input processing with:
 //! - Windows implementation using WinAPI
 //! - Unix stub implementation (unimplemented)
+//! - Platform-independent [`Key`] type with a stable text form used by config files
+//! - Ordered [`InputEvent`] stream keeping repeats, releases, typed characters, and mouse input
+//! - [`TextInput`] line editor for name entry and other typed text
+//! - [`InputInjector`] feeding input from other threads, tagged with an [`InputSource`]
+
+use std::{collections::HashSet, fmt, str::FromStr, sync::{Arc, Mutex}};
+
+/// Represents a physical keyboard key
+///
+/// # Text form
+/// Keys convert to and from strings for config files: named keys use their
+/// variant name (`"Up"`, `"Space"`, `"Esc"`), character keys are the character
+/// itself (`"w"`, `"1"`), and scancodes are `"scan:"` plus the decimal code (`"scan:17"`).
+///
+/// # Layout independence
+/// `Char` keys follow the keyboard layout: the key right of Tab is `Char('w')` on
+/// QWERTY but `Char('z')` on AZERTY. [`Key::Scan`] names the physical key instead,
+/// so bind movement to [`Key::physical`] keys to keep WASD in the same place on
+/// every layout. Console input reports both forms of each pressed key in the active
+/// key set, input events carry the layout key only.
+///
+/// # Example
+/// ```
+/// # use lonely_engine::input::Key;
+/// assert_eq!("Space".parse::<Key>().unwrap(), Key::Space);
+/// assert_eq!("w".parse::<Key>().unwrap(), Key::Char('w'));
+/// assert_eq!(Key::Left.to_string(), "Left");
+/// ```
+///
+/// # Notes
+/// - With the `serde` feature keys serialize as their text form
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Key {
+    Char(char),
+    /// Up arrow key
+    Up,
+    /// Down arrow key
+    Down,
+    /// Left arrow key
+    Left,
+    /// Right arrow key
+    Right,
+    /// Space bar
+    Space,
+    /// Enter/Return key
+    Enter,
+    /// Shift
+    Shift,
+    /// Control Key
+    Ctrl,
+    /// Escape Key
+    Esc,
+    /// Backspace Key
+    Backspace,
+    /// Page Up Key
+    PageUp,
+    /// Page Down Key
+    PageDown,
+    /// Home Key
+    Home,
+    /// End Key
+    End,
+    /// Physical key by its set 1 scancode, independent of the keyboard layout
+    Scan(u16),
+    /// Unrecognized Key
+    Unknown,
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Key::Char(c) => write!(f, "{}", c),
+            Key::Up => f.write_str("Up"),
+            Key::Down => f.write_str("Down"),
+            Key::Left => f.write_str("Left"),
+            Key::Right => f.write_str("Right"),
+            Key::Space => f.write_str("Space"),
+            Key::Enter => f.write_str("Enter"),
+            Key::Shift => f.write_str("Shift"),
+            Key::Ctrl => f.write_str("Ctrl"),
+            Key::Esc => f.write_str("Esc"),
+            Key::Backspace => f.write_str("Backspace"),
+            Key::PageUp => f.write_str("PageUp"),
+            Key::PageDown => f.write_str("PageDown"),
+            Key::Home => f.write_str("Home"),
+            Key::End => f.write_str("End"),
+            Key::Scan(code) => write!(f, "scan:{}", code),
+            Key::Unknown => f.write_str("Unknown"),
+        }
+    }
+}
+
+/// Set 1 scancodes of the letter keys, by their position on a US QWERTY keyboard
+const LETTER_SCANCODES: [(char, u16); 26] = [
+    ('q', 0x10), ('w', 0x11), ('e', 0x12), ('r', 0x13), ('t', 0x14), ('y', 0x15), ('u', 0x16), ('i', 0x17), ('o', 0x18), ('p', 0x19),
+    ('a', 0x1E), ('s', 0x1F), ('d', 0x20), ('f', 0x21), ('g', 0x22), ('h', 0x23), ('j', 0x24), ('k', 0x25), ('l', 0x26),
+    ('z', 0x2C), ('x', 0x2D), ('c', 0x2E), ('v', 0x2F), ('b', 0x30), ('n', 0x31), ('m', 0x32),
+];
+
+/// Set 1 scancodes of the number row, `1` through `0`
+const DIGIT_SCANCODES: [(char, u16); 10] = [
+    ('1', 0x02), ('2', 0x03), ('3', 0x04), ('4', 0x05), ('5', 0x06), ('6', 0x07), ('7', 0x08), ('8', 0x09), ('9', 0x0A), ('0', 0x0B),
+];
+
+impl Key {
+    /// Physical key found at a character's place on a US QWERTY keyboard
+    ///
+    /// # Returns
+    /// `Key::Scan` for letters and digits, `None` for other characters
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{input::Key, keybindings::ActionMap};
+    ///
+    /// // Same physical keys on QWERTY, AZERTY (Z Q S D), and Dvorak (, A O E)
+    /// let mut actions = ActionMap::new();
+    /// actions.set_keys("up", vec![Key::physical('w').unwrap(), Key::Up]);
+    /// actions.set_keys("left", vec![Key::physical('a').unwrap(), Key::Left]);
+    /// assert_eq!(Key::physical('W'), Some(Key::Scan(0x11)));
+    /// ```
+    pub fn physical(c: char) -> Option<Key> {
+        let c = c.to_ascii_lowercase();
+        LETTER_SCANCODES.iter().chain(&DIGIT_SCANCODES).find(|(key, _)| *key == c).map(|(_, code)| Key::Scan(*code))
+    }
+}
+
+/// Error returned when parsing an unrecognized key name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseKeyError(pub String);
+
+impl fmt::Display for ParseKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown key `{}`", self.0)
+    }
+}
+
+impl std::error::Error for ParseKeyError {}
+
+impl FromStr for Key {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(Key::Char(c));
+        }
+
+        let lower = s.to_ascii_lowercase();
+        if let Some(code) = lower.strip_prefix("scan:") {
+            return code.parse().map(Key::Scan).map_err(|_| ParseKeyError(s.to_string()));
+        }
+
+        match lower.as_str() {
+            "up" => Ok(Key::Up),
+            "down" => Ok(Key::Down),
+            "left" => Ok(Key::Left),
+            "right" => Ok(Key::Right),
+            "space" => Ok(Key::Space),
+            "enter" | "return" => Ok(Key::Enter),
+            "shift" => Ok(Key::Shift),
+            "ctrl" | "control" => Ok(Key::Ctrl),
+            "esc" | "escape" => Ok(Key::Esc),
+            "backspace" => Ok(Key::Backspace),
+            "pageup" | "pgup" => Ok(Key::PageUp),
+            "pagedown" | "pgdn" => Ok(Key::PageDown),
+            "home" => Ok(Key::Home),
+            "end" => Ok(Key::End),
+            "unknown" => Ok(Key::Unknown),
+            _ => Err(ParseKeyError(s.to_string())),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Key {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Key {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Mouse buttons reported by the console
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    /// Primary (usually left) button
+    Left,
+    /// Secondary (usually right) button
+    Right,
+    /// Wheel button
+    Middle,
+}
+
+/// What happened in a mouse event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseAction {
+    /// A button went down
+    Press(MouseButton),
+    /// A button went up
+    Release(MouseButton),
+    /// The pointer moved to another cell
+    Move,
+    /// The wheel turned, positive away from the user
+    Scroll(i32),
+}
+
+/// A single input occurrence, in the order it happened
+///
+/// # Notes
+/// - A key held down produces one `KeyDown` per auto-repeat
+/// - Character keys produce both a `KeyDown` and a `Char`, use `Char` for text entry
+///
+/// # Example
+/// ```
+/// use lonely_engine::input::{InputEvent, Key};
+///
+/// // Two taps of the same key within one frame stay two events
+/// let events = [
+///     InputEvent::KeyDown(Key::Char('a')),
+///     InputEvent::Char('a'),
+///     InputEvent::KeyUp(Key::Char('a')),
+///     InputEvent::KeyDown(Key::Char('a')),
+///     InputEvent::Char('a'),
+/// ];
+/// let typed: String = events.iter().filter_map(InputEvent::as_char).collect();
+/// assert_eq!(typed, "aa");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputEvent {
+    /// A key was pressed or auto-repeated
+    KeyDown(Key),
+    /// A key was released
+    KeyUp(Key),
+    /// A printable character was typed
+    Char(char),
+    /// Mouse input at a cell position
+    Mouse {
+        /// Column of the pointer
+        x: usize,
+        /// Row of the pointer
+        y: usize,
+        /// What the mouse did
+        action: MouseAction,
+    },
+}
+
+impl InputEvent {
+    /// Typed character, `None` for other events
+    pub fn as_char(&self) -> Option<char> {
+        match self {
+            InputEvent::Char(c) => Some(*c),
+            _ => None,
+        }
+    }
+}
+
+/// Result of feeding events to a [`TextInput`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextInputStatus {
+    /// Still typing
+    Editing,
+    /// Enter was pressed
+    Submitted,
+    /// Escape was pressed
+    Cancelled,
+}
+
+/// Single-line text entry driven by [`InputEvent`]s
+///
+/// # Notes
+/// - Typed characters append, Backspace deletes, Enter submits, and Escape cancels
+/// - Characters beyond the maximum length are ignored
+///
+/// # Example
+/// ```
+/// use lonely_engine::input::{InputEvent, Key, TextInput, TextInputStatus};
+///
+/// let mut name = TextInput::new(3);
+/// let events = [InputEvent::Char('A'), InputEvent::Char('X'), InputEvent::KeyDown(Key::Backspace), InputEvent::Char('B')];
+/// assert_eq!(name.handle(&events), TextInputStatus::Editing);
+/// assert_eq!(name.text(), "AB");
+///
+/// assert_eq!(name.handle(&[InputEvent::KeyDown(Key::Enter)]), TextInputStatus::Submitted);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextInput {
+    text: String,
+    max_len: usize,
+}
+
+impl TextInput {
+    /// Creates an empty input accepting up to `max_len` characters
+    pub fn new(max_len: usize) -> Self {
+        Self { text: String::new(), max_len }
+    }
+
+    /// Applies a frame's events
+    ///
+    /// # Returns
+    /// [`TextInputStatus::Submitted`] or [`TextInputStatus::Cancelled`] as soon as
+    /// Enter or Escape is seen, ignoring the rest of the events
+    pub fn handle(&mut self, events: &[InputEvent]) -> TextInputStatus {
+        for event in events {
+            match event {
+                InputEvent::Char(c) if self.text.chars().count() < self.max_len => self.text.push(*c),
+                InputEvent::KeyDown(Key::Backspace) => {
+                    self.text.pop();
+                },
+                InputEvent::KeyDown(Key::Enter) => return TextInputStatus::Submitted,
+                InputEvent::KeyDown(Key::Esc) => return TextInputStatus::Cancelled,
+                _ => {},
+            }
+        }
+        TextInputStatus::Editing
+    }
+
+    /// Text typed so far
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replaces the text, truncated to the maximum length
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.chars().take(self.max_len).collect();
+    }
+
+    /// Empties the input
+    pub fn clear(&mut self) {
+        self.text.clear();
+    }
+
+    /// Maximum number of characters
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+}
+
+/// Everything read from the console in one poll
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsoleInput {
+    /// Keys pressed since the previous poll
+    pub keys: HashSet<Key>,
+    /// Every input event since the previous poll, in order
+    pub events: Vec<InputEvent>,
+    /// Latest focus change: `Some(true)` gained, `Some(false)` lost
+    pub focus: Option<bool>,
+}
+
+/// Where an input event came from
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InputSource {
+    /// The console keyboard and mouse
+    Hardware,
+    /// An [`InputInjector`] with this source name
+    Injected(String),
+}
+
+/// Input events waiting for the engine, shared by every injector of one engine
+pub(crate) type InjectedQueue = Arc<Mutex<Vec<(InputSource, InputEvent)>>>;
+
+/// Handle feeding input to the engine from other threads, scripts, or tests
+///
+/// Injected events are merged with console input at the start of the next frame.
+/// A key stays active from [`key_down`](Self::key_down) to [`key_up`](Self::key_up),
+/// a [`tap`](Self::tap) makes it active for one frame.
+///
+/// # Notes
+/// - Handles are cheap to clone and can be moved to other threads
+/// - Events carry the injector's source, see `UpdateContext::input_sources`
+///
+/// # Example
+/// ```
+/// use lonely_engine::{engine::Engine, input::Key};
+///
+/// let engine = Engine::new(80, 24);
+/// let remote = engine.input_injector("network");
+/// std::thread::spawn(move || {
+///     remote.tap(Key::Space); // Opponent jumps
+///     remote.type_text("gg");
+/// }).join().unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct InputInjector {
+    source: InputSource,
+    queue: InjectedQueue,
+}
+
+impl InputInjector {
+    /// Creates a handle writing into an engine's queue
+    pub(crate) fn new(source: &str, queue: InjectedQueue) -> Self {
+        Self { source: InputSource::Injected(source.to_string()), queue }
+    }
+
+    /// Source the injected events are tagged with
+    pub fn source(&self) -> &InputSource {
+        &self.source
+    }
+
+    /// Queues any input event
+    pub fn send(&self, event: InputEvent) {
+        // A panic on another injector thread leaves the queue usable
+        let mut queue = self.queue.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        queue.push((self.source.clone(), event));
+    }
+
+    /// Holds a key down until [`key_up`](Self::key_up)
+    pub fn key_down(&self, key: Key) {
+        self.send(InputEvent::KeyDown(key));
+    }
+
+    /// Releases a key held with [`key_down`](Self::key_down)
+    pub fn key_up(&self, key: Key) {
+        self.send(InputEvent::KeyUp(key));
+    }
+
+    /// Presses and releases a key, active for one frame
+    pub fn tap(&self, key: Key) {
+        self.key_down(key.clone());
+        self.key_up(key);
+    }
+
+    /// Types characters as `InputEvent::Char` events
+    pub fn type_text(&self, text: &str) {
+        for c in text.chars() {
+            self.send(InputEvent::Char(c));
+        }
+    }
+}
+
+/// Escape sequence asking VT terminals to report focus changes
+pub const ENABLE_FOCUS_REPORTING: &str = "\x1B[?1004h";
+
+/// Escape sequence turning terminal focus reports off again
+pub const DISABLE_FOCUS_REPORTING: &str = "\x1B[?1004l";
+
+/// Parses a VT focus report (`ESC [ I` gained, `ESC [ O` lost)
+///
+/// # Returns
+/// `Some(true)` for focus gained, `Some(false)` for focus lost, `None` otherwise
+///
+/// # Example
+/// ```
+/// # use lonely_engine::input::parse_focus_report;
+/// assert_eq!(parse_focus_report(b"\x1B[O"), Some(false));
+/// assert_eq!(parse_focus_report(b"q"), None);
+/// ```
+pub fn parse_focus_report(bytes: &[u8]) -> Option<bool> {
+    match bytes {
+        b"\x1B[I" => Some(true),
+        b"\x1B[O" => Some(false),
+        _ => None,
+    }
+}
 
 #[cfg(windows)]
 mod windows_input {
     use std::io;
     use std::collections::HashSet;
-    use winapi::um::consoleapi::{GetNumberOfConsoleInputEvents, ReadConsoleInputW};
-    use winapi::um::wincon::{INPUT_RECORD, KEY_EVENT_RECORD};
-
-    /// Represents a physical keyboard key
-    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-    pub enum Key {
-        Char(char),
-        /// Up arrow key
-        Up,
-        /// Down arrow key
-        Down,
-        /// Left arrow key
-        Left,
-        /// Right arrow key
-        Right,
-        /// Space bar
-        Space,
-        /// Enter/Return key
-        Enter,
-        /// Shift
-        Shift,
-        /// Control Key
-        Ctrl,
-        /// Escape Key
-        Esc,
-        /// Unrecognized Key
-        Unknown,
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use winapi::um::consoleapi::{GetConsoleMode, GetNumberOfConsoleInputEvents, ReadConsoleInputW, SetConsoleMode};
+    use winapi::um::wincon::{
+        ENABLE_EXTENDED_FLAGS, ENABLE_MOUSE_INPUT, ENABLE_QUICK_EDIT_MODE, FROM_LEFT_1ST_BUTTON_PRESSED,
+        FROM_LEFT_2ND_BUTTON_PRESSED, INPUT_RECORD, KEY_EVENT_RECORD, MOUSE_EVENT_RECORD, MOUSE_MOVED,
+        MOUSE_WHEELED, RIGHTMOST_BUTTON_PRESSED,
+    };
+    use super::{ConsoleInput, InputEvent, Key, MouseAction, MouseButton};
+
+    /// Mouse button state of the previous mouse record, the console only reports current state
+    static MOUSE_BUTTONS: AtomicU32 = AtomicU32::new(0);
+
+    /// Asks the console to report mouse events
+    ///
+    /// # Notes
+    /// - Turns off quick edit mode, which would otherwise swallow clicks to select text
+    pub fn enable_mouse_input() -> io::Result<()> {
+        unsafe {
+            let handle = winapi::um::processenv::GetStdHandle(winapi::um::winbase::STD_INPUT_HANDLE);
+            let mut mode = 0;
+            if GetConsoleMode(handle, &mut mode) == 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let mode = (mode | ENABLE_MOUSE_INPUT | ENABLE_EXTENDED_FLAGS) & !ENABLE_QUICK_EDIT_MODE;
+            if SetConsoleMode(handle, mode) == 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+        Ok(())
     }
 
     /// Reads all currently pressed keys from the input buffer
@@ -52,7 +513,26 @@ mod windows_input {
     /// }
     /// ```
     pub fn read_active_keys() -> io::Result<HashSet<Key>> {
-        let mut keys = HashSet::new();
+        read_console_input().map(|input| input.keys)
+    }
+
+    /// Drains the console input buffer, collecting pressed keys and focus changes
+    ///
+    /// # Returns
+    /// [`ConsoleInput`] with the keys pressed since the last read and the most
+    /// recent focus change, if any
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lonely_engine::input::read_console_input;
+    ///
+    /// let input = read_console_input().unwrap();
+    /// if input.focus == Some(false) {
+    ///     println!("Window lost focus");
+    /// }
+    /// ```
+    pub fn read_console_input() -> io::Result<ConsoleInput> {
+        let mut input = ConsoleInput::default();
         unsafe {
             let handle = winapi::um::processenv::GetStdHandle(winapi::um::winbase::STD_INPUT_HANDLE);
             let mut num_events = 0;
@@ -65,21 +545,47 @@ mod windows_input {
                 let mut input_record: INPUT_RECORD = std::mem::zeroed();
                 let mut events_read = 0;
 
-                if ReadConsoleInputW(handle, &mut input_record, 1, &mut events_read) != 0 {
-                    if input_record.EventType == winapi::um::wincon::KEY_EVENT {
+                if ReadConsoleInputW(handle, &mut input_record, 1, &mut events_read) == 0 {
+                    continue;
+                }
+
+                match input_record.EventType {
+                    winapi::um::wincon::KEY_EVENT => {
                         let key_event = *input_record.Event.KeyEvent();
-                        if key_event.bKeyDown != 0 {
-                            match key_code_to_key(&key_event) {
-                                Ok(key) => { keys.insert(key); },
-                                Err(_) => { continue; },
+                        let Ok(key) = key_code_to_key(&key_event) else {
+                            continue;
+                        };
+
+                        if key_event.bKeyDown == 0 {
+                            input.events.push(InputEvent::KeyUp(key));
+                            continue;
+                        }
+
+                        let typed = char::from_u32(u32::from(*key_event.uChar.UnicodeChar())).filter(|c| !c.is_control());
+                        // Auto-repeats may be merged into one record
+                        for _ in 0..key_event.wRepeatCount.max(1) {
+                            input.events.push(InputEvent::KeyDown(key.clone()));
+                            if let Some(c) = typed {
+                                input.events.push(InputEvent::Char(c));
                             }
                         }
-                    }
+                        input.keys.insert(key);
+                        if key_event.wVirtualScanCode != 0 {
+                            input.keys.insert(Key::Scan(key_event.wVirtualScanCode));
+                        }
+                    },
+                    winapi::um::wincon::MOUSE_EVENT => {
+                        push_mouse_events(input_record.Event.MouseEvent(), &mut input.events);
+                    },
+                    winapi::um::wincon::FOCUS_EVENT => {
+                        input.focus = Some(input_record.Event.FocusEvent().bSetFocus != 0);
+                    },
+                    _ => {},
                 }
             }
         }
 
-        Ok(keys)
+        Ok(input)
     }
 
     /// Reads a single key press from stdin (blocking)
@@ -103,6 +609,38 @@ mod windows_input {
         keys.into_iter().next().ok_or(io::Error::new(io::ErrorKind::WouldBlock, "No input available"))
     }
 
+    /// Translates a console mouse record into press, release, move, and scroll events
+    fn push_mouse_events(record: &MOUSE_EVENT_RECORD, events: &mut Vec<InputEvent>) {
+        let x = record.dwMousePosition.X.max(0) as usize;
+        let y = record.dwMousePosition.Y.max(0) as usize;
+
+        if record.dwEventFlags & MOUSE_WHEELED != 0 {
+            // Wheel delta is the signed high word, 120 per notch
+            let delta = i32::from((record.dwButtonState >> 16) as u16 as i16);
+            let notches = if delta.abs() >= 120 { delta / 120 } else { delta.signum() };
+            events.push(InputEvent::Mouse { x, y, action: MouseAction::Scroll(notches) });
+            return;
+        }
+
+        if record.dwEventFlags & MOUSE_MOVED != 0 {
+            events.push(InputEvent::Mouse { x, y, action: MouseAction::Move });
+        }
+
+        let buttons = record.dwButtonState & 0xFFFF;
+        let previous = MOUSE_BUTTONS.swap(buttons, Ordering::Relaxed);
+        for (mask, button) in [
+            (FROM_LEFT_1ST_BUTTON_PRESSED, MouseButton::Left),
+            (RIGHTMOST_BUTTON_PRESSED, MouseButton::Right),
+            (FROM_LEFT_2ND_BUTTON_PRESSED, MouseButton::Middle),
+        ] {
+            match (previous & mask != 0, buttons & mask != 0) {
+                (false, true) => events.push(InputEvent::Mouse { x, y, action: MouseAction::Press(button) }),
+                (true, false) => events.push(InputEvent::Mouse { x, y, action: MouseAction::Release(button) }),
+                _ => {},
+            }
+        }
+    }
+
     /// Converts WinAPI key codes to engine's Key enum
     fn key_code_to_key(key_event: &KEY_EVENT_RECORD) -> io::Result<Key> {
         let virtual_key_code = key_event.wVirtualKeyCode;
@@ -116,6 +654,11 @@ mod windows_input {
             x if x == winapi::um::winuser::VK_SHIFT as u16 => Key::Shift,
             x if x == winapi::um::winuser::VK_CONTROL as u16 => Key::Ctrl,
             x if x == winapi::um::winuser::VK_ESCAPE as u16 => Key::Esc,
+            x if x == winapi::um::winuser::VK_BACK as u16 => Key::Backspace,
+            x if x == winapi::um::winuser::VK_PRIOR as u16 => Key::PageUp,
+            x if x == winapi::um::winuser::VK_NEXT as u16 => Key::PageDown,
+            x if x == winapi::um::winuser::VK_HOME as u16 => Key::Home,
+            x if x == winapi::um::winuser::VK_END as u16 => Key::End,
             _ => {
                 unsafe {
                     if *key_event.uChar.UnicodeChar() != 0 {
@@ -132,17 +675,8 @@ mod windows_input {
 #[cfg(not(windows))]
 mod unix_input {
     use std::io;
-
-    /// Key representation for non-Windows platforms (unimplemented)
-    pub enum Key {
-        Char(char),
-        Up,
-        Down,
-        Left,
-        Right,
-        Esc,
-        Unknown,
-    }
+    use std::collections::HashSet;
+    use super::{ConsoleInput, Key};
 
     /// Stub implementation for non-Windows platforms
     ///
@@ -150,13 +684,37 @@ mod unix_input {
     /// Always returns Error on non-Windows systems
     /// 
     /// # Example
-    /// ```should_panic
+    /// ```
     /// use lonely_engine::input::read_key;
     /// 
-    /// let key = read_key().unwrap_err();
+    /// assert!(read_key().is_err());
     /// ```
     pub fn read_key() -> io::Result<Key> {
-        Err(io::Error::new(io::ErrorKind::Other, "Input not implemented for non-Windows platforms"))
+        Err(io::Error::other("Input not implemented for non-Windows platforms"))
+    }
+
+    /// Stub implementation for non-Windows platforms
+    ///
+    /// # Note
+    /// Always returns Error on non-Windows systems
+    pub fn read_active_keys() -> io::Result<HashSet<Key>> {
+        Err(io::Error::other("Input not implemented for non-Windows platforms"))
+    }
+
+    /// Stub implementation for non-Windows platforms
+    ///
+    /// # Note
+    /// Always returns Error on non-Windows systems
+    pub fn read_console_input() -> io::Result<ConsoleInput> {
+        Err(io::Error::other("Input not implemented for non-Windows platforms"))
+    }
+
+    /// Stub implementation for non-Windows platforms
+    ///
+    /// # Note
+    /// Always returns Error on non-Windows systems
+    pub fn enable_mouse_input() -> io::Result<()> {
+        Err(io::Error::other("Input not implemented for non-Windows platforms"))
     }
 }
 
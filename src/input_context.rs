@@ -0,0 +1,155 @@
+//! Stack of input contexts deciding which systems receive input
+//!
+//! Every updatable belongs to an input context, [`GAMEPLAY`] unless it says
+//! otherwise through `Updatable::input_context`. The engine keeps a stack of
+//! contexts with gameplay at the bottom; only the top context receives keys and
+//! input events, so opening a menu by pushing [`MENU`] stops the player from
+//! walking around behind it. A context pushed with
+//! [`pass_through`](InputContext::pass_through) lets the contexts below it
+//! receive input as well, such as a chat line that only reacts to Enter.
+//! Updatables in the [`GLOBAL`] context, such as a screenshot hotkey, always
+//! receive input.
+//!
+//! Object behaviors are gameplay and receive keys only while gameplay does.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::input_context::{InputContext, InputContexts, GAMEPLAY, GLOBAL, MENU};
+//!
+//! let mut contexts = InputContexts::new();
+//! assert!(contexts.is_active(GAMEPLAY));
+//!
+//! contexts.push(InputContext::new(MENU));
+//! assert!(contexts.is_active(MENU));
+//! assert!(!contexts.is_active(GAMEPLAY));
+//! assert!(contexts.is_active(GLOBAL));
+//!
+//! contexts.pop();
+//! assert!(contexts.is_active(GAMEPLAY));
+//! ```
+
+/// Context of the game world, at the bottom of every stack
+pub const GAMEPLAY: &str = "gameplay";
+
+/// Context of menus opened over the game
+pub const MENU: &str = "menu";
+
+/// Context of text fields, which take every key as typing
+pub const TEXT_ENTRY: &str = "text-entry";
+
+/// Context of a developer console
+pub const CONSOLE: &str = "console";
+
+/// Context receiving input whatever the stack holds
+pub const GLOBAL: &str = "global";
+
+/// One entry of the context stack
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputContext {
+    /// Name updatables refer to, such as [`MENU`]
+    pub name: String,
+    /// Whether contexts below this one receive input too
+    pub pass_through: bool,
+}
+
+impl InputContext {
+    /// Creates a context blocking the contexts below it
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), pass_through: false }
+    }
+
+    /// Sets whether contexts below this one receive input too
+    pub fn pass_through(mut self, pass_through: bool) -> Self {
+        self.pass_through = pass_through;
+        self
+    }
+}
+
+/// Stack of input contexts, the topmost last
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputContexts {
+    /// Never empty, [`GAMEPLAY`] first
+    stack: Vec<InputContext>,
+}
+
+impl Default for InputContexts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InputContexts {
+    /// Creates a stack holding only [`GAMEPLAY`]
+    pub fn new() -> Self {
+        Self { stack: vec![InputContext::new(GAMEPLAY)] }
+    }
+
+    /// Puts a context on top, receiving input from now on
+    pub fn push(&mut self, context: InputContext) {
+        self.stack.push(context);
+    }
+
+    /// Removes the topmost context
+    ///
+    /// # Returns
+    /// The removed context, `None` when only [`GAMEPLAY`] is left, which is never removed
+    pub fn pop(&mut self) -> Option<InputContext> {
+        if self.stack.len() > 1 { self.stack.pop() } else { None }
+    }
+
+    /// Removes the topmost context with a name and everything above it, such as a menu that opened a text field
+    ///
+    /// # Returns
+    /// Whether a context with the name was on the stack
+    pub fn pop_to(&mut self, name: &str) -> bool {
+        match self.stack.iter().skip(1).rposition(|context| context.name == name) {
+            Some(position) => {
+                self.stack.truncate(position + 1);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Topmost context
+    pub fn top(&self) -> &InputContext {
+        // The stack always holds gameplay
+        &self.stack[self.stack.len() - 1]
+    }
+
+    /// Returns whether updatables of a context receive input
+    ///
+    /// # Notes
+    /// - True for [`GLOBAL`], and for a context on the stack with only pass-through contexts above it
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::input_context::{InputContext, InputContexts, GAMEPLAY};
+    ///
+    /// let mut contexts = InputContexts::new();
+    /// contexts.push(InputContext::new("chat").pass_through(true));
+    /// assert!(contexts.is_active("chat"));
+    /// assert!(contexts.is_active(GAMEPLAY));
+    /// assert!(!contexts.is_active("inventory"));
+    /// ```
+    pub fn is_active(&self, name: &str) -> bool {
+        if name == GLOBAL {
+            return true;
+        }
+        for context in self.stack.iter().rev() {
+            if context.name == name {
+                return true;
+            }
+            if !context.pass_through {
+                return false;
+            }
+        }
+        false
+    }
+
+    /// Contexts from the bottom up
+    pub fn iter(&self) -> impl Iterator<Item = &InputContext> {
+        self.stack.iter()
+    }
+}
@@ -0,0 +1,189 @@
+//! Frame-stamped input logs for recording and replaying play sessions
+//!
+//! An [`InputLog`] stores every input event with the frame it arrived in. The
+//! engine writes one with `--record` and feeds one back with `--replay`, see
+//! [`EngineBuilder::from_args`]. Combined with deterministic mode, a replayed
+//! log reproduces a session frame for frame, which makes bug reports and CI
+//! runs repeatable.
+//!
+//! # Format
+//! One event per line as `<frame> <event>`, blank lines and `#` comments are skipped:
+//! ```text
+//! # frame event
+//! 0 down Space
+//! 0 char a
+//! 3 up Space
+//! 5 mouse 10 4 press left
+//! 6 mouse 10 4 scroll -1
+//! ```
+//!
+//! [`EngineBuilder::from_args`]: crate::engine::EngineBuilder::from_args
+
+use std::{fs, io, path::Path};
+use crate::input::{InputEvent, Key, MouseAction, MouseButton};
+
+/// Input events with the frame each one arrived in
+///
+/// # Example
+/// ```
+/// use lonely_engine::{input::{InputEvent, Key}, input_log::InputLog};
+///
+/// let mut log = InputLog::new();
+/// log.record(0, &[InputEvent::KeyDown(Key::Space)]);
+/// log.record(4, &[InputEvent::KeyUp(Key::Space)]);
+///
+/// let text = log.to_text();
+/// assert_eq!(text, "0 down Space\n4 up Space\n");
+/// assert_eq!(InputLog::parse(&text).unwrap(), log);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputLog {
+    /// Events in the order they arrived, frames never decrease
+    events: Vec<(u64, InputEvent)>,
+}
+
+impl InputLog {
+    /// Creates an empty log
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the events of a frame
+    ///
+    /// # Notes
+    /// - Frames must be recorded in order, events are looked up by frame assuming so
+    pub fn record(&mut self, frame: u64, events: &[InputEvent]) {
+        self.events.extend(events.iter().map(|event| (frame, event.clone())));
+    }
+
+    /// Events that arrived in a frame, in their original order
+    pub fn events_at(&self, frame: u64) -> impl Iterator<Item = &InputEvent> {
+        let start = self.events.partition_point(|(at, _)| *at < frame);
+        self.events[start..].iter().take_while(move |(at, _)| *at == frame).map(|(_, event)| event)
+    }
+
+    /// Every event with its frame
+    pub fn events(&self) -> &[(u64, InputEvent)] {
+        &self.events
+    }
+
+    /// Frame of the last event, `None` for an empty log
+    pub fn last_frame(&self) -> Option<u64> {
+        self.events.last().map(|(frame, _)| *frame)
+    }
+
+    /// Number of events
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Returns whether no event was recorded
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Serializes the log, one event per line
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for (frame, event) in &self.events {
+            text.push_str(&format!("{frame} {}\n", event_to_text(event)));
+        }
+        text
+    }
+
+    /// Parses a log written by [`to_text`](Self::to_text)
+    ///
+    /// # Returns
+    /// `InvalidData` naming the line of a malformed event or a frame going backwards
+    pub fn parse(text: &str) -> io::Result<Self> {
+        let mut log = Self::new();
+        for (number, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || io::Error::new(io::ErrorKind::InvalidData, format!("invalid input log line {}: `{}`", number + 1, line));
+            let (frame, event) = line.split_once(' ').ok_or_else(invalid)?;
+            let frame: u64 = frame.parse().map_err(|_| invalid())?;
+            if log.last_frame().is_some_and(|last| frame < last) {
+                return Err(invalid());
+            }
+            log.events.push((frame, event_from_text(event).ok_or_else(invalid)?));
+        }
+        Ok(log)
+    }
+
+    /// Loads a log from a file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Writes the log to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+}
+
+/// Name of a mouse button in the log
+fn button_to_text(button: MouseButton) -> &'static str {
+    match button {
+        MouseButton::Left => "left",
+        MouseButton::Right => "right",
+        MouseButton::Middle => "middle",
+    }
+}
+
+/// Mouse button from its name in the log
+fn button_from_text(text: &str) -> Option<MouseButton> {
+    match text {
+        "left" => Some(MouseButton::Left),
+        "right" => Some(MouseButton::Right),
+        "middle" => Some(MouseButton::Middle),
+        _ => None,
+    }
+}
+
+/// Event as written after the frame number
+fn event_to_text(event: &InputEvent) -> String {
+    match event {
+        InputEvent::KeyDown(key) => format!("down {key}"),
+        InputEvent::KeyUp(key) => format!("up {key}"),
+        InputEvent::Char(c) => format!("char {c}"),
+        InputEvent::Mouse { x, y, action } => match action {
+            MouseAction::Press(button) => format!("mouse {x} {y} press {}", button_to_text(*button)),
+            MouseAction::Release(button) => format!("mouse {x} {y} release {}", button_to_text(*button)),
+            MouseAction::Move => format!("mouse {x} {y} move"),
+            MouseAction::Scroll(amount) => format!("mouse {x} {y} scroll {amount}"),
+        },
+    }
+}
+
+/// Event from the text after the frame number
+fn event_from_text(text: &str) -> Option<InputEvent> {
+    // Key and character names are taken verbatim, they may be a space
+    let (kind, rest) = text.split_once(' ')?;
+    match kind {
+        "down" => rest.parse::<Key>().ok().map(InputEvent::KeyDown),
+        "up" => rest.parse::<Key>().ok().map(InputEvent::KeyUp),
+        "char" => {
+            let mut chars = rest.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Some(InputEvent::Char(c)),
+                _ => None,
+            }
+        },
+        "mouse" => {
+            let mut parts = rest.split(' ');
+            let x = parts.next()?.parse().ok()?;
+            let y = parts.next()?.parse().ok()?;
+            let action = match (parts.next()?, parts.next()) {
+                ("press", Some(button)) => MouseAction::Press(button_from_text(button)?),
+                ("release", Some(button)) => MouseAction::Release(button_from_text(button)?),
+                ("move", None) => MouseAction::Move,
+                ("scroll", Some(amount)) => MouseAction::Scroll(amount.parse().ok()?),
+                _ => return None,
+            };
+            parts.next().is_none().then_some(InputEvent::Mouse { x, y, action })
+        },
+        _ => None,
+    }
+}
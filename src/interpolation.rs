@@ -0,0 +1,141 @@
+//! Smoothed movement of objects controlled over the network
+//!
+//! Position updates for remote players arrive a few times per second and
+//! never exactly on time. Moving the object as each one lands makes it jump
+//! several cells at once and stutter with the network. An [`Interpolation`]
+//! buffers the last few updates instead and shows the object a short delay in
+//! the past, gliding between the two updates around that moment. With the
+//! delay a little longer than the time between updates there is almost always
+//! a newer update to glide towards, at the price of seeing remote objects that
+//! much late.
+//!
+//! Interpolations are attached with [`GameObject::with_interpolation`]. The
+//! engine advances them every update and places their objects; a
+//! [`ReplicationClient`] feeds them new positions instead of moving the
+//! objects itself.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{game_object::GameObject, interpolation::Interpolation};
+//!
+//! let mut remote = GameObject::new(0, 0, 'R').with_interpolation(Interpolation::new().delay(0.1));
+//! let interpolation = remote.interpolation.as_mut().unwrap();
+//! interpolation.push(0, 0);
+//! interpolation.advance(0.1);
+//! interpolation.push(4, 0);
+//! // Halfway between the updates, a tenth of a second ago
+//! assert_eq!(interpolation.advance(0.05), Some((2, 0)));
+//! ```
+//!
+//! [`GameObject::with_interpolation`]: crate::game_object::GameObject::with_interpolation
+//! [`ReplicationClient`]: crate::replication::ReplicationClient
+
+use std::collections::VecDeque;
+
+/// Seconds remote objects are shown in the past unless configured otherwise
+pub const DEFAULT_DELAY: f32 = 0.1;
+
+/// Position updates kept unless configured otherwise
+pub const DEFAULT_CAPACITY: usize = 16;
+
+/// Buffer of timed position updates played back with a delay
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interpolation {
+    /// Seconds the shown position lags behind the newest update
+    delay: f32,
+    capacity: usize,
+    /// Updates as (time received, x, y), oldest first
+    samples: VecDeque<(f32, f32, f32)>,
+    /// Seconds since the interpolation was created
+    clock: f32,
+}
+
+impl Default for Interpolation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Interpolation {
+    /// Creates an empty buffer with a [`DEFAULT_DELAY`]
+    pub fn new() -> Self {
+        Self { delay: DEFAULT_DELAY, capacity: DEFAULT_CAPACITY, samples: VecDeque::new(), clock: 0.0 }
+    }
+
+    /// Sets how many seconds the object is shown in the past, a little more than the time between updates
+    pub fn delay(mut self, seconds: f32) -> Self {
+        self.delay = seconds.max(0.0);
+        self
+    }
+
+    /// Sets how many updates are kept, the oldest are dropped beyond that
+    pub fn capacity(mut self, updates: usize) -> Self {
+        self.capacity = updates.max(2);
+        self
+    }
+
+    /// Seconds the object is shown in the past
+    pub fn get_delay(&self) -> f32 {
+        self.delay
+    }
+
+    /// Changes the delay, such as to follow measured network jitter
+    pub fn set_delay(&mut self, seconds: f32) {
+        self.delay = seconds.max(0.0);
+    }
+
+    /// Records a position update received now
+    pub fn push(&mut self, x: usize, y: usize) {
+        self.samples.push_back((self.clock, x as f32, y as f32));
+        while self.samples.len() > self.capacity {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Number of buffered updates
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns whether no update was received yet
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Forgets every update, such as after a teleport
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Advances time and finds the cell to show
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds since the last advance
+    ///
+    /// # Returns
+    /// The cell at the delayed time, `None` before the first update
+    ///
+    /// # Notes
+    /// - Before the oldest update the object stays on it, after the newest one it stops there instead of guessing ahead
+    pub fn advance(&mut self, delta_time: f32) -> Option<(usize, usize)> {
+        self.clock += delta_time;
+        let (x, y) = self.position()?;
+        Some((x.round().max(0.0) as usize, y.round().max(0.0) as usize))
+    }
+
+    /// Smooth position at the delayed time, `None` before the first update
+    pub fn position(&mut self) -> Option<(f32, f32)> {
+        let shown = self.clock - self.delay;
+        // Updates before the pair around the shown time are not needed anymore
+        while self.samples.get(1).is_some_and(|&(time, _, _)| time <= shown) {
+            self.samples.pop_front();
+        }
+
+        let &(from_time, from_x, from_y) = self.samples.front()?;
+        let Some(&(to_time, to_x, to_y)) = self.samples.get(1).filter(|_| shown > from_time) else {
+            return Some((from_x, from_y));
+        };
+        let t = ((shown - from_time) / (to_time - from_time).max(f32::EPSILON)).clamp(0.0, 1.0);
+        Some((from_x + (to_x - from_x) * t, from_y + (to_y - from_y) * t))
+    }
+}
@@ -0,0 +1,644 @@
+//! Item inventories
+//!
+//! Provides:
+//! - [`ItemDef`] and [`ItemRegistry`] describing every item a game knows about
+//! - [`Inventory`] holding item stacks in a fixed number of slots
+//! - [`InventoryScreen`] widget with grid navigation and use/drop actions
+//!
+//! Inventories store item ids only, names, glyphs, and stack sizes come from the
+//! registry so item data lives in one place. Item definitions also say which
+//! equipment slot an item goes in, the stat modifiers it grants while equipped,
+//! and what using it does, see [`equipment`](crate::equipment).
+//!
+//! # File format
+//! Registries load from JSON with [`ItemRegistry::from_json`]:
+//! ```json
+//! {
+//!     "items": [
+//!         {
+//!             "id": "sword", "name": "Iron Sword", "glyph": "|", "color": "bright_white",
+//!             "slot": "hand",
+//!             "modifiers": [{ "stat": "strength", "add": 3 }, { "stat": "speed", "multiply": 0.9 }]
+//!         },
+//!         {
+//!             "id": "potion", "name": "Potion of Haste", "glyph": "!", "max_stack": 5,
+//!             "description": "Quickens the drinker.",
+//!             "use": [
+//!                 { "status": "haste", "speed": 1.5, "duration": 10 },
+//!                 { "stat": "agility", "add": 2, "duration": 30 },
+//!                 { "event": "drank_potion" }
+//!             ]
+//!         }
+//!     ]
+//! }
+//! ```
+//! - `name` defaults to the id, `glyph` to `?`, `max_stack` to `1`, and `color` to the terminal's
+//! - A status lasts until removed without a `duration`; it speeds up with `speed`, deals
+//!   `damage_per_second`, stuns with `"stun": true`, and has no built-in meaning otherwise
+//! - A modifier entry in `use` lasts until removed without a `duration`
+
+use std::{fs, io, path::Path};
+use crate::{
+    color::Color,
+    engine::UpdateContext,
+    event::EngineEvent,
+    input::{InputEvent, Key},
+    json::JsonValue,
+    renderer::{Cell, Renderer},
+    stat_block::{Modifier, ModifierKind},
+    status::{EffectKind, StatusEffect},
+    style::Style,
+};
+
+/// What using an item does to the object using it
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemEffect {
+    /// Applies a status effect
+    Status(StatusEffect),
+    /// Adds a stat modifier, its source is the item id
+    Modifier(Modifier),
+    /// Emits `EngineEvent::Custom` with the text, for effects the game implements
+    Event(String),
+}
+
+/// Description of an item type
+///
+/// # Example
+/// ```
+/// use lonely_engine::{color::Color, inventory::ItemDef, style::Style};
+///
+/// let potion = ItemDef::new("potion", "Healing Potion", '!')
+///     .max_stack(5)
+///     .style(Style::new().fg(Color::BRIGHT_RED))
+///     .description("Restores 10 HP.");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemDef {
+    /// Identifier stored in inventories
+    pub id: String,
+    /// Name shown to players
+    pub name: String,
+    /// Character drawn for the item
+    pub glyph: char,
+    /// Colors of the glyph
+    pub style: Style,
+    /// Most items a single slot holds
+    pub max_stack: u32,
+    /// Text shown for the selected item
+    pub description: String,
+    /// Equipment slot the item goes in, `None` for items that cannot be equipped
+    pub slot: Option<String>,
+    /// Stat modifiers granted while equipped, as (stat, kind)
+    pub modifiers: Vec<(String, ModifierKind)>,
+    /// What using the item does, in order
+    pub use_effects: Vec<ItemEffect>,
+}
+
+impl ItemDef {
+    /// Creates an unstackable item
+    pub fn new(id: &str, name: &str, glyph: char) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            glyph,
+            style: Style::new(),
+            max_stack: 1,
+            description: String::new(),
+            slot: None,
+            modifiers: Vec::new(),
+            use_effects: Vec::new(),
+        }
+    }
+
+    /// Sets how many items share a slot, at least one
+    pub fn max_stack(mut self, max_stack: u32) -> Self {
+        self.max_stack = max_stack.max(1);
+        self
+    }
+
+    /// Sets the glyph colors
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the description
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Makes the item equippable in a slot
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{inventory::ItemDef, stat_block::ModifierKind};
+    ///
+    /// let helmet = ItemDef::new("helmet", "Iron Helmet", '^').equip_slot("head").modifier("armor", ModifierKind::Add(2.0));
+    /// assert!(helmet.is_equippable());
+    /// ```
+    pub fn equip_slot(mut self, slot: &str) -> Self {
+        self.slot = Some(slot.to_string());
+        self
+    }
+
+    /// Adds a stat modifier granted while the item is equipped
+    pub fn modifier(mut self, stat: &str, kind: ModifierKind) -> Self {
+        self.modifiers.push((stat.to_string(), kind));
+        self
+    }
+
+    /// Adds something using the item does
+    pub fn on_use(mut self, effect: ItemEffect) -> Self {
+        self.use_effects.push(effect);
+        self
+    }
+
+    /// Returns whether the item goes in an equipment slot
+    pub fn is_equippable(&self) -> bool {
+        self.slot.is_some()
+    }
+
+    /// Returns whether using the item does anything
+    pub fn is_usable(&self) -> bool {
+        !self.use_effects.is_empty()
+    }
+}
+
+/// Every item definition of a game, looked up by id
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ItemRegistry {
+    items: Vec<ItemDef>,
+}
+
+impl ItemRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a definition, replacing one with the same id
+    pub fn register(&mut self, item: ItemDef) {
+        match self.items.iter_mut().find(|existing| existing.id == item.id) {
+            Some(existing) => *existing = item,
+            None => self.items.push(item),
+        }
+    }
+
+    /// Looks up a definition
+    pub fn get(&self, id: &str) -> Option<&ItemDef> {
+        self.items.iter().find(|item| item.id == id)
+    }
+
+    /// Definitions in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &ItemDef> {
+        self.items.iter()
+    }
+
+    /// Stack size of an item, `1` for unknown items
+    pub fn max_stack(&self, id: &str) -> u32 {
+        self.get(id).map_or(1, |item| item.max_stack)
+    }
+
+    /// Parses item definitions in the JSON format described in the [module docs](self)
+    ///
+    /// # Returns
+    /// `Err` with [`io::ErrorKind::InvalidData`] for invalid JSON or malformed items
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{inventory::{ItemEffect, ItemRegistry}, stat_block::ModifierKind};
+    ///
+    /// let items = ItemRegistry::from_json(r#"{ "items": [
+    ///     { "id": "ring", "name": "Ring of Might", "glyph": "=", "slot": "finger", "modifiers": [{ "stat": "strength", "multiply": 1.2 }] },
+    ///     { "id": "scroll", "use": [{ "event": "teleport" }] }
+    /// ] }"#).unwrap();
+    ///
+    /// let ring = items.get("ring").unwrap();
+    /// assert_eq!(ring.slot.as_deref(), Some("finger"));
+    /// assert_eq!(ring.modifiers, [("strength".to_string(), ModifierKind::Multiply(1.2))]);
+    /// assert_eq!(items.get("scroll").unwrap().use_effects, [ItemEffect::Event("teleport".into())]);
+    /// ```
+    pub fn from_json(text: &str) -> io::Result<Self> {
+        let root = JsonValue::parse(text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        let items = root.get("items").and_then(JsonValue::as_array).ok_or_else(|| invalid("`items` must be an array"))?;
+        let mut registry = Self::new();
+        for item in items {
+            registry.register(parse_item(item)?);
+        }
+        Ok(registry)
+    }
+
+    /// Loads item definitions from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/// Reads an optional number field
+fn number(value: &JsonValue, key: &str, id: &str) -> io::Result<Option<f32>> {
+    match value.get(key) {
+        None => Ok(None),
+        Some(number) => number.as_f64().map(|number| Some(number as f32)).ok_or_else(|| invalid(&format!("`{key}` of item `{id}` must be a number"))),
+    }
+}
+
+/// Reads an optional string field
+fn text<'a>(value: &'a JsonValue, key: &str, id: &str) -> io::Result<Option<&'a str>> {
+    match value.get(key) {
+        None => Ok(None),
+        Some(text) => text.as_str().map(Some).ok_or_else(|| invalid(&format!("`{key}` of item `{id}` must be a string"))),
+    }
+}
+
+fn parse_item(value: &JsonValue) -> io::Result<ItemDef> {
+    let id = value.get("id").and_then(JsonValue::as_str).ok_or_else(|| invalid("every item needs a string `id`"))?;
+    let glyph = match text(value, "glyph", id)? {
+        Some(glyph) => glyph.chars().next().ok_or_else(|| invalid(&format!("`glyph` of item `{id}` is empty")))?,
+        None => '?',
+    };
+    let mut item = ItemDef::new(id, text(value, "name", id)?.unwrap_or(id), glyph);
+    if let Some(color) = text(value, "color", id)? {
+        let color = Color::from_name(color).ok_or_else(|| invalid(&format!("item `{id}` has unknown color `{color}`")))?;
+        item.style = Style::new().fg(color);
+    }
+    if let Some(max_stack) = value.get("max_stack") {
+        let max_stack = max_stack.as_i64().and_then(|max_stack| u32::try_from(max_stack).ok()).ok_or_else(|| invalid(&format!("`max_stack` of item `{id}` must be a non-negative integer")))?;
+        item = item.max_stack(max_stack);
+    }
+    if let Some(description) = text(value, "description", id)? {
+        item.description = description.to_string();
+    }
+    item.slot = text(value, "slot", id)?.map(str::to_string);
+
+    let entries = |key: &str| -> io::Result<&[JsonValue]> {
+        match value.get(key) {
+            None => Ok(&[]),
+            Some(entries) => entries.as_array().ok_or_else(|| invalid(&format!("`{key}` of item `{id}` must be an array"))),
+        }
+    };
+    for entry in entries("modifiers")? {
+        let (stat, kind) = parse_modifier(entry, id)?;
+        item.modifiers.push((stat, kind));
+    }
+    for entry in entries("use")? {
+        item.use_effects.push(parse_use(entry, id)?);
+    }
+    Ok(item)
+}
+
+/// Reads the stat and kind of a `{ "stat": ..., "add" | "multiply": ... }` entry
+fn parse_modifier(value: &JsonValue, id: &str) -> io::Result<(String, ModifierKind)> {
+    let stat = text(value, "stat", id)?.ok_or_else(|| invalid(&format!("modifier of item `{id}` needs a `stat`")))?;
+    let kind = match (number(value, "add", id)?, number(value, "multiply", id)?) {
+        (Some(amount), None) => ModifierKind::Add(amount),
+        (None, Some(factor)) => ModifierKind::Multiply(factor),
+        _ => return Err(invalid(&format!("modifier of item `{id}` needs either `add` or `multiply`"))),
+    };
+    Ok((stat.to_string(), kind))
+}
+
+fn parse_use(value: &JsonValue, id: &str) -> io::Result<ItemEffect> {
+    let duration = number(value, "duration", id)?;
+    if let Some(name) = text(value, "status", id)? {
+        let kind = if let Some(multiplier) = number(value, "speed", id)? {
+            EffectKind::Speed(multiplier)
+        } else if let Some(damage) = number(value, "damage_per_second", id)? {
+            EffectKind::DamageOverTime(damage)
+        } else if value.get("stun").and_then(JsonValue::as_bool) == Some(true) {
+            EffectKind::Stun
+        } else {
+            EffectKind::Custom
+        };
+        return Ok(ItemEffect::Status(match duration {
+            Some(duration) => StatusEffect::new(name, kind, duration),
+            None => StatusEffect::permanent(name, kind),
+        }));
+    }
+    if value.get("stat").is_some() {
+        let (stat, kind) = parse_modifier(value, id)?;
+        let mut modifier = Modifier { stat, kind, source: id.to_string(), remaining: None };
+        if let Some(duration) = duration {
+            modifier = modifier.lasting(duration);
+        }
+        return Ok(ItemEffect::Modifier(modifier));
+    }
+    if let Some(event) = text(value, "event", id)? {
+        return Ok(ItemEffect::Event(event.to_string()));
+    }
+    Err(invalid(&format!("use entry of item `{id}` needs a `status`, `stat`, or `event`")))
+}
+
+/// Items of one type sharing a slot
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItemStack {
+    /// Item id
+    pub item: String,
+    /// Number of items, never zero inside an inventory
+    pub count: u32,
+}
+
+/// Fixed number of slots holding item stacks
+///
+/// # Notes
+/// - Adding fills partial stacks of the same item first, then empty slots in order
+/// - Unknown items do not stack
+///
+/// # Example
+/// ```
+/// use lonely_engine::inventory::{Inventory, ItemDef, ItemRegistry};
+///
+/// let mut items = ItemRegistry::new();
+/// items.register(ItemDef::new("arrow", "Arrow", '/').max_stack(20));
+/// items.register(ItemDef::new("sword", "Sword", '|'));
+///
+/// let mut bag = Inventory::new(2);
+/// assert_eq!(bag.add(&items, "arrow", 30), 0); // 20 + 10 over two slots
+/// assert_eq!(bag.add(&items, "sword", 1), 1); // No room left
+///
+/// assert_eq!(bag.remove("arrow", 15), 15);
+/// assert_eq!(bag.count("arrow"), 15);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Inventory {
+    slots: Vec<Option<ItemStack>>,
+}
+
+impl Inventory {
+    /// Creates an inventory of empty slots
+    pub fn new(slot_count: usize) -> Self {
+        Self { slots: vec![None; slot_count] }
+    }
+
+    /// Number of slots
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Every slot in order
+    pub fn slots(&self) -> &[Option<ItemStack>] {
+        &self.slots
+    }
+
+    /// Stack in a slot, `None` for empty or missing slots
+    pub fn slot(&self, index: usize) -> Option<&ItemStack> {
+        self.slots.get(index).and_then(Option::as_ref)
+    }
+
+    /// Total number of an item across slots
+    pub fn count(&self, item: &str) -> u32 {
+        self.slots.iter().flatten().filter(|stack| stack.item == item).map(|stack| stack.count).sum()
+    }
+
+    /// Returns whether at least `count` of an item are held
+    pub fn contains(&self, item: &str, count: u32) -> bool {
+        self.count(item) >= count
+    }
+
+    /// Returns whether every slot is empty
+    pub fn is_empty(&self) -> bool {
+        self.slots.iter().all(Option::is_none)
+    }
+
+    /// Adds items, stacking up to the registry's limit
+    ///
+    /// # Returns
+    /// Number of items that did not fit
+    pub fn add(&mut self, registry: &ItemRegistry, item: &str, count: u32) -> u32 {
+        let max_stack = registry.max_stack(item);
+        let mut remaining = count;
+
+        for stack in self.slots.iter_mut().flatten().filter(|stack| stack.item == item) {
+            let moved = remaining.min(max_stack.saturating_sub(stack.count));
+            stack.count += moved;
+            remaining -= moved;
+        }
+        for slot in self.slots.iter_mut().filter(|slot| slot.is_none()) {
+            if remaining == 0 {
+                break;
+            }
+            let moved = remaining.min(max_stack);
+            *slot = Some(ItemStack { item: item.to_string(), count: moved });
+            remaining -= moved;
+        }
+
+        remaining
+    }
+
+    /// Removes up to `count` of an item, emptying later slots first
+    ///
+    /// # Returns
+    /// Number of items removed
+    pub fn remove(&mut self, item: &str, count: u32) -> u32 {
+        let mut removed = 0;
+        for slot in self.slots.iter_mut().rev() {
+            if removed == count {
+                break;
+            }
+            if let Some(stack) = slot.as_mut().filter(|stack| stack.item == item) {
+                let taken = (count - removed).min(stack.count);
+                stack.count -= taken;
+                removed += taken;
+                if stack.count == 0 {
+                    *slot = None;
+                }
+            }
+        }
+        removed
+    }
+
+    /// Takes up to `count` items out of a slot
+    ///
+    /// # Returns
+    /// The items taken, `None` if the slot is empty or missing
+    pub fn take_from_slot(&mut self, index: usize, count: u32) -> Option<ItemStack> {
+        let slot = self.slots.get_mut(index)?;
+        let stack = slot.as_mut()?;
+        let taken = count.min(stack.count);
+        if taken == 0 {
+            return None;
+        }
+
+        stack.count -= taken;
+        let item = stack.item.clone();
+        if stack.count == 0 {
+            *slot = None;
+        }
+        Some(ItemStack { item, count: taken })
+    }
+
+    /// Swaps the contents of two slots, ignoring missing slots
+    pub fn swap(&mut self, a: usize, b: usize) {
+        if a < self.slots.len() && b < self.slots.len() {
+            self.slots.swap(a, b);
+        }
+    }
+
+    /// Empties every slot
+    pub fn clear(&mut self) {
+        self.slots.fill(None);
+    }
+}
+
+/// Action picked on the inventory screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InventoryAction {
+    /// Use the item in a slot, the game decides what it does and whether it is consumed
+    Use {
+        /// Slot index
+        slot: usize,
+        /// Item id
+        item: String,
+    },
+    /// Whole stack removed from the inventory
+    Drop(ItemStack),
+}
+
+/// Grid widget showing an inventory
+///
+/// # Controls
+/// - Arrow keys move the cursor
+/// - Enter or Space uses the selected item
+/// - Backspace drops the selected stack
+///
+/// # Events
+/// - `EngineEvent::ItemUsed` with the slot index and item id
+/// - `EngineEvent::ItemDropped` with the item id and count
+///
+/// # Example
+/// ```
+/// use lonely_engine::{
+///     input::{InputEvent, Key},
+///     inventory::{Inventory, InventoryAction, InventoryScreen, ItemDef, ItemRegistry},
+///     renderer::Renderer,
+/// };
+///
+/// let mut items = ItemRegistry::new();
+/// items.register(ItemDef::new("potion", "Potion", '!').max_stack(5));
+/// let mut bag = Inventory::new(8);
+/// bag.add(&items, "potion", 3);
+///
+/// let mut screen = InventoryScreen::new(4);
+/// match screen.handle(&mut bag, &[InputEvent::KeyDown(Key::Enter)]) {
+///     Some(InventoryAction::Use { slot, .. }) => {
+///         bag.take_from_slot(slot, 1); // Drink one
+///     },
+///     _ => {},
+/// }
+/// assert_eq!(bag.count("potion"), 2);
+///
+/// let mut renderer = Renderer::new(40, 10);
+/// screen.draw(&mut renderer, &bag, &items, 1, 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InventoryScreen {
+    columns: usize,
+    cursor: usize,
+}
+
+impl InventoryScreen {
+    /// Creates a screen laying slots out in rows of `columns`
+    pub fn new(columns: usize) -> Self {
+        Self { columns: columns.max(1), cursor: 0 }
+    }
+
+    /// Index of the selected slot
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Moves the cursor to a slot
+    pub fn set_cursor(&mut self, slot: usize) {
+        self.cursor = slot;
+    }
+
+    /// Handles this frame's input and emits item events on the event bus
+    ///
+    /// # Returns
+    /// The action picked this frame, if any
+    pub fn update(&mut self, inventory: &mut Inventory, ctx: &UpdateContext) -> Option<InventoryAction> {
+        let action = self.handle(inventory, ctx.input_events);
+        match &action {
+            Some(InventoryAction::Use { slot, item }) => ctx.event_bus.emit(EngineEvent::ItemUsed(*slot, item.clone())),
+            Some(InventoryAction::Drop(stack)) => ctx.event_bus.emit(EngineEvent::ItemDropped(stack.item.clone(), stack.count)),
+            None => {},
+        }
+        action
+    }
+
+    /// Handles input without an engine
+    ///
+    /// # Returns
+    /// The first action picked, later events of the frame are ignored after it
+    pub fn handle(&mut self, inventory: &mut Inventory, events: &[InputEvent]) -> Option<InventoryAction> {
+        let capacity = inventory.capacity();
+        if capacity == 0 {
+            return None;
+        }
+        self.cursor = self.cursor.min(capacity - 1);
+
+        for event in events {
+            match event {
+                InputEvent::KeyDown(Key::Left) => self.cursor = self.cursor.saturating_sub(1),
+                InputEvent::KeyDown(Key::Right) => self.cursor = (self.cursor + 1).min(capacity - 1),
+                InputEvent::KeyDown(Key::Up) => self.cursor = self.cursor.saturating_sub(self.columns),
+                InputEvent::KeyDown(Key::Down) if self.cursor + self.columns < capacity => self.cursor += self.columns,
+                InputEvent::KeyDown(Key::Enter | Key::Space) => {
+                    if let Some(stack) = inventory.slot(self.cursor) {
+                        return Some(InventoryAction::Use { slot: self.cursor, item: stack.item.clone() });
+                    }
+                },
+                InputEvent::KeyDown(Key::Backspace) => {
+                    let count = inventory.slot(self.cursor).map_or(0, |stack| stack.count);
+                    if let Some(stack) = inventory.take_from_slot(self.cursor, count) {
+                        return Some(InventoryAction::Drop(stack));
+                    }
+                },
+                _ => {},
+            }
+        }
+        None
+    }
+
+    /// Draws the slot grid with the selected item's name and description below
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Top-left cell of the grid
+    ///
+    /// # Notes
+    /// - Every slot takes 5 columns, `[! 3]` for a stack of three potions
+    /// - Items missing from the registry are drawn as `?`
+    pub fn draw(&self, renderer: &mut Renderer, inventory: &Inventory, registry: &ItemRegistry, x: usize, y: usize) {
+        let frame = Style::new().fg(Color::GREY);
+        for (index, slot) in inventory.slots().iter().enumerate() {
+            let (sx, sy) = (x + (index % self.columns) * 5, y + index / self.columns);
+            let selected = index == self.cursor;
+            let bracket_style = if selected { Style::new().fg(Color::BRIGHT_YELLOW).bold() } else { frame };
+            renderer.draw_styled_text(sx, sy, "[", &bracket_style);
+            renderer.draw_styled_text(sx + 4, sy, "]", &bracket_style);
+
+            let Some(stack) = slot else {
+                renderer.draw_styled_text(sx + 1, sy, "   ", &frame);
+                continue;
+            };
+            let (glyph, style) = registry.get(&stack.item).map_or(('?', Style::new()), |item| (item.glyph, item.style));
+            let style = if selected { style.reverse() } else { style };
+            renderer.set_cell(sx + 1, sy, Cell::styled(glyph, &style));
+            let count = if stack.count > 1 { format!("{:>2}", stack.count.min(99)) } else { "  ".to_string() };
+            renderer.draw_text(sx + 2, sy, &count);
+        }
+
+        let rows = inventory.capacity().div_ceil(self.columns);
+        if let Some(stack) = inventory.slot(self.cursor) {
+            let (name, description) = registry.get(&stack.item).map_or((stack.item.as_str(), ""), |item| (&item.name, &item.description));
+            renderer.draw_styled_text(x, y + rows + 1, name, &Style::new().bold());
+            renderer.draw_text(x, y + rows + 2, description);
+        }
+    }
+}
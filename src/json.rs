@@ -0,0 +1,315 @@
+//! Minimal JSON reader for data files edited outside code
+//!
+//! Parses standard JSON (RFC 8259) into [`JsonValue`] trees without pulling in
+//! extra dependencies. Objects keep their keys in file order.
+
+use std::fmt;
+
+/// Deepest nesting of arrays and objects accepted, deeper input is an error instead of a stack overflow
+pub const MAX_DEPTH: usize = 128;
+
+/// A JSON value
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    /// `null`
+    Null,
+    /// `true` or `false`
+    Boolean(bool),
+    /// Any number, stored as a 64-bit float
+    Number(f64),
+    /// Quoted string
+    String(String),
+    /// Array of values
+    Array(Vec<JsonValue>),
+    /// Key/value pairs in file order
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    /// Parses JSON text holding a single value
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::json::JsonValue;
+    /// let value = JsonValue::parse(r#"{"name": "Elder", "lines": ["Hello", "Bye"]}"#).unwrap();
+    /// assert_eq!(value.get("name").and_then(JsonValue::as_str), Some("Elder"));
+    /// assert_eq!(value.get("lines").and_then(JsonValue::as_array).map(<[_]>::len), Some(2));
+    ///
+    /// // Nesting is limited to `MAX_DEPTH` levels
+    /// assert!(JsonValue::parse(&"[".repeat(100_000)).is_err());
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, JsonError> {
+        let mut parser = Parser { chars: text.chars().collect(), pos: 0, depth: 0 };
+        let value = parser.parse_value()?;
+        parser.skip_whitespace();
+        if parser.pos < parser.chars.len() {
+            return Err(parser.error("unexpected trailing characters"));
+        }
+        Ok(value)
+    }
+
+    /// Looks up a key of an `Object` value
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Returns the string content of a `String` value
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns a `Number` value
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns a `Number` value without a fractional part as an integer
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_f64().filter(|n| n.fract() == 0.0).map(|n| n as i64)
+    }
+
+    /// Returns a `Boolean` value
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the items of an `Array` value
+    pub fn as_array(&self) -> Option<&[JsonValue]> {
+        match self {
+            JsonValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the key/value pairs of an `Object` value
+    pub fn as_object(&self) -> Option<&[(String, JsonValue)]> {
+        match self {
+            JsonValue::Object(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the value is `null`
+    pub fn is_null(&self) -> bool {
+        matches!(self, JsonValue::Null)
+    }
+}
+
+/// Error produced while parsing JSON text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonError {
+    /// 1-based line number of the problem
+    pub line: usize,
+    /// 1-based column of the problem
+    pub column: usize,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for JsonError {}
+
+/// Character-level recursive descent parser
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    /// Arrays and objects currently open
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn error(&self, message: &str) -> JsonError {
+        let before = &self.chars[..self.pos.min(self.chars.len())];
+        let line = before.iter().filter(|c| **c == '\n').count() + 1;
+        let column = before.iter().rev().take_while(|c| **c != '\n').count() + 1;
+        JsonError { line, column, message: message.to_string() }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), JsonError> {
+        self.skip_whitespace();
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", expected)))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<JsonValue, JsonError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(open @ ('{' | '[')) => {
+                if self.depth == MAX_DEPTH {
+                    return Err(self.error(&format!("nested deeper than {} levels", MAX_DEPTH)));
+                }
+                self.depth += 1;
+                let value = if open == '{' { self.parse_object() } else { self.parse_array() };
+                self.depth -= 1;
+                value
+            },
+            Some('"') => self.parse_string().map(JsonValue::String),
+            Some('-' | '0'..='9') => self.parse_number(),
+            Some(_) => self.parse_literal(),
+            None => Err(self.error("missing value")),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<JsonValue, JsonError> {
+        self.pos += 1;
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(JsonValue::Object(entries));
+        }
+
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.error("expected a quoted key"));
+            }
+            let key = self.parse_string()?;
+            self.expect(':')?;
+            entries.push((key, self.parse_value()?));
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Object(entries));
+                },
+                _ => return Err(self.error("expected `,` or `}` in object")),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<JsonValue, JsonError> {
+        self.pos += 1;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(JsonValue::Array(items));
+        }
+
+        loop {
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(JsonValue::Array(items));
+                },
+                _ => return Err(self.error("expected `,` or `]` in array")),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, JsonError> {
+        self.pos += 1;
+        let mut out = String::new();
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            match c {
+                '"' => return Ok(out),
+                '\\' => {
+                    let escaped = self.peek().ok_or_else(|| self.error("unterminated escape"))?;
+                    self.pos += 1;
+                    match escaped {
+                        'n' => out.push('\n'),
+                        't' => out.push('\t'),
+                        'r' => out.push('\r'),
+                        'b' => out.push('\u{8}'),
+                        'f' => out.push('\u{c}'),
+                        '"' | '\\' | '/' => out.push(escaped),
+                        'u' => out.push(self.parse_unicode_escape()?),
+                        other => return Err(self.error(&format!("unknown escape `\\{}`", other))),
+                    }
+                },
+                c => out.push(c),
+            }
+        }
+
+        Err(self.error("unterminated string"))
+    }
+
+    /// Reads the hex digits after `\u`, joining surrogate pairs
+    fn parse_unicode_escape(&mut self) -> Result<char, JsonError> {
+        let high = self.parse_hex4()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error("invalid unicode escape"));
+        }
+
+        if self.chars.get(self.pos..self.pos + 2) != Some(&['\\', 'u'][..]) {
+            return Err(self.error("unpaired surrogate in unicode escape"));
+        }
+        self.pos += 2;
+        let low = self.parse_hex4()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error("unpaired surrogate in unicode escape"));
+        }
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, JsonError> {
+        let hex: String = self.chars.iter().skip(self.pos).take(4).collect();
+        let code = u32::from_str_radix(&hex, 16).map_err(|_| self.error(&format!("invalid unicode escape `\\u{}`", hex)))?;
+        self.pos += 4;
+        Ok(code)
+    }
+
+    fn parse_number(&mut self) -> Result<JsonValue, JsonError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')) {
+            self.pos += 1;
+        }
+        let token: String = self.chars[start..self.pos].iter().collect();
+        token.parse::<f64>().map(JsonValue::Number).map_err(|_| self.error(&format!("invalid number `{}`", token)))
+    }
+
+    fn parse_literal(&mut self) -> Result<JsonValue, JsonError> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        let token: String = self.chars[start..self.pos].iter().collect();
+        match token.as_str() {
+            "true" => Ok(JsonValue::Boolean(true)),
+            "false" => Ok(JsonValue::Boolean(false)),
+            "null" => Ok(JsonValue::Null),
+            _ => {
+                self.pos = start;
+                Err(self.error(&format!("unexpected `{}`", if token.is_empty() { self.peek().unwrap_or(' ').to_string() } else { token })))
+            },
+        }
+    }
+}
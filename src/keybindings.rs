@@ -0,0 +1,418 @@
+//! Remappable key bindings
+//!
+//! Maps named game actions ("jump", "move_left") to one or more [`Key`]s so
+//! players can remap controls through a config file without recompiling.
+//! Provides:
+//! - [`ActionMap`] for binding and querying actions
+//! - [`Combo`]s triggering an action from a chord or a sequence of presses, matched by [`ComboMatcher`]
+//! - TOML loading and saving with fallback to game defaults, read with the `toml` crate
+//! - [`KeyBindingError`] describing invalid config files
+//!
+//! # File format
+//! ```toml
+//! [bindings]
+//! jump = ["Space", "w"]
+//! move_left = ["Left", "a"]
+//! move_right = ["Right", "scan:32"]
+//! ```
+//! `scan:` keys name physical keys and stay in place on every keyboard layout,
+//! see [`Key::physical`].
+//!
+//! With the `serde` feature an [`ActionMap`] deserializes from the same format,
+//! so bindings can sit in a game's own config struct:
+//! ```
+//! # #[cfg(feature = "serde")] {
+//! use lonely_engine::{input::Key, keybindings::ActionMap};
+//!
+//! #[derive(serde::Deserialize)]
+//! struct Config {
+//!     controls: ActionMap,
+//! }
+//!
+//! let config: Config = toml::from_str("[controls.bindings]\njump = [\"Space\", \"scan:17\"]").unwrap();
+//! assert_eq!(config.controls.keys_for("jump"), &[Key::Space, Key::Scan(17)]);
+//! # }
+//! ```
+
+use std::{collections::{HashMap, HashSet}, fmt, fs, io, path::Path};
+use crate::{direction::Direction, input::Key, toml::{TomlDocument, TomlError, TomlValue}};
+
+/// Table holding bindings in config files
+const BINDINGS_TABLE: &str = "bindings";
+
+/// Problem found while loading a key binding file
+#[derive(Debug)]
+pub enum KeyBindingError {
+    /// The file could not be read or written
+    Io(io::Error),
+    /// The file is not valid TOML
+    Parse(TomlError),
+    /// An action's value is not an array of key names
+    InvalidValue {
+        /// Action with the bad value
+        action: String,
+    },
+    /// A key name is not recognized
+    UnknownKey {
+        /// Action the key was bound to
+        action: String,
+        /// The unrecognized key name
+        key: String,
+    },
+    /// The file binds an action the game does not define
+    UnknownAction(String),
+}
+
+impl fmt::Display for KeyBindingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyBindingError::Io(error) => write!(f, "could not access key bindings: {}", error),
+            KeyBindingError::Parse(error) => write!(f, "invalid key bindings file: {}", error),
+            KeyBindingError::InvalidValue { action } => write!(f, "action `{}` must be an array of key names", action),
+            KeyBindingError::UnknownKey { action, key } => write!(f, "action `{}` uses unknown key `{}`", action, key),
+            KeyBindingError::UnknownAction(action) => write!(f, "unknown action `{}`", action),
+        }
+    }
+}
+
+impl std::error::Error for KeyBindingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            KeyBindingError::Io(error) => Some(error),
+            KeyBindingError::Parse(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for KeyBindingError {
+    fn from(error: io::Error) -> Self {
+        KeyBindingError::Io(error)
+    }
+}
+
+impl From<TomlError> for KeyBindingError {
+    fn from(error: TomlError) -> Self {
+        KeyBindingError::Parse(error)
+    }
+}
+
+/// Mapping of action names to the keys that trigger them
+///
+/// # Example
+/// ```
+/// use std::collections::HashSet;
+/// use lonely_engine::{keybindings::ActionMap, input::Key};
+///
+/// let mut actions = ActionMap::new();
+/// actions.bind("jump", Key::Space);
+/// actions.bind("jump", Key::Char('w'));
+///
+/// let held: HashSet<Key> = [Key::Char('w')].into_iter().collect();
+/// assert!(actions.is_active("jump", &held));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActionMap {
+    #[cfg_attr(feature = "serde", serde(default))]
+    bindings: HashMap<String, Vec<Key>>,
+    /// Chords and sequences with their actions, in the order they were bound
+    #[cfg_attr(feature = "serde", serde(default))]
+    combos: Vec<(String, Combo)>,
+}
+
+/// Keys pressed together or one after another that trigger a single action
+///
+/// # Example
+/// ```
+/// use lonely_engine::{input::Key, keybindings::Combo};
+///
+/// let save = Combo::Chord(vec![Key::Ctrl, Key::Shift, Key::Char('s')]);
+/// // Down, Down+Right, Right+P with at most 0.4 seconds between steps
+/// let fireball = Combo::Sequence(vec![
+///     vec![Key::Down],
+///     vec![Key::Down, Key::Right],
+///     vec![Key::Right, Key::Char('p')],
+/// ], 0.4);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Combo {
+    /// Keys held at once, matched when the last of them is pressed
+    Chord(Vec<Key>),
+    /// Steps pressed in order, each within a number of seconds of the previous one
+    ///
+    /// A step of several keys is a chord, matched when one of its keys is pressed while the others are held.
+    Sequence(Vec<Vec<Key>>, f32),
+}
+
+impl ActionMap {
+    /// Creates an empty action map
+    pub fn new() -> Self {
+        Self { bindings: HashMap::new(), combos: Vec::new() }
+    }
+
+    /// Adds a key to an action, keeping existing keys
+    pub fn bind(&mut self, action: &str, key: Key) {
+        let keys = self.bindings.entry(action.to_string()).or_default();
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+
+    /// Replaces all keys of an action
+    pub fn set_keys(&mut self, action: &str, keys: Vec<Key>) {
+        self.bindings.insert(action.to_string(), keys);
+    }
+
+    /// Removes an action with its keys and combos
+    pub fn unbind(&mut self, action: &str) {
+        self.bindings.remove(action);
+        self.combos.retain(|(bound, _)| bound != action);
+    }
+
+    /// Adds a chord or sequence triggering an action, next to its keys
+    ///
+    /// # Notes
+    /// - Combos are matched by the engine every update and reported as `EngineEvent::ComboMatched`
+    /// - Combos belong to the game, they are not written to or read from binding files
+    pub fn bind_combo(&mut self, action: &str, combo: Combo) {
+        self.combos.push((action.to_string(), combo));
+    }
+
+    /// Chords and sequences with their actions, in the order they were bound
+    pub fn combos(&self) -> &[(String, Combo)] {
+        &self.combos
+    }
+
+    /// Keys bound to an action, empty when unbound
+    pub fn keys_for(&self, action: &str) -> &[Key] {
+        self.bindings.get(action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Names of all bound actions, sorted
+    pub fn actions(&self) -> Vec<&str> {
+        let mut actions: Vec<&str> = self.bindings.keys().map(String::as_str).collect();
+        actions.sort_unstable();
+        actions
+    }
+
+    /// Returns whether any key bound to `action` is held
+    pub fn is_active(&self, action: &str, active_keys: &HashSet<Key>) -> bool {
+        self.keys_for(action).iter().any(|key| active_keys.contains(key))
+    }
+
+    /// Direction held on the `up`, `down`, `left`, and `right` actions, diagonal when two are held
+    ///
+    /// # Notes
+    /// - Opposite actions cancel out, see [`Direction::from_held`]
+    ///
+    /// # Example
+    /// ```
+    /// use std::collections::HashSet;
+    /// use lonely_engine::{direction::Direction, input::Key, keybindings::ActionMap};
+    ///
+    /// let mut actions = ActionMap::new();
+    /// actions.set_keys("up", vec![Key::Char('w')]);
+    /// actions.set_keys("left", vec![Key::Char('a')]);
+    /// let held: HashSet<Key> = [Key::Char('w'), Key::Char('a')].into();
+    /// assert_eq!(actions.direction(&held), Some(Direction::UpLeft));
+    /// ```
+    pub fn direction(&self, active_keys: &HashSet<Key>) -> Option<Direction> {
+        Direction::from_held(
+            self.is_active("up", active_keys),
+            self.is_active("down", active_keys),
+            self.is_active("left", active_keys),
+            self.is_active("right", active_keys),
+        )
+    }
+
+    /// Actions with at least one held key
+    pub fn active_actions(&self, active_keys: &HashSet<Key>) -> Vec<&str> {
+        let mut actions: Vec<&str> = self.bindings
+            .iter()
+            .filter(|(_, keys)| keys.iter().any(|key| active_keys.contains(key)))
+            .map(|(action, _)| action.as_str())
+            .collect();
+        actions.sort_unstable();
+        actions
+    }
+
+    /// Parses bindings from TOML text
+    ///
+    /// # Returns
+    /// `Err` describing the first malformed line, value, or key name
+    pub fn from_toml(text: &str) -> Result<Self, KeyBindingError> {
+        let doc = TomlDocument::parse(text)?;
+        let mut map = Self::new();
+
+        for (action, value) in doc.table(BINDINGS_TABLE).unwrap_or(&[]) {
+            let names = value.as_array().ok_or_else(|| KeyBindingError::InvalidValue { action: action.clone() })?;
+            let mut keys = Vec::new();
+            for name in names {
+                let name = name.as_str().ok_or_else(|| KeyBindingError::InvalidValue { action: action.clone() })?;
+                let key = name.parse::<Key>().map_err(|_| KeyBindingError::UnknownKey {
+                    action: action.clone(),
+                    key: name.to_string(),
+                })?;
+                keys.push(key);
+            }
+            map.set_keys(action, keys);
+        }
+
+        Ok(map)
+    }
+
+    /// Formats bindings as TOML text, actions sorted by name
+    pub fn to_toml(&self) -> String {
+        let mut doc = TomlDocument::new();
+        for action in self.actions() {
+            let keys = self.keys_for(action).iter().map(|key| TomlValue::String(key.to_string())).collect();
+            doc.set(BINDINGS_TABLE, action, TomlValue::Array(keys));
+        }
+        doc.to_string()
+    }
+
+    /// Loads bindings from a TOML file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, KeyBindingError> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    /// Loads player bindings on top of the game's defaults
+    ///
+    /// # Arguments
+    /// * `path` - Player config file
+    /// * `defaults` - Bindings the game ships with
+    ///
+    /// # Returns
+    /// * `defaults` unchanged when the file does not exist
+    /// * Defaults overridden by every action present in the file
+    /// * `Err` when the file is unreadable, malformed, or binds unknown actions
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lonely_engine::{keybindings::ActionMap, input::Key};
+    ///
+    /// let mut defaults = ActionMap::new();
+    /// defaults.bind("jump", Key::Space);
+    ///
+    /// let actions = ActionMap::load_or_default("controls.toml", &defaults).unwrap_or_else(|error| {
+    ///     eprintln!("{}, using default controls", error);
+    ///     defaults.clone()
+    /// });
+    /// ```
+    pub fn load_or_default(path: impl AsRef<Path>, defaults: &ActionMap) -> Result<Self, KeyBindingError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(defaults.clone()),
+            Err(error) => return Err(error.into()),
+        };
+
+        let overrides = Self::from_toml(&text)?;
+        let mut map = defaults.clone();
+        for (action, keys) in overrides.bindings {
+            if !defaults.bindings.contains_key(&action) {
+                return Err(KeyBindingError::UnknownAction(action));
+            }
+            map.set_keys(&action, keys);
+        }
+
+        Ok(map)
+    }
+
+    /// Writes bindings to a TOML file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), KeyBindingError> {
+        fs::write(path, self.to_toml())?;
+        Ok(())
+    }
+}
+
+/// Progress through an action map's combos, fed the keys pressed every update
+///
+/// # Example
+/// ```
+/// use std::collections::HashSet;
+/// use lonely_engine::{input::Key, keybindings::{ActionMap, Combo, ComboMatcher}};
+///
+/// let mut actions = ActionMap::new();
+/// actions.bind_combo("dash", Combo::Sequence(vec![vec![Key::Right], vec![Key::Right]], 0.25));
+/// let mut matcher = ComboMatcher::new();
+/// let right: HashSet<Key> = [Key::Right].into();
+///
+/// assert!(matcher.update(&actions, &[Key::Right], &right, 0.0).is_empty());
+/// assert!(matcher.update(&actions, &[], &HashSet::new(), 0.1).is_empty());
+/// assert_eq!(matcher.update(&actions, &[Key::Right], &right, 0.1), vec!["dash".to_string()]);
+///
+/// // Too slow the second time
+/// matcher.update(&actions, &[Key::Right], &right, 0.0);
+/// matcher.update(&actions, &[], &HashSet::new(), 0.5);
+/// assert!(matcher.update(&actions, &[Key::Right], &right, 0.0).is_empty());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ComboMatcher {
+    /// Steps completed and seconds since the last one, by combo index
+    progress: Vec<(usize, f32)>,
+}
+
+impl ComboMatcher {
+    /// Creates a matcher with no progress
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advances every combo by one update
+    ///
+    /// # Arguments
+    /// * `actions` - Action map holding the combos
+    /// * `pressed` - Keys pressed since the previous update
+    /// * `held` - Keys held now, including the pressed ones
+    /// * `delta_time` - Seconds since the previous update
+    ///
+    /// # Returns
+    /// Actions of the combos completed, in the order they were bound
+    ///
+    /// # Notes
+    /// - Pressing a key that does not continue a sequence starts it over
+    pub fn update(&mut self, actions: &ActionMap, pressed: &[Key], held: &HashSet<Key>, delta_time: f32) -> Vec<String> {
+        if self.progress.len() != actions.combos.len() {
+            self.progress = vec![(0, 0.0); actions.combos.len()];
+        }
+        let step_pressed = |step: &[Key]| !step.is_empty() && step.iter().all(|key| held.contains(key)) && step.iter().any(|key| pressed.contains(key));
+
+        let mut matched = Vec::new();
+        for ((action, combo), (done, elapsed)) in actions.combos.iter().zip(&mut self.progress) {
+            match combo {
+                Combo::Chord(keys) => {
+                    if step_pressed(keys) {
+                        matched.push(action.clone());
+                    }
+                },
+                Combo::Sequence(steps, window) => {
+                    *elapsed += delta_time;
+                    if *done > 0 && *elapsed > *window {
+                        *done = 0;
+                    }
+                    if pressed.is_empty() {
+                        continue;
+                    }
+                    if steps.get(*done).is_some_and(|step| step_pressed(step)) {
+                        *done += 1;
+                    } else {
+                        *done = usize::from(steps.first().is_some_and(|step| step_pressed(step)));
+                    }
+                    *elapsed = 0.0;
+                    if *done == steps.len() && *done > 0 {
+                        matched.push(action.clone());
+                        *done = 0;
+                    }
+                },
+            }
+        }
+        matched
+    }
+
+    /// Forgets all progress, such as when a menu opens
+    pub fn reset(&mut self) {
+        self.progress.clear();
+    }
+}
@@ -0,0 +1,290 @@
+//! Levels connected by exits
+//!
+//! Provides:
+//! - [`Exit`] leading from a cell to another level's spawn point
+//! - [`Level`] bundling a tilemap, its exits, the objects it starts with, and its fog of war
+//! - [`Levels`] registry owned by the engine, with the tags of the objects
+//!   carried from level to level
+//!
+//! When a tracked object, such as the player, stands on an exit after an
+//! update's commands, the engine loads the target level: every object that is
+//! not tracked is despawned, the level's tilemap becomes the background and
+//! the camera's world, its objects are spawned, and tracked objects are placed
+//! on the spawn point. It then emits `EngineEvent::LevelChanged`.
+//! `EngineCommand::LoadLevel` loads a level directly, at its own spawn point.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{engine::Engine, game_object::GameObject, level::{Exit, Level}, tilemap::Tilemap};
+//!
+//! let mut engine = Engine::new(80, 24);
+//! engine.levels.track("player");
+//! engine.levels.add(
+//!     Level::new("town", Tilemap::from_str("##########\n#........+\n##########"))
+//!         .spawn(1, 1)
+//!         .with_exit(9, 1, Exit::new("forest", 1, 4)),
+//! );
+//! engine.levels.add(
+//!     Level::new("forest", Tilemap::from_str("TTTTTTTTTT\nT........T\nT........T\nT........T\n.........T"))
+//!         .with_exit(0, 4, Exit::new("town", 8, 1))
+//!         .with_object(GameObject::new(5, 2, 'w')),
+//! );
+//!
+//! let mut player = GameObject::new(0, 0, '@');
+//! player.tag = "player".to_string();
+//! engine.add_object(player);
+//! assert!(engine.load_level("town"));
+//! assert_eq!(engine.levels.current(), Some("town"));
+//! assert_eq!((engine.objects[0].x, engine.objects[0].y), (1, 1));
+//! ```
+
+use std::collections::HashMap;
+use crate::{fog::FogOfWar, game_object::GameObject, tilemap::Tilemap};
+
+/// Where an exit leads
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Exit {
+    /// Name of the level loaded
+    pub target: String,
+    /// Cell tracked objects are placed on in the target level
+    pub spawn: (usize, usize),
+}
+
+impl Exit {
+    /// Creates an exit to a level, arriving at a cell
+    pub fn new(target: &str, spawn_x: usize, spawn_y: usize) -> Self {
+        Self { target: target.to_string(), spawn: (spawn_x, spawn_y) }
+    }
+}
+
+/// A map with its exits and starting objects
+///
+/// # Example
+/// ```
+/// use lonely_engine::{level::{Exit, Level}, tilemap::Tilemap};
+///
+/// let cellar = Level::new("cellar", Tilemap::from_str("#####\n#...#\n##^##"))
+///     .spawn(2, 1)
+///     .with_exit(2, 2, Exit::new("house", 4, 3));
+/// assert_eq!(cellar.exit_at(2, 2).unwrap().target, "house");
+/// assert!(cellar.exit_at(1, 1).is_none());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Level {
+    /// Name exits and `EngineCommand::LoadLevel` refer to
+    pub name: String,
+    /// Background shown while the level is loaded
+    pub tilemap: Tilemap,
+    /// Cell tracked objects arrive on when the level is loaded by name
+    pub spawn: (usize, usize),
+    /// Exits by cell
+    pub exits: HashMap<(usize, usize), Exit>,
+    /// Objects spawned every time the level is loaded
+    pub objects: Vec<GameObject>,
+    /// Explored cells, moved to `Engine::fog` while the level is loaded and back when another one is
+    pub fog: Option<FogOfWar>,
+}
+
+impl Level {
+    /// Creates a level without exits or objects, spawning at the top-left cell
+    pub fn new(name: &str, tilemap: Tilemap) -> Self {
+        Self { name: name.to_string(), tilemap, spawn: (0, 0), exits: HashMap::new(), objects: Vec::new(), fog: None }
+    }
+
+    /// Sets the cell tracked objects arrive on when the level is loaded by name
+    pub fn spawn(mut self, x: usize, y: usize) -> Self {
+        self.spawn = (x, y);
+        self
+    }
+
+    /// Adds an exit on a cell, replacing one already there
+    pub fn with_exit(mut self, x: usize, y: usize, exit: Exit) -> Self {
+        self.exits.insert((x, y), exit);
+        self
+    }
+
+    /// Adds an object spawned when the level is loaded
+    pub fn with_object(mut self, obj: GameObject) -> Self {
+        self.objects.push(obj);
+        self
+    }
+
+    /// Covers the level with fog of war, see [`crate::fog`]
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{fog::FogOfWar, level::Level, tilemap::Tilemap};
+    ///
+    /// let map = Tilemap::from_str("#####\n#...#\n#####");
+    /// let crypt = Level::new("crypt", map.clone()).with_fog(FogOfWar::from_tilemap(&map, |cell| cell.ch == '#'));
+    /// assert!(crypt.fog.is_some());
+    /// ```
+    pub fn with_fog(mut self, fog: FogOfWar) -> Self {
+        self.fog = Some(fog);
+        self
+    }
+
+    /// Returns the exit on a cell
+    pub fn exit_at(&self, x: usize, y: usize) -> Option<&Exit> {
+        self.exits.get(&(x, y))
+    }
+}
+
+/// Levels known to the engine and which one is loaded
+#[derive(Debug, Clone, Default)]
+pub struct Levels {
+    levels: Vec<Level>,
+    /// Name of the loaded level
+    current: Option<String>,
+    /// Tags of the objects kept and repositioned across levels
+    tracked: Vec<String>,
+    /// Spawn cell of the last load, its exit is ignored until every tracked object has left it
+    arrival: Option<(usize, usize)>,
+}
+
+impl Levels {
+    /// Creates an empty registry tracking no objects
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a level, replacing one with the same name
+    pub fn add(&mut self, level: Level) {
+        match self.levels.iter_mut().find(|existing| existing.name == level.name) {
+            Some(existing) => *existing = level,
+            None => self.levels.push(level),
+        }
+    }
+
+    /// Unregisters a level by name
+    ///
+    /// # Notes
+    /// - Removing the loaded level leaves its objects and background in place, but its exits stop working
+    pub fn remove(&mut self, name: &str) -> Option<Level> {
+        let position = self.levels.iter().position(|level| level.name == name)?;
+        Some(self.levels.remove(position))
+    }
+
+    /// Returns a level by name
+    pub fn get(&self, name: &str) -> Option<&Level> {
+        self.levels.iter().find(|level| level.name == name)
+    }
+
+    /// Returns a level by name for editing, such as opening a locked exit
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Level> {
+        self.levels.iter_mut().find(|level| level.name == name)
+    }
+
+    /// Name of the loaded level, `None` before the first load
+    pub fn current(&self) -> Option<&str> {
+        self.current.as_deref()
+    }
+
+    /// The loaded level
+    pub fn current_level(&self) -> Option<&Level> {
+        self.current.as_deref().and_then(|name| self.get(name))
+    }
+
+    /// Keeps objects with a tag across levels and uses them to trigger exits
+    pub fn track(&mut self, tag: &str) {
+        if !self.is_tracked(tag) {
+            self.tracked.push(tag.to_string());
+        }
+    }
+
+    /// Stops keeping objects with a tag across levels
+    pub fn untrack(&mut self, tag: &str) {
+        self.tracked.retain(|existing| existing != tag);
+    }
+
+    /// Returns whether objects with a tag are kept across levels
+    pub fn is_tracked(&self, tag: &str) -> bool {
+        self.tracked.iter().any(|existing| existing == tag)
+    }
+
+    /// Iterates over levels in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &Level> {
+        self.levels.iter()
+    }
+
+    /// Number of registered levels
+    pub fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Returns whether no level is registered
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    /// Finds the exit a tracked object stands on in the loaded level
+    ///
+    /// # Notes
+    /// - Inactive objects do not trigger exits
+    /// - The exit on the cell objects arrived on is skipped until every tracked object has stepped off it,
+    ///   so a spawn point next to the way back does not bounce the player between levels
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{game_object::GameObject, level::{Exit, Level, Levels}, tilemap::Tilemap};
+    ///
+    /// let mut levels = Levels::new();
+    /// levels.track("player");
+    /// levels.add(Level::new("hall", Tilemap::new(10, 5)).with_exit(9, 2, Exit::new("yard", 0, 2)));
+    /// levels.add(Level::new("yard", Tilemap::new(10, 5)));
+    /// assert!(levels.enter("hall").is_some());
+    ///
+    /// let mut player = GameObject::new(9, 2, '@');
+    /// player.tag = "player".to_string();
+    /// assert!(levels.triggered_exit(&[GameObject::new(9, 2, 'r')]).is_none());
+    /// assert_eq!(levels.triggered_exit(&[player]).unwrap().target, "yard");
+    /// ```
+    pub fn triggered_exit(&mut self, objects: &[GameObject]) -> Option<Exit> {
+        let level = self.current_level()?;
+        let mut tracked = objects.iter().filter(|obj| obj.active && self.is_tracked(&obj.tag)).peekable();
+        tracked.peek()?;
+
+        let mut on_arrival = false;
+        let mut triggered = None;
+        for obj in tracked {
+            if Some((obj.x, obj.y)) == self.arrival {
+                on_arrival = true;
+            } else if triggered.is_none() {
+                triggered = level.exit_at(obj.x, obj.y).cloned();
+            }
+        }
+        if !on_arrival {
+            self.arrival = None;
+        }
+        triggered
+    }
+
+    /// Marks a level as loaded
+    ///
+    /// # Returns
+    /// The level, `None` when no level has the name
+    ///
+    /// # Notes
+    /// - Only updates the registry, `Engine::load_level` also swaps the map and objects
+    pub fn enter(&mut self, name: &str) -> Option<&Level> {
+        let level = self.levels.iter().find(|level| level.name == name)?;
+        self.current = Some(level.name.clone());
+        self.arrival = Some(level.spawn);
+        Some(level)
+    }
+
+    /// Sets the cell tracked objects arrived on, whose exit is skipped until they leave it
+    pub(crate) fn set_arrival(&mut self, cell: (usize, usize)) {
+        self.arrival = Some(cell);
+    }
+
+    /// Marks a level as loaded without an arrival, for restoring snapshots
+    ///
+    /// # Returns
+    /// The level, `None` when unloading or no level has the name
+    pub(crate) fn set_current(&mut self, name: Option<&str>) -> Option<&Level> {
+        self.current = name.map(str::to_string);
+        self.arrival = None;
+        self.current_level()
+    }
+}
@@ -1,10 +1,86 @@
+pub mod accessibility;
 pub mod audio;
+pub mod behavior;
+pub mod camera;
+pub mod color;
+pub mod components;
+pub mod crash;
+pub mod cursor;
+#[cfg(feature = "debug_menu")]
+pub mod debug_menu;
+pub mod diagnostics;
+pub mod dialogue;
+pub mod direction;
+pub mod effects;
 pub mod engine;
+pub mod equipment;
 pub mod event;
+pub mod flocking;
+pub mod fog;
+pub mod font;
 pub mod game_object;
+pub mod hash;
 pub mod helpers;
+pub mod highscores;
+#[cfg(feature = "image")]
+pub mod image;
 pub mod input;
+pub mod input_context;
+pub mod input_log;
+pub mod interpolation;
+pub mod inventory;
+pub mod json;
+pub mod keybindings;
+pub mod level;
+pub mod limits;
+pub mod lobby;
+pub mod locale;
+pub mod loot;
+pub mod markup;
+pub mod metadata;
+pub mod metrics;
+pub mod mixer;
+pub mod narration;
+pub mod occupancy;
+pub mod pacing;
+pub mod page;
+pub mod pause_menu;
+pub mod path_follower;
+pub mod post_fx;
+pub mod prediction;
+pub mod profiler;
+pub mod projectiles;
+pub mod quests;
+pub mod recorder;
 pub mod renderer;
+pub mod replication;
+pub mod resources;
+pub mod rng;
+pub mod score;
+pub mod screenshot;
+pub mod server;
+pub mod settings;
+pub mod shop;
+pub mod spatial_hash;
+pub mod sprite;
+pub mod stat_block;
+pub mod state_machine;
+pub mod stats;
+pub mod status;
+pub mod steering;
+pub mod style;
+pub mod task;
+pub mod template;
+pub mod terminal;
+pub mod testing;
+pub mod tilemap;
+pub mod time;
+pub mod toml;
+pub mod transition;
+pub mod turn;
+pub mod validation;
+pub mod weather;
+pub mod zone;
 
 pub fn greet () {
     println!("Hello, Lonely Engine!");
@@ -0,0 +1,85 @@
+//! Caps on objects, effects, and events with overflow policies
+//!
+//! A runaway spawner otherwise grows the object list until rendering and
+//! collision grind to a halt. With [`Limits`] set on the engine, spawns past a
+//! cap are rejected or make room by evicting existing objects, and the engine
+//! emits `EngineEvent::LimitReached` once per frame for each cap that was hit.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{engine::Engine, limits::{LimitPolicy, Limits}};
+//!
+//! let mut engine = Engine::new(80, 24);
+//! engine.set_limits(Limits::new()
+//!     .max_objects(500, LimitPolicy::EvictByPriority)
+//!     .max_effects(64, LimitPolicy::EvictOldest)
+//!     .max_events_per_frame(1000));
+//! ```
+
+/// What happens to a spawn past a cap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum LimitPolicy {
+    /// Drop the new spawn, keeping everything already alive
+    #[default]
+    Reject,
+    /// Remove the oldest entry to make room
+    EvictOldest,
+    /// Remove the lowest priority object, oldest first among equals, or drop the
+    /// spawn when it has a lower priority than everything alive
+    ///
+    /// Effects have no priority and are evicted oldest first.
+    EvictByPriority,
+}
+
+/// Cap that was hit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LimitKind {
+    /// Number of game objects
+    Objects,
+    /// Number of live effects
+    Effects,
+    /// Events dispatched in one frame, later events in the frame are dropped
+    Events,
+}
+
+/// Configured caps, `None` for unlimited
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Limits {
+    /// Most game objects alive at once
+    pub objects: Option<usize>,
+    /// What happens to spawns past the object cap
+    pub object_policy: LimitPolicy,
+    /// Most effects, such as floating text, alive at once
+    pub effects: Option<usize>,
+    /// What happens to effects past the effect cap
+    pub effect_policy: LimitPolicy,
+    /// Most events dispatched per frame
+    pub events_per_frame: Option<usize>,
+}
+
+impl Limits {
+    /// Creates limits with every cap disabled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Caps the number of game objects
+    pub fn max_objects(mut self, max: usize, policy: LimitPolicy) -> Self {
+        self.objects = Some(max);
+        self.object_policy = policy;
+        self
+    }
+
+    /// Caps the number of live effects
+    pub fn max_effects(mut self, max: usize, policy: LimitPolicy) -> Self {
+        self.effects = Some(max);
+        self.effect_policy = policy;
+        self
+    }
+
+    /// Caps the number of events dispatched per frame
+    pub fn max_events_per_frame(mut self, max: usize) -> Self {
+        self.events_per_frame = Some(max);
+        self
+    }
+}
@@ -0,0 +1,471 @@
+//! Multiplayer lobby with ready states and text chat
+//!
+//! Provides:
+//! - [`LobbyMessage`] protocol for joining, leaving, readying up, and chatting,
+//!   one line of text per message
+//! - [`Lobby`] state shared by every peer, built by applying the same messages
+//!   in the same order
+//! - [`LobbyScreen`] widget listing the players and chat, typing chat through a [`TextInput`]
+//!
+//! Local actions update the local lobby and return the message to send, the
+//! host relays every message it receives to the other peers, and each peer
+//! applies what arrives. Applying messages queues engine events for the next
+//! [`LobbyScreen::update`]:
+//! - `EngineEvent::PlayerJoined` with the player id and name
+//! - `EngineEvent::PlayerLeft` with the player id and name
+//! - `EngineEvent::PlayerReadyChanged` with the player id and whether they are ready
+//! - `EngineEvent::ChatReceived` with the player id and text
+//!
+//! # Format
+//! ```text
+//! join 2 Ada Lovelace
+//! ready 2 true
+//! chat 2 gl hf
+//! leave 2
+//! ```
+//!
+//! Line breaks and backslashes in names and chat are escaped as `\n`, `\r`,
+//! and `\\`, so a message never spans more than one line.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::lobby::{Lobby, LobbyMessage};
+//!
+//! let mut lobby = Lobby::new(1).min_players(2);
+//! let hello = lobby.join("Ada");
+//! assert_eq!(hello.to_text(), "join 1 Ada");
+//!
+//! // Messages from the other player, as received from the host
+//! lobby.apply(&LobbyMessage::parse("join 2 Grace").unwrap());
+//! lobby.apply(&LobbyMessage::parse("ready 2 true").unwrap());
+//! assert!(!lobby.all_ready());
+//!
+//! let ready = lobby.set_ready(true);
+//! assert_eq!(ready, LobbyMessage::Ready(1, true));
+//! assert!(lobby.all_ready());
+//! ```
+//!
+//! [`TextInput`]: crate::input::TextInput
+
+use std::{collections::VecDeque, io};
+use crate::{
+    color::Color,
+    engine::UpdateContext,
+    event::EngineEvent,
+    input::{InputEvent, Key, TextInput, TextInputStatus},
+    renderer::Renderer,
+    style::Style,
+};
+
+/// Chat lines kept by default, older ones scroll away
+pub const DEFAULT_CHAT_LINES: usize = 50;
+
+/// Longest chat message typed in a [`LobbyScreen`]
+pub const MAX_CHAT_LENGTH: usize = 120;
+
+/// Something a peer did in the lobby
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LobbyMessage {
+    /// A player entered with a display name
+    Join(u32, String),
+    /// A player left
+    Leave(u32),
+    /// A player changed their ready state
+    Ready(u32, bool),
+    /// A player said something
+    Chat(u32, String),
+}
+
+impl LobbyMessage {
+    /// Id of the player the message is from
+    pub fn player(&self) -> u32 {
+        match self {
+            LobbyMessage::Join(player, _) | LobbyMessage::Leave(player) | LobbyMessage::Ready(player, _) | LobbyMessage::Chat(player, _) => *player,
+        }
+    }
+
+    /// Encodes the message as one line of text, escaping line breaks in names and chat
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::lobby::LobbyMessage;
+    ///
+    /// let sneaky = LobbyMessage::Chat(2, "hi\nchat 1 I forfeit".into());
+    /// let line = sneaky.to_text();
+    /// assert_eq!(line, "chat 2 hi\\nchat 1 I forfeit");
+    /// assert_eq!(line.lines().count(), 1);
+    /// assert_eq!(LobbyMessage::parse(&line).unwrap(), sneaky);
+    ///
+    /// let path = LobbyMessage::Join(3, r"C:\games\new".into());
+    /// assert_eq!(LobbyMessage::parse(&path.to_text()).unwrap(), path);
+    /// ```
+    pub fn to_text(&self) -> String {
+        match self {
+            LobbyMessage::Join(player, name) => format!("join {} {}", player, escape(name)),
+            LobbyMessage::Leave(player) => format!("leave {}", player),
+            LobbyMessage::Ready(player, ready) => format!("ready {} {}", player, ready),
+            LobbyMessage::Chat(player, text) => format!("chat {} {}", player, escape(text)),
+        }
+    }
+
+    /// Decodes a line written by [`to_text`](Self::to_text)
+    ///
+    /// # Returns
+    /// `Err` with [`io::ErrorKind::InvalidData`] for unknown kinds or malformed fields
+    pub fn parse(line: &str) -> io::Result<Self> {
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, format!("invalid lobby message: {}", line));
+        let mut parts = line.trim_end_matches(['\r', '\n']).splitn(3, ' ');
+        let kind = parts.next().unwrap_or_default();
+        let player: u32 = parts.next().and_then(|player| player.parse().ok()).ok_or_else(invalid)?;
+        let rest = parts.next().unwrap_or_default();
+        match kind {
+            "join" => Ok(LobbyMessage::Join(player, unescape(rest))),
+            "leave" => Ok(LobbyMessage::Leave(player)),
+            "ready" => rest.parse().map(|ready| LobbyMessage::Ready(player, ready)).map_err(|_| invalid()),
+            "chat" => Ok(LobbyMessage::Chat(player, unescape(rest))),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// A player in the lobby
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LobbyPlayer {
+    /// Id shared by every peer, such as the network client id
+    pub id: u32,
+    /// Display name
+    pub name: String,
+    /// Whether the player is ready to start
+    pub ready: bool,
+}
+
+/// A line of chat
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatLine {
+    /// Id of the player who said it
+    pub player: u32,
+    /// Their name when they said it
+    pub name: String,
+    /// What they said
+    pub text: String,
+}
+
+/// Players and chat of a lobby, as seen by one peer
+#[derive(Debug, Clone)]
+pub struct Lobby {
+    /// Id of this peer's player
+    local: u32,
+    /// Players in join order
+    players: Vec<LobbyPlayer>,
+    chat: VecDeque<ChatLine>,
+    chat_lines: usize,
+    min_players: usize,
+    /// Events waiting for the next update to reach the event bus
+    pending: Vec<EngineEvent>,
+}
+
+impl Lobby {
+    /// Creates an empty lobby for the peer whose player has id `local`
+    pub fn new(local: u32) -> Self {
+        Self { local, players: Vec::new(), chat: VecDeque::new(), chat_lines: DEFAULT_CHAT_LINES, min_players: 1, pending: Vec::new() }
+    }
+
+    /// Sets how many players must be in the lobby before it counts as ready
+    pub fn min_players(mut self, players: usize) -> Self {
+        self.min_players = players;
+        self
+    }
+
+    /// Sets how many chat lines are kept
+    pub fn chat_lines(mut self, lines: usize) -> Self {
+        self.chat_lines = lines;
+        self
+    }
+
+    /// Id of this peer's player
+    pub fn local(&self) -> u32 {
+        self.local
+    }
+
+    /// Applies a message from any peer
+    ///
+    /// # Notes
+    /// - Joining twice renames the player, messages from players not in the lobby are ignored
+    /// - Line breaks in names and chat become spaces
+    pub fn apply(&mut self, message: &LobbyMessage) {
+        let message = &match message {
+            LobbyMessage::Join(id, name) => LobbyMessage::Join(*id, single_line(name)),
+            LobbyMessage::Chat(id, text) => LobbyMessage::Chat(*id, single_line(text)),
+            other => other.clone(),
+        };
+        match message {
+            LobbyMessage::Join(id, name) => match self.players.iter_mut().find(|player| player.id == *id) {
+                Some(player) => player.name.clone_from(name),
+                None => {
+                    self.players.push(LobbyPlayer { id: *id, name: name.clone(), ready: false });
+                    self.pending.push(EngineEvent::PlayerJoined(*id, name.clone()));
+                },
+            },
+            LobbyMessage::Leave(id) => {
+                if let Some(position) = self.players.iter().position(|player| player.id == *id) {
+                    let player = self.players.remove(position);
+                    self.pending.push(EngineEvent::PlayerLeft(player.id, player.name));
+                }
+            },
+            LobbyMessage::Ready(id, ready) => {
+                if let Some(player) = self.players.iter_mut().find(|player| player.id == *id)
+                    && player.ready != *ready
+                {
+                    player.ready = *ready;
+                    self.pending.push(EngineEvent::PlayerReadyChanged(*id, *ready));
+                }
+            },
+            LobbyMessage::Chat(id, text) => {
+                if let Some(player) = self.player(*id) {
+                    let line = ChatLine { player: *id, name: player.name.clone(), text: text.clone() };
+                    self.chat.push_back(line);
+                    while self.chat.len() > self.chat_lines {
+                        self.chat.pop_front();
+                    }
+                    self.pending.push(EngineEvent::ChatReceived(*id, text.clone()));
+                }
+            },
+        }
+    }
+
+    /// Adds the local player
+    ///
+    /// # Returns
+    /// The message to send to the other peers
+    ///
+    /// # Notes
+    /// - Line breaks in the name become spaces
+    pub fn join(&mut self, name: &str) -> LobbyMessage {
+        self.local_message(LobbyMessage::Join(self.local, single_line(name)))
+    }
+
+    /// Removes the local player
+    ///
+    /// # Returns
+    /// The message to send to the other peers
+    pub fn leave(&mut self) -> LobbyMessage {
+        self.local_message(LobbyMessage::Leave(self.local))
+    }
+
+    /// Changes the local player's ready state
+    ///
+    /// # Returns
+    /// The message to send to the other peers
+    pub fn set_ready(&mut self, ready: bool) -> LobbyMessage {
+        self.local_message(LobbyMessage::Ready(self.local, ready))
+    }
+
+    /// Says something as the local player
+    ///
+    /// # Returns
+    /// The message to send to the other peers
+    ///
+    /// # Notes
+    /// - Line breaks in the text become spaces
+    pub fn say(&mut self, text: &str) -> LobbyMessage {
+        self.local_message(LobbyMessage::Chat(self.local, single_line(text)))
+    }
+
+    /// Applies a message from the local player and hands it back for sending
+    fn local_message(&mut self, message: LobbyMessage) -> LobbyMessage {
+        self.apply(&message);
+        message
+    }
+
+    /// Players in join order
+    pub fn players(&self) -> &[LobbyPlayer] {
+        &self.players
+    }
+
+    /// Returns a player by id
+    pub fn player(&self, id: u32) -> Option<&LobbyPlayer> {
+        self.players.iter().find(|player| player.id == id)
+    }
+
+    /// Returns whether the local player is ready
+    pub fn is_ready(&self) -> bool {
+        self.player(self.local).is_some_and(|player| player.ready)
+    }
+
+    /// Returns whether enough players joined and every one of them is ready
+    pub fn all_ready(&self) -> bool {
+        self.players.len() >= self.min_players.max(1) && self.players.iter().all(|player| player.ready)
+    }
+
+    /// Chat lines, oldest first
+    pub fn chat(&self) -> impl Iterator<Item = &ChatLine> {
+        self.chat.iter()
+    }
+
+    /// Returns and clears the events queued by applied messages
+    pub fn take_events(&mut self) -> Vec<EngineEvent> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+/// Widget showing a lobby's players and chat
+///
+/// # Controls
+/// - Typing enters a chat message, Enter sends it and Backspace deletes
+/// - Enter without a message toggles ready
+/// - Escape clears the message, or leaves the lobby when it is empty
+///
+/// # Example
+/// ```
+/// use lonely_engine::{
+///     input::{InputEvent, Key},
+///     lobby::{Lobby, LobbyMessage, LobbyScreen},
+///     renderer::Renderer,
+/// };
+///
+/// let mut lobby = Lobby::new(1);
+/// lobby.join("Ada");
+/// let mut screen = LobbyScreen::new();
+///
+/// let events = [InputEvent::Char('h'), InputEvent::Char('i'), InputEvent::KeyDown(Key::Enter)];
+/// assert_eq!(screen.handle(&mut lobby, &events), Some(LobbyMessage::Chat(1, "hi".into())));
+/// assert_eq!(screen.handle(&mut lobby, &[InputEvent::KeyDown(Key::Enter)]), Some(LobbyMessage::Ready(1, true)));
+///
+/// let mut renderer = Renderer::new(40, 12);
+/// screen.draw(&mut renderer, &lobby, 0, 0, 40, 12);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LobbyScreen {
+    input: TextInput,
+}
+
+impl Default for LobbyScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LobbyScreen {
+    /// Creates a screen with an empty chat line
+    pub fn new() -> Self {
+        Self { input: TextInput::new(MAX_CHAT_LENGTH) }
+    }
+
+    /// Chat message typed so far
+    pub fn message(&self) -> &str {
+        self.input.text()
+    }
+
+    /// Handles this frame's input and emits the lobby's queued events on the event bus
+    ///
+    /// # Returns
+    /// The message to send to the other peers, if the local player did something
+    pub fn update(&mut self, lobby: &mut Lobby, ctx: &UpdateContext) -> Option<LobbyMessage> {
+        let message = self.handle(lobby, ctx.input_events);
+        for event in lobby.take_events() {
+            ctx.event_bus.emit(event);
+        }
+        message
+    }
+
+    /// Handles input without an engine
+    ///
+    /// # Returns
+    /// The first message produced, later events of the frame are ignored after it
+    pub fn handle(&mut self, lobby: &mut Lobby, events: &[InputEvent]) -> Option<LobbyMessage> {
+        for (index, event) in events.iter().enumerate() {
+            if self.input.text().trim().is_empty() {
+                match event {
+                    InputEvent::KeyDown(Key::Enter) => {
+                        self.input.clear();
+                        return Some(lobby.set_ready(!lobby.is_ready()));
+                    },
+                    InputEvent::KeyDown(Key::Esc) => return Some(lobby.leave()),
+                    _ => {},
+                }
+            }
+            match self.input.handle(&events[index..=index]) {
+                TextInputStatus::Editing => {},
+                TextInputStatus::Cancelled => self.input.clear(),
+                TextInputStatus::Submitted => {
+                    let text = self.input.text().trim().to_string();
+                    self.input.clear();
+                    return Some(lobby.say(&text));
+                },
+            }
+        }
+        None
+    }
+
+    /// Draws the player list on top, the chat below it, and the message being typed on the last row
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Top-left cell of the area
+    /// * `width`, `height` - Size of the area, chat lines are cut to the width
+    pub fn draw(&self, renderer: &mut Renderer, lobby: &Lobby, x: usize, y: usize, width: usize, height: usize) {
+        if height == 0 {
+            return;
+        }
+        let ready_style = Style::new().fg(Color::BRIGHT_GREEN).bold();
+        let waiting_style = Style::new().fg(Color::GREY);
+        let title = format!("PLAYERS {}/{}", lobby.players().iter().filter(|player| player.ready).count(), lobby.players().len());
+        renderer.draw_styled_text(x, y, &title, &Style::new().bold());
+
+        let list_rows = lobby.players().len().min(height.saturating_sub(3));
+        for (row, player) in lobby.players().iter().take(list_rows).enumerate() {
+            let (mark, style) = if player.ready { ("[x]", ready_style) } else { ("[ ]", waiting_style) };
+            renderer.draw_styled_text(x, y + 1 + row, mark, &style);
+            let name_style = if player.id == lobby.local() { Style::new().bold() } else { Style::new() };
+            renderer.draw_styled_text(x + 4, y + 1 + row, &clip(&player.name, width.saturating_sub(4)), &name_style);
+        }
+
+        let chat_top = y + 2 + list_rows;
+        let chat_rows = (y + height - 1).saturating_sub(chat_top);
+        let skip = lobby.chat.len().saturating_sub(chat_rows);
+        for (row, line) in lobby.chat().skip(skip).enumerate() {
+            let prefix = format!("{}: ", line.name);
+            renderer.draw_styled_text(x, chat_top + row, &clip(&prefix, width), &Style::new().fg(Color::BRIGHT_CYAN));
+            let column = prefix.chars().count().min(width);
+            renderer.draw_text(x + column, chat_top + row, &clip(&line.text, width - column));
+        }
+
+        let prompt = format!("> {}_", self.input.text());
+        let visible = prompt.chars().skip(prompt.chars().count().saturating_sub(width)).collect::<String>();
+        renderer.draw_text(x, y + height - 1, &visible);
+    }
+}
+
+/// First `width` characters of a text
+fn clip(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
+
+/// Escapes backslashes and line breaks so text stays on one protocol line
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\n', "\\n").replace('\r', "\\r")
+}
+
+/// Reverses [`escape`], keeping unknown escapes as written
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('\\') => out.push('\\'),
+            Some(other) => out.extend(['\\', other]),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+/// Replaces line breaks with spaces, for names and chat shown on one line
+fn single_line(text: &str) -> String {
+    text.replace(['\r', '\n'], " ")
+}
@@ -0,0 +1,374 @@
+//! Localized string tables
+//!
+//! Provides:
+//! - [`StringTable`] of one language, loaded from Fluent (`.ftl`) or JSON files
+//! - [`Locale`] selecting a language with fallbacks for missing strings
+//! - The [`t!`](crate::t) macro looking up strings in the installed locale
+//!
+//! Install a locale once with [`install`] and draw `t!("menu.start")` instead
+//! of English literals. Switching languages with `Engine::set_language` emits
+//! `EngineEvent::LanguageChanged` so cached text can be rebuilt.
+//!
+//! # File formats
+//! A Fluent subset: `key = value` messages, indented continuation lines,
+//! `#` comments, and `-term` messages referenced as `{ -term }`:
+//! ```text
+//! -brand = Lonely Quest
+//! menu.start = Start { -brand }
+//! greeting = Welcome back, { $name }!
+//! ```
+//! JSON objects, nested objects flattened with dots:
+//! ```json
+//! { "menu": { "start": "Start" }, "greeting": "Welcome back, {name}!" }
+//! ```
+//! Parameters are written `{ $name }` or `{name}`. Selectors and plurals are not supported.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{locale::{self, Locale, StringTable}, t};
+//!
+//! let english = StringTable::from_ftl("en", "menu.start = Start\ngreeting = Hi, { $name }!").unwrap();
+//! let spanish = StringTable::from_ftl("es", "menu.start = Empezar").unwrap();
+//! locale::install(Locale::new("es").fallback("en").with_table(english).with_table(spanish));
+//!
+//! assert_eq!(t!("menu.start"), "Empezar");
+//! assert_eq!(t!("greeting", name = "Ada"), "Hi, Ada!"); // Missing in Spanish, English fallback
+//! assert_eq!(t!("menu.quit"), "menu.quit"); // Missing everywhere, the key shows
+//! ```
+
+use std::{collections::HashMap, fmt, fs, io, path::Path, sync::RwLock};
+use crate::json::JsonValue;
+
+/// Strings of one language by key
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StringTable {
+    language: String,
+    strings: HashMap<String, String>,
+}
+
+impl StringTable {
+    /// Creates an empty table
+    ///
+    /// # Arguments
+    /// * `language` - Language code, such as `"en"` or `"pt-BR"`
+    pub fn new(language: &str) -> Self {
+        Self { language: language.to_string(), strings: HashMap::new() }
+    }
+
+    /// Adds or replaces a string
+    pub fn with(mut self, key: &str, text: &str) -> Self {
+        self.insert(key, text);
+        self
+    }
+
+    /// Adds or replaces a string
+    pub fn insert(&mut self, key: &str, text: &str) {
+        self.strings.insert(key.to_string(), text.to_string());
+    }
+
+    /// Language code
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Looks up a string without interpolation
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.strings.get(key).map(String::as_str)
+    }
+
+    /// Number of strings
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns whether the table holds no string
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Parses Fluent text, see the [module documentation](self) for the supported subset
+    ///
+    /// # Returns
+    /// `InvalidData` naming the first line that is neither a message, a continuation, nor a comment
+    pub fn from_ftl(language: &str, text: &str) -> io::Result<Self> {
+        let mut table = Self::new(language);
+        let mut current: Option<(String, String)> = None;
+        for (number, line) in text.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if line.starts_with([' ', '\t']) {
+                let Some((_, value)) = current.as_mut() else {
+                    return Err(invalid_line(number, line));
+                };
+                if !value.is_empty() {
+                    value.push('\n');
+                }
+                value.push_str(trimmed);
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or_else(|| invalid_line(number, line))?;
+            let key = key.trim();
+            let valid_key = key.trim_start_matches('-').starts_with(|c: char| c.is_ascii_alphabetic())
+                && key.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+            if !valid_key {
+                return Err(invalid_line(number, line));
+            }
+            if let Some((key, value)) = current.replace((key.to_string(), value.trim().to_string())) {
+                table.strings.insert(key, value);
+            }
+        }
+        if let Some((key, value)) = current {
+            table.strings.insert(key, value);
+        }
+        Ok(table)
+    }
+
+    /// Parses a JSON object of strings, nested objects becoming dotted keys
+    ///
+    /// # Returns
+    /// `InvalidData` for malformed JSON or a value that is neither a string nor an object
+    pub fn from_json(language: &str, text: &str) -> io::Result<Self> {
+        let root = JsonValue::parse(text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))?;
+        let mut table = Self::new(language);
+        table.flatten("", &root)?;
+        Ok(table)
+    }
+
+    /// Loads a `.ftl` or `.json` file
+    ///
+    /// # Arguments
+    /// * `language` - Language code of the strings
+    /// * `path` - File whose extension selects the format
+    pub fn load(language: &str, path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("ftl") => Self::from_ftl(language, &text),
+            Some("json") => Self::from_json(language, &text),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("`{}` is not a .ftl or .json file", path.display()))),
+        }
+    }
+
+    /// Adds the strings of a JSON value under a key prefix
+    fn flatten(&mut self, prefix: &str, value: &JsonValue) -> io::Result<()> {
+        match value {
+            JsonValue::String(text) => {
+                self.strings.insert(prefix.to_string(), text.clone());
+            },
+            JsonValue::Object(entries) => {
+                for (key, value) in entries {
+                    let key = if prefix.is_empty() { key.clone() } else { format!("{prefix}.{key}") };
+                    self.flatten(&key, value)?;
+                }
+            },
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("string `{prefix}` must be text or an object"))),
+        }
+        Ok(())
+    }
+}
+
+/// Error for a line of a Fluent file that cannot be read
+fn invalid_line(number: usize, line: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("invalid string table line {}: `{}`", number + 1, line))
+}
+
+/// String tables with a selected language and fallbacks
+///
+/// # Notes
+/// - Lookups try the selected language, then each fallback in order, then return the key itself
+///   so missing strings are easy to spot on screen
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Locale {
+    tables: Vec<StringTable>,
+    language: String,
+    fallbacks: Vec<String>,
+}
+
+impl Locale {
+    /// Creates a locale without strings
+    ///
+    /// # Arguments
+    /// * `language` - Language selected at start
+    pub fn new(language: &str) -> Self {
+        Self { tables: Vec::new(), language: language.to_string(), fallbacks: Vec::new() }
+    }
+
+    /// Adds a language tried when a string is missing, after the ones added before
+    pub fn fallback(mut self, language: &str) -> Self {
+        self.fallbacks.push(language.to_string());
+        self
+    }
+
+    /// Adds a table, see [`add_table`](Self::add_table)
+    pub fn with_table(mut self, table: StringTable) -> Self {
+        self.add_table(table);
+        self
+    }
+
+    /// Adds a table, merging its strings into a table of the same language
+    pub fn add_table(&mut self, table: StringTable) {
+        match self.tables.iter_mut().find(|existing| existing.language == table.language) {
+            Some(existing) => existing.strings.extend(table.strings),
+            None => self.tables.push(table),
+        }
+    }
+
+    /// Loads every `<language>.ftl` and `<language>.json` file of a directory
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lonely_engine::locale::{self, Locale};
+    ///
+    /// // assets/lang/en.ftl, assets/lang/de.json, ...
+    /// let mut locale = Locale::new("de").fallback("en");
+    /// locale.load_dir("assets/lang").expect("Could not load translations");
+    /// locale::install(locale);
+    /// ```
+    pub fn load_dir(&mut self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let mut paths: Vec<_> = fs::read_dir(dir)?.map(|entry| entry.map(|entry| entry.path())).collect::<io::Result<_>>()?;
+        paths.sort();
+        for path in paths {
+            let supported = matches!(path.extension().and_then(|extension| extension.to_str()), Some("ftl" | "json"));
+            if let (true, Some(language)) = (supported, path.file_stem().and_then(|stem| stem.to_str())) {
+                self.add_table(StringTable::load(language, &path)?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Selected language
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Selects a language
+    ///
+    /// # Returns
+    /// `false`, leaving the selection unchanged, when no table of that language was added
+    pub fn set_language(&mut self, language: &str) -> bool {
+        if !self.tables.iter().any(|table| table.language == language) {
+            return false;
+        }
+        self.language = language.to_string();
+        true
+    }
+
+    /// Languages with a table, in the order they were added
+    pub fn languages(&self) -> impl Iterator<Item = &str> {
+        self.tables.iter().map(|table| table.language.as_str())
+    }
+
+    /// Looks up a string without interpolation, following the fallbacks
+    pub fn get(&self, key: &str) -> Option<&str> {
+        std::iter::once(&self.language)
+            .chain(&self.fallbacks)
+            .filter_map(|language| self.tables.iter().find(|table| &table.language == language))
+            .find_map(|table| table.get(key))
+    }
+
+    /// Looks up a string and fills in its parameters
+    ///
+    /// # Arguments
+    /// * `key` - String key, returned as is when missing
+    /// * `args` - Values of `{ $name }` parameters, unknown parameters are left in place
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::locale::{Locale, StringTable};
+    ///
+    /// let locale = Locale::new("en").with_table(StringTable::new("en").with("coins", "{ $count } coins"));
+    /// assert_eq!(locale.translate("coins", &[("count", &12)]), "12 coins");
+    /// ```
+    pub fn translate(&self, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        match self.get(key) {
+            Some(text) => self.interpolate(text, args),
+            None => key.to_string(),
+        }
+    }
+
+    /// Replaces `{ $name }`, `{name}`, and `{ -term }` placeables
+    fn interpolate(&self, text: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find('{') {
+            result.push_str(&rest[..start]);
+            let Some(length) = rest[start..].find('}') else {
+                rest = &rest[start..];
+                break;
+            };
+            let placeable = &rest[start..=start + length];
+            let name = placeable[1..placeable.len() - 1].trim();
+            let value = match name.strip_prefix('-') {
+                Some(_) => self.get(name).map(str::to_string),
+                None => {
+                    let name = name.strip_prefix('$').unwrap_or(name);
+                    args.iter().find(|(arg, _)| *arg == name).map(|(_, value)| value.to_string())
+                },
+            };
+            result.push_str(value.as_deref().unwrap_or(placeable));
+            rest = &rest[start + length + 1..];
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+/// Locale used by [`t!`](crate::t) and [`translate`]
+static INSTALLED: RwLock<Option<Locale>> = RwLock::new(None);
+
+/// Makes a locale the one used by [`t!`](crate::t), replacing any installed before
+pub fn install(locale: Locale) {
+    *INSTALLED.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(locale);
+}
+
+/// Runs a function on the installed locale, `None` when none is installed
+pub fn with_locale<R>(f: impl FnOnce(&Locale) -> R) -> Option<R> {
+    INSTALLED.read().unwrap_or_else(|poisoned| poisoned.into_inner()).as_ref().map(f)
+}
+
+/// Selects a language of the installed locale
+///
+/// # Returns
+/// `false` when no locale is installed or it has no table of that language
+///
+/// # Notes
+/// - Prefer `Engine::set_language`, which also emits `EngineEvent::LanguageChanged`
+pub fn set_language(language: &str) -> bool {
+    INSTALLED.write().unwrap_or_else(|poisoned| poisoned.into_inner()).as_mut().is_some_and(|locale| locale.set_language(language))
+}
+
+/// Selected language of the installed locale
+pub fn language() -> Option<String> {
+    with_locale(|locale| locale.language().to_string())
+}
+
+/// Looks up a string in the installed locale, see [`Locale::translate`]
+///
+/// # Returns
+/// The key itself when no locale is installed or the string is missing
+pub fn translate(key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    with_locale(|locale| locale.translate(key, args)).unwrap_or_else(|| key.to_string())
+}
+
+/// Looks up a string in the installed locale, filling in named parameters
+///
+/// # Example
+/// ```
+/// use lonely_engine::t;
+///
+/// let hp = 7;
+/// let title = t!("menu.title");
+/// let status = t!("status.hp", current = hp, max = 10);
+/// ```
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::locale::translate($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::locale::translate($key, &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+])
+    };
+}
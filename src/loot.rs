@@ -0,0 +1,264 @@
+//! Weighted random tables for loot drops and spawns
+//!
+//! Provides [`LootTable`], choosing entries by weight with the engine [`Rng`]:
+//! - Weighted entries, including "nothing" entries for chance-based drops
+//! - Nested tables, so a "rare" entry can roll on its own table
+//! - Guaranteed drops handed out on every roll
+//!
+//! Tables of item names load from JSON with [`LootTable::from_json`]. With the
+//! `serde` feature any table can be loaded through a serde format crate instead.
+//!
+//! # File format
+//! ```json
+//! {
+//!     "rolls": 2,
+//!     "guaranteed": ["gold"],
+//!     "entries": [
+//!         { "item": "potion", "weight": 6 },
+//!         { "weight": 3 },
+//!         { "weight": 1, "table": { "entries": [{ "item": "ruby" }, { "item": "sapphire" }] } }
+//!     ]
+//! }
+//! ```
+//! `rolls` and `weight` default to `1`, an entry without `item` or `table` drops nothing.
+
+use std::{fs, io, path::Path};
+use crate::{json::JsonValue, rng::Rng};
+
+/// What a table entry gives when chosen
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LootEntry<T> {
+    /// A single item
+    Item(T),
+    /// Rolls on another table
+    Table(LootTable<T>),
+    /// No drop
+    Nothing,
+}
+
+/// Entry with its chance of being chosen relative to the others
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WeightedEntry<T> {
+    /// Relative chance, entries with weight `0` are never chosen
+    pub weight: u32,
+    /// What the entry gives
+    pub entry: LootEntry<T>,
+}
+
+/// Weighted random table
+///
+/// # Example
+/// ```
+/// use lonely_engine::{loot::LootTable, rng::Rng};
+///
+/// let mut gems = LootTable::new();
+/// gems.add("ruby", 1);
+/// gems.add("sapphire", 1);
+///
+/// let mut goblin = LootTable::new();
+/// goblin.guarantee("gold");
+/// goblin.add("potion", 6);
+/// goblin.add_nothing(3);
+/// goblin.add_table(gems, 1);
+///
+/// let mut rng = Rng::new(7);
+/// let drops = goblin.roll(&mut rng);
+/// assert_eq!(drops[0], &"gold");
+/// assert!(drops.len() <= 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LootTable<T> {
+    entries: Vec<WeightedEntry<T>>,
+    guaranteed: Vec<LootEntry<T>>,
+    rolls: u32,
+}
+
+impl<T> Default for LootTable<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> LootTable<T> {
+    /// Creates an empty table rolled once
+    pub fn new() -> Self {
+        Self { entries: Vec::new(), guaranteed: Vec::new(), rolls: 1 }
+    }
+
+    /// Adds an item with a relative weight
+    pub fn add(&mut self, item: T, weight: u32) {
+        self.entries.push(WeightedEntry { weight, entry: LootEntry::Item(item) });
+    }
+
+    /// Adds a nested table with a relative weight, rolled fully when chosen
+    pub fn add_table(&mut self, table: LootTable<T>, weight: u32) {
+        self.entries.push(WeightedEntry { weight, entry: LootEntry::Table(table) });
+    }
+
+    /// Adds a chance of dropping nothing
+    pub fn add_nothing(&mut self, weight: u32) {
+        self.entries.push(WeightedEntry { weight, entry: LootEntry::Nothing });
+    }
+
+    /// Adds an item dropped on every roll of the table
+    pub fn guarantee(&mut self, item: T) {
+        self.guaranteed.push(LootEntry::Item(item));
+    }
+
+    /// Adds a nested table rolled on every roll of the table
+    pub fn guarantee_table(&mut self, table: LootTable<T>) {
+        self.guaranteed.push(LootEntry::Table(table));
+    }
+
+    /// Sets how many weighted picks a roll makes
+    pub fn set_rolls(&mut self, rolls: u32) {
+        self.rolls = rolls;
+    }
+
+    /// Weighted picks per roll
+    pub fn rolls(&self) -> u32 {
+        self.rolls
+    }
+
+    /// Weighted entries in insertion order
+    pub fn entries(&self) -> &[WeightedEntry<T>] {
+        &self.entries
+    }
+
+    /// Sum of all entry weights
+    pub fn total_weight(&self) -> u64 {
+        self.entries.iter().map(|entry| u64::from(entry.weight)).sum()
+    }
+
+    /// Returns whether the table can never drop anything
+    pub fn is_empty(&self) -> bool {
+        self.guaranteed.is_empty() && self.entries.iter().all(|entry| entry.weight == 0 || matches!(entry.entry, LootEntry::Nothing))
+    }
+
+    /// Chooses one weighted entry, resolving nested tables to a single item
+    ///
+    /// # Notes
+    /// - Guaranteed drops and roll counts are ignored, use for "what spawns here" tables
+    ///
+    /// # Returns
+    /// `None` when a "nothing" entry is chosen or the table has no weight
+    pub fn pick(&self, rng: &mut Rng) -> Option<&T> {
+        match self.choose(rng)? {
+            LootEntry::Item(item) => Some(item),
+            LootEntry::Table(table) => table.pick(rng),
+            LootEntry::Nothing => None,
+        }
+    }
+
+    /// Rolls the table: guaranteed drops first, then one weighted pick per roll
+    ///
+    /// # Arguments
+    /// * `rng` - Generator to sample from, usually `ctx.rng` or `engine.rng` so drops replay deterministically
+    ///
+    /// # Notes
+    /// - Nested tables chosen or guaranteed contribute their full roll
+    pub fn roll(&self, rng: &mut Rng) -> Vec<&T> {
+        let mut drops = Vec::new();
+        self.roll_into(rng, &mut drops);
+        drops
+    }
+
+    fn roll_into<'a>(&'a self, rng: &mut Rng, drops: &mut Vec<&'a T>) {
+        for entry in &self.guaranteed {
+            Self::resolve(entry, rng, drops);
+        }
+        for _ in 0..self.rolls {
+            if let Some(entry) = self.choose(rng) {
+                Self::resolve(entry, rng, drops);
+            }
+        }
+    }
+
+    fn resolve<'a>(entry: &'a LootEntry<T>, rng: &mut Rng, drops: &mut Vec<&'a T>) {
+        match entry {
+            LootEntry::Item(item) => drops.push(item),
+            LootEntry::Table(table) => table.roll_into(rng, drops),
+            LootEntry::Nothing => {},
+        }
+    }
+
+    /// Weighted choice among the entries, `None` when the total weight is zero
+    fn choose(&self, rng: &mut Rng) -> Option<&LootEntry<T>> {
+        let total = self.total_weight();
+        if total == 0 {
+            return None;
+        }
+
+        let mut target = rng.next_u64() % total;
+        for entry in &self.entries {
+            let weight = u64::from(entry.weight);
+            if target < weight {
+                return Some(&entry.entry);
+            }
+            target -= weight;
+        }
+        None
+    }
+}
+
+impl LootTable<String> {
+    /// Parses a table of item names in the JSON format described in the [module docs](self)
+    ///
+    /// # Returns
+    /// `Err` with [`io::ErrorKind::InvalidData`] for invalid JSON or malformed entries
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::{loot::LootTable, rng::Rng};
+    /// let chest = LootTable::from_json(r#"{ "entries": [{ "item": "key", "weight": 1 }] }"#).unwrap();
+    /// assert_eq!(chest.pick(&mut Rng::new(1)).map(String::as_str), Some("key"));
+    /// ```
+    pub fn from_json(text: &str) -> io::Result<Self> {
+        let root = JsonValue::parse(text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        Self::from_json_value(&root)
+    }
+
+    /// Loads a JSON table of item names from a file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    fn from_json_value(value: &JsonValue) -> io::Result<Self> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+        if value.as_object().is_none() {
+            return Err(invalid("loot table must be an object"));
+        }
+
+        let mut table = Self::new();
+        if let Some(rolls) = value.get("rolls") {
+            let rolls = rolls.as_i64().and_then(|rolls| u32::try_from(rolls).ok()).ok_or_else(|| invalid("`rolls` must be a non-negative integer"))?;
+            table.set_rolls(rolls);
+        }
+
+        for entry in value.get("entries").and_then(JsonValue::as_array).unwrap_or(&[]) {
+            let weight = match entry.get("weight") {
+                Some(weight) => weight.as_i64().and_then(|weight| u32::try_from(weight).ok()).ok_or_else(|| invalid("`weight` must be a non-negative integer"))?,
+                None => 1,
+            };
+            let entry = match (entry.get("item"), entry.get("table")) {
+                (Some(item), None) => LootEntry::Item(item.as_str().ok_or_else(|| invalid("`item` must be a string"))?.to_string()),
+                (None, Some(nested)) => LootEntry::Table(Self::from_json_value(nested)?),
+                (None, None) => LootEntry::Nothing,
+                (Some(_), Some(_)) => return Err(invalid("an entry has both `item` and `table`")),
+            };
+            table.entries.push(WeightedEntry { weight, entry });
+        }
+
+        for guaranteed in value.get("guaranteed").and_then(JsonValue::as_array).unwrap_or(&[]) {
+            match (guaranteed.as_str(), guaranteed.get("table")) {
+                (Some(item), _) => table.guarantee(item.to_string()),
+                (None, Some(nested)) => table.guarantee_table(Self::from_json_value(nested)?),
+                (None, None) => return Err(invalid("guaranteed drops must be item names or `{ \"table\": ... }`")),
+            }
+        }
+        Ok(table)
+    }
+}
@@ -0,0 +1,144 @@
+//! Inline markup for colored text
+//!
+//! Parses strings such as `"You found [yellow]5 gold[/yellow]!"` into styled
+//! [`Span`]s, so UI code styles words without splitting strings by hand.
+//! [`Renderer::draw_markup`] and [`Page::markup`] take markup directly.
+//!
+//! # Tags
+//! - `[red]`, `[bright_cyan]`, `[#ff8000]`, `[208]` - foreground color, see [`Color::from_name`]
+//! - `[fg=red]` and `[bg=blue]` - foreground and background color
+//! - `[bold]`, `[dim]`, `[italic]`, `[underline]`, `[blink]`, `[reverse]` - attributes
+//! - `[/red]` closes the latest open `[red]`, `[/]` closes the latest open tag
+//! - `[[` is a literal `[`, unknown tags are kept as text
+//!
+//! Tags nest, so `[bold]Warning: [red]hot[/red][/bold]` draws "hot" bold and red.
+//!
+//! [`Renderer::draw_markup`]: crate::renderer::Renderer::draw_markup
+//! [`Page::markup`]: crate::page::Page::markup
+
+use crate::{color::Color, page::Span, style::Style};
+
+/// Parses markup into spans in the default style
+///
+/// # Example
+/// ```
+/// use lonely_engine::{color::Color, markup, style::Style};
+///
+/// let spans = markup::parse("You found [yellow]5 gold[/yellow]!");
+/// assert_eq!(spans, vec![
+///     ("You found ".to_string(), Style::new()),
+///     ("5 gold".to_string(), Style::new().fg(Color::YELLOW)),
+///     ("!".to_string(), Style::new()),
+/// ]);
+/// ```
+pub fn parse(text: &str) -> Vec<Span> {
+    parse_with(text, Style::new())
+}
+
+/// Parses markup into spans, with text outside tags in a base style
+pub fn parse_with(text: &str, base: Style) -> Vec<Span> {
+    let mut spans: Vec<Span> = Vec::new();
+    // Open tags with the style in effect inside each
+    let mut open: Vec<(String, Style)> = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let style = open.last().map_or(base, |(_, style)| *style);
+        let Some(start) = rest.find('[') else {
+            push_text(&mut spans, rest, style);
+            break;
+        };
+        push_text(&mut spans, &rest[..start], style);
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("[[") {
+            push_text(&mut spans, "[", style);
+            rest = after;
+            continue;
+        }
+        let Some(end) = rest.find(']') else {
+            push_text(&mut spans, rest, style);
+            break;
+        };
+        let tag = &rest[1..end];
+        let handled = match tag.strip_prefix('/') {
+            Some("") => open.pop().is_some(),
+            Some(name) => match open.iter().rposition(|(open_name, _)| open_name == name) {
+                Some(position) => {
+                    open.remove(position);
+                    // Tags opened inside the closed one keep their own changes on top of the outer style
+                    rebuild(&mut open, base);
+                    true
+                },
+                None => false,
+            },
+            None => match apply_tag(style, tag) {
+                Some(inner) => {
+                    open.push((tag.to_string(), inner));
+                    true
+                },
+                None => false,
+            },
+        };
+        if !handled {
+            push_text(&mut spans, &rest[..=end], style);
+        }
+        rest = &rest[end + 1..];
+    }
+    spans
+}
+
+/// Text of markup without its tags
+///
+/// # Example
+/// ```
+/// # use lonely_engine::markup;
+/// assert_eq!(markup::strip("[bold]HP[/bold] [red]3[/]/10 [[x]"), "HP 3/10 [x]");
+/// ```
+pub fn strip(text: &str) -> String {
+    parse(text).into_iter().map(|(text, _)| text).collect()
+}
+
+/// Number of characters markup takes on screen
+pub fn width(text: &str) -> usize {
+    parse(text).iter().map(|(text, _)| text.chars().count()).sum()
+}
+
+/// Appends text, extending the last span when it has the same style
+fn push_text(spans: &mut Vec<Span>, text: &str, style: Style) {
+    if text.is_empty() {
+        return;
+    }
+    match spans.last_mut() {
+        Some((last, last_style)) if *last_style == style => last.push_str(text),
+        _ => spans.push((text.to_string(), style)),
+    }
+}
+
+/// Style inside a tag, `None` for unknown tags
+fn apply_tag(style: Style, tag: &str) -> Option<Style> {
+    if let Some(color) = tag.strip_prefix("fg=") {
+        return Color::from_name(color).map(|color| style.fg(color));
+    }
+    if let Some(color) = tag.strip_prefix("bg=") {
+        return Color::from_name(color).map(|color| style.bg(color));
+    }
+    match tag {
+        "bold" => Some(style.bold()),
+        "dim" => Some(style.dim()),
+        "italic" => Some(style.italic()),
+        "underline" => Some(style.underline()),
+        "blink" => Some(style.blink()),
+        "reverse" => Some(style.reverse()),
+        _ => Color::from_name(tag).map(|color| style.fg(color)),
+    }
+}
+
+/// Recomputes the styles of open tags after one in the middle was closed
+fn rebuild(open: &mut [(String, Style)], base: Style) {
+    let mut style = base;
+    for (tag, inner) in open.iter_mut() {
+        style = apply_tag(style, tag).unwrap_or(style);
+        *inner = style;
+    }
+}
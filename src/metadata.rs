@@ -0,0 +1,221 @@
+//! Free-form key-value data attached to game objects
+//!
+//! Every [`GameObject`](crate::game_object::GameObject) carries a `metadata`
+//! map of [`Value`]s, so level files and scripts can attach a door's target
+//! level, an NPC's dialogue id, or a chest's loot table without a Rust type for
+//! each. Metadata is part of snapshots and the world hash like any other field.
+//!
+//! Maps are read from level editor exports with [`from_json`] and written to and
+//! read from TOML tables with [`write_toml`] and [`read_toml`].
+//!
+//! # Example
+//! ```
+//! use lonely_engine::game_object::GameObject;
+//!
+//! let door = GameObject::new(12, 3, '+')
+//!     .with_meta("target", "cellar")
+//!     .with_meta("spawn_x", 4)
+//!     .with_meta("locked", true);
+//!
+//! assert_eq!(door.meta_str("target"), Some("cellar"));
+//! assert_eq!(door.meta_number("spawn_x"), Some(4.0));
+//! assert_eq!(door.meta_bool("locked"), Some(true));
+//! ```
+
+use std::{collections::HashMap, fmt};
+use crate::{json::JsonValue, toml::{TomlDocument, TomlValue}};
+
+/// A metadata value: text, a number, or a flag
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Value {
+    /// Text, such as an id or a name
+    String(String),
+    /// Any number, integers included
+    Number(f64),
+    /// `true` or `false`
+    Bool(bool),
+}
+
+impl Value {
+    /// Text, `None` for numbers and flags
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// Number, `None` for text and flags
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    /// Number as an integer, `None` when it has a fraction or is not a number
+    pub fn as_i64(&self) -> Option<i64> {
+        self.as_number().filter(|number| number.fract() == 0.0).map(|number| number as i64)
+    }
+
+    /// Flag, `None` for text and numbers
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(flag) => Some(*flag),
+            _ => None,
+        }
+    }
+
+    /// Converts a TOML value, `None` for arrays
+    pub fn from_toml(value: &TomlValue) -> Option<Self> {
+        match value {
+            TomlValue::String(text) => Some(Value::String(text.clone())),
+            TomlValue::Integer(number) => Some(Value::Number(*number as f64)),
+            TomlValue::Float(number) => Some(Value::Number(*number)),
+            TomlValue::Boolean(flag) => Some(Value::Bool(*flag)),
+            TomlValue::Array(_) => None,
+        }
+    }
+
+    /// Converts a JSON value, `None` for `null`, arrays, and objects
+    pub fn from_json(value: &JsonValue) -> Option<Self> {
+        match value {
+            JsonValue::String(text) => Some(Value::String(text.clone())),
+            JsonValue::Number(number) => Some(Value::Number(*number)),
+            JsonValue::Boolean(flag) => Some(Value::Bool(*flag)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::String(text) => write!(f, "{}", text),
+            Value::Number(number) => write!(f, "{}", number),
+            Value::Bool(flag) => write!(f, "{}", flag),
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Number(value)
+    }
+}
+
+impl From<f32> for Value {
+    fn from(value: f32) -> Self {
+        Value::Number(f64::from(value))
+    }
+}
+
+impl From<i32> for Value {
+    fn from(value: i32) -> Self {
+        Value::Number(f64::from(value))
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Number(value as f64)
+    }
+}
+
+impl From<u32> for Value {
+    fn from(value: u32) -> Self {
+        Value::Number(f64::from(value))
+    }
+}
+
+impl From<usize> for Value {
+    fn from(value: usize) -> Self {
+        Value::Number(value as f64)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<&Value> for TomlValue {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::String(text) => TomlValue::String(text.clone()),
+            Value::Number(number) if number.fract() == 0.0 && number.abs() < i64::MAX as f64 => TomlValue::Integer(*number as i64),
+            Value::Number(number) => TomlValue::Float(*number),
+            Value::Bool(flag) => TomlValue::Boolean(*flag),
+        }
+    }
+}
+
+/// Reads metadata from a JSON object, such as the properties of a level editor export
+///
+/// # Notes
+/// - `null`, array, and object values are skipped
+///
+/// # Example
+/// ```
+/// use lonely_engine::{json::JsonValue, metadata};
+///
+/// let properties = JsonValue::parse(r#"{"dialogue": "innkeeper", "mood": 2, "tags": []}"#).unwrap();
+/// let meta = metadata::from_json(&properties);
+/// assert_eq!(meta.len(), 2);
+/// assert_eq!(meta["dialogue"].as_str(), Some("innkeeper"));
+/// ```
+pub fn from_json(object: &JsonValue) -> HashMap<String, Value> {
+    object
+        .as_object()
+        .unwrap_or(&[])
+        .iter()
+        .filter_map(|(key, value)| Some((key.clone(), Value::from_json(value)?)))
+        .collect()
+}
+
+/// Writes metadata into a TOML table, keys sorted
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+/// use lonely_engine::{metadata::{self, Value}, toml::TomlDocument};
+///
+/// let meta = HashMap::from([("loot".to_string(), Value::from("chest_rare"))]);
+/// let mut document = TomlDocument::new();
+/// metadata::write_toml(&meta, &mut document, "chest");
+/// assert_eq!(metadata::read_toml(&document, "chest"), meta);
+/// ```
+pub fn write_toml(metadata: &HashMap<String, Value>, document: &mut TomlDocument, table: &str) {
+    let mut keys: Vec<&String> = metadata.keys().collect();
+    keys.sort();
+    for key in keys {
+        document.set(table, key, TomlValue::from(&metadata[key]));
+    }
+}
+
+/// Reads metadata from a TOML table, empty when the table is missing
+///
+/// # Notes
+/// - Array values are skipped
+pub fn read_toml(document: &TomlDocument, table: &str) -> HashMap<String, Value> {
+    document
+        .table(table)
+        .unwrap_or(&[])
+        .iter()
+        .filter_map(|(key, value)| Some((key.clone(), Value::from_toml(value)?)))
+        .collect()
+}
@@ -0,0 +1,390 @@
+//! Engine metrics for monitoring and performance tracking
+//!
+//! The engine keeps a rolling window of frame statistics and turns it into a
+//! [`Metrics`] snapshot: frame rate, frame time percentiles, object counts, and
+//! event throughput. Snapshots can be written periodically to a file, as JSON
+//! or in the Prometheus text format, or served over HTTP for a Prometheus
+//! scraper, so a long-running server can be watched and runs of different
+//! versions compared.
+//!
+//! Nothing is exported until asked for with `Engine::dump_metrics`,
+//! `Engine::serve_metrics`, or the `--metrics` command line flag.
+//!
+//! # Example
+//! ```
+//! use std::time::Duration;
+//! use lonely_engine::metrics::MetricsRecorder;
+//!
+//! let mut recorder = MetricsRecorder::new(4);
+//! for millis in [10, 20, 30, 40] {
+//!     recorder.record(Duration::from_millis(millis), Duration::from_millis(5), 12, 10, 6);
+//! }
+//! let metrics = recorder.metrics();
+//! assert_eq!(metrics.frames, 4);
+//! assert!((metrics.fps - 40.0).abs() < 0.01);
+//! assert_eq!(metrics.frame_time_p95_ms, 40.0);
+//! assert!((metrics.events_per_second - 240.0).abs() < 0.1);
+//! assert!(metrics.to_prometheus().contains("lonely_engine_objects 12\n"));
+//! ```
+
+use std::{
+    collections::VecDeque,
+    fmt::Write as _,
+    fs,
+    io::{self, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// Frames the statistics are computed over unless configured otherwise
+pub const DEFAULT_METRICS_WINDOW: usize = 300;
+
+/// Seconds between metrics dumps unless configured otherwise
+pub const DEFAULT_DUMP_INTERVAL: f32 = 10.0;
+
+/// Snapshot of engine statistics
+///
+/// # Notes
+/// - Rates and times cover the recorder's window, counts are as of the latest frame
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Metrics {
+    /// Frames recorded since start
+    pub frames: u64,
+    /// Seconds since the recorder was created
+    pub uptime: f64,
+    /// Frames per second
+    pub fps: f32,
+    /// Average time between frames, including the frame limiter wait
+    pub frame_time_ms: f32,
+    /// Median time between frames
+    pub frame_time_p50_ms: f32,
+    /// Time between frames 95% of frames stay under
+    pub frame_time_p95_ms: f32,
+    /// Time between frames 99% of frames stay under
+    pub frame_time_p99_ms: f32,
+    /// Average time spent working on a frame, excluding the wait
+    pub busy_ms: f32,
+    /// Objects in the world
+    pub objects: usize,
+    /// Objects updated and drawn
+    pub active_objects: usize,
+    /// Events dispatched per second
+    pub events_per_second: f32,
+    /// Events dispatched since start
+    pub events_total: u64,
+}
+
+impl Metrics {
+    /// Formats the snapshot as a JSON object
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::metrics::Metrics;
+    ///
+    /// let json = Metrics { frames: 3, fps: 60.0, ..Metrics::default() }.to_json();
+    /// assert!(json.starts_with("{\"frames\":3,\"uptime\":0.000,\"fps\":60.000,"));
+    /// ```
+    pub fn to_json(&self) -> String {
+        let fields = self.fields();
+        let body: Vec<String> = fields.iter().map(|(name, _, _, value)| format!("\"{name}\":{value}")).collect();
+        format!("{{{}}}", body.join(","))
+    }
+
+    /// Formats the snapshot in the Prometheus text exposition format, every metric prefixed with `lonely_engine_`
+    pub fn to_prometheus(&self) -> String {
+        let mut text = String::new();
+        for (name, kind, help, value) in self.fields() {
+            let _ = write!(text, "# HELP lonely_engine_{name} {help}\n# TYPE lonely_engine_{name} {kind}\nlonely_engine_{name} {value}\n");
+        }
+        text
+    }
+
+    /// Name, Prometheus type, description, and formatted value of every field
+    fn fields(&self) -> [(&'static str, &'static str, &'static str, String); 12] {
+        let float = |value: f32| format!("{:.3}", if value.is_finite() { value } else { 0.0 });
+        [
+            ("frames", "counter", "Frames recorded since start", self.frames.to_string()),
+            ("uptime", "gauge", "Seconds since start", format!("{:.3}", self.uptime)),
+            ("fps", "gauge", "Frames per second", float(self.fps)),
+            ("frame_time_ms", "gauge", "Average time between frames in milliseconds", float(self.frame_time_ms)),
+            ("frame_time_p50_ms", "gauge", "Median time between frames in milliseconds", float(self.frame_time_p50_ms)),
+            ("frame_time_p95_ms", "gauge", "95th percentile time between frames in milliseconds", float(self.frame_time_p95_ms)),
+            ("frame_time_p99_ms", "gauge", "99th percentile time between frames in milliseconds", float(self.frame_time_p99_ms)),
+            ("busy_ms", "gauge", "Average time working on a frame in milliseconds", float(self.busy_ms)),
+            ("objects", "gauge", "Objects in the world", self.objects.to_string()),
+            ("active_objects", "gauge", "Active objects in the world", self.active_objects.to_string()),
+            ("events_per_second", "gauge", "Events dispatched per second", float(self.events_per_second)),
+            ("events_total", "counter", "Events dispatched since start", self.events_total.to_string()),
+        ]
+    }
+}
+
+/// Format metrics are written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricsFormat {
+    /// One JSON object
+    Json,
+    /// Prometheus text exposition format
+    Prometheus,
+}
+
+impl MetricsFormat {
+    /// Picks the format by file extension, Prometheus for `.prom` and `.txt`, JSON otherwise
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("prom" | "txt") => MetricsFormat::Prometheus,
+            _ => MetricsFormat::Json,
+        }
+    }
+
+    /// Formats a snapshot
+    pub fn format(self, metrics: &Metrics) -> String {
+        match self {
+            MetricsFormat::Json => metrics.to_json(),
+            MetricsFormat::Prometheus => metrics.to_prometheus(),
+        }
+    }
+}
+
+/// Statistics of one frame
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    frame_time: Duration,
+    busy: Duration,
+    events: u64,
+}
+
+/// Rolling window of frame statistics, fed by the engine after every frame
+#[derive(Debug, Clone)]
+pub struct MetricsRecorder {
+    window: usize,
+    samples: VecDeque<Sample>,
+    frames: u64,
+    objects: usize,
+    active_objects: usize,
+    events_total: u64,
+    started: Instant,
+}
+
+impl Default for MetricsRecorder {
+    fn default() -> Self {
+        Self::new(DEFAULT_METRICS_WINDOW)
+    }
+}
+
+impl MetricsRecorder {
+    /// Creates a recorder computing rates over the last `window` frames
+    pub fn new(window: usize) -> Self {
+        Self { window: window.max(1), samples: VecDeque::new(), frames: 0, objects: 0, active_objects: 0, events_total: 0, started: Instant::now() }
+    }
+
+    /// Records a finished frame
+    ///
+    /// # Arguments
+    /// * `frame_time` - Time since the previous frame, including the wait
+    /// * `busy` - Time spent working on the frame
+    /// * `objects` - Objects in the world
+    /// * `active_objects` - Active objects in the world
+    /// * `events` - Events dispatched during the frame
+    pub fn record(&mut self, frame_time: Duration, busy: Duration, objects: usize, active_objects: usize, events: u64) {
+        if self.samples.len() == self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(Sample { frame_time, busy, events });
+        self.frames += 1;
+        self.objects = objects;
+        self.active_objects = active_objects;
+        self.events_total += events;
+    }
+
+    /// Snapshot of the statistics
+    pub fn metrics(&self) -> Metrics {
+        let count = self.samples.len().max(1) as f32;
+        let elapsed: Duration = self.samples.iter().map(|sample| sample.frame_time).sum();
+        let busy: Duration = self.samples.iter().map(|sample| sample.busy).sum();
+        let events: u64 = self.samples.iter().map(|sample| sample.events).sum();
+        let per_second = |amount: f32| if elapsed.is_zero() { 0.0 } else { amount / elapsed.as_secs_f32() };
+
+        let mut frame_times: Vec<Duration> = self.samples.iter().map(|sample| sample.frame_time).collect();
+        frame_times.sort();
+        // Nearest-rank percentile
+        let percentile = |share: f32| {
+            let rank = ((share * frame_times.len() as f32).ceil() as usize).clamp(1, frame_times.len().max(1));
+            frame_times.get(rank - 1).map_or(0.0, |time| millis(*time))
+        };
+
+        Metrics {
+            frames: self.frames,
+            uptime: self.started.elapsed().as_secs_f64(),
+            fps: per_second(self.samples.len() as f32),
+            frame_time_ms: millis(elapsed) / count,
+            frame_time_p50_ms: percentile(0.5),
+            frame_time_p95_ms: percentile(0.95),
+            frame_time_p99_ms: percentile(0.99),
+            busy_ms: millis(busy) / count,
+            objects: self.objects,
+            active_objects: self.active_objects,
+            events_per_second: per_second(events as f32),
+            events_total: self.events_total,
+        }
+    }
+}
+
+/// Writes metrics to a file at a fixed interval
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsDump {
+    path: PathBuf,
+    format: MetricsFormat,
+    interval: f32,
+    /// Seconds since the last write
+    elapsed: f32,
+}
+
+impl MetricsDump {
+    /// Dumps to a file in the format matching its extension, see [`MetricsFormat::from_path`]
+    ///
+    /// # Arguments
+    /// * `path` - File replaced on every write
+    /// * `interval` - Seconds between writes
+    pub fn new(path: impl Into<PathBuf>, interval: f32) -> Self {
+        let path = path.into();
+        Self { format: MetricsFormat::from_path(&path), path, interval: interval.max(0.0), elapsed: 0.0 }
+    }
+
+    /// Overrides the format picked from the extension
+    pub fn format(mut self, format: MetricsFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Advances the interval timer
+    ///
+    /// # Returns
+    /// Whether a write is due
+    pub fn tick(&mut self, delta_time: f32) -> bool {
+        self.elapsed += delta_time;
+        if self.elapsed < self.interval {
+            return false;
+        }
+        self.elapsed = 0.0;
+        true
+    }
+
+    /// Writes a snapshot, replacing the file in one step so readers never see half of it
+    pub fn write(&self, metrics: &Metrics) -> io::Result<()> {
+        let mut partial = self.path.clone().into_os_string();
+        partial.push(".tmp");
+        fs::write(&partial, self.format.format(metrics))?;
+        fs::rename(&partial, &self.path)
+    }
+}
+
+/// Answers HTTP requests for metrics from a background thread
+///
+/// # Notes
+/// - `GET /metrics` answers in the Prometheus format, `GET /metrics.json` as JSON
+/// - Answers carry the snapshot last given to [`publish`](Self::publish), the game loop never waits on a client
+/// - Slow or silent clients are dropped after a short timeout
+/// - The thread stops when the server is dropped
+///
+/// # Example
+/// ```
+/// use std::{io::{Read, Write}, net::TcpStream};
+/// use lonely_engine::metrics::{Metrics, MetricsServer};
+///
+/// let server = MetricsServer::bind("127.0.0.1:0").unwrap();
+/// server.publish(Metrics { objects: 12, ..Metrics::default() });
+///
+/// let mut client = TcpStream::connect(server.local_addr().unwrap()).unwrap();
+/// client.write_all(b"GET /metrics HTTP/1.1\r\n\r\n").unwrap();
+/// let mut response = String::new();
+/// client.read_to_string(&mut response).unwrap();
+/// assert!(response.starts_with("HTTP/1.1 200 OK"));
+/// assert!(response.contains("lonely_engine_objects 12\n"));
+/// ```
+#[derive(Debug)]
+pub struct MetricsServer {
+    address: SocketAddr,
+    snapshot: Arc<Mutex<Metrics>>,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MetricsServer {
+    /// Listens on an address such as `"127.0.0.1:9100"`
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        let address = listener.local_addr()?;
+        let snapshot = Arc::new(Mutex::new(Metrics::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let thread_snapshot = Arc::clone(&snapshot);
+        let thread_shutdown = Arc::clone(&shutdown);
+        let handle = thread::spawn(move || {
+            while !thread_shutdown.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, _)) => answer(stream, &thread_snapshot),
+                    Err(error) if error.kind() == io::ErrorKind::WouldBlock => thread::sleep(ACCEPT_INTERVAL),
+                    // Such as a client resetting before it was accepted
+                    Err(_) => {},
+                }
+            }
+        });
+
+        Ok(Self { address, snapshot, shutdown, handle: Some(handle) })
+    }
+
+    /// Address the server listens on, with the port picked by the system when bound to port 0
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        Ok(self.address)
+    }
+
+    /// Replaces the snapshot served to clients
+    pub fn publish(&self, metrics: Metrics) {
+        *self.snapshot.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = metrics;
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Time the server thread sleeps while no client is waiting
+const ACCEPT_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Longest a client may take to send its request or receive the answer
+const CLIENT_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Answers one request with the current snapshot
+fn answer(mut stream: TcpStream, snapshot: &Mutex<Metrics>) {
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(CLIENT_TIMEOUT));
+    let _ = stream.set_write_timeout(Some(CLIENT_TIMEOUT));
+    let mut request = [0u8; 1024];
+    let read = stream.read(&mut request).unwrap_or(0);
+    let request = String::from_utf8_lossy(&request[..read]);
+    let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+    let metrics = snapshot.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+    let (status, content_type, body) = match path {
+        "/metrics" | "/" => ("200 OK", "text/plain; version=0.0.4", metrics.to_prometheus()),
+        "/metrics.json" => ("200 OK", "application/json", metrics.to_json()),
+        _ => ("404 Not Found", "text/plain", "not found\n".to_string()),
+    };
+    let response = format!("HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len());
+    let _ = stream.write_all(response.as_bytes());
+}
+
+fn millis(duration: Duration) -> f32 {
+    duration.as_secs_f32() * 1000.0
+}
@@ -0,0 +1,233 @@
+//! Software mixing of concurrent sounds
+//!
+//! [`Mixer`] adds any number of playing voices into one stereo stream, which the
+//! audio backend hands to the sound card in small blocks. This is what lets
+//! footsteps, music, and overlapping effects play at the same time instead of
+//! cutting each other off. Voices are resampled to the output rate, panned, and
+//! scaled on the fly, so changing a voice's volume or position is free.
+//!
+//! [`AudioEngine`](crate::audio::AudioEngine) owns a mixer and drives it, games
+//! normally never touch one directly.
+//!
+//! # Example
+//! ```
+//! use std::sync::Arc;
+//! use lonely_engine::{audio::SoundHandle, mixer::{Mixer, Samples}};
+//!
+//! let beep = Arc::new(Samples::new(1, 22_050, vec![8000; 2205]));
+//! let mut mixer = Mixer::new(44_100);
+//! mixer.play(SoundHandle(1), Arc::clone(&beep), 1.0, None, false);
+//! mixer.play(SoundHandle(2), beep, 1.0, None, false);
+//!
+//! let mut block = [0i16; 2 * 64];
+//! mixer.mix(&mut block);
+//! assert_eq!(block[0], 16000); // Both voices added up
+//! ```
+
+use std::{f32::consts::FRAC_PI_2, fmt, sync::Arc};
+use crate::audio::SoundHandle;
+
+/// Sample rate of the stream sent to the sound card
+pub const OUTPUT_SAMPLE_RATE: u32 = 44_100;
+
+/// Decoded 16-bit samples of a sound, shared by all its voices
+#[derive(Clone, PartialEq, Eq)]
+pub struct Samples {
+    channels: u16,
+    sample_rate: u32,
+    /// Interleaved samples, one per channel per frame
+    data: Vec<i16>,
+}
+
+impl fmt::Debug for Samples {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Samples")
+            .field("channels", &self.channels)
+            .field("sample_rate", &self.sample_rate)
+            .field("frames", &self.frames())
+            .finish()
+    }
+}
+
+impl Samples {
+    /// Wraps interleaved samples
+    ///
+    /// # Arguments
+    /// * `channels` - `1` for mono, `2` for stereo, further channels are ignored when mixing
+    /// * `sample_rate` - Frames per second
+    /// * `data` - Samples, one per channel per frame
+    pub fn new(channels: u16, sample_rate: u32, data: Vec<i16>) -> Self {
+        Self { channels: channels.max(1), sample_rate: sample_rate.max(1), data }
+    }
+
+    /// Number of channels
+    pub fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    /// Frames per second
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Number of frames, samples per channel
+    pub fn frames(&self) -> usize {
+        self.data.len() / usize::from(self.channels)
+    }
+
+    /// Left and right sample of a frame, mono is sent to both sides
+    fn frame(&self, frame: usize) -> (f32, f32) {
+        let at = frame * usize::from(self.channels);
+        let left = f32::from(self.data[at]);
+        let right = if self.channels > 1 { f32::from(self.data[at + 1]) } else { left };
+        (left, right)
+    }
+}
+
+/// One playing sound
+#[derive(Debug)]
+struct Voice {
+    handle: SoundHandle,
+    samples: Arc<Samples>,
+    /// Frame of `samples` to play next, fractional when resampling
+    position: f64,
+    /// Source frames per output frame
+    step: f64,
+    looping: bool,
+    left_gain: f32,
+    right_gain: f32,
+}
+
+impl Voice {
+    /// Adds the voice into interleaved stereo
+    ///
+    /// # Returns
+    /// Whether the voice is still playing
+    fn mix_into(&mut self, out: &mut [f32]) -> bool {
+        let frames = self.samples.frames();
+        if frames == 0 {
+            return false;
+        }
+        for pair in out.chunks_exact_mut(2) {
+            if self.position >= frames as f64 {
+                if !self.looping {
+                    return false;
+                }
+                self.position %= frames as f64;
+            }
+
+            // Linear interpolation between neighbouring frames
+            let index = self.position as usize;
+            let fraction = (self.position - index as f64) as f32;
+            let next = if index + 1 < frames { index + 1 } else if self.looping { 0 } else { index };
+            let (left_a, right_a) = self.samples.frame(index);
+            let (left_b, right_b) = self.samples.frame(next);
+            pair[0] += (left_a + (left_b - left_a) * fraction) * self.left_gain;
+            pair[1] += (right_a + (right_b - right_a) * fraction) * self.right_gain;
+            self.position += self.step;
+        }
+        self.looping || self.position < frames as f64
+    }
+
+    fn set_mix(&mut self, gain: f32, pan: Option<f32>) {
+        (self.left_gain, self.right_gain) = channel_gains(gain, pan);
+    }
+}
+
+/// Per-channel gains, equal-power panning when a pan is given
+fn channel_gains(gain: f32, pan: Option<f32>) -> (f32, f32) {
+    match pan {
+        None => (gain, gain),
+        Some(pan) => {
+            let angle = (pan.clamp(-1.0, 1.0) + 1.0) * FRAC_PI_2 / 2.0;
+            (gain * angle.cos(), gain * angle.sin())
+        },
+    }
+}
+
+/// Adds playing voices into one stereo stream
+#[derive(Debug)]
+pub struct Mixer {
+    sample_rate: u32,
+    voices: Vec<Voice>,
+    /// Accumulates a block before it is clipped to 16 bits
+    scratch: Vec<f32>,
+}
+
+impl Mixer {
+    /// Creates a silent mixer producing `sample_rate` frames per second
+    pub fn new(sample_rate: u32) -> Self {
+        Self { sample_rate: sample_rate.max(1), voices: Vec::new(), scratch: Vec::new() }
+    }
+
+    /// Starts a voice
+    ///
+    /// # Arguments
+    /// * `handle` - Identifies the voice for later changes, a voice with the same handle is replaced
+    /// * `samples` - Sound to play
+    /// * `gain` - Volume multiplier, `1.0` as recorded
+    /// * `pan` - `-1.0` (left) to `1.0` (right) with equal-power panning, `None` keeps the channels as they are
+    /// * `looping` - Repeat until stopped
+    pub fn play(&mut self, handle: SoundHandle, samples: Arc<Samples>, gain: f32, pan: Option<f32>, looping: bool) {
+        self.stop(handle);
+        let step = f64::from(samples.sample_rate) / f64::from(self.sample_rate);
+        let (left_gain, right_gain) = channel_gains(gain, pan);
+        self.voices.push(Voice { handle, samples, position: 0.0, step, looping, left_gain, right_gain });
+    }
+
+    /// Changes the volume and panning of a playing voice, see [`play`](Self::play)
+    pub fn set_mix(&mut self, handle: SoundHandle, gain: f32, pan: Option<f32>) {
+        if let Some(voice) = self.voices.iter_mut().find(|voice| voice.handle == handle) {
+            voice.set_mix(gain, pan);
+        }
+    }
+
+    /// Changes the playback speed of a playing voice, which shifts its pitch along
+    ///
+    /// # Arguments
+    /// * `handle` - Voice to change
+    /// * `speed` - `1.0` as recorded, `2.0` twice as fast and an octave higher
+    pub fn set_speed(&mut self, handle: SoundHandle, speed: f32) {
+        let sample_rate = self.sample_rate;
+        if let Some(voice) = self.voices.iter_mut().find(|voice| voice.handle == handle) {
+            voice.step = f64::from(voice.samples.sample_rate) / f64::from(sample_rate) * f64::from(speed.max(0.0));
+        }
+    }
+
+    /// Stops a voice, does nothing when it already ended
+    pub fn stop(&mut self, handle: SoundHandle) {
+        self.voices.retain(|voice| voice.handle != handle);
+    }
+
+    /// Stops every voice
+    pub fn stop_all(&mut self) {
+        self.voices.clear();
+    }
+
+    /// Returns whether a voice is still playing
+    pub fn is_playing(&self, handle: SoundHandle) -> bool {
+        self.voices.iter().any(|voice| voice.handle == handle)
+    }
+
+    /// Number of voices playing
+    pub fn voice_count(&self) -> usize {
+        self.voices.len()
+    }
+
+    /// Fills a block of the output stream, dropping voices that ended
+    ///
+    /// # Arguments
+    /// * `out` - Interleaved stereo samples, left first
+    ///
+    /// # Notes
+    /// - Voices are added up and clipped to the 16-bit range
+    pub fn mix(&mut self, out: &mut [i16]) {
+        self.scratch.clear();
+        self.scratch.resize(out.len(), 0.0);
+        let scratch = &mut self.scratch;
+        self.voices.retain_mut(|voice| voice.mix_into(scratch));
+        for (sample, mixed) in out.iter_mut().zip(&self.scratch) {
+            *sample = mixed.clamp(f32::from(i16::MIN), f32::from(i16::MAX)) as i16;
+        }
+    }
+}
@@ -0,0 +1,277 @@
+//! Plain-text narration of the game for screen readers
+//!
+//! A [`Narrator`] turns key game events into plain lines of text and writes
+//! them to a stream a screen reader can follow, or hands them to the
+//! operating system's speech service. Together with the visual renderer turned
+//! off, see `Engine::set_display`, blind players can play text-heavy games
+//! without the screen being read cell by cell.
+//!
+//! Lines come from:
+//! - [`Narrator::say`] and `EngineCommand::Narrate`, the game's message log
+//! - Tagged `Custom` events, `"say:You found a key"` reads "You found a key"
+//! - Rules added with [`Narrator::describe`], and built-in ones for levels,
+//!   achievements, score milestones, pausing, and chat
+//!
+//! Lines are queued while the frame runs and written together at its end.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{event::{EngineEvent, EventBus, EventKind}, narration::{NarrationOutput, Narrator}};
+//!
+//! let narrator = Narrator::new(NarrationOutput::Memory);
+//! narrator.describe(EventKind::ItemUsed, |event| match event {
+//!     EngineEvent::ItemUsed(_, item) => Some(format!("Used {item}")),
+//!     _ => None,
+//! });
+//! let mut bus = EventBus::new();
+//! narrator.subscribe(&mut bus);
+//!
+//! bus.emit(EngineEvent::LevelChanged("Crypt".into()));
+//! bus.emit(EngineEvent::Custom("say:The door creaks open".into()));
+//! bus.emit(EngineEvent::ItemUsed(0, "potion".into()));
+//! narrator.say("You feel better");
+//! narrator.flush().unwrap();
+//!
+//! assert_eq!(narrator.history(), ["Level Crypt", "The door creaks open", "Used potion", "You feel better"]);
+//! ```
+
+use std::{cell::RefCell, collections::VecDeque, fs::OpenOptions, io::{self, Write}, path::PathBuf, process::{Command, Stdio}, rc::Rc};
+use crate::event::{EngineEvent, EventBus, EventFilter};
+
+/// Prefix of `Custom` events read out as they are, without it
+pub const SAY_TAG: &str = "say:";
+
+/// Lines kept for [`Narrator::history`], so players can have them repeated
+pub const HISTORY_CAPACITY: usize = 50;
+
+/// Where narrated lines go
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NarrationOutput {
+    /// Standard output, for games running with the display turned off
+    Stdout,
+    /// Standard error, next to the visual display on standard output
+    Stderr,
+    /// A file lines are appended to, followed by a screen reader or `tail -f`
+    File(PathBuf),
+    /// A program run with the lines of a frame as its last argument, such as a speech synthesizer
+    Command(String, Vec<String>),
+    /// Only the history, for tests and games drawing their own transcript
+    Memory,
+}
+
+impl NarrationOutput {
+    /// The operating system's speech service
+    ///
+    /// # Notes
+    /// - `spd-say` (Speech Dispatcher) on Linux, `say` on macOS, and the speech synthesizer through PowerShell on Windows
+    /// - Nothing is spoken when the program is missing
+    pub fn speech() -> Self {
+        if cfg!(windows) {
+            let script = "Add-Type -AssemblyName System.Speech; (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak($args[0])";
+            NarrationOutput::Command("powershell".to_string(), vec!["-NoProfile".to_string(), "-Command".to_string(), script.to_string()])
+        } else if cfg!(target_os = "macos") {
+            NarrationOutput::Command("say".to_string(), Vec::new())
+        } else {
+            NarrationOutput::Command("spd-say".to_string(), Vec::new())
+        }
+    }
+
+    /// Parses an output named on the command line: `stdout`, `stderr`, `speech`, or a file path
+    pub fn parse(text: &str) -> Self {
+        match text {
+            "stdout" | "-" => NarrationOutput::Stdout,
+            "stderr" => NarrationOutput::Stderr,
+            "speech" => NarrationOutput::speech(),
+            path => NarrationOutput::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Writes lines, in order
+    fn write(&self, lines: &[String]) -> io::Result<()> {
+        let text: String = lines.iter().map(|line| format!("{line}\n")).collect();
+        match self {
+            NarrationOutput::Stdout => {
+                let mut stdout = io::stdout().lock();
+                stdout.write_all(text.as_bytes()).and_then(|_| stdout.flush())
+            },
+            NarrationOutput::Stderr => io::stderr().lock().write_all(text.as_bytes()),
+            NarrationOutput::File(path) => OpenOptions::new().create(true).append(true).open(path)?.write_all(text.as_bytes()),
+            NarrationOutput::Command(program, args) => {
+                // One process for the frame, so lines are spoken in order without overlapping
+                Command::new(program)
+                    .args(args)
+                    .arg(lines.join(". "))
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .spawn()
+                    .map(|_| ())
+            },
+            NarrationOutput::Memory => Ok(()),
+        }
+    }
+}
+
+/// Turns an event into a line, `None` to stay silent
+type Describe = Box<dyn Fn(&EngineEvent) -> Option<String>>;
+
+/// Lines and rules shared with the event bus subscription
+struct State {
+    output: NarrationOutput,
+    rules: Vec<(EventFilter, Describe)>,
+    /// Lines waiting for [`Narrator::flush`]
+    pending: Vec<String>,
+    /// Lines written, oldest first
+    history: VecDeque<String>,
+    muted: bool,
+}
+
+/// Accessibility output channel writing game events as plain lines
+///
+/// # Notes
+/// - Clones share their lines and rules, the engine keeps one subscribed to its event bus
+#[derive(Clone)]
+pub struct Narrator {
+    state: Rc<RefCell<State>>,
+}
+
+impl std::fmt::Debug for Narrator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.state.borrow();
+        f.debug_struct("Narrator")
+            .field("output", &state.output)
+            .field("rules", &state.rules.len())
+            .field("pending", &state.pending)
+            .finish()
+    }
+}
+
+impl Narrator {
+    /// Creates a narrator with the built-in rules
+    pub fn new(output: NarrationOutput) -> Self {
+        let narrator = Self {
+            state: Rc::new(RefCell::new(State { output, rules: Vec::new(), pending: Vec::new(), history: VecDeque::new(), muted: false })),
+        };
+        narrator.describe(EventFilter::Any, describe_builtin);
+        narrator
+    }
+
+    /// Creates a narrator reading only tagged events and lines said by the game
+    pub fn silent(output: NarrationOutput) -> Self {
+        let narrator = Self::new(output);
+        narrator.state.borrow_mut().rules.clear();
+        narrator
+    }
+
+    /// Adds a rule describing events passing a filter, after the ones already added
+    ///
+    /// # Arguments
+    /// * `filter` - Events the rule is asked about
+    /// * `describe` - Line read for an event, `None` to stay silent
+    pub fn describe(&self, filter: impl Into<EventFilter>, describe: impl Fn(&EngineEvent) -> Option<String> + 'static) {
+        self.state.borrow_mut().rules.push((filter.into(), Box::new(describe)));
+    }
+
+    /// Narrates an event: tagged `Custom` events as they are, others through the rules
+    ///
+    /// # Notes
+    /// - Called for every event once [`subscribe`](Self::subscribe)d
+    pub fn hear(&self, event: &EngineEvent) {
+        if let EngineEvent::Custom(text) = event && let Some(line) = text.strip_prefix(SAY_TAG) {
+            self.say(line);
+            return;
+        }
+        let lines: Vec<String> = {
+            let state = self.state.borrow();
+            state.rules.iter().filter(|(filter, _)| filter.matches(event)).filter_map(|(_, describe)| describe(event)).collect()
+        };
+        for line in lines {
+            self.say(&line);
+        }
+    }
+
+    /// Queues a line, such as an entry of the game's message log
+    ///
+    /// # Notes
+    /// - Empty lines and lines said while muted are dropped
+    pub fn say(&self, line: &str) {
+        let line = line.trim();
+        let mut state = self.state.borrow_mut();
+        if !line.is_empty() && !state.muted {
+            state.pending.push(line.to_string());
+        }
+    }
+
+    /// Feeds every event of a bus to [`hear`](Self::hear)
+    pub fn subscribe(&self, bus: &mut EventBus) {
+        let narrator = self.clone();
+        bus.subscribe(move |event| narrator.hear(event));
+    }
+
+    /// Writes the queued lines, called by the engine at the end of every frame
+    ///
+    /// # Returns
+    /// The error of the output, the lines still count as said
+    pub fn flush(&self) -> io::Result<()> {
+        let mut state = self.state.borrow_mut();
+        if state.pending.is_empty() {
+            return Ok(());
+        }
+        let lines = std::mem::take(&mut state.pending);
+        let result = state.output.write(&lines);
+        for line in lines {
+            if state.history.len() == HISTORY_CAPACITY {
+                state.history.pop_front();
+            }
+            state.history.push_back(line);
+        }
+        result
+    }
+
+    /// Queues the latest written line again, for a "repeat that" key
+    pub fn repeat_last(&self) {
+        let last = self.state.borrow().history.back().cloned();
+        if let Some(line) = last {
+            self.say(&line);
+        }
+    }
+
+    /// Lines written, oldest first, at most [`HISTORY_CAPACITY`]
+    pub fn history(&self) -> Vec<String> {
+        self.state.borrow().history.iter().cloned().collect()
+    }
+
+    /// Stops or resumes queueing lines
+    pub fn set_muted(&self, muted: bool) {
+        let mut state = self.state.borrow_mut();
+        state.muted = muted;
+        if muted {
+            state.pending.clear();
+        }
+    }
+
+    /// Returns whether lines are dropped
+    pub fn is_muted(&self) -> bool {
+        self.state.borrow().muted
+    }
+
+    /// Replaces where lines go
+    pub fn set_output(&self, output: NarrationOutput) {
+        self.state.borrow_mut().output = output;
+    }
+}
+
+/// Lines for the events worth reading in any game
+fn describe_builtin(event: &EngineEvent) -> Option<String> {
+    match event {
+        EngineEvent::LevelChanged(name) => Some(format!("Level {name}")),
+        EngineEvent::AchievementUnlocked(name) => Some(format!("Achievement unlocked: {name}")),
+        EngineEvent::ScoreMilestone(score) => Some(format!("Score {score}")),
+        EngineEvent::GamePaused => Some("Paused".to_string()),
+        EngineEvent::GameResumed => Some("Resumed".to_string()),
+        EngineEvent::ChatReceived(_, text) => Some(text.clone()),
+        EngineEvent::PlayerJoined(_, name) => Some(format!("{name} joined")),
+        EngineEvent::PlayerLeft(_, name) => Some(format!("{name} left")),
+        _ => None,
+    }
+}
@@ -0,0 +1,140 @@
+//! Grid occupancy of solid objects and how moves into them resolve
+//!
+//! The engine keeps an [`OccupancyMap`] of every cell covered by an active
+//! object with `solid` set. With a [`MovePolicy`] other than `Overlap`, a solid
+//! object's `EngineCommand::MoveObject` into an occupied cell does not go
+//! through:
+//! - A single blocking object with `pushable` set is pushed one step along,
+//!   when the cell behind it is free, the way crates move in Sokoban
+//! - Otherwise the move fails and `EngineEvent::MoveBlocked` is emitted
+//! - With `MovePolicy::Slide`, a blocked diagonal move first tries its
+//!   horizontal, then its vertical step, so objects slide along walls
+//!
+//! Objects that are not solid, such as pickups and effects, move freely and
+//! never block.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{engine::Engine, game_object::GameObject, occupancy::MovePolicy};
+//!
+//! let mut engine = Engine::builder(20, 10).move_policy(MovePolicy::Block).build();
+//! engine.add_object(GameObject::new(2, 2, '@').with_solid(true));
+//! engine.add_object(GameObject::new(3, 2, '#').with_solid(true));
+//! assert_eq!(engine.occupancy().occupant(3, 2), Some(1));
+//! ```
+
+use std::collections::HashMap;
+use crate::game_object::GameObject;
+
+/// What happens when a solid object moves into a cell held by another solid object
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MovePolicy {
+    /// Objects move through each other, the default
+    #[default]
+    Overlap,
+    /// The move fails and `MoveBlocked` is emitted
+    Block,
+    /// Like `Block`, but a blocked diagonal move tries its horizontal and vertical steps
+    Slide,
+}
+
+/// Which solid object covers each grid cell
+///
+/// # Example
+/// ```
+/// use lonely_engine::{game_object::GameObject, occupancy::OccupancyMap, sprite::Sprite};
+///
+/// let objects = vec![
+///     GameObject::with_sprite(4, 4, Sprite::from_str("##\n##")).with_solid(true),
+///     GameObject::new(1, 1, '*'),
+/// ];
+/// let map = OccupancyMap::from_objects(&objects);
+/// assert_eq!(map.occupant(5, 5), Some(0));
+/// assert!(map.is_free(1, 1)); // Not solid
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OccupancyMap {
+    cells: HashMap<(usize, usize), usize>,
+}
+
+impl OccupancyMap {
+    /// Creates an empty map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps the cells of every active solid object, later objects win shared cells
+    pub fn from_objects(objects: &[GameObject]) -> Self {
+        let mut map = Self::new();
+        for (index, obj) in objects.iter().enumerate() {
+            map.insert(index, obj);
+        }
+        map
+    }
+
+    /// Index of the solid object covering a cell
+    pub fn occupant(&self, x: usize, y: usize) -> Option<usize> {
+        self.cells.get(&(x, y)).copied()
+    }
+
+    /// Returns whether no solid object covers a cell
+    pub fn is_free(&self, x: usize, y: usize) -> bool {
+        !self.cells.contains_key(&(x, y))
+    }
+
+    /// Number of occupied cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns whether no cell is occupied
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Solid objects other than `index` that an object would overlap at a position
+    ///
+    /// # Arguments
+    /// * `index` - The object's own index, its cells never block it
+    /// * `obj` - The object, for its shape
+    /// * `x`, `y` - Position to test, the top-left cell for sprites
+    ///
+    /// # Returns
+    /// Indices of the blocking objects in ascending order, empty when the way is free
+    pub fn blockers(&self, index: usize, obj: &GameObject, x: usize, y: usize) -> Vec<usize> {
+        let mut blockers: Vec<usize> = footprint(obj, x, y)
+            .into_iter()
+            .filter_map(|cell| self.cells.get(&cell).copied())
+            .filter(|&occupant| occupant != index)
+            .collect();
+        blockers.sort_unstable();
+        blockers.dedup();
+        blockers
+    }
+
+    /// Adds an object's cells, ignored unless it is solid and active
+    pub fn insert(&mut self, index: usize, obj: &GameObject) {
+        if obj.solid && obj.active {
+            for cell in footprint(obj, obj.x, obj.y) {
+                self.cells.insert(cell, index);
+            }
+        }
+    }
+
+    /// Removes the cells an object covers at its current position
+    pub fn remove(&mut self, index: usize, obj: &GameObject) {
+        for cell in footprint(obj, obj.x, obj.y) {
+            if self.cells.get(&cell) == Some(&index) {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+}
+
+/// Grid cells an object covers at a position: its visible sprite cells, or the single cell of a character
+pub fn footprint(obj: &GameObject, x: usize, y: usize) -> Vec<(usize, usize)> {
+    match obj.current_sprite() {
+        Some(sprite) => sprite.cells().map(|(cell_x, cell_y, _)| (x + cell_x, y + cell_y)).collect(),
+        None => vec![(x, y)],
+    }
+}
@@ -0,0 +1,209 @@
+//! Frame pacing between presented frames
+//!
+//! After presenting a frame the engine waits out the rest of the frame
+//! interval. How it waits is a [`FramePacing`] strategy:
+//! - `Sleep` hands the whole wait to the OS, cheapest but late by the
+//!   scheduler's granularity, up to 15 ms on Windows
+//! - `Hybrid` sleeps until shortly before the deadline and spins the rest,
+//!   accurate to microseconds for a little CPU time every frame
+//! - `Adaptive` paces against absolute deadlines and learns how late the OS
+//!   wakes it up, sleeping that much shorter. Late frames are made up by
+//!   starting the next one sooner instead of drifting, the default
+//!
+//! The measured time between frames and its deviation from the target, the
+//! jitter, are part of every frame's [`FrameTimings`](crate::profiler::FrameTimings).
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{engine::Engine, pacing::FramePacing};
+//!
+//! let mut engine = Engine::builder(80, 24).frame_pacing(FramePacing::hybrid()).build();
+//! let jitter = engine.frame_profile().average.jitter;
+//! ```
+
+use std::time::{Duration, Instant};
+
+/// Time a hybrid wait spins before the deadline by default
+pub const DEFAULT_SPIN: Duration = Duration::from_millis(2);
+
+/// Longest sleep correction `Adaptive` learns, beyond that the OS is not late but stalled
+const MAX_CORRECTION: Duration = Duration::from_millis(20);
+
+/// How the engine waits between frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FramePacing {
+    /// Sleep for the rest of the frame interval
+    Sleep,
+    /// Sleep until this long before the deadline, then spin
+    Hybrid(Duration),
+    /// Sleep against absolute deadlines, corrected by the measured oversleep
+    #[default]
+    Adaptive,
+}
+
+impl FramePacing {
+    /// Hybrid pacing spinning for the last [`DEFAULT_SPIN`]
+    pub fn hybrid() -> Self {
+        FramePacing::Hybrid(DEFAULT_SPIN)
+    }
+
+    /// Strategy named `sleep`, `hybrid`, or `adaptive`, as given on the command line
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::pacing::FramePacing;
+    /// assert_eq!(FramePacing::from_name("hybrid"), Some(FramePacing::hybrid()));
+    /// assert_eq!(FramePacing::from_name("vsync"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "sleep" => Some(FramePacing::Sleep),
+            "hybrid" => Some(Self::hybrid()),
+            "adaptive" => Some(FramePacing::Adaptive),
+            _ => None,
+        }
+    }
+}
+
+/// Waits out frame intervals and measures how well they were kept
+///
+/// # Example
+/// ```
+/// use std::time::{Duration, Instant};
+/// use lonely_engine::pacing::{FramePacer, FramePacing};
+///
+/// let mut pacer = FramePacer::new(FramePacing::hybrid(), Duration::from_millis(5));
+/// for _ in 0..3 {
+///     let frame_start = Instant::now();
+///     pacer.wait(frame_start);
+/// }
+/// assert!(pacer.last_frame_time() >= Duration::from_millis(5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FramePacer {
+    pacing: FramePacing,
+    interval: Duration,
+    /// When the next frame is due, for `Adaptive`
+    deadline: Option<Instant>,
+    /// Average of how late sleeps return, for `Adaptive`
+    oversleep: Duration,
+    /// When the last wait returned
+    last_wake: Option<Instant>,
+    last_frame_time: Duration,
+    last_jitter: Duration,
+}
+
+impl FramePacer {
+    /// Creates a pacer presenting a frame every `interval`
+    pub fn new(pacing: FramePacing, interval: Duration) -> Self {
+        Self {
+            pacing,
+            interval,
+            deadline: None,
+            oversleep: Duration::ZERO,
+            last_wake: None,
+            last_frame_time: Duration::ZERO,
+            last_jitter: Duration::ZERO,
+        }
+    }
+
+    /// Strategy used to wait
+    pub fn pacing(&self) -> FramePacing {
+        self.pacing
+    }
+
+    /// Changes the strategy from the next frame on
+    pub fn set_pacing(&mut self, pacing: FramePacing) {
+        self.pacing = pacing;
+        self.deadline = None;
+    }
+
+    /// Target time between frames
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Changes the target time between frames
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+        self.deadline = None;
+    }
+
+    /// Waits until the next frame is due
+    ///
+    /// # Arguments
+    /// * `frame_start` - When work on the frame began, `Sleep` and `Hybrid` wait one interval after it
+    pub fn wait(&mut self, frame_start: Instant) {
+        match self.pacing {
+            FramePacing::Sleep => {
+                let elapsed = frame_start.elapsed();
+                if elapsed < self.interval {
+                    std::thread::sleep(self.interval - elapsed);
+                }
+            },
+            FramePacing::Hybrid(spin) => sleep_then_spin(frame_start + self.interval, spin),
+            FramePacing::Adaptive => self.wait_adaptive(frame_start),
+        }
+        self.measure();
+    }
+
+    /// Sleeps towards an absolute deadline, keeping the average frame time on target
+    fn wait_adaptive(&mut self, frame_start: Instant) {
+        let now = Instant::now();
+        let mut deadline = self.deadline.unwrap_or(frame_start + self.interval);
+        // More than a whole frame behind: catching up would rush several frames, start over instead
+        if now > deadline + self.interval {
+            deadline = now;
+        }
+
+        if let Some(sleep) = deadline.checked_duration_since(now).and_then(|left| left.checked_sub(self.oversleep)) {
+            let before = Instant::now();
+            std::thread::sleep(sleep);
+            let late = before.elapsed().saturating_sub(sleep).min(MAX_CORRECTION);
+            // Exponential moving average, an eighth of each new measurement
+            self.oversleep = (self.oversleep * 7 + late) / 8;
+        }
+        spin_until(deadline);
+        self.deadline = Some(deadline + self.interval);
+    }
+
+    /// Records the time since the previous wait returned
+    fn measure(&mut self) {
+        let now = Instant::now();
+        if let Some(last_wake) = self.last_wake {
+            self.last_frame_time = now - last_wake;
+            self.last_jitter = self.last_frame_time.abs_diff(self.interval);
+        }
+        self.last_wake = Some(now);
+    }
+
+    /// Time between the last two presented frames, including the wait
+    pub fn last_frame_time(&self) -> Duration {
+        self.last_frame_time
+    }
+
+    /// How far the last frame time was off the interval, in either direction
+    pub fn last_jitter(&self) -> Duration {
+        self.last_jitter
+    }
+
+    /// How late the OS currently wakes up from sleeps, as learned by `Adaptive`
+    pub fn oversleep(&self) -> Duration {
+        self.oversleep
+    }
+}
+
+/// Sleeps until `spin` before a deadline, then spins until it
+fn sleep_then_spin(deadline: Instant, spin: Duration) {
+    if let Some(sleep) = deadline.checked_duration_since(Instant::now()).and_then(|left| left.checked_sub(spin)) {
+        std::thread::sleep(sleep);
+    }
+    spin_until(deadline);
+}
+
+/// Busy-waits until a deadline, yielding to other threads in between
+fn spin_until(deadline: Instant) {
+    while Instant::now() < deadline {
+        std::thread::yield_now();
+    }
+}
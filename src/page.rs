@@ -0,0 +1,227 @@
+//! Full-screen text pages for help screens, credits, and story interludes
+//!
+//! A [`Page`] is a block of preformatted, styled lines shown over the whole
+//! screen with a title bar and a footer. Pages are pushed onto the engine with
+//! [`Engine::push_page`] or `EngineCommand::PushPage`; the topmost page takes
+//! the input and the world waits until it is dismissed.
+//!
+//! # Keys
+//! - Up and Down scroll one line, PageUp and PageDown one screen
+//! - Home and End jump to the top and bottom
+//! - Esc dismisses the page, plus any key added with [`Page::dismiss_key`]
+//!
+//! [`Engine::push_page`]: crate::engine::Engine::push_page
+
+use crate::{
+    color::Color,
+    input::{InputEvent, Key},
+    markup,
+    renderer::Renderer,
+    style::Style,
+};
+
+/// Run of text sharing one style
+pub type Span = (String, Style);
+
+/// Scrollable full-screen block of styled text
+///
+/// # Example
+/// ```
+/// use lonely_engine::{color::Color, engine::Engine, input::Key, page::Page, style::Style};
+///
+/// let help = Page::new("How to play")
+///     .styled_line("Movement", Style::new().fg(Color::BRIGHT_YELLOW).bold())
+///     .text("  Arrows  move\n  Space   attack")
+///     .line("")
+///     .spans(vec![("Press ".to_string(), Style::new()), ("H".to_string(), Style::new().bold()), (" to close".to_string(), Style::new())])
+///     .dismiss_key(Key::Char('h'));
+///
+/// let mut engine = Engine::new(80, 24);
+/// engine.push_page(help);
+/// assert_eq!(engine.page().map(Page::title), Some("How to play"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page {
+    title: String,
+    lines: Vec<Vec<Span>>,
+    dismiss_keys: Vec<Key>,
+    /// Index of the first line shown
+    scroll: usize,
+}
+
+impl Page {
+    /// Creates an empty page dismissed with Esc
+    pub fn new(title: &str) -> Self {
+        Self { title: title.to_string(), lines: Vec::new(), dismiss_keys: vec![Key::Esc], scroll: 0 }
+    }
+
+    /// Appends one unstyled line
+    pub fn line(self, text: &str) -> Self {
+        self.styled_line(text, Style::new())
+    }
+
+    /// Appends one line in a single style
+    pub fn styled_line(mut self, text: &str, style: Style) -> Self {
+        self.lines.push(vec![(text.to_string(), style)]);
+        self
+    }
+
+    /// Appends one line made of differently styled spans
+    pub fn spans(mut self, spans: Vec<Span>) -> Self {
+        self.lines.push(spans);
+        self
+    }
+
+    /// Appends text with inline markup tags, one line per text line
+    ///
+    /// # Notes
+    /// - Tags are parsed per line, a tag left open does not carry over
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::page::Page;
+    ///
+    /// let page = Page::new("Help").markup("[bold]Move[/bold]  arrow keys\n[bold]Jump[/bold]  space");
+    /// assert_eq!(page.lines().len(), 2);
+    /// assert_eq!(page.lines()[0][1].0, "  arrow keys");
+    /// ```
+    pub fn markup(mut self, text: &str) -> Self {
+        for line in text.lines() {
+            self = self.spans(markup::parse(line));
+        }
+        self
+    }
+
+    /// Appends preformatted text, one line per text line
+    pub fn text(self, text: &str) -> Self {
+        self.styled_text(text, Style::new())
+    }
+
+    /// Appends preformatted text in a single style, one line per text line
+    pub fn styled_text(mut self, text: &str, style: Style) -> Self {
+        for line in text.lines() {
+            self = self.styled_line(line, style);
+        }
+        self
+    }
+
+    /// Adds a key that closes the page besides Esc
+    pub fn dismiss_key(mut self, key: Key) -> Self {
+        if !self.dismiss_keys.contains(&key) {
+            self.dismiss_keys.push(key);
+        }
+        self
+    }
+
+    /// Title shown in the top bar
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Lines of styled spans
+    pub fn lines(&self) -> &[Vec<Span>] {
+        &self.lines
+    }
+
+    /// Index of the first line shown
+    pub fn scroll(&self) -> usize {
+        self.scroll
+    }
+
+    /// Scrolls to a line, clamped so the last screen stays full
+    ///
+    /// # Arguments
+    /// * `line` - Index of the first line to show
+    /// * `view_height` - Rows available for text, see [`content_height`](Self::content_height)
+    pub fn scroll_to(&mut self, line: usize, view_height: usize) {
+        self.scroll = line.min(self.lines.len().saturating_sub(view_height));
+    }
+
+    /// Rows available for text on a screen of a given height, between the title bar and footer
+    pub fn content_height(screen_height: usize) -> usize {
+        screen_height.saturating_sub(2)
+    }
+
+    /// Scrolls for this frame's key presses
+    ///
+    /// # Arguments
+    /// * `events` - Input events of the frame
+    /// * `view_height` - Rows available for text, see [`content_height`](Self::content_height)
+    ///
+    /// # Returns
+    /// `true` when a dismiss key was pressed
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{input::{InputEvent, Key}, page::Page};
+    ///
+    /// let mut credits = Page::new("Credits").text(&"Thanks!\n".repeat(50));
+    /// assert!(!credits.handle(&[InputEvent::KeyDown(Key::PageDown)], 20));
+    /// assert_eq!(credits.scroll(), 19);
+    /// assert!(!credits.handle(&[InputEvent::KeyDown(Key::End)], 20));
+    /// assert_eq!(credits.scroll(), 30);
+    /// assert!(credits.handle(&[InputEvent::KeyDown(Key::Esc)], 20));
+    /// ```
+    pub fn handle(&mut self, events: &[InputEvent], view_height: usize) -> bool {
+        let page = view_height.saturating_sub(1).max(1);
+        for event in events {
+            let InputEvent::KeyDown(key) = event else {
+                continue;
+            };
+            if self.dismiss_keys.contains(key) {
+                return true;
+            }
+            let target = match key {
+                Key::Up => self.scroll.saturating_sub(1),
+                Key::Down => self.scroll + 1,
+                Key::PageUp => self.scroll.saturating_sub(page),
+                Key::PageDown => self.scroll + page,
+                Key::Home => 0,
+                Key::End => usize::MAX,
+                _ => continue,
+            };
+            self.scroll_to(target, view_height);
+        }
+        false
+    }
+
+    /// Draws the page over the whole screen
+    ///
+    /// # Notes
+    /// - Lines wider than the screen are cut off, pages are preformatted
+    pub fn draw(&self, renderer: &mut Renderer) {
+        let (width, height) = (renderer.get_width(), renderer.get_height());
+        if height < 3 {
+            return;
+        }
+        let view_height = Self::content_height(height);
+        let bar = Style::new().fg(Color::BLACK).bg(Color::WHITE);
+
+        let title: String = format!(" {} ", self.title).chars().take(width).collect();
+        renderer.draw_styled_text(0, 0, &" ".repeat(width), &bar);
+        renderer.draw_styled_text(width.saturating_sub(title.chars().count()) / 2, 0, &title, &bar.bold());
+
+        for row in 0..view_height {
+            renderer.draw_text(0, row + 1, &" ".repeat(width));
+            let Some(spans) = self.lines.get(self.scroll + row) else {
+                continue;
+            };
+            let mut column = 0;
+            for (text, style) in spans {
+                if column >= width {
+                    break;
+                }
+                let shown: String = text.chars().take(width - column).collect();
+                renderer.draw_styled_text(column, row + 1, &shown, style);
+                column += shown.chars().count();
+            }
+        }
+
+        let last = (self.scroll + view_height).min(self.lines.len());
+        let position = format!("{}-{}/{} ", (self.scroll + 1).min(last), last, self.lines.len());
+        let hint = " ↑↓ PgUp PgDn scroll · Esc close";
+        let footer = format!("{hint}{}{position}", " ".repeat(width.saturating_sub(hint.chars().count() + position.chars().count())));
+        let footer: String = footer.chars().take(width).collect();
+        renderer.draw_styled_text(0, height - 1, &footer, &bar);
+    }
+}
@@ -0,0 +1,214 @@
+//! Waypoint movement for game objects
+//!
+//! Provides:
+//! - [`PathFollower`] component moving an object through a list of cells
+//! - [`PathMode`] for one-shot paths and looping or ping-pong patrol routes
+//! - [`PathStep`] describing what a follower did during an update
+//!
+//! Paths are assigned with `EngineCommand::FollowPath` or [`GameObject::with_path`].
+//! The engine advances them every update, moves the object through
+//! `ObjectMoved`, and emits `EngineEvent::WaypointReached` and
+//! `EngineEvent::PathCompleted`. Stunned objects stand still and speed effects
+//! scale the movement.
+//!
+//! [`GameObject::with_path`]: crate::game_object::GameObject::with_path
+
+use std::hash::{Hash, Hasher};
+
+/// What happens after the last waypoint is reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum PathMode {
+    /// Stop at the last waypoint and complete the path
+    #[default]
+    Once,
+    /// Head back to the first waypoint and start over
+    Loop,
+    /// Walk the waypoints in reverse, then forward again
+    PingPong,
+}
+
+/// Something a follower did while advancing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathStep {
+    /// Moved one cell by (dx, dy)
+    Move(i32, i32),
+    /// Arrived at the waypoint with this index
+    WaypointReached(usize),
+    /// Arrived at the last waypoint of a [`PathMode::Once`] path
+    Completed,
+}
+
+/// Moves an object cell by cell through a list of waypoints
+///
+/// # Notes
+/// - Steps are taken towards the next waypoint on both axes at once, so
+///   straight and diagonal segments move at the same number of steps per second
+/// - Any cell list works as waypoints, including a pathfinding result whose
+///   first cell is the object's own position
+/// - `Loop` and `PingPong` paths with fewer than two waypoints complete like `Once`
+///
+/// # Example
+/// ```
+/// use lonely_engine::{game_object::GameObject, path_follower::{PathFollower, PathMode}};
+///
+/// // Guard walking back and forth along a corridor at 4 cells per second
+/// let route = PathFollower::new(vec![(2, 5), (20, 5), (20, 9)], 4.0).mode(PathMode::PingPong);
+/// let guard = GameObject::new(2, 5, 'G').with_path(route);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathFollower {
+    waypoints: Vec<(usize, usize)>,
+    /// Cells per second
+    speed: f32,
+    mode: PathMode,
+    /// Index of the waypoint being walked to
+    next: usize,
+    /// Walking towards higher indices, only `false` on the way back of a ping-pong
+    forward: bool,
+    /// Steps earned but not taken yet
+    budget: f32,
+    finished: bool,
+}
+
+impl PathFollower {
+    /// Creates a one-shot path
+    ///
+    /// # Arguments
+    /// * `waypoints` - Cells visited in order
+    /// * `speed` - Cells per second
+    pub fn new(waypoints: Vec<(usize, usize)>, speed: f32) -> Self {
+        let finished = waypoints.is_empty();
+        Self { waypoints, speed: speed.max(0.0), mode: PathMode::Once, next: 0, forward: true, budget: 0.0, finished }
+    }
+
+    /// Sets what happens after the last waypoint
+    pub fn mode(mut self, mode: PathMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Cells visited in order
+    pub fn waypoints(&self) -> &[(usize, usize)] {
+        &self.waypoints
+    }
+
+    /// Cells per second
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Changes the speed without restarting the path
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    /// Index of the waypoint being walked to, `None` once finished
+    pub fn next_waypoint(&self) -> Option<usize> {
+        (!self.finished).then_some(self.next)
+    }
+
+    /// Cell being walked to, `None` once finished
+    pub fn target(&self) -> Option<(usize, usize)> {
+        self.next_waypoint().map(|index| self.waypoints[index])
+    }
+
+    /// Returns whether a one-shot path reached its last waypoint
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Walks from a position for a span of time
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Current position of the object
+    /// * `delta_time` - Seconds to walk for
+    ///
+    /// # Returns
+    /// Steps in the order they happened, applied by the engine as moves and events
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::path_follower::{PathFollower, PathStep};
+    ///
+    /// let mut path = PathFollower::new(vec![(1, 0), (1, 2)], 2.0);
+    /// assert_eq!(path.advance(0, 0, 0.5), vec![PathStep::Move(1, 0), PathStep::WaypointReached(0)]);
+    /// assert_eq!(path.advance(1, 0, 1.0), vec![PathStep::Move(0, 1), PathStep::Move(0, 1), PathStep::WaypointReached(1), PathStep::Completed]);
+    /// assert!(path.is_finished());
+    /// ```
+    pub fn advance(&mut self, x: usize, y: usize, delta_time: f32) -> Vec<PathStep> {
+        let mut steps = Vec::new();
+        if self.finished {
+            return steps;
+        }
+
+        self.budget += self.speed * delta_time;
+        let (mut x, mut y) = (x as i64, y as i64);
+        // Waypoints sharing a cell are reached without moving, stop once each was visited
+        let mut reached_in_place = 0;
+        loop {
+            let (target_x, target_y) = self.waypoints[self.next];
+            let (dx, dy) = ((target_x as i64 - x).signum(), (target_y as i64 - y).signum());
+            if (dx, dy) == (0, 0) {
+                steps.push(PathStep::WaypointReached(self.next));
+                if !self.select_next() {
+                    self.finished = true;
+                    self.budget = 0.0;
+                    steps.push(PathStep::Completed);
+                    break;
+                }
+                reached_in_place += 1;
+                if reached_in_place >= self.waypoints.len() {
+                    break;
+                }
+                continue;
+            }
+
+            if self.budget < 1.0 {
+                break;
+            }
+            self.budget -= 1.0;
+            reached_in_place = 0;
+            x += dx;
+            y += dy;
+            steps.push(PathStep::Move(dx as i32, dy as i32));
+        }
+        steps
+    }
+
+    /// Moves on to the waypoint after the current one
+    ///
+    /// # Returns
+    /// `false` when the path is over
+    fn select_next(&mut self) -> bool {
+        let last = self.waypoints.len() - 1;
+        if last == 0 {
+            return false;
+        }
+        match self.mode {
+            PathMode::Once if self.next == last => return false,
+            PathMode::Loop if self.next == last => self.next = 0,
+            PathMode::PingPong if self.forward && self.next == last => {
+                self.forward = false;
+                self.next -= 1;
+            },
+            PathMode::PingPong if !self.forward && self.next == 0 => {
+                self.forward = true;
+                self.next = 1;
+            },
+            _ if self.forward => self.next += 1,
+            _ => self.next -= 1,
+        }
+        true
+    }
+
+    /// Feeds the follower's progress into a hasher
+    pub(crate) fn hash_state(&self, hasher: &mut impl Hasher) {
+        self.waypoints.hash(hasher);
+        self.speed.to_bits().hash(hasher);
+        self.mode.hash(hasher);
+        self.next.hash(hasher);
+        self.forward.hash(hasher);
+        self.budget.to_bits().hash(hasher);
+        self.finished.hash(hasher);
+    }
+}
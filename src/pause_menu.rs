@@ -0,0 +1,253 @@
+//! Built-in pause menu
+//!
+//! [`PauseMenu`] is the Resume / Options / Quit box most games need. Once set
+//! with [`Engine::set_pause_menu`], the toggle key (Esc by default) opens it
+//! over the scene and the world waits until it is closed.
+//!
+//! # Defaults
+//! - Resume closes the menu
+//! - Options pushes the options page, or a page listing the controls of the
+//!   [`ActionMap`] given to [`PauseMenu::controls`]
+//! - Quit stops the engine
+//!
+//! Every choice also emits `EngineEvent::PauseMenuSelected`, so games add their
+//! own entries with [`PauseAction::Custom`] and react to them with a subscriber.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{engine::Engine, pause_menu::{PauseAction, PauseMenu}};
+//!
+//! let mut engine = Engine::new(80, 24);
+//! engine.set_pause_menu(Some(PauseMenu::new()
+//!     .item("Save game", PauseAction::Custom("save".into()))
+//!     .item("Quit", PauseAction::Quit)));
+//! ```
+//!
+//! [`Engine::set_pause_menu`]: crate::engine::Engine::set_pause_menu
+
+use crate::{
+    color::Color,
+    input::{InputEvent, Key},
+    keybindings::ActionMap,
+    page::Page,
+    renderer::Renderer,
+    style::Style,
+};
+
+/// Action moving the selection up when [`PauseMenu::controls`] is set
+pub const UP_ACTION: &str = "menu_up";
+/// Action moving the selection down when [`PauseMenu::controls`] is set
+pub const DOWN_ACTION: &str = "menu_down";
+/// Action choosing the selected entry when [`PauseMenu::controls`] is set
+pub const SELECT_ACTION: &str = "menu_select";
+/// Action opening and closing the menu when [`PauseMenu::controls`] is set
+pub const PAUSE_ACTION: &str = "pause";
+
+/// What a pause menu entry does
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PauseAction {
+    /// Closes the menu and resumes the game
+    Resume,
+    /// Shows the options page
+    Options,
+    /// Stops the engine
+    Quit,
+    /// Only emits `EngineEvent::PauseMenuSelected` with this id, the menu stays open
+    Custom(String),
+}
+
+/// Menu shown over the scene while the game is paused
+///
+/// # Notes
+/// - Up and Down move the selection, Enter or Space choose, the toggle key resumes
+/// - With [`controls`](Self::controls) set, the keys bound to [`UP_ACTION`],
+///   [`DOWN_ACTION`], [`SELECT_ACTION`], and [`PAUSE_ACTION`] work as well, so
+///   remapped controls carry over to the menu
+///
+/// # Example
+/// ```
+/// use lonely_engine::{input::{InputEvent, Key}, pause_menu::{PauseAction, PauseMenu}};
+///
+/// let mut menu = PauseMenu::new();
+/// assert_eq!(menu.handle(&[InputEvent::KeyDown(Key::Down)]), None);
+/// assert_eq!(menu.selected(), 1);
+/// assert_eq!(menu.handle(&[InputEvent::KeyDown(Key::Enter)]), Some(PauseAction::Options));
+/// assert_eq!(menu.handle(&[InputEvent::KeyDown(Key::Esc)]), Some(PauseAction::Resume));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PauseMenu {
+    title: String,
+    items: Vec<(String, PauseAction)>,
+    selected: usize,
+    toggle_keys: Vec<Key>,
+    controls: Option<ActionMap>,
+    options: Option<Page>,
+}
+
+impl Default for PauseMenu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PauseMenu {
+    /// Creates the default Resume / Options / Quit menu, toggled with Esc
+    pub fn new() -> Self {
+        Self {
+            title: "Paused".to_string(),
+            items: vec![
+                ("Resume".to_string(), PauseAction::Resume),
+                ("Options".to_string(), PauseAction::Options),
+                ("Quit".to_string(), PauseAction::Quit),
+            ],
+            selected: 0,
+            toggle_keys: vec![Key::Esc],
+            controls: None,
+            options: None,
+        }
+    }
+
+    /// Creates a menu without entries, for games listing their own
+    pub fn empty() -> Self {
+        Self { items: Vec::new(), ..Self::new() }
+    }
+
+    /// Sets the title shown above the entries
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    /// Appends an entry, replacing the entry with the same label
+    pub fn item(mut self, label: &str, action: PauseAction) -> Self {
+        match self.items.iter_mut().find(|(existing, _)| existing == label) {
+            Some(item) => item.1 = action,
+            None => self.items.push((label.to_string(), action)),
+        }
+        self
+    }
+
+    /// Removes the entry with a label
+    pub fn without_item(mut self, label: &str) -> Self {
+        self.items.retain(|(existing, _)| existing != label);
+        self
+    }
+
+    /// Adds a key that opens and closes the menu besides Esc
+    pub fn toggle_key(mut self, key: Key) -> Self {
+        if !self.toggle_keys.contains(&key) {
+            self.toggle_keys.push(key);
+        }
+        self
+    }
+
+    /// Uses the game's key bindings for menu navigation and the default options page
+    pub fn controls(mut self, controls: ActionMap) -> Self {
+        self.controls = Some(controls);
+        self
+    }
+
+    /// Replaces the key bindings used for navigation and the default options page
+    pub fn set_controls(&mut self, controls: ActionMap) {
+        self.controls = Some(controls);
+    }
+
+    /// Sets the page pushed by [`PauseAction::Options`]
+    pub fn options_page(mut self, page: Page) -> Self {
+        self.options = Some(page);
+        self
+    }
+
+    /// Entries as (label, action), top to bottom
+    pub fn items(&self) -> &[(String, PauseAction)] {
+        &self.items
+    }
+
+    /// Index of the selected entry
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Moves the selection back to the first entry
+    pub fn reset(&mut self) {
+        self.selected = 0;
+    }
+
+    /// Returns whether a key opens and closes the menu
+    pub fn is_toggle(&self, key: &Key) -> bool {
+        self.toggle_keys.contains(key) || self.bound(PAUSE_ACTION, key)
+    }
+
+    /// Page shown by [`PauseAction::Options`]
+    ///
+    /// # Returns
+    /// The page set with [`options_page`](Self::options_page), else a page listing
+    /// the [`controls`](Self::controls), `None` when neither is set
+    pub fn options(&self) -> Option<Page> {
+        if let Some(page) = &self.options {
+            return Some(page.clone());
+        }
+        let controls = self.controls.as_ref()?;
+        let mut page = Page::new("Controls");
+        for action in controls.actions() {
+            let keys: Vec<String> = controls.keys_for(action).iter().map(Key::to_string).collect();
+            page = page.line(&format!("  {:<16}{}", action, keys.join(", ")));
+        }
+        Some(page)
+    }
+
+    /// Navigates for this frame's key presses
+    ///
+    /// # Returns
+    /// The chosen action, [`PauseAction::Resume`] when the toggle key was pressed
+    pub fn handle(&mut self, events: &[InputEvent]) -> Option<PauseAction> {
+        for event in events {
+            let InputEvent::KeyDown(key) = event else {
+                continue;
+            };
+            if self.is_toggle(key) {
+                return Some(PauseAction::Resume);
+            }
+            if self.items.is_empty() {
+                continue;
+            }
+            if *key == Key::Up || self.bound(UP_ACTION, key) {
+                self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+            } else if *key == Key::Down || self.bound(DOWN_ACTION, key) {
+                self.selected = (self.selected + 1) % self.items.len();
+            } else if matches!(key, Key::Enter | Key::Space) || self.bound(SELECT_ACTION, key) {
+                return self.items.get(self.selected).map(|(_, action)| action.clone());
+            }
+        }
+        None
+    }
+
+    /// Draws the menu as a box centered on the screen
+    pub fn draw(&self, renderer: &mut Renderer) {
+        let label_width = self.items.iter().map(|(label, _)| label.chars().count()).chain([self.title.chars().count()]).max().unwrap_or(0);
+        let width = (label_width + 8).max(20);
+        let height = self.items.len() + 4;
+        let x = renderer.get_width().saturating_sub(width) / 2;
+        let y = renderer.get_height().saturating_sub(height) / 2;
+        let frame = Style::new().fg(Color::WHITE);
+
+        renderer.draw_styled_text(x, y, &format!("┌{}┐", "─".repeat(width - 2)), &frame);
+        for row in 1..height - 1 {
+            renderer.draw_styled_text(x, y + row, &format!("│{}│", " ".repeat(width - 2)), &frame);
+        }
+        renderer.draw_styled_text(x, y + height - 1, &format!("└{}┘", "─".repeat(width - 2)), &frame);
+
+        let centered = |text: &str| x + (width - text.chars().count()) / 2;
+        renderer.draw_styled_text(centered(&self.title), y + 1, &self.title, &Style::new().fg(Color::BRIGHT_YELLOW).bold());
+        for (index, (label, _)) in self.items.iter().enumerate() {
+            let style = if index == self.selected { Style::new().reverse() } else { Style::new() };
+            let text = format!(" {label} ");
+            renderer.draw_styled_text(centered(&text), y + 3 + index, &text, &style);
+        }
+    }
+
+    /// Returns whether a key is bound to an action of the controls
+    fn bound(&self, action: &str, key: &Key) -> bool {
+        self.controls.as_ref().is_some_and(|controls| controls.keys_for(action).contains(key))
+    }
+}
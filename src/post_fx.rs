@@ -0,0 +1,136 @@
+//! Color transforms applied to whole frames
+//!
+//! A [`PostFx`] changes the colors of every cell after the frame is composed
+//! and before it is compared with the screen, so one call darkens a night scene,
+//! flashes the screen red on damage, or greys out the world behind a pause menu
+//! without touching what draws it. Effects are set on the renderer and stay
+//! until replaced; several can be chained, each working on the result of the
+//! one before.
+//!
+//! Scanlines, flicker, and a green or amber monochrome screen give games the
+//! look of an old CRT terminal, and can be toggled at runtime like any other
+//! effect with [`Renderer::toggle_post_fx`].
+//!
+//! # Notes
+//! - The terminal's default colors are unknown, cells using them are treated as
+//!   light grey text on black
+//! - Transformed colors are true colors, reduced to what the terminal shows as usual
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{color::Color, post_fx::PostFx, renderer::Renderer};
+//!
+//! let mut renderer = Renderer::new(20, 5);
+//! renderer.set_post_fx(PostFx::Tint(Color::Rgb(0, 0, 80), 0.5));
+//! renderer.add_post_fx(PostFx::Dim(0.2));
+//! renderer.present().unwrap();
+//!
+//! renderer.clear_post_fx();
+//!
+//! // A retro terminal, switchable from the options menu
+//! renderer.set_post_fx(PostFx::Monochrome(PostFx::GREEN_PHOSPHOR));
+//! renderer.add_post_fx(PostFx::Scanlines(0.3));
+//! renderer.add_post_fx(PostFx::Flicker(0.05));
+//! assert!(!renderer.toggle_post_fx(PostFx::Flicker(0.05)));
+//! ```
+//!
+//! [`Renderer::toggle_post_fx`]: crate::renderer::Renderer::toggle_post_fx
+
+use crate::{color::Color, renderer::Cell, rng::Rng};
+
+/// Color the terminal default foreground is treated as
+const DEFAULT_FG: (u8, u8, u8) = (229, 229, 229);
+
+/// Color the terminal default background is treated as
+const DEFAULT_BG: (u8, u8, u8) = (0, 0, 0);
+
+/// Color transform applied to every cell of a frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PostFx {
+    /// Blends every color towards a color by an amount from `0.0` to `1.0`
+    Tint(Color, f32),
+    /// Darkens every color towards black by an amount from `0.0` to `1.0`
+    Dim(f32),
+    /// Replaces colors with their brightness
+    Grayscale,
+    /// Replaces colors with their opposites
+    Invert,
+    /// Replaces colors with shades of one color by their brightness, such as [`PostFx::GREEN_PHOSPHOR`]
+    Monochrome(Color),
+    /// Darkens every other row by an amount from `0.0` to `1.0`
+    Scanlines(f32),
+    /// Darkens the whole frame by a random share of an amount every frame
+    Flicker(f32),
+}
+
+impl PostFx {
+    /// Glow of a green phosphor monitor, for [`PostFx::Monochrome`]
+    pub const GREEN_PHOSPHOR: Color = Color::Rgb(51, 255, 102);
+
+    /// Glow of an amber phosphor monitor, for [`PostFx::Monochrome`]
+    pub const AMBER_PHOSPHOR: Color = Color::Rgb(255, 176, 0);
+
+    /// Returns whether the effect changes from frame to frame, redrawing the whole screen each time
+    pub fn is_animated(&self) -> bool {
+        matches!(self, PostFx::Flicker(_))
+    }
+
+    /// Transforms one color
+    ///
+    /// # Notes
+    /// - Scanlines and flicker depend on the row and frame and leave the color as is, see [`apply`](Self::apply)
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{color::Color, post_fx::PostFx};
+    ///
+    /// assert_eq!(PostFx::Invert.apply_color(Color::Rgb(255, 0, 100)), Color::Rgb(0, 255, 155));
+    /// assert_eq!(PostFx::Dim(0.5).apply_color(Color::Rgb(200, 100, 50)), Color::Rgb(100, 50, 25));
+    /// ```
+    pub fn apply_color(&self, color: Color) -> Color {
+        let Some((r, g, b)) = color.to_rgb() else {
+            return color;
+        };
+        match *self {
+            PostFx::Tint(tint, amount) => Color::lerp(color, tint, amount),
+            PostFx::Dim(amount) => Color::lerp(color, Color::Rgb(0, 0, 0), amount),
+            PostFx::Grayscale => {
+                let level = luminance(r, g, b).round() as u8;
+                Color::Rgb(level, level, level)
+            },
+            PostFx::Invert => Color::Rgb(255 - r, 255 - g, 255 - b),
+            PostFx::Monochrome(tint) => Color::lerp(Color::Rgb(0, 0, 0), tint, luminance(r, g, b) / 255.0),
+            PostFx::Scanlines(_) | PostFx::Flicker(_) => color,
+        }
+    }
+
+    /// Transforms the colors of a cell, keeping its character and attributes
+    ///
+    /// # Arguments
+    /// * `cell` - Cell to change
+    /// * `y` - Row of the cell on screen
+    /// * `frame` - Number of the presented frame, varying flicker
+    pub fn apply(&self, cell: &mut Cell, y: usize, frame: u64) {
+        let fx = match *self {
+            PostFx::Scanlines(amount) if y % 2 == 1 => PostFx::Dim(amount),
+            PostFx::Flicker(amount) => PostFx::Dim(Rng::new(frame).next_f32() * amount),
+            fx => fx,
+        };
+        cell.fg = fx.apply_color(resolve(cell.fg, DEFAULT_FG));
+        cell.bg = fx.apply_color(resolve(cell.bg, DEFAULT_BG));
+    }
+}
+
+/// Perceived brightness of a color from `0.0` to `255.0`
+fn luminance(r: u8, g: u8, b: u8) -> f32 {
+    0.299 * f32::from(r) + 0.587 * f32::from(g) + 0.114 * f32::from(b)
+}
+
+/// Replaces the terminal default with the color it is assumed to be
+fn resolve(color: Color, default: (u8, u8, u8)) -> Color {
+    match color {
+        Color::Default => Color::Rgb(default.0, default.1, default.2),
+        color => color,
+    }
+}
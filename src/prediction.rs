@@ -0,0 +1,173 @@
+//! Client-side prediction with server reconciliation
+//!
+//! Waiting a round trip before the player's own character moves makes online
+//! play feel sluggish. With a [`Prediction`] the client applies each input to
+//! its object right away, numbers it, and sends it to the server along with the
+//! number. When a server state arrives, inputs the server confirmed are
+//! dropped, the object is rolled back to the authoritative state, and the
+//! inputs still in flight are replayed on top. While client and server simulate
+//! inputs the same way the replay lands where the prediction was; when they
+//! disagree, such as the server seeing a door the client did not, the object
+//! snaps to the corrected position.
+//!
+//! The server reports the last input it applied for a client with
+//! [`ReplicationServer::acknowledge_input`], which travels with every
+//! replication message. The client marks the object with
+//! [`ReplicationClient::predict`] so applying server states leaves it alone.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{game_object::GameObject, prediction::Prediction, replication::{ReplicationClient, ReplicationServer}};
+//!
+//! fn step(obj: &mut GameObject, &(dx, dy): &(i32, i32)) {
+//!     obj.x = obj.x.saturating_add_signed(dx as isize);
+//!     obj.y = obj.y.saturating_add_signed(dy as isize);
+//! }
+//!
+//! let mut server = ReplicationServer::new();
+//! let mut world = vec![GameObject::new(5, 5, '@')];
+//! let player = server.replicate(&mut world[0]);
+//! server.add_client(1);
+//!
+//! let mut client = ReplicationClient::new(0.05);
+//! let mut local = Vec::new();
+//! server.capture(&world);
+//! client.receive(&server.encode(1)).unwrap();
+//! client.apply(&mut local);
+//! client.predict(player);
+//! let mut prediction = Prediction::new(player);
+//!
+//! // The player presses right twice and sees the moves at once
+//! let first = prediction.apply((1, 0), &mut local[0], step);
+//! prediction.apply((1, 0), &mut local[0], step);
+//! assert_eq!(local[0].x, 7);
+//!
+//! // The server has applied the first input so far
+//! step(&mut world[0], &(1, 0));
+//! server.acknowledge_input(1, first);
+//! server.capture(&world);
+//! client.receive(&server.encode(1)).unwrap();
+//!
+//! assert!(!prediction.reconcile(&client, &mut local, step));
+//! assert_eq!(local[0].x, 7);
+//! assert_eq!(prediction.pending().count(), 1);
+//! ```
+//!
+//! [`ReplicationServer::acknowledge_input`]: crate::replication::ReplicationServer::acknowledge_input
+//! [`ReplicationClient::predict`]: crate::replication::ReplicationClient::predict
+
+use std::collections::VecDeque;
+use crate::{game_object::GameObject, replication::{ObjectState, ReplicationClient}};
+
+/// Inputs kept by default while waiting for the server, two seconds of inputs at 60 per second
+pub const DEFAULT_MAX_PENDING: usize = 120;
+
+/// Predicts one replicated object from local inputs
+///
+/// # Notes
+/// - `I` is whatever the game sends as input, such as a move delta or an action enum
+#[derive(Debug, Clone)]
+pub struct Prediction<I> {
+    net_id: u32,
+    next_sequence: u32,
+    /// Inputs applied locally but not confirmed by the server, oldest first
+    pending: VecDeque<(u32, I)>,
+    max_pending: usize,
+    /// Newest server tick reconciled against
+    reconciled_tick: u32,
+    corrections: u32,
+}
+
+impl<I> Prediction<I> {
+    /// Creates a prediction for the object with a net id, numbering inputs from 1
+    pub fn new(net_id: u32) -> Self {
+        Self {
+            net_id,
+            next_sequence: 1,
+            pending: VecDeque::new(),
+            max_pending: DEFAULT_MAX_PENDING,
+            reconciled_tick: 0,
+            corrections: 0,
+        }
+    }
+
+    /// Sets how many unconfirmed inputs are kept, the oldest are dropped beyond that
+    pub fn max_pending(mut self, inputs: usize) -> Self {
+        self.max_pending = inputs.max(1);
+        self
+    }
+
+    /// Net id of the predicted object
+    pub fn net_id(&self) -> u32 {
+        self.net_id
+    }
+
+    /// Applies an input locally and buffers it until the server confirms it
+    ///
+    /// # Arguments
+    /// * `input` - Input to send to the server
+    /// * `obj` - The predicted object
+    /// * `simulate` - Applies an input to an object, the same way the server does
+    ///
+    /// # Returns
+    /// The input's sequence number, to send to the server with it
+    pub fn apply(&mut self, input: I, obj: &mut GameObject, mut simulate: impl FnMut(&mut GameObject, &I)) -> u32 {
+        simulate(obj, &input);
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        self.pending.push_back((sequence, input));
+        while self.pending.len() > self.max_pending {
+            self.pending.pop_front();
+        }
+        sequence
+    }
+
+    /// Inputs not confirmed yet with their sequence numbers, oldest first, for resending over lossy links
+    pub fn pending(&self) -> impl Iterator<Item = (u32, &I)> {
+        self.pending.iter().map(|(sequence, input)| (*sequence, input))
+    }
+
+    /// Rolls the object back to the newest server state and replays the unconfirmed inputs
+    ///
+    /// # Arguments
+    /// * `client` - Client the server states were received by
+    /// * `objects` - Local objects, including the predicted one
+    /// * `simulate` - Applies an input to an object, as in [`apply`](Self::apply)
+    ///
+    /// # Returns
+    /// Whether the prediction was wrong and the object was corrected
+    ///
+    /// # Notes
+    /// - Does nothing until a state newer than the last reconciled one arrives
+    pub fn reconcile(&mut self, client: &ReplicationClient, objects: &mut [GameObject], mut simulate: impl FnMut(&mut GameObject, &I)) -> bool {
+        let Some(latest) = client.latest().filter(|latest| latest.tick > self.reconciled_tick) else {
+            return false;
+        };
+        self.reconciled_tick = latest.tick;
+        let acknowledged = client.input_acknowledged();
+        self.pending.retain(|(sequence, _)| *sequence > acknowledged);
+
+        let (Some(authoritative), Some(obj)) = (
+            latest.objects.get(&self.net_id),
+            objects.iter_mut().find(|obj| obj.net_id == Some(self.net_id)),
+        ) else {
+            return false;
+        };
+        let predicted = ObjectState::from_object(obj);
+        authoritative.apply_to(obj);
+        for (_, input) in &self.pending {
+            simulate(obj, input);
+        }
+
+        let corrected = ObjectState::from_object(obj) != predicted;
+        if corrected {
+            self.corrections += 1;
+        }
+        corrected
+    }
+
+    /// Number of reconciliations that corrected the object, a measure of how often client and server disagree
+    pub fn corrections(&self) -> u32 {
+        self.corrections
+    }
+}
@@ -0,0 +1,178 @@
+//! Per-phase frame profiling
+//!
+//! Measures how long each part of a frame takes so it is clear whether rendering,
+//! command processing, or a particular updatable is eating the frame budget.
+//! Provides:
+//! - [`FrameTimings`] with durations for every engine phase and updatable
+//! - [`FrameProfile`] with the last frame and a rolling average
+//! - [`Profiler`] collecting samples over a fixed window
+
+use std::{collections::VecDeque, time::Duration};
+
+/// Number of frames averaged by default
+pub const DEFAULT_PROFILE_WINDOW: usize = 60;
+
+/// Time spent in each phase of a single frame
+///
+/// # Notes
+/// - `events` is the time spent inside event subscribers. It overlaps with the
+///   phase that emitted the events (input transitions, animation, commands)
+#[derive(Debug, Clone, Default)]
+pub struct FrameTimings {
+    /// Reading hardware input
+    pub input: Duration,
+    /// Advancing object animations
+    pub animation: Duration,
+    /// Each registered updatable, in registration order
+    pub updatables: Vec<(String, Duration)>,
+    /// Applying queued engine commands
+    pub commands: Duration,
+    /// Dispatching events to subscribers
+    pub events: Duration,
+    /// Building and presenting the frame
+    pub render: Duration,
+    /// Whole frame excluding the frame limiter sleep
+    pub total: Duration,
+    /// Time since the previous frame was presented, including the frame limiter wait
+    pub frame_time: Duration,
+    /// How far `frame_time` was off the render rate's interval, see [`pacing`](crate::pacing)
+    pub jitter: Duration,
+}
+
+impl FrameTimings {
+    /// Combined time of all updatables
+    pub fn updatables_total(&self) -> Duration {
+        self.updatables.iter().map(|(_, duration)| *duration).sum()
+    }
+}
+
+/// Snapshot of profiling data exposed by [`Engine::frame_profile`]
+///
+/// # Example
+/// ```
+/// # use lonely_engine::engine::Engine;
+/// let engine = Engine::new(80, 24);
+/// let profile = engine.frame_profile();
+/// println!("render: {:?} avg over {} frames", profile.average.render, profile.frames_sampled);
+/// ```
+///
+/// [`Engine::frame_profile`]: crate::engine::Engine::frame_profile
+#[derive(Debug, Clone, Default)]
+pub struct FrameProfile {
+    /// Timings of the most recently completed frame
+    pub last: FrameTimings,
+    /// Rolling average over the profiler window
+    pub average: FrameTimings,
+    /// Number of frames contributing to `average`
+    pub frames_sampled: usize,
+}
+
+impl FrameProfile {
+    /// Formats the average timings as one line per phase, suitable for an on-screen overlay
+    pub fn breakdown_lines(&self) -> Vec<String> {
+        let average = &self.average;
+        let mut lines = vec![
+            format!("frame    {:>7.2}ms", millis(average.total)),
+            format!("input    {:>7.2}ms", millis(average.input)),
+            format!("anim     {:>7.2}ms", millis(average.animation)),
+            format!("update   {:>7.2}ms", millis(average.updatables_total())),
+        ];
+        for (name, duration) in &average.updatables {
+            lines.push(format!(" {:<8.8}{:>7.2}ms", short_type_name(name), millis(*duration)));
+        }
+        lines.push(format!("commands {:>7.2}ms", millis(average.commands)));
+        lines.push(format!("events   {:>7.2}ms", millis(average.events)));
+        lines.push(format!("render   {:>7.2}ms", millis(average.render)));
+        lines.push(format!("jitter   {:>7.2}ms", millis(average.jitter)));
+        lines
+    }
+}
+
+/// Collects frame timings and maintains rolling averages
+#[derive(Debug)]
+pub struct Profiler {
+    window: usize,
+    history: VecDeque<FrameTimings>,
+    profile: FrameProfile,
+}
+
+impl Profiler {
+    /// Creates a profiler averaging over `window` frames
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(1),
+            history: VecDeque::new(),
+            profile: FrameProfile::default(),
+        }
+    }
+
+    /// Records a completed frame and refreshes the rolling average
+    pub fn record(&mut self, timings: FrameTimings) {
+        if self.history.len() == self.window {
+            self.history.pop_front();
+        }
+        self.history.push_back(timings.clone());
+
+        self.profile.last = timings;
+        self.profile.frames_sampled = self.history.len();
+        self.profile.average = self.average();
+    }
+
+    /// Returns the latest profile snapshot
+    pub fn profile(&self) -> &FrameProfile {
+        &self.profile
+    }
+
+    fn average(&self) -> FrameTimings {
+        let count = self.history.len().max(1) as u32;
+        let mut average = FrameTimings::default();
+
+        for timings in &self.history {
+            average.input += timings.input;
+            average.animation += timings.animation;
+            average.commands += timings.commands;
+            average.events += timings.events;
+            average.render += timings.render;
+            average.total += timings.total;
+            average.frame_time += timings.frame_time;
+            average.jitter += timings.jitter;
+
+            for (name, duration) in &timings.updatables {
+                match average.updatables.iter_mut().find(|(existing, _)| existing == name) {
+                    Some((_, total)) => *total += *duration,
+                    None => average.updatables.push((name.clone(), *duration)),
+                }
+            }
+        }
+
+        average.input /= count;
+        average.animation /= count;
+        average.commands /= count;
+        average.events /= count;
+        average.render /= count;
+        average.total /= count;
+        average.frame_time /= count;
+        average.jitter /= count;
+        for (_, duration) in &mut average.updatables {
+            *duration /= count;
+        }
+
+        average
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new(DEFAULT_PROFILE_WINDOW)
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Strips module paths from a type name, keeping generic arguments readable
+fn short_type_name(name: &str) -> &str {
+    let base = name.split('<').next().unwrap_or(name);
+    base.rsplit("::").next().unwrap_or(base)
+}
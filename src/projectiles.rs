@@ -0,0 +1,263 @@
+//! Projectiles flying in a straight line until they hit something
+//!
+//! Provides:
+//! - [`Projectile`] component moving an object at a speed for a limited range
+//! - [`OnHit`] choosing whether a hit despawns, pierces, or bounces the projectile
+//! - [`ProjectileStep`] describing what a projectile did during an update
+//!
+//! Projectiles are attached with [`GameObject::with_projectile`] and spawned like
+//! any object. The engine advances them every update one cell at a time, so fast
+//! shots do not skip over thin walls, and tests each cell against the solid
+//! objects of the [occupancy map](crate::occupancy). It emits:
+//! - `EngineEvent::ProjectileHit` with the projectile and the object it hit,
+//!   never its owner
+//! - `EngineEvent::ProjectileExpired` when it flew its range or left the world,
+//!   right before despawning it
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{
+//!     direction::Direction,
+//!     engine::EngineCommand,
+//!     game_object::GameObject,
+//!     projectiles::{OnHit, Projectile},
+//! };
+//!
+//! let player_index = 0;
+//! let arrow = Projectile::new(Direction::Right.vector(), 20.0)
+//!     .range(12.0)
+//!     .owner(player_index)
+//!     .on_hit(OnHit::Pierce(1));
+//! let command = EngineCommand::SpawnObject(GameObject::new(5, 5, '-').with_projectile(arrow));
+//! ```
+//!
+//! [`GameObject::with_projectile`]: crate::game_object::GameObject::with_projectile
+
+/// What a projectile does when it hits a solid object
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OnHit {
+    /// Despawn on the first hit
+    #[default]
+    Despawn,
+    /// Fly through this many objects, despawning on the hit after that
+    Pierce(u32),
+    /// Bounce off this many objects or world edges, despawning on the hit after that
+    Bounce(u32),
+}
+
+/// Something a projectile did while advancing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectileStep {
+    /// Moved one cell by (dx, dy)
+    Move(i32, i32),
+    /// Flew its full range
+    Expired,
+}
+
+/// Result of a hit, decided by [`OnHit`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitOutcome {
+    /// The projectile is used up
+    Despawn,
+    /// The projectile flies on through the object
+    PassThrough,
+    /// The projectile turned around and stays in its cell
+    Bounced,
+}
+
+/// Moves an object in a straight line and reports what it runs into
+///
+/// # Example
+/// ```
+/// use lonely_engine::projectiles::{Projectile, ProjectileStep};
+///
+/// let mut bullet = Projectile::new((1.0, 0.0), 10.0).range(3.0);
+/// assert_eq!(bullet.advance(0.2), vec![ProjectileStep::Move(1, 0), ProjectileStep::Move(1, 0)]);
+/// assert_eq!(bullet.advance(0.2), vec![ProjectileStep::Move(1, 0), ProjectileStep::Expired]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Projectile {
+    /// Unit vector of flight
+    direction: (f32, f32),
+    /// Cells per second
+    speed: f32,
+    /// Cells to fly before expiring
+    range: f32,
+    /// Cells flown so far
+    traveled: f32,
+    /// Index of the object that fired, never hit
+    owner: Option<usize>,
+    on_hit: OnHit,
+    /// Pierces or bounces left
+    remaining: u32,
+    /// Objects already pierced, not hit again
+    pierced: Vec<usize>,
+    /// Distance from the center of the current cell
+    offset: (f32, f32),
+}
+
+impl Projectile {
+    /// Creates a projectile that despawns on its first hit and flies until it leaves the world
+    ///
+    /// # Arguments
+    /// * `direction` - Direction of flight, normalized, such as `Direction::UpLeft.vector()`
+    /// * `speed` - Cells per second
+    pub fn new(direction: (f32, f32), speed: f32) -> Self {
+        let length = direction.0.hypot(direction.1);
+        let direction = if length > f32::EPSILON { (direction.0 / length, direction.1 / length) } else { (0.0, 0.0) };
+        Self {
+            direction,
+            speed: speed.max(0.0),
+            range: f32::INFINITY,
+            traveled: 0.0,
+            owner: None,
+            on_hit: OnHit::Despawn,
+            remaining: 0,
+            pierced: Vec::new(),
+            offset: (0.0, 0.0),
+        }
+    }
+
+    /// Sets how many cells the projectile flies before expiring
+    pub fn range(mut self, cells: f32) -> Self {
+        self.range = cells.max(0.0);
+        self
+    }
+
+    /// Sets the object that fired the projectile, which it never hits
+    pub fn owner(mut self, index: usize) -> Self {
+        self.owner = Some(index);
+        self
+    }
+
+    /// Sets what happens on a hit
+    pub fn on_hit(mut self, on_hit: OnHit) -> Self {
+        self.on_hit = on_hit;
+        self.remaining = match on_hit {
+            OnHit::Despawn => 0,
+            OnHit::Pierce(count) | OnHit::Bounce(count) => count,
+        };
+        self
+    }
+
+    /// Unit vector of flight
+    pub fn direction(&self) -> (f32, f32) {
+        self.direction
+    }
+
+    /// Cells per second
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Index of the object that fired, `None` once it despawned
+    pub fn get_owner(&self) -> Option<usize> {
+        self.owner
+    }
+
+    /// Cells flown so far
+    pub fn traveled(&self) -> f32 {
+        self.traveled
+    }
+
+    /// Returns whether an object can be hit: not the owner and not pierced already
+    pub fn can_hit(&self, index: usize) -> bool {
+        self.owner != Some(index) && !self.pierced.contains(&index)
+    }
+
+    /// Flies for a span of time
+    ///
+    /// # Returns
+    /// Single-cell moves in the order they happened, then `Expired` once the range is flown
+    pub fn advance(&mut self, delta_time: f32) -> Vec<ProjectileStep> {
+        let distance = (self.speed * delta_time).min(self.range - self.traveled).max(0.0);
+        self.traveled += distance;
+
+        // Half-cell substeps never cross more than one cell boundary per axis
+        let substeps = (distance / 0.5).ceil().max(1.0) as usize;
+        let step_length = distance / substeps as f32;
+        let mut steps = Vec::new();
+        for _ in 0..substeps {
+            self.offset.0 += self.direction.0 * step_length;
+            self.offset.1 += self.direction.1 * step_length;
+            let (dx, dy) = (self.offset.0.round(), self.offset.1.round());
+            if dx != 0.0 || dy != 0.0 {
+                self.offset.0 -= dx;
+                self.offset.1 -= dy;
+                steps.push(ProjectileStep::Move(dx as i32, dy as i32));
+            }
+        }
+        if self.traveled >= self.range {
+            steps.push(ProjectileStep::Expired);
+        }
+        steps
+    }
+
+    /// Applies a hit according to [`OnHit`]
+    ///
+    /// # Arguments
+    /// * `target` - Index of the object hit
+    /// * `flip_x`, `flip_y` - Axes blocked by the object, reversed when bouncing
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::projectiles::{HitOutcome, OnHit, Projectile};
+    ///
+    /// let mut ball = Projectile::new((1.0, 1.0), 8.0).on_hit(OnHit::Bounce(1));
+    /// assert_eq!(ball.hit(3, true, false), HitOutcome::Bounced);
+    /// assert!(ball.direction().0 < 0.0 && ball.direction().1 > 0.0);
+    /// assert_eq!(ball.hit(3, false, true), HitOutcome::Despawn);
+    /// ```
+    pub fn hit(&mut self, target: usize, flip_x: bool, flip_y: bool) -> HitOutcome {
+        if self.on_hit == OnHit::Despawn || self.remaining == 0 {
+            return HitOutcome::Despawn;
+        }
+        self.remaining -= 1;
+        match self.on_hit {
+            OnHit::Pierce(_) => {
+                self.pierced.push(target);
+                HitOutcome::PassThrough
+            },
+            _ => {
+                self.bounce(flip_x, flip_y);
+                HitOutcome::Bounced
+            },
+        }
+    }
+
+    /// Leaves the world through one of its edges
+    ///
+    /// # Returns
+    /// Whether the projectile bounced back instead of expiring
+    pub fn hit_edge(&mut self, flip_x: bool, flip_y: bool) -> bool {
+        if !matches!(self.on_hit, OnHit::Bounce(_)) || self.remaining == 0 {
+            return false;
+        }
+        self.remaining -= 1;
+        self.bounce(flip_x, flip_y);
+        true
+    }
+
+    /// Reverses the blocked axes, both when neither is given, and recenters in the cell
+    fn bounce(&mut self, flip_x: bool, flip_y: bool) {
+        let (flip_x, flip_y) = if flip_x || flip_y { (flip_x, flip_y) } else { (true, true) };
+        if flip_x {
+            self.direction.0 = -self.direction.0;
+        }
+        if flip_y {
+            self.direction.1 = -self.direction.1;
+        }
+        self.offset = (0.0, 0.0);
+    }
+
+    /// Keeps object indices valid after the object at `index` was removed
+    pub fn object_removed(&mut self, index: usize) {
+        let shift = |existing: usize| match existing.cmp(&index) {
+            std::cmp::Ordering::Less => Some(existing),
+            std::cmp::Ordering::Equal => None,
+            std::cmp::Ordering::Greater => Some(existing - 1),
+        };
+        self.owner = self.owner.and_then(shift);
+        self.pierced = self.pierced.iter().filter_map(|&existing| shift(existing)).collect();
+    }
+}
@@ -0,0 +1,631 @@
+//! Quests, their objectives, and the player's journal
+//!
+//! Provides:
+//! - [`QuestDef`] made of [`Stage`]s, each finished once all of its
+//!   [`Objective`]s are met
+//! - [`Condition`]s over engine events, counting towards an objective or
+//!   failing a quest
+//! - [`QuestLog`] tracking the progress of every started quest, saved and
+//!   loaded between sessions as TOML
+//! - [`QuestJournal`] widget listing the started quests and what is left to do
+//!
+//! The engine owns a [`QuestLog`] and shows it every event emitted on the
+//! event bus, so objectives are met by the events the game emits anyway,
+//! such as `ZoneEntered` or `Custom("Killed:wolf")`. Quests are started,
+//! advanced, and failed with `EngineCommand::StartQuest`,
+//! `EngineCommand::AdvanceQuest`, and `EngineCommand::FailQuest`, and the
+//! engine emits `QuestStarted`, `QuestAdvanced`, `QuestCompleted`, and
+//! `QuestFailed`.
+//!
+//! # File format
+//! ```toml
+//! [wolves]
+//! state = "active"
+//! stage = 0
+//! counts = [2]
+//! ```
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{
+//!     event::{EngineEvent, EventFilter},
+//!     quests::{Condition, Objective, QuestDef, QuestLog, QuestState, Stage},
+//! };
+//!
+//! let mut quests = QuestLog::new();
+//! quests.add(
+//!     QuestDef::new("wolves", "Wolf Trouble")
+//!         .stage(Stage::new("Thin out the pack").objective(Objective::new("Slay wolves", EventFilter::custom("Killed:wolf")).times(2)))
+//!         .stage(Stage::new("Report back").objective(Objective::new(
+//!             "Return to the village",
+//!             Condition::Predicate(|event| matches!(event, EngineEvent::ZoneEntered(_, zone) if zone == "village")),
+//!         ))),
+//! );
+//! quests.start("wolves");
+//!
+//! quests.observe(&EngineEvent::Custom("Killed:wolf".into()));
+//! assert_eq!(quests.count("wolves", 0), Some(1));
+//! quests.observe(&EngineEvent::Custom("Killed:wolf".into()));
+//! assert_eq!(quests.stage("wolves"), Some(1));
+//!
+//! quests.observe(&EngineEvent::ZoneEntered(0, "village".into()));
+//! assert_eq!(quests.state("wolves"), Some(QuestState::Completed));
+//! ```
+
+use std::{fs, io, path::Path};
+use crate::{
+    color::Color,
+    event::{EngineEvent, EventFilter, EventKind},
+    input::{InputEvent, Key},
+    renderer::Renderer,
+    style::Style,
+    toml::{TomlDocument, TomlValue},
+};
+
+/// Decides whether an event meets a condition
+pub type EventPredicate = fn(&EngineEvent) -> bool;
+
+/// Events counting towards an objective or failing a quest
+#[derive(Debug, Clone)]
+pub enum Condition {
+    /// Events passing a filter, such as `EventFilter::custom("Killed:wolf")`
+    Filter(EventFilter),
+    /// Events a function accepts, for conditions on event data
+    Predicate(EventPredicate),
+}
+
+impl Condition {
+    /// Returns whether an event meets the condition
+    pub fn matches(&self, event: &EngineEvent) -> bool {
+        match self {
+            Condition::Filter(filter) => filter.matches(event),
+            Condition::Predicate(predicate) => predicate(event),
+        }
+    }
+}
+
+impl From<EventFilter> for Condition {
+    fn from(filter: EventFilter) -> Self {
+        Condition::Filter(filter)
+    }
+}
+
+impl From<EventKind> for Condition {
+    fn from(kind: EventKind) -> Self {
+        Condition::Filter(EventFilter::Kind(kind))
+    }
+}
+
+/// Something to do for a stage, met after its condition held a number of times
+#[derive(Debug, Clone)]
+pub struct Objective {
+    /// Text shown in the journal
+    pub description: String,
+    /// Events counting towards the objective
+    pub condition: Condition,
+    /// Matching events needed
+    pub required: u32,
+}
+
+impl Objective {
+    /// Creates an objective met by one matching event
+    pub fn new(description: &str, condition: impl Into<Condition>) -> Self {
+        Self { description: description.to_string(), condition: condition.into(), required: 1 }
+    }
+
+    /// Sets how many matching events are needed, at least one
+    pub fn times(mut self, required: u32) -> Self {
+        self.required = required.max(1);
+        self
+    }
+}
+
+/// Step of a quest, finished once every objective is met
+///
+/// # Notes
+/// - A stage without objectives finishes as soon as it is reached, such as a closing line in the journal
+#[derive(Debug, Clone)]
+pub struct Stage {
+    /// Text shown in the journal while the stage is current
+    pub description: String,
+    /// Objectives to meet, in any order
+    pub objectives: Vec<Objective>,
+}
+
+impl Stage {
+    /// Creates a stage without objectives
+    pub fn new(description: &str) -> Self {
+        Self { description: description.to_string(), objectives: Vec::new() }
+    }
+
+    /// Adds an objective and returns the stage, for builder-style construction
+    pub fn objective(mut self, objective: Objective) -> Self {
+        self.objectives.push(objective);
+        self
+    }
+}
+
+/// Definition of a quest
+#[derive(Debug, Clone)]
+pub struct QuestDef {
+    /// Identifier stored in save files and events
+    pub id: String,
+    /// Name shown to players
+    pub title: String,
+    /// Text shown in the journal under the title
+    pub description: String,
+    /// Stages in the order they are done
+    pub stages: Vec<Stage>,
+    /// Events failing the quest while it is active, such as the quest giver dying
+    pub fail_on: Option<Condition>,
+}
+
+impl QuestDef {
+    /// Creates a quest without stages
+    pub fn new(id: &str, title: &str) -> Self {
+        Self { id: id.to_string(), title: title.to_string(), description: String::new(), stages: Vec::new(), fail_on: None }
+    }
+
+    /// Sets the text shown under the title
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Adds a stage and returns the quest, for builder-style construction
+    pub fn stage(mut self, stage: Stage) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Fails the quest on events meeting a condition while it is active
+    pub fn fail_on(mut self, condition: impl Into<Condition>) -> Self {
+        self.fail_on = Some(condition.into());
+        self
+    }
+}
+
+/// Where a started quest stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QuestState {
+    /// Under way
+    Active,
+    /// Every stage finished
+    Completed,
+    /// Failed by its condition or by command
+    Failed,
+}
+
+impl QuestState {
+    /// Name used in save files
+    pub fn name(self) -> &'static str {
+        match self {
+            QuestState::Active => "active",
+            QuestState::Completed => "completed",
+            QuestState::Failed => "failed",
+        }
+    }
+
+    /// Parses a name written by [`name`](Self::name)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "active" => Some(QuestState::Active),
+            "completed" => Some(QuestState::Completed),
+            "failed" => Some(QuestState::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// Progress of a started quest
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuestProgress {
+    /// Where the quest stands
+    pub state: QuestState,
+    /// Index of the current stage, the number of stages once completed
+    pub stage: usize,
+    /// Matching events seen for each objective of the current stage
+    pub counts: Vec<u32>,
+}
+
+/// Something that happened to a quest, turned into engine events
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuestEvent {
+    /// The quest was started
+    Started(String),
+    /// The quest moved on to the stage at an index
+    Advanced(String, usize),
+    /// The last stage was finished
+    Completed(String),
+    /// The quest failed
+    Failed(String),
+}
+
+/// Quest definitions and the progress of every started quest
+///
+/// # Notes
+/// - A quest starts at most once, completed and failed quests stay in the log
+/// - Progress of quests without a definition is kept, so a save loads before the definitions do
+#[derive(Debug, Clone, Default)]
+pub struct QuestLog {
+    defs: Vec<QuestDef>,
+    /// Started quests in the order they were started
+    progress: Vec<(String, QuestProgress)>,
+    /// Happened since the last `take_events`
+    events: Vec<QuestEvent>,
+}
+
+impl QuestLog {
+    /// Creates a log without quests
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a quest, replacing the definition with the same id
+    pub fn add(&mut self, def: QuestDef) {
+        self.defs.retain(|existing| existing.id != def.id);
+        self.defs.push(def);
+    }
+
+    /// Definition of a quest
+    pub fn get(&self, id: &str) -> Option<&QuestDef> {
+        self.defs.iter().find(|def| def.id == id)
+    }
+
+    /// Registered quests in registration order
+    pub fn definitions(&self) -> &[QuestDef] {
+        &self.defs
+    }
+
+    /// Returns whether no quest is registered
+    pub fn is_empty(&self) -> bool {
+        self.defs.is_empty()
+    }
+
+    /// Starts a registered quest at its first stage
+    ///
+    /// # Returns
+    /// `false` when the quest is unknown or was started before
+    pub fn start(&mut self, id: &str) -> bool {
+        if self.get(id).is_none() || self.progress(id).is_some() {
+            return false;
+        }
+        self.progress.push((id.to_string(), QuestProgress { state: QuestState::Active, stage: 0, counts: Vec::new() }));
+        self.events.push(QuestEvent::Started(id.to_string()));
+        self.settle(self.progress.len() - 1);
+        true
+    }
+
+    /// Finishes the current stage of an active quest whatever its objectives, such as from a cutscene
+    ///
+    /// # Returns
+    /// `false` when the quest is not active
+    pub fn advance(&mut self, id: &str) -> bool {
+        let Some(index) = self.active_index(id) else {
+            return false;
+        };
+        self.next_stage(index);
+        self.settle(index);
+        true
+    }
+
+    /// Fails an active quest
+    ///
+    /// # Returns
+    /// `false` when the quest is not active
+    pub fn fail(&mut self, id: &str) -> bool {
+        let Some(index) = self.active_index(id) else {
+            return false;
+        };
+        self.progress[index].1.state = QuestState::Failed;
+        self.events.push(QuestEvent::Failed(id.to_string()));
+        true
+    }
+
+    /// Progress of a started quest
+    pub fn progress(&self, id: &str) -> Option<&QuestProgress> {
+        self.progress.iter().find(|(quest, _)| quest == id).map(|(_, progress)| progress)
+    }
+
+    /// Where a quest stands, `None` when it was never started
+    pub fn state(&self, id: &str) -> Option<QuestState> {
+        self.progress(id).map(|progress| progress.state)
+    }
+
+    /// Index of the current stage of a started quest
+    pub fn stage(&self, id: &str) -> Option<usize> {
+        self.progress(id).map(|progress| progress.stage)
+    }
+
+    /// Current stage of an active quest
+    pub fn current_stage(&self, id: &str) -> Option<&Stage> {
+        let progress = self.progress(id).filter(|progress| progress.state == QuestState::Active)?;
+        self.get(id)?.stages.get(progress.stage)
+    }
+
+    /// Matching events seen for an objective of the current stage
+    pub fn count(&self, id: &str, objective: usize) -> Option<u32> {
+        self.current_stage(id)?.objectives.get(objective)?;
+        Some(self.progress(id)?.counts.get(objective).copied().unwrap_or(0))
+    }
+
+    /// Returns whether a quest is under way
+    pub fn is_active(&self, id: &str) -> bool {
+        self.state(id) == Some(QuestState::Active)
+    }
+
+    /// Returns whether a quest was completed
+    pub fn is_completed(&self, id: &str) -> bool {
+        self.state(id) == Some(QuestState::Completed)
+    }
+
+    /// Started quests with their progress, in the order they were started
+    pub fn started(&self) -> impl Iterator<Item = (&str, &QuestProgress)> {
+        self.progress.iter().map(|(id, progress)| (id.as_str(), progress))
+    }
+
+    /// Counts an event towards the objectives of every active quest, failing or advancing them
+    ///
+    /// # Notes
+    /// - Called by the engine for every event emitted on its event bus
+    pub fn observe(&mut self, event: &EngineEvent) {
+        for index in 0..self.progress.len() {
+            if self.progress[index].1.state != QuestState::Active {
+                continue;
+            }
+            let Some(def) = self.defs.iter().find(|def| def.id == self.progress[index].0) else {
+                continue;
+            };
+            if def.fail_on.as_ref().is_some_and(|condition| condition.matches(event)) {
+                let id = self.progress[index].0.clone();
+                self.fail(&id);
+                continue;
+            }
+            let Some(stage) = def.stages.get(self.progress[index].1.stage) else {
+                continue;
+            };
+            let counts = &mut self.progress[index].1.counts;
+            counts.resize(stage.objectives.len(), 0);
+            let mut counted = false;
+            for (objective, count) in stage.objectives.iter().zip(counts.iter_mut()) {
+                if *count < objective.required && objective.condition.matches(event) {
+                    *count += 1;
+                    counted = true;
+                }
+            }
+            if counted {
+                self.settle(index);
+            }
+        }
+    }
+
+    /// Drains what happened to quests since the previous call, in order
+    pub fn take_events(&mut self) -> Vec<QuestEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Forgets every started quest, keeping the definitions
+    pub fn reset(&mut self) {
+        self.progress.clear();
+        self.events.clear();
+    }
+
+    /// Formats the progress of every started quest as TOML
+    pub fn to_toml(&self) -> String {
+        let mut doc = TomlDocument::new();
+        for (id, progress) in &self.progress {
+            doc.set(id, "state", progress.state.name());
+            doc.set(id, "stage", progress.stage as i64);
+            let counts = progress.counts.iter().map(|count| TomlValue::Integer(i64::from(*count))).collect();
+            doc.set(id, "counts", TomlValue::Array(counts));
+        }
+        doc.to_string()
+    }
+
+    /// Replaces the progress of started quests with the progress in TOML text
+    ///
+    /// # Notes
+    /// - Loading reports no quest events
+    ///
+    /// # Returns
+    /// `Err` with [`io::ErrorKind::InvalidData`] if the text is not valid TOML or a quest has an unknown state
+    pub fn load_toml(&mut self, text: &str) -> io::Result<()> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let doc = TomlDocument::parse(text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        let mut progress = Vec::new();
+        for id in doc.table_names() {
+            let state = doc.get(id, "state").and_then(TomlValue::as_str).and_then(QuestState::from_name);
+            let state = state.ok_or_else(|| invalid(format!("quest `{id}` needs a `state` of active, completed, or failed")))?;
+            let stage = doc.get(id, "stage").and_then(TomlValue::as_integer).and_then(|stage| usize::try_from(stage).ok()).unwrap_or(0);
+            let counts = doc.get(id, "counts").and_then(TomlValue::as_array).unwrap_or(&[]);
+            let counts = counts.iter().filter_map(TomlValue::as_integer).map(|count| u32::try_from(count).unwrap_or(0)).collect();
+            progress.push((id.to_string(), QuestProgress { state, stage, counts }));
+        }
+        self.progress = progress;
+        Ok(())
+    }
+
+    /// Loads progress saved by [`save`](Self::save)
+    ///
+    /// # Returns
+    /// * `Ok(())` without changes when the file does not exist yet
+    /// * `Err` if the file is unreadable or malformed
+    pub fn load(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        match fs::read_to_string(path) {
+            Ok(text) => self.load_toml(&text),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Writes the progress of every started quest to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_toml())
+    }
+
+    fn active_index(&self, id: &str) -> Option<usize> {
+        self.progress.iter().position(|(quest, progress)| quest == id && progress.state == QuestState::Active)
+    }
+
+    /// Moves an active quest past its current stage, completing it after the last
+    fn next_stage(&mut self, index: usize) {
+        let stages = self.get(&self.progress[index].0).map_or(0, |def| def.stages.len());
+        let (id, progress) = &mut self.progress[index];
+        progress.stage += 1;
+        progress.counts.clear();
+        if progress.stage >= stages {
+            progress.stage = stages;
+            progress.state = QuestState::Completed;
+            self.events.push(QuestEvent::Completed(id.clone()));
+        } else {
+            self.events.push(QuestEvent::Advanced(id.clone(), progress.stage));
+        }
+    }
+
+    /// Finishes every stage in a row whose objectives are all met
+    fn settle(&mut self, index: usize) {
+        while self.progress[index].1.state == QuestState::Active {
+            let (id, progress) = &self.progress[index];
+            let Some(def) = self.get(id) else {
+                return;
+            };
+            let met = def.stages.get(progress.stage).is_none_or(|stage| {
+                stage.objectives.iter().enumerate().all(|(objective, goal)| progress.counts.get(objective).copied().unwrap_or(0) >= goal.required)
+            });
+            if !met {
+                return;
+            }
+            self.next_stage(index);
+        }
+    }
+}
+
+/// Widget listing the started quests, with the current stage of the selected one
+///
+/// # Controls
+/// - Up and Down select a quest
+/// - Escape closes the journal
+///
+/// # Example
+/// ```
+/// use lonely_engine::{
+///     event::EventKind,
+///     input::{InputEvent, Key},
+///     quests::{Objective, QuestDef, QuestJournal, QuestLog, Stage},
+///     renderer::Renderer,
+/// };
+///
+/// let mut quests = QuestLog::new();
+/// quests.add(QuestDef::new("letter", "A Letter Home").stage(Stage::new("Find a courier").objective(Objective::new("Talk to anyone", EventKind::DialogueEnded))));
+/// quests.add(QuestDef::new("rats", "Rats!").stage(Stage::new("Clear the cellar")));
+/// quests.start("letter");
+/// quests.start("rats");
+///
+/// let mut journal = QuestJournal::new();
+/// assert!(!journal.handle(&quests, &[InputEvent::KeyDown(Key::Down)]));
+/// assert_eq!(journal.selected(&quests), Some("rats"));
+/// assert!(journal.handle(&quests, &[InputEvent::KeyDown(Key::Esc)]));
+///
+/// let mut renderer = Renderer::new(60, 16);
+/// journal.draw(&mut renderer, &quests, 1, 1);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuestJournal {
+    cursor: usize,
+}
+
+impl QuestJournal {
+    /// Creates a journal with the first quest selected
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index of the selected quest among the started ones
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Id of the selected quest
+    pub fn selected<'a>(&self, log: &'a QuestLog) -> Option<&'a str> {
+        log.started().nth(self.cursor).map(|(id, _)| id)
+    }
+
+    /// Handles this frame's input
+    ///
+    /// # Returns
+    /// `true` when the player closes the journal
+    pub fn handle(&mut self, log: &QuestLog, events: &[InputEvent]) -> bool {
+        let rows = log.started().count();
+        self.cursor = self.cursor.min(rows.saturating_sub(1));
+        for event in events {
+            match event {
+                InputEvent::KeyDown(Key::Up) => self.cursor = self.cursor.saturating_sub(1),
+                InputEvent::KeyDown(Key::Down) if self.cursor + 1 < rows => self.cursor += 1,
+                InputEvent::KeyDown(Key::Esc) => return true,
+                _ => {},
+            }
+        }
+        false
+    }
+
+    /// Draws the quest titles, then the description and current objectives of the selected quest
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Top-left cell of the journal
+    ///
+    /// # Notes
+    /// - Completed quests are greyed out and failed ones red
+    /// - Objectives show their count towards the number needed, met ones checked
+    pub fn draw(&self, renderer: &mut Renderer, log: &QuestLog, x: usize, y: usize) {
+        renderer.draw_styled_text(x, y, "Journal", &Style::new().bold());
+        let mut row = y + 2;
+        for (index, (id, progress)) in log.started().enumerate() {
+            let title = log.get(id).map_or(id, |def| def.title.as_str());
+            let mut style = match progress.state {
+                QuestState::Active => Style::new(),
+                QuestState::Completed => Style::new().fg(Color::GREY),
+                QuestState::Failed => Style::new().fg(Color::RED),
+            };
+            if index == self.cursor {
+                style = style.reverse();
+            }
+            let line = match progress.state {
+                QuestState::Active => title.to_string(),
+                state => format!("{title} ({})", state.name()),
+            };
+            renderer.draw_styled_text(x, row, &line, &style);
+            row += 1;
+        }
+
+        let Some(id) = self.selected(log) else {
+            return;
+        };
+        let Some(def) = log.get(id) else {
+            return;
+        };
+        row += 1;
+        renderer.draw_styled_text(x, row, &def.title, &Style::new().bold());
+        if !def.description.is_empty() {
+            row += 1;
+            renderer.draw_text(x, row, &def.description);
+        }
+        let Some(stage) = log.current_stage(id) else {
+            return;
+        };
+        row += 2;
+        renderer.draw_styled_text(x, row, &stage.description, &Style::new().italic());
+        for (index, objective) in stage.objectives.iter().enumerate() {
+            row += 1;
+            let count = log.count(id, index).unwrap_or(0);
+            let mark = if count >= objective.required { 'x' } else { ' ' };
+            let line = if objective.required > 1 {
+                format!("[{mark}] {} {count}/{}", objective.description, objective.required)
+            } else {
+                format!("[{mark}] {}", objective.description)
+            };
+            renderer.draw_text(x, row, &line);
+        }
+    }
+}
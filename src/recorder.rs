@@ -0,0 +1,303 @@
+//! Gameplay recording
+//!
+//! [`Recorder`] collects presented frames with their timing so gameplay clips can
+//! be shared straight from the terminal. Provides:
+//! - asciinema cast (v2) export, playable with `asciinema play` or the web player
+//! - Animated GIF export, with the `gif` feature enabled
+//!
+//! Start and stop recording with [`Engine::start_recording`] and
+//! [`Engine::stop_recording`], or bind a hotkey with [`Engine::set_recording_hotkey`].
+//!
+//! [`Engine::start_recording`]: crate::engine::Engine::start_recording
+//! [`Engine::stop_recording`]: crate::engine::Engine::stop_recording
+//! [`Engine::set_recording_hotkey`]: crate::engine::Engine::set_recording_hotkey
+
+use std::{fs, io, path::Path, time::{Duration, Instant, SystemTime, UNIX_EPOCH}};
+use crate::screenshot::Screenshot;
+
+/// Captured frames with the time each one appeared
+///
+/// # Notes
+/// - Frames identical to the previous one are skipped, the previous frame simply stays on screen longer
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use lonely_engine::{recorder::Recorder, renderer::Cell, screenshot::Screenshot};
+///
+/// let mut recorder = Recorder::new();
+/// recorder.capture_at(Duration::ZERO, Screenshot::new(2, 1, vec![Cell::new('h'), Cell::new('i')]));
+/// recorder.capture_at(Duration::from_millis(500), Screenshot::new(2, 1, vec![Cell::new('y'), Cell::new('o')]));
+///
+/// let cast = recorder.to_asciicast();
+/// assert!(cast.starts_with("{\"version\": 2, \"width\": 2, \"height\": 1"));
+/// assert_eq!(cast.lines().count(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    started: Instant,
+    timestamp: u64,
+    frames: Vec<(Duration, Screenshot)>,
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Recorder {
+    /// Starts an empty recording, timed from now
+    pub fn new() -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+
+        Self { started: Instant::now(), timestamp, frames: Vec::new() }
+    }
+
+    /// Adds a frame timed by the wall clock
+    pub fn capture(&mut self, frame: Screenshot) {
+        self.capture_at(self.started.elapsed(), frame);
+    }
+
+    /// Adds a frame at an explicit time since the recording started
+    pub fn capture_at(&mut self, time: Duration, frame: Screenshot) {
+        if self.frames.last().is_some_and(|(_, last)| *last == frame) {
+            return;
+        }
+        self.frames.push((time, frame));
+    }
+
+    /// Number of distinct frames captured
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns whether no frames were captured
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Time of the last captured frame
+    pub fn duration(&self) -> Duration {
+        self.frames.last().map(|(time, _)| *time).unwrap_or_default()
+    }
+
+    /// Formats the recording as an asciinema v2 cast
+    ///
+    /// # Notes
+    /// - Terminal size is taken from the first frame
+    /// - Each frame redraws the whole screen from the top-left corner
+    pub fn to_asciicast(&self) -> String {
+        let (width, height) = self.frames
+            .first()
+            .map(|(_, frame)| (frame.width(), frame.height()))
+            .unwrap_or_default();
+        let mut cast = format!(
+            "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {}}}\n",
+            width, height, self.timestamp,
+        );
+
+        for (i, (time, frame)) in self.frames.iter().enumerate() {
+            let mut output = String::from(if i == 0 { "\x1B[2J\x1B[H" } else { "\x1B[H" });
+            output.push_str(&frame.to_ansi().trim_end_matches('\n').replace('\n', "\r\n"));
+            cast.push_str(&format!("[{:.6}, \"o\", \"{}\"]\n", time.as_secs_f64(), escape_json(&output)));
+        }
+
+        cast
+    }
+
+    /// Writes the asciinema cast to a file (conventionally `.cast`)
+    pub fn save_asciicast(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_asciicast())
+    }
+
+    /// Encodes the recording as a looping animated GIF
+    ///
+    /// # Notes
+    /// - Frames are rasterized like [`Screenshot::to_png`]
+    /// - Colors are mapped to the 256-color terminal palette
+    /// - Frame delays are rounded to the GIF resolution of 10ms
+    #[cfg(feature = "gif")]
+    pub fn to_gif(&self) -> Vec<u8> {
+        gif::encode(&self.frames)
+    }
+
+    /// Writes the animated GIF to a file
+    #[cfg(feature = "gif")]
+    pub fn save_gif(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_gif())
+    }
+}
+
+/// Escapes text for use inside a JSON string literal
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Minimal animated GIF encoder (LZW, fixed 256-color palette, no dependencies)
+#[cfg(feature = "gif")]
+mod gif {
+    use std::{collections::HashMap, time::Duration};
+    use crate::{color::Color, screenshot::Screenshot};
+
+    /// Largest LZW code GIF allows
+    const MAX_CODE: u16 = 4095;
+    /// Palette indices use 8 bits
+    const MIN_CODE_SIZE: u8 = 8;
+
+    pub(super) fn encode(frames: &[(Duration, Screenshot)]) -> Vec<u8> {
+        let palette: Vec<(u8, u8, u8)> = (0..=255u8).filter_map(|index| Color::Indexed(index).to_rgb()).collect();
+        let (width, height) = frames
+            .first()
+            .map(|(_, frame)| {
+                let (width, height, _) = frame.rasterize();
+                (width, height)
+            })
+            .unwrap_or_default();
+
+        let mut gif = b"GIF89a".to_vec();
+        gif.extend_from_slice(&(width as u16).to_le_bytes());
+        gif.extend_from_slice(&(height as u16).to_le_bytes());
+        gif.extend_from_slice(&[0xF7, 0, 0]); // Global 256-entry palette
+        for (r, g, b) in &palette {
+            gif.extend_from_slice(&[*r, *g, *b]);
+        }
+        // Loop forever
+        gif.extend_from_slice(&[0x21, 0xFF, 0x0B]);
+        gif.extend_from_slice(b"NETSCAPE2.0");
+        gif.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+
+        let mut nearest_cache = HashMap::new();
+        for (i, (time, frame)) in frames.iter().enumerate() {
+            let next_time = frames.get(i + 1).map(|(next, _)| *next).unwrap_or(*time + Duration::from_secs(1));
+            let delay = ((next_time.saturating_sub(*time)).as_millis() / 10).clamp(1, u16::MAX as u128) as u16;
+
+            // Graphic control extension carrying the frame delay
+            gif.extend_from_slice(&[0x21, 0xF9, 0x04, 0x00]);
+            gif.extend_from_slice(&delay.to_le_bytes());
+            gif.extend_from_slice(&[0x00, 0x00]);
+
+            let (frame_width, frame_height, pixels) = frame.rasterize();
+            gif.push(0x2C);
+            gif.extend_from_slice(&[0, 0, 0, 0]);
+            gif.extend_from_slice(&(frame_width as u16).to_le_bytes());
+            gif.extend_from_slice(&(frame_height as u16).to_le_bytes());
+            gif.push(0x00);
+
+            let indices: Vec<u8> = pixels
+                .chunks(3)
+                .map(|rgb| {
+                    let rgb = (rgb[0], rgb[1], rgb[2]);
+                    *nearest_cache.entry(rgb).or_insert_with(|| nearest(&palette, rgb))
+                })
+                .collect();
+
+            gif.push(MIN_CODE_SIZE);
+            for block in lzw(&indices).chunks(255) {
+                gif.push(block.len() as u8);
+                gif.extend_from_slice(block);
+            }
+            gif.push(0x00);
+        }
+
+        gif.push(0x3B);
+        gif
+    }
+
+    /// Index of the palette entry closest to a color
+    fn nearest(palette: &[(u8, u8, u8)], (r, g, b): (u8, u8, u8)) -> u8 {
+        let distance = |(pr, pg, pb): &(u8, u8, u8)| {
+            let (dr, dg, db) = (i32::from(*pr) - i32::from(r), i32::from(*pg) - i32::from(g), i32::from(*pb) - i32::from(b));
+            dr * dr + dg * dg + db * db
+        };
+        (0..palette.len()).min_by_key(|index| distance(&palette[*index])).unwrap_or(0) as u8
+    }
+
+    /// Compresses palette indices with GIF-flavored variable-width LZW
+    fn lzw(indices: &[u8]) -> Vec<u8> {
+        let clear = 1u16 << MIN_CODE_SIZE;
+        let end = clear + 1;
+
+        let mut output = BitWriter::default();
+        let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+        let mut code_size = u32::from(MIN_CODE_SIZE) + 1;
+        let mut next_code = end + 1;
+        output.write(clear, code_size);
+
+        let mut pixels = indices.iter();
+        let Some(&first) = pixels.next() else {
+            output.write(end, code_size);
+            return output.finish();
+        };
+
+        let mut prefix = u16::from(first);
+        for &pixel in pixels {
+            if let Some(&code) = table.get(&(prefix, pixel)) {
+                prefix = code;
+                continue;
+            }
+
+            output.write(prefix, code_size);
+            if next_code <= MAX_CODE {
+                table.insert((prefix, pixel), next_code);
+                // Widen once the new code no longer fits
+                if next_code == (1 << code_size) && code_size < 12 {
+                    code_size += 1;
+                }
+                next_code += 1;
+            } else {
+                output.write(clear, code_size);
+                table.clear();
+                code_size = u32::from(MIN_CODE_SIZE) + 1;
+                next_code = end + 1;
+            }
+            prefix = u16::from(pixel);
+        }
+
+        output.write(prefix, code_size);
+        output.write(end, code_size);
+        output.finish()
+    }
+
+    /// Packs codes least significant bit first
+    #[derive(Default)]
+    struct BitWriter {
+        bytes: Vec<u8>,
+        buffer: u32,
+        bits: u32,
+    }
+
+    impl BitWriter {
+        fn write(&mut self, code: u16, size: u32) {
+            self.buffer |= u32::from(code) << self.bits;
+            self.bits += size;
+            while self.bits >= 8 {
+                self.bytes.push(self.buffer as u8);
+                self.buffer >>= 8;
+                self.bits -= 8;
+            }
+        }
+
+        fn finish(mut self) -> Vec<u8> {
+            if self.bits > 0 {
+                self.bytes.push(self.buffer as u8);
+            }
+            self.bytes
+        }
+    }
+}
@@ -4,10 +4,222 @@
 //! Provides:
 //! - Coordinate-based character placement
 //! - ANSI color support
-//! - Minimal screen updates through frame comparison
+//! - Minimal screen updates through frame comparison, skipping untouched rows
+//! - Optional render thread so slow terminal output never blocks the game loop
+//! - Output adapted to the terminal's [`Capabilities`]: colors reduced to what it shows,
+//!   ASCII-only output for dumb terminals, serial consoles, and minimal SSH clients
+//! - Whole-frame color transforms such as tints and dimming, see [`post_fx`](crate::post_fx)
 
-use std::io::{self, Write};
-use crate::game_object::GameObject;
+use std::{io::{self, Write}, sync::{Arc, Condvar, Mutex}, thread::{self, JoinHandle}};
+use crate::{accessibility::Accessibility, color::Color, fog::{FogOfWar, Visibility}, font::Font, game_object::GameObject, markup, post_fx::PostFx, screenshot::Screenshot, sprite::Sprite, style::{Attributes, Style}, terminal::{Capabilities, ColorDepth}, tilemap::Tilemap};
+
+/// A single screen cell: character, colors, and attributes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cell {
+    /// Displayed character
+    pub ch: char,
+    /// Foreground color
+    pub fg: Color,
+    /// Background color
+    pub bg: Color,
+    /// Bold, underline, and other text attributes
+    pub attrs: Attributes,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self { ch: ' ', fg: Color::Default, bg: Color::Default, attrs: Attributes::NONE }
+    }
+}
+
+impl Cell {
+    /// Creates a cell with default colors
+    pub fn new(ch: char) -> Self {
+        Self { ch, ..Self::default() }
+    }
+
+    /// The character wrapped in its escape codes, followed by a reset
+    pub fn to_ansi(&self) -> String {
+        let prefix = format!("{}{}{}", self.attrs.escape(), self.fg.fg_escape(), self.bg.bg_escape());
+        if prefix.is_empty() {
+            self.ch.to_string()
+        } else {
+            format!("{}{}\x1B[0m", prefix, self.ch)
+        }
+    }
+
+    /// Decodes a character wrapped in SGR escape codes, e.g. a cell of captured terminal output
+    ///
+    /// # Notes
+    /// - Understands the 16, 256, and true color forms and the attributes in [`Attributes`]
+    /// - Unknown escape sequences are skipped, empty entries decode as a blank
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{color::Color, renderer::Cell};
+    ///
+    /// let cell = Cell::from_ansi("\x1B[1m\x1B[31m@\x1B[0m");
+    /// assert_eq!(cell.ch, '@');
+    /// assert_eq!(cell.fg, Color::RED);
+    /// assert!(cell.attrs.bold);
+    /// ```
+    pub fn from_ansi(text: &str) -> Self {
+        let mut style = Style::new();
+        let mut ch = None;
+        let mut chars = text.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            if c != '\x1B' {
+                ch = ch.or(Some(c));
+                continue;
+            }
+            if chars.peek() != Some(&'[') {
+                continue;
+            }
+            chars.next();
+
+            let mut params = String::new();
+            let mut terminator = None;
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    terminator = Some(c);
+                    break;
+                }
+                params.push(c);
+            }
+            // Resets after the character belong to the next cell
+            if terminator == Some('m') && ch.is_none() {
+                style.apply_sgr(&params);
+            }
+        }
+
+        Self::styled(ch.unwrap_or(' '), &style)
+    }
+
+    /// Creates a cell drawn with a style
+    pub fn styled(ch: char, style: &Style) -> Self {
+        Self { ch, fg: style.fg_color, bg: style.bg_color, attrs: style.attributes }
+    }
+
+    /// The cell as a dumb terminal shows it: an ASCII character without colors or attributes
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{color::Color, renderer::Cell, style::Style};
+    ///
+    /// let wall = Cell::styled('║', &Style::new().fg(Color::BLUE).bold());
+    /// assert_eq!(wall.to_ascii(), Cell::new('|'));
+    /// ```
+    pub fn to_ascii(&self) -> Self {
+        Self::new(ascii_fallback(self.ch))
+    }
+}
+
+/// ASCII stand-in for a character, for terminals without Unicode
+///
+/// # Notes
+/// - Box drawing lines become `-`, `=`, and `|`, their corners, tees, and crossings `+`
+/// - Solid and half blocks become `#`, shades `.`, `:`, and `#` from light to dark
+/// - Arrows, triangles, bullets, and typographic punctuation map to similar ASCII
+/// - Other non-ASCII characters become `?`, ASCII is kept as is
+///
+/// # Example
+/// ```
+/// use lonely_engine::renderer::ascii_fallback;
+///
+/// let frame: String = "╔══╗│█░→…é".chars().map(ascii_fallback).collect();
+/// assert_eq!(frame, "+==+|#.>.?");
+/// ```
+pub fn ascii_fallback(ch: char) -> char {
+    if ch.is_ascii() {
+        return ch;
+    }
+    match ch as u32 {
+        0x2500 | 0x2501 | 0x2504 | 0x2505 | 0x2508 | 0x2509 | 0x254C | 0x254D => '-',
+        0x2574 | 0x2576 | 0x2578 | 0x257A | 0x257C | 0x257E => '-',
+        0x2502 | 0x2503 | 0x2506 | 0x2507 | 0x250A | 0x250B | 0x254E | 0x254F | 0x2551 => '|',
+        0x2575 | 0x2577 | 0x2579 | 0x257B | 0x257D | 0x257F => '|',
+        0x2550 => '=',
+        0x2571 => '/',
+        0x2572 => '\\',
+        0x2573 => 'X',
+        0x250C..=0x257F => '+',
+        0x2591 => '.',
+        0x2592 => ':',
+        0x2580..=0x259F => '#',
+        0x2800 => ' ',
+        0x2801..=0x28FF => ':',
+        _ => match ch {
+            '\u{00A0}' => ' ',
+            '←' | '◄' | '◀' | '«' => '<',
+            '→' | '►' | '▶' | '»' => '>',
+            '↑' | '▲' => '^',
+            '↓' | '▼' => 'v',
+            '↔' => '-',
+            '↕' => '|',
+            '■' | '▪' | '◼' => '#',
+            '□' | '▫' | '◻' => 'O',
+            '●' | '○' | '◯' | '◎' | '°' => 'o',
+            '◆' | '◇' | '♦' | '★' | '☆' | '♥' | '♡' | '•' | '×' => '*',
+            '·' | '…' => '.',
+            '‐' | '‑' | '‒' | '–' | '—' | '−' => '-',
+            '‘' | '’' | '′' => '\'',
+            '“' | '”' | '″' => '"',
+            _ => '?',
+        },
+    }
+}
+
+/// Mapping from world coordinates to screen cells, such as a camera's
+///
+/// The screen cell `(sx, sy)` shows the world cell `(x + sx * zoom, y + sy * zoom)`,
+/// so zooming out skips the cells in between.
+///
+/// # Example
+/// ```
+/// use lonely_engine::renderer::View;
+///
+/// let view = View { x: 10, y: -2, zoom: 2 };
+/// assert_eq!(view.world_to_screen(14, 0), Some((2, 1)));
+/// assert_eq!(view.world_to_screen(15, 0), None); // Skipped by the zoom
+/// assert_eq!(view.screen_to_world(2, 1), (14, 0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct View {
+    /// World column shown in the leftmost screen column
+    pub x: i32,
+    /// World row shown in the top screen row
+    pub y: i32,
+    /// World cells per screen cell, `1` for no zoom
+    pub zoom: usize,
+}
+
+impl Default for View {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl View {
+    /// World and screen coordinates are the same
+    pub const IDENTITY: View = View { x: 0, y: 0, zoom: 1 };
+
+    /// Screen cell showing a world cell, `None` left or above the view or skipped by the zoom
+    ///
+    /// # Notes
+    /// - The result is not clipped to the screen size
+    pub fn world_to_screen(&self, x: i32, y: i32) -> Option<(usize, usize)> {
+        let zoom = self.zoom.max(1) as i32;
+        let (dx, dy) = (x - self.x, y - self.y);
+        (dx >= 0 && dy >= 0 && dx % zoom == 0 && dy % zoom == 0).then(|| ((dx / zoom) as usize, (dy / zoom) as usize))
+    }
+
+    /// World cell shown at a screen cell, for example under the mouse
+    pub fn screen_to_world(&self, x: usize, y: usize) -> (i32, i32) {
+        let zoom = self.zoom.max(1) as i32;
+        (self.x + x as i32 * zoom, self.y + y as i32 * zoom)
+    }
+}
 
 /// Handles terminal rendering with double buffering
 ///
@@ -16,12 +228,191 @@ use crate::game_object::GameObject;
 /// - Front buffer: Previously displayed frame
 ///
 /// # Performance
-/// Only updates changed characters between frames using ANSI cursor positioning
+/// - Buffers are flat, row-major [`Cell`] arrays, so clearing and swapping are plain copies
+/// - Only updates changed characters between frames using ANSI cursor positioning
 pub struct Renderer {
     width: usize,
     height: usize,
-    front_buffer: Vec<Vec<String>>,
-    back_buffer: Vec<Vec<String>>,
+    front_buffer: Vec<Cell>,
+    back_buffer: Vec<Cell>,
+    /// Whether the terminal shows `front_buffer` (false until the first present)
+    screen_synced: bool,
+    /// Rows of the back buffer changed since the last present
+    dirty_rows: Vec<bool>,
+    /// Rows of the back buffer written since the last clear
+    touched_rows: Vec<bool>,
+    /// Background layer in world coordinates, at least as large as the screen
+    background: Vec<Cell>,
+    background_width: usize,
+    background_height: usize,
+    /// Background as seen through `view`, what the back buffer resets to on clear
+    background_view: Vec<Cell>,
+    /// World to screen mapping of the background and world space drawing
+    view: View,
+    /// Whether drawing calls take world coordinates mapped through `view`
+    world_space: bool,
+    /// Cell shown wherever neither the background nor anything drawn covers the screen
+    clear_cell: Cell,
+    /// Output statistics of the last synchronous present
+    stats: RenderStats,
+    /// Writes frames to the terminal when threaded rendering is enabled
+    render_thread: Option<RenderThread>,
+    /// Keep frames in memory without writing to the terminal
+    headless: bool,
+    /// What the terminal can show, output is adapted to it
+    capabilities: Capabilities,
+    /// Write plain ASCII without colors or attributes, whatever the capabilities
+    ascii_only: bool,
+    /// Color transforms applied to presented frames, in order
+    post_fx: Vec<PostFx>,
+    /// Back buffer with `post_fx` applied, what is presented while effects are set
+    post_buffer: Vec<Cell>,
+    /// Frames presented, varying animated effects
+    frame: u64,
+    /// Player options applied to presented frames after `post_fx`
+    accessibility: Accessibility,
+}
+
+/// A frame as stored in the renderer buffers
+type Frame = Vec<Cell>;
+
+/// Output statistics of one presented frame
+///
+/// # Example
+/// ```
+/// # use lonely_engine::renderer::Renderer;
+/// let mut renderer = Renderer::new(40, 10);
+/// renderer.draw_text(0, 0, "Score: 10");
+/// renderer.present().unwrap();
+///
+/// renderer.clear_back_buffer();
+/// renderer.draw_text(0, 0, "Score: 11");
+/// renderer.present().unwrap();
+///
+/// let stats = renderer.last_frame_stats();
+/// assert_eq!(stats.cells_written, 1); // Only the changed digit
+/// assert_eq!(stats.rows_skipped, 9);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RenderStats {
+    /// Cells sent to the terminal
+    pub cells_written: usize,
+    /// Rows compared cell by cell against the previous frame
+    pub rows_compared: usize,
+    /// Rows skipped without comparison because nothing touched them
+    pub rows_skipped: usize,
+}
+
+/// Frame handoff between the game loop and the render thread
+///
+/// Three buffers rotate: the renderer's copy being filled, `pending` waiting
+/// to be written, and the thread's copy on screen. When the thread falls behind,
+/// newer frames replace `pending` so it always writes the latest one.
+#[derive(Default)]
+struct FrameSlot {
+    pending: Option<Frame>,
+    /// Rows changed since the frame the thread last wrote
+    pending_dirty: Vec<bool>,
+    spare: Option<Frame>,
+    /// Statistics of the frame the thread last wrote
+    stats: RenderStats,
+    /// What the frames are written for
+    capabilities: Capabilities,
+    /// Write every cell of the next frame, the screen no longer matches the last one written
+    redraw: bool,
+    shutdown: bool,
+}
+
+/// Background thread writing frames to stdout
+struct RenderThread {
+    slot: Arc<(Mutex<FrameSlot>, Condvar)>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl RenderThread {
+    fn spawn(width: usize, capabilities: Capabilities) -> Self {
+        let slot = Arc::new((Mutex::new(FrameSlot { capabilities, ..FrameSlot::default() }), Condvar::new()));
+        let thread_slot = Arc::clone(&slot);
+        let handle = thread::spawn(move || {
+            let (lock, ready) = &*thread_slot;
+            let mut on_screen: Option<Frame> = None;
+            loop {
+                let (frame, dirty, capabilities) = {
+                    let mut slot = lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                    while slot.pending.is_none() && !slot.shutdown {
+                        slot = ready.wait(slot).unwrap_or_else(|poisoned| poisoned.into_inner());
+                    }
+                    if std::mem::take(&mut slot.redraw) {
+                        on_screen = None;
+                    }
+                    match slot.pending.take() {
+                        Some(frame) => (frame, std::mem::take(&mut slot.pending_dirty), slot.capabilities),
+                        None => return,
+                    }
+                };
+
+                let (output, stats) = frame_diff(width, on_screen.as_deref(), &frame, &dirty, &capabilities);
+                let mut stdout = io::stdout().lock();
+                let _ = stdout.write_all(output.as_bytes()).and_then(|_| stdout.flush());
+                drop(stdout);
+
+                // Hand the replaced frame back for reuse
+                let mut slot = lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                slot.stats = stats;
+                if let Some(old) = on_screen.replace(frame) {
+                    slot.spare = Some(old);
+                }
+            }
+        });
+
+        Self { slot, handle: Some(handle) }
+    }
+
+    /// Queues a frame, replacing one the thread has not started writing yet
+    ///
+    /// # Notes
+    /// - Dirty rows of a replaced frame are kept, since the screen never showed its changes
+    fn submit(&self, frame: &Frame, dirty: &[bool]) {
+        let (lock, ready) = &*self.slot;
+        let mut slot = lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let mut buffer = slot.pending.take().or_else(|| slot.spare.take()).unwrap_or_default();
+        buffer.clone_from(frame);
+        slot.pending = Some(buffer);
+
+        slot.pending_dirty.resize(dirty.len(), false);
+        for (pending, dirty) in slot.pending_dirty.iter_mut().zip(dirty) {
+            *pending |= *dirty;
+        }
+        ready.notify_one();
+    }
+
+    /// Changes what frames are written for, redrawing the whole next frame
+    fn set_capabilities(&self, capabilities: Capabilities) {
+        let mut slot = self.slot.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        slot.capabilities = capabilities;
+        slot.redraw = true;
+    }
+
+    /// Statistics of the frame the thread last wrote
+    fn stats(&self) -> RenderStats {
+        self.slot.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).stats
+    }
+
+    /// Writes the pending frame, then stops the thread
+    fn shutdown(&mut self) {
+        let (lock, ready) = &*self.slot;
+        lock.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).shutdown = true;
+        ready.notify_one();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for RenderThread {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
 }
 
 impl Renderer {
@@ -40,15 +431,174 @@ impl Renderer {
     /// ```
     pub fn new(width: usize, height: usize) -> Self {
         // Initilize both buffers with spaces. 
-        let front_buffer = vec![vec![String::new(); width]; height];
-        let back_buffer = vec![vec![String::new(); width]; height];
+        let front_buffer = vec![Cell::default(); width * height];
+        let back_buffer = vec![Cell::default(); width * height];
 
         Self {
             width,
             height,
             front_buffer,
             back_buffer,
+            screen_synced: false,
+            dirty_rows: vec![true; height],
+            touched_rows: vec![false; height],
+            background: vec![Cell::default(); width * height],
+            background_width: width,
+            background_height: height,
+            background_view: vec![Cell::default(); width * height],
+            view: View::IDENTITY,
+            world_space: false,
+            clear_cell: Cell::default(),
+            stats: RenderStats::default(),
+            render_thread: None,
+            headless: false,
+            capabilities: Capabilities::full(),
+            ascii_only: false,
+            post_fx: Vec::new(),
+            post_buffer: Vec::new(),
+            frame: 0,
+            accessibility: Accessibility::default(),
+        }
+    }
+
+    /// Stops or resumes writing frames to the terminal
+    ///
+    /// # Notes
+    /// - Headless presents still update the buffers, screenshots, and statistics,
+    ///   for automated runs and CI without a console
+    /// - A running render thread is left idle while headless
+    /// - Resuming redraws the whole screen on the next present
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::renderer::Renderer;
+    /// let mut renderer = Renderer::new(20, 5);
+    /// renderer.start_render_thread();
+    /// renderer.set_headless(true);
+    /// renderer.draw_text(0, 0, "Quiet");
+    /// renderer.present().unwrap(); // Nothing reaches stdout
+    /// assert_eq!(renderer.last_frame_stats().cells_written, 100);
+    /// ```
+    pub fn set_headless(&mut self, headless: bool) {
+        if self.headless && !headless {
+            self.output_changed();
         }
+        self.headless = headless;
+    }
+
+    /// Returns whether frames are kept off the terminal
+    pub fn is_headless(&self) -> bool {
+        self.headless
+    }
+
+    /// Writes plain ASCII without colors or attributes, for dumb terminals
+    ///
+    /// # Notes
+    /// - Characters are replaced by [`ascii_fallback`] on output only, drawing and
+    ///   screenshots still see the original cells
+    /// - Overrides the [capabilities](Self::set_capabilities), which already give ASCII-only
+    ///   output for terminals without Unicode or escape codes. The engine forces it through `EngineBuilder::ascii_only`
+    /// - Switching redraws the whole screen on the next present
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::renderer::Renderer;
+    /// let mut renderer = Renderer::new(20, 5);
+    /// renderer.set_headless(true);
+    /// renderer.set_ascii_only(true);
+    /// renderer.draw_text(0, 0, "┌─┐");
+    /// renderer.present().unwrap();
+    /// assert_eq!(renderer.cell(0, 0).unwrap().ch, '┌');
+    /// ```
+    pub fn set_ascii_only(&mut self, ascii_only: bool) {
+        if ascii_only != self.ascii_only {
+            self.ascii_only = ascii_only;
+            self.output_changed();
+        }
+    }
+
+    /// Returns whether plain ASCII output without colors or attributes is forced
+    pub fn is_ascii_only(&self) -> bool {
+        self.ascii_only
+    }
+
+    /// Adapts output to what the terminal can show
+    ///
+    /// # Notes
+    /// - Colors are reduced to the color depth with [`Color::reduce`], non-ASCII characters
+    ///   replaced by [`ascii_fallback`] without Unicode, on output only
+    /// - The engine sets the capabilities it detected when it was created
+    /// - Changing them redraws the whole screen on the next present
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{renderer::Renderer, terminal::{Capabilities, ColorDepth}};
+    ///
+    /// let mut renderer = Renderer::new(80, 24);
+    /// renderer.set_capabilities(Capabilities { color_depth: ColorDepth::Ansi16, ..Capabilities::full() });
+    /// assert!(!renderer.capabilities().colors_256());
+    /// ```
+    pub fn set_capabilities(&mut self, capabilities: Capabilities) {
+        if capabilities != self.capabilities {
+            self.capabilities = capabilities;
+            self.output_changed();
+        }
+    }
+
+    /// What the terminal can show, as last set
+    pub fn capabilities(&self) -> Capabilities {
+        self.capabilities
+    }
+
+    /// What frames are written for, the capabilities unless ASCII-only output is forced
+    fn output_capabilities(&self) -> Capabilities {
+        match self.ascii_only {
+            true => Capabilities { color_depth: ColorDepth::None, unicode: false, ..self.capabilities },
+            false => self.capabilities,
+        }
+    }
+
+    /// Redraws the whole screen on the next present, after the output format changed
+    fn output_changed(&mut self) {
+        self.screen_synced = false;
+        self.dirty_rows.fill(true);
+        if let Some(render_thread) = &self.render_thread {
+            render_thread.set_capabilities(self.output_capabilities());
+        }
+    }
+
+    /// Moves terminal output to a background thread
+    ///
+    /// # Notes
+    /// - [`present`](Self::present) then only copies the frame and returns immediately
+    /// - When the terminal is slower than the game, intermediate frames are skipped
+    /// - The engine enables this through `EngineBuilder::threaded_rendering`
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::renderer::Renderer;
+    /// let mut renderer = Renderer::new(20, 5);
+    /// renderer.start_render_thread();
+    /// renderer.draw_text(0, 0, "Loading...");
+    /// renderer.present().expect("Rendering failed");
+    /// renderer.stop_render_thread(); // Waits for the frame to be written
+    /// ```
+    pub fn start_render_thread(&mut self) {
+        if self.render_thread.is_none() {
+            self.render_thread = Some(RenderThread::spawn(self.width, self.output_capabilities()));
+        }
+    }
+
+    /// Writes any queued frame and returns output to the calling thread
+    pub fn stop_render_thread(&mut self) {
+        if let Some(mut render_thread) = self.render_thread.take() {
+            render_thread.shutdown();
+        }
+    }
+
+    /// Returns whether frames are written by a background thread
+    pub fn is_threaded(&self) -> bool {
+        self.render_thread.is_some()
     }
 
     /// Gets current render width
@@ -61,7 +611,7 @@ impl Renderer {
         self.height
     }
 
-    /// Resets back buffer to empty state
+    /// Resets back buffer to the background, or the clear style where there is none
     ///
     /// # Example
     /// ```
@@ -69,12 +619,221 @@ impl Renderer {
     /// # let mut renderer = Renderer::new(10, 10);
     /// renderer.clear_back_buffer();
     /// ```
+    ///
+    /// # Notes
+    /// - Only rows written since the last clear are reset and marked dirty
     pub fn clear_back_buffer(&mut self) {
-        for row in self.back_buffer.iter_mut() {
-            for cell in row.iter_mut() {
-                *cell = String::new();
+        for y in 0..self.height {
+            if std::mem::take(&mut self.touched_rows[y]) {
+                let row = y * self.width..(y + 1) * self.width;
+                self.back_buffer[row.clone()].copy_from_slice(&self.background_view[row]);
+                self.dirty_rows[y] = true;
+            }
+        }
+    }
+
+    /// Sets a background layer that persists across clears
+    ///
+    /// # Arguments
+    /// * `background` - A [`Tilemap`], or anything convertible into one (text grid, sprite, screenshot)
+    ///
+    /// # Notes
+    /// - The background is placed at world position (0, 0), the top-left corner
+    ///   unless a [`View`] is set, and may be larger than the screen
+    /// - Blank tilemap cells and cells outside the background show the clear style
+    /// - The back buffer is reset to the new background immediately
+    /// - Objects and text drawn afterwards composite over it
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::renderer::Renderer;
+    /// let mut renderer = Renderer::new(5, 3);
+    /// renderer.set_background("#####\n#   #\n#####");
+    /// renderer.draw_text(2, 1, "@");
+    /// renderer.present().unwrap();
+    ///
+    /// renderer.clear_back_buffer();
+    /// assert_eq!(renderer.cell(0, 0).unwrap().ch, '#');
+    /// assert_eq!(renderer.cell(2, 1).unwrap().ch, ' ');
+    /// ```
+    pub fn set_background(&mut self, background: impl Into<Tilemap>) {
+        let background = background.into();
+        self.background_width = background.width().max(self.width);
+        self.background_height = background.height().max(self.height);
+        self.background = vec![self.clear_cell; self.background_width * self.background_height];
+        for (y, row) in background.rows().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if *cell != Cell::default() {
+                    self.background[y * self.background_width + x] = *cell;
+                }
+            }
+        }
+        self.reset_to_background();
+    }
+
+    /// Changes one background cell, such as a door opening in the level
+    ///
+    /// # Notes
+    /// - Takes world coordinates, positions outside the background are ignored
+    /// - The back buffer cell is updated too, covering anything drawn there this frame
+    pub fn set_background_cell(&mut self, x: usize, y: usize, cell: Cell) {
+        if let Some(index) = self.background_index(x, y) {
+            self.background[index] = cell;
+            if let Some((sx, sy)) = self.view.world_to_screen(x as i32, y as i32)
+                && let Some(screen_index) = self.index(sx, sy)
+            {
+                self.background_view[screen_index] = cell;
+                self.back_buffer[screen_index] = cell;
+                self.mark_row(sy);
+            }
+        }
+    }
+
+    /// Reads a background cell in world coordinates, `None` outside the background
+    pub fn background_cell(&self, x: usize, y: usize) -> Option<&Cell> {
+        self.background_index(x, y).map(|index| &self.background[index])
+    }
+
+    /// Removes the background so clears reset to the clear style
+    pub fn clear_background(&mut self) {
+        self.background_width = self.width;
+        self.background_height = self.height;
+        self.background = vec![self.clear_cell; self.width * self.height];
+        self.reset_to_background();
+    }
+
+    /// Sets the world to screen mapping of the background and world space drawing
+    ///
+    /// # Notes
+    /// - The background moves at the next [`clear_back_buffer`](Self::clear_back_buffer)
+    /// - The engine sets this from its camera every frame
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::renderer::{Renderer, View};
+    ///
+    /// let mut renderer = Renderer::new(4, 1);
+    /// renderer.set_background("abcdefgh");
+    /// renderer.set_view(View { x: 2, y: 0, zoom: 1 });
+    /// renderer.clear_back_buffer();
+    /// assert_eq!(renderer.cell(0, 0).unwrap().ch, 'c');
+    /// ```
+    pub fn set_view(&mut self, view: View) {
+        if view != self.view {
+            self.view = view;
+            self.rebuild_background_view();
+            // Every row shows different background cells now
+            self.touched_rows.fill(true);
+        }
+    }
+
+    /// Current world to screen mapping
+    pub fn view(&self) -> View {
+        self.view
+    }
+
+    /// Switches drawing calls between screen and world coordinates
+    ///
+    /// # Notes
+    /// - In world space every drawing call maps its position through the [`View`],
+    ///   cells outside the screen or skipped by the zoom are dropped
+    /// - The engine draws objects and effects in world space and updatable
+    ///   overlays such as HUDs in screen space
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::renderer::{Renderer, View};
+    ///
+    /// let mut renderer = Renderer::new(10, 5);
+    /// renderer.set_view(View { x: 20, y: 0, zoom: 1 });
+    /// renderer.set_world_space(true);
+    /// renderer.draw_text(23, 1, "@");
+    /// renderer.set_world_space(false);
+    /// assert_eq!(renderer.cell(3, 1).unwrap().ch, '@');
+    /// ```
+    pub fn set_world_space(&mut self, world_space: bool) {
+        self.world_space = world_space;
+    }
+
+    /// Returns whether drawing calls take world coordinates
+    pub fn is_world_space(&self) -> bool {
+        self.world_space
+    }
+
+    /// Sets the cell that fills empty screen space
+    ///
+    /// # Arguments
+    /// * `ch` - Fill character, `' '` for a solid color
+    /// * `fg` - Color of the fill character
+    /// * `bg` - Color of empty cells
+    ///
+    /// # Notes
+    /// - Applies to clears, blanked cells, and blank background cells
+    /// - The back buffer is reset immediately
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::renderer::Renderer;
+    /// use lonely_engine::color::Color;
+    ///
+    /// let mut renderer = Renderer::new(10, 5);
+    /// renderer.set_clear_style('·', Color::BLUE, Color::Indexed(17)); // Dotted dark blue grid
+    /// renderer.draw_text(0, 0, "@");
+    /// renderer.clear_back_buffer();
+    /// assert_eq!(renderer.cell(0, 0).unwrap().ch, '·');
+    /// ```
+    pub fn set_clear_style(&mut self, ch: char, fg: Color, bg: Color) {
+        let previous = self.clear_cell;
+        self.clear_cell = Cell { ch, fg, bg, attrs: Attributes::NONE };
+        for cell in &mut self.background {
+            if *cell == previous {
+                *cell = self.clear_cell;
             }
         }
+        self.rebuild_background_view();
+        self.reset_to_background();
+    }
+
+    /// Cell that fills empty screen space, see [`set_clear_style`](Self::set_clear_style)
+    pub fn clear_cell(&self) -> Cell {
+        self.clear_cell
+    }
+
+    /// Copies the whole background into the back buffer
+    fn reset_to_background(&mut self) {
+        self.rebuild_background_view();
+        self.back_buffer.copy_from_slice(&self.background_view);
+        self.dirty_rows.fill(true);
+        self.touched_rows.fill(false);
+    }
+
+    /// Reads a back buffer cell, `None` outside dimensions
+    pub fn cell(&self, x: usize, y: usize) -> Option<&Cell> {
+        self.index(x, y).map(|index| &self.back_buffer[index])
+    }
+
+    /// Writes a cell to the back buffer
+    ///
+    /// # Notes
+    /// - Positions outside dimensions are ignored
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::renderer::{Cell, Renderer};
+    /// use lonely_engine::color::Color;
+    ///
+    /// # let mut renderer = Renderer::new(10, 10);
+    /// renderer.set_cell(3, 3, Cell { fg: Color::GREEN, ..Cell::new('$') });
+    /// assert_eq!(renderer.cell(3, 3).unwrap().ch, '$');
+    /// ```
+    pub fn set_cell(&mut self, x: usize, y: usize, cell: Cell) {
+        let Some((x, y)) = self.to_screen(x, y) else {
+            return;
+        };
+        if let Some(index) = self.index(x, y) {
+            self.back_buffer[index] = cell;
+            self.mark_row(y);
+        }
     }
 
     /// Writes a game object to the back buffer
@@ -91,36 +850,325 @@ impl Renderer {
     /// # Example
     /// ```
     /// # use lonely_engine::{renderer::Renderer, game_object::GameObject};
+    /// use lonely_engine::color::Color;
+    ///
     /// # let mut renderer = Renderer::new(10, 10);
     /// let mut obj = GameObject::new(5, 5, '@');
-    /// obj.fg_color = Some("\x1B[31m".into()); // Red
+    /// obj.fg_color = Color::RED;
     /// renderer.set_char(5, 5, &obj);
     /// ```
     pub fn set_char(&mut self, x: usize, y: usize, obj: &GameObject) {
-        if x < self.width && y < self.height {
-            let mut ansi_str = String::new();
-            
-            // Apply colors if present
-            if let Some(fg) = &obj.fg_color {
-                ansi_str.push_str(fg);
+        self.set_cell(x, y, Cell { ch: obj.character, fg: obj.fg_color, bg: obj.bg_color, attrs: obj.attributes });
+    }
+
+    /// Writes a multi-cell sprite to the back buffer
+    ///
+    /// # Arguments
+    /// * `x` - Column of the sprite's top-left cell
+    /// * `y` - Row of the sprite's top-left cell
+    /// * `sprite` - Sprite to draw
+    ///
+    /// # Notes
+    /// - Transparent sprite cells leave the buffer untouched
+    /// - Cells falling outside dimensions are clipped
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::{renderer::Renderer, sprite::Sprite};
+    /// # let mut renderer = Renderer::new(10, 10);
+    /// let crate_sprite = Sprite::from_str("+-+\n| |\n+-+");
+    /// renderer.draw_sprite(2, 2, &crate_sprite);
+    /// ```
+    pub fn draw_sprite(&mut self, x: usize, y: usize, sprite: &Sprite) {
+        for (dx, dy, cell) in sprite.cells() {
+            self.set_cell(x + dx, y + dy, Cell { ch: cell.character, fg: cell.fg_color, bg: cell.bg_color, attrs: cell.attributes });
+        }
+    }
+
+    /// Writes a line of uncolored text to the back buffer
+    ///
+    /// # Arguments
+    /// * `x` - Column of the first character
+    /// * `y` - Row of the text
+    /// * `text` - Characters to write left to right
+    ///
+    /// # Notes
+    /// - Characters falling outside dimensions are clipped
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::renderer::Renderer;
+    /// # let mut renderer = Renderer::new(20, 5);
+    /// renderer.draw_text(1, 1, "PAUSED");
+    /// ```
+    pub fn draw_text(&mut self, x: usize, y: usize, text: &str) {
+        for (i, c) in text.chars().enumerate() {
+            self.set_cell(x + i, y, Cell::new(c));
+        }
+    }
+
+    /// Writes a line of styled text to the back buffer
+    ///
+    /// # Arguments
+    /// * `x` - Column of the first character
+    /// * `y` - Row of the text
+    /// * `text` - Characters to write left to right
+    /// * `style` - Colors and attributes applied to every character
+    ///
+    /// # Notes
+    /// - Characters falling outside dimensions are clipped
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::renderer::Renderer;
+    /// use lonely_engine::style::Style;
+    ///
+    /// # let mut renderer = Renderer::new(20, 5);
+    /// renderer.draw_styled_text(1, 1, "> Start", &Style::new().bold().reverse());
+    /// ```
+    pub fn draw_styled_text(&mut self, x: usize, y: usize, text: &str, style: &Style) {
+        for (i, c) in text.chars().enumerate() {
+            self.set_cell(x + i, y, Cell::styled(c, style));
+        }
+    }
+
+    /// Writes a line of text with inline markup tags to the back buffer
+    ///
+    /// # Arguments
+    /// * `x` - Column of the first character
+    /// * `y` - Row of the text
+    /// * `text` - Text with tags such as `[red]` and `[bold]`, see [`markup`](crate::markup)
+    ///
+    /// # Returns
+    /// Number of columns drawn, tags excluded
+    ///
+    /// # Notes
+    /// - Characters falling outside dimensions are clipped
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::renderer::Renderer;
+    /// # let mut renderer = Renderer::new(30, 5);
+    /// let width = renderer.draw_markup(1, 1, "You found [yellow]5 gold[/yellow]!");
+    /// assert_eq!(width, 17);
+    /// ```
+    pub fn draw_markup(&mut self, x: usize, y: usize, text: &str) -> usize {
+        let mut column = x;
+        for (span, style) in markup::parse(text) {
+            self.draw_styled_text(column, y, &span, &style);
+            column += span.chars().count();
+        }
+        column - x
+    }
+
+    /// Writes text as large multi-cell glyphs to the back buffer
+    ///
+    /// # Arguments
+    /// * `x` - Column of the text's top-left cell
+    /// * `y` - Row of the text's top-left cell
+    /// * `text` - Characters to draw left to right
+    /// * `font` - Glyph set, see [`Font`]
+    /// * `style` - Colors applied to every lit glyph cell
+    ///
+    /// # Notes
+    /// - Spaces inside glyphs are transparent
+    /// - Characters missing from the font are skipped
+    /// - Cells falling outside dimensions are clipped
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::renderer::Renderer;
+    /// use lonely_engine::{color::Color, font::Font, style::Style};
+    ///
+    /// # let mut renderer = Renderer::new(80, 24);
+    /// let font = Font::standard();
+    /// let (width, _) = font.measure("GAME OVER");
+    /// renderer.draw_big_text((80 - width) / 2, 8, "GAME OVER", &font, &Style::new().fg(Color::RED));
+    /// ```
+    pub fn draw_big_text(&mut self, x: usize, y: usize, text: &str, font: &Font, style: &Style) {
+        let mut cursor = x;
+        for c in text.chars() {
+            let Some(rows) = font.glyph(c) else {
+                continue;
+            };
+
+            for (dy, row) in rows.iter().enumerate() {
+                for (dx, pixel) in row.chars().enumerate() {
+                    if pixel != ' ' {
+                        self.set_cell(cursor + dx, y + dy, Cell::styled(pixel, style));
+                    }
+                }
             }
-            if let Some(bg) =&obj.bg_color {
-                ansi_str.push_str(bg);
+
+            cursor += font.glyph_width(c) + font.spacing();
+        }
+    }
+
+    /// Replaces a back buffer cell with the clear style, hiding anything drawn there
+    ///
+    /// # Notes
+    /// - Positions outside dimensions are ignored
+    pub fn blank_cell(&mut self, x: usize, y: usize) {
+        self.set_cell(x, y, self.clear_cell);
+    }
+
+    /// Dims a back buffer cell, keeping its character
+    ///
+    /// # Arguments
+    /// * `x` - Column position (0-based)
+    /// * `y` - Row position (0-based)
+    /// * `grey` - Also replace the cell's colors with dark grey
+    ///
+    /// # Notes
+    /// - Positions outside dimensions, empty cells, and clear style cells are ignored
+    /// - Takes world coordinates in world space, like [`set_cell`](Self::set_cell)
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::renderer::{Renderer, View};
+    ///
+    /// let mut renderer = Renderer::new(10, 5);
+    /// renderer.set_view(View { x: 20, y: 0, zoom: 1 });
+    /// renderer.set_world_space(true);
+    /// renderer.draw_text(23, 1, "#");
+    /// renderer.dim_cell(23, 1, false);
+    /// assert!(renderer.cell(3, 1).unwrap().attrs.dim);
+    /// ```
+    pub fn dim_cell(&mut self, x: usize, y: usize, grey: bool) {
+        let Some((x, y)) = self.to_screen(x, y) else {
+            return;
+        };
+        let Some(index) = self.index(x, y) else {
+            return;
+        };
+
+        let cell = &mut self.back_buffer[index];
+        if cell.ch != ' ' && *cell != self.clear_cell {
+            cell.attrs.dim = true;
+            if grey {
+                cell.fg = Color::GREY;
+                cell.bg = Color::Default;
+            }
+            self.mark_row(y);
+        }
+    }
+
+    /// Covers the world drawn so far with fog of war
+    ///
+    /// # Notes
+    /// - Hidden cells show the clear style, explored ones the background dimmed and grey, hiding objects on them
+    /// - Screen cells showing the world outside the fog are left alone
+    /// - The engine calls this after drawing objects and effects, before screen space overlays
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{color::Color, fog::FogOfWar, game_object::GameObject, renderer::Renderer};
+    ///
+    /// let mut renderer = Renderer::new(10, 1);
+    /// renderer.set_background("..........");
+    /// renderer.set_char(1, 0, &GameObject::new(1, 0, 'g'));
+    /// let mut fog = FogOfWar::new(10, 1);
+    /// fog.reveal((0, 0), 1.0);
+    /// fog.clear_visible();
+    /// fog.reveal((9, 0), 1.0);
+    /// renderer.draw_fog(&fog);
+    ///
+    /// assert_eq!(renderer.cell(1, 0).unwrap().ch, '.'); // Remembered floor, the goblin is out of sight
+    /// assert_eq!(renderer.cell(1, 0).unwrap().fg, Color::GREY);
+    /// assert_eq!(renderer.cell(5, 0).unwrap().ch, ' '); // Never seen
+    /// assert_eq!(renderer.cell(9, 0).unwrap().ch, '.');
+    /// ```
+    pub fn draw_fog(&mut self, fog: &FogOfWar) {
+        for sy in 0..self.height {
+            let mut changed = false;
+            for sx in 0..self.width {
+                let (x, y) = self.view.screen_to_world(sx, sy);
+                if x < 0 || y < 0 || x as usize >= fog.width() || y as usize >= fog.height() {
+                    continue;
+                }
+                let index = sy * self.width + sx;
+                let cell = match fog.visibility(x as usize, y as usize) {
+                    Visibility::Visible => continue,
+                    Visibility::Hidden => self.clear_cell,
+                    Visibility::Explored => {
+                        let mut cell = self.background_view[index];
+                        if cell.ch != ' ' && cell != self.clear_cell {
+                            cell.attrs.dim = true;
+                            cell.fg = Color::GREY;
+                            cell.bg = Color::Default;
+                        }
+                        cell
+                    },
+                };
+                if self.back_buffer[index] != cell {
+                    self.back_buffer[index] = cell;
+                    changed = true;
+                }
+            }
+            if changed {
+                self.mark_row(sy);
+            }
+        }
+    }
+
+    /// Records that a back buffer row changed
+    fn mark_row(&mut self, y: usize) {
+        self.dirty_rows[y] = true;
+        self.touched_rows[y] = true;
+    }
+
+    /// Position of a world cell in the background, `None` outside it
+    fn background_index(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.background_width && y < self.background_height).then(|| y * self.background_width + x)
+    }
+
+    /// Samples the background through the view into the screen-sized cache
+    fn rebuild_background_view(&mut self) {
+        for sy in 0..self.height {
+            for sx in 0..self.width {
+                let (x, y) = self.view.screen_to_world(sx, sy);
+                let cell = if x < 0 || y < 0 {
+                    None
+                } else {
+                    self.background_index(x as usize, y as usize).map(|index| self.background[index])
+                };
+                self.background_view[sy * self.width + sx] = cell.unwrap_or(self.clear_cell);
             }
+        }
+    }
 
-            ansi_str.push(obj.character);
-            ansi_str.push_str("\x1B[0m");
-            self.back_buffer[y][x] = ansi_str;
+    /// Position of a cell in the flat buffers, `None` outside dimensions
+    /// Screen position of a drawing position, translated by the view in world space
+    fn to_screen(&self, x: usize, y: usize) -> Option<(usize, usize)> {
+        if self.world_space {
+            self.view.world_to_screen(x as i32, y as i32)
+        } else {
+            Some((x, y))
         }
     }
 
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    /// Captures the last presented frame
+    ///
+    /// # Returns
+    /// A [`Screenshot`] that can be exported as text, ANSI text, HTML, or PNG
+    pub fn screenshot(&self) -> Screenshot {
+        Screenshot::new(self.width, self.height, self.front_buffer.clone())
+    }
+
     /// Renders the back buffer to screen and swaps buffers
     ///
     /// # Implementation
-    /// 1. Moves cursor to home position (0,0)
-    /// 2. Only updates changed characters
+    /// 1. Compares the back buffer against the previous frame
+    /// 2. Only updates changed characters, using ANSI cursor positioning
     /// 3. Flushes output buffer
     ///
+    /// With the render thread running, steps 1-3 happen on that thread and
+    /// this call returns as soon as the frame is queued.
+    ///
     /// # Example
     /// ```no_run
     /// # use lonely_engine::{renderer::Renderer, game_object::GameObject};
@@ -131,16 +1179,213 @@ impl Renderer {
     /// renderer.present().expect("Rendering failed");
     /// ```
     pub fn present(&mut self) -> io::Result<()> {
-        // Move cursor to top-left
-        print!("\x1B[H");
+        let processed = self.transforms_frames();
+        let frame = if processed {
+            self.apply_post_fx();
+            &self.post_buffer
+        } else {
+            &self.back_buffer
+        };
+        let result = match &self.render_thread {
+            Some(render_thread) if !self.headless => {
+                render_thread.submit(frame, &self.dirty_rows);
+                Ok(())
+            },
+            _ => {
+                let previous = self.screen_synced.then_some(self.front_buffer.as_slice());
+                let (output, stats) = frame_diff(self.width, previous, frame, &self.dirty_rows, &self.output_capabilities());
+                self.stats = stats;
+                if self.headless {
+                    Ok(())
+                } else {
+                    let mut stdout = io::stdout().lock();
+                    stdout.write_all(output.as_bytes()).and_then(|_| stdout.flush())
+                }
+            },
+        };
 
+        let frame = if processed { &self.post_buffer } else { &self.back_buffer };
         for y in 0..self.height {
-            for x in 0..self.width {
-                // Only update changed cells
-                print!("\x1B[{};{}H{}", y + 1, x + 1, self.back_buffer[y][x]);
-                self.front_buffer[y][x] = self.back_buffer[y][x].clone();
+            if std::mem::take(&mut self.dirty_rows[y]) {
+                let row = y * self.width..(y + 1) * self.width;
+                self.front_buffer[row.clone()].copy_from_slice(&frame[row]);
             }
         }
-        io::stdout().flush()
+        self.screen_synced = true;
+        result
+    }
+
+    /// Replaces the color transforms of presented frames with one effect
+    ///
+    /// # Notes
+    /// - The back buffer keeps the colors drawn, only the presented frame and screenshots are transformed
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{color::Color, post_fx::PostFx, renderer::Renderer, style::Style};
+    ///
+    /// let mut renderer = Renderer::new(10, 2);
+    /// renderer.draw_styled_text(0, 0, "#", &Style::new().fg(Color::Rgb(200, 200, 200)));
+    /// renderer.set_post_fx(PostFx::Invert);
+    /// renderer.present().unwrap();
+    /// assert_eq!(renderer.screenshot().cell(0, 0).unwrap().fg, Color::Rgb(55, 55, 55));
+    /// assert_eq!(renderer.cell(0, 0).unwrap().fg, Color::Rgb(200, 200, 200));
+    /// ```
+    pub fn set_post_fx(&mut self, fx: PostFx) {
+        self.post_fx = vec![fx];
+        self.post_fx_changed();
+    }
+
+    /// Adds a color transform applied after the ones already set
+    pub fn add_post_fx(&mut self, fx: PostFx) {
+        self.post_fx.push(fx);
+        self.post_fx_changed();
     }
-}
\ No newline at end of file
+
+    /// Turns an effect on, or off when one of the same kind is set, such as from an options menu
+    ///
+    /// # Returns
+    /// Whether the effect is on now
+    pub fn toggle_post_fx(&mut self, fx: PostFx) -> bool {
+        let kind = std::mem::discriminant(&fx);
+        let count = self.post_fx.len();
+        self.post_fx.retain(|existing| std::mem::discriminant(existing) != kind);
+        let enabled = self.post_fx.len() == count;
+        if enabled {
+            self.post_fx.push(fx);
+        }
+        self.post_fx_changed();
+        enabled
+    }
+
+    /// Removes every color transform, presenting frames as drawn
+    pub fn clear_post_fx(&mut self) {
+        if !self.post_fx.is_empty() {
+            self.post_fx.clear();
+            self.post_fx_changed();
+        }
+    }
+
+    /// Color transforms applied to presented frames, in order
+    pub fn post_fx(&self) -> &[PostFx] {
+        &self.post_fx
+    }
+
+    /// Sets the accessibility options applied to presented frames, after any color transforms
+    ///
+    /// # Notes
+    /// - With reduced flashing, animated effects such as [`PostFx::Flicker`] are skipped
+    /// - The engine sets this from the player's settings
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{accessibility::Accessibility, color::Color, renderer::Renderer, style::Style};
+    ///
+    /// let mut renderer = Renderer::new(10, 2);
+    /// renderer.draw_styled_text(0, 0, "!", &Style::new().fg(Color::Rgb(200, 180, 20)));
+    /// renderer.set_accessibility(Accessibility::new().high_contrast(true));
+    /// renderer.present().unwrap();
+    /// assert_eq!(renderer.screenshot().cell(0, 0).unwrap().fg, Color::BRIGHT_YELLOW);
+    /// ```
+    pub fn set_accessibility(&mut self, accessibility: Accessibility) {
+        if self.accessibility != accessibility {
+            self.accessibility = accessibility;
+            self.post_fx_changed();
+        }
+    }
+
+    /// Accessibility options applied to presented frames
+    pub fn accessibility(&self) -> Accessibility {
+        self.accessibility
+    }
+
+    /// Returns whether presented frames differ from the back buffer
+    fn transforms_frames(&self) -> bool {
+        !self.post_fx.is_empty() || self.accessibility.changes_cells()
+    }
+
+    /// Redraws every row, since each one looks different now
+    fn post_fx_changed(&mut self) {
+        self.dirty_rows.fill(true);
+    }
+
+    /// Transforms the dirty rows of the back buffer into the post buffer
+    fn apply_post_fx(&mut self) {
+        self.frame += 1;
+        let calm = self.accessibility.reduce_flashing;
+        if !calm && self.post_fx.iter().any(PostFx::is_animated) {
+            self.dirty_rows.fill(true);
+        }
+        self.post_buffer.resize(self.back_buffer.len(), Cell::default());
+        for y in (0..self.height).filter(|&y| self.dirty_rows[y]) {
+            let row = y * self.width..(y + 1) * self.width;
+            for (out, cell) in self.post_buffer[row.clone()].iter_mut().zip(&self.back_buffer[row]) {
+                *out = *cell;
+                for fx in self.post_fx.iter().filter(|fx| !(calm && fx.is_animated())) {
+                    fx.apply(out, y, self.frame);
+                }
+                self.accessibility.apply(out);
+            }
+        }
+    }
+
+    /// Output statistics of the last presented frame
+    ///
+    /// # Notes
+    /// - With the render thread running, these describe the last frame the thread wrote
+    pub fn last_frame_stats(&self) -> RenderStats {
+        match &self.render_thread {
+            Some(render_thread) if !self.headless => render_thread.stats(),
+            _ => self.stats,
+        }
+    }
+
+    /// Number of cells sent to the terminal for the last presented frame
+    pub fn cells_written_last_frame(&self) -> usize {
+        self.last_frame_stats().cells_written
+    }
+}
+
+/// Builds the terminal output turning `previous` into `next`
+///
+/// # Arguments
+/// * `width` - Cells per row
+/// * `previous` - Frame currently on screen, `None` to redraw every cell
+/// * `next` - Frame to show
+/// * `dirty` - Rows changed since `previous`, other rows are skipped without comparison
+/// * `capabilities` - Terminal the output is adapted to
+fn frame_diff(width: usize, previous: Option<&[Cell]>, next: &[Cell], dirty: &[bool], capabilities: &Capabilities) -> (String, RenderStats) {
+    let mut output = String::new();
+    let mut stats = RenderStats::default();
+    // Cursor position after the last write, used to skip redundant cursor moves
+    let mut cursor = None;
+
+    for (y, row) in next.chunks(width.max(1)).enumerate() {
+        let previous_row = previous.and_then(|previous| previous.get(y * width..(y + 1) * width));
+        if previous_row.is_some() && !dirty.get(y).copied().unwrap_or(true) {
+            stats.rows_skipped += 1;
+            continue;
+        }
+        stats.rows_compared += 1;
+
+        for (x, cell) in row.iter().enumerate() {
+            if previous_row.is_some_and(|previous_row| previous_row[x] == *cell) {
+                continue;
+            }
+
+            if cursor != Some((x, y)) {
+                output.push_str(&format!("\x1B[{};{}H", y + 1, x + 1));
+            }
+            let ch = if capabilities.unicode { cell.ch } else { ascii_fallback(cell.ch) };
+            match capabilities.color_depth {
+                ColorDepth::None => output.push(ch),
+                ColorDepth::TrueColor => output.push_str(&Cell { ch, ..*cell }.to_ansi()),
+                depth => output.push_str(&Cell { ch, fg: cell.fg.reduce(depth), bg: cell.bg.reduce(depth), attrs: cell.attrs }.to_ansi()),
+            }
+            cursor = Some((x + 1, y));
+            stats.cells_written += 1;
+        }
+    }
+
+    (output, stats)
+}
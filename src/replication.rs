@@ -0,0 +1,743 @@
+//! Delta-compressed world replication for networked games
+//!
+//! Provides:
+//! - [`ObjectState`] holding the fields of an object sent over the network
+//! - [`WorldState`] capturing every replicated object at a server tick, and
+//!   encoding it as a diff against an older state
+//! - [`ReplicationServer`] keeping recent states and what each client acknowledged
+//! - [`ReplicationClient`] rebuilding states from diffs and applying them to
+//!   its objects, interpolating positions between the last two ticks
+//!
+//! Objects take part once they have a `net_id`, given by
+//! [`ReplicationServer::replicate`]. Every tick the server captures the world
+//! and encodes a message per client containing only the objects that changed
+//! since the last tick that client acknowledged, its baseline, and only their
+//! changed fields. Clients without a baseline, or whose baseline dropped out
+//! of the server's history, get a full state. Messages are plain bytes, sent
+//! over whatever transport the game uses; losing one costs nothing but a
+//! larger next message.
+//!
+//! # Format
+//! Little-endian, one message per tick:
+//! ```text
+//! u32  last input sequence the server applied for the receiving client, 0 for none
+//! u32  tick
+//! u32  baseline tick, u32::MAX for a full state
+//! u16  number of changed objects, then for each:
+//!      u32 net id, u16 field mask, the masked fields in bit order
+//! u16  number of removed objects, then their u32 net ids
+//! ```
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{game_object::GameObject, replication::{ReplicationClient, ReplicationServer}};
+//!
+//! let mut server = ReplicationServer::new();
+//! let mut world = vec![GameObject::new(3, 4, '@')];
+//! server.replicate(&mut world[0]);
+//! server.add_client(1);
+//!
+//! let mut client = ReplicationClient::new(1.0 / 20.0);
+//! let mut local = Vec::new();
+//!
+//! server.capture(&world);
+//! let full = server.encode(1);
+//! let ack = client.receive(&full).unwrap();
+//! server.acknowledge(1, ack);
+//! client.apply(&mut local);
+//! assert_eq!((local[0].x, local[0].y, local[0].character), (3, 4, '@'));
+//!
+//! // Only the changed x travels once the client acknowledged a baseline
+//! world[0].x = 4;
+//! server.capture(&world);
+//! let delta = server.encode(1);
+//! assert!(delta.len() < full.len());
+//! client.receive(&delta).unwrap();
+//! client.update(1.0);
+//! client.apply(&mut local);
+//! assert_eq!(local[0].x, 4);
+//! ```
+
+use std::{collections::{BTreeMap, BTreeSet, VecDeque}, fmt, io};
+use crate::{color::Color, direction::Direction, game_object::GameObject, style::Attributes};
+
+/// States kept by default to diff against, about half a second at 60 ticks per second
+pub const DEFAULT_HISTORY: usize = 32;
+
+/// Baseline tick marking a full state
+const FULL_STATE: u32 = u32::MAX;
+
+const FIELD_X: u16 = 1 << 0;
+const FIELD_Y: u16 = 1 << 1;
+const FIELD_CHARACTER: u16 = 1 << 2;
+const FIELD_TAG: u16 = 1 << 3;
+const FIELD_FG: u16 = 1 << 4;
+const FIELD_BG: u16 = 1 << 5;
+const FIELD_ATTRIBUTES: u16 = 1 << 6;
+const FIELD_VISIBLE: u16 = 1 << 7;
+const FIELD_ACTIVE: u16 = 1 << 8;
+const FIELD_FACING: u16 = 1 << 9;
+const FIELD_FRAME: u16 = 1 << 10;
+const ALL_FIELDS: u16 = (1 << 11) - 1;
+
+/// Fields of an object that are replicated
+///
+/// # Notes
+/// - Sprites, behaviors, and other components stay on each side, clients attach
+///   them when spawning by tag, see [`ReplicationClient::spawner`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectState {
+    /// Horizontal position in grid cells
+    pub x: usize,
+    /// Vertical position in grid cells
+    pub y: usize,
+    /// Display character
+    pub character: char,
+    /// Classification tag
+    pub tag: String,
+    /// Foreground color
+    pub fg_color: Color,
+    /// Background color
+    pub bg_color: Color,
+    /// Text attributes
+    pub attributes: Attributes,
+    /// Whether the object is drawn
+    pub visible: bool,
+    /// Whether the object is updated
+    pub active: bool,
+    /// Direction of the last move
+    pub facing: Direction,
+    /// Animation frame shown
+    pub current_frame: usize,
+}
+
+impl ObjectState {
+    /// Reads the replicated fields of an object
+    pub fn from_object(obj: &GameObject) -> Self {
+        Self {
+            x: obj.x,
+            y: obj.y,
+            character: obj.character,
+            tag: obj.tag.clone(),
+            fg_color: obj.fg_color,
+            bg_color: obj.bg_color,
+            attributes: obj.attributes,
+            visible: obj.visible,
+            active: obj.active,
+            facing: obj.facing,
+            current_frame: obj.current_frame,
+        }
+    }
+
+    /// Writes the replicated fields onto an object
+    pub fn apply_to(&self, obj: &mut GameObject) {
+        obj.x = self.x;
+        obj.y = self.y;
+        obj.character = self.character;
+        obj.tag.clone_from(&self.tag);
+        obj.fg_color = self.fg_color;
+        obj.bg_color = self.bg_color;
+        obj.attributes = self.attributes;
+        obj.visible = self.visible;
+        obj.active = self.active;
+        obj.facing = self.facing;
+        obj.current_frame = self.current_frame;
+    }
+
+    /// Creates a plain object with the replicated fields
+    pub fn to_object(&self) -> GameObject {
+        let mut obj = GameObject::new(self.x, self.y, self.character);
+        self.apply_to(&mut obj);
+        obj
+    }
+
+    /// Mask of the fields that differ from another state
+    fn changes(&self, baseline: &ObjectState) -> u16 {
+        [
+            (self.x != baseline.x, FIELD_X),
+            (self.y != baseline.y, FIELD_Y),
+            (self.character != baseline.character, FIELD_CHARACTER),
+            (self.tag != baseline.tag, FIELD_TAG),
+            (self.fg_color != baseline.fg_color, FIELD_FG),
+            (self.bg_color != baseline.bg_color, FIELD_BG),
+            (self.attributes != baseline.attributes, FIELD_ATTRIBUTES),
+            (self.visible != baseline.visible, FIELD_VISIBLE),
+            (self.active != baseline.active, FIELD_ACTIVE),
+            (self.facing != baseline.facing, FIELD_FACING),
+            (self.current_frame != baseline.current_frame, FIELD_FRAME),
+        ]
+        .into_iter()
+        .filter(|(changed, _)| *changed)
+        .fold(0, |mask, (_, field)| mask | field)
+    }
+
+    /// Appends the masked fields
+    fn write(&self, mask: u16, out: &mut Vec<u8>) {
+        if mask & FIELD_X != 0 {
+            write_u32(out, self.x as u32);
+        }
+        if mask & FIELD_Y != 0 {
+            write_u32(out, self.y as u32);
+        }
+        if mask & FIELD_CHARACTER != 0 {
+            write_u32(out, u32::from(self.character));
+        }
+        if mask & FIELD_TAG != 0 {
+            write_u16(out, self.tag.len() as u16);
+            out.extend_from_slice(self.tag.as_bytes());
+        }
+        if mask & FIELD_FG != 0 {
+            write_color(out, self.fg_color);
+        }
+        if mask & FIELD_BG != 0 {
+            write_color(out, self.bg_color);
+        }
+        if mask & FIELD_ATTRIBUTES != 0 {
+            let a = self.attributes;
+            let bits = [a.bold, a.dim, a.italic, a.underline, a.blink, a.reverse]
+                .into_iter()
+                .enumerate()
+                .fold(0u8, |bits, (bit, set)| bits | (u8::from(set) << bit));
+            out.push(bits);
+        }
+        if mask & FIELD_VISIBLE != 0 {
+            out.push(u8::from(self.visible));
+        }
+        if mask & FIELD_ACTIVE != 0 {
+            out.push(u8::from(self.active));
+        }
+        if mask & FIELD_FACING != 0 {
+            out.push(Direction::ALL.iter().position(|&direction| direction == self.facing).unwrap_or(4) as u8);
+        }
+        if mask & FIELD_FRAME != 0 {
+            write_u32(out, self.current_frame as u32);
+        }
+    }
+
+    /// Overwrites the masked fields from a message
+    fn read(&mut self, mask: u16, reader: &mut Reader) -> io::Result<()> {
+        if mask & FIELD_X != 0 {
+            self.x = reader.u32()? as usize;
+        }
+        if mask & FIELD_Y != 0 {
+            self.y = reader.u32()? as usize;
+        }
+        if mask & FIELD_CHARACTER != 0 {
+            self.character = char::from_u32(reader.u32()?).ok_or_else(|| invalid("replicated character is not valid"))?;
+        }
+        if mask & FIELD_TAG != 0 {
+            let length = usize::from(reader.u16()?);
+            self.tag = String::from_utf8(reader.bytes(length)?.to_vec()).map_err(|_| invalid("replicated tag is not utf-8"))?;
+        }
+        if mask & FIELD_FG != 0 {
+            self.fg_color = reader.color()?;
+        }
+        if mask & FIELD_BG != 0 {
+            self.bg_color = reader.color()?;
+        }
+        if mask & FIELD_ATTRIBUTES != 0 {
+            let bits = reader.u8()?;
+            let set = |bit: u8| bits & (1 << bit) != 0;
+            self.attributes = Attributes { bold: set(0), dim: set(1), italic: set(2), underline: set(3), blink: set(4), reverse: set(5) };
+        }
+        if mask & FIELD_VISIBLE != 0 {
+            self.visible = reader.u8()? != 0;
+        }
+        if mask & FIELD_ACTIVE != 0 {
+            self.active = reader.u8()? != 0;
+        }
+        if mask & FIELD_FACING != 0 {
+            self.facing = *Direction::ALL.get(usize::from(reader.u8()?)).ok_or_else(|| invalid("replicated facing is not a direction"))?;
+        }
+        if mask & FIELD_FRAME != 0 {
+            self.current_frame = reader.u32()? as usize;
+        }
+        Ok(())
+    }
+}
+
+/// Every replicated object at a server tick, by net id
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorldState {
+    /// Server tick the state was captured at
+    pub tick: u32,
+    /// Object states by net id
+    pub objects: BTreeMap<u32, ObjectState>,
+}
+
+impl WorldState {
+    /// Captures the objects that have a `net_id`
+    pub fn capture(tick: u32, objects: &[GameObject]) -> Self {
+        let objects = objects.iter().filter_map(|obj| Some((obj.net_id?, ObjectState::from_object(obj)))).collect();
+        Self { tick, objects }
+    }
+
+    /// Encodes the state as a diff
+    ///
+    /// # Arguments
+    /// * `baseline` - State the receiver already has, `None` encodes every object in full
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{game_object::GameObject, replication::WorldState};
+    ///
+    /// let mut player = GameObject::new(1, 1, '@').with_net_id(7);
+    /// let before = WorldState::capture(1, std::slice::from_ref(&player));
+    /// player.y = 2;
+    /// let after = WorldState::capture(2, &[player]);
+    ///
+    /// let message = after.encode(Some(&before));
+    /// let decoded = WorldState::decode(&message, |tick| (tick == 1).then_some(&before)).unwrap();
+    /// assert_eq!(decoded, after);
+    /// ```
+    pub fn encode(&self, baseline: Option<&WorldState>) -> Vec<u8> {
+        let mut out = Vec::new();
+        write_u32(&mut out, self.tick);
+        write_u32(&mut out, baseline.map_or(FULL_STATE, |baseline| baseline.tick));
+
+        let changed: Vec<(u32, &ObjectState, u16)> = self
+            .objects
+            .iter()
+            .filter_map(|(&id, state)| {
+                let mask = baseline.and_then(|baseline| baseline.objects.get(&id)).map_or(ALL_FIELDS, |old| state.changes(old));
+                (mask != 0).then_some((id, state, mask))
+            })
+            .collect();
+        write_u16(&mut out, changed.len() as u16);
+        for (id, state, mask) in changed {
+            write_u32(&mut out, id);
+            write_u16(&mut out, mask);
+            state.write(mask, &mut out);
+        }
+
+        let removed: Vec<u32> = baseline
+            .map(|baseline| baseline.objects.keys().copied().filter(|id| !self.objects.contains_key(id)).collect())
+            .unwrap_or_default();
+        write_u16(&mut out, removed.len() as u16);
+        for id in removed {
+            write_u32(&mut out, id);
+        }
+        out
+    }
+
+    /// Rebuilds a state from a message
+    ///
+    /// # Arguments
+    /// * `baseline` - Looks up a state the receiver kept by tick
+    ///
+    /// # Returns
+    /// `Err` with [`io::ErrorKind::InvalidData`] for malformed messages, or
+    /// [`io::ErrorKind::NotFound`] when the baseline is not kept anymore
+    pub fn decode<'a>(bytes: &[u8], baseline: impl FnOnce(u32) -> Option<&'a WorldState>) -> io::Result<Self> {
+        let mut reader = Reader { bytes, position: 0 };
+        let tick = reader.u32()?;
+        let baseline_tick = reader.u32()?;
+        let mut state = if baseline_tick == FULL_STATE {
+            WorldState { tick, objects: BTreeMap::new() }
+        } else {
+            let baseline = baseline(baseline_tick).ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "replication baseline is not kept"))?;
+            WorldState { tick, objects: baseline.objects.clone() }
+        };
+
+        for _ in 0..reader.u16()? {
+            let id = reader.u32()?;
+            let mask = reader.u16()?;
+            let object = state.objects.entry(id).or_insert_with(|| ObjectState::from_object(&GameObject::new(0, 0, ' ')));
+            object.read(mask, &mut reader)?;
+        }
+        for _ in 0..reader.u16()? {
+            let id = reader.u32()?;
+            state.objects.remove(&id);
+        }
+        Ok(state)
+    }
+}
+
+/// Captures world states and encodes them for each client
+///
+/// # Example
+/// ```
+/// use lonely_engine::{game_object::GameObject, replication::ReplicationServer};
+///
+/// let mut server = ReplicationServer::new().history(64);
+/// let mut enemy = GameObject::new(10, 2, 'g');
+/// let id = server.replicate(&mut enemy);
+/// assert_eq!(enemy.net_id, Some(id));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReplicationServer {
+    tick: u32,
+    next_id: u32,
+    /// Recent states, oldest first
+    states: VecDeque<WorldState>,
+    history: usize,
+    clients: BTreeMap<u32, ClientState>,
+}
+
+/// What the server knows about a client
+#[derive(Debug, Clone, Copy, Default)]
+struct ClientState {
+    /// Last tick the client acknowledged
+    acked: Option<u32>,
+    /// Last input sequence applied for the client
+    input: u32,
+}
+
+impl Default for ReplicationServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReplicationServer {
+    /// Creates a server at tick 0 keeping [`DEFAULT_HISTORY`] states
+    pub fn new() -> Self {
+        Self { tick: 0, next_id: 1, states: VecDeque::new(), history: DEFAULT_HISTORY, clients: BTreeMap::new() }
+    }
+
+    /// Sets how many states are kept as baselines, clients acknowledging older ticks get full states
+    pub fn history(mut self, ticks: usize) -> Self {
+        self.history = ticks.max(1);
+        self
+    }
+
+    /// Gives an object a net id so it is replicated, keeping an id it already has
+    ///
+    /// # Returns
+    /// The object's net id
+    pub fn replicate(&mut self, obj: &mut GameObject) -> u32 {
+        if let Some(id) = obj.net_id {
+            self.next_id = self.next_id.max(id.saturating_add(1));
+            return id;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        obj.net_id = Some(id);
+        id
+    }
+
+    /// Captures the replicated objects as the next tick
+    ///
+    /// # Returns
+    /// The new tick
+    pub fn capture(&mut self, objects: &[GameObject]) -> u32 {
+        self.tick += 1;
+        self.states.push_back(WorldState::capture(self.tick, objects));
+        while self.states.len() > self.history {
+            self.states.pop_front();
+        }
+        self.tick
+    }
+
+    /// Last captured tick, `0` before the first capture
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Last captured state
+    pub fn latest(&self) -> Option<&WorldState> {
+        self.states.back()
+    }
+
+    /// Starts sending to a client, the first message is a full state
+    pub fn add_client(&mut self, client: u32) {
+        self.clients.entry(client).or_default();
+    }
+
+    /// Stops tracking a client
+    pub fn remove_client(&mut self, client: u32) {
+        self.clients.remove(&client);
+    }
+
+    /// Ids of the clients sent to
+    pub fn clients(&self) -> impl Iterator<Item = u32> + '_ {
+        self.clients.keys().copied()
+    }
+
+    /// Records that a client received a tick, making it the baseline of its next message
+    ///
+    /// # Notes
+    /// - Acknowledgements older than the client's last one are ignored, so reordered packets are harmless
+    pub fn acknowledge(&mut self, client: u32, tick: u32) {
+        if let Some(state) = self.clients.get_mut(&client) {
+            state.acked = Some(state.acked.map_or(tick, |acked| acked.max(tick)));
+        }
+    }
+
+    /// Last tick a client acknowledged
+    pub fn acknowledged(&self, client: u32) -> Option<u32> {
+        self.clients.get(&client).and_then(|state| state.acked)
+    }
+
+    /// Records the last input sequence applied for a client, sent back so it can drop confirmed predictions
+    ///
+    /// # Notes
+    /// - Older sequences are ignored, like tick acknowledgements
+    pub fn acknowledge_input(&mut self, client: u32, sequence: u32) {
+        if let Some(state) = self.clients.get_mut(&client) {
+            state.input = state.input.max(sequence);
+        }
+    }
+
+    /// Last input sequence applied for a client, `0` before any
+    pub fn input_acknowledged(&self, client: u32) -> u32 {
+        self.clients.get(&client).map_or(0, |state| state.input)
+    }
+
+    /// Encodes the latest state for a client, diffed against its acknowledged tick
+    ///
+    /// # Notes
+    /// - Unknown clients and clients whose baseline left the history get a full state
+    pub fn encode(&self, client: u32) -> Vec<u8> {
+        let baseline = self.acknowledged(client).and_then(|tick| self.states.iter().find(|state| state.tick == tick));
+        let mut out = Vec::new();
+        write_u32(&mut out, self.input_acknowledged(client));
+        match self.states.back() {
+            Some(latest) => out.extend(latest.encode(baseline)),
+            None => out.extend(WorldState::default().encode(None)),
+        }
+        out
+    }
+}
+
+/// Builds client-side objects for net ids seen for the first time
+type Spawner = Box<dyn Fn(&ObjectState) -> GameObject>;
+
+/// Rebuilds world states from server messages and applies them to local objects
+pub struct ReplicationClient {
+    /// Received states, oldest first
+    states: VecDeque<WorldState>,
+    history: usize,
+    /// Seconds between server ticks
+    tick_interval: f32,
+    /// Progress from the second newest to the newest state, 0 to 1
+    blend: f32,
+    /// Last input sequence the server applied for this client
+    input_ack: u32,
+    /// Net ids moved by local prediction, never overwritten by [`apply`](Self::apply)
+    predicted: BTreeSet<u32>,
+    /// Newest tick [`apply`](Self::apply) handed to interpolations
+    applied_tick: u32,
+    spawner: Spawner,
+}
+
+impl fmt::Debug for ReplicationClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplicationClient")
+            .field("tick", &self.tick())
+            .field("tick_interval", &self.tick_interval)
+            .field("blend", &self.blend)
+            .finish()
+    }
+}
+
+impl ReplicationClient {
+    /// Creates a client keeping [`DEFAULT_HISTORY`] states
+    ///
+    /// # Arguments
+    /// * `tick_interval` - Seconds between server captures, the time positions take to move to a new state
+    pub fn new(tick_interval: f32) -> Self {
+        Self {
+            states: VecDeque::new(),
+            history: DEFAULT_HISTORY,
+            tick_interval: tick_interval.max(f32::EPSILON),
+            blend: 1.0,
+            input_ack: 0,
+            predicted: BTreeSet::new(),
+            applied_tick: 0,
+            spawner: Box::new(ObjectState::to_object),
+        }
+    }
+
+    /// Sets how many received states are kept as baselines, should match the server's
+    pub fn history(mut self, ticks: usize) -> Self {
+        self.history = ticks.max(2);
+        self
+    }
+
+    /// Sets how objects are created for new net ids, such as attaching sprites by tag
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{game_object::GameObject, replication::ReplicationClient, sprite::Sprite};
+    ///
+    /// let client = ReplicationClient::new(0.05).spawner(|state| match state.tag.as_str() {
+    ///     "ship" => GameObject::with_sprite(state.x, state.y, Sprite::from_str("/^\\")),
+    ///     _ => state.to_object(),
+    /// });
+    /// ```
+    pub fn spawner(mut self, spawner: impl Fn(&ObjectState) -> GameObject + 'static) -> Self {
+        self.spawner = Box::new(spawner);
+        self
+    }
+
+    /// Decodes a server message
+    ///
+    /// # Returns
+    /// The message's tick, to acknowledge to the server, or `Err` when it cannot be decoded
+    ///
+    /// # Notes
+    /// - Messages older than the newest state are decoded but dropped
+    pub fn receive(&mut self, bytes: &[u8]) -> io::Result<u32> {
+        let input_ack = Reader { bytes, position: 0 }.u32()?;
+        let state = WorldState::decode(&bytes[4..], |tick| self.states.iter().find(|state| state.tick == tick))?;
+        let tick = state.tick;
+        if self.states.back().is_none_or(|latest| tick > latest.tick) {
+            self.input_ack = input_ack;
+            self.states.push_back(state);
+            while self.states.len() > self.history {
+                self.states.pop_front();
+            }
+            self.blend = if self.states.len() > 1 { 0.0 } else { 1.0 };
+        }
+        Ok(tick)
+    }
+
+    /// Advances interpolation towards the newest state
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds since the last update
+    pub fn update(&mut self, delta_time: f32) {
+        self.blend = (self.blend + delta_time / self.tick_interval).min(1.0);
+    }
+
+    /// Newest tick received, `0` before the first message
+    pub fn tick(&self) -> u32 {
+        self.states.back().map_or(0, |state| state.tick)
+    }
+
+    /// Newest state received
+    pub fn latest(&self) -> Option<&WorldState> {
+        self.states.back()
+    }
+
+    /// Last input sequence the server applied for this client, as of the newest state
+    pub fn input_acknowledged(&self) -> u32 {
+        self.input_ack
+    }
+
+    /// Leaves an object to local prediction, see [`prediction`](crate::prediction)
+    pub fn predict(&mut self, net_id: u32) {
+        self.predicted.insert(net_id);
+    }
+
+    /// Hands an object back to the server's states
+    pub fn stop_predicting(&mut self, net_id: u32) {
+        self.predicted.remove(&net_id);
+    }
+
+    /// Returns whether an object is left to local prediction
+    pub fn is_predicted(&self, net_id: u32) -> bool {
+        self.predicted.contains(&net_id)
+    }
+
+    /// Makes local objects match the newest state
+    ///
+    /// # Notes
+    /// - Objects with a net id missing from the state are removed, new net ids are spawned at the end
+    /// - Positions are interpolated from the previous state, every other field is set right away
+    /// - Objects without a net id are left alone, such as local effects and the HUD
+    /// - Predicted objects are only spawned and removed, their state comes from reconciliation
+    /// - Objects with an [`Interpolation`](crate::interpolation::Interpolation) get each new position pushed into it and are placed by the engine
+    pub fn apply(&mut self, objects: &mut Vec<GameObject>) {
+        let Some(latest) = self.states.back() else {
+            return;
+        };
+        let previous = self.states.len().checked_sub(2).and_then(|index| self.states.get(index));
+        let new_tick = latest.tick > self.applied_tick;
+        self.applied_tick = latest.tick;
+
+        objects.retain(|obj| obj.net_id.is_none_or(|id| latest.objects.contains_key(&id)));
+        for (&id, state) in &latest.objects {
+            let index = match objects.iter().position(|obj| obj.net_id == Some(id)) {
+                Some(index) => index,
+                None => {
+                    let mut obj = (self.spawner)(state);
+                    obj.net_id = Some(id);
+                    objects.push(obj);
+                    objects.len() - 1
+                },
+            };
+            if self.predicted.contains(&id) {
+                continue;
+            }
+            let obj = &mut objects[index];
+            let (x, y) = (obj.x, obj.y);
+            state.apply_to(obj);
+            if let Some(interpolation) = obj.interpolation.as_mut() {
+                if new_tick {
+                    interpolation.push(state.x, state.y);
+                }
+                if !interpolation.is_empty() {
+                    obj.x = x;
+                    obj.y = y;
+                }
+            } else if let Some(old) = previous.and_then(|previous| previous.objects.get(&id)) {
+                obj.x = lerp_cell(old.x, state.x, self.blend);
+                obj.y = lerp_cell(old.y, state.y, self.blend);
+            }
+        }
+    }
+}
+
+/// Cell between two cells at a fraction of the way
+fn lerp_cell(from: usize, to: usize, t: f32) -> usize {
+    (from as f32 + (to as f32 - from as f32) * t).round().max(0.0) as usize
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn write_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_color(out: &mut Vec<u8>, color: Color) {
+    match color {
+        Color::Default => out.push(0),
+        Color::Indexed(index) => out.extend_from_slice(&[1, index]),
+        Color::Rgb(r, g, b) => out.extend_from_slice(&[2, r, g, b]),
+    }
+}
+
+/// Cursor over a message
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, length: usize) -> io::Result<&'a [u8]> {
+        let bytes = self.bytes.get(self.position..self.position + length).ok_or_else(|| invalid("replication message ended early"))?;
+        self.position += length;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> io::Result<u16> {
+        let b = self.bytes(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        let b = self.bytes(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn color(&mut self) -> io::Result<Color> {
+        match self.u8()? {
+            0 => Ok(Color::Default),
+            1 => Ok(Color::Indexed(self.u8()?)),
+            2 => Ok(Color::Rgb(self.u8()?, self.u8()?, self.u8()?)),
+            _ => Err(invalid("replicated color is not valid")),
+        }
+    }
+}
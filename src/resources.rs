@@ -0,0 +1,116 @@
+//! Typed global state shared between systems
+//!
+//! [`Resources`] holds at most one value of each type, such as the game's
+//! configuration, difficulty, or wave counter. The engine owns one, filled with
+//! [`Engine::insert_resource`] and read by updatables through
+//! `UpdateContext::resource` and `UpdateContext::resource_mut`, so shared state
+//! does not need an `Rc<RefCell<_>>` passed into every updatable's constructor.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::engine::{Engine, EngineCommand, Updatable, UpdateContext};
+//!
+//! struct Difficulty {
+//!     enemy_speed: f32,
+//! }
+//!
+//! struct Spawner;
+//!
+//! impl Updatable for Spawner {
+//!     fn update_with_context(&mut self, ctx: &mut UpdateContext) -> Vec<EngineCommand> {
+//!         let ramp = 0.01 * ctx.delta_time;
+//!         if let Some(difficulty) = ctx.resource_mut::<Difficulty>() {
+//!             difficulty.enemy_speed += ramp;
+//!         }
+//!         Vec::new()
+//!     }
+//! }
+//!
+//! let mut engine = Engine::new(80, 24);
+//! engine.insert_resource(Difficulty { enemy_speed: 2.0 });
+//! engine.add_updatable(Spawner);
+//! assert_eq!(engine.resource::<Difficulty>().unwrap().enemy_speed, 2.0);
+//! ```
+//!
+//! [`Engine::insert_resource`]: crate::engine::Engine::insert_resource
+
+use std::{any::{Any, TypeId, type_name}, collections::HashMap, fmt};
+
+/// One value per type, looked up by type
+///
+/// # Example
+/// ```
+/// use lonely_engine::resources::Resources;
+///
+/// let mut resources = Resources::new();
+/// resources.insert(3u32);
+/// *resources.get_mut::<u32>().unwrap() += 1;
+/// assert_eq!(resources.get::<u32>(), Some(&4));
+/// assert_eq!(resources.insert(10u32), Some(4));
+/// ```
+#[derive(Default)]
+pub struct Resources {
+    /// Values with the name of their type, for debug output
+    values: HashMap<TypeId, (&'static str, Box<dyn Any>)>,
+}
+
+impl fmt::Debug for Resources {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<&str> = self.values.values().map(|(name, _)| *name).collect();
+        names.sort_unstable();
+        f.debug_set().entries(names).finish()
+    }
+}
+
+impl Resources {
+    /// Creates an empty container
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores a value, replacing the value of the same type
+    ///
+    /// # Returns
+    /// The replaced value, if there was one
+    pub fn insert<T: 'static>(&mut self, value: T) -> Option<T> {
+        self.values
+            .insert(TypeId::of::<T>(), (type_name::<T>(), Box::new(value)))
+            .and_then(|(_, previous)| previous.downcast().ok())
+            .map(|previous| *previous)
+    }
+
+    /// Value of a type, `None` when none was inserted
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.values.get(&TypeId::of::<T>()).and_then(|(_, value)| value.downcast_ref())
+    }
+
+    /// Mutable value of a type, `None` when none was inserted
+    pub fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        self.values.get_mut(&TypeId::of::<T>()).and_then(|(_, value)| value.downcast_mut())
+    }
+
+    /// Takes the value of a type out
+    pub fn remove<T: 'static>(&mut self) -> Option<T> {
+        self.values.remove(&TypeId::of::<T>()).and_then(|(_, value)| value.downcast().ok()).map(|value| *value)
+    }
+
+    /// Returns whether a value of a type is stored
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Number of stored values
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns whether nothing is stored
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Removes every value
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
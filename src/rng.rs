@@ -0,0 +1,85 @@
+//! Small, dependency-free pseudo random number generator
+//!
+//! Provides [`Rng`], a seedable xorshift generator used by engine effects and
+//! available to games. It is fast and reproducible, not cryptographically secure.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seedable xorshift64* random number generator
+///
+/// # Example
+/// ```
+/// # use lonely_engine::rng::Rng;
+/// let mut rng = Rng::new(42);
+/// let roll = rng.range(1, 7); // 1..=6
+/// assert!((1..7).contains(&roll));
+///
+/// // Same seed, same sequence
+/// assert_eq!(Rng::new(7).next_u64(), Rng::new(7).next_u64());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a generator from a seed (any value, including zero)
+    pub fn new(seed: u64) -> Self {
+        // Scramble the seed with splitmix64 so nearby seeds give unrelated sequences
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        Self { state: if z == 0 { 0x2545_F491_4F6C_DD1D } else { z } }
+    }
+
+    /// Creates a generator seeded from the system clock
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos() as u64)
+            .unwrap_or_default();
+        Self::new(nanos)
+    }
+
+    /// Returns the next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Returns a float in `[0.0, 1.0)`
+    pub fn next_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+
+    /// Returns an integer in `[low, high)`, or `low` when the range is empty
+    pub fn range(&mut self, low: usize, high: usize) -> usize {
+        if high <= low {
+            return low;
+        }
+        low + (self.next_u64() % (high - low) as u64) as usize
+    }
+
+    /// Returns `true` with the given probability (0.0 to 1.0)
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.next_f32() < probability
+    }
+
+    /// Shuffles a slice in place (Fisher-Yates)
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.range(0, i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::from_time()
+    }
+}
@@ -0,0 +1,356 @@
+//! Arcade score with combos, multipliers, and milestones
+//!
+//! Provides:
+//! - [`Score`] counting points per source tag, with a combo that grows with
+//!   every score and breaks when no points arrive within its window
+//! - [`MultiplierRule`]s scaling awarded points by combo, source, or a flat bonus
+//! - [`ScoreView`] drawing the score and combo as a HUD line
+//!
+//! The engine owns a [`Score`], updated through `EngineCommand::AddScore` and
+//! `EngineCommand::BreakCombo` and ticked with game time. It emits
+//! `EngineEvent::ScoreAdded` for every award, `EngineEvent::ScoreMilestone` for
+//! every milestone crossed, and `EngineEvent::ComboEnded` when a combo breaks.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::score::{MultiplierRule, Score};
+//!
+//! let mut score = Score::new()
+//!     .combo_window(1.5)
+//!     .with_rule("chain", MultiplierRule::Combo { every: 5, bonus: 1.0, max: 4.0 })
+//!     .with_rule("headshots", MultiplierRule::Source("headshot".into(), 2.0))
+//!     .milestones(vec![1_000, 5_000, 10_000]);
+//!
+//! score.add(100, "enemy");
+//! score.add(100, "headshot");
+//! assert_eq!(score.points(), 300);
+//! assert_eq!(score.combo(), 2);
+//!
+//! score.update(2.0); // Too slow, the combo breaks
+//! assert_eq!(score.combo(), 0);
+//! assert_eq!(score.best_combo(), 2);
+//! ```
+
+use std::collections::BTreeMap;
+use crate::{color::Color, renderer::Renderer, style::Style};
+
+/// Combo window of a new score in seconds
+pub const DEFAULT_COMBO_WINDOW: f32 = 2.0;
+
+/// Scales awarded points, all rules multiply together
+#[derive(Debug, Clone, PartialEq)]
+pub enum MultiplierRule {
+    /// `bonus` more for every `every` combo steps, capped at `max`: `1 + combo / every * bonus`
+    Combo {
+        /// Combo steps per bonus
+        every: u32,
+        /// Multiplier added per `every` steps
+        bonus: f32,
+        /// Highest multiplier the rule gives
+        max: f32,
+    },
+    /// Points from a source tag count this many times
+    Source(String, f32),
+    /// Every award counts this many times, such as a double points power-up
+    Flat(f32),
+}
+
+impl MultiplierRule {
+    /// Factor the rule applies to an award
+    fn factor(&self, combo: u32, source: &str) -> f32 {
+        match self {
+            MultiplierRule::Combo { every, bonus, max } => {
+                let steps = combo / (*every).max(1);
+                (1.0 + steps as f32 * bonus).min(*max)
+            },
+            MultiplierRule::Source(tag, factor) if tag == source => *factor,
+            MultiplierRule::Source(_, _) => 1.0,
+            MultiplierRule::Flat(factor) => *factor,
+        }
+    }
+}
+
+/// Something that happened to a score, turned into engine events
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScoreEvent {
+    /// Points awarded after multipliers, with their source tag
+    Added(i64, String),
+    /// The total reached a milestone
+    Milestone(i64),
+    /// A combo broke, with the number of scores it chained
+    ComboEnded(u32),
+}
+
+/// Points, combo, and multipliers of a play session
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    points: i64,
+    by_source: BTreeMap<String, i64>,
+    combo: u32,
+    best_combo: u32,
+    /// Seconds a combo survives without new points
+    combo_window: f32,
+    /// Seconds left before the current combo breaks
+    combo_remaining: f32,
+    /// Rules by id
+    rules: Vec<(String, MultiplierRule)>,
+    /// Milestones not reached yet, ascending
+    milestones: Vec<i64>,
+    events: Vec<ScoreEvent>,
+}
+
+impl Default for Score {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Score {
+    /// Creates a zero score with a [`DEFAULT_COMBO_WINDOW`] and no rules or milestones
+    pub fn new() -> Self {
+        Self {
+            points: 0,
+            by_source: BTreeMap::new(),
+            combo: 0,
+            best_combo: 0,
+            combo_window: DEFAULT_COMBO_WINDOW,
+            combo_remaining: 0.0,
+            rules: Vec::new(),
+            milestones: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Sets how many seconds a combo survives without new points
+    pub fn combo_window(mut self, seconds: f32) -> Self {
+        self.combo_window = seconds.max(0.0);
+        self
+    }
+
+    /// Adds a multiplier rule, see [`set_rule`](Self::set_rule)
+    pub fn with_rule(mut self, id: &str, rule: MultiplierRule) -> Self {
+        self.set_rule(id, rule);
+        self
+    }
+
+    /// Sets the totals that emit a milestone event when reached, in any order
+    pub fn milestones(mut self, milestones: Vec<i64>) -> Self {
+        self.set_milestones(milestones);
+        self
+    }
+
+    /// Adds a multiplier rule, replacing one with the same id
+    pub fn set_rule(&mut self, id: &str, rule: MultiplierRule) {
+        match self.rules.iter_mut().find(|(existing, _)| existing == id) {
+            Some((_, existing)) => *existing = rule,
+            None => self.rules.push((id.to_string(), rule)),
+        }
+    }
+
+    /// Removes a multiplier rule, such as an expired power-up
+    pub fn remove_rule(&mut self, id: &str) {
+        self.rules.retain(|(existing, _)| existing != id);
+    }
+
+    /// Replaces the milestones, those already below the total are skipped
+    pub fn set_milestones(&mut self, mut milestones: Vec<i64>) {
+        milestones.sort_unstable();
+        milestones.dedup();
+        milestones.retain(|&milestone| milestone > self.points);
+        self.milestones = milestones;
+    }
+
+    /// Scores points, extending the combo
+    ///
+    /// # Arguments
+    /// * `points` - Base points, negative for penalties
+    /// * `source` - Tag of what scored, such as `"enemy"` or `"coin"`, for source rules and per-source totals
+    ///
+    /// # Returns
+    /// Points awarded after multipliers
+    ///
+    /// # Notes
+    /// - The award counts towards the combo before multipliers are applied,
+    ///   so the fifth hit of a combo already gets a five-step bonus
+    /// - Penalties are not multiplied and do not extend the combo
+    pub fn add(&mut self, points: i64, source: &str) -> i64 {
+        let awarded = if points > 0 {
+            self.combo += 1;
+            self.best_combo = self.best_combo.max(self.combo);
+            self.combo_remaining = self.combo_window;
+            (points as f64 * f64::from(self.multiplier_for(source))).round() as i64
+        } else {
+            points
+        };
+
+        self.points += awarded;
+        *self.by_source.entry(source.to_string()).or_insert(0) += awarded;
+        self.events.push(ScoreEvent::Added(awarded, source.to_string()));
+        let reached = self.milestones.iter().take_while(|&&milestone| milestone <= self.points).count();
+        for milestone in self.milestones.drain(..reached) {
+            self.events.push(ScoreEvent::Milestone(milestone));
+        }
+        awarded
+    }
+
+    /// Counts down the combo window
+    ///
+    /// # Arguments
+    /// * `delta_time` - Game seconds since the last update
+    pub fn update(&mut self, delta_time: f32) {
+        if self.combo == 0 {
+            return;
+        }
+        self.combo_remaining -= delta_time;
+        if self.combo_remaining <= 0.0 {
+            self.break_combo();
+        }
+    }
+
+    /// Ends the current combo, such as when the player takes damage
+    pub fn break_combo(&mut self) {
+        if self.combo > 0 {
+            self.events.push(ScoreEvent::ComboEnded(self.combo));
+        }
+        self.combo = 0;
+        self.combo_remaining = 0.0;
+    }
+
+    /// Starts a new session at zero, keeping rules, the combo window, and milestones not reached yet
+    pub fn reset(&mut self) {
+        self.points = 0;
+        self.by_source.clear();
+        self.combo = 0;
+        self.best_combo = 0;
+        self.combo_remaining = 0.0;
+    }
+
+    /// Total points
+    pub fn points(&self) -> i64 {
+        self.points
+    }
+
+    /// Points scored from a source tag
+    pub fn source_points(&self, source: &str) -> i64 {
+        self.by_source.get(source).copied().unwrap_or(0)
+    }
+
+    /// Scores chained in the current combo, `0` without one
+    pub fn combo(&self) -> u32 {
+        self.combo
+    }
+
+    /// Longest combo since the last reset
+    pub fn best_combo(&self) -> u32 {
+        self.best_combo
+    }
+
+    /// Seconds left before the current combo breaks
+    pub fn combo_remaining(&self) -> f32 {
+        self.combo_remaining
+    }
+
+    /// Fraction of the combo window left, for drawing a draining bar
+    pub fn combo_progress(&self) -> f32 {
+        if self.combo_window <= 0.0 { 0.0 } else { (self.combo_remaining / self.combo_window).clamp(0.0, 1.0) }
+    }
+
+    /// Multiplier for sources without a source rule at the current combo
+    pub fn multiplier(&self) -> f32 {
+        self.multiplier_for("")
+    }
+
+    /// Multiplier the next award from a source would get, without counting it towards the combo
+    pub fn multiplier_for(&self, source: &str) -> f32 {
+        self.rules.iter().map(|(_, rule)| rule.factor(self.combo, source)).product()
+    }
+
+    /// Returns and clears what happened since the last call
+    pub fn take_events(&mut self) -> Vec<ScoreEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
+
+/// Draws a score as a HUD line: `SCORE 001250  x3 COMBO 12`
+///
+/// # Example
+/// ```
+/// use lonely_engine::{renderer::Renderer, score::{Score, ScoreView}};
+///
+/// let mut score = Score::new();
+/// score.add(1250, "coin");
+///
+/// let mut renderer = Renderer::new(40, 3);
+/// ScoreView::new().label("PTS").digits(6).draw(&mut renderer, &score, 1, 0);
+/// assert_eq!(renderer.cell(5, 0).unwrap().ch, '0');
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreView {
+    label: String,
+    digits: usize,
+    style: Style,
+    combo_style: Style,
+}
+
+impl Default for ScoreView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScoreView {
+    /// Creates a view labelled `SCORE` without zero padding
+    pub fn new() -> Self {
+        Self {
+            label: "SCORE".to_string(),
+            digits: 0,
+            style: Style::new().bold(),
+            combo_style: Style::new().fg(Color::BRIGHT_YELLOW).bold(),
+        }
+    }
+
+    /// Sets the text before the points, empty for none
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = label.to_string();
+        self
+    }
+
+    /// Pads the points with zeros to a number of digits
+    pub fn digits(mut self, digits: usize) -> Self {
+        self.digits = digits;
+        self
+    }
+
+    /// Sets the style of the label and points
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the style of the multiplier and combo
+    pub fn combo_style(mut self, style: Style) -> Self {
+        self.combo_style = style;
+        self
+    }
+
+    /// Draws the points, then the multiplier and combo while a combo is running
+    ///
+    /// # Arguments
+    /// * `x`, `y` - First cell of the line
+    pub fn draw(&self, renderer: &mut Renderer, score: &Score, x: usize, y: usize) {
+        let mut text = format!("{:0width$}", score.points(), width = self.digits);
+        if !self.label.is_empty() {
+            text = format!("{} {}", self.label, text);
+        }
+        renderer.draw_styled_text(x, y, &text, &self.style);
+        if score.combo() > 1 {
+            let combo = format!("  x{} COMBO {}", format_multiplier(score.multiplier()), score.combo());
+            renderer.draw_styled_text(x + text.chars().count(), y, &combo, &self.combo_style);
+        }
+    }
+}
+
+/// Multiplier without a fraction when it is whole, `3` or `2.5`
+fn format_multiplier(multiplier: f32) -> String {
+    if multiplier.fract() == 0.0 { format!("{}", multiplier as i64) } else { format!("{:.1}", multiplier) }
+}
@@ -0,0 +1,340 @@
+//! Frame capture and export
+//!
+//! [`Renderer::screenshot`] copies the last presented frame into a [`Screenshot`],
+//! which can be exported as:
+//! - Plain text, for bug reports and text diffs
+//! - ANSI-colored text, viewable with `cat` in a terminal
+//! - HTML with colors, for sharing in browsers
+//! - PNG images, with the `png` feature enabled
+//!
+//! [`Renderer::screenshot`]: crate::renderer::Renderer::screenshot
+
+use std::{fs, io, path::Path};
+use crate::{color::Color, renderer::Cell};
+
+/// Foreground used for cells with the terminal's default color
+const DEFAULT_FG: (u8, u8, u8) = (192, 192, 192);
+/// Background used for cells with the terminal's default color
+const DEFAULT_BG: (u8, u8, u8) = (0, 0, 0);
+
+/// A captured frame
+///
+/// # Example
+/// ```
+/// use lonely_engine::{game_object::GameObject, renderer::Renderer};
+///
+/// let mut renderer = Renderer::new(3, 1);
+/// renderer.set_char(1, 0, &GameObject::new(1, 0, '@'));
+/// renderer.present().unwrap();
+///
+/// let shot = renderer.screenshot();
+/// assert_eq!(shot.to_text(), " @ \n");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Screenshot {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Screenshot {
+    /// Creates a screenshot from row-major cells
+    ///
+    /// # Notes
+    /// - Missing cells are filled with blanks, extra cells are dropped
+    pub fn new(width: usize, height: usize, mut cells: Vec<Cell>) -> Self {
+        cells.resize(width * height, Cell::default());
+        Self { width, height, cells }
+    }
+
+    /// Width in cells
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height in cells
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Cell at a position, `None` outside the frame
+    pub fn cell(&self, x: usize, y: usize) -> Option<&Cell> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    /// Rows of cells, top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    /// Characters only, one line per row
+    pub fn to_text(&self) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            text.extend(row.iter().map(|cell| cell.ch));
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Characters with ANSI colors and attributes, one line per row
+    pub fn to_ansi(&self) -> String {
+        let mut text = String::new();
+        for row in self.rows() {
+            for cell in row {
+                text.push_str(&cell.to_ansi());
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Standalone HTML page showing the frame with its colors
+    ///
+    /// # Notes
+    /// - Adjacent cells with the same style share one `<span>`
+    /// - Default colors are shown as light grey on black
+    pub fn to_html(&self) -> String {
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>Screenshot</title></head>\n<body style=\"background:{}\">\n<pre style=\"font-family:monospace;line-height:1.1;color:{};background:{}\">",
+            css(DEFAULT_BG), css(DEFAULT_FG), css(DEFAULT_BG),
+        );
+
+        for row in self.rows() {
+            let mut run_style: Option<String> = None;
+            for cell in row {
+                let style = cell_css(cell);
+                if run_style.as_ref() != Some(&style) {
+                    if run_style.is_some() {
+                        html.push_str("</span>");
+                    }
+                    html.push_str(&format!("<span style=\"{}\">", style));
+                    run_style = Some(style);
+                }
+                match cell.ch {
+                    '<' => html.push_str("&lt;"),
+                    '>' => html.push_str("&gt;"),
+                    '&' => html.push_str("&amp;"),
+                    c => html.push(c),
+                }
+            }
+            if run_style.is_some() {
+                html.push_str("</span>");
+            }
+            html.push('\n');
+        }
+
+        html.push_str("</pre>\n</body>\n</html>\n");
+        html
+    }
+
+    /// Writes the plain text export to a file
+    pub fn save_text(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_text())
+    }
+
+    /// Writes the ANSI-colored export to a file
+    pub fn save_ansi(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_ansi())
+    }
+
+    /// Writes the HTML export to a file
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::renderer::Renderer;
+    /// # let renderer = Renderer::new(80, 24);
+    /// renderer.screenshot().save_html("bug-report.html").expect("Could not save screenshot");
+    /// ```
+    pub fn save_html(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_html())
+    }
+
+    /// Rasterizes the frame into a PNG image
+    ///
+    /// # Notes
+    /// - Every cell becomes a [`CELL_WIDTH`] x [`CELL_HEIGHT`] pixel block
+    /// - Glyphs come from the built-in 5x7 font, lowercase drawn as uppercase
+    /// - Characters without a glyph are drawn as hollow boxes
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::{renderer::Cell, screenshot::Screenshot};
+    /// let shot = Screenshot::new(2, 1, vec![Cell::new('O'), Cell::new('K')]);
+    /// let png = shot.to_png();
+    /// assert_eq!(&png[1..4], b"PNG");
+    /// ```
+    #[cfg(feature = "png")]
+    pub fn to_png(&self) -> Vec<u8> {
+        let (width, height, pixels) = self.rasterize();
+        png::encode(width, height, &pixels)
+    }
+
+    /// Writes the PNG export to a file
+    #[cfg(feature = "png")]
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_png())
+    }
+}
+
+/// Resolves a cell's displayed `(foreground, background)` RGB, applying reverse video
+fn cell_rgb(cell: &Cell) -> ((u8, u8, u8), (u8, u8, u8)) {
+    let fg = cell.fg.to_rgb().unwrap_or(DEFAULT_FG);
+    let bg = cell.bg.to_rgb().unwrap_or(DEFAULT_BG);
+    if cell.attrs.reverse { (bg, fg) } else { (fg, bg) }
+}
+
+fn cell_css(cell: &Cell) -> String {
+    let (fg, bg) = cell_rgb(cell);
+    let mut style = format!("color:{};background:{}", css(fg), css(bg));
+    if cell.attrs.bold {
+        style.push_str(";font-weight:bold");
+    }
+    if cell.attrs.dim {
+        style.push_str(";opacity:0.6");
+    }
+    if cell.attrs.italic {
+        style.push_str(";font-style:italic");
+    }
+    if cell.attrs.underline {
+        style.push_str(";text-decoration:underline");
+    }
+    style
+}
+
+fn css(rgb: (u8, u8, u8)) -> String {
+    Color::Rgb(rgb.0, rgb.1, rgb.2).to_hex().unwrap_or_default()
+}
+
+/// Pixel width of one cell in rasterized exports
+#[cfg(any(feature = "png", feature = "gif"))]
+pub const CELL_WIDTH: usize = 6;
+/// Pixel height of one cell in rasterized exports
+#[cfg(any(feature = "png", feature = "gif"))]
+pub const CELL_HEIGHT: usize = 10;
+
+#[cfg(any(feature = "png", feature = "gif"))]
+impl Screenshot {
+    /// Renders the frame as 8-bit RGB pixels, [`CELL_WIDTH`] x [`CELL_HEIGHT`] per cell
+    ///
+    /// # Returns
+    /// `(width, height, pixels)` with pixels stored row-major as `r, g, b` triples
+    pub fn rasterize(&self) -> (usize, usize, Vec<u8>) {
+        let font = crate::font::Font::standard();
+        let width = self.width * CELL_WIDTH;
+        let height = self.height * CELL_HEIGHT;
+        let mut pixels = vec![0u8; width * height * 3];
+
+        for (cy, row) in self.rows().enumerate() {
+            for (cx, cell) in row.iter().enumerate() {
+                let (fg, bg) = cell_rgb(cell);
+                let lit = |px: usize, py: usize| glyph_pixel(&font, cell.ch, px, py)
+                    || (cell.attrs.underline && py == CELL_HEIGHT - 1);
+
+                for py in 0..CELL_HEIGHT {
+                    for px in 0..CELL_WIDTH {
+                        let (r, g, b) = if lit(px, py) { fg } else { bg };
+                        let at = ((cy * CELL_HEIGHT + py) * width + cx * CELL_WIDTH + px) * 3;
+                        pixels[at..at + 3].copy_from_slice(&[r, g, b]);
+                    }
+                }
+            }
+        }
+
+        (width, height, pixels)
+    }
+}
+
+/// Returns whether a pixel of a cell is covered by its character
+#[cfg(any(feature = "png", feature = "gif"))]
+fn glyph_pixel(font: &crate::font::Font, ch: char, px: usize, py: usize) -> bool {
+    match ch {
+        ' ' => false,
+        '█' => true,
+        '▀' => py < CELL_HEIGHT / 2,
+        '▄' => py >= CELL_HEIGHT / 2,
+        '▌' => px < CELL_WIDTH / 2,
+        '▐' => px >= CELL_WIDTH / 2,
+        '░' => (px + py).is_multiple_of(4),
+        '▒' => (px + py).is_multiple_of(2),
+        '▓' => !(px + py).is_multiple_of(4),
+        _ => {
+            // 5x7 glyph placed one pixel from the top, leaving a spacing column on the right
+            let (gx, gy) = (px, py.wrapping_sub(1));
+            if gx >= 5 || gy >= 7 {
+                return false;
+            }
+            match font.glyph(ch) {
+                Some(rows) => rows[gy].chars().nth(gx).is_some_and(|pixel| pixel != ' '),
+                None => gx == 0 || gx == 4 || gy == 0 || gy == 6,
+            }
+        },
+    }
+}
+
+/// Minimal PNG encoder (uncompressed deflate, no dependencies)
+#[cfg(feature = "png")]
+mod png {
+    /// Largest block a stored deflate block can hold
+    const MAX_STORED_BLOCK: usize = 65_535;
+
+    /// Encodes 8-bit RGB pixels as a PNG file
+    pub(super) fn encode(width: usize, height: usize, pixels: &[u8]) -> Vec<u8> {
+        // Every scanline starts with filter type 0 (none)
+        let mut raw = Vec::with_capacity((width * 3 + 1) * height);
+        for row in pixels.chunks(width * 3).take(height) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+
+        let mut zlib = vec![0x78, 0x01];
+        let blocks: Vec<&[u8]> = if raw.is_empty() { vec![&[]] } else { raw.chunks(MAX_STORED_BLOCK).collect() };
+        for (i, block) in blocks.iter().enumerate() {
+            zlib.push(u8::from(i == blocks.len() - 1));
+            let len = block.len() as u16;
+            zlib.extend_from_slice(&len.to_le_bytes());
+            zlib.extend_from_slice(&(!len).to_le_bytes());
+            zlib.extend_from_slice(block);
+        }
+        zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend_from_slice(&(width as u32).to_be_bytes());
+        ihdr.extend_from_slice(&(height as u32).to_be_bytes());
+        ihdr.extend_from_slice(&[8, 2, 0, 0, 0]); // 8-bit RGB, deflate, no filter, no interlace
+
+        let mut png = vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+        write_chunk(&mut png, b"IHDR", &ihdr);
+        write_chunk(&mut png, b"IDAT", &zlib);
+        write_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+        png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        png.extend_from_slice(kind);
+        png.extend_from_slice(data);
+        let crc = crc32(kind.iter().chain(data));
+        png.extend_from_slice(&crc.to_be_bytes());
+    }
+
+    fn crc32<'a>(bytes: impl Iterator<Item = &'a u8>) -> u32 {
+        let mut crc = 0xFFFF_FFFFu32;
+        for byte in bytes {
+            crc ^= u32::from(*byte);
+            for _ in 0..8 {
+                crc = if crc & 1 == 1 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            }
+        }
+        !crc
+    }
+
+    fn adler32(bytes: &[u8]) -> u32 {
+        let (mut a, mut b) = (1u32, 0u32);
+        for byte in bytes {
+            a = (a + u32::from(*byte)) % 65_521;
+            b = (b + a) % 65_521;
+        }
+        (b << 16) | a
+    }
+}
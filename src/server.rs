@@ -0,0 +1,111 @@
+//! Dedicated server runtime
+//!
+//! An engine built with [`EngineBuilder::server`] simulates the world at a
+//! fixed tick rate without a terminal: nothing is read from the console,
+//! nothing is drawn, sound is off, and effects, weather, and the camera are not
+//! updated. Updatables, behaviors, and commands run as in the client, so one
+//! codebase builds both, switched by a feature of the game's own crate:
+//!
+//! ```toml
+//! [features]
+//! server = []
+//! ```
+//!
+//! Closing the console, Ctrl+C, Ctrl+Break, and system shutdown, the Windows
+//! counterparts of `SIGINT` and `SIGTERM`, stop a server gracefully: the engine
+//! emits `EngineEvent::ShutdownRequested`, finishes the tick, and returns from
+//! `run` so the game can save and say goodbye to clients.
+//!
+//! # Example
+//! ```no_run
+//! use lonely_engine::engine::Engine;
+//!
+//! let builder = Engine::builder(80, 24);
+//! #[cfg(feature = "server")]
+//! let builder = builder.server(lonely_engine::server::DEFAULT_TICK_RATE);
+//! let mut engine = builder.build();
+//!
+//! if !engine.is_server() {
+//!     // Client-only setup: menus, music, the HUD
+//! }
+//! engine.run();
+//! ```
+//!
+//! [`EngineBuilder::server`]: crate::engine::EngineBuilder::server
+
+use std::sync::{Once, atomic::{AtomicBool, Ordering}};
+
+/// Ticks per second of a server unless configured otherwise
+pub const DEFAULT_TICK_RATE: f32 = 30.0;
+
+/// Set when the process was asked to stop
+static SHUTDOWN: AtomicBool = AtomicBool::new(false);
+/// Set once the engine finished stopping, so a closing console can let the process end
+static FINISHED: AtomicBool = AtomicBool::new(false);
+static INSTALL: Once = Once::new();
+
+/// Asks running servers to stop at the end of their current tick, such as from an admin command
+pub fn request_shutdown() {
+    SHUTDOWN.store(true, Ordering::SeqCst);
+}
+
+/// Returns whether a shutdown was requested by a signal or [`request_shutdown`]
+pub fn shutdown_requested() -> bool {
+    SHUTDOWN.load(Ordering::SeqCst)
+}
+
+/// Routes console close and interrupt events to [`request_shutdown`], once per process
+///
+/// # Notes
+/// - Only implemented on Windows, elsewhere servers stop through [`request_shutdown`] or `EngineCommand::Quit`
+pub(crate) fn install_shutdown_handler() {
+    INSTALL.call_once(console::install);
+}
+
+/// Lets a pending console close end the process
+pub(crate) fn finish_shutdown() {
+    FINISHED.store(true, Ordering::SeqCst);
+}
+
+#[cfg(windows)]
+mod console {
+    use std::{sync::atomic::Ordering, thread, time::{Duration, Instant}};
+    use winapi::shared::minwindef::{BOOL, DWORD, FALSE, TRUE};
+    use winapi::um::consoleapi::SetConsoleCtrlHandler;
+    use winapi::um::wincon::{CTRL_BREAK_EVENT, CTRL_C_EVENT, CTRL_CLOSE_EVENT, CTRL_LOGOFF_EVENT, CTRL_SHUTDOWN_EVENT};
+    use super::{FINISHED, request_shutdown};
+
+    /// Windows ends the process this long after a close event at the latest, the handler waits a little less
+    const CLOSE_GRACE: Duration = Duration::from_millis(4500);
+
+    pub fn install() {
+        unsafe {
+            SetConsoleCtrlHandler(Some(handle), TRUE);
+        }
+    }
+
+    unsafe extern "system" fn handle(event: DWORD) -> BOOL {
+        match event {
+            CTRL_C_EVENT | CTRL_BREAK_EVENT => {
+                request_shutdown();
+                TRUE
+            },
+            CTRL_CLOSE_EVENT | CTRL_LOGOFF_EVENT | CTRL_SHUTDOWN_EVENT => {
+                request_shutdown();
+                // The process ends as soon as this returns, give the engine time to stop
+                let start = Instant::now();
+                while !FINISHED.load(Ordering::SeqCst) && start.elapsed() < CLOSE_GRACE {
+                    thread::sleep(Duration::from_millis(10));
+                }
+                TRUE
+            },
+            _ => FALSE,
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod console {
+    /// Signals are not handled on other platforms
+    pub fn install() {}
+}
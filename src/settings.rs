@@ -0,0 +1,475 @@
+//! Player settings with live effect and TOML persistence
+//!
+//! [`Settings`] holds the options players expect to change: volume, key
+//! bindings, color theme, frame rate cap, and accessibility options, next to any
+//! keys the game adds.
+//! Values are typed on the way in and out through [`SettingValue`].
+//!
+//! Set on the engine with [`Engine::set_settings`], changes take effect at the
+//! start of the next frame: audio, renderer, and pause menu controls follow
+//! along and `EngineEvent::SettingChanged` is emitted with the changed key.
+//!
+//! # File format
+//! ```toml
+//! [audio]
+//! volume = 0.8
+//! muted = false
+//! music_volume = 0.5
+//!
+//! [video]
+//! fps = 60.0
+//! theme = "light"
+//!
+//! [accessibility]
+//! high_contrast = false
+//! reduce_flashing = true
+//! min_frame_duration = 0.2
+//!
+//! [game]
+//! difficulty = "hard"
+//!
+//! [bindings]
+//! jump = ["Space", "w"]
+//! ```
+//!
+//! [`Engine::set_settings`]: crate::engine::Engine::set_settings
+
+use std::{env, fs, io, path::{Path, PathBuf}};
+use crate::{
+    accessibility::Accessibility,
+    audio::AudioGroup,
+    color::Color,
+    engine::DEFAULT_RENDER_RATE,
+    input::Key,
+    keybindings::ActionMap,
+    toml::{TomlDocument, TomlValue},
+};
+
+/// Master volume, `f32` from `0.0` to `1.0`
+pub const VOLUME: &str = "audio.volume";
+/// Whether all sound is muted, `bool`
+pub const MUTED: &str = "audio.muted";
+/// Volume of the music group, `f32` from `0.0` to `1.0`
+pub const MUSIC_VOLUME: &str = "audio.music_volume";
+/// Volume of the sound effects group, `f32` from `0.0` to `1.0`
+pub const SFX_VOLUME: &str = "audio.sfx_volume";
+/// Volume of the interface sounds group, `f32` from `0.0` to `1.0`
+pub const UI_VOLUME: &str = "audio.ui_volume";
+/// Volume of the voice group, `f32` from `0.0` to `1.0`
+pub const VOICE_VOLUME: &str = "audio.voice_volume";
+/// Frames presented per second, `f32`
+pub const FPS_CAP: &str = "video.fps";
+/// Name of the color theme, `String`, see [`Settings::add_theme`]
+pub const THEME: &str = "video.theme";
+/// Whether frames use the high-contrast palette, `bool`
+pub const HIGH_CONTRAST: &str = "accessibility.high_contrast";
+/// Whether blinking, flicker, and screen shake are turned off, `bool`
+pub const REDUCE_FLASHING: &str = "accessibility.reduce_flashing";
+/// Seconds every animation frame is shown at least, `f32`
+pub const MIN_FRAME_DURATION: &str = "accessibility.min_frame_duration";
+/// Change key reported when the key bindings change
+pub const BINDINGS: &str = "bindings";
+
+/// Theme used unless another is selected
+pub const DEFAULT_THEME: &str = "default";
+
+/// File name of the settings inside a game's config directory
+pub const SETTINGS_FILE: &str = "settings.toml";
+
+/// Value that can be stored in [`Settings`]
+pub trait SettingValue: Sized {
+    /// Reads the value, `None` when the stored value has another type
+    fn from_toml(value: &TomlValue) -> Option<Self>;
+    /// Converts the value for storage
+    fn into_toml(self) -> TomlValue;
+}
+
+impl SettingValue for bool {
+    fn from_toml(value: &TomlValue) -> Option<Self> {
+        value.as_bool()
+    }
+
+    fn into_toml(self) -> TomlValue {
+        self.into()
+    }
+}
+
+impl SettingValue for i64 {
+    fn from_toml(value: &TomlValue) -> Option<Self> {
+        value.as_integer()
+    }
+
+    fn into_toml(self) -> TomlValue {
+        self.into()
+    }
+}
+
+impl SettingValue for i32 {
+    fn from_toml(value: &TomlValue) -> Option<Self> {
+        value.as_integer().and_then(|value| value.try_into().ok())
+    }
+
+    fn into_toml(self) -> TomlValue {
+        self.into()
+    }
+}
+
+impl SettingValue for f64 {
+    fn from_toml(value: &TomlValue) -> Option<Self> {
+        value.as_float()
+    }
+
+    fn into_toml(self) -> TomlValue {
+        self.into()
+    }
+}
+
+impl SettingValue for f32 {
+    fn from_toml(value: &TomlValue) -> Option<Self> {
+        value.as_float().map(|value| value as f32)
+    }
+
+    fn into_toml(self) -> TomlValue {
+        self.into()
+    }
+}
+
+impl SettingValue for String {
+    fn from_toml(value: &TomlValue) -> Option<Self> {
+        value.as_str().map(str::to_string)
+    }
+
+    fn into_toml(self) -> TomlValue {
+        self.into()
+    }
+}
+
+/// Colors the screen is cleared to, see [`Settings::add_theme`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Text color of empty cells
+    pub foreground: Color,
+    /// Background of empty cells
+    pub background: Color,
+}
+
+/// Typed player settings, saved as TOML
+///
+/// # Notes
+/// - Keys are written `table.key`, such as `"game.difficulty"`; keys without a dot live in the root table
+/// - Setting a value records the key as changed, the engine applies and reports changes once per frame
+///
+/// # Example
+/// ```
+/// use lonely_engine::settings::{self, Settings};
+///
+/// let mut options = Settings::new().with_default("game.difficulty", "normal".to_string());
+/// options.set(settings::VOLUME, 0.5);
+/// options.set("game.difficulty", "hard".to_string());
+///
+/// assert_eq!(options.volume(), 0.5);
+/// assert_eq!(options.get::<String>("game.difficulty").as_deref(), Some("hard"));
+/// assert_eq!(options.take_changes(), vec![settings::VOLUME, "game.difficulty"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    values: TomlDocument,
+    bindings: ActionMap,
+    themes: Vec<(String, Theme)>,
+    /// Keys set since the last [`Settings::take_changes`], in order without repeats
+    changed: Vec<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Settings {
+    /// Creates settings at full volume, the default render rate, and the terminal's own colors
+    pub fn new() -> Self {
+        let mut values = TomlDocument::new();
+        values.set("audio", "volume", 1.0);
+        values.set("audio", "muted", false);
+        for group in AudioGroup::ALL {
+            values.set("audio", split_key(group_volume_key(group)).1, 1.0);
+        }
+        values.set("video", "fps", DEFAULT_RENDER_RATE);
+        values.set("video", "theme", DEFAULT_THEME);
+        values.set("accessibility", "high_contrast", false);
+        values.set("accessibility", "reduce_flashing", false);
+        values.set("accessibility", "min_frame_duration", 0.0);
+        let themes = vec![
+            (DEFAULT_THEME.to_string(), Theme { foreground: Color::Default, background: Color::Default }),
+            ("dark".to_string(), Theme { foreground: Color::WHITE, background: Color::BLACK }),
+            ("light".to_string(), Theme { foreground: Color::BLACK, background: Color::WHITE }),
+        ];
+        Self { values, bindings: ActionMap::new(), themes, changed: Vec::new() }
+    }
+
+    /// Adds a game setting with its default value, without reporting a change
+    pub fn with_default<T: SettingValue>(mut self, key: &str, value: T) -> Self {
+        let (table, name) = split_key(key);
+        self.values.set(table, name, value.into_toml());
+        self
+    }
+
+    /// Sets the default key bindings, without reporting a change
+    pub fn with_bindings(mut self, bindings: ActionMap) -> Self {
+        self.bindings = bindings;
+        self
+    }
+
+    /// Looks up a value
+    ///
+    /// # Returns
+    /// `None` when the key is missing or holds another type
+    pub fn get<T: SettingValue>(&self, key: &str) -> Option<T> {
+        let (table, name) = split_key(key);
+        self.values.get(table, name).and_then(T::from_toml)
+    }
+
+    /// Stores a value, recording the key as changed when the value differs
+    pub fn set<T: SettingValue>(&mut self, key: &str, value: T) {
+        let (table, name) = split_key(key);
+        let value = value.into_toml();
+        if self.values.get(table, name) != Some(&value) {
+            self.values.set(table, name, value);
+            self.mark_changed(key);
+        }
+    }
+
+    /// Master volume from `0.0` to `1.0`
+    pub fn volume(&self) -> f32 {
+        self.get(VOLUME).unwrap_or(1.0_f32).clamp(0.0, 1.0)
+    }
+
+    /// Volume of an audio group from `0.0` to `1.0`, on top of the master volume
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{audio::AudioGroup, settings::{self, Settings}};
+    ///
+    /// let mut options = Settings::new();
+    /// options.set(settings::MUSIC_VOLUME, 0.25);
+    /// assert_eq!(options.group_volume(AudioGroup::Music), 0.25);
+    /// assert_eq!(options.group_volume(AudioGroup::Sfx), 1.0);
+    /// ```
+    pub fn group_volume(&self, group: AudioGroup) -> f32 {
+        self.get(group_volume_key(group)).unwrap_or(1.0_f32).clamp(0.0, 1.0)
+    }
+
+    /// Returns whether all sound is muted
+    pub fn muted(&self) -> bool {
+        self.get(MUTED).unwrap_or(false)
+    }
+
+    /// Frames presented per second
+    pub fn fps_cap(&self) -> f32 {
+        self.get(FPS_CAP).filter(|fps: &f32| *fps > 0.0).unwrap_or(DEFAULT_RENDER_RATE)
+    }
+
+    /// Name of the selected color theme
+    pub fn theme_name(&self) -> String {
+        self.get(THEME).unwrap_or_else(|| DEFAULT_THEME.to_string())
+    }
+
+    /// Colors of the selected theme, falling back to the default theme for unknown names
+    pub fn theme(&self) -> Theme {
+        let name = self.theme_name();
+        self.theme_colors(&name).or_else(|| self.theme_colors(DEFAULT_THEME)).unwrap_or(Theme { foreground: Color::Default, background: Color::Default })
+    }
+
+    /// Registers a theme players can select, replacing one with the same name
+    ///
+    /// # Notes
+    /// - `"default"`, `"dark"`, and `"light"` are built in
+    pub fn add_theme(&mut self, name: &str, theme: Theme) {
+        match self.themes.iter_mut().find(|(existing, _)| existing == name) {
+            Some(existing) => existing.1 = theme,
+            None => self.themes.push((name.to_string(), theme)),
+        }
+    }
+
+    /// Colors of a theme by name
+    pub fn theme_colors(&self, name: &str) -> Option<Theme> {
+        self.themes.iter().find(|(existing, _)| existing == name).map(|(_, theme)| *theme)
+    }
+
+    /// Names of the registered themes, in registration order
+    pub fn theme_names(&self) -> impl Iterator<Item = &str> {
+        self.themes.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Accessibility options chosen by the player
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::settings::{self, Settings};
+    ///
+    /// let mut options = Settings::new();
+    /// options.set(settings::REDUCE_FLASHING, true);
+    /// assert!(options.accessibility().reduce_flashing);
+    /// assert!(!options.accessibility().high_contrast);
+    /// ```
+    pub fn accessibility(&self) -> Accessibility {
+        Accessibility::new()
+            .high_contrast(self.get(HIGH_CONTRAST).unwrap_or(false))
+            .reduce_flashing(self.get(REDUCE_FLASHING).unwrap_or(false))
+            .min_frame_duration(self.get(MIN_FRAME_DURATION).filter(|seconds: &f32| seconds.is_finite()).unwrap_or(0.0))
+    }
+
+    /// Key bindings chosen by the player
+    pub fn bindings(&self) -> &ActionMap {
+        &self.bindings
+    }
+
+    /// Rebinds one action, recording [`BINDINGS`] as changed
+    pub fn set_keys(&mut self, action: &str, keys: Vec<Key>) {
+        if self.bindings.keys_for(action) != keys.as_slice() {
+            self.bindings.set_keys(action, keys);
+            self.mark_changed(BINDINGS);
+        }
+    }
+
+    /// Removes and returns the keys changed since the previous call, in the order they were first set
+    pub fn take_changes(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.changed)
+    }
+
+    /// Every stored key as `table.key`, in file order
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys = Vec::new();
+        for table in self.values.table_names() {
+            for (name, _) in self.values.table(table).unwrap_or(&[]) {
+                keys.push(if table.is_empty() { name.clone() } else { format!("{table}.{name}") });
+            }
+        }
+        keys
+    }
+
+    /// Formats the settings as TOML text
+    pub fn to_toml(&self) -> String {
+        let mut doc = self.values.clone();
+        for action in self.bindings.actions() {
+            let keys = self.bindings.keys_for(action).iter().map(|key| TomlValue::String(key.to_string())).collect();
+            doc.set(BINDINGS, action, TomlValue::Array(keys));
+        }
+        doc.to_string()
+    }
+
+    /// Applies TOML text over these settings, reporting every value that differs as changed
+    ///
+    /// # Returns
+    /// `InvalidData` for malformed TOML or a binding with an unknown key name
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::settings::Settings;
+    ///
+    /// let mut options = Settings::new();
+    /// options.merge_toml("[video]\ntheme = \"light\"\n\n[bindings]\njump = [\"Space\"]").unwrap();
+    /// assert_eq!(options.theme_name(), "light");
+    /// assert_eq!(options.take_changes(), vec!["video.theme", "bindings"]);
+    /// ```
+    pub fn merge_toml(&mut self, text: &str) -> io::Result<()> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+        let doc = TomlDocument::parse(text).map_err(|error| invalid(format!("invalid settings file: {error}")))?;
+        for table in doc.table_names() {
+            for (name, value) in doc.table(table).unwrap_or(&[]) {
+                if table == BINDINGS {
+                    let keys = value.as_array().unwrap_or(&[]).iter()
+                        .map(|key| key.as_str().and_then(|key| key.parse::<Key>().ok()))
+                        .collect::<Option<Vec<Key>>>()
+                        .ok_or_else(|| invalid(format!("action `{name}` must be an array of key names")))?;
+                    self.set_keys(name, keys);
+                } else if self.values.get(table, name) != Some(value) {
+                    self.values.set(table, name, value.clone());
+                    self.mark_changed(&if table.is_empty() { name.clone() } else { format!("{table}.{name}") });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads player settings on top of the game's defaults
+    ///
+    /// # Returns
+    /// * `defaults` unchanged when the file does not exist
+    /// * `Err` when the file is unreadable or malformed
+    ///
+    /// # Example
+    /// ```no_run
+    /// use lonely_engine::settings::{self, Settings};
+    ///
+    /// let path = settings::config_dir("my_game").unwrap_or_default().join(settings::SETTINGS_FILE);
+    /// let options = Settings::load_or_default(&path, Settings::new()).unwrap_or_else(|error| {
+    ///     eprintln!("{}, using default settings", error);
+    ///     Settings::new()
+    /// });
+    /// ```
+    pub fn load_or_default(path: impl AsRef<Path>, defaults: Settings) -> io::Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(defaults),
+            Err(error) => return Err(error),
+        };
+        let mut settings = defaults;
+        settings.merge_toml(&text)?;
+        settings.changed.clear();
+        Ok(settings)
+    }
+
+    /// Writes the settings to a TOML file, creating its directory if needed
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, self.to_toml())
+    }
+
+    /// Records a key as changed once
+    fn mark_changed(&mut self, key: &str) {
+        if !self.changed.iter().any(|changed| changed == key) {
+            self.changed.push(key.to_string());
+        }
+    }
+}
+
+/// Setting key holding the volume of an audio group
+pub fn group_volume_key(group: AudioGroup) -> &'static str {
+    match group {
+        AudioGroup::Music => MUSIC_VOLUME,
+        AudioGroup::Sfx => SFX_VOLUME,
+        AudioGroup::Ui => UI_VOLUME,
+        AudioGroup::Voice => VOICE_VOLUME,
+    }
+}
+
+/// Splits `table.key` at its first dot, keys without one belong to the root table
+fn split_key(key: &str) -> (&str, &str) {
+    key.split_once('.').unwrap_or(("", key))
+}
+
+/// Per-user config directory of a game
+///
+/// # Returns
+/// * `%APPDATA%\<game>` on Windows
+/// * `~/Library/Application Support/<game>` on macOS
+/// * `$XDG_CONFIG_HOME/<game>` elsewhere, defaulting to `~/.config/<game>`
+/// * `None` when the home directory is unknown
+pub fn config_dir(game: &str) -> Option<PathBuf> {
+    let base = if cfg!(windows) {
+        PathBuf::from(env::var_os("APPDATA")?)
+    } else if cfg!(target_os = "macos") {
+        PathBuf::from(env::var_os("HOME")?).join("Library").join("Application Support")
+    } else {
+        match env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        }
+    };
+    Some(base.join(game))
+}
@@ -0,0 +1,504 @@
+//! Shops, trading, and the currency they take
+//!
+//! Provides:
+//! - [`Wallet`] holding the player's coins, kept as an engine resource
+//! - [`Shop`] with its stock, prices, and what it pays for items, loaded from JSON
+//! - [`ShopScreen`] widget to buy from a shop and sell from an inventory
+//!
+//! Trades move items between the shop and an [`Inventory`] and coins between
+//! the shop and the [`Wallet`]; a trade that cannot happen in full changes
+//! nothing and reports a [`TradeError`]. The screen emits
+//! `EngineEvent::ItemBought` and `EngineEvent::ItemSold` for completed trades.
+//!
+//! # File format
+//! ```json
+//! {
+//!     "name": "Blacksmith",
+//!     "sell_rate": 0.5,
+//!     "stock": [
+//!         { "item": "sword", "price": 30, "count": 2 },
+//!         { "item": "arrow", "price": 1 }
+//!     ]
+//! }
+//! ```
+//! - `count` leaves the stock unlimited when missing
+//! - `sell_rate` is the part of its price the shop pays for an item, `0.5` when missing
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{inventory::{Inventory, ItemDef, ItemRegistry}, shop::{Shop, TradeError, Wallet}};
+//!
+//! let mut items = ItemRegistry::new();
+//! items.register(ItemDef::new("sword", "Sword", '|'));
+//! items.register(ItemDef::new("arrow", "Arrow", '/').max_stack(50));
+//!
+//! let mut shop = Shop::from_json(r#"{ "name": "Blacksmith", "stock": [
+//!     { "item": "sword", "price": 30, "count": 1 },
+//!     { "item": "arrow", "price": 1 }
+//! ] }"#).unwrap();
+//! let mut wallet = Wallet::new(40);
+//! let mut bag = Inventory::new(4);
+//!
+//! assert_eq!(shop.buy("sword", 1, &mut wallet, &mut bag, &items), Ok(30));
+//! assert_eq!(shop.buy("sword", 1, &mut wallet, &mut bag, &items), Err(TradeError::OutOfStock));
+//! assert_eq!(shop.buy("arrow", 20, &mut wallet, &mut bag, &items), Err(TradeError::NotEnoughMoney));
+//!
+//! assert_eq!(shop.sell("sword", 1, &mut wallet, &mut bag), Ok(15));
+//! assert_eq!(wallet.balance(), 25);
+//! assert_eq!(shop.stock("sword"), Some(1));
+//! ```
+
+use std::{fmt, fs, io, path::Path};
+use crate::{
+    color::Color,
+    engine::UpdateContext,
+    event::EngineEvent,
+    input::{InputEvent, Key},
+    inventory::{Inventory, ItemRegistry},
+    json::JsonValue,
+    renderer::{Cell, Renderer},
+    style::Style,
+};
+
+/// Part of its price a shop pays for an item unless configured otherwise
+pub const DEFAULT_SELL_RATE: f32 = 0.5;
+
+/// Coins owned by the player
+///
+/// # Example
+/// ```
+/// use lonely_engine::{engine::Engine, shop::Wallet};
+///
+/// let mut engine = Engine::new(80, 24);
+/// engine.insert_resource(Wallet::new(100));
+/// assert!(engine.resource_mut::<Wallet>().unwrap().withdraw(60));
+/// assert!(!engine.resource_mut::<Wallet>().unwrap().withdraw(60));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Wallet {
+    coins: u64,
+}
+
+impl Wallet {
+    /// Creates a wallet holding some coins
+    pub fn new(coins: u64) -> Self {
+        Self { coins }
+    }
+
+    /// Coins held
+    pub fn balance(&self) -> u64 {
+        self.coins
+    }
+
+    /// Adds coins, such as loot or a quest reward
+    pub fn deposit(&mut self, coins: u64) {
+        self.coins = self.coins.saturating_add(coins);
+    }
+
+    /// Takes coins if there are enough
+    ///
+    /// # Returns
+    /// `false` and nothing taken when the wallet holds fewer coins
+    pub fn withdraw(&mut self, coins: u64) -> bool {
+        if self.coins < coins {
+            return false;
+        }
+        self.coins -= coins;
+        true
+    }
+
+    /// Returns whether the wallet holds at least some coins
+    pub fn can_afford(&self, coins: u64) -> bool {
+        self.coins >= coins
+    }
+}
+
+/// Why a trade did not happen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeError {
+    /// The shop does not deal in the item
+    NotTraded,
+    /// The shop has fewer items than asked for
+    OutOfStock,
+    /// The wallet holds fewer coins than the price
+    NotEnoughMoney,
+    /// The inventory has no room for the items
+    InventoryFull,
+    /// The inventory holds fewer items than offered
+    NotOwned,
+}
+
+impl fmt::Display for TradeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            TradeError::NotTraded => "the shop does not trade this item",
+            TradeError::OutOfStock => "out of stock",
+            TradeError::NotEnoughMoney => "not enough money",
+            TradeError::InventoryFull => "no room in the inventory",
+            TradeError::NotOwned => "not enough items to sell",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for TradeError {}
+
+/// An item a shop deals in
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShopItem {
+    /// Item id
+    pub item: String,
+    /// Coins paid for one item
+    pub price: u64,
+    /// Items left, `None` for an unlimited supply
+    pub stock: Option<u32>,
+}
+
+/// Stock and prices of a merchant
+///
+/// # Notes
+/// - A shop buys the items it sells, and only those
+/// - Sold items go back into limited stock
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Shop {
+    /// Name shown at the top of the shop screen
+    pub name: String,
+    /// Items dealt in, in the order they are listed
+    pub items: Vec<ShopItem>,
+    /// Part of its price the shop pays for an item
+    pub sell_rate: f32,
+}
+
+impl Shop {
+    /// Creates a shop without stock
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_string(), items: Vec::new(), sell_rate: DEFAULT_SELL_RATE }
+    }
+
+    /// Adds an item to the stock and returns the shop, for builder-style construction
+    ///
+    /// # Arguments
+    /// * `item` - Item id
+    /// * `price` - Coins paid for one item
+    /// * `stock` - Items available, `None` for an unlimited supply
+    pub fn with_item(mut self, item: &str, price: u64, stock: Option<u32>) -> Self {
+        self.items.retain(|listed| listed.item != item);
+        self.items.push(ShopItem { item: item.to_string(), price, stock });
+        self
+    }
+
+    /// Sets the part of its price the shop pays for an item
+    pub fn sell_rate(mut self, rate: f32) -> Self {
+        self.sell_rate = rate.max(0.0);
+        self
+    }
+
+    /// Parses a shop in the JSON format described in the [module docs](self)
+    ///
+    /// # Returns
+    /// `Err` with [`io::ErrorKind::InvalidData`] for invalid JSON or malformed entries
+    pub fn from_json(text: &str) -> io::Result<Self> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+        let root = JsonValue::parse(text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        let name = root.get("name").and_then(JsonValue::as_str).ok_or_else(|| invalid("shop needs a string `name`"))?;
+        let mut shop = Self::new(name);
+        if let Some(rate) = root.get("sell_rate") {
+            shop.sell_rate = rate.as_f64().ok_or_else(|| invalid("`sell_rate` must be a number"))?.max(0.0) as f32;
+        }
+
+        let stock = root.get("stock").and_then(JsonValue::as_array).ok_or_else(|| invalid("`stock` must be an array"))?;
+        for entry in stock {
+            let item = entry.get("item").and_then(JsonValue::as_str).ok_or_else(|| invalid("every stock entry needs a string `item`"))?;
+            let price = entry.get("price").and_then(JsonValue::as_i64).and_then(|price| u64::try_from(price).ok());
+            let price = price.ok_or_else(|| invalid(&format!("`price` of `{item}` must be a non-negative integer")))?;
+            let count = match entry.get("count") {
+                None => None,
+                Some(count) => Some(count.as_i64().and_then(|count| u32::try_from(count).ok()).ok_or_else(|| invalid(&format!("`count` of `{item}` must be a non-negative integer")))?),
+            };
+            shop = shop.with_item(item, price, count);
+        }
+        Ok(shop)
+    }
+
+    /// Loads a shop from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// Listing of an item
+    pub fn get(&self, item: &str) -> Option<&ShopItem> {
+        self.items.iter().find(|listed| listed.item == item)
+    }
+
+    /// Coins paid for one item bought from the shop
+    pub fn price(&self, item: &str) -> Option<u64> {
+        self.get(item).map(|listed| listed.price)
+    }
+
+    /// Coins the shop pays for one item, rounded down
+    pub fn sell_price(&self, item: &str) -> Option<u64> {
+        self.get(item).map(|listed| (listed.price as f64 * f64::from(self.sell_rate)).floor() as u64)
+    }
+
+    /// Items left, `None` when unlimited or not traded
+    pub fn stock(&self, item: &str) -> Option<u32> {
+        self.get(item).and_then(|listed| listed.stock)
+    }
+
+    /// Buys items from the shop into an inventory
+    ///
+    /// # Returns
+    /// Coins paid
+    pub fn buy(&mut self, item: &str, count: u32, wallet: &mut Wallet, inventory: &mut Inventory, registry: &ItemRegistry) -> Result<u64, TradeError> {
+        let listed = self.items.iter_mut().find(|listed| listed.item == item).ok_or(TradeError::NotTraded)?;
+        if listed.stock.is_some_and(|stock| stock < count) {
+            return Err(TradeError::OutOfStock);
+        }
+        let cost = listed.price.saturating_mul(u64::from(count));
+        if !wallet.can_afford(cost) {
+            return Err(TradeError::NotEnoughMoney);
+        }
+        let left = inventory.add(registry, item, count);
+        if left > 0 {
+            inventory.remove(item, count - left);
+            return Err(TradeError::InventoryFull);
+        }
+        wallet.withdraw(cost);
+        if let Some(stock) = &mut listed.stock {
+            *stock -= count;
+        }
+        Ok(cost)
+    }
+
+    /// Sells items from an inventory to the shop
+    ///
+    /// # Returns
+    /// Coins received
+    pub fn sell(&mut self, item: &str, count: u32, wallet: &mut Wallet, inventory: &mut Inventory) -> Result<u64, TradeError> {
+        let payment = self.sell_price(item).ok_or(TradeError::NotTraded)?.saturating_mul(u64::from(count));
+        if !inventory.contains(item, count) {
+            return Err(TradeError::NotOwned);
+        }
+        inventory.remove(item, count);
+        wallet.deposit(payment);
+        if let Some(stock) = self.items.iter_mut().find(|listed| listed.item == item).and_then(|listed| listed.stock.as_mut()) {
+            *stock = stock.saturating_add(count);
+        }
+        Ok(payment)
+    }
+}
+
+/// Side of the shop screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShopTab {
+    /// The shop's stock
+    Buy,
+    /// The player's inventory
+    Sell,
+}
+
+/// What happened on the shop screen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShopAction {
+    /// One item bought
+    Bought {
+        /// Item id
+        item: String,
+        /// Coins paid
+        price: u64,
+    },
+    /// One item sold
+    Sold {
+        /// Item id
+        item: String,
+        /// Coins received
+        price: u64,
+    },
+    /// A trade was refused
+    Failed(TradeError),
+    /// The player left the shop
+    Close,
+}
+
+/// List widget to buy from a shop and sell from an inventory
+///
+/// # Controls
+/// - Up and Down move the cursor
+/// - Left and Right switch between buying and selling
+/// - Enter or Space trades one of the selected item
+/// - Escape closes the shop
+///
+/// # Events
+/// - `EngineEvent::ItemBought` with the item id and coins paid
+/// - `EngineEvent::ItemSold` with the item id and coins received
+///
+/// # Example
+/// ```
+/// use lonely_engine::{
+///     input::{InputEvent, Key},
+///     inventory::{Inventory, ItemDef, ItemRegistry},
+///     renderer::Renderer,
+///     shop::{Shop, ShopAction, ShopScreen, Wallet},
+/// };
+///
+/// let mut items = ItemRegistry::new();
+/// items.register(ItemDef::new("potion", "Potion", '!').max_stack(5));
+/// let mut shop = Shop::new("Apothecary").with_item("potion", 12, Some(3));
+/// let mut wallet = Wallet::new(50);
+/// let mut bag = Inventory::new(8);
+///
+/// let mut screen = ShopScreen::new();
+/// let action = screen.handle(&mut shop, &mut wallet, &mut bag, &items, &[InputEvent::KeyDown(Key::Enter)]);
+/// assert_eq!(action, Some(ShopAction::Bought { item: "potion".into(), price: 12 }));
+/// assert_eq!((wallet.balance(), bag.count("potion")), (38, 1));
+///
+/// let mut renderer = Renderer::new(40, 12);
+/// screen.draw(&mut renderer, &shop, &wallet, &bag, &items, 1, 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShopScreen {
+    tab: ShopTab,
+    cursor: usize,
+}
+
+impl Default for ShopScreen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ShopScreen {
+    /// Creates a screen opening on the shop's stock
+    pub fn new() -> Self {
+        Self { tab: ShopTab::Buy, cursor: 0 }
+    }
+
+    /// Side shown
+    pub fn tab(&self) -> ShopTab {
+        self.tab
+    }
+
+    /// Shows a side, moving the cursor to its first row
+    pub fn set_tab(&mut self, tab: ShopTab) {
+        self.tab = tab;
+        self.cursor = 0;
+    }
+
+    /// Index of the selected row
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Handles this frame's input against the [`Wallet`] resource and emits trade events on the event bus
+    ///
+    /// # Returns
+    /// The action picked this frame, if any
+    ///
+    /// # Notes
+    /// - Without a `Wallet` resource the player has no coins to spend, and sold items pay into a wallet that is thrown away
+    pub fn update(&mut self, shop: &mut Shop, inventory: &mut Inventory, registry: &ItemRegistry, ctx: &mut UpdateContext) -> Option<ShopAction> {
+        let events = ctx.input_events;
+        let mut spare = Wallet::default();
+        let wallet = ctx.resources.get_mut::<Wallet>().unwrap_or(&mut spare);
+        let action = self.handle(shop, wallet, inventory, registry, events);
+        match &action {
+            Some(ShopAction::Bought { item, price }) => ctx.event_bus.emit(EngineEvent::ItemBought(item.clone(), *price)),
+            Some(ShopAction::Sold { item, price }) => ctx.event_bus.emit(EngineEvent::ItemSold(item.clone(), *price)),
+            _ => {},
+        }
+        action
+    }
+
+    /// Handles input without an engine
+    ///
+    /// # Returns
+    /// The first action picked, later events of the frame are ignored after it
+    pub fn handle(&mut self, shop: &mut Shop, wallet: &mut Wallet, inventory: &mut Inventory, registry: &ItemRegistry, events: &[InputEvent]) -> Option<ShopAction> {
+        for event in events {
+            let rows = self.rows(shop, inventory).len();
+            self.cursor = self.cursor.min(rows.saturating_sub(1));
+            match event {
+                InputEvent::KeyDown(Key::Up) => self.cursor = self.cursor.saturating_sub(1),
+                InputEvent::KeyDown(Key::Down) if self.cursor + 1 < rows => self.cursor += 1,
+                InputEvent::KeyDown(Key::Left | Key::Right) => {
+                    self.set_tab(if self.tab == ShopTab::Buy { ShopTab::Sell } else { ShopTab::Buy });
+                },
+                InputEvent::KeyDown(Key::Esc) => return Some(ShopAction::Close),
+                InputEvent::KeyDown(Key::Enter | Key::Space) => {
+                    let Some(item) = self.rows(shop, inventory).get(self.cursor).cloned() else {
+                        continue;
+                    };
+                    return Some(match self.tab {
+                        ShopTab::Buy => match shop.buy(&item, 1, wallet, inventory, registry) {
+                            Ok(price) => ShopAction::Bought { item, price },
+                            Err(error) => ShopAction::Failed(error),
+                        },
+                        ShopTab::Sell => match shop.sell(&item, 1, wallet, inventory) {
+                            Ok(price) => ShopAction::Sold { item, price },
+                            Err(error) => ShopAction::Failed(error),
+                        },
+                    });
+                },
+                _ => {},
+            }
+        }
+        None
+    }
+
+    /// Draws the shop's name, the wallet, the two tabs, and the rows of the shown side
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Top-left cell of the screen
+    ///
+    /// # Notes
+    /// - Buy rows show the glyph, name, price, and stock left, sell rows what the shop pays and how many are owned
+    /// - Items the wallet cannot pay for are greyed out
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw(&self, renderer: &mut Renderer, shop: &Shop, wallet: &Wallet, inventory: &Inventory, registry: &ItemRegistry, x: usize, y: usize) {
+        renderer.draw_styled_text(x, y, &shop.name, &Style::new().bold());
+        let coins = format!("Coins: {}", wallet.balance());
+        renderer.draw_styled_text(x + shop.name.chars().count() + 3, y, &coins, &Style::new().fg(Color::BRIGHT_YELLOW));
+
+        let tab_style = |tab: ShopTab| if self.tab == tab { Style::new().bold().reverse() } else { Style::new().fg(Color::GREY) };
+        renderer.draw_styled_text(x, y + 1, " Buy ", &tab_style(ShopTab::Buy));
+        renderer.draw_styled_text(x + 6, y + 1, " Sell ", &tab_style(ShopTab::Sell));
+
+        for (row, item) in self.rows(shop, inventory).iter().enumerate() {
+            let sy = y + 3 + row;
+            let (glyph, name, style) = registry.get(item).map_or(('?', item.as_str(), Style::new()), |def| (def.glyph, def.name.as_str(), def.style));
+            let line = match self.tab {
+                ShopTab::Buy => {
+                    let stock = shop.stock(item).map_or(String::new(), |stock| format!(" x{stock}"));
+                    format!("{name:<16} {:>5}{stock}", shop.price(item).unwrap_or(0))
+                },
+                ShopTab::Sell => format!("{name:<16} {:>5} x{}", shop.sell_price(item).unwrap_or(0), inventory.count(item)),
+            };
+            let affordable = self.tab == ShopTab::Sell || shop.price(item).is_some_and(|price| wallet.can_afford(price));
+            let mut text_style = if affordable { Style::new() } else { Style::new().fg(Color::GREY) };
+            if row == self.cursor {
+                text_style = text_style.reverse();
+            }
+            renderer.set_cell(x, sy, Cell::styled(glyph, &style));
+            renderer.draw_styled_text(x + 2, sy, &line, &text_style);
+        }
+    }
+
+    /// Item ids listed on the shown side: the shop's stock, or the owned items the shop buys
+    fn rows(&self, shop: &Shop, inventory: &Inventory) -> Vec<String> {
+        match self.tab {
+            ShopTab::Buy => shop.items.iter().map(|listed| listed.item.clone()).collect(),
+            ShopTab::Sell => {
+                let mut owned: Vec<String> = Vec::new();
+                for stack in inventory.slots().iter().flatten() {
+                    if shop.get(&stack.item).is_some() && !owned.contains(&stack.item) {
+                        owned.push(stack.item.clone());
+                    }
+                }
+                owned
+            },
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Uniform grid for finding nearby points quickly
+//!
+//! [`SpatialHash`] sorts points into square buckets so a radius query only
+//! looks at the buckets the circle touches instead of every point. Rebuilding
+//! it every frame is cheap, which suits crowds of moving things such as
+//! flocks, particles, or enemies looking for allies.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::spatial_hash::SpatialHash;
+//!
+//! let mut grid = SpatialHash::new(4.0);
+//! grid.insert(0, (1.0, 1.0));
+//! grid.insert(1, (3.0, 2.0));
+//! grid.insert(2, (40.0, 40.0));
+//!
+//! let mut near = grid.query((0.0, 0.0), 5.0);
+//! near.sort();
+//! assert_eq!(near, vec![0, 1]);
+//! ```
+
+use std::collections::HashMap;
+
+/// Ids and positions of the points in one bucket
+type Bucket = Vec<(usize, (f32, f32))>;
+
+/// Points bucketed by position, each with an id such as an object index
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Bucket>,
+    len: usize,
+}
+
+impl SpatialHash {
+    /// Creates an empty grid
+    ///
+    /// # Arguments
+    /// * `cell_size` - Side of a bucket, best close to the usual query radius
+    pub fn new(cell_size: f32) -> Self {
+        Self { cell_size: if cell_size > 0.0 { cell_size } else { 1.0 }, cells: HashMap::new(), len: 0 }
+    }
+
+    /// Side of a bucket
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Adds a point, an id may be added more than once
+    pub fn insert(&mut self, id: usize, position: (f32, f32)) {
+        self.cells.entry(self.bucket(position)).or_default().push((id, position));
+        self.len += 1;
+    }
+
+    /// Removes every point, keeping the buckets' memory for the next frame
+    pub fn clear(&mut self) {
+        for points in self.cells.values_mut() {
+            points.clear();
+        }
+        self.len = 0;
+    }
+
+    /// Number of points
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether there are no points
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Ids of the points within a distance of a position, in no particular order
+    pub fn query(&self, center: (f32, f32), radius: f32) -> Vec<usize> {
+        let mut found = Vec::new();
+        self.for_each_near(center, radius, |id, _| found.push(id));
+        found
+    }
+
+    /// Calls a function with the id and position of every point within a distance of a position
+    pub fn for_each_near(&self, center: (f32, f32), radius: f32, mut f: impl FnMut(usize, (f32, f32))) {
+        let radius = radius.max(0.0);
+        let (min_x, min_y) = self.bucket((center.0 - radius, center.1 - radius));
+        let (max_x, max_y) = self.bucket((center.0 + radius, center.1 + radius));
+        for bucket_y in min_y..=max_y {
+            for bucket_x in min_x..=max_x {
+                let Some(points) = self.cells.get(&(bucket_x, bucket_y)) else {
+                    continue;
+                };
+                for &(id, position) in points {
+                    if (position.0 - center.0).hypot(position.1 - center.1) <= radius {
+                        f(id, position);
+                    }
+                }
+            }
+        }
+    }
+
+    fn bucket(&self, position: (f32, f32)) -> (i32, i32) {
+        ((position.0 / self.cell_size).floor() as i32, (position.1 / self.cell_size).floor() as i32)
+    }
+}
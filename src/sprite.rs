@@ -0,0 +1,346 @@
+//! Multi-cell ASCII art sprites
+//!
+//! Provides loading of ASCII art assets from strings and text files:
+//! - [`Sprite`] struct holding a grid of optionally colored cells
+//! - [`Palette`] mapping markup characters to colors and attributes
+//! - Animation frame sequences from a directory or delimited sections of one file
+//!
+//! # Palette files
+//! A sprite loaded with [`Sprite::from_file`] picks up a sidecar palette file with
+//! the same name and a `.pal` extension when one exists. The palette file lists
+//! `key = sgr` entries, followed by a blank line and a color mask with the same
+//! shape as the art:
+//! ```text
+//! r = 31
+//! Y = 33;1
+//!
+//!  r
+//! YYY
+//! ```
+
+use std::{collections::HashMap, fs, io, path::Path};
+use crate::{color::Color, style::{Attributes, Style}};
+
+/// Line separating animation frames inside a single sprite file
+pub const FRAME_SEPARATOR: &str = "---frame---";
+
+/// A single visible cell of a sprite
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpriteCell {
+    /// Display character
+    pub character: char,
+    /// Foreground color
+    pub fg_color: Color,
+    /// Background color
+    pub bg_color: Color,
+    /// Bold, underline, and other text attributes
+    pub attributes: Attributes,
+}
+
+/// Maps color mask characters to styles
+///
+/// # Example
+/// ```
+/// # use lonely_engine::sprite::Palette;
+/// use lonely_engine::color::Color;
+///
+/// let palette = Palette::parse("r = 31\ng = 32;1").unwrap();
+/// assert_eq!(palette.get('r').unwrap().fg_color, Color::RED);
+/// assert!(palette.get('g').unwrap().attributes.bold);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Palette {
+    colors: HashMap<char, Style>,
+}
+
+impl Palette {
+    /// Creates an empty palette
+    pub fn new() -> Self {
+        Self { colors: HashMap::new() }
+    }
+
+    /// Assigns a style to a markup character
+    pub fn insert(&mut self, key: char, style: Style) {
+        self.colors.insert(key, style);
+    }
+
+    /// Looks up the style for a markup character
+    pub fn get(&self, key: char) -> Option<&Style> {
+        self.colors.get(&key)
+    }
+
+    /// Parses `key = sgr` lines into a palette
+    ///
+    /// # Notes
+    /// - `sgr` is the parameter list of an SGR sequence (`31`, `38;5;208`, ...)
+    /// - Empty lines and lines starting with `#` are ignored
+    ///
+    /// # Returns
+    /// `Err` with [`io::ErrorKind::InvalidData`] on malformed lines
+    pub fn parse(text: &str) -> io::Result<Self> {
+        let mut palette = Self::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, sgr) = line.split_once('=').ok_or_else(|| invalid_data(format!("palette line {}: expected `key = sgr`", number + 1)))?;
+            let mut key_chars = key.trim().chars();
+            let key = match (key_chars.next(), key_chars.next()) {
+                (Some(c), None) => c,
+                _ => return Err(invalid_data(format!("palette line {}: key must be a single character", number + 1))),
+            };
+
+            let sgr = sgr.trim();
+            if sgr.is_empty() || !sgr.chars().all(|c| c.is_ascii_digit() || c == ';') {
+                return Err(invalid_data(format!("palette line {}: invalid SGR parameters `{}`", number + 1, sgr)));
+            }
+            palette.insert(key, Style::from_sgr(sgr));
+        }
+
+        Ok(palette)
+    }
+}
+
+/// Rectangular block of ASCII art rendered as one object
+///
+/// Spaces in the source art are transparent and leave whatever is below them visible.
+///
+/// # Example
+/// ```
+/// # use lonely_engine::sprite::Sprite;
+/// let ship = Sprite::from_str(" ^ \n/#\\");
+/// assert_eq!((ship.width, ship.height), (3, 2));
+/// assert!(ship.get(0, 0).is_none());
+/// assert_eq!(ship.get(1, 1).unwrap().character, '#');
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sprite {
+    /// Width in cells (longest row)
+    pub width: usize,
+    /// Height in cells (number of rows)
+    pub height: usize,
+    /// Row-major cells, `None` marks a transparent cell
+    cells: Vec<Option<SpriteCell>>,
+}
+
+impl Sprite {
+    /// Parses multi-line ASCII art without colors
+    ///
+    /// # Arguments
+    /// * `art` - Art rows separated by newlines
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(art: &str) -> Self {
+        let rows: Vec<Vec<char>> = art.lines().map(|line| line.chars().collect()).collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = rows.len();
+
+        let mut cells = vec![None; width * height];
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &c) in row.iter().enumerate() {
+                if c != ' ' {
+                    cells[y * width + x] = Some(SpriteCell { character: c, fg_color: Color::Default, bg_color: Color::Default, attributes: Attributes::NONE });
+                }
+            }
+        }
+
+        Self { width, height, cells }
+    }
+
+    /// Creates a sprite from row-major cells
+    ///
+    /// # Arguments
+    /// * `width` - Width in cells
+    /// * `height` - Height in cells
+    /// * `cells` - Cells row by row, `None` marking transparent ones
+    ///
+    /// # Notes
+    /// - Missing cells are transparent, extra cells are dropped
+    pub fn from_cells(width: usize, height: usize, mut cells: Vec<Option<SpriteCell>>) -> Self {
+        cells.resize(width * height, None);
+        Self { width, height, cells }
+    }
+
+    /// Parses ASCII art colored by a mask grid
+    ///
+    /// # Arguments
+    /// * `art` - Art rows separated by newlines
+    /// * `mask` - Rows of palette keys aligned with `art`
+    /// * `palette` - Mapping of mask keys to styles
+    ///
+    /// # Notes
+    /// - Mask cells that are spaces, missing, or unknown to the palette leave the cell uncolored
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::sprite::{Palette, Sprite};
+    /// use lonely_engine::color::Color;
+    ///
+    /// let palette = Palette::parse("r = 31").unwrap();
+    /// let heart = Sprite::from_str_with_palette("<3", " r", &palette);
+    /// assert_eq!(heart.get(1, 0).unwrap().fg_color, Color::RED);
+    /// ```
+    pub fn from_str_with_palette(art: &str, mask: &str, palette: &Palette) -> Self {
+        let mut sprite = Self::from_str(art);
+        for (y, row) in mask.lines().enumerate().take(sprite.height) {
+            for (x, key) in row.chars().enumerate().take(sprite.width) {
+                if let (Some(cell), Some(style)) = (sprite.cells[y * sprite.width + x].as_mut(), palette.get(key)) {
+                    cell.fg_color = style.fg_color;
+                    cell.bg_color = style.bg_color;
+                    cell.attributes = style.attributes;
+                }
+            }
+        }
+
+        sprite
+    }
+
+    /// Loads a sprite from a text file
+    ///
+    /// # Arguments
+    /// * `path` - Path to the art file
+    ///
+    /// # Notes
+    /// - A sidecar `.pal` file next to `path` is applied when present (see module docs)
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::sprite::Sprite;
+    /// let dragon = Sprite::from_file("assets/dragon.txt").expect("Missing sprite");
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let art = fs::read_to_string(path)?;
+        let palette_path = path.with_extension("pal");
+
+        if palette_path.exists() {
+            let palette_text = fs::read_to_string(&palette_path)?;
+            let (palette, mask) = split_palette_file(&palette_text)?;
+            Ok(Self::from_str_with_palette(&art, &mask, &palette))
+        } else {
+            Ok(Self::from_str(&art))
+        }
+    }
+
+    /// Loads a sprite from a PNG or BMP image
+    ///
+    /// # Arguments
+    /// * `path` - Path to the image file
+    /// * `import` - Character ramp, palette, and other conversion settings
+    ///
+    /// # Notes
+    /// - Requires the `image` feature, see [`image`](crate::image)
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::sprite::Sprite;
+    /// use lonely_engine::image::ImageImport;
+    ///
+    /// let slime = Sprite::from_image("assets/slime.png", &ImageImport::new().half_blocks()).expect("Missing sprite");
+    /// ```
+    #[cfg(feature = "image")]
+    pub fn from_image(path: impl AsRef<Path>, import: &crate::image::ImageImport) -> io::Result<Self> {
+        Ok(import.to_sprite(&crate::image::Image::load(path)?))
+    }
+
+    /// Parses several animation frames separated by [`FRAME_SEPARATOR`] lines
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::sprite::Sprite;
+    /// let frames = Sprite::frames_from_str("o\n---frame---\nO");
+    /// assert_eq!(frames.len(), 2);
+    /// ```
+    pub fn frames_from_str(text: &str) -> Vec<Self> {
+        split_frames(text).iter().map(|frame| Self::from_str(frame)).collect()
+    }
+
+    /// Loads animation frames from delimited sections of one file
+    ///
+    /// # Notes
+    /// - A sidecar `.pal` file may hold one color mask per frame, also separated by [`FRAME_SEPARATOR`]
+    pub fn frames_from_file(path: impl AsRef<Path>) -> io::Result<Vec<Self>> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        let palette_path = path.with_extension("pal");
+
+        if !palette_path.exists() {
+            return Ok(Self::frames_from_str(&text));
+        }
+
+        let palette_text = fs::read_to_string(&palette_path)?;
+        let (palette, masks) = split_palette_file(&palette_text)?;
+        let masks = split_frames(&masks);
+        Ok(split_frames(&text)
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| Self::from_str_with_palette(frame, masks.get(i).map(String::as_str).unwrap_or(""), &palette))
+            .collect())
+    }
+
+    /// Loads one frame per `.txt` file in a directory
+    ///
+    /// # Notes
+    /// - Files are ordered by name, so `walk_00.txt`, `walk_01.txt`, ... play in sequence
+    /// - Each file may have its own sidecar palette
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::sprite::Sprite;
+    /// let walk_cycle = Sprite::frames_from_dir("assets/hero_walk").expect("Missing frames");
+    /// ```
+    pub fn frames_from_dir(dir: impl AsRef<Path>) -> io::Result<Vec<Self>> {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "txt") {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        paths.iter().map(Self::from_file).collect()
+    }
+
+    /// Returns the cell at sprite-local coordinates, `None` when transparent or out of range
+    pub fn get(&self, x: usize, y: usize) -> Option<&SpriteCell> {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x].as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// Iterates visible cells as `(x, y, cell)` in sprite-local coordinates
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize, &SpriteCell)> {
+        self.cells.iter().enumerate().filter_map(move |(i, cell)| {
+            cell.as_ref().map(|cell| (i % self.width, i / self.width, cell))
+        })
+    }
+}
+
+/// Splits palette file text into the palette entries and the color mask
+fn split_palette_file(text: &str) -> io::Result<(Palette, String)> {
+    let normalized = text.replace("\r\n", "\n");
+    let (entries, mask) = normalized.split_once("\n\n").unwrap_or((&normalized, ""));
+    Ok((Palette::parse(entries)?, mask.to_string()))
+}
+
+/// Splits text into frame sections on [`FRAME_SEPARATOR`] lines
+fn split_frames(text: &str) -> Vec<String> {
+    let mut frames: Vec<Vec<&str>> = vec![Vec::new()];
+    for line in text.lines() {
+        if line.trim_end() == FRAME_SEPARATOR {
+            frames.push(Vec::new());
+        } else if let Some(frame) = frames.last_mut() {
+            frame.push(line);
+        }
+    }
+
+    frames.iter().map(|lines| lines.join("\n")).collect()
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
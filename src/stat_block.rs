@@ -0,0 +1,285 @@
+//! RPG stats with modifiers from equipment and buffs
+//!
+//! Provides:
+//! - [`Modifier`] adding to or multiplying a stat, tagged with its source and
+//!   lasting until removed or for a number of seconds
+//! - [`StatBlock`] component stored on every [`GameObject`], holding base
+//!   stats, formulas for derived stats, and the active modifiers
+//!
+//! A stat's value is its base plus every additive modifier, times every
+//! multiplicative one. Derived stats, such as maximum health from vitality,
+//! are computed from the values of the stats before them and take modifiers
+//! like any other stat. Values are recomputed whenever something changes.
+//!
+//! Base stats and modifiers are changed with `EngineCommand::SetStat`,
+//! `EngineCommand::AddModifier`, and `EngineCommand::RemoveModifiers`. The
+//! engine ticks timed modifiers every update and emits
+//! `EngineEvent::StatChanged` for every value that changed.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::stat_block::{Modifier, StatBlock};
+//!
+//! let mut stats = StatBlock::new()
+//!     .with_base("strength", 10.0)
+//!     .with_base("vitality", 8.0)
+//!     .with_derived("max_health", |stats| 20.0 + stats.get("vitality") * 5.0);
+//! assert_eq!(stats.get("max_health"), 60.0);
+//!
+//! stats.add_modifier(Modifier::add("strength", 4.0, "iron sword"));
+//! stats.add_modifier(Modifier::multiply("strength", 1.5, "rage").lasting(10.0));
+//! stats.add_modifier(Modifier::add("vitality", 2.0, "ring of health"));
+//! assert_eq!(stats.get("strength"), 21.0);
+//! assert_eq!(stats.get("max_health"), 70.0);
+//!
+//! stats.remove_source("iron sword");
+//! assert_eq!(stats.get("strength"), 15.0);
+//! ```
+//!
+//! [`GameObject`]: crate::game_object::GameObject
+
+use std::collections::BTreeMap;
+
+/// Computes a derived stat from the values of the stats before it
+pub type Formula = fn(&StatBlock) -> f32;
+
+/// How a modifier changes a stat
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ModifierKind {
+    /// Adds to the base value, negative amounts subtract
+    Add(f32),
+    /// Multiplies the value after additions, `1.1` is ten percent more
+    Multiply(f32),
+}
+
+/// A change to one stat from one source
+///
+/// # Example
+/// ```
+/// use lonely_engine::stat_block::{Modifier, ModifierKind};
+///
+/// let haste = Modifier::multiply("speed", 1.5, "haste potion").lasting(30.0);
+/// assert_eq!(haste.kind, ModifierKind::Multiply(1.5));
+/// assert_eq!(haste.remaining, Some(30.0));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Modifier {
+    /// Name of the stat changed
+    pub stat: String,
+    /// What the modifier does
+    pub kind: ModifierKind,
+    /// What granted it, such as an item or a spell, used to remove its modifiers together
+    pub source: String,
+    /// Seconds left, `None` lasts until removed
+    pub remaining: Option<f32>,
+}
+
+impl Modifier {
+    /// Creates a modifier adding to a stat until removed
+    pub fn add(stat: &str, amount: f32, source: &str) -> Self {
+        Self { stat: stat.to_string(), kind: ModifierKind::Add(amount), source: source.to_string(), remaining: None }
+    }
+
+    /// Creates a modifier multiplying a stat until removed
+    pub fn multiply(stat: &str, factor: f32, source: &str) -> Self {
+        Self { stat: stat.to_string(), kind: ModifierKind::Multiply(factor), source: source.to_string(), remaining: None }
+    }
+
+    /// Makes the modifier expire after a number of seconds
+    pub fn lasting(mut self, seconds: f32) -> Self {
+        self.remaining = Some(seconds);
+        self
+    }
+}
+
+/// Base stats, derived stat formulas, and modifiers of a game object
+///
+/// # Notes
+/// - Unknown stats read as `0.0`, so a modifier on a stat without a base still counts
+/// - Derived stats are computed in the order they were added
+#[derive(Debug, Clone, Default)]
+pub struct StatBlock {
+    base: BTreeMap<String, f32>,
+    derived: Vec<(String, Formula)>,
+    modifiers: Vec<Modifier>,
+    /// Final values, recomputed after every change
+    values: BTreeMap<String, f32>,
+    /// Stats whose value changed since the last [`take_changes`](Self::take_changes)
+    changes: Vec<(String, f32)>,
+}
+
+impl StatBlock {
+    /// Creates an empty stat block
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a base stat and returns the block, for builder-style construction
+    pub fn with_base(mut self, stat: &str, value: f32) -> Self {
+        self.base.insert(stat.to_string(), value);
+        self.recompute();
+        self.changes.clear();
+        self
+    }
+
+    /// Adds a derived stat and returns the block, for builder-style construction
+    ///
+    /// # Arguments
+    /// * `stat` - Name of the derived stat, replacing a derived stat of the same name
+    /// * `formula` - Computes its value before modifiers from the stats before it
+    pub fn with_derived(mut self, stat: &str, formula: Formula) -> Self {
+        self.derived.retain(|(name, _)| name != stat);
+        self.derived.push((stat.to_string(), formula));
+        self.recompute();
+        self.changes.clear();
+        self
+    }
+
+    /// Final value of a stat, `0.0` for unknown stats
+    pub fn get(&self, stat: &str) -> f32 {
+        self.values.get(stat).copied().unwrap_or(0.0)
+    }
+
+    /// Base value of a stat, before modifiers
+    pub fn base(&self, stat: &str) -> Option<f32> {
+        self.base.get(stat).copied()
+    }
+
+    /// Returns whether a stat has a base, formula, or modifier
+    pub fn contains(&self, stat: &str) -> bool {
+        self.values.contains_key(stat)
+    }
+
+    /// Names and final values of every stat, by name
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f32)> {
+        self.values.iter().map(|(name, value)| (name.as_str(), *value))
+    }
+
+    /// Changes a base stat, such as on level up
+    pub fn set_base(&mut self, stat: &str, value: f32) {
+        self.base.insert(stat.to_string(), value);
+        self.recompute();
+    }
+
+    /// Adds a modifier, next to any others from the same source
+    pub fn add_modifier(&mut self, modifier: Modifier) {
+        self.modifiers.push(modifier);
+        self.recompute();
+    }
+
+    /// Removes every modifier granted by a source
+    ///
+    /// # Returns
+    /// Number of modifiers removed
+    pub fn remove_source(&mut self, source: &str) -> usize {
+        let before = self.modifiers.len();
+        self.modifiers.retain(|modifier| modifier.source != source);
+        let removed = before - self.modifiers.len();
+        if removed > 0 {
+            self.recompute();
+        }
+        removed
+    }
+
+    /// Active modifiers in the order they were added
+    pub fn modifiers(&self) -> &[Modifier] {
+        &self.modifiers
+    }
+
+    /// Returns whether a source grants any active modifier
+    pub fn has_source(&self, source: &str) -> bool {
+        self.modifiers.iter().any(|modifier| modifier.source == source)
+    }
+
+    /// Advances modifier timers, removing the ones that run out
+    ///
+    /// # Arguments
+    /// * `delta_time` - Time since last update in seconds
+    ///
+    /// # Returns
+    /// Modifiers that expired, in the order they were added
+    ///
+    /// # Notes
+    /// - Called by the engine every update
+    pub fn tick(&mut self, delta_time: f32) -> Vec<Modifier> {
+        let mut expired = Vec::new();
+        let mut index = 0;
+        while index < self.modifiers.len() {
+            if let Some(remaining) = &mut self.modifiers[index].remaining {
+                *remaining -= delta_time;
+                if *remaining <= 0.0 {
+                    expired.push(self.modifiers.remove(index));
+                    continue;
+                }
+            }
+            index += 1;
+        }
+        if !expired.is_empty() {
+            self.recompute();
+        }
+        expired
+    }
+
+    /// Returns the stats whose value changed since the last call, with their new values
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::stat_block::{Modifier, StatBlock};
+    ///
+    /// let mut stats = StatBlock::new().with_base("armor", 5.0).with_derived("block", |stats| stats.get("armor") / 10.0);
+    /// stats.add_modifier(Modifier::add("armor", 5.0, "shield"));
+    /// assert_eq!(stats.take_changes(), [("armor".to_string(), 10.0), ("block".to_string(), 1.0)]);
+    /// assert!(stats.take_changes().is_empty());
+    /// ```
+    pub fn take_changes(&mut self) -> Vec<(String, f32)> {
+        std::mem::take(&mut self.changes)
+    }
+
+    /// Value of a stat before modifiers through its modifiers
+    fn modified(&self, stat: &str, value: f32) -> f32 {
+        let (mut added, mut factor) = (0.0, 1.0);
+        for modifier in self.modifiers.iter().filter(|modifier| modifier.stat == stat) {
+            match modifier.kind {
+                ModifierKind::Add(amount) => added += amount,
+                ModifierKind::Multiply(multiplier) => factor *= multiplier,
+            }
+        }
+        (value + added) * factor
+    }
+
+    /// Recomputes every value and records the ones that changed
+    fn recompute(&mut self) {
+        let previous = std::mem::take(&mut self.values);
+        let mut values = BTreeMap::new();
+        for (stat, base) in &self.base {
+            values.insert(stat.clone(), self.modified(stat, *base));
+        }
+        for modifier in &self.modifiers {
+            if !values.contains_key(&modifier.stat) && !self.derived.iter().any(|(name, _)| *name == modifier.stat) {
+                values.insert(modifier.stat.clone(), self.modified(&modifier.stat, 0.0));
+            }
+        }
+        self.values = values;
+        for index in 0..self.derived.len() {
+            let (stat, formula) = self.derived[index].clone();
+            let value = self.modified(&stat, formula(self));
+            self.values.insert(stat, value);
+        }
+
+        // Changed and added stats in name order, then removed ones
+        let mut changes: Vec<(String, f32)> = self.values
+            .iter()
+            .filter(|(stat, value)| previous.get(*stat) != Some(value))
+            .map(|(stat, value)| (stat.clone(), *value))
+            .collect();
+        changes.extend(previous.keys().filter(|stat| !self.values.contains_key(*stat)).map(|stat| (stat.clone(), 0.0)));
+        for (stat, value) in changes {
+            match self.changes.iter_mut().find(|(changed, _)| *changed == stat) {
+                Some(change) => change.1 = value,
+                None => self.changes.push((stat, value)),
+            }
+        }
+    }
+}
@@ -0,0 +1,317 @@
+//! Generic finite state machine for game flow and entity AI
+//!
+//! Provides [`StateMachine`], which tracks a current state and runs
+//! enter/exit/update callbacks registered per state. Transitions happen either
+//! explicitly through [`StateMachine::transition_to`] or automatically when a
+//! registered condition passes. The machine implements [`Updatable`], so it can
+//! drive a whole game flow (menu → playing → game over) directly, and
+//! [`Behavior`], so each entity can carry its own machine for its AI.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{game_object::GameObject, state_machine::StateMachine, testing::TestEngine};
+//!
+//! #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+//! enum Guard { Patrol, Flee }
+//!
+//! let mut ai = StateMachine::new(Guard::Patrol);
+//! ai.on_object_update(Guard::Patrol, |obj, _, _| {
+//!     obj.x += 1;
+//!     Vec::new()
+//! });
+//! ai.add_object_transition(Guard::Patrol, Guard::Flee, |obj, _| obj.x >= 12);
+//!
+//! // Every guard walks and flees on its own
+//! let mut test = TestEngine::new(80, 24);
+//! for (x, y) in [(10, 5), (0, 8)] {
+//!     let mut guard = GameObject::new(x, y, 'G');
+//!     guard.add_behavior(ai.clone());
+//!     test.add_object(guard);
+//! }
+//! test.run(3);
+//! test.assert_position(0, 12, 5);
+//! test.assert_position(1, 3, 8);
+//! ```
+
+use std::{cell::RefCell, collections::{HashMap, HashSet}, hash::Hash, rc::Rc};
+use crate::{behavior::Behavior, engine::{EngineCommand, Updatable}, game_object::GameObject, input::Key};
+
+/// Callback run when a state is entered or exited
+type StateCallback = Rc<RefCell<dyn FnMut() -> Vec<EngineCommand>>>;
+/// Callback run every frame while a state is active
+type UpdateCallback = Rc<RefCell<dyn FnMut(f32, &HashSet<Key>) -> Vec<EngineCommand>>>;
+/// Callback run every frame while a state is active, with the object carrying the machine
+type ObjectUpdateCallback = Rc<RefCell<dyn FnMut(&mut GameObject, f32, &HashSet<Key>) -> Vec<EngineCommand>>>;
+/// Predicate deciding whether an automatic transition fires
+type TransitionCondition = Rc<RefCell<dyn FnMut(f32, &HashSet<Key>) -> bool>>;
+/// Predicate on the object carrying the machine deciding whether an automatic transition fires
+type ObjectCondition = Rc<RefCell<dyn FnMut(&GameObject, f32) -> bool>>;
+
+/// What an automatic transition waits for
+#[derive(Clone)]
+enum Condition {
+    Keys(TransitionCondition),
+    Object(ObjectCondition),
+}
+
+/// Automatic transition between two states
+#[derive(Clone)]
+struct Transition<S> {
+    to: S,
+    condition: Condition,
+}
+
+/// Finite state machine over user-defined states
+///
+/// # Notes
+/// - The initial state's enter callbacks run on the first update
+/// - Automatic transitions are checked after update callbacks, in registration order
+/// - At most one automatic transition fires per update
+/// - Clones share their callbacks, and whatever the callbacks captured, but
+///   track their state separately, so one machine can be cloned onto many objects
+/// - Object callbacks and transitions only run while the machine is attached to an object
+///
+/// # Example
+/// ```
+/// use lonely_engine::{state_machine::StateMachine, engine::{Engine, EngineCommand}, input::Key};
+///
+/// #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// enum Flow { Menu, Playing, GameOver }
+///
+/// let mut flow = StateMachine::new(Flow::Menu);
+/// flow.add_transition(Flow::Menu, Flow::Playing, |_, keys| keys.contains(&Key::Enter));
+/// flow.on_enter(Flow::GameOver, || vec![EngineCommand::Quit]);
+///
+/// let mut engine = Engine::new(80, 24);
+/// engine.add_updatable(flow);
+/// ```
+pub struct StateMachine<S> {
+    current: S,
+    previous: Option<S>,
+    time_in_state: f32,
+    started: bool,
+    pending_commands: Vec<EngineCommand>,
+    on_enter: HashMap<S, Vec<StateCallback>>,
+    on_exit: HashMap<S, Vec<StateCallback>>,
+    on_update: HashMap<S, Vec<UpdateCallback>>,
+    on_object_update: HashMap<S, Vec<ObjectUpdateCallback>>,
+    transitions: HashMap<S, Vec<Transition<S>>>,
+}
+
+impl<S: Clone> Clone for StateMachine<S> {
+    /// Clones the machine without the commands waiting for the original's next update
+    fn clone(&self) -> Self {
+        Self {
+            current: self.current.clone(),
+            previous: self.previous.clone(),
+            time_in_state: self.time_in_state,
+            started: self.started,
+            pending_commands: Vec::new(),
+            on_enter: self.on_enter.clone(),
+            on_exit: self.on_exit.clone(),
+            on_update: self.on_update.clone(),
+            on_object_update: self.on_object_update.clone(),
+            transitions: self.transitions.clone(),
+        }
+    }
+}
+
+impl<S: Clone + Eq + Hash> StateMachine<S> {
+    /// Creates a state machine starting in `initial`
+    pub fn new(initial: S) -> Self {
+        Self {
+            current: initial,
+            previous: None,
+            time_in_state: 0.0,
+            started: false,
+            pending_commands: Vec::new(),
+            on_enter: HashMap::new(),
+            on_exit: HashMap::new(),
+            on_update: HashMap::new(),
+            on_object_update: HashMap::new(),
+            transitions: HashMap::new(),
+        }
+    }
+
+    /// Returns the active state
+    pub fn current(&self) -> &S {
+        &self.current
+    }
+
+    /// Returns the state active before the last transition
+    pub fn previous(&self) -> Option<&S> {
+        self.previous.as_ref()
+    }
+
+    /// Returns whether the machine is in `state`
+    pub fn is_in(&self, state: &S) -> bool {
+        self.current == *state
+    }
+
+    /// Seconds spent in the active state
+    pub fn time_in_state(&self) -> f32 {
+        self.time_in_state
+    }
+
+    /// Registers a callback run when `state` becomes active
+    pub fn on_enter(&mut self, state: S, callback: impl FnMut() -> Vec<EngineCommand> + 'static) {
+        self.on_enter.entry(state).or_default().push(Rc::new(RefCell::new(callback)));
+    }
+
+    /// Registers a callback run when `state` stops being active
+    pub fn on_exit(&mut self, state: S, callback: impl FnMut() -> Vec<EngineCommand> + 'static) {
+        self.on_exit.entry(state).or_default().push(Rc::new(RefCell::new(callback)));
+    }
+
+    /// Registers a callback run every frame while `state` is active
+    ///
+    /// # Arguments
+    /// * `state` - State the callback belongs to
+    /// * `callback` - Receives delta time and active keys, returns engine commands
+    pub fn on_update(&mut self, state: S, callback: impl FnMut(f32, &HashSet<Key>) -> Vec<EngineCommand> + 'static) {
+        self.on_update.entry(state).or_default().push(Rc::new(RefCell::new(callback)));
+    }
+
+    /// Registers a callback run every frame while `state` is active, when the machine is attached to an object
+    ///
+    /// # Arguments
+    /// * `state` - State the callback belongs to
+    /// * `callback` - Receives the object, delta time, and active keys, returns engine commands
+    pub fn on_object_update(&mut self, state: S, callback: impl FnMut(&mut GameObject, f32, &HashSet<Key>) -> Vec<EngineCommand> + 'static) {
+        self.on_object_update.entry(state).or_default().push(Rc::new(RefCell::new(callback)));
+    }
+
+    /// Registers an automatic transition
+    ///
+    /// # Arguments
+    /// * `from` - State the transition leaves
+    /// * `to` - State the transition enters
+    /// * `condition` - Receives time spent in `from` and active keys, fires when `true`
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::state_machine::StateMachine;
+    /// # #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    /// # enum Enemy { Idle, Patrol }
+    /// let mut ai = StateMachine::new(Enemy::Idle);
+    /// // Start patrolling after two idle seconds
+    /// ai.add_transition(Enemy::Idle, Enemy::Patrol, |time, _| time >= 2.0);
+    /// ```
+    pub fn add_transition(&mut self, from: S, to: S, condition: impl FnMut(f32, &HashSet<Key>) -> bool + 'static) {
+        let condition = Condition::Keys(Rc::new(RefCell::new(condition)));
+        self.transitions.entry(from).or_default().push(Transition { to, condition });
+    }
+
+    /// Registers an automatic transition on the object carrying the machine
+    ///
+    /// # Arguments
+    /// * `from` - State the transition leaves
+    /// * `to` - State the transition enters
+    /// * `condition` - Receives the object and time spent in `from`, fires when `true`
+    ///
+    /// # Notes
+    /// - Never fires while the machine runs as an updatable
+    pub fn add_object_transition(&mut self, from: S, to: S, condition: impl FnMut(&GameObject, f32) -> bool + 'static) {
+        let condition = Condition::Object(Rc::new(RefCell::new(condition)));
+        self.transitions.entry(from).or_default().push(Transition { to, condition });
+    }
+
+    /// Switches to `state` immediately, running exit and enter callbacks
+    ///
+    /// # Notes
+    /// - Commands returned by the callbacks are delivered on the next update
+    pub fn transition_to(&mut self, state: S) {
+        if !self.started {
+            self.enter_initial();
+        }
+
+        let exit_commands = run_callbacks(self.on_exit.get(&self.current));
+        self.pending_commands.extend(exit_commands);
+
+        self.previous = Some(std::mem::replace(&mut self.current, state));
+        self.time_in_state = 0.0;
+
+        let enter_commands = run_callbacks(self.on_enter.get(&self.current));
+        self.pending_commands.extend(enter_commands);
+    }
+
+    /// Advances the machine by one frame
+    ///
+    /// # Arguments
+    /// * `delta_time` - Time since last update in seconds
+    /// * `active_keys` - Set of currently pressed keyboard keys
+    ///
+    /// # Returns
+    /// Commands produced by callbacks during this step
+    pub fn step(&mut self, delta_time: f32, active_keys: &HashSet<Key>) -> Vec<EngineCommand> {
+        self.advance(None, delta_time, active_keys)
+    }
+
+    /// Advances the machine attached to an object by one frame, see [`step`](Self::step)
+    pub fn step_object(&mut self, obj: &mut GameObject, delta_time: f32, active_keys: &HashSet<Key>) -> Vec<EngineCommand> {
+        self.advance(Some(obj), delta_time, active_keys)
+    }
+
+    fn advance(&mut self, mut obj: Option<&mut GameObject>, delta_time: f32, active_keys: &HashSet<Key>) -> Vec<EngineCommand> {
+        if !self.started {
+            self.enter_initial();
+        }
+
+        self.time_in_state += delta_time;
+        if let Some(callbacks) = self.on_update.get(&self.current) {
+            for callback in callbacks {
+                let commands = (callback.borrow_mut())(delta_time, active_keys);
+                self.pending_commands.extend(commands);
+            }
+        }
+        if let (Some(obj), Some(callbacks)) = (obj.as_deref_mut(), self.on_object_update.get(&self.current)) {
+            for callback in callbacks {
+                let commands = (callback.borrow_mut())(obj, delta_time, active_keys);
+                self.pending_commands.extend(commands);
+            }
+        }
+
+        let time_in_state = self.time_in_state;
+        let next = self.transitions.get(&self.current).and_then(|transitions| {
+            transitions.iter().find_map(|transition| {
+                let fires = match (&transition.condition, obj.as_deref()) {
+                    (Condition::Keys(condition), _) => (condition.borrow_mut())(time_in_state, active_keys),
+                    (Condition::Object(condition), Some(obj)) => (condition.borrow_mut())(obj, time_in_state),
+                    (Condition::Object(_), None) => false,
+                };
+                fires.then(|| transition.to.clone())
+            })
+        });
+        if let Some(next) = next {
+            self.transition_to(next);
+        }
+
+        std::mem::take(&mut self.pending_commands)
+    }
+
+    fn enter_initial(&mut self) {
+        self.started = true;
+        let commands = run_callbacks(self.on_enter.get(&self.current));
+        self.pending_commands.extend(commands);
+    }
+}
+
+impl<S: Clone + Eq + Hash> Updatable for StateMachine<S> {
+    fn update(&mut self, delta_time: f32, active_keys: &HashSet<Key>) -> Vec<EngineCommand> {
+        self.step(delta_time, active_keys)
+    }
+}
+
+impl<S: Clone + Eq + Hash + 'static> Behavior for StateMachine<S> {
+    fn update(&mut self, obj: &mut GameObject, delta_time: f32, active_keys: &HashSet<Key>) -> Vec<EngineCommand> {
+        self.step_object(obj, delta_time, active_keys)
+    }
+}
+
+/// Runs a list of enter/exit callbacks and collects their commands
+fn run_callbacks(callbacks: Option<&Vec<StateCallback>>) -> Vec<EngineCommand> {
+    callbacks
+        .into_iter()
+        .flat_map(|callbacks| callbacks.iter().flat_map(|callback| (callback.borrow_mut())()))
+        .collect()
+}
@@ -0,0 +1,259 @@
+//! Persistent stats and achievements
+//!
+//! Provides:
+//! - [`Stats`] holding named counters and flags (`enemies_killed`, `beat_boss`)
+//! - [`Achievement`]s unlocked when a counter reaches a threshold or a flag is set
+//! - Saving and loading between sessions as TOML
+//!
+//! The engine owns a [`Stats`] instance, updated through `EngineCommand::IncrementStat`
+//! and `EngineCommand::SetStatFlag`, and emits `EngineEvent::AchievementUnlocked`
+//! for every achievement a command unlocks.
+//!
+//! # File format
+//! ```toml
+//! [counters]
+//! enemies_killed = 42
+//!
+//! [flags]
+//! beat_boss = true
+//!
+//! [achievements]
+//! unlocked = ["first_blood"]
+//! ```
+
+use std::{collections::{BTreeMap, BTreeSet}, fs, io, path::Path};
+use crate::toml::{TomlDocument, TomlValue};
+
+/// What unlocks an achievement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AchievementCondition {
+    /// Counter reaches at least the value
+    AtLeast(String, i64),
+    /// Flag is set
+    Flag(String),
+}
+
+/// A named goal unlocked once its condition is met
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Achievement {
+    /// Identifier stored in save files and events
+    pub id: String,
+    /// Name shown to players
+    pub title: String,
+    /// Condition unlocking the achievement
+    pub condition: AchievementCondition,
+}
+
+impl Achievement {
+    /// Achievement unlocked when `stat` reaches `threshold`
+    pub fn at_least(id: &str, title: &str, stat: &str, threshold: i64) -> Self {
+        Self { id: id.to_string(), title: title.to_string(), condition: AchievementCondition::AtLeast(stat.to_string(), threshold) }
+    }
+
+    /// Achievement unlocked when `flag` is set
+    pub fn flag(id: &str, title: &str, flag: &str) -> Self {
+        Self { id: id.to_string(), title: title.to_string(), condition: AchievementCondition::Flag(flag.to_string()) }
+    }
+}
+
+/// Named counters and flags with achievements tracked over them
+///
+/// # Notes
+/// - Missing counters read as `0` and missing flags as `false`
+/// - Achievements unlock at most once, including across saved sessions
+///
+/// # Example
+/// ```
+/// use lonely_engine::stats::{Achievement, Stats};
+///
+/// let mut stats = Stats::new();
+/// stats.add_achievement(Achievement::at_least("exterminator", "Exterminator", "enemies_killed", 3));
+///
+/// stats.increment("enemies_killed");
+/// stats.add("enemies_killed", 2);
+/// assert_eq!(stats.get("enemies_killed"), 3);
+/// assert!(stats.is_unlocked("exterminator"));
+/// assert_eq!(stats.take_unlocked(), vec!["exterminator".to_string()]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stats {
+    counters: BTreeMap<String, i64>,
+    flags: BTreeSet<String>,
+    achievements: Vec<Achievement>,
+    unlocked: BTreeSet<String>,
+    /// Unlocked since the last `take_unlocked`
+    newly_unlocked: Vec<String>,
+}
+
+impl Stats {
+    /// Creates empty stats without achievements
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an achievement, unlocking it right away if already earned
+    pub fn add_achievement(&mut self, achievement: Achievement) {
+        self.achievements.retain(|existing| existing.id != achievement.id);
+        self.achievements.push(achievement);
+        self.check_achievements();
+    }
+
+    /// Registered achievements in registration order
+    pub fn achievements(&self) -> &[Achievement] {
+        &self.achievements
+    }
+
+    /// Adds one to a counter
+    pub fn increment(&mut self, name: &str) {
+        self.add(name, 1);
+    }
+
+    /// Adds an amount (possibly negative) to a counter
+    pub fn add(&mut self, name: &str, amount: i64) {
+        *self.counters.entry(name.to_string()).or_default() += amount;
+        self.check_achievements();
+    }
+
+    /// Overwrites a counter
+    pub fn set(&mut self, name: &str, value: i64) {
+        self.counters.insert(name.to_string(), value);
+        self.check_achievements();
+    }
+
+    /// Keeps the larger of a counter and `value`, for bests such as high scores
+    pub fn set_max(&mut self, name: &str, value: i64) {
+        if value > self.get(name) || !self.counters.contains_key(name) {
+            self.set(name, value);
+        }
+    }
+
+    /// Current value of a counter
+    pub fn get(&self, name: &str) -> i64 {
+        self.counters.get(name).copied().unwrap_or(0)
+    }
+
+    /// Sets or clears a flag
+    pub fn set_flag(&mut self, name: &str, value: bool) {
+        if value {
+            self.flags.insert(name.to_string());
+            self.check_achievements();
+        } else {
+            self.flags.remove(name);
+        }
+    }
+
+    /// Returns whether a flag is set
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.contains(name)
+    }
+
+    /// Returns whether an achievement has been unlocked
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains(id)
+    }
+
+    /// Identifiers of every unlocked achievement, sorted
+    pub fn unlocked(&self) -> impl Iterator<Item = &str> {
+        self.unlocked.iter().map(String::as_str)
+    }
+
+    /// Drains achievements unlocked since the previous call, in unlock order
+    pub fn take_unlocked(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.newly_unlocked)
+    }
+
+    /// Resets counters, flags, and unlocks, keeping achievement definitions
+    pub fn reset(&mut self) {
+        self.counters.clear();
+        self.flags.clear();
+        self.unlocked.clear();
+        self.newly_unlocked.clear();
+    }
+
+    /// Formats counters, flags, and unlocked achievements as TOML
+    pub fn to_toml(&self) -> String {
+        let mut doc = TomlDocument::new();
+        for (name, value) in &self.counters {
+            doc.set("counters", name, *value);
+        }
+        for name in &self.flags {
+            doc.set("flags", name, true);
+        }
+        let unlocked = self.unlocked.iter().map(|id| TomlValue::String(id.clone())).collect();
+        doc.set("achievements", "unlocked", TomlValue::Array(unlocked));
+        doc.to_string()
+    }
+
+    /// Merges values from TOML text into these stats
+    ///
+    /// # Notes
+    /// - Achievements unlocked in the text are not reported again by [`take_unlocked`](Self::take_unlocked)
+    ///
+    /// # Returns
+    /// `Err` with [`io::ErrorKind::InvalidData`] if the text is not valid TOML
+    pub fn load_toml(&mut self, text: &str) -> io::Result<()> {
+        let doc = TomlDocument::parse(text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        for (name, value) in doc.table("counters").unwrap_or(&[]) {
+            if let Some(value) = value.as_integer() {
+                self.counters.insert(name.clone(), value);
+            }
+        }
+        for (name, value) in doc.table("flags").unwrap_or(&[]) {
+            if value.as_bool() == Some(true) {
+                self.flags.insert(name.clone());
+            }
+        }
+        let unlocked = doc.get("achievements", "unlocked").and_then(TomlValue::as_array).unwrap_or(&[]);
+        self.unlocked.extend(unlocked.iter().filter_map(TomlValue::as_str).map(str::to_string));
+
+        // Conditions met by loaded values count as unlocked in an earlier session
+        let reported = self.newly_unlocked.len();
+        self.check_achievements();
+        self.newly_unlocked.truncate(reported);
+        Ok(())
+    }
+
+    /// Loads stats saved by [`save`](Self::save)
+    ///
+    /// # Returns
+    /// * `Ok(())` without changes when the file does not exist yet
+    /// * `Err` if the file is unreadable or malformed
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::stats::Stats;
+    /// let mut stats = Stats::new();
+    /// stats.load("stats.toml").expect("Corrupt stats file");
+    /// stats.increment("games_played");
+    /// stats.save("stats.toml").expect("Could not save stats");
+    /// ```
+    pub fn load(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        match fs::read_to_string(path) {
+            Ok(text) => self.load_toml(&text),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Writes counters, flags, and unlocked achievements to a file
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_toml())
+    }
+
+    /// Unlocks every achievement whose condition now holds
+    fn check_achievements(&mut self) {
+        for achievement in &self.achievements {
+            if self.unlocked.contains(&achievement.id) {
+                continue;
+            }
+            let earned = match &achievement.condition {
+                AchievementCondition::AtLeast(stat, threshold) => self.counters.get(stat).copied().unwrap_or(0) >= *threshold,
+                AchievementCondition::Flag(flag) => self.flags.contains(flag),
+            };
+            if earned {
+                self.unlocked.insert(achievement.id.clone());
+                self.newly_unlocked.push(achievement.id.clone());
+            }
+        }
+    }
+}
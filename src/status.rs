@@ -0,0 +1,216 @@
+//! Timed status effects (buffs and debuffs) attached to game objects
+//!
+//! Provides:
+//! - [`EffectKind`] describing what an effect does
+//! - [`StatusEffect`] pairing a named effect with its duration
+//! - [`StatusEffects`] component stored on every [`GameObject`]
+//!
+//! Effects are applied and removed with `EngineCommand::ApplyEffect` and
+//! `EngineCommand::RemoveEffect`. The engine ticks them every update, emits
+//! `EngineEvent::EffectExpired` when they run out, and skips the behaviors of
+//! stunned objects. Speed and damage are left to game systems to query.
+//!
+//! [`GameObject`]: crate::game_object::GameObject
+
+/// What a status effect does while active
+#[derive(Debug, Clone, PartialEq)]
+pub enum EffectKind {
+    /// Multiplies movement speed, `0.5` halves it and `2.0` doubles it
+    Speed(f32),
+    /// Deals damage continuously, in points per second
+    DamageOverTime(f32),
+    /// Prevents the object's behaviors from running
+    Stun,
+    /// Game-defined effect with no built-in meaning
+    Custom,
+}
+
+/// A named effect with a remaining duration
+///
+/// # Example
+/// ```
+/// use lonely_engine::status::{EffectKind, StatusEffect};
+///
+/// let poison = StatusEffect::new("poison", EffectKind::DamageOverTime(2.0), 5.0);
+/// let blessing = StatusEffect::permanent("blessing", EffectKind::Speed(1.25));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusEffect {
+    /// Identifier used to refresh and remove the effect
+    pub name: String,
+    /// What the effect does
+    pub kind: EffectKind,
+    /// Seconds left, `None` lasts until removed
+    pub remaining: Option<f32>,
+}
+
+impl StatusEffect {
+    /// Creates an effect lasting `duration` seconds
+    pub fn new(name: &str, kind: EffectKind, duration: f32) -> Self {
+        Self { name: name.to_string(), kind, remaining: Some(duration) }
+    }
+
+    /// Creates an effect that lasts until removed
+    pub fn permanent(name: &str, kind: EffectKind) -> Self {
+        Self { name: name.to_string(), kind, remaining: None }
+    }
+}
+
+/// Active status effects of a game object
+///
+/// # Notes
+/// - Applying an effect with an existing name replaces it, refreshing its duration
+/// - Damage over time accumulates until collected with [`take_damage`](Self::take_damage)
+///
+/// # Example
+/// ```
+/// use lonely_engine::status::{EffectKind, StatusEffect, StatusEffects};
+///
+/// let mut effects = StatusEffects::new();
+/// effects.apply(StatusEffect::new("frozen", EffectKind::Stun, 1.0));
+/// effects.apply(StatusEffect::new("slow", EffectKind::Speed(0.5), 3.0));
+/// assert!(effects.is_stunned());
+///
+/// let expired = effects.tick(1.5);
+/// assert_eq!(expired, vec!["frozen".to_string()]);
+/// assert_eq!(effects.speed_multiplier(), 0.5);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatusEffects {
+    effects: Vec<StatusEffect>,
+    pending_damage: f32,
+}
+
+impl StatusEffects {
+    /// Creates an empty effect list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an effect, replacing any active effect with the same name
+    pub fn apply(&mut self, effect: StatusEffect) {
+        match self.effects.iter_mut().find(|active| active.name == effect.name) {
+            Some(active) => *active = effect,
+            None => self.effects.push(effect),
+        }
+    }
+
+    /// Removes an effect by name
+    ///
+    /// # Returns
+    /// `true` when the effect was active
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.effects.len();
+        self.effects.retain(|effect| effect.name != name);
+        self.effects.len() != before
+    }
+
+    /// Removes every effect and discards pending damage
+    pub fn clear(&mut self) {
+        self.effects.clear();
+        self.pending_damage = 0.0;
+    }
+
+    /// Returns whether an effect is active
+    pub fn has(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Returns an active effect by name
+    pub fn get(&self, name: &str) -> Option<&StatusEffect> {
+        self.effects.iter().find(|effect| effect.name == name)
+    }
+
+    /// Iterates over active effects in the order they were applied
+    pub fn iter(&self) -> impl Iterator<Item = &StatusEffect> {
+        self.effects.iter()
+    }
+
+    /// Number of active effects
+    pub fn len(&self) -> usize {
+        self.effects.len()
+    }
+
+    /// Returns whether no effects are active
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// Returns whether any stun effect is active
+    pub fn is_stunned(&self) -> bool {
+        self.effects.iter().any(|effect| effect.kind == EffectKind::Stun)
+    }
+
+    /// Product of all speed modifiers, `1.0` when none are active
+    pub fn speed_multiplier(&self) -> f32 {
+        self.effects
+            .iter()
+            .filter_map(|effect| match effect.kind {
+                EffectKind::Speed(multiplier) => Some(multiplier),
+                _ => None,
+            })
+            .product()
+    }
+
+    /// Sum of all damage-over-time effects in points per second
+    pub fn damage_per_second(&self) -> f32 {
+        self.effects
+            .iter()
+            .filter_map(|effect| match effect.kind {
+                EffectKind::DamageOverTime(damage) => Some(damage),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// Returns damage accumulated since the last call and resets it
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::status::{EffectKind, StatusEffect, StatusEffects};
+    /// let mut effects = StatusEffects::new();
+    /// effects.apply(StatusEffect::new("burning", EffectKind::DamageOverTime(4.0), 2.0));
+    /// effects.tick(0.5);
+    /// assert_eq!(effects.take_damage(), 2.0);
+    /// assert_eq!(effects.take_damage(), 0.0);
+    /// ```
+    pub fn take_damage(&mut self) -> f32 {
+        std::mem::take(&mut self.pending_damage)
+    }
+
+    /// Advances effect timers and accumulates damage over time
+    ///
+    /// # Arguments
+    /// * `delta_time` - Time since last update in seconds
+    ///
+    /// # Returns
+    /// Names of effects that expired, in the order they were applied
+    ///
+    /// # Notes
+    /// - Called by the engine every update
+    /// - Expiring effects only deal damage for the time they were still active
+    pub fn tick(&mut self, delta_time: f32) -> Vec<String> {
+        let mut expired = Vec::new();
+
+        for effect in &mut self.effects {
+            let active_time = match effect.remaining.as_mut() {
+                Some(remaining) => {
+                    let active_time = delta_time.min(*remaining).max(0.0);
+                    *remaining -= delta_time;
+                    if *remaining <= 0.0 {
+                        expired.push(effect.name.clone());
+                    }
+                    active_time
+                },
+                None => delta_time,
+            };
+
+            if let EffectKind::DamageOverTime(damage) = effect.kind {
+                self.pending_damage += damage * active_time;
+            }
+        }
+
+        self.effects.retain(|effect| effect.remaining.is_none_or(|remaining| remaining > 0.0));
+        expired
+    }
+}
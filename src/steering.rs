@@ -0,0 +1,274 @@
+//! Steering behaviors for AI movement
+//!
+//! Each behavior turns positions into a suggested velocity in cells per second:
+//! - [`seek`] and [`flee`] head straight towards or away from a point
+//! - [`arrive`] seeks but slows down to stop on the target
+//! - [`Wander`] drifts around randomly with smooth turns
+//! - [`separation`] keeps a group from bunching up
+//! - [`cohesion`] and [`alignment`] pull a group together and along, see [`flocking`](crate::flocking)
+//!
+//! Suggestions are blended with [`Steering`] and applied to an [`Agent`], which
+//! keeps the fractional position grid objects lack and reports whole-cell moves
+//! to send as `EngineCommand::MoveObject`.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{engine::EngineCommand, rng::Rng, steering::{self, Agent, Steering, Wander}};
+//!
+//! let mut bat = Agent::new(10.0, 5.0, 6.0);
+//! let mut wander = Wander::new(0.5);
+//! let mut rng = Rng::new(3);
+//! let player = (30.0, 12.0);
+//! let flock = [(11.0, 5.0), (9.0, 6.0)];
+//!
+//! let velocity = Steering::new()
+//!     .add(steering::seek(bat.position(), player, bat.max_speed), 1.0)
+//!     .add(steering::separation(bat.position(), &flock, 3.0, bat.max_speed), 1.5)
+//!     .add(wander.next(&mut rng, bat.max_speed), 0.3)
+//!     .result(bat.max_speed);
+//!
+//! let (dx, dy) = bat.integrate(velocity, 1.0 / 30.0);
+//! let command = EngineCommand::MoveObject(0, dx, dy);
+//! ```
+
+use crate::{direction::Direction, game_object::GameObject, rng::Rng};
+
+/// Length of a vector
+fn length(vector: (f32, f32)) -> f32 {
+    vector.0.hypot(vector.1)
+}
+
+/// Scales a vector to a length, leaving zero vectors unchanged
+fn with_length(vector: (f32, f32), target: f32) -> (f32, f32) {
+    let current = length(vector);
+    if current <= f32::EPSILON {
+        return (0.0, 0.0);
+    }
+    (vector.0 / current * target, vector.1 / current * target)
+}
+
+/// Shortens a vector longer than a limit
+fn truncate(vector: (f32, f32), max: f32) -> (f32, f32) {
+    if length(vector) > max { with_length(vector, max) } else { vector }
+}
+
+/// Full speed straight towards a target
+///
+/// # Example
+/// ```
+/// # use lonely_engine::steering;
+/// assert_eq!(steering::seek((0.0, 0.0), (10.0, 0.0), 4.0), (4.0, 0.0));
+/// ```
+pub fn seek(position: (f32, f32), target: (f32, f32), max_speed: f32) -> (f32, f32) {
+    with_length((target.0 - position.0, target.1 - position.1), max_speed)
+}
+
+/// Full speed straight away from a threat closer than a distance
+///
+/// # Arguments
+/// * `panic_distance` - Threats further away than this are ignored
+///
+/// # Returns
+/// `(0.0, 0.0)` when the threat is out of range
+pub fn flee(position: (f32, f32), threat: (f32, f32), max_speed: f32, panic_distance: f32) -> (f32, f32) {
+    let away = (position.0 - threat.0, position.1 - threat.1);
+    if length(away) > panic_distance {
+        return (0.0, 0.0);
+    }
+    with_length(away, max_speed)
+}
+
+/// Seeks a target, slowing down inside a radius so it stops on the target instead of overshooting
+///
+/// # Arguments
+/// * `slowing_radius` - Distance at which braking starts
+///
+/// # Example
+/// ```
+/// # use lonely_engine::steering;
+/// // Halfway into the slowing radius moves at half speed
+/// assert_eq!(steering::arrive((0.0, 0.0), (2.0, 0.0), 6.0, 4.0), (3.0, 0.0));
+/// ```
+pub fn arrive(position: (f32, f32), target: (f32, f32), max_speed: f32, slowing_radius: f32) -> (f32, f32) {
+    let offset = (target.0 - position.0, target.1 - position.1);
+    let distance = length(offset);
+    let speed = if distance < slowing_radius { max_speed * distance / slowing_radius } else { max_speed };
+    with_length(offset, speed)
+}
+
+/// Pushes away from neighbors inside a radius, harder the closer they are
+///
+/// # Arguments
+/// * `neighbors` - Positions of the others in the group, the agent's own position is skipped
+/// * `radius` - Neighbors further away than this are ignored
+pub fn separation(position: (f32, f32), neighbors: &[(f32, f32)], radius: f32, max_speed: f32) -> (f32, f32) {
+    let mut push = (0.0, 0.0);
+    for neighbor in neighbors {
+        let away = (position.0 - neighbor.0, position.1 - neighbor.1);
+        let distance = length(away);
+        if distance <= f32::EPSILON || distance > radius {
+            continue;
+        }
+        let strength = (radius - distance) / radius;
+        let away = with_length(away, strength);
+        push.0 += away.0;
+        push.1 += away.1;
+    }
+    truncate((push.0 * max_speed, push.1 * max_speed), max_speed)
+}
+
+/// Heads for the center of the neighbors, slowing down close to it
+///
+/// # Arguments
+/// * `neighbors` - Positions of the others in the group
+///
+/// # Example
+/// ```
+/// # use lonely_engine::steering;
+/// assert_eq!(steering::cohesion((0.0, 0.0), &[(8.0, 2.0), (8.0, -2.0)], 4.0), (4.0, 0.0));
+/// ```
+pub fn cohesion(position: (f32, f32), neighbors: &[(f32, f32)], max_speed: f32) -> (f32, f32) {
+    if neighbors.is_empty() {
+        return (0.0, 0.0);
+    }
+    let count = neighbors.len() as f32;
+    let center = (neighbors.iter().map(|n| n.0).sum::<f32>() / count, neighbors.iter().map(|n| n.1).sum::<f32>() / count);
+    arrive(position, center, max_speed, max_speed)
+}
+
+/// Matches the average heading of the neighbors at full speed
+///
+/// # Arguments
+/// * `velocities` - Velocities of the others in the group
+pub fn alignment(velocities: &[(f32, f32)], max_speed: f32) -> (f32, f32) {
+    let sum = velocities.iter().fold((0.0, 0.0), |sum, v| (sum.0 + v.0, sum.1 + v.1));
+    with_length(sum, max_speed)
+}
+
+/// Random drifting that turns smoothly instead of jittering in place
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wander {
+    /// Current heading in radians
+    pub heading: f32,
+    /// Largest heading change per call in radians
+    pub jitter: f32,
+}
+
+impl Wander {
+    /// Creates a wander heading right
+    ///
+    /// # Arguments
+    /// * `jitter` - Largest heading change per call in radians
+    pub fn new(jitter: f32) -> Self {
+        Self { heading: 0.0, jitter }
+    }
+
+    /// Turns the heading by a random amount and returns full speed along it
+    pub fn next(&mut self, rng: &mut Rng, max_speed: f32) -> (f32, f32) {
+        self.heading += (rng.next_f32() * 2.0 - 1.0) * self.jitter;
+        (self.heading.cos() * max_speed, self.heading.sin() * max_speed)
+    }
+}
+
+/// Weighted blend of steering suggestions
+///
+/// # Example
+/// ```
+/// # use lonely_engine::steering::Steering;
+/// let velocity = Steering::new().add((4.0, 0.0), 1.0).add((0.0, 4.0), 0.5).result(10.0);
+/// assert_eq!(velocity, (4.0, 2.0));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Steering {
+    total: (f32, f32),
+}
+
+impl Steering {
+    /// Creates an empty blend
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a suggestion scaled by a weight
+    pub fn add(mut self, velocity: (f32, f32), weight: f32) -> Self {
+        self.total.0 += velocity.0 * weight;
+        self.total.1 += velocity.1 * weight;
+        self
+    }
+
+    /// Blended velocity, shortened to a top speed
+    pub fn result(self, max_speed: f32) -> (f32, f32) {
+        truncate(self.total, max_speed)
+    }
+}
+
+/// Fractional position and velocity of a steered object
+///
+/// # Notes
+/// - Moves are reported in whole cells, fractions carry over to the next frame
+/// - Keep one agent per object and resync with [`sync`](Self::sync) if something
+///   else moves the object
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Agent {
+    /// Horizontal position in cells
+    pub x: f32,
+    /// Vertical position in cells
+    pub y: f32,
+    /// Velocity of the last integration in cells per second
+    pub velocity: (f32, f32),
+    /// Top speed in cells per second
+    pub max_speed: f32,
+}
+
+impl Agent {
+    /// Creates a resting agent
+    pub fn new(x: f32, y: f32, max_speed: f32) -> Self {
+        Self { x, y, velocity: (0.0, 0.0), max_speed }
+    }
+
+    /// Creates a resting agent on an object's cell
+    pub fn at(obj: &GameObject, max_speed: f32) -> Self {
+        Self::new(obj.x as f32, obj.y as f32, max_speed)
+    }
+
+    /// Current position
+    pub fn position(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    /// Nearest of the eight directions the agent last moved in, `None` at rest
+    ///
+    /// # Notes
+    /// - Send as `EngineCommand::FaceObject` to turn an object that has not crossed into a new cell yet
+    pub fn facing(&self) -> Option<Direction> {
+        Direction::from_vector(self.velocity)
+    }
+
+    /// Snaps to an object's cell when it moved to a different one
+    pub fn sync(&mut self, obj: &GameObject) {
+        if (self.x.round() as i64, self.y.round() as i64) != (obj.x as i64, obj.y as i64) {
+            self.x = obj.x as f32;
+            self.y = obj.y as f32;
+        }
+    }
+
+    /// Moves along a velocity, limited to the top speed
+    ///
+    /// # Returns
+    /// Whole-cell change of the rounded position, for `EngineCommand::MoveObject`
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::steering::Agent;
+    /// let mut agent = Agent::new(0.0, 0.0, 10.0);
+    /// assert_eq!(agent.integrate((20.0, 0.0), 0.1), (1, 0)); // Capped at 10 cells per second
+    /// assert_eq!(agent.integrate((2.0, 0.0), 0.1), (0, 0)); // 1.2 still rounds to 1
+    /// ```
+    pub fn integrate(&mut self, velocity: (f32, f32), delta_time: f32) -> (i32, i32) {
+        self.velocity = truncate(velocity, self.max_speed);
+        let before = (self.x.round(), self.y.round());
+        self.x += self.velocity.0 * delta_time;
+        self.y += self.velocity.1 * delta_time;
+        ((self.x.round() - before.0) as i32, (self.y.round() - before.1) as i32)
+    }
+}
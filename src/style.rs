@@ -0,0 +1,210 @@
+//! Text styling shared by renderer drawing helpers
+//!
+//! Contains:
+//! - [`Style`] describing how drawn characters are colored and emphasized
+//! - [`Attributes`] for bold, dim, italic, underline, blink, and reverse video
+
+use crate::color::Color;
+
+/// Text attributes applied to a cell, reset after every cell
+///
+/// # Notes
+/// - Terminals without support for an attribute ignore it (blink and italic are often missing)
+///
+/// # Example
+/// ```
+/// use lonely_engine::style::Attributes;
+///
+/// let emphasis = Attributes { bold: true, underline: true, ..Attributes::NONE };
+/// assert_eq!(emphasis.escape(), "\x1B[1;4m");
+/// assert_eq!(Attributes::NONE.escape(), "");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Attributes {
+    /// Bold or bright text
+    pub bold: bool,
+    /// Faint text
+    pub dim: bool,
+    /// Italic text
+    pub italic: bool,
+    /// Underlined text
+    pub underline: bool,
+    /// Blinking text
+    pub blink: bool,
+    /// Swapped foreground and background colors
+    pub reverse: bool,
+}
+
+impl Attributes {
+    /// No attributes set
+    pub const NONE: Attributes = Attributes {
+        bold: false,
+        dim: false,
+        italic: false,
+        underline: false,
+        blink: false,
+        reverse: false,
+    };
+
+    /// Returns whether no attribute is set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::NONE
+    }
+
+    /// ANSI escape code enabling the attributes, empty when none are set
+    pub fn escape(&self) -> String {
+        let codes: Vec<&str> = [
+            (self.bold, "1"),
+            (self.dim, "2"),
+            (self.italic, "3"),
+            (self.underline, "4"),
+            (self.blink, "5"),
+            (self.reverse, "7"),
+        ]
+        .iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, code)| *code)
+        .collect();
+
+        if codes.is_empty() {
+            String::new()
+        } else {
+            format!("\x1B[{}m", codes.join(";"))
+        }
+    }
+}
+
+/// Colors and attributes applied to characters drawn through the renderer helpers
+///
+/// # Example
+/// ```
+/// use lonely_engine::{color::Color, style::Style};
+///
+/// let title = Style::new().fg(Color::YELLOW).bg(Color::BLUE).bold();
+/// let warning = Style::new().fg(Color::RED).blink();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    /// Foreground color
+    pub fg_color: Color,
+    /// Background color
+    pub bg_color: Color,
+    /// Bold, underline, and other text attributes
+    pub attributes: Attributes,
+}
+
+impl Style {
+    /// Creates a style that leaves the terminal's default colors
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the parameter list of an SGR escape sequence (`31`, `38;5;208`, `1;33`, ...)
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{color::Color, style::Style};
+    ///
+    /// let style = Style::from_sgr("1;38;5;208");
+    /// assert_eq!(style.fg_color, Color::Indexed(208));
+    /// assert!(style.attributes.bold);
+    /// ```
+    pub fn from_sgr(params: &str) -> Self {
+        let mut style = Self::new();
+        style.apply_sgr(params);
+        style
+    }
+
+    /// Applies the parameters of an SGR escape sequence on top of this style
+    ///
+    /// # Notes
+    /// - Understands the 16, 256, and true color forms and every attribute in [`Attributes`]
+    /// - `0` resets to the default style, unknown codes are ignored
+    pub fn apply_sgr(&mut self, params: &str) {
+        let codes: Vec<u16> = params.split(';').map(|code| code.parse().unwrap_or(0)).collect();
+        let mut i = 0;
+        while i < codes.len() {
+            match codes[i] {
+                0 => *self = Self::default(),
+                1 => self.attributes.bold = true,
+                2 => self.attributes.dim = true,
+                3 => self.attributes.italic = true,
+                4 => self.attributes.underline = true,
+                5 => self.attributes.blink = true,
+                7 => self.attributes.reverse = true,
+                code @ 30..=37 => self.fg_color = Color::Indexed((code - 30) as u8),
+                code @ 40..=47 => self.bg_color = Color::Indexed((code - 40) as u8),
+                code @ 90..=97 => self.fg_color = Color::Indexed((code - 90 + 8) as u8),
+                code @ 100..=107 => self.bg_color = Color::Indexed((code - 100 + 8) as u8),
+                39 => self.fg_color = Color::Default,
+                49 => self.bg_color = Color::Default,
+                code @ (38 | 48) => {
+                    let color = match codes.get(i + 1) {
+                        Some(5) => {
+                            i += 2;
+                            codes.get(i).map(|index| Color::Indexed(*index as u8))
+                        },
+                        Some(2) => {
+                            i += 4;
+                            codes.get(i - 2..=i).map(|rgb| Color::Rgb(rgb[0] as u8, rgb[1] as u8, rgb[2] as u8))
+                        },
+                        _ => None,
+                    };
+                    if let Some(color) = color {
+                        if code == 38 { self.fg_color = color } else { self.bg_color = color }
+                    }
+                },
+                _ => {},
+            }
+            i += 1;
+        }
+    }
+
+    /// Sets the foreground color
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg_color = color;
+        self
+    }
+
+    /// Sets the background color
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg_color = color;
+        self
+    }
+
+    /// Makes text bold
+    pub fn bold(mut self) -> Self {
+        self.attributes.bold = true;
+        self
+    }
+
+    /// Makes text faint
+    pub fn dim(mut self) -> Self {
+        self.attributes.dim = true;
+        self
+    }
+
+    /// Makes text italic
+    pub fn italic(mut self) -> Self {
+        self.attributes.italic = true;
+        self
+    }
+
+    /// Underlines text
+    pub fn underline(mut self) -> Self {
+        self.attributes.underline = true;
+        self
+    }
+
+    /// Makes text blink
+    pub fn blink(mut self) -> Self {
+        self.attributes.blink = true;
+        self
+    }
+
+    /// Swaps foreground and background colors
+    pub fn reverse(mut self) -> Self {
+        self.attributes.reverse = true;
+        self
+    }
+}
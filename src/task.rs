@@ -0,0 +1,279 @@
+//! Coroutines for gameplay logic spanning several frames
+//!
+//! Sequenced logic such as cutscenes, timed traps, or "walk there, wait, then
+//! talk" is written as a plain `async` block instead of a hand-rolled state
+//! machine. [`Engine::spawn_task`] runs it alongside the game: the engine polls
+//! every task once per update, after updatables and behaviors, and applies the
+//! commands it queued in the same update.
+//!
+//! # Awaitable primitives
+//! - [`wait_frames`] - resumes after a number of updates
+//! - [`wait_secs`] - resumes after game time passed
+//! - [`wait_event`] - resumes with the next event passing a filter
+//! - [`wait_for`] - resumes with the next event a predicate accepts
+//! - [`move_to`] - walks an object to a cell and resumes when it arrives
+//!
+//! [`command`] and [`play_sound`] queue engine commands from inside a task.
+//!
+//! # Notes
+//! - Tasks only advance while the world updates, pages and the pause menu hold them
+//! - Any other future can be awaited, but nothing wakes tasks early, they are simply polled every update
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{engine::Engine, task::{self, move_to, wait_secs}};
+//!
+//! let mut engine = Engine::new(80, 24);
+//! engine.spawn_task(async move {
+//!     move_to(0, 10, 5, 8.0).await;
+//!     wait_secs(1.0).await;
+//!     task::play_sound("door_open");
+//! });
+//! ```
+//!
+//! [`Engine::spawn_task`]: crate::engine::Engine::spawn_task
+
+use std::{cell::RefCell, future::Future, pin::Pin, task::{Context, Poll, Waker}};
+use crate::{engine::EngineCommand, event::{EngineEvent, EventFilter}, path_follower::PathFollower};
+
+/// Identifies a spawned task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskHandle(pub u64);
+
+/// Boxed future of a running task
+type Task = Pin<Box<dyn Future<Output = ()>>>;
+
+/// What a task sees while it is polled
+struct Frame {
+    delta_time: f32,
+    events: Vec<EngineEvent>,
+    commands: Vec<EngineCommand>,
+}
+
+thread_local! {
+    /// Update being run by [`Tasks::poll`], `None` outside of it
+    static FRAME: RefCell<Option<Frame>> = const { RefCell::new(None) };
+}
+
+/// Running tasks, polled once per update
+///
+/// The engine owns one, see [`Engine::spawn_task`]. It can also be driven by
+/// hand, which is how tasks are unit tested.
+///
+/// # Example
+/// ```
+/// use lonely_engine::{engine::EngineCommand, task::{self, Tasks}};
+///
+/// let mut tasks = Tasks::new();
+/// let handle = tasks.spawn(async {
+///     task::wait_frames(1).await;
+///     task::play_sound("ding");
+/// });
+///
+/// assert!(tasks.poll(0.1, Vec::new()).is_empty());
+/// let commands = tasks.poll(0.1, Vec::new());
+/// assert!(matches!(&commands[..], [EngineCommand::PlaySound(name)] if name == "ding"));
+/// assert!(!tasks.is_running(handle));
+/// ```
+///
+/// [`Engine::spawn_task`]: crate::engine::Engine::spawn_task
+#[derive(Default)]
+pub struct Tasks {
+    tasks: Vec<(TaskHandle, Task)>,
+    next_id: u64,
+}
+
+impl std::fmt::Debug for Tasks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tasks").field("running", &self.tasks.len()).finish()
+    }
+}
+
+impl Tasks {
+    /// Creates an empty task list
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a task, first polled by the next [`poll`](Self::poll)
+    pub fn spawn(&mut self, task: impl Future<Output = ()> + 'static) -> TaskHandle {
+        let handle = TaskHandle(self.next_id);
+        self.next_id += 1;
+        self.tasks.push((handle, Box::pin(task)));
+        handle
+    }
+
+    /// Drops a task without running the rest of it
+    ///
+    /// # Returns
+    /// `false` when the task already finished or was cancelled
+    pub fn cancel(&mut self, handle: TaskHandle) -> bool {
+        let count = self.tasks.len();
+        self.tasks.retain(|(running, _)| *running != handle);
+        self.tasks.len() != count
+    }
+
+    /// Returns whether a task has neither finished nor been cancelled
+    pub fn is_running(&self, handle: TaskHandle) -> bool {
+        self.tasks.iter().any(|(running, _)| *running == handle)
+    }
+
+    /// Number of running tasks
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns whether no task is running
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Advances every task by one update
+    ///
+    /// # Arguments
+    /// * `delta_time` - Game time of the update in seconds, counted by [`wait_secs`]
+    /// * `events` - Events emitted since the previous poll, seen by [`wait_event`]
+    ///
+    /// # Returns
+    /// Commands queued by the tasks, in the order they were queued
+    pub fn poll(&mut self, delta_time: f32, events: Vec<EngineEvent>) -> Vec<EngineCommand> {
+        FRAME.with_borrow_mut(|frame| *frame = Some(Frame { delta_time, events, commands: Vec::new() }));
+        let mut context = Context::from_waker(Waker::noop());
+        self.tasks.retain_mut(|(_, task)| task.as_mut().poll(&mut context).is_pending());
+        FRAME.with_borrow_mut(|frame| frame.take().map(|frame| frame.commands).unwrap_or_default())
+    }
+}
+
+/// Queues an engine command from inside a task, does nothing outside of one
+pub fn command(command: EngineCommand) {
+    FRAME.with_borrow_mut(|frame| {
+        if let Some(frame) = frame {
+            frame.commands.push(command);
+        }
+    });
+}
+
+/// Queues a preloaded sound to play, see [`command`]
+pub fn play_sound(name: &str) {
+    command(EngineCommand::PlaySound(name.to_string()));
+}
+
+/// Resumes after a number of updates, `wait_frames(1)` resumes in the next one
+pub fn wait_frames(frames: u32) -> WaitFrames {
+    WaitFrames { remaining: frames }
+}
+
+/// Resumes once `seconds` of game time passed, counted from the next update
+pub fn wait_secs(seconds: f32) -> WaitSecs {
+    WaitSecs { remaining: seconds, started: false }
+}
+
+/// Resumes with the next event passing a filter
+///
+/// # Notes
+/// - Only events emitted after the wait starts count
+///
+/// # Example
+/// ```
+/// use lonely_engine::{event::{EngineEvent, EventKind}, task::{self, Tasks}};
+///
+/// let mut tasks = Tasks::new();
+/// let handle = tasks.spawn(async {
+///     let event = task::wait_event(EventKind::AchievementUnlocked).await;
+///     println!("{event:?}");
+/// });
+///
+/// tasks.poll(0.1, vec![]);
+/// tasks.poll(0.1, vec![EngineEvent::GamePaused]);
+/// assert!(tasks.is_running(handle));
+/// tasks.poll(0.1, vec![EngineEvent::AchievementUnlocked("first_blood".into())]);
+/// assert!(!tasks.is_running(handle));
+/// ```
+pub fn wait_event(filter: impl Into<EventFilter>) -> WaitEvent<impl Fn(&EngineEvent) -> bool> {
+    let filter = filter.into();
+    wait_for(move |event| filter.matches(event))
+}
+
+/// Resumes with the next event a predicate accepts
+///
+/// # Notes
+/// - Only events emitted after the wait starts count
+pub fn wait_for<F: Fn(&EngineEvent) -> bool>(predicate: F) -> WaitEvent<F> {
+    WaitEvent { predicate, started: false }
+}
+
+/// Walks an object to a cell and resumes when it arrives
+///
+/// # Arguments
+/// * `index` - Object index
+/// * `x`, `y` - Destination cell
+/// * `speed` - Cells per second
+///
+/// # Notes
+/// - Replaces the object's current path, like `EngineCommand::FollowPath`
+/// - Never resumes if the path is stopped or replaced before the object arrives
+pub async fn move_to(index: usize, x: usize, y: usize, speed: f32) {
+    command(EngineCommand::FollowPath(index, PathFollower::new(vec![(x, y)], speed)));
+    wait_for(|event| matches!(event, EngineEvent::PathCompleted(completed) if *completed == index)).await;
+}
+
+/// Future returned by [`wait_frames`]
+#[derive(Debug)]
+pub struct WaitFrames {
+    remaining: u32,
+}
+
+impl Future for WaitFrames {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+        if self.remaining == 0 {
+            return Poll::Ready(());
+        }
+        self.remaining -= 1;
+        Poll::Pending
+    }
+}
+
+/// Future returned by [`wait_secs`]
+#[derive(Debug)]
+pub struct WaitSecs {
+    remaining: f32,
+    /// Whether the update the wait started in was skipped
+    started: bool,
+}
+
+impl Future for WaitSecs {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+        if std::mem::replace(&mut self.started, true) {
+            self.remaining -= FRAME.with_borrow(|frame| frame.as_ref().map_or(0.0, |frame| frame.delta_time));
+        }
+        if self.remaining <= 0.0 { Poll::Ready(()) } else { Poll::Pending }
+    }
+}
+
+/// Future returned by [`wait_event`] and [`wait_for`]
+pub struct WaitEvent<F> {
+    predicate: F,
+    /// Whether the update the wait started in was skipped, its events came before the wait
+    started: bool,
+}
+
+// The predicate is never pinned, it is only called through a shared reference
+impl<F> Unpin for WaitEvent<F> {}
+
+impl<F: Fn(&EngineEvent) -> bool> Future for WaitEvent<F> {
+    type Output = EngineEvent;
+
+    fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<EngineEvent> {
+        if !std::mem::replace(&mut self.started, true) {
+            return Poll::Pending;
+        }
+        let event = FRAME.with_borrow(|frame| {
+            frame.as_ref().and_then(|frame| frame.events.iter().find(|event| (self.predicate)(event)).cloned())
+        });
+        event.map_or(Poll::Pending, Poll::Ready)
+    }
+}
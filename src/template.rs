@@ -0,0 +1,213 @@
+//! Game project scaffolding
+//!
+//! [`new_project`] writes a ready-to-run game crate: a player moving around a
+//! walled room collecting coins, wired up with remappable controls, sound, and
+//! collision. It is the quickest way to a working starting point:
+//! ```text
+//! my_game/
+//! ├── Cargo.toml
+//! ├── keybindings.toml
+//! ├── .gitignore
+//! └── src/main.rs
+//! ```
+//! See the `examples/` directory of the engine for larger reference games.
+
+use std::{fs, io, path::Path};
+use crate::{input::Key, keybindings::ActionMap};
+
+/// Repository the generated project depends on by default
+pub const ENGINE_GIT_URL: &str = "https://github.com/RonaldoAPSD/LonelyEngineMK1.git";
+
+/// Placeholder replaced with the project name in template files
+const NAME_PLACEHOLDER: &str = "{{project_name}}";
+
+const MAIN_TEMPLATE: &str = r#"//! {{project_name}}, made with Lonely Engine
+
+use std::time::Duration;
+use lonely_engine::{
+    audio::tone,
+    color::Color,
+    engine::{Engine, EngineCommand, Updatable, UpdateContext},
+    game_object::GameObject,
+    helpers::check_collision,
+    input::Key,
+    keybindings::ActionMap,
+    renderer::Renderer,
+    tilemap::Tilemap,
+};
+
+const WIDTH: usize = 60;
+const HEIGHT: usize = 20;
+const COINS: usize = 5;
+
+/// Moves the player and collects coins
+struct Game {
+    actions: ActionMap,
+    score: u32,
+}
+
+impl Updatable for Game {
+    fn update_with_context(&mut self, ctx: &mut UpdateContext) -> Vec<EngineCommand> {
+        if self.actions.is_active("quit", ctx.active_keys) {
+            return vec![EngineCommand::Quit];
+        }
+        let Some((player_index, player)) = ctx.find_tag("player") else {
+            return Vec::new();
+        };
+
+        let mut commands = Vec::new();
+        // Two held arrows move diagonally
+        if let Some(direction) = self.actions.direction(ctx.active_keys) {
+            let (dx, dy) = direction.delta();
+            let (x, y) = ((player.x as i32 + dx) as usize, (player.y as i32 + dy) as usize);
+            // Stay inside the walls
+            if x > 0 && y > 0 && x < WIDTH - 1 && y < HEIGHT - 1 {
+                commands.push(EngineCommand::MoveObject(player_index, dx, dy));
+            }
+        }
+
+        // Highest index first so earlier indices stay valid
+        let collected: Vec<usize> = ctx.with_tag("coin")
+            .filter(|(_, coin)| check_collision(player, coin, &[]))
+            .map(|(index, _)| index)
+            .collect();
+        for index in collected.into_iter().rev() {
+            self.score += 1;
+            commands.push(EngineCommand::DespawnObject(index));
+            commands.push(EngineCommand::SpawnObject(coin(ctx.rng.range(1, WIDTH - 1), ctx.rng.range(1, HEIGHT - 1))));
+            commands.push(EngineCommand::PlaySound("coin".to_string()));
+        }
+
+        commands
+    }
+
+    fn draw(&self, renderer: &mut Renderer) {
+        renderer.draw_text(2, 0, &format!(" Score: {} ", self.score));
+    }
+}
+
+fn coin(x: usize, y: usize) -> GameObject {
+    let mut coin = GameObject::new(x, y, 'o');
+    coin.tag = "coin".to_string();
+    coin.fg_color = Color::BRIGHT_YELLOW;
+    coin
+}
+
+/// Walled room the game takes place in
+fn room() -> Tilemap {
+    let mut rows = vec![format!("+{}+", "-".repeat(WIDTH - 2))];
+    rows.extend((2..HEIGHT).map(|_| format!("|{}|", " ".repeat(WIDTH - 2))));
+    rows.push(rows[0].clone());
+    Tilemap::from_str(&rows.join("\n"))
+}
+
+fn default_controls() -> ActionMap {
+    let mut actions = ActionMap::new();
+    actions.set_keys("up", vec![Key::Up, Key::Char('w')]);
+    actions.set_keys("down", vec![Key::Down, Key::Char('s')]);
+    actions.set_keys("left", vec![Key::Left, Key::Char('a')]);
+    actions.set_keys("right", vec![Key::Right, Key::Char('d')]);
+    actions.set_keys("quit", vec![Key::Esc]);
+    actions
+}
+
+fn main() {
+    let defaults = default_controls();
+    let actions = ActionMap::load_or_default("keybindings.toml", &defaults).unwrap_or_else(|error| {
+        eprintln!("{}, using default controls", error);
+        defaults.clone()
+    });
+
+    let mut engine = Engine::new(WIDTH, HEIGHT);
+    engine.renderer.set_background(room());
+    let _ = engine.audio.preload_bytes("coin", tone(988.0, Duration::from_millis(80)));
+
+    let mut player = GameObject::new(WIDTH / 2, HEIGHT / 2, '@');
+    player.tag = "player".to_string();
+    player.fg_color = Color::BRIGHT_CYAN;
+    engine.add_object(player);
+    for _ in 0..COINS {
+        let (x, y) = (engine.rng.range(1, WIDTH - 1), engine.rng.range(1, HEIGHT - 1));
+        engine.add_object(coin(x, y));
+    }
+
+    engine.add_updatable(Game { actions, score: 0 });
+    engine.run();
+}
+"#;
+
+/// Creates a new game project depending on the engine's git repository
+///
+/// # Arguments
+/// * `path` - Directory to create, its name becomes the crate name
+///
+/// # Returns
+/// * `Err` with [`io::ErrorKind::AlreadyExists`] if `path` exists and is not empty
+/// * `Err` with [`io::ErrorKind::InvalidInput`] if the directory name is not a usable crate name
+/// * `Err` if writing the files failed
+///
+/// # Example
+/// ```no_run
+/// use lonely_engine::template::new_project;
+///
+/// new_project("my_game").expect("Could not create project");
+/// // cd my_game && cargo run
+/// ```
+pub fn new_project(path: impl AsRef<Path>) -> io::Result<()> {
+    scaffold(path.as_ref(), format!("{{ git = \"{}\" }}", ENGINE_GIT_URL))
+}
+
+/// Creates a new game project depending on a local engine checkout
+///
+/// # Arguments
+/// * `path` - Directory to create, its name becomes the crate name
+/// * `engine_path` - Engine directory as written into `Cargo.toml`, relative to `path` or absolute
+///
+/// # Returns
+/// Same as [`new_project`]
+pub fn new_project_with_engine_path(path: impl AsRef<Path>, engine_path: impl AsRef<Path>) -> io::Result<()> {
+    let engine_path = engine_path.as_ref().to_string_lossy().replace('\\', "/");
+    scaffold(path.as_ref(), format!("{{ path = \"{}\" }}", engine_path))
+}
+
+/// Writes the template files with the given engine dependency specification
+fn scaffold(path: &Path, dependency: String) -> io::Result<()> {
+    if path.exists() && fs::read_dir(path)?.next().is_some() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists and is not empty", path.display())));
+    }
+
+    let name = path
+        .file_name()
+        .map(|name| crate_name(&name.to_string_lossy()))
+        .filter(|name| name.starts_with(|c: char| c.is_ascii_alphabetic()))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("{} is not a usable crate name", path.display())))?;
+
+    let manifest = format!(
+        "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2024\"\n\n[dependencies]\nlonely_engine_mk1 = {}\n",
+        name, dependency,
+    );
+
+    fs::create_dir_all(path.join("src"))?;
+    fs::write(path.join("Cargo.toml"), manifest)?;
+    fs::write(path.join(".gitignore"), "/target\n")?;
+    fs::write(path.join("keybindings.toml"), default_controls().to_toml())?;
+    fs::write(path.join("src").join("main.rs"), MAIN_TEMPLATE.replace(NAME_PLACEHOLDER, &name))
+}
+
+/// Controls written to the generated `keybindings.toml`, matching the template's defaults
+fn default_controls() -> ActionMap {
+    let mut actions = ActionMap::new();
+    actions.set_keys("up", vec![Key::Up, Key::Char('w')]);
+    actions.set_keys("down", vec![Key::Down, Key::Char('s')]);
+    actions.set_keys("left", vec![Key::Left, Key::Char('a')]);
+    actions.set_keys("right", vec![Key::Right, Key::Char('d')]);
+    actions.set_keys("quit", vec![Key::Esc]);
+    actions
+}
+
+/// Lowercases a directory name and replaces characters Cargo does not allow
+fn crate_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect()
+}
@@ -0,0 +1,220 @@
+//! Terminal capability detection
+//!
+//! [`Capabilities`] describes what the terminal the game runs in can show:
+//! - Colors: none, the 16 ANSI colors, the 256-color palette, or 24-bit true color
+//! - Unicode box drawing and block characters, or ASCII only
+//! - Its size and whether it reports mouse input
+//! - Whether output goes to a Windows console and whether it understands escape codes
+//!
+//! The engine probes the terminal when it is created and hands the result to the
+//! renderer, which reduces colors and replaces characters on output so games
+//! draw with full colors and Unicode everywhere and degrade automatically.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{color::Color, engine::Engine, terminal::ColorDepth};
+//!
+//! let engine = Engine::new(80, 24);
+//! let accent = match engine.capabilities().color_depth {
+//!     ColorDepth::TrueColor => Color::Rgb(255, 140, 0),
+//!     _ => Color::YELLOW,
+//! };
+//! ```
+
+/// How many colors the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorDepth {
+    /// No escape codes at all: no colors and no attributes
+    None,
+    /// The 16 standard ANSI colors
+    Ansi16,
+    /// The 256-color palette
+    Indexed256,
+    /// 24-bit RGB colors
+    TrueColor,
+}
+
+/// What the terminal can show
+///
+/// # Example
+/// ```
+/// use lonely_engine::terminal::{Capabilities, ColorDepth};
+///
+/// let xterm = Capabilities::from_vars(|name| match name {
+///     "TERM" => Some("xterm-256color".to_string()),
+///     "LANG" => Some("en_US.UTF-8".to_string()),
+///     _ => None,
+/// });
+/// assert_eq!(xterm.color_depth, ColorDepth::Indexed256);
+/// assert!(xterm.unicode);
+///
+/// let serial = Capabilities::from_vars(|name| (name == "TERM").then(|| "dumb".to_string()));
+/// assert_eq!(serial.color_depth, ColorDepth::None);
+/// assert!(!serial.unicode);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Capabilities {
+    /// Colors the terminal can show
+    pub color_depth: ColorDepth,
+    /// Whether box drawing, block, and other non-ASCII characters display
+    pub unicode: bool,
+    /// Visible columns and rows, `None` when unknown
+    pub size: Option<(usize, usize)>,
+    /// Whether mouse input is reported
+    pub mouse: bool,
+    /// Whether output goes to a Windows console rather than a pipe or terminal emulator
+    pub windows_console: bool,
+    /// Whether escape codes are interpreted instead of printed
+    pub virtual_terminal: bool,
+}
+
+impl Default for Capabilities {
+    fn default() -> Self {
+        Self::full()
+    }
+}
+
+impl Capabilities {
+    /// A modern terminal: true color, Unicode, and escape codes, size unknown and no mouse
+    pub fn full() -> Self {
+        Self {
+            color_depth: ColorDepth::TrueColor,
+            unicode: true,
+            size: None,
+            mouse: false,
+            windows_console: false,
+            virtual_terminal: true,
+        }
+    }
+
+    /// A dumb terminal: plain ASCII without escape codes
+    pub fn dumb() -> Self {
+        Self { color_depth: ColorDepth::None, unicode: false, virtual_terminal: false, ..Self::full() }
+    }
+
+    /// Probes the terminal of this process
+    ///
+    /// # Notes
+    /// - Reads `TERM`, `COLORTERM`, `TERM_PROGRAM`, `WT_SESSION`, the locale, `COLUMNS`, and `LINES`, see [`from_vars`](Self::from_vars)
+    /// - On Windows, the console itself is asked for its size, mouse input, and escape code support.
+    ///   A console without escape code support gets no colors, since the codes would be printed
+    pub fn detect() -> Self {
+        let from_env = Self::from_vars(|name| std::env::var(name).ok());
+        console::probe(from_env)
+    }
+
+    /// Capabilities described by environment variables
+    ///
+    /// # Arguments
+    /// * `var` - Looks up a variable, `None` when unset
+    ///
+    /// # Notes
+    /// - `TERM=dumb` means plain ASCII without escape codes
+    /// - True color is assumed for `COLORTERM=truecolor` or `24bit`, Windows Terminal, and
+    ///   terminals announcing it in `TERM_PROGRAM` or `TERM`, 256 colors for `TERM=*256color*`,
+    ///   and 16 colors for any other terminal
+    /// - Unicode follows the first of `LC_ALL`, `LC_CTYPE`, and `LANG` that is set, and is assumed without any
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let var = |name: &str| var(name).filter(|value| !value.is_empty());
+        let term = var("TERM").unwrap_or_default().to_ascii_lowercase();
+        if term == "dumb" {
+            return Self { size: size_from_vars(var), ..Self::dumb() };
+        }
+
+        let colorterm = var("COLORTERM").unwrap_or_default().to_ascii_lowercase();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+        let color_depth = if matches!(colorterm.as_str(), "truecolor" | "24bit")
+            || var("WT_SESSION").is_some()
+            || matches!(program.as_str(), "iTerm.app" | "WezTerm" | "vscode" | "Hyper")
+            || ["truecolor", "24bit", "direct"].iter().any(|name| term.contains(name))
+        {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Indexed256
+        } else if term.is_empty() {
+            // Windows consoles set no TERM, the console probe decides for them
+            ColorDepth::TrueColor
+        } else {
+            ColorDepth::Ansi16
+        };
+
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"].iter().find_map(|name| var(name));
+        let unicode = locale.is_none_or(|locale| {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        });
+
+        Self { color_depth, unicode, size: size_from_vars(var), ..Self::full() }
+    }
+
+    /// Returns whether 24-bit colors display as they are
+    pub fn truecolor(&self) -> bool {
+        self.color_depth == ColorDepth::TrueColor
+    }
+
+    /// Returns whether the 256-color palette is available
+    pub fn colors_256(&self) -> bool {
+        self.color_depth >= ColorDepth::Indexed256
+    }
+}
+
+/// Screen size from `COLUMNS` and `LINES`, as exported by most shells
+fn size_from_vars(var: impl Fn(&str) -> Option<String>) -> Option<(usize, usize)> {
+    let columns = var("COLUMNS")?.trim().parse().ok()?;
+    let lines = var("LINES")?.trim().parse().ok()?;
+    Some((columns, lines))
+}
+
+#[cfg(windows)]
+mod console {
+    use winapi::um::consoleapi::{GetConsoleMode, SetConsoleMode};
+    use winapi::um::processenv::GetStdHandle;
+    use winapi::um::winbase::{STD_INPUT_HANDLE, STD_OUTPUT_HANDLE};
+    use winapi::um::wincon::{CONSOLE_SCREEN_BUFFER_INFO, ENABLE_VIRTUAL_TERMINAL_PROCESSING, GetConsoleScreenBufferInfo};
+    use super::{Capabilities, ColorDepth};
+
+    /// Refines environment capabilities with what the console reports
+    pub fn probe(mut capabilities: Capabilities) -> Capabilities {
+        unsafe {
+            let output = GetStdHandle(STD_OUTPUT_HANDLE);
+            let mut mode = 0;
+            if GetConsoleMode(output, &mut mode) == 0 {
+                // Redirected to a pipe or a terminal emulator, the environment tells the rest
+                return capabilities;
+            }
+            capabilities.windows_console = true;
+
+            // Support is tested by switching escape codes on, then restoring the mode
+            capabilities.virtual_terminal = mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+                || SetConsoleMode(output, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0;
+            SetConsoleMode(output, mode);
+            if !capabilities.virtual_terminal {
+                capabilities.color_depth = ColorDepth::None;
+            }
+            // Console output is written as UTF-16, independent of the code page
+            capabilities.unicode = true;
+
+            let mut info: CONSOLE_SCREEN_BUFFER_INFO = std::mem::zeroed();
+            if GetConsoleScreenBufferInfo(output, &mut info) != 0 {
+                let window = info.srWindow;
+                let columns = (window.Right - window.Left + 1).max(0) as usize;
+                let rows = (window.Bottom - window.Top + 1).max(0) as usize;
+                capabilities.size = Some((columns, rows));
+            }
+
+            let mut input_mode = 0;
+            capabilities.mouse = GetConsoleMode(GetStdHandle(STD_INPUT_HANDLE), &mut input_mode) != 0;
+        }
+        capabilities
+    }
+}
+
+#[cfg(not(windows))]
+mod console {
+    use super::Capabilities;
+
+    /// Console input is not implemented on other platforms, so neither is the mouse
+    pub fn probe(capabilities: Capabilities) -> Capabilities {
+        capabilities
+    }
+}
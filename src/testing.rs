@@ -0,0 +1,516 @@
+//! Test harnesses for rendering and simulation
+//!
+//! [`TestEngine`] steps a headless, deterministic engine frame by frame with
+//! scripted input, recording the events emitted and the commands applied, so
+//! updatables, behaviors, and command handling are tested without a console.
+//!
+//! [`TestRenderer`] is a headless [`Renderer`] that captures every presented
+//! frame and checks it with assertions, so a rendering regression fails a
+//! test instead of slipping through:
+//! - single cells, by character or with their colors and attributes
+//! - text at a position, or the whole frame against an inline string
+//! - golden files holding the expected frame, as plain text or as ANSI text
+//!   keeping the styles, viewable with `cat`
+//!
+//! A golden file that does not exist yet is written from the captured frame,
+//! and every golden file is rewritten when the [`UPDATE_GOLDEN_VAR`]
+//! environment variable is set, for accepting intended changes:
+//! ```text
+//! UPDATE_GOLDEN=1 cargo test
+//! ```
+//!
+//! Frames rendered elsewhere, such as by an engine, are checked the same way
+//! after passing them to [`TestRenderer::record`].
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{color::Color, game_object::GameObject, style::Style, testing::TestRenderer};
+//!
+//! let mut renderer = TestRenderer::new(8, 2);
+//! renderer.set_char(1, 0, &GameObject::new(1, 0, '@'));
+//! renderer.draw_styled_text(0, 1, "HP 10", &Style::new().fg(Color::RED));
+//! renderer.capture();
+//!
+//! renderer.assert_cell(1, 0, '@');
+//! renderer.assert_text_at(0, 1, "HP 10");
+//! renderer.assert_frame(" @      \nHP 10   \n");
+//! assert_eq!(renderer.frame_cell(0, 1).map(|cell| cell.fg), Some(Color::RED));
+//! ```
+
+use std::{cell::RefCell, env, fs, ops::{Deref, DerefMut}, path::Path, rc::Rc};
+use crate::{
+    engine::{DETERMINISTIC_TIMESTEP, Engine},
+    event::{EngineEvent, EventKind},
+    input::{InputEvent, InputInjector, Key},
+    input_log::InputLog,
+    renderer::{Cell, Renderer},
+    screenshot::Screenshot,
+};
+
+/// Environment variable rewriting golden files from the captured frames when set
+pub const UPDATE_GOLDEN_VAR: &str = "UPDATE_GOLDEN";
+
+/// Input source of the events scripted on a [`TestEngine`]
+pub const TEST_SOURCE: &str = "test";
+
+/// A command applied by the engine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandRecord {
+    /// Frame the command was applied in
+    pub frame: u64,
+    /// Variant name, such as `"MoveObject"`
+    pub name: &'static str,
+    /// Index of the object the command names, if any
+    pub target: Option<usize>,
+    /// The whole command in debug formatting
+    pub text: String,
+}
+
+/// Headless engine stepped frame by frame with scripted input
+///
+/// # Notes
+/// - Dereferences to the [`Engine`], so a test sets up objects, updatables, and resources as a game does
+/// - Frames run back to back without waiting, updates use a fixed timestep
+/// - Input is scripted by frame number, frames count from `0` like [`Engine::frame`]
+/// - Assertions panic with what was found when they fail
+///
+/// # Example
+/// ```
+/// use lonely_engine::{
+///     engine::{EngineCommand, Updatable, UpdateContext},
+///     event::EngineEvent,
+///     game_object::GameObject,
+///     input::Key,
+///     testing::TestEngine,
+/// };
+///
+/// /// Walks the first object right while Right is held
+/// struct Walker;
+///
+/// impl Updatable for Walker {
+///     fn update_with_context(&mut self, ctx: &mut UpdateContext) -> Vec<EngineCommand> {
+///         if ctx.active_keys.contains(&Key::Right) {
+///             return vec![EngineCommand::MoveObject(0, 1, 0)];
+///         }
+///         Vec::new()
+///     }
+/// }
+///
+/// let mut test = TestEngine::new(20, 5);
+/// test.add_object(GameObject::new(2, 2, '@'));
+/// test.add_updatable(Walker);
+/// test.tap(1, Key::Right);
+/// test.key_down(3, Key::Right);
+/// test.key_up(5, Key::Right);
+/// test.run(8);
+///
+/// test.assert_position(0, 5, 2);
+/// test.assert_emitted(|event| matches!(event, EngineEvent::ObjectMoved(0, 3, 2)));
+/// assert_eq!(test.commands_named("MoveObject").count(), 3);
+/// ```
+pub struct TestEngine {
+    engine: Engine,
+    script: InputLog,
+    injector: InputInjector,
+    /// Events emitted during the current frame, moved to `events` once it ends
+    sink: Rc<RefCell<Vec<EngineEvent>>>,
+    events: Vec<(u64, EngineEvent)>,
+}
+
+impl TestEngine {
+    /// Creates a headless engine in deterministic mode with seed `0`
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::from_engine(Engine::builder(width, height).headless(true).deterministic(0, DETERMINISTIC_TIMESTEP).build())
+    }
+
+    /// Watches an engine configured elsewhere, making it headless
+    ///
+    /// # Notes
+    /// - Runs stay reproducible only if the engine is deterministic, see [`Engine::set_deterministic`]
+    pub fn from_engine(mut engine: Engine) -> Self {
+        engine.renderer.set_headless(true);
+        engine.set_command_log(true);
+        let sink = Rc::new(RefCell::new(Vec::new()));
+        let events = Rc::clone(&sink);
+        engine.event_bus.subscribe(move |event| events.borrow_mut().push(event.clone()));
+        let injector = engine.input_injector(TEST_SOURCE);
+        Self { engine, script: InputLog::new(), injector, sink, events: Vec::new() }
+    }
+
+    /// Adds the events of an input log to the script, and returns the harness, for builder-style construction
+    pub fn input(mut self, log: InputLog) -> Self {
+        for (frame, event) in log.events() {
+            self.script.record(*frame, std::slice::from_ref(event));
+        }
+        self
+    }
+
+    /// Scripts an input event for a frame
+    pub fn send(&mut self, frame: u64, event: InputEvent) {
+        self.script.record(frame, &[event]);
+    }
+
+    /// Scripts a key pressed for a frame and released within it
+    pub fn tap(&mut self, frame: u64, key: Key) {
+        self.script.record(frame, &[InputEvent::KeyDown(key.clone()), InputEvent::KeyUp(key)]);
+    }
+
+    /// Scripts a key pressed from a frame on, until released with [`key_up`](Self::key_up)
+    pub fn key_down(&mut self, frame: u64, key: Key) {
+        self.script.record(frame, &[InputEvent::KeyDown(key)]);
+    }
+
+    /// Scripts a held key released in a frame, the frame no longer sees it held
+    pub fn key_up(&mut self, frame: u64, key: Key) {
+        self.script.record(frame, &[InputEvent::KeyUp(key)]);
+    }
+
+    /// Runs one frame with the input scripted for it
+    ///
+    /// # Returns
+    /// `false` without running when the engine has stopped, such as after `EngineCommand::Quit`
+    pub fn step(&mut self) -> bool {
+        if !self.engine.is_running() {
+            return false;
+        }
+        let frame = self.engine.frame();
+        for event in self.script.events_at(frame) {
+            self.injector.send(event.clone());
+        }
+        self.engine.run_frame(false);
+        self.events.extend(self.sink.take().into_iter().map(|event| (frame, event)));
+        true
+    }
+
+    /// Runs frames one after another, stopping early when the engine stops
+    ///
+    /// # Returns
+    /// Number of frames run
+    pub fn run(&mut self, frames: u64) -> u64 {
+        let mut run = 0;
+        while run < frames && self.step() {
+            run += 1;
+        }
+        run
+    }
+
+    /// Events emitted so far with the frame each one was emitted in, oldest first
+    ///
+    /// # Notes
+    /// - Events emitted between frames, such as while setting up, count towards the next frame
+    pub fn events(&self) -> &[(u64, EngineEvent)] {
+        &self.events
+    }
+
+    /// Events of one kind emitted so far, oldest first
+    pub fn events_of(&self, kind: EventKind) -> impl Iterator<Item = &EngineEvent> {
+        self.events.iter().map(|(_, event)| event).filter(move |event| event.kind() == kind)
+    }
+
+    /// Forgets the recorded events and commands, such as after setting up a scene
+    pub fn clear_history(&mut self) {
+        self.events.clear();
+        self.sink.borrow_mut().clear();
+        self.engine.set_command_log(true);
+    }
+
+    /// Commands applied so far, in the order they were applied
+    pub fn commands(&self) -> &[CommandRecord] {
+        self.engine.command_log()
+    }
+
+    /// Commands of one variant applied so far, such as `"SpawnObject"`
+    pub fn commands_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a CommandRecord> {
+        self.commands().iter().filter(move |record| record.name == name)
+    }
+
+    /// Last presented frame, see [`TestRenderer::record`] for assertions on it
+    pub fn screenshot(&self) -> Screenshot {
+        self.engine.renderer.screenshot()
+    }
+
+    /// Gives the engine back
+    pub fn into_engine(self) -> Engine {
+        self.engine
+    }
+
+    /// Asserts the position of the object at an index
+    pub fn assert_position(&self, index: usize, x: usize, y: usize) {
+        match self.engine.objects.get(index) {
+            Some(obj) if (obj.x, obj.y) == (x, y) => {},
+            Some(obj) => panic!("object {index} is at ({}, {}), expected ({x}, {y}) after frame {}", obj.x, obj.y, self.engine.frame()),
+            None => panic!("no object {index}, the world holds {} objects", self.engine.objects.len()),
+        }
+    }
+
+    /// Asserts the position of the first object with a tag
+    pub fn assert_tag_position(&self, tag: &str, x: usize, y: usize) {
+        match self.engine.objects.iter().find(|obj| obj.tag == tag) {
+            Some(obj) if (obj.x, obj.y) == (x, y) => {},
+            Some(obj) => panic!("object tagged {tag:?} is at ({}, {}), expected ({x}, {y}) after frame {}", obj.x, obj.y, self.engine.frame()),
+            None => panic!("no object tagged {tag:?}"),
+        }
+    }
+
+    /// Asserts that an emitted event passes a predicate
+    pub fn assert_emitted(&self, predicate: impl Fn(&EngineEvent) -> bool) {
+        if !self.events.iter().any(|(_, event)| predicate(event)) {
+            panic!("no matching event among {} emitted:\n{}", self.events.len(), self.describe_events());
+        }
+    }
+
+    /// Asserts that no emitted event passes a predicate
+    pub fn assert_not_emitted(&self, predicate: impl Fn(&EngineEvent) -> bool) {
+        if let Some((frame, event)) = self.events.iter().find(|(_, event)| predicate(event)) {
+            panic!("unexpected event in frame {frame}: {event:?}");
+        }
+    }
+
+    /// Asserts that a command of a variant was applied, such as `"DespawnObject"`
+    pub fn assert_command(&self, name: &str) {
+        if self.commands_named(name).next().is_none() {
+            let applied: Vec<&str> = self.commands().iter().map(|record| record.name).collect();
+            panic!("no {name} command applied, applied: {applied:?}");
+        }
+    }
+
+    fn describe_events(&self) -> String {
+        self.events.iter().map(|(frame, event)| format!("  frame {frame}: {event:?}\n")).collect()
+    }
+}
+
+impl Deref for TestEngine {
+    type Target = Engine;
+
+    fn deref(&self) -> &Engine {
+        &self.engine
+    }
+}
+
+impl DerefMut for TestEngine {
+    fn deref_mut(&mut self) -> &mut Engine {
+        &mut self.engine
+    }
+}
+
+/// Headless renderer recording its frames for assertions
+///
+/// # Notes
+/// - Dereferences to the [`Renderer`], so drawing code under test takes it as is
+/// - Assertions check the last captured frame and panic with both frames when they fail
+/// - Asserting before any capture panics
+pub struct TestRenderer {
+    renderer: Renderer,
+    frames: Vec<Screenshot>,
+}
+
+impl TestRenderer {
+    /// Creates a headless renderer with specified dimensions
+    pub fn new(width: usize, height: usize) -> Self {
+        let mut renderer = Renderer::new(width, height);
+        renderer.set_headless(true);
+        Self { renderer, frames: Vec::new() }
+    }
+
+    /// Presents what was drawn and records it as the current frame
+    ///
+    /// # Returns
+    /// The captured frame
+    pub fn capture(&mut self) -> &Screenshot {
+        // Headless presents only update the buffers
+        let _ = self.renderer.present();
+        self.record(self.renderer.screenshot())
+    }
+
+    /// Records a frame rendered elsewhere as the current frame
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{engine::Engine, testing::TestRenderer};
+    ///
+    /// let engine = Engine::new(20, 5);
+    /// let mut frames = TestRenderer::new(20, 5);
+    /// frames.record(engine.renderer.screenshot());
+    /// frames.assert_frame(&format!("{}\n", " ".repeat(20)).repeat(5));
+    /// ```
+    pub fn record(&mut self, frame: Screenshot) -> &Screenshot {
+        self.frames.push(frame);
+        &self.frames[self.frames.len() - 1]
+    }
+
+    /// Last captured frame
+    pub fn frame(&self) -> Option<&Screenshot> {
+        self.frames.last()
+    }
+
+    /// Every captured frame, oldest first
+    pub fn frames(&self) -> &[Screenshot] {
+        &self.frames
+    }
+
+    /// Forgets the captured frames
+    pub fn clear_frames(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Characters of the last captured frame, one line per row
+    pub fn text(&self) -> String {
+        self.current().to_text()
+    }
+
+    /// Cell of the last captured frame with its colors and attributes, `None` outside the frame
+    ///
+    /// # Notes
+    /// - Unlike `Renderer::cell`, which reads what is being drawn, this reads what was captured
+    pub fn frame_cell(&self, x: usize, y: usize) -> Option<Cell> {
+        self.current().cell(x, y).copied()
+    }
+
+    /// Cells of the last captured frame, one row per line
+    pub fn grid(&self) -> Vec<Vec<Cell>> {
+        self.current().rows().map(<[Cell]>::to_vec).collect()
+    }
+
+    /// Asserts the character at a cell of the last captured frame
+    pub fn assert_cell(&self, x: usize, y: usize, ch: char) {
+        let actual = self.frame_cell(x, y).map(|cell| cell.ch);
+        if actual != Some(ch) {
+            panic!("cell ({x}, {y}) holds {actual:?}, expected {ch:?}\n{}", self.text());
+        }
+    }
+
+    /// Asserts a cell of the last captured frame, colors and attributes included
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{color::Color, renderer::Cell, style::Style, testing::TestRenderer};
+    ///
+    /// let mut renderer = TestRenderer::new(4, 1);
+    /// let wall = Cell::styled('#', &Style::new().fg(Color::BLUE).bold());
+    /// renderer.set_cell(2, 0, wall);
+    /// renderer.capture();
+    /// renderer.assert_styled_cell(2, 0, wall);
+    /// ```
+    pub fn assert_styled_cell(&self, x: usize, y: usize, expected: Cell) {
+        let actual = self.frame_cell(x, y);
+        if actual != Some(expected) {
+            panic!("cell ({x}, {y}) is {actual:?}, expected {expected:?}\n{}", self.current().to_ansi());
+        }
+    }
+
+    /// Asserts the characters starting at a cell of the last captured frame
+    ///
+    /// # Notes
+    /// - Text running past the right edge of the frame fails
+    pub fn assert_text_at(&self, x: usize, y: usize, text: &str) {
+        let actual: String = (0..text.chars().count()).map(|offset| self.frame_cell(x + offset, y).map_or('\u{0}', |cell| cell.ch)).collect();
+        if actual != text {
+            panic!("text at ({x}, {y}) is {:?}, expected {text:?}\n{}", actual.replace('\u{0}', ""), self.text());
+        }
+    }
+
+    /// Asserts the characters of the whole last captured frame, one line per row with a final newline
+    pub fn assert_frame(&self, expected: &str) {
+        assert_same("frame", expected, &self.text());
+    }
+
+    /// Asserts the characters of the last captured frame against a golden text file
+    ///
+    /// # Notes
+    /// - Writes the file instead when it is missing or [`UPDATE_GOLDEN_VAR`] is set
+    /// - Windows line endings in the file are read as plain newlines
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use lonely_engine::testing::TestRenderer;
+    /// let mut renderer = TestRenderer::new(80, 24);
+    /// renderer.draw_text(0, 0, "Game Over");
+    /// renderer.capture();
+    /// renderer.assert_frame_matches("tests/golden/game_over.txt");
+    /// ```
+    pub fn assert_frame_matches(&self, golden: impl AsRef<Path>) {
+        assert_golden(golden.as_ref(), &self.text());
+    }
+
+    /// Asserts the last captured frame with its styles against a golden ANSI text file
+    ///
+    /// # Notes
+    /// - Same rules as [`assert_frame_matches`](Self::assert_frame_matches)
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{color::Color, style::Style, testing::TestRenderer};
+    ///
+    /// let golden = std::env::temp_dir().join("lonely_engine_styled_golden.ans");
+    /// # let _ = std::fs::remove_file(&golden);
+    /// let mut renderer = TestRenderer::new(6, 1);
+    /// renderer.draw_styled_text(0, 0, "Ready", &Style::new().fg(Color::GREEN));
+    /// renderer.capture();
+    ///
+    /// // The first run writes the golden file, later runs compare against it
+    /// renderer.assert_styled_frame_matches(&golden);
+    /// renderer.assert_styled_frame_matches(&golden);
+    /// ```
+    pub fn assert_styled_frame_matches(&self, golden: impl AsRef<Path>) {
+        assert_golden(golden.as_ref(), &self.current().to_ansi());
+    }
+
+    fn current(&self) -> &Screenshot {
+        match self.frames.last() {
+            Some(frame) => frame,
+            None => panic!("no frame captured yet, call `capture` first"),
+        }
+    }
+}
+
+impl Deref for TestRenderer {
+    type Target = Renderer;
+
+    fn deref(&self) -> &Renderer {
+        &self.renderer
+    }
+}
+
+impl DerefMut for TestRenderer {
+    fn deref_mut(&mut self) -> &mut Renderer {
+        &mut self.renderer
+    }
+}
+
+/// Compares a frame to its golden file, writing the file when missing or updating
+fn assert_golden(path: &Path, actual: &str) {
+    let update = env::var_os(UPDATE_GOLDEN_VAR).is_some();
+    match fs::read_to_string(path) {
+        Ok(expected) if !update => assert_same(&path.display().to_string(), &expected.replace("\r\n", "\n"), actual),
+        Err(error) if error.kind() != std::io::ErrorKind::NotFound => panic!("could not read golden file {}: {error}", path.display()),
+        _ => {
+            if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+                let _ = fs::create_dir_all(parent);
+            }
+            if let Err(error) = fs::write(path, actual) {
+                panic!("could not write golden file {}: {error}", path.display());
+            }
+        },
+    }
+}
+
+/// Panics with the first differing line and both frames when they differ
+fn assert_same(name: &str, expected: &str, actual: &str) {
+    if expected == actual {
+        return;
+    }
+    let (mut expected_lines, mut actual_lines) = (expected.lines(), actual.lines());
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (Some(wanted), Some(got)) if wanted == got => line += 1,
+            (wanted, got) => {
+                panic!(
+                    "{name} differs at line {line}\n  expected: {:?}\n    actual: {:?}\n--- expected\n{expected}--- actual\n{actual}",
+                    wanted.unwrap_or_default(),
+                    got.unwrap_or_default(),
+                );
+            },
+        }
+    }
+}
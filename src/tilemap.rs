@@ -0,0 +1,117 @@
+//! Static background grids
+//!
+//! A [`Tilemap`] holds level geometry as a grid of styled cells. Handing it to
+//! [`Renderer::set_background`] keeps it on screen across frames without
+//! spawning an object per wall tile.
+//!
+//! [`Renderer::set_background`]: crate::renderer::Renderer::set_background
+
+use crate::{renderer::Cell, screenshot::Screenshot, sprite::{Palette, Sprite}};
+
+/// Rectangular grid of background cells
+///
+/// # Example
+/// ```
+/// # use lonely_engine::tilemap::Tilemap;
+/// let level = Tilemap::from_str("#####\n#...#\n#####");
+/// assert_eq!((level.width(), level.height()), (5, 3));
+/// assert_eq!(level.get(1, 1).unwrap().ch, '.');
+/// assert!(level.get(5, 0).is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tilemap {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Tilemap {
+    /// Creates a blank tilemap
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { width, height, cells: vec![Cell::default(); width * height] }
+    }
+
+    /// Parses rows of characters without colors
+    ///
+    /// # Notes
+    /// - Short rows are padded with blank cells up to the longest row
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(text: &str) -> Self {
+        Self::from_sprite(&Sprite::from_str(text))
+    }
+
+    /// Parses rows of characters colored by a mask grid, like [`Sprite::from_str_with_palette`]
+    pub fn from_str_with_palette(text: &str, mask: &str, palette: &Palette) -> Self {
+        Self::from_sprite(&Sprite::from_str_with_palette(text, mask, palette))
+    }
+
+    /// Copies a sprite into a tilemap, transparent sprite cells becoming blank
+    pub fn from_sprite(sprite: &Sprite) -> Self {
+        let mut tilemap = Self::new(sprite.width, sprite.height);
+        for (x, y, cell) in sprite.cells() {
+            tilemap.set(x, y, Cell { ch: cell.character, fg: cell.fg_color, bg: cell.bg_color, attrs: cell.attributes });
+        }
+        tilemap
+    }
+
+    /// Width in cells
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height in cells
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns the cell at a position, `None` outside the map
+    pub fn get(&self, x: usize, y: usize) -> Option<&Cell> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    /// Replaces the cell at a position
+    ///
+    /// # Notes
+    /// - Positions outside the map are ignored
+    pub fn set(&mut self, x: usize, y: usize, cell: Cell) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = cell;
+        }
+    }
+
+    /// Fills a rectangle with one cell, clipped to the map
+    pub fn fill_rect(&mut self, x: usize, y: usize, width: usize, height: usize, cell: Cell) {
+        for row in y..(y + height).min(self.height) {
+            for column in x..(x + width).min(self.width) {
+                self.cells[row * self.width + column] = cell;
+            }
+        }
+    }
+
+    /// Rows of cells, top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.width.max(1))
+    }
+}
+
+impl From<Sprite> for Tilemap {
+    fn from(sprite: Sprite) -> Self {
+        Self::from_sprite(&sprite)
+    }
+}
+
+impl From<&str> for Tilemap {
+    fn from(text: &str) -> Self {
+        Self::from_str(text)
+    }
+}
+
+impl From<Screenshot> for Tilemap {
+    fn from(screenshot: Screenshot) -> Self {
+        let mut tilemap = Self::new(screenshot.width(), screenshot.height());
+        for (y, row) in screenshot.rows().enumerate() {
+            tilemap.cells[y * tilemap.width..(y + 1) * tilemap.width].copy_from_slice(row);
+        }
+        tilemap
+    }
+}
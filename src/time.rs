@@ -0,0 +1,287 @@
+//! Game clocks, stopwatches, and cooldowns
+//!
+//! The engine keeps one [`GameTime`] advanced by the main loop, available as
+//! [`Engine::time`] and `UpdateContext::time`. It tracks:
+//! - Game time, which follows the time scale and stops while paused
+//! - Real time since the engine started, unscaled and never paused
+//! - Scene time, game time since the last [`Engine::reset_scene_time`]
+//! - Frames presented and updates run
+//!
+//! [`Stopwatch`] and [`Cooldown`] read the game time of the clock last advanced
+//! on the current thread, so they need no `delta_time` plumbing and pause, slow
+//! down, and replay deterministically along with the game.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::time::{Cooldown, GameTime};
+//!
+//! let mut clock = GameTime::new();
+//! let mut dash = Cooldown::new(1.5);
+//! assert!(dash.trigger());
+//! assert!(!dash.ready());
+//!
+//! clock.advance(1.5);
+//! assert!(dash.ready());
+//! ```
+//!
+//! [`Engine::time`]: crate::engine::Engine::time
+//! [`Engine::reset_scene_time`]: crate::engine::Engine::reset_scene_time
+
+use std::{cell::Cell, time::{Duration, Instant}};
+
+thread_local! {
+    /// Game time of the clock last advanced on this thread
+    static NOW: Cell<f64> = const { Cell::new(0.0) };
+}
+
+/// Game seconds of the clock last advanced on this thread, `0.0` before any
+pub fn now() -> f64 {
+    NOW.get()
+}
+
+/// Clocks advanced by the engine's main loop
+///
+/// # Example
+/// ```
+/// use lonely_engine::time::GameTime;
+///
+/// let mut clock = GameTime::new();
+/// clock.set_scale(0.5);
+/// clock.advance(clock.scaled(0.2));
+/// assert!((clock.elapsed() - 0.1).abs() < 1e-6);
+/// assert_eq!(clock.updates(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameTime {
+    elapsed: f64,
+    scene_elapsed: f64,
+    delta: f32,
+    scale: f32,
+    frame: u64,
+    updates: u64,
+    started: Instant,
+}
+
+impl Default for GameTime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameTime {
+    /// Creates a clock at zero with a time scale of `1.0`, real time starting now
+    pub fn new() -> Self {
+        Self { elapsed: 0.0, scene_elapsed: 0.0, delta: 0.0, scale: 1.0, frame: 0, updates: 0, started: Instant::now() }
+    }
+
+    /// Moves game and scene time forward by one update
+    ///
+    /// # Arguments
+    /// * `delta_time` - Game seconds of the update, already scaled
+    ///
+    /// # Notes
+    /// - Also makes this the clock read by [`now`], [`Stopwatch`], and [`Cooldown`] on this thread
+    pub fn advance(&mut self, delta_time: f32) {
+        self.delta = delta_time;
+        self.elapsed += f64::from(delta_time);
+        self.scene_elapsed += f64::from(delta_time);
+        self.updates += 1;
+        NOW.set(self.elapsed);
+    }
+
+    /// Records that a frame was presented
+    pub fn count_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    /// Restarts scene time from zero
+    pub fn reset_scene(&mut self) {
+        self.scene_elapsed = 0.0;
+    }
+
+    /// Sets how fast game time runs, `0.5` for slow motion and `0.0` to freeze
+    ///
+    /// # Notes
+    /// - Negative scales are treated as `0.0`
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale.max(0.0);
+    }
+
+    /// How fast game time runs compared to real time
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    /// Game seconds for a span of real seconds at the current scale
+    pub fn scaled(&self, real_seconds: f32) -> f32 {
+        real_seconds * self.scale
+    }
+
+    /// Game seconds since the engine started, stopped while paused
+    pub fn elapsed(&self) -> f64 {
+        self.elapsed
+    }
+
+    /// Game seconds since the scene time was last reset
+    pub fn scene_elapsed(&self) -> f64 {
+        self.scene_elapsed
+    }
+
+    /// Real time since the clock was created, ignoring scale and pauses
+    pub fn real_elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Game seconds of the last update
+    pub fn delta(&self) -> f32 {
+        self.delta
+    }
+
+    /// Frames presented so far
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Updates run so far, several per frame at a fixed update rate
+    pub fn updates(&self) -> u64 {
+        self.updates
+    }
+}
+
+/// Measures game time, pausable
+///
+/// # Example
+/// ```
+/// use lonely_engine::time::{GameTime, Stopwatch};
+///
+/// let mut clock = GameTime::new();
+/// let mut lap = Stopwatch::new();
+/// clock.advance(2.0);
+/// lap.pause();
+/// clock.advance(5.0);
+/// assert_eq!(lap.elapsed(), 2.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stopwatch {
+    /// Time counted before the last pause
+    banked: f64,
+    /// Game time the stopwatch last started or resumed at, `None` while paused
+    since: Option<f64>,
+}
+
+impl Default for Stopwatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Stopwatch {
+    /// Creates a stopwatch counting from now
+    pub fn new() -> Self {
+        Self { banked: 0.0, since: Some(now()) }
+    }
+
+    /// Creates a paused stopwatch at zero
+    pub fn paused() -> Self {
+        Self { banked: 0.0, since: None }
+    }
+
+    /// Game seconds counted
+    pub fn elapsed(&self) -> f64 {
+        self.banked + self.since.map_or(0.0, |since| now() - since)
+    }
+
+    /// Stops counting, keeping the elapsed time
+    pub fn pause(&mut self) {
+        if let Some(since) = self.since.take() {
+            self.banked += now() - since;
+        }
+    }
+
+    /// Continues counting after a pause
+    pub fn resume(&mut self) {
+        if self.since.is_none() {
+            self.since = Some(now());
+        }
+    }
+
+    /// Returns whether the stopwatch is paused
+    pub fn is_paused(&self) -> bool {
+        self.since.is_none()
+    }
+
+    /// Goes back to zero, keeping the paused or running state
+    pub fn reset(&mut self) {
+        self.banked = 0.0;
+        if self.since.is_some() {
+            self.since = Some(now());
+        }
+    }
+
+    /// Returns the elapsed time and starts counting again from zero
+    pub fn lap(&mut self) -> f64 {
+        let elapsed = self.elapsed();
+        self.reset();
+        elapsed
+    }
+}
+
+/// Limits how often an action can happen, in game seconds
+///
+/// # Notes
+/// - A new cooldown is ready
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cooldown {
+    duration: f64,
+    /// Game time the cooldown was last triggered at
+    triggered: Option<f64>,
+}
+
+impl Cooldown {
+    /// Creates a ready cooldown lasting `seconds` after each trigger
+    pub fn new(seconds: f32) -> Self {
+        Self { duration: f64::from(seconds.max(0.0)), triggered: None }
+    }
+
+    /// Returns whether the cooldown has run out
+    pub fn ready(&self) -> bool {
+        self.remaining() <= 0.0
+    }
+
+    /// Starts the cooldown if it is ready
+    ///
+    /// # Returns
+    /// Whether the action may happen
+    pub fn trigger(&mut self) -> bool {
+        if !self.ready() {
+            return false;
+        }
+        self.triggered = Some(now());
+        true
+    }
+
+    /// Starts the cooldown even when it has not run out
+    pub fn restart(&mut self) {
+        self.triggered = Some(now());
+    }
+
+    /// Makes the cooldown ready immediately
+    pub fn reset(&mut self) {
+        self.triggered = None;
+    }
+
+    /// Game seconds until ready, `0.0` when ready
+    pub fn remaining(&self) -> f64 {
+        self.triggered.map_or(0.0, |triggered| (triggered + self.duration - now()).max(0.0))
+    }
+
+    /// Fraction of the cooldown elapsed, `1.0` when ready, for drawing cooldown bars
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 { 1.0 } else { (1.0 - self.remaining() / self.duration) as f32 }
+    }
+
+    /// Seconds the cooldown lasts after a trigger
+    pub fn duration(&self) -> f32 {
+        self.duration as f32
+    }
+}
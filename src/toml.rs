@@ -0,0 +1,347 @@
+//! TOML documents for engine config files
+//!
+//! Reads files with the `toml` crate into the flat shape key bindings,
+//! settings, and save data use:
+//! - Tables by name, nested tables named by their dotted path (`"player.keys"`)
+//! - Strings, integers, floats, booleans, and arrays as [`TomlValue`]s
+//! - Dates kept as their text
+//!
+//! Arrays of tables are not supported. Writing keeps tables and keys in the
+//! order they were set, so saved files stay stable.
+
+use std::fmt;
+
+/// A TOML value
+#[derive(Debug, Clone, PartialEq)]
+pub enum TomlValue {
+    /// Quoted string
+    String(String),
+    /// 64-bit signed integer
+    Integer(i64),
+    /// 64-bit float
+    Float(f64),
+    /// `true` or `false`
+    Boolean(bool),
+    /// Array of values
+    Array(Vec<TomlValue>),
+}
+
+impl TomlValue {
+    /// Returns the string content of a `String` value
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            TomlValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns an `Integer` value
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            TomlValue::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Returns a `Float` value, widening integers
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            TomlValue::Float(f) => Some(*f),
+            TomlValue::Integer(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    /// Returns a `Boolean` value
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            TomlValue::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Returns the items of an `Array` value
+    pub fn as_array(&self) -> Option<&[TomlValue]> {
+        match self {
+            TomlValue::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TomlValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TomlValue::String(s) => write_quoted(f, s),
+            TomlValue::Integer(i) => write!(f, "{}", i),
+            TomlValue::Float(x) if x.is_finite() && x.fract() == 0.0 => write!(f, "{:.1}", x),
+            TomlValue::Float(x) => write!(f, "{}", x),
+            TomlValue::Boolean(b) => write!(f, "{}", b),
+            TomlValue::Array(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_str("]")
+            },
+        }
+    }
+}
+
+impl From<&str> for TomlValue {
+    fn from(value: &str) -> Self {
+        TomlValue::String(value.to_string())
+    }
+}
+
+impl From<String> for TomlValue {
+    fn from(value: String) -> Self {
+        TomlValue::String(value)
+    }
+}
+
+impl From<i64> for TomlValue {
+    fn from(value: i64) -> Self {
+        TomlValue::Integer(value)
+    }
+}
+
+impl From<i32> for TomlValue {
+    fn from(value: i32) -> Self {
+        TomlValue::Integer(value.into())
+    }
+}
+
+impl From<f32> for TomlValue {
+    fn from(value: f32) -> Self {
+        TomlValue::Float(value.into())
+    }
+}
+
+impl From<f64> for TomlValue {
+    fn from(value: f64) -> Self {
+        TomlValue::Float(value)
+    }
+}
+
+impl From<bool> for TomlValue {
+    fn from(value: bool) -> Self {
+        TomlValue::Boolean(value)
+    }
+}
+
+/// Error produced while parsing TOML text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TomlError {
+    /// 1-based line number of the problem
+    pub line: usize,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl fmt::Display for TomlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for TomlError {}
+
+/// Ordered collection of tables and their key/value pairs
+///
+/// Keys before the first `[table]` header live in the root table, named `""`.
+/// Tables and keys keep their file order so saved files stay stable.
+///
+/// # Example
+/// ```
+/// # use lonely_engine::toml::{TomlDocument, TomlValue};
+/// let doc = TomlDocument::parse("[audio]\nvolume = 0.8\nmuted = false").unwrap();
+/// assert_eq!(doc.get("audio", "volume").and_then(TomlValue::as_float), Some(0.8));
+///
+/// let mut doc = TomlDocument::new();
+/// doc.set("video", "fps", 30);
+/// assert_eq!(doc.to_string(), "[video]\nfps = 30\n");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TomlDocument {
+    tables: Vec<(String, Vec<(String, TomlValue)>)>,
+}
+
+impl TomlDocument {
+    /// Creates an empty document
+    pub fn new() -> Self {
+        Self { tables: Vec::new() }
+    }
+
+    /// Parses TOML text with the `toml` crate
+    ///
+    /// # Notes
+    /// - Nested tables are named by their dotted path, `[player.keys]` is table `"player.keys"`
+    /// - Dates and times are kept as their text
+    /// - Arrays of tables and tables inside arrays are rejected
+    ///
+    /// # Example
+    /// ```
+    /// # use lonely_engine::toml::{TomlDocument, TomlValue};
+    /// let doc = TomlDocument::parse("[quests.\"lost ring\"]\nstage = 2 # Found the well").unwrap();
+    /// assert_eq!(doc.get("quests.lost ring", "stage"), Some(&TomlValue::Integer(2)));
+    /// assert_eq!(doc.to_string(), "[quests.\"lost ring\"]\nstage = 2\n");
+    ///
+    /// let error = TomlDocument::parse("[audio]\nvolume = = 0.8").unwrap_err();
+    /// assert_eq!(error.line, 2);
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, TomlError> {
+        let root: ::toml::Table = text.parse().map_err(|error: ::toml::de::Error| TomlError {
+            line: error.span().map_or(1, |span| line_at(text, span.start)),
+            message: error.message().to_string(),
+        })?;
+        let mut doc = Self::new();
+        doc.add_table(text, String::new(), root)?;
+        Ok(doc)
+    }
+
+    /// Looks up a value
+    pub fn get(&self, table: &str, key: &str) -> Option<&TomlValue> {
+        self.table(table)?.iter().find(|(k, _)| k == key).map(|(_, value)| value)
+    }
+
+    /// Sets a value, creating the table if needed and keeping the key's position when it exists
+    pub fn set(&mut self, table: &str, key: &str, value: impl Into<TomlValue>) {
+        let value = value.into();
+        let entries = self.table_entry(table);
+        match entries.iter_mut().find(|(k, _)| k == key) {
+            Some((_, existing)) => *existing = value,
+            None => entries.push((key.to_string(), value)),
+        }
+    }
+
+    /// Returns the key/value pairs of a table in file order
+    pub fn table(&self, name: &str) -> Option<&[(String, TomlValue)]> {
+        self.tables.iter().find(|(table, _)| table == name).map(|(_, entries)| entries.as_slice())
+    }
+
+    /// Returns table names in file order
+    pub fn table_names(&self) -> impl Iterator<Item = &str> {
+        self.tables.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Adds the values of a parsed table and, after them, its nested tables
+    fn add_table(&mut self, text: &str, name: String, table: ::toml::Table) -> Result<(), TomlError> {
+        let mut nested = Vec::new();
+        let empty = table.is_empty();
+        for (key, value) in table {
+            match value {
+                ::toml::Value::Table(inner) if name.is_empty() => nested.push((key, inner)),
+                ::toml::Value::Table(inner) => nested.push((format!("{}.{}", name, key), inner)),
+                value => {
+                    let value = convert(value).ok_or_else(|| TomlError {
+                        line: text.find(key.as_str()).map_or(1, |at| line_at(text, at)),
+                        message: format!("arrays of tables are not supported, found one in `{}`", key),
+                    })?;
+                    self.table_entry(&name).push((key, value));
+                },
+            }
+        }
+        // Headers of empty tables still create them, parents only holding tables do not
+        if empty {
+            self.table_entry(&name);
+        }
+        for (name, inner) in nested {
+            self.add_table(text, name, inner)?;
+        }
+        Ok(())
+    }
+
+    fn table_entry(&mut self, name: &str) -> &mut Vec<(String, TomlValue)> {
+        let index = match self.tables.iter().position(|(table, _)| table == name) {
+            Some(index) => index,
+            None => {
+                self.tables.push((name.to_string(), Vec::new()));
+                self.tables.len() - 1
+            },
+        };
+        &mut self.tables[index].1
+    }
+}
+
+impl fmt::Display for TomlDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        // Root keys must come before any header
+        let ordered = self.tables.iter().filter(|(name, _)| name.is_empty())
+            .chain(self.tables.iter().filter(|(name, _)| !name.is_empty()));
+
+        for (name, entries) in ordered {
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+
+            if !name.is_empty() {
+                f.write_str("[")?;
+                for (i, part) in name.split('.').enumerate() {
+                    if i > 0 {
+                        f.write_str(".")?;
+                    }
+                    write_key(f, part)?;
+                }
+                writeln!(f, "]")?;
+            }
+            for (key, value) in entries {
+                write_key(f, key)?;
+                writeln!(f, " = {}", value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Value read by the `toml` crate, `None` for tables inside arrays
+fn convert(value: ::toml::Value) -> Option<TomlValue> {
+    Some(match value {
+        ::toml::Value::String(text) => TomlValue::String(text),
+        ::toml::Value::Integer(number) => TomlValue::Integer(number),
+        ::toml::Value::Float(number) => TomlValue::Float(number),
+        ::toml::Value::Boolean(flag) => TomlValue::Boolean(flag),
+        ::toml::Value::Datetime(datetime) => TomlValue::String(datetime.to_string()),
+        ::toml::Value::Array(items) => TomlValue::Array(items.into_iter().map(convert).collect::<Option<_>>()?),
+        ::toml::Value::Table(_) => return None,
+    })
+}
+
+/// 1-based line of a byte offset
+fn line_at(text: &str, offset: usize) -> usize {
+    text.as_bytes()[..offset.min(text.len())].iter().filter(|byte| **byte == b'\n').count() + 1
+}
+
+fn is_bare_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+fn write_key(f: &mut fmt::Formatter<'_>, key: &str) -> fmt::Result {
+    if key.chars().all(is_bare_key_char) && !key.is_empty() {
+        f.write_str(key)
+    } else {
+        write_quoted(f, key)
+    }
+}
+
+fn write_quoted(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\t' => f.write_str("\\t")?,
+            '\r' => f.write_str("\\r")?,
+            c if c.is_control() => write!(f, "\\u{:04X}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    f.write_str("\"")
+}
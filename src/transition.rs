@@ -0,0 +1,179 @@
+//! Full-screen transition effects between scenes
+//!
+//! Transitions are applied by the engine to the composed frame, after objects
+//! are drawn and before it is presented. Provides:
+//! - [`TransitionKind`]: fade to black, wipe, and dissolve effects
+//! - [`TransitionDirection`]: covering the scene (out) or revealing it (in)
+//! - [`Transition`]: an effect driven over a fixed number of frames
+//!
+//! A typical scene switch runs an `Out` transition, swaps objects when
+//! `EngineEvent::TransitionFinished` arrives, then runs an `In` transition.
+
+use crate::{renderer::Renderer, rng::Rng};
+
+/// Visual style of a transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionKind {
+    /// Dims every cell, then greys it out, then blanks it
+    FadeToBlack,
+    /// Sweeps a blank edge across the screen from left to right
+    Wipe,
+    /// Blanks cells in a random order
+    Dissolve,
+}
+
+/// Whether a transition hides or reveals the scene
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionDirection {
+    /// Scene → black. The screen stays black once finished until another transition starts
+    Out,
+    /// Black → scene
+    In,
+}
+
+/// A screen transition effect running over a number of frames
+///
+/// # Example
+/// ```
+/// use lonely_engine::{engine::Engine, transition::Transition};
+///
+/// let mut engine = Engine::new(80, 24);
+/// // Fade out over one second at ~30 FPS
+/// engine.start_transition(Transition::fade_out(30));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Transition {
+    kind: TransitionKind,
+    direction: TransitionDirection,
+    frames: usize,
+    frame: usize,
+    /// Cell reveal order for dissolves, generated on first use
+    dissolve_order: Vec<usize>,
+}
+
+impl Transition {
+    /// Creates a transition
+    ///
+    /// # Arguments
+    /// * `kind` - Visual effect
+    /// * `direction` - Hide (`Out`) or reveal (`In`) the scene
+    /// * `frames` - Number of frames the effect lasts (at least 1)
+    pub fn new(kind: TransitionKind, direction: TransitionDirection, frames: usize) -> Self {
+        Self {
+            kind,
+            direction,
+            frames: frames.max(1),
+            frame: 0,
+            dissolve_order: Vec::new(),
+        }
+    }
+
+    /// Fades the scene to black
+    pub fn fade_out(frames: usize) -> Self {
+        Self::new(TransitionKind::FadeToBlack, TransitionDirection::Out, frames)
+    }
+
+    /// Fades the scene in from black
+    pub fn fade_in(frames: usize) -> Self {
+        Self::new(TransitionKind::FadeToBlack, TransitionDirection::In, frames)
+    }
+
+    /// Wipes the scene away from left to right
+    pub fn wipe_out(frames: usize) -> Self {
+        Self::new(TransitionKind::Wipe, TransitionDirection::Out, frames)
+    }
+
+    /// Wipes the scene in from left to right
+    pub fn wipe_in(frames: usize) -> Self {
+        Self::new(TransitionKind::Wipe, TransitionDirection::In, frames)
+    }
+
+    /// Dissolves the scene away cell by cell
+    pub fn dissolve_out(frames: usize) -> Self {
+        Self::new(TransitionKind::Dissolve, TransitionDirection::Out, frames)
+    }
+
+    /// Dissolves the scene in cell by cell
+    pub fn dissolve_in(frames: usize) -> Self {
+        Self::new(TransitionKind::Dissolve, TransitionDirection::In, frames)
+    }
+
+    /// Visual effect of this transition
+    pub fn kind(&self) -> TransitionKind {
+        self.kind
+    }
+
+    /// Direction of this transition
+    pub fn direction(&self) -> TransitionDirection {
+        self.direction
+    }
+
+    /// Returns whether every frame of the effect has been shown
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.frames
+    }
+
+    /// Fraction of the screen covered on the next applied frame (0.0 = scene visible, 1.0 = black)
+    pub fn coverage(&self) -> f32 {
+        let progress = ((self.frame + 1) as f32 / self.frames as f32).min(1.0);
+        match self.direction {
+            TransitionDirection::Out => progress,
+            TransitionDirection::In => 1.0 - progress,
+        }
+    }
+
+    /// Applies the effect to the renderer's back buffer and advances one frame
+    ///
+    /// # Arguments
+    /// * `renderer` - Renderer holding the composed frame
+    /// * `rng` - Random source used for dissolve ordering
+    pub fn apply(&mut self, renderer: &mut Renderer, rng: &mut Rng) {
+        let width = renderer.get_width();
+        let height = renderer.get_height();
+        let coverage = self.coverage();
+
+        match self.kind {
+            TransitionKind::FadeToBlack => {
+                for y in 0..height {
+                    for x in 0..width {
+                        match coverage {
+                            c if c >= 1.0 => renderer.blank_cell(x, y),
+                            c if c >= 0.66 => renderer.dim_cell(x, y, true),
+                            c if c >= 0.33 => renderer.dim_cell(x, y, false),
+                            _ => {},
+                        }
+                    }
+                }
+            },
+            TransitionKind::Wipe => {
+                // The blank region always sits behind the sweeping edge
+                let covered = ((coverage * width as f32).round() as usize).min(width);
+                let columns = match self.direction {
+                    TransitionDirection::Out => 0..covered,
+                    TransitionDirection::In => width - covered..width,
+                };
+                for y in 0..height {
+                    for x in columns.clone() {
+                        renderer.blank_cell(x, y);
+                    }
+                }
+            },
+            TransitionKind::Dissolve => {
+                let cell_count = width * height;
+                if self.dissolve_order.len() != cell_count {
+                    self.dissolve_order = (0..cell_count).collect();
+                    rng.shuffle(&mut self.dissolve_order);
+                }
+
+                let covered = (coverage * cell_count as f32).round() as usize;
+                for &cell in &self.dissolve_order[..covered.min(cell_count)] {
+                    renderer.blank_cell(cell % width, cell / width);
+                }
+            },
+        }
+
+        if self.frame < self.frames {
+            self.frame += 1;
+        }
+    }
+}
@@ -0,0 +1,400 @@
+//! Energy-based turn scheduling for turn-based games
+//!
+//! Actors accumulate energy according to their speed and act once they reach
+//! [`ACTION_COST`]. A speed-200 actor therefore acts twice for every turn of a
+//! speed-100 actor. Pair [`TurnScheduler`] with [`EngineMode::TurnBased`] so the
+//! world only advances when the player acts.
+//!
+//! Besides actors, the scheduler keeps:
+//! - Delayed actions, one-shot entries taking a single turn after a number of
+//!   normal-speed turns, such as a lit fuse or a spell landing later
+//! - Multi-turn actions, which keep their actor busy for several of its own
+//!   turns before it is handed the turn that completes them
+//!
+//! The engine keeps one in `Engine::turns`, keyed by object index. It emits
+//! `TurnStarted` when an actor's turn begins and `TurnEnded` once the actor's
+//! `EngineCommand::EndTurn` or `EngineCommand::BeginLongAction` is applied. In
+//! turn-based mode the world keeps updating through other actors' turns and
+//! waits for input on the player's.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{
+//!     engine::{Engine, EngineCommand, EngineMode},
+//!     event::{EngineEvent, EventKind},
+//!     game_object::GameObject,
+//!     turn::{ACTION_COST, NORMAL_SPEED},
+//! };
+//!
+//! let mut engine = Engine::new(80, 24);
+//! engine.set_mode(EngineMode::TurnBased);
+//! engine.add_object(GameObject::new(5, 5, '@'));
+//! engine.add_object(GameObject::new(9, 5, 'g'));
+//! engine.turns.add_actor(0, NORMAL_SPEED);
+//! engine.turns.add_actor(1, NORMAL_SPEED * 2);
+//! engine.turns.set_player(Some(0));
+//!
+//! // Monsters act as soon as their turn starts, the player's input ends theirs
+//! engine.event_bus.subscribe_filtered_with_commands(EventKind::TurnStarted, |event, commands| {
+//!     if let EngineEvent::TurnStarted(actor) = event && *actor != 0 {
+//!         commands.push(EngineCommand::MoveObject(*actor, -1, 0));
+//!         commands.push(EngineCommand::EndTurn(*actor, ACTION_COST));
+//!     }
+//! });
+//! ```
+//!
+//! [`EngineMode::TurnBased`]: crate::engine::EngineMode::TurnBased
+
+/// Energy an actor spends on a standard action
+pub const ACTION_COST: i32 = 100;
+
+/// Speed of an average actor
+pub const NORMAL_SPEED: i32 = 100;
+
+/// Actor registered with the scheduler
+#[derive(Debug, Clone, PartialEq)]
+struct Actor<A> {
+    id: A,
+    speed: i32,
+    energy: i32,
+    /// Removed once its turn is over, for delayed actions
+    once: bool,
+    /// Turns skipped before a multi-turn action completes
+    busy: u32,
+    /// Whether the next turn completes a multi-turn action
+    completing: bool,
+}
+
+impl<A> Actor<A> {
+    fn new(id: A, speed: i32, energy: i32, once: bool) -> Self {
+        Self { id, speed: speed.max(1), energy, once, busy: 0, completing: false }
+    }
+}
+
+/// Decides which actor acts next based on speed and accumulated energy
+///
+/// # Example
+/// ```
+/// use lonely_engine::turn::{TurnScheduler, NORMAL_SPEED};
+///
+/// let mut turns = TurnScheduler::new();
+/// turns.add_actor("player", NORMAL_SPEED);
+/// turns.add_actor("bat", NORMAL_SPEED * 2);
+/// turns.add_actor("slime", NORMAL_SPEED / 2);
+///
+/// // After the player's move, the bat acts twice and the slime every other turn
+/// let others = turns.run_turn(&"player");
+/// assert_eq!(others.iter().filter(|id| **id == "bat").count(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TurnScheduler<A> {
+    actors: Vec<Actor<A>>,
+    /// Actor whose turn began and has not ended
+    current: Option<A>,
+    /// Actor waiting for input on its turns
+    player: Option<A>,
+}
+
+impl<A: Clone + PartialEq> Default for TurnScheduler<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Clone + PartialEq> TurnScheduler<A> {
+    /// Creates an empty scheduler
+    pub fn new() -> Self {
+        Self { actors: Vec::new(), current: None, player: None }
+    }
+
+    /// Registers an actor
+    ///
+    /// # Arguments
+    /// * `id` - Identifier returned when the actor's turn comes up
+    /// * `speed` - Energy gained per tick, [`NORMAL_SPEED`] for average actors
+    ///
+    /// # Notes
+    /// - Actors start ready to act, earlier registrations win ties
+    pub fn add_actor(&mut self, id: A, speed: i32) {
+        self.actors.push(Actor::new(id, speed, ACTION_COST, false));
+    }
+
+    /// Schedules a delayed action taking one turn after a number of normal-speed turns
+    ///
+    /// # Arguments
+    /// * `id` - Identifier returned when the action's turn comes up
+    /// * `turns` - Normal-speed turns to wait, `0` to take the next turn
+    ///
+    /// # Notes
+    /// - The entry is removed once it [`spend`](Self::spend)s its turn
+    /// - Actors registered earlier act first when they are ready on the same tick
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::turn::{TurnScheduler, ACTION_COST, NORMAL_SPEED};
+    ///
+    /// let mut turns = TurnScheduler::new();
+    /// turns.add_actor("player", NORMAL_SPEED);
+    /// turns.schedule("bomb", 2);
+    ///
+    /// let mut order = Vec::new();
+    /// for _ in 0..4 {
+    ///     let next = turns.next_actor().unwrap();
+    ///     turns.spend(&next, ACTION_COST);
+    ///     order.push(next);
+    /// }
+    /// assert_eq!(order, ["player", "player", "player", "bomb"]);
+    /// assert_eq!(turns.len(), 1);
+    /// ```
+    pub fn schedule(&mut self, id: A, turns: u32) {
+        let wait = NORMAL_SPEED.saturating_mul(turns.min(i32::MAX as u32) as i32);
+        self.actors.push(Actor::new(id, NORMAL_SPEED, ACTION_COST.saturating_sub(wait), true));
+    }
+
+    /// Unregisters an actor (e.g. when it dies) or cancels a delayed action
+    pub fn remove_actor(&mut self, id: &A) {
+        self.actors.retain(|actor| actor.id != *id);
+        if self.current.as_ref() == Some(id) {
+            self.current = None;
+        }
+    }
+
+    /// Returns whether an actor or delayed action is registered
+    pub fn contains(&self, id: &A) -> bool {
+        self.actors.iter().any(|actor| actor.id == *id)
+    }
+
+    /// Changes an actor's speed, for hasted or slowed actors
+    pub fn set_speed(&mut self, id: &A, speed: i32) {
+        if let Some(actor) = self.actor_mut(id) {
+            actor.speed = speed.max(1);
+        }
+    }
+
+    /// Returns the current energy of an actor
+    pub fn energy(&self, id: &A) -> Option<i32> {
+        self.actor(id).map(|actor| actor.energy)
+    }
+
+    /// Returns whether an actor is in the middle of a multi-turn action
+    pub fn is_busy(&self, id: &A) -> bool {
+        self.actor(id).is_some_and(|actor| actor.busy > 0 || actor.completing)
+    }
+
+    /// Returns whether the actor's coming turn completes a multi-turn action
+    ///
+    /// # Notes
+    /// - Asked when the actor's turn comes up, the game resolves the action and the actor then acts as usual
+    pub fn completes_action(&self, id: &A) -> bool {
+        self.actor(id).is_some_and(|actor| actor.completing && actor.busy == 0)
+    }
+
+    /// Number of registered actors
+    pub fn len(&self) -> usize {
+        self.actors.len()
+    }
+
+    /// Returns whether no actors are registered
+    pub fn is_empty(&self) -> bool {
+        self.actors.is_empty()
+    }
+
+    /// Returns the actor that acts next, advancing time until someone is ready
+    ///
+    /// # Notes
+    /// - The returned actor must [`spend`](Self::spend) energy or it will be picked again
+    /// - Turns of actors busy with a multi-turn action are spent without being returned
+    pub fn next_actor(&mut self) -> Option<A> {
+        if self.actors.is_empty() {
+            return None;
+        }
+
+        loop {
+            let ready = self.actors
+                .iter()
+                .enumerate()
+                .filter(|(_, actor)| actor.energy >= ACTION_COST)
+                .max_by(|(a_index, a), (b_index, b)| a.energy.cmp(&b.energy).then(b_index.cmp(a_index)))
+                .map(|(index, _)| index);
+            if let Some(index) = ready {
+                let actor = &mut self.actors[index];
+                if actor.busy > 0 {
+                    actor.busy -= 1;
+                    actor.energy -= ACTION_COST;
+                    continue;
+                }
+                return Some(actor.id.clone());
+            }
+
+            for actor in &mut self.actors {
+                actor.energy += actor.speed;
+            }
+        }
+    }
+
+    /// Deducts energy after an actor acts
+    ///
+    /// # Arguments
+    /// * `id` - Actor that acted
+    /// * `cost` - Energy cost, [`ACTION_COST`] for standard actions
+    pub fn spend(&mut self, id: &A, cost: i32) {
+        if let Some(actor) = self.actor_mut(id) {
+            actor.energy -= cost;
+            actor.completing = false;
+            if actor.once {
+                self.remove_actor(id);
+            }
+        }
+    }
+
+    /// Spends a standard action starting a multi-turn action
+    ///
+    /// # Arguments
+    /// * `id` - Actor that acted
+    /// * `turns` - Turns of the actor the action takes, including this one
+    ///
+    /// # Notes
+    /// - The actor's turns in between are skipped, then its turn comes up with
+    ///   [`completes_action`](Self::completes_action) returning `true`
+    /// - Faster actors finish sooner, the action lasts turns of the actor and not of the world
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::turn::{TurnScheduler, ACTION_COST, NORMAL_SPEED};
+    ///
+    /// let mut turns = TurnScheduler::new();
+    /// turns.add_actor("player", NORMAL_SPEED);
+    /// turns.add_actor("smith", NORMAL_SPEED);
+    ///
+    /// // The smith forges for three turns while the player keeps acting
+    /// turns.spend(&"player", ACTION_COST);
+    /// turns.spend_over(&"smith", 3);
+    /// for _ in 0..3 {
+    ///     assert_eq!(turns.next_actor(), Some("player"));
+    ///     turns.spend(&"player", ACTION_COST);
+    /// }
+    /// assert_eq!(turns.next_actor(), Some("smith"));
+    /// assert!(turns.completes_action(&"smith"));
+    /// ```
+    pub fn spend_over(&mut self, id: &A, turns: u32) {
+        if let Some(actor) = self.actor_mut(id) {
+            actor.energy -= ACTION_COST;
+            actor.busy = turns.saturating_sub(1);
+            actor.completing = turns > 0;
+        }
+    }
+
+    /// Starts the next turn, advancing time until an actor is ready
+    ///
+    /// # Returns
+    /// The actor whose turn began, or the one still holding the turn when [`end_turn`](Self::end_turn) was not called
+    pub fn begin_turn(&mut self) -> Option<A> {
+        if self.current.is_none() {
+            self.current = self.next_actor();
+        }
+        self.current.clone()
+    }
+
+    /// Actor whose turn began and has not ended
+    pub fn current(&self) -> Option<&A> {
+        self.current.as_ref()
+    }
+
+    /// Ends the current turn, spending energy
+    ///
+    /// # Arguments
+    /// * `cost` - Energy cost, [`ACTION_COST`] for standard actions
+    ///
+    /// # Returns
+    /// The actor whose turn ended
+    pub fn end_turn(&mut self, cost: i32) -> Option<A> {
+        let id = self.current.take()?;
+        self.spend(&id, cost);
+        Some(id)
+    }
+
+    /// Marks the actor controlled by input, see [`waiting_for_input`](Self::waiting_for_input)
+    pub fn set_player(&mut self, id: Option<A>) {
+        self.player = id;
+    }
+
+    /// Actor controlled by input
+    pub fn player(&self) -> Option<&A> {
+        self.player.as_ref()
+    }
+
+    /// Returns whether the current turn belongs to the player, so the world waits for input
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::turn::{TurnScheduler, NORMAL_SPEED};
+    ///
+    /// let mut turns = TurnScheduler::new();
+    /// turns.add_actor(0, NORMAL_SPEED);
+    /// turns.add_actor(1, NORMAL_SPEED);
+    /// turns.set_player(Some(1));
+    ///
+    /// assert_eq!(turns.begin_turn(), Some(0));
+    /// assert!(!turns.waiting_for_input());
+    /// turns.end_turn(100);
+    /// assert_eq!(turns.begin_turn(), Some(1));
+    /// assert!(turns.waiting_for_input());
+    /// ```
+    pub fn waiting_for_input(&self) -> bool {
+        self.current.is_some() && self.current == self.player
+    }
+
+    /// Spends the player's action and collects every other actor's turn until the player is ready again
+    ///
+    /// # Arguments
+    /// * `player` - Actor controlled by input
+    ///
+    /// # Returns
+    /// Actors to act, in order; each has already spent [`ACTION_COST`]
+    pub fn run_turn(&mut self, player: &A) -> Vec<A> {
+        if self.energy(player).is_none() {
+            return Vec::new();
+        }
+        self.spend(player, ACTION_COST);
+
+        let mut acted = Vec::new();
+        while let Some(next) = self.next_actor() {
+            if next == *player {
+                break;
+            }
+            self.spend(&next, ACTION_COST);
+            acted.push(next);
+        }
+
+        acted
+    }
+
+    fn actor(&self, id: &A) -> Option<&Actor<A>> {
+        self.actors.iter().find(|actor| actor.id == *id)
+    }
+
+    fn actor_mut(&mut self, id: &A) -> Option<&mut Actor<A>> {
+        self.actors.iter_mut().find(|actor| actor.id == *id)
+    }
+}
+
+impl TurnScheduler<usize> {
+    /// Follows the removal of the object at `index` when actors are object indices
+    ///
+    /// # Notes
+    /// - The object's actor and delayed actions are dropped, higher indices shift down by one
+    pub fn object_removed(&mut self, index: usize) {
+        self.actors.retain(|actor| actor.id != index);
+        if self.current == Some(index) {
+            self.current = None;
+        }
+        if self.player == Some(index) {
+            self.player = None;
+        }
+        for id in self.actors.iter_mut().map(|actor| &mut actor.id).chain(self.current.as_mut()).chain(self.player.as_mut()) {
+            if *id > index {
+                *id -= 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Validation of commands before the engine applies them
+//!
+//! Commands come from updatables, scripts, and the network, and a garbage one
+//! such as a move by `i32::MAX` or a despawn of an object that is long gone
+//! would otherwise overflow or act on whichever object took its index. The
+//! engine checks every command against its [`Validation`] first and drops the
+//! ones naming missing objects, cells outside the world, moves further than
+//! the world is wide, or numbers that are NaN, infinite, or negative where
+//! that makes no sense.
+//!
+//! What happens to a dropped command depends on the [`Strictness`]: a warning
+//! in the diagnostics panel, an `EngineEvent::CommandRejected` as well, or a
+//! panic in debug builds to catch the mistake where it happens.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{
+//!     engine::{Engine, EngineCommand},
+//!     game_object::GameObject,
+//!     validation::{CommandError, Strictness, Validation},
+//! };
+//!
+//! let mut engine = Engine::new(80, 24);
+//! engine.set_validation(Validation::new().strictness(Strictness::Event).max_delta(4));
+//! engine.add_object(GameObject::new(5, 5, '@'));
+//!
+//! let validation = engine.validation();
+//! let bounds = (80, 24);
+//! assert_eq!(validation.check(&EngineCommand::MoveObject(0, 1, 0), &engine.objects, bounds), Ok(()));
+//! assert_eq!(validation.check(&EngineCommand::MoveObject(3, 1, 0), &engine.objects, bounds), Err(CommandError::DeadObject(3)));
+//! assert_eq!(validation.check(&EngineCommand::MoveObject(0, 9, 0), &engine.objects, bounds), Err(CommandError::InvalidDelta(9, 0)));
+//! ```
+
+use std::fmt;
+use crate::{engine::EngineCommand, game_object::GameObject};
+
+/// What happens to a command that fails validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash)]
+pub enum Strictness {
+    /// Drop the command and list the problem in the diagnostics panel
+    #[default]
+    Warn,
+    /// Drop the command, list the problem, and emit `EngineEvent::CommandRejected`
+    Event,
+    /// Panic in debug builds, behave like [`Strictness::Event`] in release builds
+    Panic,
+}
+
+/// Why a command was rejected
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CommandError {
+    /// The command names an object index past the end of the object list
+    DeadObject(usize),
+    /// The command spawns an object at a cell outside the world
+    OutOfBounds(usize, usize),
+    /// The command moves further than allowed in one step, contains the delta
+    InvalidDelta(i32, i32),
+    /// A number of the command is NaN, infinite, or out of range, contains what it stands for
+    InvalidNumber(&'static str),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::DeadObject(index) => write!(f, "object {index} does not exist"),
+            CommandError::OutOfBounds(x, y) => write!(f, "({x}, {y}) is outside the world"),
+            CommandError::InvalidDelta(dx, dy) => write!(f, "move by ({dx}, {dy}) is too far"),
+            CommandError::InvalidNumber(number) => write!(f, "invalid {number}"),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+/// Checks run on every command the engine applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Validation {
+    /// What happens to a rejected command
+    pub strictness: Strictness,
+    /// Largest move along either axis in one command, `None` to allow moves as far as the world is wide or high
+    pub max_delta: Option<u32>,
+}
+
+impl Validation {
+    /// Creates validation warning about rejected commands, allowing moves across the whole world
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets what happens to a rejected command
+    pub fn strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    /// Caps how far one command moves an object along either axis
+    pub fn max_delta(mut self, max: u32) -> Self {
+        self.max_delta = Some(max);
+        self
+    }
+
+    /// Checks a command against the world it is about to be applied to
+    ///
+    /// # Arguments
+    /// * `command` - Command to check
+    /// * `objects` - Every object in the world, by index
+    /// * `bounds` - Width and height of the world in cells
+    ///
+    /// # Returns
+    /// The first problem found
+    pub fn check(&self, command: &EngineCommand, objects: &[GameObject], bounds: (usize, usize)) -> Result<(), CommandError> {
+        if let Some(index) = command.target().filter(|&index| index >= objects.len()) {
+            return Err(CommandError::DeadObject(index));
+        }
+        match command {
+            EngineCommand::SpawnObject(obj) if obj.x >= bounds.0 || obj.y >= bounds.1 => Err(CommandError::OutOfBounds(obj.x, obj.y)),
+            EngineCommand::MoveObject(_, dx, dy) | EngineCommand::MoveGroup(_, dx, dy) => self.check_delta(*dx, *dy, bounds),
+            EngineCommand::SetStat(_, _, value) => finite("stat value", *value),
+            EngineCommand::SetTimeScale(scale) => non_negative("time scale", *scale),
+            EngineCommand::ShakeCamera(intensity, duration) => {
+                non_negative("shake intensity", *intensity)?;
+                non_negative("shake duration", *duration)
+            },
+            EngineCommand::CameraFollow(_, smoothing) => non_negative("follow smoothing", *smoothing),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_delta(&self, dx: i32, dy: i32, bounds: (usize, usize)) -> Result<(), CommandError> {
+        let (max_x, max_y) = match self.max_delta {
+            Some(max) => (max as u64, max as u64),
+            None => (bounds.0 as u64, bounds.1 as u64),
+        };
+        if dx.unsigned_abs() as u64 > max_x || dy.unsigned_abs() as u64 > max_y {
+            return Err(CommandError::InvalidDelta(dx, dy));
+        }
+        Ok(())
+    }
+}
+
+fn finite(number: &'static str, value: f32) -> Result<(), CommandError> {
+    if value.is_finite() { Ok(()) } else { Err(CommandError::InvalidNumber(number)) }
+}
+
+fn non_negative(number: &'static str, value: f32) -> Result<(), CommandError> {
+    if value.is_finite() && value >= 0.0 { Ok(()) } else { Err(CommandError::InvalidNumber(number)) }
+}
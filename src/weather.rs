@@ -0,0 +1,309 @@
+//! Full-screen ambient effects
+//!
+//! [`Weather`] adds rain, snow, twinkling stars, or drifting fog over the whole
+//! screen. Set one per scene with [`Engine::set_weather`] or
+//! `EngineCommand::SetWeather`, and tune it with a density and a wind.
+//!
+//! # Notes
+//! - Rain, snow, and stars are drawn only on empty cells, so they stay behind objects
+//! - Fog dims whatever is drawn and fills empty cells with patches of mist
+//! - Weather uses its own random number generator, so deterministic games are unaffected
+//! - Like other effects, weather stands still while the game is paused
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{engine::Engine, weather::Weather};
+//!
+//! let mut engine = Engine::new(80, 24);
+//! engine.set_weather(Some(Weather::rain().density(0.8).wind(-6.0)));
+//! ```
+//!
+//! [`Engine::set_weather`]: crate::engine::Engine::set_weather
+
+use std::hash::{Hash, Hasher};
+use crate::{color::Color, hash::StableHasher, renderer::{Cell, Renderer}, rng::Rng, style::Style};
+
+/// Density used unless configured otherwise
+pub const DEFAULT_DENSITY: f32 = 0.5;
+
+/// Kind of ambient effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeatherKind {
+    /// Fast falling streaks, slanted by the wind
+    Rain,
+    /// Slowly falling, swaying flakes
+    Snow,
+    /// Fixed twinkling points, the wind is ignored
+    Stars,
+    /// Drifting patches dimming the scene
+    Fog,
+}
+
+impl WeatherKind {
+    /// Particles per screen cell at full density
+    fn cells_per_particle(self) -> f32 {
+        match self {
+            WeatherKind::Rain => 0.06,
+            WeatherKind::Snow => 0.04,
+            WeatherKind::Stars => 0.02,
+            WeatherKind::Fog => 0.0,
+        }
+    }
+
+    /// Default look of the particles
+    fn default_style(self) -> Style {
+        match self {
+            WeatherKind::Rain => Style::new().fg(Color::BLUE),
+            WeatherKind::Snow => Style::new().fg(Color::BRIGHT_WHITE),
+            WeatherKind::Stars => Style::new().fg(Color::WHITE),
+            WeatherKind::Fog => Style::new().fg(Color::GREY),
+        }
+    }
+}
+
+/// One raindrop, snowflake, or star
+#[derive(Debug, Clone, PartialEq)]
+struct Particle {
+    x: f32,
+    y: f32,
+    /// Falling speed in cells per second
+    speed: f32,
+    /// Offset of the sway or twinkle cycle
+    phase: f32,
+}
+
+/// Ambient effect covering the screen
+///
+/// # Example
+/// ```
+/// use lonely_engine::{renderer::Renderer, weather::Weather};
+///
+/// let mut snow = Weather::snow().density(1.0);
+/// let mut renderer = Renderer::new(40, 10);
+/// snow.update(0.1, 40, 10);
+/// snow.draw(&mut renderer);
+/// assert!(snow.particle_count() > 0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Weather {
+    kind: WeatherKind,
+    density: f32,
+    wind: f32,
+    style: Style,
+    particles: Vec<Particle>,
+    /// Screen size the particles were spread over
+    area: (usize, usize),
+    /// Seconds since the weather started
+    time: f32,
+    rng: Rng,
+}
+
+impl Weather {
+    /// Creates an effect of a kind with default density, no wind, and default colors
+    pub fn new(kind: WeatherKind) -> Self {
+        Self {
+            kind,
+            density: DEFAULT_DENSITY,
+            wind: 0.0,
+            style: kind.default_style(),
+            particles: Vec::new(),
+            area: (0, 0),
+            time: 0.0,
+            rng: Rng::new(0x5EED),
+        }
+    }
+
+    /// Creates falling rain
+    pub fn rain() -> Self {
+        Self::new(WeatherKind::Rain)
+    }
+
+    /// Creates falling snow
+    pub fn snow() -> Self {
+        Self::new(WeatherKind::Snow)
+    }
+
+    /// Creates a twinkling star field
+    pub fn stars() -> Self {
+        Self::new(WeatherKind::Stars)
+    }
+
+    /// Creates drifting fog
+    pub fn fog() -> Self {
+        Self::new(WeatherKind::Fog)
+    }
+
+    /// Sets the intensity from `0.0` (nothing) to `1.0` (a downpour or thick fog)
+    pub fn density(mut self, density: f32) -> Self {
+        self.set_density(density);
+        self
+    }
+
+    /// Sets the horizontal drift in cells per second, positive blows to the right
+    pub fn wind(mut self, wind: f32) -> Self {
+        self.wind = wind;
+        self
+    }
+
+    /// Sets the colors and attributes of particles and mist
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Seeds the particle placement
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = Rng::new(seed);
+        self
+    }
+
+    /// Changes the intensity while running, particles are added or removed gradually by the next update
+    pub fn set_density(&mut self, density: f32) {
+        self.density = density.clamp(0.0, 1.0);
+    }
+
+    /// Changes the wind while running
+    pub fn set_wind(&mut self, wind: f32) {
+        self.wind = wind;
+    }
+
+    /// Kind of effect
+    pub fn kind(&self) -> WeatherKind {
+        self.kind
+    }
+
+    /// Intensity from `0.0` to `1.0`
+    pub fn get_density(&self) -> f32 {
+        self.density
+    }
+
+    /// Horizontal drift in cells per second
+    pub fn get_wind(&self) -> f32 {
+        self.wind
+    }
+
+    /// Number of raindrops, snowflakes, or stars, `0` for fog
+    pub fn particle_count(&self) -> usize {
+        self.particles.len()
+    }
+
+    /// Moves the particles and drifts the fog
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds since the previous update
+    /// * `width`, `height` - Screen size in cells
+    pub fn update(&mut self, delta_time: f32, width: usize, height: usize) {
+        self.time += delta_time;
+        if (width, height) != self.area {
+            // Spread over the whole screen at once instead of raining in from the top
+            self.area = (width, height);
+            self.particles.clear();
+            self.spawn_up_to(self.target_count(), false);
+        }
+        self.particles.truncate(self.target_count());
+        self.spawn_up_to(self.target_count(), true);
+
+        let (width, height) = (width as f32, height as f32);
+        let (kind, wind, time) = (self.kind, self.wind, self.time);
+        for index in 0..self.particles.len() {
+            let particle = &mut self.particles[index];
+            match kind {
+                WeatherKind::Rain => {
+                    particle.y += particle.speed * delta_time;
+                    particle.x += wind * delta_time;
+                },
+                WeatherKind::Snow => {
+                    particle.y += particle.speed * delta_time;
+                    particle.x += (wind + (time * 1.5 + particle.phase).sin()) * delta_time;
+                },
+                WeatherKind::Stars | WeatherKind::Fog => continue,
+            }
+            particle.x = particle.x.rem_euclid(width.max(1.0));
+            if particle.y >= height {
+                particle.y -= height;
+                particle.x = self.rng.next_f32() * width;
+            }
+        }
+    }
+
+    /// Draws the effect over the back buffer in screen coordinates
+    pub fn draw(&self, renderer: &mut Renderer) {
+        if self.kind == WeatherKind::Fog {
+            self.draw_fog(renderer);
+            return;
+        }
+        let clear = renderer.clear_cell();
+        for particle in &self.particles {
+            let (x, y) = (particle.x as usize, particle.y as usize);
+            if renderer.cell(x, y) != Some(&clear) {
+                continue;
+            }
+            let (ch, style) = match self.kind {
+                WeatherKind::Rain if self.wind > 2.0 => ('\\', self.style),
+                WeatherKind::Rain if self.wind < -2.0 => ('/', self.style),
+                WeatherKind::Rain => ('|', self.style),
+                WeatherKind::Snow => (if particle.speed > 3.0 { '*' } else { '.' }, self.style),
+                _ => match ((self.time * 0.8 + particle.phase) % 4.0) as u8 {
+                    0 => ('+', self.style.bold()),
+                    1 => ('.', self.style),
+                    2 => ('.', self.style.dim()),
+                    _ => ('*', self.style),
+                },
+            };
+            let bg = if style.bg_color == Color::Default { clear.bg } else { style.bg_color };
+            renderer.set_cell(x, y, Cell { ch, fg: style.fg_color, bg, attrs: style.attributes });
+        }
+    }
+
+    /// Dims cells under fog patches and fills empty ones with mist
+    fn draw_fog(&self, renderer: &mut Renderer) {
+        let clear = renderer.clear_cell();
+        let offset = (self.time * self.wind) as i64;
+        for y in 0..renderer.get_height() {
+            for x in 0..renderer.get_width() {
+                let thickness = patch_noise(x as i64 - offset, y as i64, (self.time * 0.25) as i64);
+                if thickness >= self.density {
+                    continue;
+                }
+                if renderer.cell(x, y) == Some(&clear) {
+                    if thickness < self.density * 0.4 {
+                        let mist = Cell { ch: '░', fg: self.style.fg_color, bg: clear.bg, attrs: self.style.attributes };
+                        renderer.set_cell(x, y, mist);
+                    }
+                } else {
+                    renderer.dim_cell(x, y, self.density > 0.75);
+                }
+            }
+        }
+    }
+
+    /// Particles wanted for the current area and density
+    fn target_count(&self) -> usize {
+        let (width, height) = self.area;
+        ((width * height) as f32 * self.kind.cells_per_particle() * self.density) as usize
+    }
+
+    /// Adds particles until there are `count`, at the top edge or anywhere on screen
+    fn spawn_up_to(&mut self, count: usize, from_top: bool) {
+        let (width, height) = (self.area.0 as f32, self.area.1 as f32);
+        let speeds = match self.kind {
+            WeatherKind::Rain => (15.0, 25.0),
+            WeatherKind::Snow => (1.5, 4.5),
+            WeatherKind::Stars | WeatherKind::Fog => (0.0, 0.0),
+        };
+        while self.particles.len() < count {
+            let x = self.rng.next_f32() * width;
+            let y = if from_top && self.kind != WeatherKind::Stars { 0.0 } else { self.rng.next_f32() * height };
+            let speed = speeds.0 + self.rng.next_f32() * (speeds.1 - speeds.0);
+            let phase = self.rng.next_f32() * std::f32::consts::TAU;
+            self.particles.push(Particle { x, y, speed, phase });
+        }
+    }
+}
+
+/// Fog thickness of a cell from `0.0` to `1.0`, constant over 4x2 blocks and slowly changing over `epoch`
+fn patch_noise(x: i64, y: i64, epoch: i64) -> f32 {
+    let mut hasher = StableHasher::new();
+    (x.div_euclid(4), y.div_euclid(2), epoch).hash(&mut hasher);
+    (hasher.finish() % 1000) as f32 / 1000.0
+}
@@ -0,0 +1,227 @@
+//! Trigger zones for declarative level scripting
+//!
+//! Provides:
+//! - [`ZoneShape`] covering a rectangle or an arbitrary set of cells
+//! - [`TriggerZone`] watching objects, optionally only those with given tags
+//! - [`TriggerZones`] registry owned by the engine
+//!
+//! The engine evaluates zones after processing each update's commands and
+//! emits `EngineEvent::ZoneEntered` and `EngineEvent::ZoneExited`, so "the door
+//! opens when the player steps on the plate" becomes an event subscription
+//! instead of a per-frame position check.
+//!
+//! # Example
+//! ```
+//! use lonely_engine::{engine::Engine, zone::TriggerZone};
+//!
+//! let mut engine = Engine::new(80, 24);
+//! engine.zones.add(TriggerZone::rect("pressure_plate", 10, 4, 2, 1).with_tag("player").with_tag("crate"));
+//! engine.zones.add(TriggerZone::cells("lava", [(3, 7), (4, 7), (4, 8)]));
+//! ```
+
+use std::collections::{BTreeSet, HashSet};
+use crate::{event::EngineEvent, game_object::GameObject};
+
+/// Cells covered by a zone
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZoneShape {
+    /// Rectangle from its top-left cell
+    Rect {
+        /// Left column
+        x: usize,
+        /// Top row
+        y: usize,
+        /// Columns covered
+        width: usize,
+        /// Rows covered
+        height: usize,
+    },
+    /// Any set of cells, for irregular rooms and paths
+    Cells(HashSet<(usize, usize)>),
+}
+
+impl ZoneShape {
+    /// Returns whether a cell is inside the shape
+    pub fn contains(&self, cell_x: usize, cell_y: usize) -> bool {
+        match self {
+            ZoneShape::Rect { x, y, width, height } => (*x..x + width).contains(&cell_x) && (*y..y + height).contains(&cell_y),
+            ZoneShape::Cells(cells) => cells.contains(&(cell_x, cell_y)),
+        }
+    }
+}
+
+/// Region reporting objects moving in and out of it
+///
+/// # Notes
+/// - An object is inside when its (`x`, `y`) cell is, sprite size is not considered
+/// - Inactive objects are skipped: freezing an object inside a zone does not make it leave
+/// - Despawning an object inside a zone reports it leaving, before `ObjectDespawned`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriggerZone {
+    /// Identifier carried by zone events
+    pub id: String,
+    /// Cells covered
+    pub shape: ZoneShape,
+    /// Tags of the objects watched, empty watches every object
+    pub tags: Vec<String>,
+    /// Indices of the objects inside, kept sorted so events come out in a stable order
+    occupants: BTreeSet<usize>,
+}
+
+impl TriggerZone {
+    /// Creates a zone with a shape
+    pub fn new(id: &str, shape: ZoneShape) -> Self {
+        Self { id: id.to_string(), shape, tags: Vec::new(), occupants: BTreeSet::new() }
+    }
+
+    /// Creates a rectangular zone from its top-left cell and size
+    pub fn rect(id: &str, x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self::new(id, ZoneShape::Rect { x, y, width, height })
+    }
+
+    /// Creates a zone covering a set of cells
+    pub fn cells(id: &str, cells: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        Self::new(id, ZoneShape::Cells(cells.into_iter().collect()))
+    }
+
+    /// Watches objects with a tag, the zone ignores other tags once one is added
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    /// Returns whether the zone reacts to an object's tag
+    pub fn watches(&self, obj: &GameObject) -> bool {
+        self.tags.is_empty() || self.tags.contains(&obj.tag)
+    }
+
+    /// Indices of the objects inside as of the last evaluation, in ascending order
+    pub fn occupants(&self) -> impl Iterator<Item = usize> + '_ {
+        self.occupants.iter().copied()
+    }
+
+    /// Returns whether any watched object is inside
+    pub fn is_occupied(&self) -> bool {
+        !self.occupants.is_empty()
+    }
+
+    /// Updates the occupants, appending the resulting events
+    fn evaluate(&mut self, objects: &[GameObject], events: &mut Vec<EngineEvent>) {
+        for (index, obj) in objects.iter().enumerate().filter(|(_, obj)| obj.active) {
+            let inside = self.watches(obj) && self.shape.contains(obj.x, obj.y);
+            if inside && self.occupants.insert(index) {
+                events.push(EngineEvent::ZoneEntered(index, self.id.clone()));
+            } else if !inside && self.occupants.remove(&index) {
+                events.push(EngineEvent::ZoneExited(index, self.id.clone()));
+            }
+        }
+    }
+
+    /// Forgets a removed object and shifts the indices of the objects after it
+    fn object_removed(&mut self, index: usize, events: &mut Vec<EngineEvent>) {
+        if self.occupants.remove(&index) {
+            events.push(EngineEvent::ZoneExited(index, self.id.clone()));
+        }
+        let shifted: Vec<usize> = self.occupants.split_off(&index).into_iter().map(|occupant| occupant - 1).collect();
+        self.occupants.extend(shifted);
+    }
+}
+
+/// Trigger zones evaluated by the engine every update
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TriggerZones {
+    zones: Vec<TriggerZone>,
+}
+
+impl TriggerZones {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a zone, replacing one with the same id
+    ///
+    /// # Notes
+    /// - Objects already inside a new zone are reported as entering at the next evaluation
+    pub fn add(&mut self, zone: TriggerZone) {
+        match self.zones.iter_mut().find(|existing| existing.id == zone.id) {
+            Some(existing) => *existing = zone,
+            None => self.zones.push(zone),
+        }
+    }
+
+    /// Unregisters a zone without reporting its occupants as leaving
+    pub fn remove(&mut self, id: &str) -> Option<TriggerZone> {
+        let position = self.zones.iter().position(|zone| zone.id == id)?;
+        Some(self.zones.remove(position))
+    }
+
+    /// Returns a zone by id
+    pub fn get(&self, id: &str) -> Option<&TriggerZone> {
+        self.zones.iter().find(|zone| zone.id == id)
+    }
+
+    /// Iterates over zones in registration order
+    pub fn iter(&self) -> impl Iterator<Item = &TriggerZone> {
+        self.zones.iter()
+    }
+
+    /// Ids of the zones an object is inside
+    pub fn zones_of(&self, index: usize) -> impl Iterator<Item = &str> {
+        self.zones.iter().filter(move |zone| zone.occupants.contains(&index)).map(|zone| zone.id.as_str())
+    }
+
+    /// Number of registered zones
+    pub fn len(&self) -> usize {
+        self.zones.len()
+    }
+
+    /// Returns whether no zone is registered
+    pub fn is_empty(&self) -> bool {
+        self.zones.is_empty()
+    }
+
+    /// Unregisters every zone
+    pub fn clear(&mut self) {
+        self.zones.clear();
+    }
+
+    /// Compares object positions against every zone
+    ///
+    /// # Returns
+    /// `ZoneEntered` and `ZoneExited` events, grouped by zone in registration order
+    ///
+    /// # Example
+    /// ```
+    /// use lonely_engine::{event::EngineEvent, game_object::GameObject, zone::{TriggerZone, TriggerZones}};
+    ///
+    /// let mut zones = TriggerZones::new();
+    /// zones.add(TriggerZone::rect("exit", 5, 5, 1, 1));
+    ///
+    /// let mut hero = GameObject::new(5, 5, '@');
+    /// assert!(matches!(zones.evaluate(&[hero.clone()])[..], [EngineEvent::ZoneEntered(0, ref id)] if id == "exit"));
+    /// assert!(zones.evaluate(&[hero.clone()]).is_empty()); // Still inside
+    ///
+    /// hero.x = 6;
+    /// assert!(matches!(zones.evaluate(&[hero])[..], [EngineEvent::ZoneExited(0, _)]));
+    /// ```
+    pub fn evaluate(&mut self, objects: &[GameObject]) -> Vec<EngineEvent> {
+        let mut events = Vec::new();
+        for zone in &mut self.zones {
+            zone.evaluate(objects, &mut events);
+        }
+        events
+    }
+
+    /// Updates occupant indices for an object removed from the engine's list
+    ///
+    /// # Returns
+    /// `ZoneExited` for every zone the object was inside
+    pub fn object_removed(&mut self, index: usize) -> Vec<EngineEvent> {
+        let mut events = Vec::new();
+        for zone in &mut self.zones {
+            zone.object_removed(index, &mut events);
+        }
+        events
+    }
+}